@@ -1,10 +1,11 @@
-use crate::config::{ChannelConfig, StateConfig, StrategyConfig};
-use crate::constants::MAX_INITIAL_SEQUENCE;
+use crate::config::{defaults, ChannelConfig, StateConfig, StrategyConfig};
+use crate::constants::{max_initial_sequence, MAX_BUFFER_SIZE, MIN_BUFFER_SIZE};
 use crate::error::Result;
+use crate::net::source::is_link_local;
 use crate::{
-    Error, IcmpExtensionParseMode, MaxInflight, MaxRounds, MultipathStrategy, PacketSize,
-    PayloadPattern, PortDirection, PrivilegeMode, Protocol, Sequence, TimeToLive, TraceId, Tracer,
-    TypeOfService, MAX_TTL,
+    Error, FlowLabel, IcmpExtensionParseMode, MaxInflight, MaxRounds, MultipathStrategy,
+    PacketSize, PayloadPattern, PayloadStrategy, PortDirection, PrivilegeMode, Protocol, Sequence,
+    TimeToLive, TraceId, Tracer, TypeOfService, MAX_TTL,
 };
 use std::net::IpAddr;
 use std::num::NonZeroUsize;
@@ -36,6 +37,7 @@ use std::time::Duration;
 ///
 /// - [`Tracer`] - A traceroute implementation.
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Builder {
     interface: Option<String>,
     source_addr: Option<IpAddr>,
@@ -45,22 +47,40 @@ pub struct Builder {
     packet_size: PacketSize,
     payload_pattern: PayloadPattern,
     tos: TypeOfService,
+    flow_label: FlowLabel,
     icmp_extension_parse_mode: IcmpExtensionParseMode,
     read_timeout: Duration,
     tcp_connect_timeout: Duration,
+    kernel_recv_timestamps: bool,
+    stamped_payload: bool,
+    payload_strategy: PayloadStrategy,
     trace_identifier: TraceId,
     max_rounds: Option<MaxRounds>,
     first_ttl: TimeToLive,
     max_ttl: TimeToLive,
     grace_duration: Duration,
     max_inflight: MaxInflight,
+    send_batch_size: u8,
+    recv_batch_size: u8,
     initial_sequence: Sequence,
     multipath_strategy: MultipathStrategy,
     port_direction: PortDirection,
     min_round_duration: Duration,
     max_round_duration: Duration,
+    min_probe_interval: Option<Duration>,
+    loss_timeout: Option<Duration>,
+    max_unresponsive_hops: Option<u8>,
+    ignore_identifier: bool,
+    buffer_size: u16,
+    accept_late_responses: bool,
+    adaptive_read_timeout: bool,
+    send_jitter: Option<Duration>,
+    send_jitter_seed: Option<u64>,
+    setup_retry_attempts: usize,
+    setup_retry_backoff: Duration,
     max_samples: usize,
     max_flows: usize,
+    discard_first_hop_warmup: bool,
     drop_privileges: bool,
 }
 
@@ -75,22 +95,40 @@ impl Default for Builder {
             packet_size: ChannelConfig::default().packet_size,
             payload_pattern: ChannelConfig::default().payload_pattern,
             tos: ChannelConfig::default().tos,
+            flow_label: ChannelConfig::default().flow_label,
             icmp_extension_parse_mode: ChannelConfig::default().icmp_extension_parse_mode,
             read_timeout: ChannelConfig::default().read_timeout,
             tcp_connect_timeout: ChannelConfig::default().tcp_connect_timeout,
+            kernel_recv_timestamps: ChannelConfig::default().kernel_recv_timestamps,
+            stamped_payload: ChannelConfig::default().stamped_payload,
+            payload_strategy: ChannelConfig::default().payload_strategy,
             trace_identifier: StrategyConfig::default().trace_identifier,
             max_rounds: StrategyConfig::default().max_rounds,
             first_ttl: StrategyConfig::default().first_ttl,
             max_ttl: StrategyConfig::default().max_ttl,
             grace_duration: StrategyConfig::default().grace_duration,
             max_inflight: StrategyConfig::default().max_inflight,
+            send_batch_size: StrategyConfig::default().send_batch_size,
+            recv_batch_size: ChannelConfig::default().recv_batch_size,
             initial_sequence: StrategyConfig::default().initial_sequence,
             multipath_strategy: StrategyConfig::default().multipath_strategy,
             port_direction: StrategyConfig::default().port_direction,
             min_round_duration: StrategyConfig::default().min_round_duration,
             max_round_duration: StrategyConfig::default().max_round_duration,
+            min_probe_interval: StrategyConfig::default().min_probe_interval,
+            loss_timeout: StrategyConfig::default().loss_timeout,
+            max_unresponsive_hops: StrategyConfig::default().max_unresponsive_hops,
+            ignore_identifier: StrategyConfig::default().ignore_identifier,
+            buffer_size: StrategyConfig::default().buffer_size,
+            accept_late_responses: StrategyConfig::default().accept_late_responses,
+            adaptive_read_timeout: StrategyConfig::default().adaptive_read_timeout,
+            send_jitter: StrategyConfig::default().send_jitter,
+            send_jitter_seed: StrategyConfig::default().send_jitter_seed,
+            setup_retry_attempts: defaults::DEFAULT_SETUP_RETRY_ATTEMPTS,
+            setup_retry_backoff: defaults::DEFAULT_SETUP_RETRY_BACKOFF,
             max_samples: StateConfig::default().max_samples,
             max_flows: StateConfig::default().max_flows,
+            discard_first_hop_warmup: StateConfig::default().discard_first_hop_warmup,
             drop_privileges: false,
         }
     }
@@ -154,6 +192,9 @@ impl Builder {
     /// If not provided the source address will be determined by OS based on
     /// the target IPv4 or IPv6 address.
     ///
+    /// The source interface is required for link-local IPv6 target addresses
+    /// and is used as the scope (zone) of the target address.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -309,6 +350,9 @@ impl Builder {
 
     /// Set the type of service.
     ///
+    /// This is the `IPv4` `TOS` header byte, or the `IPv6` traffic class, where the upper 6 bits
+    /// are the `DSCP` and the lower 2 bits are the `ECN` field.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -329,6 +373,37 @@ impl Builder {
         }
     }
 
+    /// Set the `IPv6` flow label.
+    ///
+    /// The flow label is set for `IPv6` ICMP and UDP probes, which allows the
+    /// paths of an ECMP network which balances by flow label to be traced.  A
+    /// flow label of zero leaves the flow label to the kernel.
+    ///
+    /// The flow label must be within 20 bits.  It is ignored, with a warning,
+    /// for `IPv4` targets.
+    ///
+    /// Setting the flow label is only supported on Linux.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([0x2606, 0x4700, 0x4700, 0, 0, 0, 0, 0x1111]);
+    /// let tracer = Builder::new(addr).flow_label(0x12345).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn flow_label(self, flow_label: u32) -> Self {
+        Self {
+            flow_label: FlowLabel(flow_label),
+            ..self
+        }
+    }
+
     /// Set the ICMP extensions mode.
     ///
     /// # Examples
@@ -406,6 +481,119 @@ impl Builder {
         }
     }
 
+    /// Set whether to use kernel receive timestamps.
+    ///
+    /// If set then the time at which a probe response was received is taken from
+    /// the kernel (i.e. `SO_TIMESTAMPNS` on Linux and `SO_TIMESTAMP` on other Unix
+    /// platforms) rather than measured in user space, which reduces the jitter
+    /// introduced by scheduling delays.
+    ///
+    /// Only receive timestamps are taken from the kernel, the time at which a
+    /// probe was sent is always measured in user space.  Kernel timestamps are
+    /// not used for TCP probes, which are completed when the connection is
+    /// established.
+    ///
+    /// If kernel timestamps are not supported on the platform (i.e. on Windows)
+    /// then this has no effect and user space timestamps are used.
+    ///
+    /// By default user space timestamps are used.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).kernel_recv_timestamps(true).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn kernel_recv_timestamps(self, kernel_recv_timestamps: bool) -> Self {
+        Self {
+            kernel_recv_timestamps,
+            ..self
+        }
+    }
+
+    /// Set whether to embed a stamp in the payload of ICMP probes.
+    ///
+    /// If set then the payload of each ICMP probe begins with a marker
+    /// followed by the identifier, sequence number and send time of the probe.
+    /// The stamp is read back from the original packet quoted in ICMP
+    /// responses and used in place of the identifier and sequence number in
+    /// the headers of the original packet, and the send time is used to
+    /// calculate the round trip time.  This allows responses to be correlated
+    /// on paths where a middlebox rewrites the ICMP identifier or sequence
+    /// number.
+    ///
+    /// The stamp is 16 bytes and so the packet size must be at least 16 bytes
+    /// larger than the minimum ICMP packet size (i.e. 44 bytes for IPv4 and
+    /// 64 bytes for IPv6).  Responses which quote a truncated original packet
+    /// are correlated using the ICMP identifier and sequence number.
+    ///
+    /// This has no effect for the UDP and TCP protocols.
+    ///
+    /// By default the payload is not stamped.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).stamped_payload(true).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn stamped_payload(self, stamped_payload: bool) -> Self {
+        Self {
+            stamped_payload,
+            ..self
+        }
+    }
+
+    /// Set the strategy for the payload of ICMP probes.
+    ///
+    /// If set to `PayloadStrategy::ConstantChecksum` then the last 16-bit
+    /// aligned word of the payload (i.e. the last two bytes if the payload
+    /// size is even) is adjusted such that the ICMP checksum is the same for
+    /// every probe.  This keeps all probes on the same path through routers
+    /// which balance load on the ICMP checksum, similar to the Paris
+    /// traceroute.  The payload must be at least 2 bytes, or 18 bytes if the
+    /// payload is also stamped.
+    ///
+    /// This has no effect for the UDP and TCP protocols.
+    ///
+    /// By default the payload is filled with the payload pattern.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::{Builder, PayloadStrategy};
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr)
+    ///     .payload_strategy(PayloadStrategy::ConstantChecksum)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn payload_strategy(self, payload_strategy: PayloadStrategy) -> Self {
+        Self {
+            payload_strategy,
+            ..self
+        }
+    }
+
     /// Set the maximum number of rounds.
     ///
     /// If set to `None` then the tracer will run indefinitely, otherwise it
@@ -523,6 +711,71 @@ impl Builder {
         }
     }
 
+    /// Set the maximum number of probes to send in a single batch.
+    ///
+    /// When more than one probe is ready to be sent, up to this many probes are sent together
+    /// using a single system call where the platform supports it, which reduces the overhead of
+    /// sending probes at high rates.  All probes in a batch are recorded as sent at the time the
+    /// batch was sent.
+    ///
+    /// Batching is only supported for IPv4 `icmp` and `udp` probes sent in privileged mode and on
+    /// platforms which support `sendmmsg`, otherwise the probes of a batch are sent in turn.  A
+    /// batch size of 1 disables batching.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).send_batch_size(8).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn send_batch_size(self, send_batch_size: u8) -> Self {
+        Self {
+            send_batch_size,
+            ..self
+        }
+    }
+
+    /// Set the maximum number of packets to receive in a single batch.
+    ///
+    /// When probe responses arrive faster than they are processed, up to this many packets are
+    /// received together using a single system call where the platform supports it, which reduces
+    /// the overhead of receiving responses at high rates.  The responses of a batch are still
+    /// processed one at a time.
+    ///
+    /// Each response is stamped with its kernel receive timestamp if kernel timestamps are
+    /// enabled, otherwise all responses in a batch are stamped with the time the batch was
+    /// received.
+    ///
+    /// Batching is only supported on platforms which support `recvmmsg`, otherwise a single packet
+    /// is received at a time.  A batch size of 1 disables batching.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).recv_batch_size(8).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn recv_batch_size(self, recv_batch_size: u8) -> Self {
+        Self {
+            recv_batch_size,
+            ..self
+        }
+    }
+
     /// Set the initial sequence number.
     ///
     /// # Examples
@@ -619,6 +872,343 @@ impl Builder {
         }
     }
 
+    /// Set the minimum interval between sending probes.
+    ///
+    /// If set, the tracer will not send a probe until at least this interval
+    /// has elapsed since the previous probe was sent.  Each probe whose send
+    /// is deferred by this limit is counted once and reported in the
+    /// [`crate::Round`].
+    ///
+    /// If not set (the default) then probes are sent as fast as the in-flight
+    /// limit allows.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use std::time::Duration;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr)
+    ///     .min_probe_interval(Some(Duration::from_millis(20)))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn min_probe_interval(self, min_probe_interval: Option<Duration>) -> Self {
+        Self {
+            min_probe_interval,
+            ..self
+        }
+    }
+
+    /// Set the per-probe loss timeout.
+    ///
+    /// If set, any probe which does not receive a reply within this duration of
+    /// being sent is considered lost, even if a reply is later received during
+    /// the same round.  This makes loss accounting independent of the round
+    /// timing.
+    ///
+    /// If not set (the default) then a probe is only considered lost if no reply
+    /// is received before the end of the round.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use std::time::Duration;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr)
+    ///     .loss_timeout(Some(Duration::from_millis(500)))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn loss_timeout(self, loss_timeout: Option<Duration>) -> Self {
+        Self {
+            loss_timeout,
+            ..self
+        }
+    }
+
+    /// Set the maximum number of unresponsive hops to probe beyond the furthest
+    /// hop which has responded.
+    ///
+    /// If set then no probes are sent for any time-to-live (ttl) greater than
+    /// the largest ttl for which a response has been received in any round plus
+    /// the maximum.  This avoids probing every ttl up to the maximum ttl when the
+    /// target, and the hops before it, do not respond.
+    ///
+    /// A value of zero is treated as not set, which is the default.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).max_unresponsive_hops(Some(3)).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_unresponsive_hops(self, max_unresponsive_hops: Option<u8>) -> Self {
+        Self {
+            max_unresponsive_hops: max_unresponsive_hops.filter(|&max| max > 0),
+            ..self
+        }
+    }
+
+    /// Set whether to ignore the identifier of ICMP responses.
+    ///
+    /// If set then responses are correlated with probes using the sequence number
+    /// only and the echoed identifier is not checked.  This is useful for
+    /// environments where the identifier is known to be rewritten, such as by
+    /// some NAT devices, which would otherwise result in 100% apparent loss.
+    ///
+    /// The fields of the original packet quoted in ICMP responses are not
+    /// otherwise used for correlation, except that if a stamped payload is
+    /// also configured (see [`Builder::stamped_payload`]) then the identifier
+    /// read from the stamp, which is not rewritten in transit, is still
+    /// checked.
+    ///
+    /// Warning: this may cause responses for probes sent by other tracers to be
+    /// misattributed to this tracer.
+    ///
+    /// By default the identifier is checked.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).ignore_identifier(true).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn ignore_identifier(self, ignore_identifier: bool) -> Self {
+        Self {
+            ignore_identifier,
+            ..self
+        }
+    }
+
+    /// Set the maximum number of sequence numbers which may be used in a round.
+    ///
+    /// A sequence number is used for every probe sent and for every probe which is re-issued, such
+    /// as when a port cannot be bound for a tcp probe.  A larger buffer allows more probes to be
+    /// re-issued in a round, at the cost of a lower maximum initial sequence number.
+    ///
+    /// Must be in the range `256..=4096`, the default is `512`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).buffer_size(1024).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn buffer_size(self, buffer_size: u16) -> Self {
+        Self {
+            buffer_size,
+            ..self
+        }
+    }
+
+    /// Set whether to accept late responses for probes sent in the previous round.
+    ///
+    /// By default, a response which is received after the round in which the probe was sent has
+    /// completed is dropped and the probe is counted as lost.  If set, such a response is instead
+    /// attributed to the hop of the probe, which improves the accuracy of the loss on links where
+    /// responses routinely arrive a round late.
+    ///
+    /// As the round of the probe has already been published, the late response updates the
+    /// totals and round-trip time statistics of the hop but not the history of the round.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).accept_late_responses(true).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn accept_late_responses(self, accept_late_responses: bool) -> Self {
+        Self {
+            accept_late_responses,
+            ..self
+        }
+    }
+
+    /// Set whether to adapt the read timeout to the observed round-trip time.
+    ///
+    /// If set, the read timeout is derived from a smoothed estimate of the round-trip time of all
+    /// responses plus a margin of four times its variation, in the same way as the TCP
+    /// retransmission timeout (RFC 6298).  The configured read timeout is used until the first
+    /// response is received.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).adaptive_read_timeout(true).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn adaptive_read_timeout(self, adaptive_read_timeout: bool) -> Self {
+        Self {
+            adaptive_read_timeout,
+            ..self
+        }
+    }
+
+    /// Set the maximum send jitter.
+    ///
+    /// If set, the tracer sleeps for a random duration of up to this duration before sending each
+    /// probe (or batch of probes), such that the probes of many tracers of the same target are not
+    /// sent in bursts.
+    ///
+    /// The time spent sleeping is not counted towards the duration of the round.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use std::time::Duration;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr)
+    ///     .send_jitter(Some(Duration::from_millis(5)))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn send_jitter(self, send_jitter: Option<Duration>) -> Self {
+        Self {
+            send_jitter,
+            ..self
+        }
+    }
+
+    /// Set the seed of the random number generator used for the send jitter.
+    ///
+    /// If not set, the generator is seeded from the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use std::time::Duration;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr)
+    ///     .send_jitter(Some(Duration::from_millis(5)))
+    ///     .send_jitter_seed(Some(42))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn send_jitter_seed(self, send_jitter_seed: Option<u64>) -> Self {
+        Self {
+            send_jitter_seed,
+            ..self
+        }
+    }
+
+    /// Set the maximum number of attempts to retry the setup of the tracer.
+    ///
+    /// If the setup of the tracer, such as discovering the source address or
+    /// opening the network channel, fails due to a transient condition, such
+    /// as the network being unreachable or an interface being down, then it is
+    /// retried up to this many times before failing.  Fatal errors, such as
+    /// invalid configuration, are never retried.
+    ///
+    /// By default (zero) the setup of the tracer is not retried.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).setup_retry_attempts(10).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn setup_retry_attempts(self, setup_retry_attempts: usize) -> Self {
+        Self {
+            setup_retry_attempts,
+            ..self
+        }
+    }
+
+    /// Set the initial backoff between attempts to retry the setup of the tracer.
+    ///
+    /// The backoff is doubled after each attempt, up to a maximum of one minute
+    /// or the initial backoff, whichever is larger.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use std::time::Duration;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr)
+    ///     .setup_retry_attempts(10)
+    ///     .setup_retry_backoff(Duration::from_secs(2))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn setup_retry_backoff(self, setup_retry_backoff: Duration) -> Self {
+        Self {
+            setup_retry_backoff,
+            ..self
+        }
+    }
+
     /// Set the maximum number of samples to record.
     ///
     /// # Examples
@@ -660,6 +1250,34 @@ impl Builder {
         Self { max_flows, ..self }
     }
 
+    /// Discard the first response from the first hop.
+    ///
+    /// The first probe of a trace may be delayed by ARP or neighbour discovery
+    /// for the first hop, which skews the best and average round-trip time of
+    /// the hop.  If enabled, the first response from the first hop in the
+    /// first round of the trace is discarded, as if the probe had not been
+    /// sent.  No other responses, and no other rounds, are discarded.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// use std::net::IpAddr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from([1, 1, 1, 1]);
+    /// let tracer = Builder::new(addr).discard_first_hop_warmup(true).build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn discard_first_hop_warmup(self, discard_first_hop_warmup: bool) -> Self {
+        Self {
+            discard_first_hop_warmup,
+            ..self
+        }
+    }
+
     /// Drop privileges after connection is established.
     ///
     /// # Examples
@@ -699,7 +1317,10 @@ impl Builder {
     ///
     /// # Errors
     ///
-    /// This function will return `Error::BadConfig` if the configuration is invalid.
+    /// This function will return `Error::BadConfig` if the configuration is invalid, including if
+    /// the target address is a multicast or broadcast address, the first ttl is greater than the
+    /// maximum ttl or the minimum round duration is greater than the maximum round duration.
+    #[allow(clippy::too_many_lines)]
     pub fn build(self) -> Result<Tracer> {
         match (self.protocol, self.port_direction) {
             (Protocol::Udp, PortDirection::None) => {
@@ -726,12 +1347,73 @@ impl Builder {
                 self.max_ttl.0
             )));
         }
-        if self.initial_sequence.0 > MAX_INITIAL_SEQUENCE {
+        if self.first_ttl > self.max_ttl {
             return Err(Error::BadConfig(format!(
-                "initial_sequence {} > {MAX_INITIAL_SEQUENCE}",
+                "first_ttl {} > max_ttl {}",
+                self.first_ttl.0, self.max_ttl.0
+            )));
+        }
+        if self.min_round_duration > self.max_round_duration {
+            return Err(Error::BadConfig(format!(
+                "min_round_duration {:?} > max_round_duration {:?}",
+                self.min_round_duration, self.max_round_duration
+            )));
+        }
+        if self.target_addr.is_multicast() {
+            return Err(Error::BadConfig(format!(
+                "multicast target address {} is not supported, trace to a unicast address instead",
+                self.target_addr
+            )));
+        }
+        if matches!(self.target_addr, IpAddr::V4(addr) if addr.is_broadcast()) {
+            return Err(Error::BadConfig(format!(
+                "broadcast target address {} is not supported, trace to a unicast address instead",
+                self.target_addr
+            )));
+        }
+        if is_link_local(self.target_addr) && self.interface.is_none() {
+            return Err(Error::BadConfig(format!(
+                "link-local target address {} requires an interface",
+                self.target_addr
+            )));
+        }
+        if !(MIN_BUFFER_SIZE..=MAX_BUFFER_SIZE).contains(&self.buffer_size) {
+            return Err(Error::BadConfig(format!(
+                "buffer_size {} must be in the range {MIN_BUFFER_SIZE}..={MAX_BUFFER_SIZE}",
+                self.buffer_size
+            )));
+        }
+        let max_initial_sequence = max_initial_sequence(self.buffer_size);
+        if self.initial_sequence.0 > max_initial_sequence {
+            return Err(Error::BadConfig(format!(
+                "initial_sequence {} > {max_initial_sequence}",
                 self.initial_sequence.0
             )));
         }
+        if self.flow_label > FlowLabel::MAX {
+            return Err(Error::BadConfig(format!(
+                "flow_label {:#x} > {:#x}",
+                self.flow_label.0,
+                FlowLabel::MAX.0
+            )));
+        }
+        if self.flow_label != FlowLabel::default() && self.target_addr.is_ipv4() {
+            tracing::warn!(
+                flow_label = self.flow_label.0,
+                "ignoring flow label for IPv4 target {}",
+                self.target_addr
+            );
+        }
+        if self.send_batch_size == 0 {
+            return Err(Error::BadConfig(
+                "send_batch_size must be greater than zero".to_string(),
+            ));
+        }
+        if self.recv_batch_size == 0 {
+            return Err(Error::BadConfig(
+                "recv_batch_size must be greater than zero".to_string(),
+            ));
+        }
         Ok(Tracer::new(
             self.interface,
             self.source_addr,
@@ -741,22 +1423,40 @@ impl Builder {
             self.packet_size,
             self.payload_pattern,
             self.tos,
+            self.flow_label,
             self.icmp_extension_parse_mode,
             self.read_timeout,
             self.tcp_connect_timeout,
+            self.kernel_recv_timestamps,
+            self.stamped_payload,
+            self.payload_strategy,
             self.trace_identifier,
             self.max_rounds,
             self.first_ttl,
             self.max_ttl,
             self.grace_duration,
             self.max_inflight,
+            self.send_batch_size,
+            self.recv_batch_size,
             self.initial_sequence,
             self.multipath_strategy,
             self.port_direction,
             self.min_round_duration,
             self.max_round_duration,
+            self.min_probe_interval,
+            self.loss_timeout,
+            self.max_unresponsive_hops,
+            self.ignore_identifier,
+            self.buffer_size,
+            self.accept_late_responses,
+            self.adaptive_read_timeout,
+            self.send_jitter,
+            self.send_jitter_seed,
+            self.setup_retry_attempts,
+            self.setup_retry_backoff,
             self.max_samples,
             self.max_flows,
+            self.discard_first_hop_warmup,
             self.drop_privileges,
         ))
     }
@@ -769,6 +1469,7 @@ mod tests {
     use config::defaults;
     use std::net::Ipv4Addr;
     use std::num::NonZeroUsize;
+    use std::str::FromStr;
 
     const SOURCE_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0));
     const TARGET_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::new(2, 2, 2, 2));
@@ -781,6 +1482,7 @@ mod tests {
         assert_eq!(None, tracer.interface());
         assert_eq!(defaults::DEFAULT_MAX_SAMPLES, tracer.max_samples());
         assert_eq!(defaults::DEFAULT_MAX_FLOWS, tracer.max_flows());
+        assert!(!tracer.discard_first_hop_warmup());
         assert_eq!(defaults::DEFAULT_STRATEGY_PROTOCOL, tracer.protocol());
         assert_eq!(TraceId::default(), tracer.trace_identifier());
         assert_eq!(defaults::DEFAULT_PRIVILEGE_MODE, tracer.privilege_mode());
@@ -797,6 +1499,7 @@ mod tests {
             tracer.payload_pattern().0
         );
         assert_eq!(defaults::DEFAULT_STRATEGY_TOS, tracer.tos().0);
+        assert_eq!(defaults::DEFAULT_STRATEGY_FLOW_LABEL, tracer.flow_label().0);
         assert_eq!(
             defaults::DEFAULT_ICMP_EXTENSION_PARSE_MODE,
             tracer.icmp_extension_parse_mode()
@@ -820,6 +1523,14 @@ mod tests {
             defaults::DEFAULT_STRATEGY_MAX_INFLIGHT,
             tracer.max_inflight().0
         );
+        assert_eq!(
+            defaults::DEFAULT_STRATEGY_SEND_BATCH_SIZE,
+            tracer.send_batch_size()
+        );
+        assert_eq!(
+            defaults::DEFAULT_STRATEGY_RECV_BATCH_SIZE,
+            tracer.recv_batch_size()
+        );
         assert_eq!(
             defaults::DEFAULT_STRATEGY_INITIAL_SEQUENCE,
             tracer.initial_sequence().0
@@ -833,6 +1544,20 @@ mod tests {
             defaults::DEFAULT_STRATEGY_MAX_ROUND_DURATION,
             tracer.max_round_duration()
         );
+        assert_eq!(None, tracer.min_probe_interval());
+        assert_eq!(None, tracer.loss_timeout());
+        assert_eq!(None, tracer.max_unresponsive_hops());
+        assert_eq!(None, tracer.send_jitter());
+        assert_eq!(None, tracer.send_jitter_seed());
+        assert!(!tracer.adaptive_read_timeout());
+        assert_eq!(
+            defaults::DEFAULT_SETUP_RETRY_ATTEMPTS,
+            tracer.setup_retry_attempts()
+        );
+        assert_eq!(
+            defaults::DEFAULT_SETUP_RETRY_BACKOFF,
+            tracer.setup_retry_backoff()
+        );
     }
 
     #[test]
@@ -842,6 +1567,7 @@ mod tests {
             .interface(Some("eth0"))
             .max_samples(10)
             .max_flows(20)
+            .discard_first_hop_warmup(true)
             .protocol(Protocol::Udp)
             .trace_identifier(101)
             .privilege_mode(PrivilegeMode::Unprivileged)
@@ -849,6 +1575,7 @@ mod tests {
             .packet_size(128)
             .payload_pattern(0xff)
             .tos(0x1a)
+            .flow_label(0x12345)
             .icmp_extension_parse_mode(IcmpExtensionParseMode::Enabled)
             .read_timeout(Duration::from_millis(50))
             .tcp_connect_timeout(Duration::from_millis(100))
@@ -857,10 +1584,20 @@ mod tests {
             .max_ttl(16)
             .grace_duration(Duration::from_millis(100))
             .max_inflight(22)
+            .send_batch_size(8)
+            .recv_batch_size(16)
             .initial_sequence(35000)
             .port_direction(PortDirection::FixedSrc(Port(8080)))
             .min_round_duration(Duration::from_millis(500))
             .max_round_duration(Duration::from_millis(1500))
+            .min_probe_interval(Some(Duration::from_millis(20)))
+            .loss_timeout(Some(Duration::from_millis(500)))
+            .max_unresponsive_hops(Some(3))
+            .send_jitter(Some(Duration::from_millis(5)))
+            .send_jitter_seed(Some(42))
+            .adaptive_read_timeout(true)
+            .setup_retry_attempts(5)
+            .setup_retry_backoff(Duration::from_secs(2))
             .build()
             .unwrap();
 
@@ -870,6 +1607,7 @@ mod tests {
         assert_eq!(Some("eth0"), tracer.interface());
         assert_eq!(10, tracer.max_samples());
         assert_eq!(20, tracer.max_flows());
+        assert!(tracer.discard_first_hop_warmup());
         assert_eq!(Protocol::Udp, tracer.protocol());
         assert_eq!(TraceId(101), tracer.trace_identifier());
         assert_eq!(PrivilegeMode::Unprivileged, tracer.privilege_mode());
@@ -877,6 +1615,7 @@ mod tests {
         assert_eq!(PacketSize(128), tracer.packet_size());
         assert_eq!(PayloadPattern(0xff), tracer.payload_pattern());
         assert_eq!(TypeOfService(0x1a), tracer.tos());
+        assert_eq!(FlowLabel(0x12345), tracer.flow_label());
         assert_eq!(
             IcmpExtensionParseMode::Enabled,
             tracer.icmp_extension_parse_mode()
@@ -891,10 +1630,20 @@ mod tests {
         assert_eq!(TimeToLive(16), tracer.max_ttl());
         assert_eq!(Duration::from_millis(100), tracer.grace_duration());
         assert_eq!(MaxInflight(22), tracer.max_inflight());
+        assert_eq!(8, tracer.send_batch_size());
+        assert_eq!(16, tracer.recv_batch_size());
         assert_eq!(Sequence(35000), tracer.initial_sequence());
         assert_eq!(PortDirection::FixedSrc(Port(8080)), tracer.port_direction());
         assert_eq!(Duration::from_millis(500), tracer.min_round_duration());
         assert_eq!(Duration::from_millis(1500), tracer.max_round_duration());
+        assert_eq!(Some(Duration::from_millis(20)), tracer.min_probe_interval());
+        assert_eq!(Some(Duration::from_millis(500)), tracer.loss_timeout());
+        assert_eq!(Some(3), tracer.max_unresponsive_hops());
+        assert_eq!(Some(Duration::from_millis(5)), tracer.send_jitter());
+        assert_eq!(Some(42), tracer.send_jitter_seed());
+        assert!(tracer.adaptive_read_timeout());
+        assert_eq!(5, tracer.setup_retry_attempts());
+        assert_eq!(Duration::from_secs(2), tracer.setup_retry_backoff());
     }
 
     #[test]
@@ -906,6 +1655,15 @@ mod tests {
         assert_eq!(None, tracer.max_rounds());
     }
 
+    #[test]
+    fn test_zero_max_unresponsive_hops() {
+        let tracer = Builder::new(IpAddr::from([1, 2, 3, 4]))
+            .max_unresponsive_hops(Some(0))
+            .build()
+            .unwrap();
+        assert_eq!(None, tracer.max_unresponsive_hops());
+    }
+
     #[test]
     fn test_invalid_initial_sequence() {
         let err = Builder::new(IpAddr::from([1, 2, 3, 4]))
@@ -914,4 +1672,125 @@ mod tests {
             .unwrap_err();
         assert!(matches!(err, Error::BadConfig(s) if s == "initial_sequence 65535 > 64511"));
     }
+
+    #[test]
+    fn test_buffer_size() {
+        let tracer = Builder::new(IpAddr::from([1, 2, 3, 4]))
+            .buffer_size(1024)
+            .initial_sequence(63487)
+            .build()
+            .unwrap();
+        assert_eq!(1024, tracer.buffer_size());
+    }
+
+    #[test]
+    fn test_invalid_buffer_size() {
+        let err = Builder::new(IpAddr::from([1, 2, 3, 4]))
+            .buffer_size(255)
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::BadConfig(s) if s == "buffer_size 255 must be in the range 256..=4096")
+        );
+        let err = Builder::new(IpAddr::from([1, 2, 3, 4]))
+            .buffer_size(1024)
+            .initial_sequence(63488)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::BadConfig(s) if s == "initial_sequence 63488 > 63487"));
+    }
+
+    #[test]
+    fn test_first_ttl_greater_than_max_ttl() {
+        let err = Builder::new(IpAddr::from([1, 2, 3, 4]))
+            .first_ttl(10)
+            .max_ttl(5)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::BadConfig(s) if s == "first_ttl 10 > max_ttl 5"));
+    }
+
+    #[test]
+    fn test_min_round_duration_greater_than_max_round_duration() {
+        let err = Builder::new(IpAddr::from([1, 2, 3, 4]))
+            .min_round_duration(Duration::from_secs(2))
+            .max_round_duration(Duration::from_secs(1))
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::BadConfig(s) if s == "min_round_duration 2s > max_round_duration 1s")
+        );
+    }
+
+    #[test]
+    fn test_invalid_flow_label() {
+        let err = Builder::new(IpAddr::from([0x2001, 0xdb8, 0, 0, 0, 0, 0, 1]))
+            .flow_label(0x10_0000)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, Error::BadConfig(s) if s == "flow_label 0x100000 > 0xfffff"));
+    }
+
+    #[test]
+    fn test_invalid_send_batch_size() {
+        let err = Builder::new(IpAddr::from([1, 2, 3, 4]))
+            .send_batch_size(0)
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::BadConfig(s) if s == "send_batch_size must be greater than zero")
+        );
+    }
+
+    #[test]
+    fn test_invalid_recv_batch_size() {
+        let err = Builder::new(IpAddr::from([1, 2, 3, 4]))
+            .recv_batch_size(0)
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::BadConfig(s) if s == "recv_batch_size must be greater than zero")
+        );
+    }
+
+    #[test]
+    fn test_link_local_target_requires_interface() {
+        let err = Builder::new(IpAddr::from_str("fe80::1").unwrap())
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::BadConfig(s) if s == "link-local target address fe80::1 requires an interface")
+        );
+        let tracer = Builder::new(IpAddr::from_str("fe80::1").unwrap())
+            .interface(Some("eth0"))
+            .build()
+            .unwrap();
+        assert_eq!(Some("eth0"), tracer.interface());
+    }
+
+    #[test]
+    fn test_multicast_target() {
+        let err = Builder::new(IpAddr::from([224, 0, 0, 1]))
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::BadConfig(s) if s == "multicast target address 224.0.0.1 is not supported, trace to a unicast address instead")
+        );
+        let err = Builder::new(IpAddr::from_str("ff02::1").unwrap())
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::BadConfig(s) if s == "multicast target address ff02::1 is not supported, trace to a unicast address instead")
+        );
+    }
+
+    #[test]
+    fn test_broadcast_target() {
+        let err = Builder::new(IpAddr::from([255, 255, 255, 255]))
+            .build()
+            .unwrap_err();
+        assert!(
+            matches!(err, Error::BadConfig(s) if s == "broadcast target address 255.255.255.255 is not supported, trace to a unicast address instead")
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use crate::types::Port;
 use crate::{
-    MaxInflight, MaxRounds, PacketSize, PayloadPattern, Sequence, TimeToLive, TraceId,
+    FlowLabel, MaxInflight, MaxRounds, PacketSize, PayloadPattern, Sequence, TimeToLive, TraceId,
     TypeOfService,
 };
 use std::fmt::{Display, Formatter};
@@ -9,7 +9,7 @@ use std::time::Duration;
 
 /// Default values for configuration.
 pub mod defaults {
-    use crate::config::IcmpExtensionParseMode;
+    use crate::config::{IcmpExtensionParseMode, PayloadStrategy};
     use crate::{MultipathStrategy, PrivilegeMode, Protocol};
     use std::time::Duration;
 
@@ -26,9 +26,18 @@ pub mod defaults {
     pub const DEFAULT_ICMP_EXTENSION_PARSE_MODE: IcmpExtensionParseMode =
         IcmpExtensionParseMode::Disabled;
 
+    /// The default value for `payload-strategy`.
+    pub const DEFAULT_STRATEGY_PAYLOAD_STRATEGY: PayloadStrategy = PayloadStrategy::Pattern;
+
     /// The default value for `max-inflight`.
     pub const DEFAULT_STRATEGY_MAX_INFLIGHT: u8 = 24;
 
+    /// The default value for `send-batch-size`.
+    pub const DEFAULT_STRATEGY_SEND_BATCH_SIZE: u8 = 1;
+
+    /// The default value for `recv-batch-size`.
+    pub const DEFAULT_STRATEGY_RECV_BATCH_SIZE: u8 = 1;
+
     /// The default value for `first-ttl`.
     pub const DEFAULT_STRATEGY_FIRST_TTL: u8 = 1;
 
@@ -50,9 +59,19 @@ pub mod defaults {
     /// The default value for `initial-sequence`.
     pub const DEFAULT_STRATEGY_INITIAL_SEQUENCE: u16 = 33434;
 
+    /// The default value for `buffer-size`.
+    ///
+    /// This is set to be far larger than the `MAX_TTL` to allow for the re-issue of probes (with
+    /// the next sequence number, but the same ttl) which can occur for some protocols such as TCP
+    /// when it cannot bind to a given port.
+    pub const DEFAULT_STRATEGY_BUFFER_SIZE: u16 = 512;
+
     /// The default value for `tos`.
     pub const DEFAULT_STRATEGY_TOS: u8 = 0;
 
+    /// The default value for `flow-label`.
+    pub const DEFAULT_STRATEGY_FLOW_LABEL: u32 = 0;
+
     /// The default value for `read-timeout`.
     pub const DEFAULT_STRATEGY_READ_TIMEOUT: Duration = Duration::from_millis(10);
 
@@ -67,6 +86,12 @@ pub mod defaults {
 
     /// The default value for `max-flows`.
     pub const DEFAULT_MAX_FLOWS: usize = 64;
+
+    /// The default number of attempts to retry the setup of a trace.
+    pub const DEFAULT_SETUP_RETRY_ATTEMPTS: usize = 0;
+
+    /// The default initial backoff between attempts to retry the setup of a trace.
+    pub const DEFAULT_SETUP_RETRY_BACKOFF: Duration = Duration::from_secs(1);
 }
 
 /// The privilege mode.
@@ -97,6 +122,21 @@ impl Display for PrivilegeMode {
     }
 }
 
+/// The strategy for the payload of ICMP probes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PayloadStrategy {
+    /// Fill the payload with the payload pattern.
+    Pattern,
+    /// Adjust the payload such that the ICMP checksum is the same for every probe.
+    ///
+    /// The last 16-bit aligned word of the payload is replaced with a value which holds the
+    /// checksum at the value it would have for a probe with a sequence number of zero and a
+    /// payload of only the payload pattern.  As the identifier is fixed for a trace, the checksum
+    /// does not vary between probes, such that routers which balance load on the ICMP checksum
+    /// forward every probe of the trace on the same path.
+    ConstantChecksum,
+}
+
 /// The ICMP extension parsing mode.
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum IcmpExtensionParseMode {
@@ -258,6 +298,12 @@ pub struct StateConfig {
     /// Once the maximum number of flows has been reached no new flows will be
     /// created, existing flows are updated and are never removed.
     pub max_flows: usize,
+    /// Discard the first response from the first hop.
+    ///
+    /// The first probe of a trace may be delayed by ARP or neighbour discovery
+    /// for the first hop, and so the first response from the first hop may be
+    /// discarded such that the statistics of the hop are not skewed by it.
+    pub discard_first_hop_warmup: bool,
 }
 
 impl Default for StateConfig {
@@ -265,6 +311,7 @@ impl Default for StateConfig {
         Self {
             max_samples: defaults::DEFAULT_MAX_SAMPLES,
             max_flows: defaults::DEFAULT_MAX_FLOWS,
+            discard_first_hop_warmup: false,
         }
     }
 }
@@ -276,13 +323,21 @@ pub struct ChannelConfig {
     pub protocol: Protocol,
     pub source_addr: IpAddr,
     pub target_addr: IpAddr,
+    /// The IPv6 scope id of the target address, zero if not scoped.
+    pub target_scope_id: u32,
     pub packet_size: PacketSize,
     pub payload_pattern: PayloadPattern,
     pub initial_sequence: Sequence,
     pub tos: TypeOfService,
+    /// The `IPv6` flow label of probes, zero to leave it to the kernel.
+    pub flow_label: FlowLabel,
     pub icmp_extension_parse_mode: IcmpExtensionParseMode,
     pub read_timeout: Duration,
     pub tcp_connect_timeout: Duration,
+    pub kernel_recv_timestamps: bool,
+    pub recv_batch_size: u8,
+    pub stamped_payload: bool,
+    pub payload_strategy: PayloadStrategy,
 }
 
 impl Default for ChannelConfig {
@@ -292,13 +347,19 @@ impl Default for ChannelConfig {
             protocol: defaults::DEFAULT_STRATEGY_PROTOCOL,
             source_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
             target_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            target_scope_id: 0,
             packet_size: PacketSize(defaults::DEFAULT_STRATEGY_PACKET_SIZE),
             payload_pattern: PayloadPattern(defaults::DEFAULT_STRATEGY_PAYLOAD_PATTERN),
             initial_sequence: Sequence(defaults::DEFAULT_STRATEGY_INITIAL_SEQUENCE),
             tos: TypeOfService(defaults::DEFAULT_STRATEGY_TOS),
+            flow_label: FlowLabel(defaults::DEFAULT_STRATEGY_FLOW_LABEL),
             icmp_extension_parse_mode: defaults::DEFAULT_ICMP_EXTENSION_PARSE_MODE,
             read_timeout: defaults::DEFAULT_STRATEGY_READ_TIMEOUT,
             tcp_connect_timeout: defaults::DEFAULT_STRATEGY_TCP_CONNECT_TIMEOUT,
+            kernel_recv_timestamps: false,
+            recv_batch_size: defaults::DEFAULT_STRATEGY_RECV_BATCH_SIZE,
+            stamped_payload: false,
+            payload_strategy: defaults::DEFAULT_STRATEGY_PAYLOAD_STRATEGY,
         }
     }
 }
@@ -314,11 +375,21 @@ pub struct StrategyConfig {
     pub max_ttl: TimeToLive,
     pub grace_duration: Duration,
     pub max_inflight: MaxInflight,
+    pub send_batch_size: u8,
     pub initial_sequence: Sequence,
     pub multipath_strategy: MultipathStrategy,
     pub port_direction: PortDirection,
     pub min_round_duration: Duration,
     pub max_round_duration: Duration,
+    pub min_probe_interval: Option<Duration>,
+    pub loss_timeout: Option<Duration>,
+    pub max_unresponsive_hops: Option<u8>,
+    pub ignore_identifier: bool,
+    pub buffer_size: u16,
+    pub accept_late_responses: bool,
+    pub adaptive_read_timeout: bool,
+    pub send_jitter: Option<Duration>,
+    pub send_jitter_seed: Option<u64>,
 }
 
 impl Default for StrategyConfig {
@@ -332,11 +403,21 @@ impl Default for StrategyConfig {
             max_ttl: TimeToLive(defaults::DEFAULT_STRATEGY_MAX_TTL),
             grace_duration: defaults::DEFAULT_STRATEGY_GRACE_DURATION,
             max_inflight: MaxInflight(defaults::DEFAULT_STRATEGY_MAX_INFLIGHT),
+            send_batch_size: defaults::DEFAULT_STRATEGY_SEND_BATCH_SIZE,
             initial_sequence: Sequence(defaults::DEFAULT_STRATEGY_INITIAL_SEQUENCE),
             multipath_strategy: defaults::DEFAULT_STRATEGY_MULTIPATH,
             port_direction: PortDirection::None,
             min_round_duration: defaults::DEFAULT_STRATEGY_MIN_ROUND_DURATION,
             max_round_duration: defaults::DEFAULT_STRATEGY_MAX_ROUND_DURATION,
+            min_probe_interval: None,
+            loss_timeout: None,
+            max_unresponsive_hops: None,
+            ignore_identifier: false,
+            buffer_size: defaults::DEFAULT_STRATEGY_BUFFER_SIZE,
+            accept_late_responses: false,
+            adaptive_read_timeout: false,
+            send_jitter: None,
+            send_jitter_seed: None,
         }
     }
 }
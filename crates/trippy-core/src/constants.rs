@@ -1,19 +1,35 @@
+use std::time::Duration;
+
 /// The maximum time-to-live value allowed.
 ///
 /// The IP `ttl` is an u8 (0..255) but since a `ttl` of zero isn't useful we only allow 254 distinct
 /// hops (1..255).
 pub const MAX_TTL: u8 = 254;
 
+/// The maximum number of distinct responding addresses recorded for each time-to-live.
+///
+/// Any further addresses which respond for a `ttl` are not recorded.
+pub const MAX_TTL_ADDRS: usize = 16;
+
+/// The minimum read timeout when the read timeout is adapted to the observed round-trip time.
+pub const MIN_ADAPTIVE_READ_TIMEOUT: Duration = Duration::from_millis(10);
+
+/// The minimum number of sequence numbers allowed per round.
+///
+/// This must be larger than the `MAX_TTL` to allow a probe to be sent for every `ttl` in a round.
+pub const MIN_BUFFER_SIZE: u16 = 256;
+
 /// The maximum number of sequence numbers allowed per round.
 ///
-/// This is set to be far larger than the `MAX_TTL` to allow for the re-issue of probes (with the
-/// next sequence number, but the same ttl) which can occur for some protocols such as TCP when it
-/// cannot bind to a given port.
-pub const MAX_SEQUENCE_PER_ROUND: u16 = 512;
+/// This ensures that there are sufficient sequence numbers available for at least _two_ rounds
+/// after the largest initial sequence number.
+pub const MAX_BUFFER_SIZE: u16 = 4096;
 
-/// The maximum _starting_ sequence number allowed.
+/// The maximum _starting_ sequence number allowed for a number of sequence numbers per round.
 ///
 /// This ensures that there are sufficient sequence numbers available for at least _two_ rounds.  We
 /// require two rounds to ensure that delayed probe responses from the immediate prior round can be
 /// detected and excluded.
-pub const MAX_INITIAL_SEQUENCE: u16 = u16::MAX - (MAX_SEQUENCE_PER_ROUND * 2);
+pub const fn max_initial_sequence(buffer_size: u16) -> u16 {
+    u16::MAX - (buffer_size * 2)
+}
@@ -35,6 +35,28 @@ pub enum Error {
     Other(String),
 }
 
+impl Error {
+    /// Whether the error is caused by a transient condition of the environment.
+    ///
+    /// Transient errors, such as the network being unreachable or an interface
+    /// being down (and so having no address), may resolve without intervention
+    /// and so the setup of a trace may be retried.  All other errors, such as
+    /// invalid configuration or insufficient privileges, are fatal.
+    #[must_use]
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::IoError(err) | Self::ProbeFailed(err) => matches!(
+                err.kind(),
+                ErrorKind::NetUnreachable
+                    | ErrorKind::HostUnreachable
+                    | ErrorKind::Std(io::ErrorKind::AddrNotAvailable)
+            ),
+            Self::UnknownInterface(_) | Self::InvalidSourceAddr(_) | Self::AddressInUse(_) => true,
+            _ => false,
+        }
+    }
+}
+
 /// Custom IO error result.
 pub type IoResult<T> = std::result::Result<T, IoError>;
 
@@ -90,6 +112,9 @@ pub enum IoOperation {
     SetReusePort,
     SetHeaderIncluded,
     SetUnicastHopsV6,
+    SetTclassV6,
+    SetFlowLabelV6,
+    SetRecvTimestamps,
     Close,
     WSACreateEvent,
     WSARecvFrom,
@@ -121,6 +146,9 @@ impl Display for IoOperation {
             Self::SetReusePort => write!(f, "set reuse port"),
             Self::SetHeaderIncluded => write!(f, "set header included"),
             Self::SetUnicastHopsV6 => write!(f, "set unicast hops v6"),
+            Self::SetTclassV6 => write!(f, "set tclass v6"),
+            Self::SetFlowLabelV6 => write!(f, "set flow label v6"),
+            Self::SetRecvTimestamps => write!(f, "set recv timestamps"),
             Self::Close => write!(f, "close"),
             Self::WSACreateEvent => write!(f, "WSA create event"),
             Self::WSARecvFrom => write!(f, "WSA recv from"),
@@ -136,3 +164,35 @@ impl Display for IoOperation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv4Addr;
+
+    const ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
+
+    #[test]
+    fn test_transient() {
+        let net_unreachable = io::Error::from(ErrorKind::NetUnreachable);
+        let host_unreachable = io::Error::from(ErrorKind::HostUnreachable);
+        let addr_not_available = io::Error::from(io::ErrorKind::AddrNotAvailable);
+        assert!(Error::IoError(IoError::Connect(net_unreachable, ADDR)).is_transient());
+        assert!(Error::ProbeFailed(IoError::SendTo(host_unreachable, ADDR)).is_transient());
+        assert!(Error::IoError(IoError::Bind(addr_not_available, ADDR)).is_transient());
+        assert!(Error::UnknownInterface(String::from("eth0")).is_transient());
+        assert!(Error::InvalidSourceAddr(ADDR.ip()).is_transient());
+        assert!(Error::AddressInUse(ADDR).is_transient());
+    }
+
+    #[test]
+    fn test_fatal() {
+        let permission_denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        let new_socket = IoError::Other(permission_denied, IoOperation::NewSocket);
+        assert!(!Error::IoError(new_socket).is_transient());
+        assert!(!Error::BadConfig(String::from("bad")).is_transient());
+        assert!(!Error::InvalidPacketSize(2048).is_transient());
+        assert!(!Error::MissingAddr.is_transient());
+        assert!(!Error::Other(String::from("other")).is_transient());
+    }
+}
@@ -47,6 +47,38 @@
 //! # }
 //! ```
 //!
+//! # Multiple Targets
+//!
+//! A [`Tracer`] traces a single target.  To trace several targets at once, a
+//! tracer is built and spawned for each target, every one with a distinct
+//! trace identifier:
+//!
+//! ```no_run
+//! # fn main() -> anyhow::Result<()> {
+//! # use std::net::IpAddr;
+//! # use std::str::FromStr;
+//! use trippy_core::Builder;
+//!
+//! let addrs = [IpAddr::from_str("1.1.1.1")?, IpAddr::from_str("8.8.8.8")?];
+//! let tracers = addrs
+//!     .into_iter()
+//!     .zip(1000..)
+//!     .map(|(addr, trace_id)| Builder::new(addr).trace_identifier(trace_id).build()?.spawn())
+//!     .collect::<Result<Vec<_>, _>>()?;
+//! for (tracer, _) in &tracers {
+//!     println!("{}: {:?}", tracer.target_addr(), tracer.snapshot().hops());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Each tracer maintains its own state and uses the full sequence space, so
+//! the tracers do not need to partition sequence numbers between them.
+//! Instead, responses are matched to a tracer by the trace identifier for
+//! ICMP and by the destination address and ports for UDP and TCP.  Responses
+//! which do not carry a trace identifier are accepted by every tracer, so the
+//! identifier `0` should not be used alongside other tracers.
+//!
 //! # See Also
 //!
 //! - [`Builder`] - Build a [`Tracer`].
@@ -72,19 +104,21 @@ use net::source::SourceAddr;
 
 pub use builder::Builder;
 pub use config::{
-    defaults, IcmpExtensionParseMode, MultipathStrategy, PortDirection, PrivilegeMode, Protocol,
+    defaults, IcmpExtensionParseMode, MultipathStrategy, PayloadStrategy, PortDirection,
+    PrivilegeMode, Protocol,
 };
 pub use constants::MAX_TTL;
 pub use error::Error;
 pub use flows::{FlowEntry, FlowId};
+pub use net::source::is_link_local;
 pub use probe::{
-    Extension, Extensions, IcmpPacketType, MplsLabelStack, MplsLabelStackMember, Probe,
-    ProbeComplete, ProbeStatus, UnknownExtension,
+    Extension, Extensions, IcmpPacketCode, IcmpPacketType, MplsLabelStack, MplsLabelStackMember,
+    Probe, ProbeComplete, ProbeStatus, TtlAddrs, UnknownExtension, UnreachableCode,
 };
 pub use state::{Hop, NatStatus, State};
 pub use strategy::{CompletionReason, Round, Strategy};
 pub use tracer::Tracer;
 pub use types::{
-    Flags, MaxInflight, MaxRounds, PacketSize, PayloadPattern, Port, RoundId, Sequence, TimeToLive,
-    TraceId, TypeOfService,
+    Flags, FlowLabel, MaxInflight, MaxRounds, PacketSize, PayloadPattern, Port, RoundId, Sequence,
+    TimeToLive, TraceId, TypeOfService,
 };
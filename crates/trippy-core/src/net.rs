@@ -1,5 +1,6 @@
 use crate::error::Result;
 use crate::probe::{Probe, Response};
+use std::time::Duration;
 
 /// Common types and helper functions.
 mod common;
@@ -13,6 +14,9 @@ mod ipv6;
 /// ICMP extensions.
 mod extension;
 
+/// Probe payload stamps.
+mod stamp;
+
 /// Platform specific network code.
 mod platform;
 
@@ -22,6 +26,9 @@ mod socket;
 /// A channel for sending and receiving probes.
 pub mod channel;
 
+/// A network which does not send or receive probes.
+pub mod dry_run;
+
 /// Determine the source address.
 pub mod source;
 
@@ -34,8 +41,22 @@ pub trait Network {
     /// Send a `Probe`.
     fn send_probe(&mut self, probe: Probe) -> Result<()>;
 
+    /// Send a batch of `Probe`s.
+    ///
+    /// The result of sending each probe is returned in the same order as the probes.  The default
+    /// implementation sends each probe in turn.
+    fn send_probes(&mut self, probes: &[Probe]) -> Vec<Result<()>> {
+        probes
+            .iter()
+            .map(|probe| self.send_probe(probe.clone()))
+            .collect()
+    }
+
     /// Receive the next Icmp packet and return a `ProbeResponse`.
     ///
     /// Returns `None` if the read times out or the packet read is not one of the types expected.
     fn recv_probe(&mut self) -> Result<Option<Response>>;
+
+    /// Set the maximum time to wait when receiving a probe response.
+    fn set_read_timeout(&mut self, read_timeout: Duration);
 }
@@ -3,8 +3,9 @@ use crate::error::{Error, Result};
 use crate::net::socket::Socket;
 use crate::net::{ipv4::Ipv4, ipv6::Ipv6, platform, Network};
 use crate::probe::{Probe, Response};
-use crate::{Port, PrivilegeMode, Protocol};
+use crate::{FlowLabel, Port, PrivilegeMode, Protocol, TypeOfService};
 use arrayvec::ArrayVec;
+use std::collections::VecDeque;
 use std::net::IpAddr;
 use std::time::{Duration, SystemTime};
 use tracing::instrument;
@@ -22,6 +23,8 @@ pub struct Channel<S: Socket> {
     tcp_connect_timeout: Duration,
     send_socket: Option<S>,
     recv_socket: S,
+    recv_batch_size: usize,
+    recv_queue: VecDeque<Response>,
     tcp_probes: ArrayVec<TcpProbe<S>, MAX_TCP_PROBES>,
     family_config: FamilyConfig,
 }
@@ -46,11 +49,13 @@ impl<S: Socket> Channel<S> {
         platform::startup()?;
         let ipv4_length_order = platform::Ipv4ByteOrder::for_address(config.source_addr)?;
         let send_socket = match config.protocol {
-            Protocol::Icmp => Some(make_icmp_send_socket(config.source_addr, raw)?),
-            Protocol::Udp => Some(make_udp_send_socket(config.source_addr, raw)?),
+            Protocol::Icmp => Some(make_icmp_send_socket(config, raw)?),
+            Protocol::Udp => Some(make_udp_send_socket(config, raw)?),
             Protocol::Tcp => None,
         };
-        let recv_socket = make_recv_socket(config.source_addr, raw)?;
+        let mut recv_socket = make_recv_socket(config.source_addr, raw)?;
+        let kernel_recv_timestamps =
+            config.kernel_recv_timestamps && enable_recv_timestamps(&mut recv_socket);
         let family_config = match (config.source_addr, config.target_addr) {
             (IpAddr::V4(src_addr), IpAddr::V4(dest_addr)) => FamilyConfig::V4(Ipv4 {
                 src_addr,
@@ -62,16 +67,25 @@ impl<S: Socket> Channel<S> {
                 tos: config.tos,
                 protocol: config.protocol,
                 icmp_extension_mode: config.icmp_extension_parse_mode,
+                kernel_recv_timestamps,
+                stamped_payload: config.stamped_payload,
+                payload_strategy: config.payload_strategy,
             }),
             (IpAddr::V6(src_addr), IpAddr::V6(dest_addr)) => FamilyConfig::V6(Ipv6 {
                 src_addr,
                 dest_addr,
+                dest_scope_id: config.target_scope_id,
                 packet_size: config.packet_size,
                 payload_pattern: config.payload_pattern,
                 privilege_mode: config.privilege_mode,
+                tos: config.tos,
+                flow_label: config.flow_label,
                 protocol: config.protocol,
                 icmp_extension_mode: config.icmp_extension_parse_mode,
                 initial_sequence: config.initial_sequence,
+                kernel_recv_timestamps,
+                stamped_payload: config.stamped_payload,
+                payload_strategy: config.payload_strategy,
             }),
             _ => unreachable!(),
         };
@@ -81,6 +95,8 @@ impl<S: Socket> Channel<S> {
             tcp_connect_timeout: config.tcp_connect_timeout,
             send_socket,
             recv_socket,
+            recv_batch_size: usize::from(config.recv_batch_size),
+            recv_queue: VecDeque::new(),
             tcp_probes: ArrayVec::new(),
             family_config,
         })
@@ -96,6 +112,24 @@ impl<S: Socket> Network for Channel<S> {
             Protocol::Tcp => self.dispatch_tcp_probe(probe),
         }
     }
+    /// Send a batch of probes.
+    ///
+    /// IPv4 `icmp` and `udp` probes sent from a raw socket are sent using a single system call
+    /// where the platform supports it, all other probes are sent in turn.
+    #[instrument(skip_all, fields(count = probes.len()))]
+    fn send_probes(&mut self, probes: &[Probe]) -> Vec<Result<()>> {
+        match (&self.family_config, self.send_socket.as_mut()) {
+            (FamilyConfig::V4(ipv4), Some(socket))
+                if ipv4.privilege_mode == PrivilegeMode::Privileged =>
+            {
+                ipv4.dispatch_probes_raw(socket, probes)
+            }
+            _ => probes
+                .iter()
+                .map(|probe| self.send_probe(probe.clone()))
+                .collect(),
+        }
+    }
     #[instrument(skip_all)]
     fn recv_probe(&mut self) -> Result<Option<Response>> {
         let prob_response = match self.protocol {
@@ -110,6 +144,10 @@ impl<S: Socket> Network for Channel<S> {
         }
         Ok(prob_response)
     }
+
+    fn set_read_timeout(&mut self, read_timeout: Duration) {
+        self.read_timeout = read_timeout;
+    }
 }
 
 impl<S: Socket> Channel<S> {
@@ -150,12 +188,30 @@ impl<S: Socket> Channel<S> {
     }
 
     /// Generate a `ProbeResponse` for the next available ICMP packet, if any
+    ///
+    /// If a receive batch size greater than one is configured then up to that many packets are
+    /// received at once and the responses are queued and returned one at a time.
     #[instrument(skip(self))]
     fn recv_icmp_probe(&mut self) -> Result<Option<Response>> {
+        if let Some(resp) = self.recv_queue.pop_front() {
+            return Ok(Some(resp));
+        }
         if self.recv_socket.is_readable(self.read_timeout)? {
-            match &self.family_config {
-                FamilyConfig::V4(ipv4) => ipv4.recv_icmp_probe(&mut self.recv_socket),
-                FamilyConfig::V6(ipv6) => ipv6.recv_icmp_probe(&mut self.recv_socket),
+            if self.recv_batch_size > 1 {
+                self.recv_queue.extend(match &self.family_config {
+                    FamilyConfig::V4(ipv4) => {
+                        ipv4.recv_icmp_probes(&mut self.recv_socket, self.recv_batch_size)?
+                    }
+                    FamilyConfig::V6(ipv6) => {
+                        ipv6.recv_icmp_probes(&mut self.recv_socket, self.recv_batch_size)?
+                    }
+                });
+                Ok(self.recv_queue.pop_front())
+            } else {
+                match &self.family_config {
+                    FamilyConfig::V4(ipv4) => ipv4.recv_icmp_probe(&mut self.recv_socket),
+                    FamilyConfig::V6(ipv6) => ipv6.recv_icmp_probe(&mut self.recv_socket),
+                }
             }
         } else {
             Ok(None)
@@ -217,21 +273,61 @@ impl<S: Socket> TcpProbe<S> {
 }
 
 /// Make a socket for sending raw `ICMP` packets.
-#[instrument]
-fn make_icmp_send_socket<S: Socket>(addr: IpAddr, raw: bool) -> Result<S> {
-    Ok(match addr {
-        IpAddr::V4(_) => S::new_icmp_send_socket_ipv4(raw),
-        IpAddr::V6(_) => S::new_icmp_send_socket_ipv6(raw),
-    }?)
+///
+/// For `IPv4` the type of service is set in the header of each packet, for `IPv6` the traffic
+/// class and any flow label are set on the socket.
+#[instrument(skip(config))]
+fn make_icmp_send_socket<S: Socket>(config: &ChannelConfig, raw: bool) -> Result<S> {
+    Ok(match config.source_addr {
+        IpAddr::V4(_) => S::new_icmp_send_socket_ipv4(raw)?,
+        IpAddr::V6(_) => {
+            let mut socket = S::new_icmp_send_socket_ipv6(raw)?;
+            set_send_options_v6(
+                &mut socket,
+                config.target_addr,
+                config.tos,
+                config.flow_label,
+            )?;
+            socket
+        }
+    })
 }
 
 /// Make a socket for sending `UDP` packets.
-#[instrument]
-fn make_udp_send_socket<S: Socket>(addr: IpAddr, raw: bool) -> Result<S> {
-    Ok(match addr {
-        IpAddr::V4(_) => S::new_udp_send_socket_ipv4(raw),
-        IpAddr::V6(_) => S::new_udp_send_socket_ipv6(raw),
-    }?)
+///
+/// For `IPv4` the type of service is set in the header of each packet, for `IPv6` the traffic
+/// class and any flow label are set on the socket.
+#[instrument(skip(config))]
+fn make_udp_send_socket<S: Socket>(config: &ChannelConfig, raw: bool) -> Result<S> {
+    Ok(match config.source_addr {
+        IpAddr::V4(_) => S::new_udp_send_socket_ipv4(raw)?,
+        IpAddr::V6(_) => {
+            let mut socket = S::new_udp_send_socket_ipv6(raw)?;
+            set_send_options_v6(
+                &mut socket,
+                config.target_addr,
+                config.tos,
+                config.flow_label,
+            )?;
+            socket
+        }
+    })
+}
+
+/// Set the traffic class and, if not zero, the flow label of an `IPv6` send socket.
+fn set_send_options_v6<S: Socket>(
+    socket: &mut S,
+    target_addr: IpAddr,
+    tos: TypeOfService,
+    flow_label: FlowLabel,
+) -> Result<()> {
+    socket.set_tclass_v6(u32::from(tos.0))?;
+    match target_addr {
+        IpAddr::V6(dest_addr) if flow_label != FlowLabel::default() => {
+            Ok(socket.set_flow_label_v6(dest_addr, flow_label.0)?)
+        }
+        _ => Ok(()),
+    }
 }
 
 /// Make a socket for receiving raw `ICMP` packets.
@@ -242,3 +338,72 @@ fn make_recv_socket<S: Socket>(addr: IpAddr, raw: bool) -> Result<S> {
         IpAddr::V6(ipv6addr) => S::new_recv_socket_ipv6(ipv6addr, raw),
     }?)
 }
+
+/// Enable kernel receive timestamps on a socket.
+///
+/// Returns false, and user space timestamps should be used, if they are not supported.
+#[instrument(skip(socket))]
+fn enable_recv_timestamps<S: Socket>(socket: &mut S) -> bool {
+    match socket.set_recv_timestamps(true) {
+        Ok(()) => true,
+        Err(err) => {
+            tracing::warn!("kernel timestamps not supported, using user space timestamps: {err}");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::socket::MockSocket;
+    use mockall::predicate;
+    use std::net::Ipv6Addr;
+    use std::str::FromStr;
+    use std::sync::Mutex;
+
+    static MTX: Mutex<()> = Mutex::new(());
+
+    // Test connecting an IPv6/ICMP channel with a flow label.
+    #[test]
+    fn test_connect_ipv6_flow_label() -> anyhow::Result<()> {
+        let _m = MTX.lock();
+        let src_addr = Ipv6Addr::from_str("fd7a:115c:a1e0:ab12:4843:cd96:6263:82a")?;
+        let dest_addr = Ipv6Addr::from_str("2a00:1450:4009:815::200e")?;
+
+        let send_ctx = MockSocket::new_icmp_send_socket_ipv6_context();
+        send_ctx
+            .expect()
+            .with(predicate::eq(true))
+            .returning(move |_| {
+                let mut mocket = MockSocket::new();
+                mocket
+                    .expect_set_tclass_v6()
+                    .with(predicate::eq(0))
+                    .times(1)
+                    .returning(|_| Ok(()));
+                mocket
+                    .expect_set_flow_label_v6()
+                    .with(predicate::eq(dest_addr), predicate::eq(0x12345))
+                    .times(1)
+                    .returning(|_, _| Ok(()));
+                Ok(mocket)
+            });
+        let recv_ctx = MockSocket::new_recv_socket_ipv6_context();
+        recv_ctx
+            .expect()
+            .with(predicate::eq(src_addr), predicate::eq(true))
+            .returning(|_, _| Ok(MockSocket::new()));
+
+        let config = ChannelConfig {
+            privilege_mode: PrivilegeMode::Privileged,
+            protocol: Protocol::Icmp,
+            source_addr: IpAddr::V6(src_addr),
+            target_addr: IpAddr::V6(dest_addr),
+            flow_label: FlowLabel(0x12345),
+            ..Default::default()
+        };
+        Channel::<MockSocket>::connect(&config)?;
+        Ok(())
+    }
+}
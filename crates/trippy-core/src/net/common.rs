@@ -1,7 +1,12 @@
+use crate::config::PayloadStrategy;
 use crate::error::ErrorKind;
 use crate::error::{Error, Result};
+use crate::net::stamp::STAMP_SIZE;
 use std::net::SocketAddr;
 
+/// The size of the word of an ICMP payload which is adjusted to hold the checksum constant.
+const CHECKSUM_WORD_SIZE: usize = 2;
+
 /// Utility methods to map errors.
 pub struct ErrorMapper;
 
@@ -39,6 +44,36 @@ impl ErrorMapper {
     }
 }
 
+/// The minimum size of the payload of an ICMP probe.
+pub const fn min_icmp_payload_size(
+    stamped_payload: bool,
+    payload_strategy: PayloadStrategy,
+) -> usize {
+    let stamp_size = if stamped_payload { STAMP_SIZE } else { 0 };
+    match payload_strategy {
+        PayloadStrategy::Pattern => stamp_size,
+        PayloadStrategy::ConstantChecksum => stamp_size + CHECKSUM_WORD_SIZE,
+    }
+}
+
+/// The offset of the last 16-bit aligned word of an ICMP payload of `payload_size` bytes.
+///
+/// The ICMP header is 8 bytes and so a word at an even offset of the payload is also aligned in
+/// the ICMP packet.
+pub const fn checksum_word_offset(payload_size: usize) -> usize {
+    (payload_size - CHECKSUM_WORD_SIZE) & !1
+}
+
+/// The value of the checksum word for which an ICMP packet has the checksum `target`.
+///
+/// The `checksum` is that of the packet when the checksum word is zero.  As the checksum is the
+/// one's complement of the one's complement sum of the packet, the word is the difference between
+/// the sum for the `target` checksum and the sum of the packet.
+pub const fn checksum_word(checksum: u16, target: u16) -> u16 {
+    let sum = (!target) as u32 + checksum as u32;
+    ((sum & 0xffff) + (sum >> 16)) as u16
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,70 @@
+use crate::error::Result;
+use crate::net::Network;
+use crate::probe::{Probe, Response};
+use std::thread;
+use std::time::Duration;
+
+/// A network which does not send or receive any probes.
+///
+/// Every probe which would be sent is instead passed to a function, and no responses are ever
+/// received.  Receiving a response waits for the read timeout, as for a real network, such that
+/// the rounds of the trace are paced as they would be if no responses were received.
+pub struct DryRunNetwork<F> {
+    read_timeout: Duration,
+    on_send: F,
+}
+
+impl<F: Fn(&Probe)> DryRunNetwork<F> {
+    pub const fn new(read_timeout: Duration, on_send: F) -> Self {
+        Self {
+            read_timeout,
+            on_send,
+        }
+    }
+}
+
+impl<F: Fn(&Probe)> Network for DryRunNetwork<F> {
+    fn send_probe(&mut self, probe: Probe) -> Result<()> {
+        (self.on_send)(&probe);
+        Ok(())
+    }
+
+    fn recv_probe(&mut self) -> Result<Option<Response>> {
+        thread::sleep(self.read_timeout);
+        Ok(None)
+    }
+
+    fn set_read_timeout(&mut self, read_timeout: Duration) {
+        self.read_timeout = read_timeout;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Flags, Port, RoundId, Sequence, TimeToLive, TraceId};
+    use std::cell::RefCell;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_dry_run_network() -> anyhow::Result<()> {
+        let sent = RefCell::new(vec![]);
+        let mut network = DryRunNetwork::new(Duration::ZERO, |probe: &Probe| {
+            sent.borrow_mut().push(probe.ttl);
+        });
+        let probe = Probe::new(
+            Sequence(33434),
+            TraceId(1234),
+            Port(0),
+            Port(0),
+            TimeToLive(3),
+            RoundId(0),
+            SystemTime::now(),
+            Flags::empty(),
+        );
+        network.send_probe(probe)?;
+        assert!(network.recv_probe()?.is_none());
+        assert_eq!(vec![TimeToLive(3)], *sent.borrow());
+        Ok(())
+    }
+}
@@ -17,6 +17,8 @@ impl TryFrom<&[u8]> for Extensions {
     }
 }
 
+/// Malformed extension objects, such as an MPLS label stack object without any label stack
+/// entries, are ignored.
 impl TryFrom<ExtensionsPacket<'_>> for Extensions {
     type Error = Error;
 
@@ -28,14 +30,15 @@ impl TryFrom<ExtensionsPacket<'_>> for Extensions {
         let extensions = value
             .objects()
             .flat_map(ExtensionObjectPacket::new_view)
-            .map(|obj| match obj.get_class_num() {
+            .filter_map(|obj| match obj.get_class_num() {
                 ClassNum::MultiProtocolLabelSwitchingLabelStack => {
                     MplsLabelStackPacket::new_view(obj.payload())
+                        .ok()
                         .map(|mpls| Extension::Mpls(MplsLabelStack::from(mpls)))
                 }
-                _ => Ok(Extension::Unknown(UnknownExtension::from(obj))),
+                _ => Some(Extension::Unknown(UnknownExtension::from(obj))),
             })
-            .collect::<Result<_, _>>()?;
+            .collect();
         Ok(Self { extensions })
     }
 }
@@ -73,10 +76,55 @@ impl From<ExtensionObjectPacket<'_>> for UnknownExtension {
     }
 }
 
+/// The length of the original datagram quoted in a `TimeExceeded` or `DestinationUnreachable`
+/// ICMP message.
+///
+/// The ICMP payload is only considered to be padded to the rfc4884 length if it is followed by a
+/// valid extension structure, otherwise the entire ICMP payload is the quoted original datagram.
+///
+/// Lengths which do not fit in a `u16` are saturated.
+pub fn quoted_len(payload: &[u8], payload_raw: &[u8], extension: Option<&[u8]>) -> u16 {
+    let is_extension = extension
+        .and_then(|ext| ExtensionsPacket::new_view(ext).ok())
+        .and_then(|ext| {
+            ExtensionHeaderPacket::new_view(ext.header())
+                .ok()
+                .map(|header| header.get_version())
+        })
+        .is_some_and(|version| version == ICMP_EXTENSION_VERSION);
+    let len = if is_extension {
+        payload.len()
+    } else {
+        payload_raw.len()
+    };
+    u16::try_from(len).unwrap_or(u16::MAX)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_quoted_len_no_extension() {
+        let raw = [0_u8; 256];
+        assert_eq!(256, quoted_len(&raw[..128], &raw, Some(&raw[128..])));
+    }
+
+    #[test]
+    fn test_quoted_len_extension() {
+        let mut raw = [0_u8; 144];
+        raw[128..].copy_from_slice(&hex_literal::hex!(
+            "20 00 96 53 00 0c 01 01 06 9f 18 01 00 00 29 ff"
+        ));
+        assert_eq!(128, quoted_len(&raw[..128], &raw, Some(&raw[128..])));
+    }
+
+    #[test]
+    fn test_quoted_len_short() {
+        let raw = [0_u8; 28];
+        assert_eq!(28, quoted_len(&raw, &raw, None));
+    }
+
     /// Convert a single MPLS extension which contains two labels.
     #[test]
     fn test_convert_mpls_extensions() {
@@ -118,6 +166,19 @@ mod tests {
         }
     }
 
+    /// Convert an MPLS extension which has no label stack entries, followed by an unknown
+    /// extension.
+    #[test]
+    fn test_convert_malformed_mpls_extension() {
+        let buf = hex_literal::hex!("20 00 96 53 00 04 01 01 00 08 99 01 06 9f 18 01");
+        let exts = Extensions::try_from(buf.as_slice()).unwrap();
+        assert_eq!(1, exts.extensions.len());
+        match &exts.extensions[0] {
+            Extension::Unknown(unknown) => assert_eq!(0x99, unknown.class_num),
+            Extension::Mpls(_) => panic!("expected Extension::Unknown"),
+        }
+    }
+
     /// Convert an extension with an unknown header version.
     #[test]
     fn test_convert_unknown_version() {
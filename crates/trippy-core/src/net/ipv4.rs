@@ -1,12 +1,14 @@
-use crate::config::IcmpExtensionParseMode;
+use crate::config::{IcmpExtensionParseMode, PayloadStrategy};
 use crate::error::{Error, ErrorKind, Result};
 use crate::net::channel::MAX_PACKET_SIZE;
-use crate::net::common::ErrorMapper;
+use crate::net::common::{checksum_word, checksum_word_offset, min_icmp_payload_size, ErrorMapper};
+use crate::net::extension::quoted_len;
 use crate::net::platform;
 use crate::net::socket::{Socket, SocketError};
+use crate::net::stamp::{read_stamp, write_stamp};
 use crate::probe::{
-    Extensions, IcmpPacketCode, Probe, Response, ResponseData, ResponseSeq, ResponseSeqIcmp,
-    ResponseSeqTcp, ResponseSeqUdp,
+    Extensions, IcmpPacketCode, PayloadStamp, Probe, Response, ResponseData, ResponseSeq,
+    ResponseSeqIcmp, ResponseSeqTcp, ResponseSeqUdp,
 };
 use crate::types::{PacketSize, PayloadPattern, Sequence, TraceId, TypeOfService};
 use crate::{Flags, Port, PrivilegeMode, Protocol};
@@ -62,6 +64,12 @@ pub struct Ipv4 {
     pub tos: TypeOfService,
     pub protocol: Protocol,
     pub icmp_extension_mode: IcmpExtensionParseMode,
+    /// Use kernel receive timestamps.
+    pub kernel_recv_timestamps: bool,
+    /// Embed a stamp in the payload of ICMP probes.
+    pub stamped_payload: bool,
+    /// The strategy for the payload of ICMP probes.
+    pub payload_strategy: PayloadStrategy,
 }
 
 impl Default for Ipv4 {
@@ -76,6 +84,9 @@ impl Default for Ipv4 {
             tos: TypeOfService(0),
             protocol: Protocol::Icmp,
             icmp_extension_mode: IcmpExtensionParseMode::Disabled,
+            kernel_recv_timestamps: false,
+            stamped_payload: false,
+            payload_strategy: PayloadStrategy::Pattern,
         }
     }
 }
@@ -89,67 +100,141 @@ impl Ipv4 {
         probe: Probe,
     ) -> Result<()> {
         let mut ipv4_buf = [0_u8; MAX_PACKET_SIZE];
+        let (len, remote_addr) = self.make_icmp_probe_packet(&mut ipv4_buf, &probe)?;
+        self.send_raw_packet(icmp_send_socket, &ipv4_buf[..len], remote_addr)
+    }
+
+    /// Dispatch a UDP probe.
+    #[instrument(skip(self, raw_send_socket, probe))]
+    pub fn dispatch_udp_probe<S: Socket>(
+        &self,
+        raw_send_socket: &mut S,
+        probe: Probe,
+    ) -> Result<()> {
+        let payload_size = self.udp_payload_size()?;
+        let payload = &[self.payload_pattern.0; MAX_UDP_PAYLOAD_BUF][0..payload_size];
+        match self.privilege_mode {
+            PrivilegeMode::Privileged => {
+                self.dispatch_udp_probe_raw(raw_send_socket, probe, payload)
+            }
+            PrivilegeMode::Unprivileged => self.dispatch_udp_probe_non_raw::<S>(probe, payload),
+        }
+    }
+
+    /// Dispatch a batch of ICMP or UDP probes using a raw socket with `IP_HDRINCL` set.
+    ///
+    /// The packets for all probes are built up front and sent using a single call to
+    /// `Socket::send_to_many`.  Any probes which were not sent by that call, such as when the send
+    /// of one of the packets failed, are sent individually such that any failure is attributed to
+    /// the correct probe.
+    ///
+    /// The result of dispatching each probe is returned in the same order as the probes.
+    #[instrument(skip(self, raw_send_socket, probes))]
+    pub fn dispatch_probes_raw<S: Socket>(
+        &self,
+        raw_send_socket: &mut S,
+        probes: &[Probe],
+    ) -> Vec<Result<()>> {
+        let mut bufs = vec![[0_u8; MAX_PACKET_SIZE]; probes.len()];
+        let udp_payload = [self.payload_pattern.0; MAX_UDP_PAYLOAD_BUF];
+        let packets = probes
+            .iter()
+            .zip(bufs.iter_mut())
+            .map(|(probe, buf)| match self.protocol {
+                Protocol::Icmp => self.make_icmp_probe_packet(buf, probe),
+                Protocol::Udp | Protocol::Tcp => {
+                    let payload = &udp_payload[0..self.udp_payload_size()?];
+                    self.make_udp_probe_packet_raw(buf, probe, payload)
+                }
+            })
+            .collect::<Vec<_>>();
+        let built = packets
+            .iter()
+            .zip(&bufs)
+            .filter_map(|(packet, buf)| {
+                packet
+                    .as_ref()
+                    .ok()
+                    .map(|&(len, remote_addr)| (&buf[..len], remote_addr))
+            })
+            .collect::<Vec<_>>();
+        let mut sent = raw_send_socket.send_to_many(&built).unwrap_or_default();
+        packets
+            .into_iter()
+            .zip(&bufs)
+            .map(|(packet, buf)| {
+                let (len, remote_addr) = packet?;
+                if sent > 0 {
+                    sent -= 1;
+                    Ok(())
+                } else {
+                    self.send_raw_packet(raw_send_socket, &buf[..len], remote_addr)
+                }
+            })
+            .collect()
+    }
+
+    /// Build the IPv4 packet for an ICMP probe.
+    ///
+    /// Returns the length of the packet written to `ipv4_buf` and the address to send it to.
+    fn make_icmp_probe_packet(
+        &self,
+        ipv4_buf: &mut [u8],
+        probe: &Probe,
+    ) -> Result<(usize, SocketAddr)> {
         let mut icmp_buf = [0_u8; MAX_ICMP_PACKET_BUF];
         let packet_size = usize::from(self.packet_size.0);
         if !(MIN_PACKET_SIZE_ICMP..=MAX_PACKET_SIZE).contains(&packet_size) {
             return Err(Error::InvalidPacketSize(packet_size));
         }
+        if icmp_payload_size(packet_size)
+            < min_icmp_payload_size(self.stamped_payload, self.payload_strategy)
+        {
+            return Err(Error::InvalidPacketSize(packet_size));
+        }
         let echo_request = self.make_echo_request_icmp_packet(
             &mut icmp_buf,
             probe.identifier,
             probe.sequence,
+            probe.sent,
             icmp_payload_size(packet_size),
         )?;
         let ipv4 = self.make_ipv4_packet(
-            &mut ipv4_buf,
+            ipv4_buf,
             IpProtocol::Icmp,
             probe.ttl.0,
             0,
             echo_request.packet(),
         )?;
         let remote_addr = SocketAddr::new(IpAddr::V4(self.dest_addr), 0);
-        icmp_send_socket
-            .send_to(ipv4.packet(), remote_addr)
-            .map_err(Error::IoError)
-            .map_err(|err| ErrorMapper::probe_failed(err, ErrorKind::HostUnreachable))
-            .map_err(|err| ErrorMapper::probe_failed(err, ErrorKind::NetUnreachable))
-            .map_err(|err| ErrorMapper::probe_failed(err, INVALID_INPUT_KIND))?;
-        Ok(())
+        Ok((ipv4.packet().len(), remote_addr))
     }
 
-    /// Dispatch a UDP probe.
+    /// Dispatch a UDP probe using a raw socket with `IP_HDRINCL` set.
     #[instrument(skip(self, raw_send_socket, probe))]
-    pub fn dispatch_udp_probe<S: Socket>(
+    fn dispatch_udp_probe_raw<S: Socket>(
         &self,
         raw_send_socket: &mut S,
         probe: Probe,
+        payload: &[u8],
     ) -> Result<()> {
-        let packet_size = usize::from(self.packet_size.0);
-        if !(MIN_PACKET_SIZE_UDP..=MAX_PACKET_SIZE).contains(&packet_size) {
-            return Err(Error::InvalidPacketSize(packet_size));
-        }
-        let payload_size = udp_payload_size(packet_size);
-        let payload = &[self.payload_pattern.0; MAX_UDP_PAYLOAD_BUF][0..payload_size];
-        match self.privilege_mode {
-            PrivilegeMode::Privileged => {
-                self.dispatch_udp_probe_raw(raw_send_socket, probe, payload)
-            }
-            PrivilegeMode::Unprivileged => self.dispatch_udp_probe_non_raw::<S>(probe, payload),
-        }
+        let mut ipv4_buf = [0_u8; MAX_PACKET_SIZE];
+        let (len, remote_addr) = self.make_udp_probe_packet_raw(&mut ipv4_buf, &probe, payload)?;
+        self.send_raw_packet(raw_send_socket, &ipv4_buf[..len], remote_addr)
     }
 
-    /// Dispatch a UDP probe using a raw socket with `IP_HDRINCL` set.
+    /// Build the IPv4 packet for a UDP probe to be sent using a raw socket.
     ///
     /// As `IP_HDRINCL` is set we must supply the IP and UDP headers which allows us to set custom
     /// values for certain fields such as the checksum as required by the Paris tracing strategy.
-    #[instrument(skip(self, raw_send_socket, probe))]
-    fn dispatch_udp_probe_raw<S: Socket>(
+    ///
+    /// Returns the length of the packet written to `ipv4_buf` and the address to send it to.
+    fn make_udp_probe_packet_raw(
         &self,
-        raw_send_socket: &mut S,
-        probe: Probe,
+        ipv4_buf: &mut [u8],
+        probe: &Probe,
         payload: &[u8],
-    ) -> Result<()> {
-        let mut ipv4_buf = [0_u8; MAX_PACKET_SIZE];
+    ) -> Result<(usize, SocketAddr)> {
         let mut udp_buf = [0_u8; MAX_UDP_PACKET_BUF];
         let payload_paris = probe.sequence.0.to_be_bytes();
         let payload = if probe.flags.contains(Flags::PARIS_CHECKSUM) {
@@ -166,19 +251,46 @@ impl Ipv4 {
             udp.set_payload(&checksum);
         }
         let ipv4 = self.make_ipv4_packet(
-            &mut ipv4_buf,
+            ipv4_buf,
             IpProtocol::Udp,
             probe.ttl.0,
             probe.identifier.0,
             udp.packet(),
         )?;
         let remote_addr = SocketAddr::new(IpAddr::V4(self.dest_addr), probe.dest_port.0);
-        raw_send_socket
-            .send_to(ipv4.packet(), remote_addr)
+        Ok((ipv4.packet().len(), remote_addr))
+    }
+
+    /// The size of the payload of UDP probes.
+    fn udp_payload_size(&self) -> Result<usize> {
+        let packet_size = usize::from(self.packet_size.0);
+        if !(MIN_PACKET_SIZE_UDP..=MAX_PACKET_SIZE).contains(&packet_size) {
+            return Err(Error::InvalidPacketSize(packet_size));
+        }
+        Ok(udp_payload_size(packet_size))
+    }
+
+    /// Send a packet using a raw socket.
+    ///
+    /// Some errors are mapped to `Error::ProbeFailed` such that they are not considered fatal.
+    fn send_raw_packet<S: Socket>(
+        &self,
+        raw_send_socket: &mut S,
+        buf: &[u8],
+        remote_addr: SocketAddr,
+    ) -> Result<()> {
+        let sent = raw_send_socket
+            .send_to(buf, remote_addr)
             .map_err(Error::IoError)
             .map_err(|err| ErrorMapper::probe_failed(err, ErrorKind::HostUnreachable))
-            .map_err(|err| ErrorMapper::probe_failed(err, ErrorKind::NetUnreachable))?;
-        Ok(())
+            .map_err(|err| ErrorMapper::probe_failed(err, ErrorKind::NetUnreachable))
+            .map_err(|err| ErrorMapper::probe_failed(err, WOULD_BLOCK_KIND));
+        match self.protocol {
+            Protocol::Icmp => {
+                sent.map_err(|err| ErrorMapper::probe_failed(err, INVALID_INPUT_KIND))
+            }
+            Protocol::Udp | Protocol::Tcp => sent,
+        }
     }
 
     /// Dispatch a UDP probe using a new UDP datagram socket.
@@ -194,6 +306,7 @@ impl Ipv4 {
             .map_err(|err| ErrorMapper::addr_in_use(err, local_addr))
             .map_err(|err| ErrorMapper::probe_failed(err, ADDR_NOT_AVAILABLE_KIND))?;
         socket.set_ttl(u32::from(probe.ttl.0))?;
+        socket.set_tos(u32::from(self.tos.0))?;
         socket.send_to(payload, remote_addr)?;
         Ok(())
     }
@@ -225,10 +338,20 @@ impl Ipv4 {
     #[instrument(skip(self, recv_socket))]
     pub fn recv_icmp_probe<S: Socket>(&self, recv_socket: &mut S) -> Result<Option<Response>> {
         let mut buf = [0_u8; MAX_PACKET_SIZE];
-        match recv_socket.read(&mut buf) {
-            Ok(bytes_read) => {
+        let received = if self.kernel_recv_timestamps {
+            recv_socket
+                .recv_from_timestamped(&mut buf)
+                .map(|(bytes_read, _, timestamp)| (bytes_read, timestamp))
+        } else {
+            recv_socket
+                .read(&mut buf)
+                .map(|bytes_read| (bytes_read, None))
+        };
+        match received {
+            Ok((bytes_read, timestamp)) => {
+                let recv = timestamp.unwrap_or_else(SystemTime::now);
                 let ipv4 = Ipv4Packet::new_view(&buf[..bytes_read])?;
-                Ok(self.extract_probe_resp(&ipv4)?)
+                Ok(self.extract_probe_resp(&ipv4, recv)?)
             }
             Err(err) => match err.kind() {
                 ErrorKind::Std(io::ErrorKind::WouldBlock) => Ok(None),
@@ -237,6 +360,42 @@ impl Ipv4 {
         }
     }
 
+    /// Receive a batch of ICMP probe responses.
+    ///
+    /// Up to `count` packets are received using a single call to `Socket::recv_from_many` and the
+    /// responses are returned in the order in which they were received.
+    ///
+    /// Each response is stamped with the kernel receive timestamp of the packet, if available,
+    /// otherwise all responses in the batch are stamped with the time at which the batch was
+    /// received.
+    #[instrument(skip(self, recv_socket))]
+    pub fn recv_icmp_probes<S: Socket>(
+        &self,
+        recv_socket: &mut S,
+        count: usize,
+    ) -> Result<Vec<Response>> {
+        let mut bufs = vec![[0_u8; MAX_PACKET_SIZE]; count];
+        let mut slices = bufs.iter_mut().map(|buf| &mut buf[..]).collect::<Vec<_>>();
+        let received = match recv_socket.recv_from_many(&mut slices) {
+            Ok(received) => received,
+            Err(err) => {
+                return match err.kind() {
+                    ErrorKind::Std(io::ErrorKind::WouldBlock) => Ok(vec![]),
+                    _ => Err(Error::IoError(err)),
+                }
+            }
+        };
+        let now = SystemTime::now();
+        let mut responses = Vec::with_capacity(received.len());
+        for ((bytes_read, _, timestamp), buf) in received.into_iter().zip(&bufs) {
+            let ipv4 = Ipv4Packet::new_view(&buf[..bytes_read])?;
+            if let Some(resp) = self.extract_probe_resp(&ipv4, timestamp.unwrap_or(now))? {
+                responses.push(resp);
+            }
+        }
+        Ok(responses)
+    }
+
     /// Receive a TCP probe response.
     #[instrument(skip(self, tcp_socket))]
     pub fn recv_tcp_socket<S: Socket>(
@@ -258,6 +417,8 @@ impl Ipv4 {
                     SystemTime::now(),
                     addr,
                     resp_seq,
+                    None,
+                    None,
                 ))));
             }
             Some(err) => match err {
@@ -266,12 +427,14 @@ impl Ipv4 {
                         SystemTime::now(),
                         IpAddr::V4(self.dest_addr),
                         resp_seq,
+                        None,
+                        None,
                     ))));
                 }
                 SocketError::HostUnreachable => {
                     let error_addr = tcp_socket.icmp_error_info()?;
                     return Ok(Some(Response::TimeExceeded(
-                        ResponseData::new(SystemTime::now(), error_addr, resp_seq),
+                        ResponseData::new(SystemTime::now(), error_addr, resp_seq, None, None),
                         IcmpPacketCode(1),
                         None,
                     )));
@@ -283,8 +446,11 @@ impl Ipv4 {
     }
 
     #[instrument(skip(self))]
-    fn extract_probe_resp(&self, ipv4: &Ipv4Packet<'_>) -> Result<Option<Response>> {
-        let recv = SystemTime::now();
+    fn extract_probe_resp(
+        &self,
+        ipv4: &Ipv4Packet<'_>,
+        recv: SystemTime,
+    ) -> Result<Option<Response>> {
         let src = IpAddr::V4(ipv4.get_source());
         let icmp_v4 = IcmpPacket::new_view(ipv4.payload())?;
         let icmp_type = icmp_v4.get_icmp_type();
@@ -304,9 +470,20 @@ impl Ipv4 {
                             (ipv4, None)
                         }
                     };
+                    let quoted_len = Some(quoted_len(
+                        packet.payload(),
+                        packet.payload_raw(),
+                        packet.extension(),
+                    ));
                     self.extract_probe_resp_seq(&nested_ipv4)?.map(|resp_seq| {
                         Response::TimeExceeded(
-                            ResponseData::new(recv, src, resp_seq),
+                            ResponseData::new(
+                                recv,
+                                src,
+                                resp_seq,
+                                Some(ipv4.get_ttl()),
+                                quoted_len,
+                            ),
                             IcmpPacketCode(icmp_code.0),
                             extension,
                         )
@@ -324,9 +501,14 @@ impl Ipv4 {
                     }
                     IcmpExtensionParseMode::Disabled => None,
                 };
+                let quoted_len = Some(quoted_len(
+                    packet.payload(),
+                    packet.payload_raw(),
+                    packet.extension(),
+                ));
                 self.extract_probe_resp_seq(&nested_ipv4)?.map(|resp_seq| {
                     Response::DestinationUnreachable(
-                        ResponseData::new(recv, src, resp_seq),
+                        ResponseData::new(recv, src, resp_seq, Some(ipv4.get_ttl()), quoted_len),
                         IcmpPacketCode(icmp_code.0),
                         extension,
                     )
@@ -337,9 +519,10 @@ impl Ipv4 {
                     let packet = EchoReplyPacket::new_view(icmp_v4.packet())?;
                     let id = packet.get_identifier();
                     let seq = packet.get_sequence();
-                    let resp_seq = ResponseSeq::Icmp(ResponseSeqIcmp::new(id, seq));
+                    let stamp = self.extract_stamp(packet.payload());
+                    let resp_seq = ResponseSeq::Icmp(ResponseSeqIcmp::new(id, seq, stamp));
                     Some(Response::EchoReply(
-                        ResponseData::new(recv, src, resp_seq),
+                        ResponseData::new(recv, src, resp_seq, Some(ipv4.get_ttl()), None),
                         IcmpPacketCode(icmp_code.0),
                     ))
                 }
@@ -356,8 +539,9 @@ impl Ipv4 {
                 let echo_request = extract_echo_request(ipv4)?;
                 let identifier = echo_request.get_identifier();
                 let sequence = echo_request.get_sequence();
+                let stamp = self.extract_stamp(echo_request.payload());
                 Some(ResponseSeq::Icmp(ResponseSeqIcmp::new(
-                    identifier, sequence,
+                    identifier, sequence, stamp,
                 )))
             }
             (Protocol::Udp, IpProtocol::Udp) => {
@@ -389,25 +573,55 @@ impl Ipv4 {
     }
 
     /// Create an ICMP `EchoRequest` packet.
+    ///
+    /// If the payload strategy is `PayloadStrategy::ConstantChecksum` then the last 16-bit aligned
+    /// word of the payload is set such that the checksum is that of a packet with a sequence number
+    /// of zero and a payload of only the payload pattern.
     fn make_echo_request_icmp_packet<'a>(
         &self,
         icmp_buf: &'a mut [u8],
         identifier: TraceId,
         sequence: Sequence,
+        sent: SystemTime,
         payload_size: usize,
     ) -> Result<EchoRequestPacket<'a>> {
-        let payload_buf = [self.payload_pattern.0; MAX_ICMP_PAYLOAD_BUF];
+        let mut payload_buf = [self.payload_pattern.0; MAX_ICMP_PAYLOAD_BUF];
+        if self.stamped_payload {
+            let stamp = PayloadStamp::new(identifier.0, sequence.0, sent);
+            write_stamp(&mut payload_buf[..payload_size], stamp);
+        }
         let packet_size = IcmpPacket::minimum_packet_size() + payload_size;
         let mut icmp = EchoRequestPacket::new(&mut icmp_buf[..packet_size])?;
         icmp.set_icmp_type(IcmpType::EchoRequest);
         icmp.set_icmp_code(IcmpCode(0));
         icmp.set_identifier(identifier.0);
+        if self.payload_strategy == PayloadStrategy::ConstantChecksum {
+            let pattern = [self.payload_pattern.0; MAX_ICMP_PAYLOAD_BUF];
+            icmp.set_payload(&pattern[..payload_size]);
+            icmp.set_sequence(0);
+            let target = icmp_ipv4_checksum(icmp.packet());
+            let offset = checksum_word_offset(payload_size);
+            payload_buf[offset..offset + 2].fill(0);
+            icmp.set_payload(&payload_buf[..payload_size]);
+            icmp.set_sequence(sequence.0);
+            let word = checksum_word(icmp_ipv4_checksum(icmp.packet()), target);
+            payload_buf[offset..offset + 2].copy_from_slice(&word.to_be_bytes());
+        }
         icmp.set_payload(&payload_buf[..payload_size]);
         icmp.set_sequence(sequence.0);
         icmp.set_checksum(icmp_ipv4_checksum(icmp.packet()));
         Ok(icmp)
     }
 
+    /// Read the stamp from the payload of an ICMP probe, if enabled.
+    fn extract_stamp(&self, payload: &[u8]) -> Option<PayloadStamp> {
+        if self.stamped_payload {
+            read_stamp(payload)
+        } else {
+            None
+        }
+    }
+
     /// Create a `UdpPacket`
     fn make_udp_packet<'a>(
         &self,
@@ -445,6 +659,8 @@ impl Ipv4 {
         let mut ipv4 = Ipv4Packet::new(&mut ipv4_buf[..ipv4_total_length as usize])?;
         ipv4.set_version(4);
         ipv4.set_header_length(5);
+        ipv4.set_dscp(self.tos.dscp());
+        ipv4.set_ecn(self.tos.ecn());
         ipv4.set_total_length(ipv4_total_length_header);
         ipv4.set_ttl(ttl);
         ipv4.set_protocol(protocol);
@@ -477,6 +693,7 @@ impl Ipv4 {
 
 const ADDR_NOT_AVAILABLE_KIND: ErrorKind = ErrorKind::Std(io::ErrorKind::AddrNotAvailable);
 const INVALID_INPUT_KIND: ErrorKind = ErrorKind::Std(io::ErrorKind::InvalidInput);
+const WOULD_BLOCK_KIND: ErrorKind = ErrorKind::Std(io::ErrorKind::WouldBlock);
 
 const fn icmp_payload_size(packet_size: usize) -> usize {
     let ip_header_size = Ipv4Packet::minimum_packet_size();
@@ -536,16 +753,37 @@ fn extract_tcp_packet(ipv4: &Ipv4Packet<'_>) -> Result<(u16, u16)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error::IoResult;
-    use crate::mocket_read;
+    use crate::error::{IoError, IoResult};
     use crate::net::socket::MockSocket;
+    use crate::{mocket_read, mocket_recv_from_timestamped};
     use crate::{Flags, Port, RoundId, TimeToLive};
     use mockall::predicate;
     use std::str::FromStr;
     use std::sync::Mutex;
+    use std::time::Duration;
 
     static MTX: Mutex<()> = Mutex::new(());
 
+    // Test dispatching a IPv4/ICMP probe for which the send would block.
+    #[test]
+    fn test_dispatch_icmp_probe_would_block() -> anyhow::Result<()> {
+        let probe = make_icmp_probe();
+        let mut mocket = MockSocket::new();
+        mocket.expect_send_to().times(1).returning(|_, addr| {
+            let err = io::Error::from(io::ErrorKind::WouldBlock);
+            Err(IoError::SendTo(err, addr))
+        });
+        let ipv4 = Ipv4 {
+            src_addr: Ipv4Addr::from_str("1.2.3.4")?,
+            dest_addr: Ipv4Addr::from_str("5.6.7.8")?,
+            packet_size: PacketSize(28),
+            ..Default::default()
+        };
+        let err = ipv4.dispatch_icmp_probe(&mut mocket, probe).unwrap_err();
+        assert!(matches!(err, Error::ProbeFailed(_)));
+        Ok(())
+    }
+
     // Test dispatching a IPv4/ICMP probe.
     #[test]
     fn test_dispatch_icmp_probe_no_payload() -> anyhow::Result<()> {
@@ -585,6 +823,99 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dispatch_icmp_probe_with_tos() -> anyhow::Result<()> {
+        let probe = make_icmp_probe();
+        let src_addr = Ipv4Addr::from_str("1.2.3.4")?;
+        let dest_addr = Ipv4Addr::from_str("5.6.7.8")?;
+        let packet_size = PacketSize(28);
+        let payload_pattern = PayloadPattern(0x00);
+        let byte_order = platform::Ipv4ByteOrder::Network;
+        let tos = TypeOfService(0xb9);
+        let expected_send_to_buf = hex_literal::hex!(
+            "
+            45 b9 00 1c 00 00 40 00 0a 01 00 00 01 02 03 04
+            05 06 07 08 08 00 70 93 04 d2 82 9a
+            "
+        );
+        let expected_send_to_addr = SocketAddr::new(IpAddr::V4(dest_addr), 0);
+
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_send_to()
+            .with(
+                predicate::eq(expected_send_to_buf),
+                predicate::eq(expected_send_to_addr),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let ipv4 = Ipv4 {
+            src_addr,
+            dest_addr,
+            byte_order,
+            packet_size,
+            payload_pattern,
+            tos,
+            ..Default::default()
+        };
+        ipv4.dispatch_icmp_probe(&mut mocket, probe)?;
+        Ok(())
+    }
+
+    // Test dispatching a batch of IPv4/ICMP probes where only the first
+    // packet of the batch is sent and the remaining packet is sent
+    // individually.
+    #[test]
+    fn test_dispatch_probes_raw_partial() -> anyhow::Result<()> {
+        let probes = [make_icmp_probe(), make_icmp_probe()];
+        let src_addr = Ipv4Addr::from_str("1.2.3.4")?;
+        let dest_addr = Ipv4Addr::from_str("5.6.7.8")?;
+        let packet_size = PacketSize(28);
+        let payload_pattern = PayloadPattern(0x00);
+        let byte_order = platform::Ipv4ByteOrder::Network;
+        let expected_send_to_buf = hex_literal::hex!(
+            "
+            45 00 00 1c 00 00 40 00 0a 01 00 00 01 02 03 04
+            05 06 07 08 08 00 70 93 04 d2 82 9a
+            "
+        );
+        let expected_send_to_addr = SocketAddr::new(IpAddr::V4(dest_addr), 0);
+
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_send_to_many()
+            .withf(move |bufs| {
+                bufs.len() == 2
+                    && bufs.iter().all(|&(buf, addr)| {
+                        buf == expected_send_to_buf && addr == expected_send_to_addr
+                    })
+            })
+            .times(1)
+            .returning(|_| Ok(1));
+        mocket
+            .expect_send_to()
+            .with(
+                predicate::eq(expected_send_to_buf),
+                predicate::eq(expected_send_to_addr),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let ipv4 = Ipv4 {
+            src_addr,
+            dest_addr,
+            byte_order,
+            packet_size,
+            payload_pattern,
+            ..Default::default()
+        };
+        let results = ipv4.dispatch_probes_raw(&mut mocket, &probes);
+        assert_eq!(2, results.len());
+        assert!(results.iter().all(Result::is_ok));
+        Ok(())
+    }
+
     #[test]
     fn test_dispatch_icmp_probe_with_payload() -> anyhow::Result<()> {
         let probe = make_icmp_probe();
@@ -624,6 +955,123 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dispatch_icmp_probe_stamped_payload() -> anyhow::Result<()> {
+        let probe = Probe {
+            sent: SystemTime::UNIX_EPOCH + Duration::from_secs(1),
+            ..make_icmp_probe()
+        };
+        let src_addr = Ipv4Addr::from_str("1.2.3.4")?;
+        let dest_addr = Ipv4Addr::from_str("5.6.7.8")?;
+        let packet_size = PacketSize(48);
+        let payload_pattern = PayloadPattern(0xff);
+        let byte_order = platform::Ipv4ByteOrder::Network;
+        let expected_send_to_buf = hex_literal::hex!(
+            "
+            45 00 00 30 00 00 40 00 0a 01 00 00 01 02 03 04
+            05 06 07 08 08 00 02 2c 04 d2 82 9a 54 52 50 59
+            04 d2 82 9a 00 00 00 00 00 0f 42 40 ff ff ff ff
+            "
+        );
+        let expected_send_to_addr = SocketAddr::new(IpAddr::V4(dest_addr), 0);
+
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_send_to()
+            .with(
+                predicate::eq(expected_send_to_buf),
+                predicate::eq(expected_send_to_addr),
+            )
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let ipv4 = Ipv4 {
+            src_addr,
+            dest_addr,
+            byte_order,
+            packet_size,
+            payload_pattern,
+            stamped_payload: true,
+            ..Default::default()
+        };
+        ipv4.dispatch_icmp_probe(&mut mocket, probe)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_dispatch_icmp_probe_stamped_payload_invalid_packet_size() -> anyhow::Result<()> {
+        let probe = make_icmp_probe();
+        let src_addr = Ipv4Addr::from_str("1.2.3.4")?;
+        let dest_addr = Ipv4Addr::from_str("5.6.7.8")?;
+        let packet_size = PacketSize(43);
+        let mut mocket = MockSocket::new();
+        let ipv4 = Ipv4 {
+            src_addr,
+            dest_addr,
+            packet_size,
+            stamped_payload: true,
+            ..Default::default()
+        };
+        let err = ipv4.dispatch_icmp_probe(&mut mocket, probe).unwrap_err();
+        assert!(matches!(err, Error::InvalidPacketSize(43)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_dispatch_icmp_probe_constant_checksum() -> anyhow::Result<()> {
+        let probe1 = Probe {
+            sequence: Sequence(33434),
+            ttl: TimeToLive(1),
+            sent: SystemTime::UNIX_EPOCH + Duration::from_secs(1),
+            ..make_icmp_probe()
+        };
+        let probe2 = Probe {
+            sequence: Sequence(33435),
+            ttl: TimeToLive(2),
+            sent: SystemTime::UNIX_EPOCH + Duration::from_secs(2),
+            ..make_icmp_probe()
+        };
+        let ipv4 = Ipv4 {
+            src_addr: Ipv4Addr::from_str("1.2.3.4")?,
+            dest_addr: Ipv4Addr::from_str("5.6.7.8")?,
+            byte_order: platform::Ipv4ByteOrder::Network,
+            packet_size: PacketSize(48),
+            payload_pattern: PayloadPattern(0xff),
+            stamped_payload: true,
+            payload_strategy: PayloadStrategy::ConstantChecksum,
+            ..Default::default()
+        };
+        let mut buf1 = [0_u8; MAX_PACKET_SIZE];
+        let mut buf2 = [0_u8; MAX_PACKET_SIZE];
+        let (len1, _) = ipv4.make_icmp_probe_packet(&mut buf1, &probe1)?;
+        let (len2, _) = ipv4.make_icmp_probe_packet(&mut buf2, &probe2)?;
+        assert_eq!(48, len1);
+        assert_eq!(48, len2);
+        assert_ne!(buf1[20..48], buf2[20..48]);
+        assert_eq!(buf1[22..24], buf2[22..24]);
+        assert_eq!([0xf3, 0x2d], buf1[22..24]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_dispatch_icmp_probe_constant_checksum_invalid_packet_size() -> anyhow::Result<()> {
+        let probe = make_icmp_probe();
+        let src_addr = Ipv4Addr::from_str("1.2.3.4")?;
+        let dest_addr = Ipv4Addr::from_str("5.6.7.8")?;
+        let packet_size = PacketSize(29);
+        let mut mocket = MockSocket::new();
+        let ipv4 = Ipv4 {
+            src_addr,
+            dest_addr,
+            packet_size,
+            payload_strategy: PayloadStrategy::ConstantChecksum,
+            ..Default::default()
+        };
+        let err = ipv4.dispatch_icmp_probe(&mut mocket, probe).unwrap_err();
+        assert!(matches!(err, Error::InvalidPacketSize(29)));
+        Ok(())
+    }
+
     #[test]
     fn test_dispatch_icmp_probe_invalid_packet_size_low() -> anyhow::Result<()> {
         let probe = make_icmp_probe();
@@ -871,6 +1319,12 @@ mod tests {
                 .times(1)
                 .returning(|_| Ok(()));
 
+            mocket
+                .expect_set_tos()
+                .with(predicate::eq(0))
+                .times(1)
+                .returning(|_| Ok(()));
+
             mocket
                 .expect_send_to()
                 .with(
@@ -927,6 +1381,12 @@ mod tests {
                 .times(1)
                 .returning(|_| Ok(()));
 
+            mocket
+                .expect_set_tos()
+                .with(predicate::eq(0))
+                .times(1)
+                .returning(|_| Ok(()));
+
             mocket
                 .expect_send_to()
                 .with(
@@ -1082,7 +1542,9 @@ mod tests {
                     ResponseSeq::Icmp(ResponseSeqIcmp {
                         identifier,
                         sequence,
+                        ..
                     }),
+                ttl,
                 ..
             },
             icmp_code,
@@ -1096,10 +1558,92 @@ mod tests {
         );
         assert_eq!(30167, identifier);
         assert_eq!(33049, sequence);
+        assert_eq!(Some(59), ttl);
         assert_eq!(IcmpPacketCode(0), icmp_code);
         Ok(())
     }
 
+    #[test]
+    fn test_recv_icmp_probe_echo_reply_kernel_timestamp() -> anyhow::Result<()> {
+        let expected_read_buf = hex_literal::hex!(
+            "
+            45 20 00 54 00 00 00 00 3b 01 50 02 8e fb de ce
+            c0 a8 01 15 00 00 09 0f 75 d7 81 19 00 00 00 00
+            00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            00 00 00 00
+           "
+        );
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_nanos(1_700_000_000_123_456_789);
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_recv_from_timestamped()
+            .times(1)
+            .returning(mocket_recv_from_timestamped!(
+                expected_read_buf,
+                None,
+                Some(timestamp)
+            ));
+        let ipv4 = Ipv4 {
+            protocol: Protocol::Icmp,
+            icmp_extension_mode: IcmpExtensionParseMode::Disabled,
+            kernel_recv_timestamps: true,
+            ..Default::default()
+        };
+        let resp = ipv4.recv_icmp_probe(&mut mocket)?.unwrap();
+        let Response::EchoReply(ResponseData { recv, .. }, _) = resp else {
+            panic!("expected EchoReply")
+        };
+        assert_eq!(timestamp, recv);
+        Ok(())
+    }
+
+    // Test receiving a batch of two IPv4/ICMP echo replies where only the
+    // first has a kernel receive timestamp.
+    #[test]
+    fn test_recv_icmp_probes_echo_reply() -> anyhow::Result<()> {
+        let expected_read_buf = hex_literal::hex!(
+            "
+            45 20 00 54 00 00 00 00 3b 01 50 02 8e fb de ce
+            c0 a8 01 15 00 00 09 0f 75 d7 81 19 00 00 00 00
+            00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            00 00 00 00
+           "
+        );
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_nanos(1_700_000_000_123_456_789);
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_recv_from_many()
+            .withf(|bufs| bufs.len() == 8)
+            .times(1)
+            .returning(move |bufs| {
+                let len = expected_read_buf.len();
+                bufs[0][..len].copy_from_slice(&expected_read_buf);
+                bufs[1][..len].copy_from_slice(&expected_read_buf);
+                Ok(vec![(len, None, Some(timestamp)), (len, None, None)])
+            });
+        let ipv4 = Ipv4 {
+            protocol: Protocol::Icmp,
+            icmp_extension_mode: IcmpExtensionParseMode::Disabled,
+            ..Default::default()
+        };
+        let resps = ipv4.recv_icmp_probes(&mut mocket, 8)?;
+        assert_eq!(2, resps.len());
+        let Response::EchoReply(ResponseData { recv, addr, .. }, _) = &resps[0] else {
+            panic!("expected EchoReply")
+        };
+        assert_eq!(timestamp, *recv);
+        assert_eq!(IpAddr::V4(Ipv4Addr::from_str("142.251.222.206")?), *addr);
+        let Response::EchoReply(ResponseData { recv, .. }, _) = &resps[1] else {
+            panic!("expected EchoReply")
+        };
+        assert_ne!(timestamp, *recv);
+        Ok(())
+    }
+
     #[test]
     fn test_recv_icmp_probe_time_exceeded_icmp_no_extensions() -> anyhow::Result<()> {
         let expected_read_buf = hex_literal::hex!(
@@ -1132,6 +1676,7 @@ mod tests {
                     ResponseSeq::Icmp(ResponseSeqIcmp {
                         identifier,
                         sequence,
+                        ..
                     }),
                 ..
             },
@@ -1152,6 +1697,60 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_recv_icmp_probe_time_exceeded_icmp_stamped_payload() -> anyhow::Result<()> {
+        let expected_read_buf = hex_literal::hex!(
+            "
+             45 20 00 70 07 d7 00 00 3b 01 e9 5d 8e fa 3d 81
+             c0 a8 01 15 0b 00 f4 ff 00 00 00 00 45 60 00 54
+             65 b0 40 00 01 01 e4 11 c0 a8 01 15 8e fb de ce
+             08 00 01 11 75 d7 81 17 54 52 50 59 04 d2 82 9a
+             00 00 00 00 00 0f 42 40 00 00 00 00 00 00 00 00
+             00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+             00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+           "
+        );
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_read()
+            .times(1)
+            .returning(mocket_read!(expected_read_buf));
+        let ipv4 = Ipv4 {
+            protocol: Protocol::Icmp,
+            icmp_extension_mode: IcmpExtensionParseMode::Disabled,
+            stamped_payload: true,
+            ..Default::default()
+        };
+        let resp = ipv4.recv_icmp_probe(&mut mocket)?.unwrap();
+
+        let Response::TimeExceeded(
+            ResponseData {
+                resp_seq:
+                    ResponseSeq::Icmp(ResponseSeqIcmp {
+                        identifier,
+                        sequence,
+                        stamp,
+                    }),
+                ..
+            },
+            ..,
+        ) = resp
+        else {
+            panic!("expected TimeExceeded")
+        };
+        assert_eq!(30167, identifier);
+        assert_eq!(33047, sequence);
+        assert_eq!(
+            Some(PayloadStamp::new(
+                1234,
+                33434,
+                SystemTime::UNIX_EPOCH + Duration::from_secs(1)
+            )),
+            stamp
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_recv_icmp_probe_destination_unreachable_icmp_no_extensions() -> anyhow::Result<()> {
         let expected_read_buf = hex_literal::hex!(
@@ -1181,6 +1780,7 @@ mod tests {
                     ResponseSeq::Icmp(ResponseSeqIcmp {
                         identifier,
                         sequence,
+                        ..
                     }),
                 ..
             },
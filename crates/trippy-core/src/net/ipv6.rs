@@ -1,16 +1,18 @@
-use crate::config::IcmpExtensionParseMode;
+use crate::config::{IcmpExtensionParseMode, PayloadStrategy};
 use crate::error::{Error, ErrorKind, Result};
 use crate::net::channel::MAX_PACKET_SIZE;
-use crate::net::common::ErrorMapper;
+use crate::net::common::{checksum_word, checksum_word_offset, min_icmp_payload_size, ErrorMapper};
+use crate::net::extension::quoted_len;
 use crate::net::socket::{Socket, SocketError};
+use crate::net::stamp::{read_stamp, write_stamp};
 use crate::probe::{
-    Extensions, IcmpPacketCode, Probe, Response, ResponseData, ResponseSeq, ResponseSeqIcmp,
-    ResponseSeqTcp, ResponseSeqUdp,
+    Extensions, IcmpPacketCode, PayloadStamp, Probe, Response, ResponseData, ResponseSeq,
+    ResponseSeqIcmp, ResponseSeqTcp, ResponseSeqUdp,
 };
-use crate::types::{PacketSize, PayloadPattern, Sequence, TraceId};
+use crate::types::{FlowLabel, PacketSize, PayloadPattern, Sequence, TraceId, TypeOfService};
 use crate::{Flags, Port, PrivilegeMode, Protocol};
 use std::io;
-use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr, SocketAddrV6};
 use std::time::SystemTime;
 use tracing::instrument;
 use trippy_packet::checksum::{icmp_ipv6_checksum, udp_ipv6_checksum};
@@ -52,11 +54,22 @@ const MAGIC: &[u8] = b"trippy";
 pub struct Ipv6 {
     pub src_addr: Ipv6Addr,
     pub dest_addr: Ipv6Addr,
+    pub dest_scope_id: u32,
     pub packet_size: PacketSize,
     pub payload_pattern: PayloadPattern,
     pub privilege_mode: PrivilegeMode,
+    /// The traffic class of probes.
+    pub tos: TypeOfService,
+    /// The flow label of probes, zero to leave it to the kernel.
+    pub flow_label: FlowLabel,
     pub protocol: Protocol,
     pub icmp_extension_mode: IcmpExtensionParseMode,
+    /// Use kernel receive timestamps.
+    pub kernel_recv_timestamps: bool,
+    /// Embed a stamp in the payload of ICMP probes.
+    pub stamped_payload: bool,
+    /// The strategy for the payload of ICMP probes.
+    pub payload_strategy: PayloadStrategy,
     pub initial_sequence: Sequence,
 }
 
@@ -65,17 +78,35 @@ impl Default for Ipv6 {
         Self {
             src_addr: Ipv6Addr::UNSPECIFIED,
             dest_addr: Ipv6Addr::UNSPECIFIED,
+            dest_scope_id: 0,
             packet_size: PacketSize(0),
             payload_pattern: PayloadPattern(0),
             privilege_mode: PrivilegeMode::Privileged,
+            tos: TypeOfService(0),
+            flow_label: FlowLabel(0),
             protocol: Protocol::Icmp,
             icmp_extension_mode: IcmpExtensionParseMode::Disabled,
+            kernel_recv_timestamps: false,
+            stamped_payload: false,
+            payload_strategy: PayloadStrategy::Pattern,
             initial_sequence: Sequence(0),
         }
     }
 }
 
 impl Ipv6 {
+    /// The remote `SocketAddr` for the target, including any scope id and flow label.
+    ///
+    /// The `flowinfo` is passed to the kernel as is, which expects it in network byte order.
+    const fn remote_addr(&self, port: u16) -> SocketAddr {
+        SocketAddr::V6(SocketAddrV6::new(
+            self.dest_addr,
+            port,
+            self.flow_label.0.to_be(),
+            self.dest_scope_id,
+        ))
+    }
+
     /// Dispatch an ICMP probe.
     #[instrument(skip(self, icmp_send_socket, probe))]
     pub fn dispatch_icmp_probe<S: Socket>(
@@ -88,16 +119,21 @@ impl Ipv6 {
         if !(MIN_PACKET_SIZE_ICMP..=MAX_PACKET_SIZE).contains(&packet_size) {
             return Err(Error::InvalidPacketSize(packet_size));
         }
+        if icmp_payload_size(packet_size)
+            < min_icmp_payload_size(self.stamped_payload, self.payload_strategy)
+        {
+            return Err(Error::InvalidPacketSize(packet_size));
+        }
         let echo_request = self.make_echo_request_icmp_packet(
             &mut icmp_buf,
             probe.identifier,
             probe.sequence,
+            probe.sent,
             icmp_payload_size(packet_size),
         )?;
         icmp_send_socket.set_unicast_hops_v6(probe.ttl.0)?;
-        let remote_addr = SocketAddr::new(IpAddr::V6(self.dest_addr), 0);
-        icmp_send_socket.send_to(echo_request.packet(), remote_addr)?;
-        Ok(())
+        let remote_addr = self.remote_addr(0);
+        Self::send_raw_packet(icmp_send_socket, echo_request.packet(), remote_addr)
     }
 
     /// Dispatch a UDP probe.
@@ -152,15 +188,28 @@ impl Ipv6 {
         // Note that we set the port to be 0 in the remote `SocketAddr` as the target port is
         // encoded in the `UDP` packet.  If we (redundantly) set the target port here then
         // the send_to will fail with `EINVAL`.
-        let remote_addr = SocketAddr::new(IpAddr::V6(self.dest_addr), 0);
-        udp_send_socket.send_to(udp.packet(), remote_addr)?;
-        Ok(())
+        let remote_addr = self.remote_addr(0);
+        Self::send_raw_packet(udp_send_socket, udp.packet(), remote_addr)
+    }
+
+    /// Send a packet using a raw socket.
+    ///
+    /// Some errors are mapped to `Error::ProbeFailed` such that they are not considered fatal.
+    fn send_raw_packet<S: Socket>(
+        raw_send_socket: &mut S,
+        buf: &[u8],
+        remote_addr: SocketAddr,
+    ) -> Result<()> {
+        raw_send_socket
+            .send_to(buf, remote_addr)
+            .map_err(Error::IoError)
+            .map_err(|err| ErrorMapper::probe_failed(err, WOULD_BLOCK_KIND))
     }
 
     #[instrument(skip(self, probe))]
     fn dispatch_udp_probe_non_raw<S: Socket>(&self, probe: Probe, payload: &[u8]) -> Result<()> {
         let local_addr = SocketAddr::new(IpAddr::V6(self.src_addr), probe.src_port.0);
-        let remote_addr = SocketAddr::new(IpAddr::V6(self.dest_addr), probe.dest_port.0);
+        let remote_addr = self.remote_addr(probe.dest_port.0);
         let mut socket = S::new_udp_send_socket_ipv6(false)?;
         socket
             .bind(local_addr)
@@ -168,6 +217,10 @@ impl Ipv6 {
             .or_else(ErrorMapper::in_progress)
             .map_err(|err| ErrorMapper::addr_in_use(err, local_addr))?;
         socket.set_unicast_hops_v6(probe.ttl.0)?;
+        socket.set_tclass_v6(u32::from(self.tos.0))?;
+        if self.flow_label != FlowLabel::default() {
+            socket.set_flow_label_v6(self.dest_addr, self.flow_label.0)?;
+        }
         socket.send_to(payload, remote_addr)?;
         Ok(())
     }
@@ -183,7 +236,8 @@ impl Ipv6 {
             .or_else(ErrorMapper::in_progress)
             .map_err(|err| ErrorMapper::addr_in_use(err, local_addr))?;
         socket.set_unicast_hops_v6(probe.ttl.0)?;
-        let remote_addr = SocketAddr::new(IpAddr::V6(self.dest_addr), probe.dest_port.0);
+        socket.set_tclass_v6(u32::from(self.tos.0))?;
+        let remote_addr = self.remote_addr(probe.dest_port.0);
         socket
             .connect(remote_addr)
             .map_err(Error::IoError)
@@ -196,14 +250,22 @@ impl Ipv6 {
     #[instrument(skip(self, recv_socket))]
     pub fn recv_icmp_probe<S: Socket>(&self, recv_socket: &mut S) -> Result<Option<Response>> {
         let mut buf = [0_u8; MAX_PACKET_SIZE];
-        match recv_socket.recv_from(&mut buf) {
-            Ok((bytes_read, addr)) => {
+        let received = if self.kernel_recv_timestamps {
+            recv_socket.recv_from_timestamped(&mut buf)
+        } else {
+            recv_socket
+                .recv_from(&mut buf)
+                .map(|(bytes_read, addr)| (bytes_read, addr, None))
+        };
+        match received {
+            Ok((bytes_read, addr, timestamp)) => {
+                let recv = timestamp.unwrap_or_else(SystemTime::now);
                 let icmp_v6 = IcmpPacket::new_view(&buf[..bytes_read])?;
                 let src_addr = match addr.as_ref().ok_or(Error::MissingAddr)? {
                     SocketAddr::V6(addr) => addr.ip(),
                     SocketAddr::V4(_) => panic!(),
                 };
-                Ok(self.extract_probe_resp(&icmp_v6, *src_addr)?)
+                Ok(self.extract_probe_resp(&icmp_v6, *src_addr, recv)?)
             }
             Err(err) => match err.kind() {
                 ErrorKind::Std(io::ErrorKind::WouldBlock) => Ok(None),
@@ -212,6 +274,47 @@ impl Ipv6 {
         }
     }
 
+    /// Receive a batch of ICMP probe responses.
+    ///
+    /// Up to `count` packets are received using a single call to `Socket::recv_from_many` and the
+    /// responses are returned in the order in which they were received.
+    ///
+    /// Each response is stamped with the kernel receive timestamp of the packet, if available,
+    /// otherwise all responses in the batch are stamped with the time at which the batch was
+    /// received.
+    #[instrument(skip(self, recv_socket))]
+    pub fn recv_icmp_probes<S: Socket>(
+        &self,
+        recv_socket: &mut S,
+        count: usize,
+    ) -> Result<Vec<Response>> {
+        let mut bufs = vec![[0_u8; MAX_PACKET_SIZE]; count];
+        let mut slices = bufs.iter_mut().map(|buf| &mut buf[..]).collect::<Vec<_>>();
+        let received = match recv_socket.recv_from_many(&mut slices) {
+            Ok(received) => received,
+            Err(err) => {
+                return match err.kind() {
+                    ErrorKind::Std(io::ErrorKind::WouldBlock) => Ok(vec![]),
+                    _ => Err(Error::IoError(err)),
+                }
+            }
+        };
+        let now = SystemTime::now();
+        let mut responses = Vec::with_capacity(received.len());
+        for ((bytes_read, addr, timestamp), buf) in received.into_iter().zip(&bufs) {
+            let icmp_v6 = IcmpPacket::new_view(&buf[..bytes_read])?;
+            let src_addr = match addr.as_ref().ok_or(Error::MissingAddr)? {
+                SocketAddr::V6(addr) => addr.ip(),
+                SocketAddr::V4(_) => panic!(),
+            };
+            let recv = timestamp.unwrap_or(now);
+            if let Some(resp) = self.extract_probe_resp(&icmp_v6, *src_addr, recv)? {
+                responses.push(resp);
+            }
+        }
+        Ok(responses)
+    }
+
     /// Receive a TCP probe.
     #[instrument(skip(self, tcp_socket))]
     pub fn recv_tcp_socket<S: Socket>(
@@ -233,6 +336,8 @@ impl Ipv6 {
                     SystemTime::now(),
                     addr,
                     resp_seq,
+                    None,
+                    None,
                 ))));
             }
             Some(err) => match err {
@@ -241,12 +346,14 @@ impl Ipv6 {
                         SystemTime::now(),
                         IpAddr::V6(self.dest_addr),
                         resp_seq,
+                        None,
+                        None,
                     ))));
                 }
                 SocketError::HostUnreachable => {
                     let error_addr = tcp_socket.icmp_error_info()?;
                     return Ok(Some(Response::TimeExceeded(
-                        ResponseData::new(SystemTime::now(), error_addr, resp_seq),
+                        ResponseData::new(SystemTime::now(), error_addr, resp_seq, None, None),
                         IcmpPacketCode(1),
                         None,
                     )));
@@ -261,8 +368,8 @@ impl Ipv6 {
         &self,
         icmp_v6: &IcmpPacket<'_>,
         src: Ipv6Addr,
+        recv: SystemTime,
     ) -> Result<Option<Response>> {
-        let recv = SystemTime::now();
         let ip = IpAddr::V6(src);
         let icmp_type = icmp_v6.get_icmp_type();
         let icmp_code = icmp_v6.get_icmp_code();
@@ -281,9 +388,14 @@ impl Ipv6 {
                             (ipv6, None)
                         }
                     };
+                    let quoted_len = Some(quoted_len(
+                        packet.payload(),
+                        packet.payload_raw(),
+                        packet.extension(),
+                    ));
                     self.extract_probe_resp_seq(&nested_ipv6)?.map(|resp_seq| {
                         Response::TimeExceeded(
-                            ResponseData::new(recv, ip, resp_seq),
+                            ResponseData::new(recv, ip, resp_seq, None, quoted_len),
                             IcmpPacketCode(icmp_code.0),
                             extension,
                         )
@@ -301,9 +413,14 @@ impl Ipv6 {
                     }
                     IcmpExtensionParseMode::Disabled => None,
                 };
+                let quoted_len = Some(quoted_len(
+                    packet.payload(),
+                    packet.payload_raw(),
+                    packet.extension(),
+                ));
                 self.extract_probe_resp_seq(&nested_ipv6)?.map(|resp_seq| {
                     Response::DestinationUnreachable(
-                        ResponseData::new(recv, ip, resp_seq),
+                        ResponseData::new(recv, ip, resp_seq, None, quoted_len),
                         IcmpPacketCode(icmp_code.0),
                         extension,
                     )
@@ -314,9 +431,10 @@ impl Ipv6 {
                     let packet = EchoReplyPacket::new_view(icmp_v6.packet())?;
                     let id = packet.get_identifier();
                     let seq = packet.get_sequence();
-                    let resp_seq = ResponseSeq::Icmp(ResponseSeqIcmp::new(id, seq));
+                    let stamp = self.extract_stamp(packet.payload());
+                    let resp_seq = ResponseSeq::Icmp(ResponseSeqIcmp::new(id, seq, stamp));
                     Some(Response::EchoReply(
-                        ResponseData::new(recv, ip, resp_seq),
+                        ResponseData::new(recv, ip, resp_seq, None, None),
                         IcmpPacketCode(icmp_code.0),
                     ))
                 }
@@ -329,9 +447,10 @@ impl Ipv6 {
     fn extract_probe_resp_seq(&self, ipv6: &Ipv6Packet<'_>) -> Result<Option<ResponseSeq>> {
         Ok(match (self.protocol, ipv6.get_next_header()) {
             (Protocol::Icmp, IpProtocol::IcmpV6) => {
-                let (identifier, sequence) = extract_echo_request(ipv6)?;
+                let (identifier, sequence, payload) = extract_echo_request(ipv6)?;
+                let stamp = self.extract_stamp(payload);
                 Some(ResponseSeq::Icmp(ResponseSeqIcmp::new(
-                    identifier, sequence,
+                    identifier, sequence, stamp,
                 )))
             }
             (Protocol::Udp, IpProtocol::Udp) => {
@@ -389,19 +508,43 @@ impl Ipv6 {
     }
 
     /// Create an ICMP `EchoRequest` packet.
+    ///
+    /// If the payload strategy is `PayloadStrategy::ConstantChecksum` then the last 16-bit aligned
+    /// word of the payload is set such that the checksum is that of a packet with a sequence number
+    /// of zero and a payload of only the payload pattern.
     fn make_echo_request_icmp_packet<'a>(
         &self,
         icmp_buf: &'a mut [u8],
         identifier: TraceId,
         sequence: Sequence,
+        sent: SystemTime,
         payload_size: usize,
     ) -> Result<EchoRequestPacket<'a>> {
-        let payload_buf = [self.payload_pattern.0; MAX_ICMP_PAYLOAD_BUF];
+        let mut payload_buf = [self.payload_pattern.0; MAX_ICMP_PAYLOAD_BUF];
+        if self.stamped_payload {
+            let stamp = PayloadStamp::new(identifier.0, sequence.0, sent);
+            write_stamp(&mut payload_buf[..payload_size], stamp);
+        }
         let packet_size = IcmpPacket::minimum_packet_size() + payload_size;
         let mut icmp = EchoRequestPacket::new(&mut icmp_buf[..packet_size])?;
         icmp.set_icmp_type(IcmpType::EchoRequest);
         icmp.set_icmp_code(IcmpCode(0));
         icmp.set_identifier(identifier.0);
+        if self.payload_strategy == PayloadStrategy::ConstantChecksum {
+            let pattern = [self.payload_pattern.0; MAX_ICMP_PAYLOAD_BUF];
+            icmp.set_payload(&pattern[..payload_size]);
+            icmp.set_sequence(0);
+            let target = icmp_ipv6_checksum(icmp.packet(), self.src_addr, self.dest_addr);
+            let offset = checksum_word_offset(payload_size);
+            payload_buf[offset..offset + 2].fill(0);
+            icmp.set_payload(&payload_buf[..payload_size]);
+            icmp.set_sequence(sequence.0);
+            let word = checksum_word(
+                icmp_ipv6_checksum(icmp.packet(), self.src_addr, self.dest_addr),
+                target,
+            );
+            payload_buf[offset..offset + 2].copy_from_slice(&word.to_be_bytes());
+        }
         icmp.set_payload(&payload_buf[..payload_size]);
         icmp.set_sequence(sequence.0);
         icmp.set_checksum(icmp_ipv6_checksum(
@@ -411,8 +554,19 @@ impl Ipv6 {
         ));
         Ok(icmp)
     }
+
+    /// Read the stamp from the payload of an ICMP probe, if enabled.
+    fn extract_stamp(&self, payload: &[u8]) -> Option<PayloadStamp> {
+        if self.stamped_payload {
+            read_stamp(payload)
+        } else {
+            None
+        }
+    }
 }
 
+const WOULD_BLOCK_KIND: ErrorKind = ErrorKind::Std(io::ErrorKind::WouldBlock);
+
 const fn icmp_payload_size(packet_size: usize) -> usize {
     let ip_header_size = Ipv6Packet::minimum_packet_size();
     let icmp_header_size = IcmpPacket::minimum_packet_size();
@@ -425,11 +579,12 @@ const fn udp_payload_size(packet_size: usize) -> usize {
     packet_size - udp_header_size - ip_header_size
 }
 
-fn extract_echo_request(ipv6: &Ipv6Packet<'_>) -> Result<(u16, u16)> {
+fn extract_echo_request<'a>(ipv6: &'a Ipv6Packet<'a>) -> Result<(u16, u16, &'a [u8])> {
     let echo_request_packet = EchoRequestPacket::new_view(ipv6.payload())?;
     Ok((
         echo_request_packet.get_identifier(),
         echo_request_packet.get_sequence(),
+        &ipv6.payload()[EchoRequestPacket::minimum_packet_size()..],
     ))
 }
 
@@ -475,16 +630,41 @@ fn udp_payload_has_magic_prefix(ipv6: &Ipv6Packet<'_>) -> Result<bool> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error::IoResult;
-    use crate::mocket_recv_from;
+    use crate::error::{IoError, IoResult};
     use crate::net::socket::MockSocket;
+    use crate::{mocket_recv_from, mocket_recv_from_timestamped};
     use crate::{Flags, Port, RoundId, TimeToLive};
     use mockall::predicate;
     use std::str::FromStr;
     use std::sync::Mutex;
+    use std::time::Duration;
 
     static MTX: Mutex<()> = Mutex::new(());
 
+    // Test dispatching an IPv6/ICMP probe for which the send would block.
+    #[test]
+    fn test_dispatch_icmp_probe_would_block() -> anyhow::Result<()> {
+        let probe = make_icmp_probe();
+        let mut mocket = MockSocket::new();
+        mocket.expect_send_to().times(1).returning(|_, addr| {
+            let err = io::Error::from(io::ErrorKind::WouldBlock);
+            Err(IoError::SendTo(err, addr))
+        });
+        mocket
+            .expect_set_unicast_hops_v6()
+            .times(1)
+            .returning(|_| Ok(()));
+        let ipv6 = Ipv6 {
+            src_addr: Ipv6Addr::from_str("fd7a:115c:a1e0:ab12:4843:cd96:6263:82a")?,
+            dest_addr: Ipv6Addr::from_str("2a00:1450:4009:815::200e")?,
+            packet_size: PacketSize(48),
+            ..Default::default()
+        };
+        let err = ipv6.dispatch_icmp_probe(&mut mocket, probe).unwrap_err();
+        assert!(matches!(err, Error::ProbeFailed(_)));
+        Ok(())
+    }
+
     // Test dispatching an IPv6/ICMP probe.
     #[test]
     fn test_dispatch_icmp_probe_no_payload() -> anyhow::Result<()> {
@@ -521,6 +701,37 @@ mod tests {
         Ok(())
     }
 
+    // Test dispatching an IPv6/ICMP probe to a link-local target with a scope id.
+    #[test]
+    fn test_dispatch_icmp_probe_link_local_scope_id() -> anyhow::Result<()> {
+        let probe = make_icmp_probe();
+        let src_addr = Ipv6Addr::from_str("fe80::2")?;
+        let dest_addr = Ipv6Addr::from_str("fe80::1")?;
+        let packet_size = PacketSize(48);
+        let expected_send_to_addr = SocketAddr::V6(SocketAddrV6::new(dest_addr, 0, 0, 3));
+
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_send_to()
+            .with(predicate::always(), predicate::eq(expected_send_to_addr))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mocket
+            .expect_set_unicast_hops_v6()
+            .times(1)
+            .with(predicate::eq(10))
+            .returning(|_| Ok(()));
+        let ipv6 = Ipv6 {
+            src_addr,
+            dest_addr,
+            dest_scope_id: 3,
+            packet_size,
+            ..Default::default()
+        };
+        ipv6.dispatch_icmp_probe(&mut mocket, probe)?;
+        Ok(())
+    }
+
     #[test]
     fn test_dispatch_icmp_probe_with_payload() -> anyhow::Result<()> {
         let probe = make_icmp_probe();
@@ -561,6 +772,59 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_make_echo_request_icmp_packet_constant_checksum() -> anyhow::Result<()> {
+        let ipv6 = Ipv6 {
+            src_addr: Ipv6Addr::from_str("fd7a:115c:a1e0:ab12:4843:cd96:6263:82a")?,
+            dest_addr: Ipv6Addr::from_str("2a00:1450:4009:815::200e")?,
+            payload_pattern: PayloadPattern(0xff),
+            stamped_payload: true,
+            payload_strategy: PayloadStrategy::ConstantChecksum,
+            ..Default::default()
+        };
+        let mut buf1 = [0_u8; MAX_ICMP_PACKET_BUF];
+        let mut buf2 = [0_u8; MAX_ICMP_PACKET_BUF];
+        let sent1 = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let sent2 = SystemTime::UNIX_EPOCH + Duration::from_secs(2);
+        let echo1 = ipv6.make_echo_request_icmp_packet(
+            &mut buf1,
+            TraceId(1234),
+            Sequence(33434),
+            sent1,
+            21,
+        )?;
+        let echo2 = ipv6.make_echo_request_icmp_packet(
+            &mut buf2,
+            TraceId(1234),
+            Sequence(33435),
+            sent2,
+            21,
+        )?;
+        assert_ne!(echo1.packet(), echo2.packet());
+        assert_eq!(echo1.get_checksum(), echo2.get_checksum());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dispatch_icmp_probe_constant_checksum_invalid_packet_size() -> anyhow::Result<()> {
+        let probe = make_icmp_probe();
+        let src_addr = Ipv6Addr::from_str("fd7a:115c:a1e0:ab12:4843:cd96:6263:82a")?;
+        let dest_addr = Ipv6Addr::from_str("2a00:1450:4009:815::200e")?;
+        let packet_size = PacketSize(65);
+        let mut mocket = MockSocket::new();
+        let ipv6 = Ipv6 {
+            src_addr,
+            dest_addr,
+            packet_size,
+            stamped_payload: true,
+            payload_strategy: PayloadStrategy::ConstantChecksum,
+            ..Default::default()
+        };
+        let err = ipv6.dispatch_icmp_probe(&mut mocket, probe).unwrap_err();
+        assert!(matches!(err, Error::InvalidPacketSize(65)));
+        Ok(())
+    }
+
     #[test]
     fn test_dispatch_icmp_probe_invalid_packet_size_low() -> anyhow::Result<()> {
         let probe = make_icmp_probe();
@@ -821,6 +1085,12 @@ mod tests {
                 .with(predicate::eq(expected_set_unicast_hops_v6))
                 .returning(|_| Ok(()));
 
+            mocket
+                .expect_set_tclass_v6()
+                .with(predicate::eq(0))
+                .times(1)
+                .returning(|_| Ok(()));
+
             mocket
                 .expect_send_to()
                 .with(
@@ -846,6 +1116,37 @@ mod tests {
         Ok(())
     }
 
+    // Test dispatching an IPv6/ICMP probe with a flow label.
+    #[test]
+    fn test_dispatch_icmp_probe_flow_label() -> anyhow::Result<()> {
+        let probe = make_icmp_probe();
+        let src_addr = Ipv6Addr::from_str("fd7a:115c:a1e0:ab12:4843:cd96:6263:82a")?;
+        let dest_addr = Ipv6Addr::from_str("2a00:1450:4009:815::200e")?;
+        let expected_send_to_addr =
+            SocketAddr::V6(SocketAddrV6::new(dest_addr, 0, 0x12345_u32.to_be(), 0));
+
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_send_to()
+            .with(predicate::always(), predicate::eq(expected_send_to_addr))
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mocket
+            .expect_set_unicast_hops_v6()
+            .times(1)
+            .with(predicate::eq(10))
+            .returning(|_| Ok(()));
+        let ipv6 = Ipv6 {
+            src_addr,
+            dest_addr,
+            packet_size: PacketSize(48),
+            flow_label: FlowLabel(0x12345),
+            ..Default::default()
+        };
+        ipv6.dispatch_icmp_probe(&mut mocket, probe)?;
+        Ok(())
+    }
+
     #[test]
     fn test_dispatch_udp_probe_classic_unprivileged_with_payload() -> anyhow::Result<()> {
         let _m = MTX.lock();
@@ -878,6 +1179,12 @@ mod tests {
                 .with(predicate::eq(expected_set_unicast_hops_v6))
                 .returning(|_| Ok(()));
 
+            mocket
+                .expect_set_tclass_v6()
+                .with(predicate::eq(0))
+                .times(1)
+                .returning(|_| Ok(()));
+
             mocket
                 .expect_send_to()
                 .with(
@@ -976,6 +1283,12 @@ mod tests {
                 .with(predicate::eq(expected_set_unicast_hops_v6))
                 .returning(|_| Ok(()));
 
+            mocket
+                .expect_set_tclass_v6()
+                .with(predicate::eq(0))
+                .times(1)
+                .returning(|_| Ok(()));
+
             mocket
                 .expect_connect()
                 .with(predicate::eq(expected_connect_addr))
@@ -1027,6 +1340,7 @@ mod tests {
                     ResponseSeq::Icmp(ResponseSeqIcmp {
                         identifier,
                         sequence,
+                        ..
                     }),
                 ..
             },
@@ -1042,6 +1356,42 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_recv_icmp_probe_echo_reply_kernel_timestamp() -> anyhow::Result<()> {
+        let recv_from_addr = IpAddr::V6(Ipv6Addr::from_str("2604:a880:ffff:6:1::41c").unwrap());
+        let expected_recv_from_buf = hex_literal::hex!(
+            "
+            81 00 52 c0 55 b9 81 26 00 00 00 00 00 00 00 00
+            00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            00 00 00 00 00 00 00 00 00 00 00 00
+           "
+        );
+        let expected_recv_from_addr = SocketAddr::new(recv_from_addr, 0);
+        let timestamp = SystemTime::UNIX_EPOCH + Duration::from_nanos(1_700_000_000_123_456_789);
+        let mut mocket = MockSocket::new();
+        mocket
+            .expect_recv_from_timestamped()
+            .times(1)
+            .returning(mocket_recv_from_timestamped!(
+                expected_recv_from_buf,
+                Some(expected_recv_from_addr),
+                Some(timestamp)
+            ));
+        let ipv6 = Ipv6 {
+            protocol: Protocol::Icmp,
+            icmp_extension_mode: IcmpExtensionParseMode::Disabled,
+            kernel_recv_timestamps: true,
+            ..Default::default()
+        };
+        let resp = ipv6.recv_icmp_probe(&mut mocket)?.unwrap();
+        let Response::EchoReply(ResponseData { addr, recv, .. }, _) = resp else {
+            panic!("expected EchoReply")
+        };
+        assert_eq!(recv_from_addr, addr);
+        assert_eq!(timestamp, recv);
+        Ok(())
+    }
+
     #[test]
     fn test_recv_icmp_probe_time_exceeded_icmp_no_extensions() -> anyhow::Result<()> {
         let recv_from_addr = IpAddr::V6(Ipv6Addr::from_str("2604:a880:ffff:6:1::41c").unwrap());
@@ -1078,6 +1428,7 @@ mod tests {
                     ResponseSeq::Icmp(ResponseSeqIcmp {
                         identifier,
                         sequence,
+                        ..
                     }),
                 ..
             },
@@ -1131,6 +1482,7 @@ mod tests {
                     ResponseSeq::Icmp(ResponseSeqIcmp {
                         identifier,
                         sequence,
+                        ..
                     }),
                 ..
             },
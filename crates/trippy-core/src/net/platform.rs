@@ -28,6 +28,11 @@ pub trait Platform {
     /// is selected and returned.
     fn lookup_interface_addr(addr: IpAddr, name: &str) -> Result<IpAddr>;
 
+    /// Lookup the index of an interface.
+    ///
+    /// This is used as the scope id for link-local IPv6 addresses.
+    fn lookup_interface_index(name: &str) -> Result<u32>;
+
     /// Discover a local `IpAddr` which can route to the target address.
     fn discover_local_addr(target_addr: IpAddr, port: u16) -> Result<IpAddr>;
 }
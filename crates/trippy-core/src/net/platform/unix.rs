@@ -11,6 +11,9 @@ impl Platform for PlatformImpl {
     fn lookup_interface_addr(addr: IpAddr, name: &str) -> Result<IpAddr> {
         address::lookup_interface_addr(addr, name)
     }
+    fn lookup_interface_index(name: &str) -> Result<u32> {
+        address::lookup_interface_index(name)
+    }
     fn discover_local_addr(target_addr: IpAddr, port: u16) -> Result<IpAddr> {
         address::discover_local_addr(target_addr, port)
     }
@@ -137,6 +140,11 @@ mod address {
             .ok_or_else(|| Error::UnknownInterface(name.to_string()))
     }
 
+    #[instrument(ret)]
+    pub fn lookup_interface_index(name: &str) -> Result<u32> {
+        nix::net::if_::if_nametoindex(name).map_err(|_| Error::UnknownInterface(name.to_string()))
+    }
+
     // Note that no packets are transmitted by this method.
     #[instrument(ret)]
     pub fn discover_local_addr(target_addr: IpAddr, port: u16) -> Result<IpAddr> {
@@ -156,18 +164,27 @@ mod socket {
     use itertools::Itertools;
     use nix::{
         sys::select::FdSet,
+        sys::socket::{recvmsg, setsockopt, sockopt},
+        sys::socket::{ControlMessageOwned, MsgFlags, SockaddrStorage},
         sys::time::{TimeVal, TimeValLike},
         Error,
     };
     use socket2::{Domain, Protocol, SockAddr, Type};
     use std::io;
-    use std::io::Read;
+    use std::io::{IoSliceMut, Read};
     use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
     use std::net::{Shutdown, SocketAddr};
-    use std::os::fd::AsFd;
-    use std::time::Duration;
+    use std::os::fd::{AsFd, AsRawFd};
+    use std::time::{Duration, SystemTime};
     use tracing::instrument;
 
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    use crate::net::socket::RecvDatagram;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    use nix::sys::socket::{recvmmsg, sendmmsg, MultiHeaders};
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    use std::io::IoSlice;
+
     #[allow(clippy::unnecessary_wraps)]
     #[instrument]
     pub fn startup() -> Result<()> {
@@ -366,6 +383,40 @@ mod socket {
                 .map_err(|err| IoError::Other(err, IoOperation::SetUnicastHopsV6))
         }
         #[instrument(skip(self))]
+        fn set_tclass_v6(&mut self, tclass: u32) -> IoResult<()> {
+            self.inner
+                .set_tclass_v6(tclass)
+                .map_err(|err| IoError::Other(err, IoOperation::SetTclassV6))
+        }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        #[instrument(skip(self))]
+        fn set_flow_label_v6(&mut self, dest_addr: Ipv6Addr, flow_label: u32) -> IoResult<()> {
+            self.inner
+                .set_flow_label_v6(dest_addr, flow_label)
+                .map_err(|err| IoError::Other(err, IoOperation::SetFlowLabelV6))
+        }
+        /// Setting the flow label is only supported on Linux.
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        #[instrument(skip(self))]
+        fn set_flow_label_v6(&mut self, _dest_addr: Ipv6Addr, _flow_label: u32) -> IoResult<()> {
+            Err(IoError::Other(
+                io::Error::from(io::ErrorKind::Unsupported),
+                IoOperation::SetFlowLabelV6,
+            ))
+        }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        #[instrument(skip(self))]
+        fn set_recv_timestamps(&mut self, enabled: bool) -> IoResult<()> {
+            setsockopt(&self.inner, sockopt::ReceiveTimestampns, &enabled)
+                .map_err(|err| IoError::Other(err.into(), IoOperation::SetRecvTimestamps))
+        }
+        #[cfg(not(any(target_os = "linux", target_os = "android")))]
+        #[instrument(skip(self))]
+        fn set_recv_timestamps(&mut self, enabled: bool) -> IoResult<()> {
+            setsockopt(&self.inner, sockopt::ReceiveTimestamp, &enabled)
+                .map_err(|err| IoError::Other(err.into(), IoOperation::SetRecvTimestamps))
+        }
+        #[instrument(skip(self))]
         fn connect(&mut self, address: SocketAddr) -> IoResult<()> {
             tracing::debug!(?address);
             self.inner
@@ -380,6 +431,34 @@ mod socket {
                 .map_err(|err| IoError::SendTo(err, addr))?;
             Ok(())
         }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        #[instrument(skip(self, bufs), fields(count = bufs.len()))]
+        fn send_to_many(&mut self, bufs: &[(&[u8], SocketAddr)]) -> IoResult<usize> {
+            let Some(&(_, first_addr)) = bufs.first() else {
+                return Ok(0);
+            };
+            let slices = bufs
+                .iter()
+                .map(|(buf, _)| [IoSlice::new(buf)])
+                .collect::<Vec<_>>();
+            let addrs = bufs
+                .iter()
+                .map(|(_, addr)| Some(SockaddrStorage::from(*addr)))
+                .collect::<Vec<_>>();
+            let mut headers = MultiHeaders::<SockaddrStorage>::preallocate(bufs.len(), None);
+            let sent = sendmmsg(
+                self.inner.as_raw_fd(),
+                &mut headers,
+                &slices,
+                &addrs,
+                [],
+                MsgFlags::empty(),
+            )
+            .map_err(|err| IoError::SendTo(err.into(), first_addr))?
+            .count();
+            tracing::debug!(sent);
+            Ok(sent)
+        }
         #[instrument(skip(self))]
         fn is_readable(&mut self, timeout: Duration) -> IoResult<bool> {
             let mut read = FdSet::new();
@@ -445,6 +524,67 @@ mod socket {
             );
             Ok(bytes_read)
         }
+        #[instrument(skip(self, buf), ret)]
+        fn recv_from_timestamped(
+            &mut self,
+            buf: &mut [u8],
+        ) -> IoResult<(usize, Option<SocketAddr>, Option<SystemTime>)> {
+            let mut iov = [IoSliceMut::new(buf)];
+            let mut cmsg_buf = nix::cmsg_space!(RecvTimestamp);
+            let msg = recvmsg::<SockaddrStorage>(
+                self.inner.as_raw_fd(),
+                &mut iov,
+                Some(&mut cmsg_buf),
+                MsgFlags::empty(),
+            )
+            .map_err(|err| IoError::Other(err.into(), IoOperation::RecvFrom))?;
+            let bytes_read = msg.bytes;
+            let addr = msg.address.as_ref().and_then(socket_addr);
+            let timestamp = msg
+                .cmsgs()
+                .into_iter()
+                .flatten()
+                .find_map(|cmsg| recv_timestamp(&cmsg));
+            tracing::debug!(
+                buf = format!("{:02x?}", buf[..bytes_read].iter().format(" ")),
+                bytes_read,
+                ?addr,
+                ?timestamp
+            );
+            Ok((bytes_read, addr, timestamp))
+        }
+        #[cfg(any(target_os = "linux", target_os = "android"))]
+        #[instrument(skip(self, bufs), fields(count = bufs.len()))]
+        fn recv_from_many(&mut self, bufs: &mut [&mut [u8]]) -> IoResult<Vec<RecvDatagram>> {
+            let mut slices = bufs
+                .iter_mut()
+                .map(|buf| [IoSliceMut::new(buf)])
+                .collect::<Vec<_>>();
+            let mut headers = MultiHeaders::<SockaddrStorage>::preallocate(
+                slices.len(),
+                Some(nix::cmsg_space!(RecvTimestamp)),
+            );
+            let received = recvmmsg(
+                self.inner.as_raw_fd(),
+                &mut headers,
+                &mut slices,
+                MsgFlags::MSG_DONTWAIT,
+                None,
+            )
+            .map_err(|err| IoError::Other(err.into(), IoOperation::RecvFrom))?
+            .map(|msg| {
+                let addr = msg.address.as_ref().and_then(socket_addr);
+                let timestamp = msg
+                    .cmsgs()
+                    .into_iter()
+                    .flatten()
+                    .find_map(|cmsg| recv_timestamp(&cmsg));
+                (msg.bytes, addr, timestamp)
+            })
+            .collect::<Vec<_>>();
+            tracing::debug!(received = received.len());
+            Ok(received)
+        }
         #[instrument(skip(self))]
         fn shutdown(&mut self) -> IoResult<()> {
             self.inner
@@ -487,6 +627,38 @@ mod socket {
         }
     }
 
+    /// The type of the kernel receive timestamp control message.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    type RecvTimestamp = nix::sys::time::TimeSpec;
+
+    /// The type of the kernel receive timestamp control message.
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    type RecvTimestamp = TimeVal;
+
+    /// Extract the kernel receive timestamp from a control message, if any.
+    fn recv_timestamp(cmsg: &ControlMessageOwned) -> Option<SystemTime> {
+        match cmsg {
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            ControlMessageOwned::ScmTimestampns(ts) => {
+                SystemTime::UNIX_EPOCH.checked_add(Duration::from(*ts))
+            }
+            ControlMessageOwned::ScmTimestamp(tv) => SystemTime::UNIX_EPOCH.checked_add(
+                Duration::from_micros(u64::try_from(tv.num_microseconds()).ok()?),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Convert a `SockaddrStorage` to a `SocketAddr`, if it is an IPv4 or IPv6 address.
+    fn socket_addr(addr: &SockaddrStorage) -> Option<SocketAddr> {
+        addr.as_sockaddr_in()
+            .map(|addr| SocketAddr::V4((*addr).into()))
+            .or_else(|| {
+                addr.as_sockaddr_in6()
+                    .map(|addr| SocketAddr::V6((*addr).into()))
+            })
+    }
+
     impl From<&io::Error> for ErrorKind {
         fn from(value: &io::Error) -> Self {
             if value.raw_os_error() == io::Error::from(Error::EINPROGRESS).raw_os_error() {
@@ -540,6 +712,88 @@ mod socket {
                 .map(|(size, addr)| (size, addr.as_socket()))
         }
     }
+
+    /// An extension trait to allow sending with a given `IPv6` flow label.
+    ///
+    /// Linux requires that a flow label is leased, via `IPV6_FLOWLABEL_MGR`, before it may be
+    /// sent and that sending the flow label is enabled, via `IPV6_FLOWINFO_SEND`.  Neither
+    /// `nix` nor `socket2` currently provide these options.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    trait FlowLabelV6 {
+        fn set_flow_label_v6(&self, dest_addr: Ipv6Addr, flow_label: u32) -> io::Result<()>;
+    }
+
+    /// The `struct in6_flowlabel_req` from `linux/in6.h`.
+    ///
+    /// The field names mirror those of `linux/in6.h`.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    #[allow(clippy::struct_field_names)]
+    #[repr(C)]
+    struct In6FlowlabelReq {
+        flr_dst: nix::libc::in6_addr,
+        flr_label: u32,
+        flr_action: u8,
+        flr_share: u8,
+        flr_flags: u16,
+        flr_expires: u16,
+        flr_linger: u16,
+        flr_pad: u32,
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const IPV6_FL_A_GET: u8 = 0;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const IPV6_FL_S_ANY: u8 = 255;
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const IPV6_FL_F_CREATE: u16 = 1;
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    impl FlowLabelV6 for socket2::Socket {
+        // Safety: the option values passed to `setsockopt` are valid for the given lengths and
+        // outlive the calls.
+        #![allow(unsafe_code)]
+        fn set_flow_label_v6(&self, dest_addr: Ipv6Addr, flow_label: u32) -> io::Result<()> {
+            let req = In6FlowlabelReq {
+                flr_dst: nix::libc::in6_addr {
+                    s6_addr: dest_addr.octets(),
+                },
+                flr_label: flow_label.to_be(),
+                flr_action: IPV6_FL_A_GET,
+                flr_share: IPV6_FL_S_ANY,
+                flr_flags: IPV6_FL_F_CREATE,
+                flr_expires: 0,
+                flr_linger: 0,
+                flr_pad: 0,
+            };
+            let enabled: nix::libc::c_int = 1;
+            let fd = self.as_raw_fd();
+            let res = unsafe {
+                nix::libc::setsockopt(
+                    fd,
+                    nix::libc::IPPROTO_IPV6,
+                    nix::libc::IPV6_FLOWLABEL_MGR,
+                    std::ptr::from_ref(&req).cast(),
+                    std::mem::size_of::<In6FlowlabelReq>() as nix::libc::socklen_t,
+                )
+            };
+            if res < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let res = unsafe {
+                nix::libc::setsockopt(
+                    fd,
+                    nix::libc::IPPROTO_IPV6,
+                    nix::libc::IPV6_FLOWINFO_SEND,
+                    std::ptr::from_ref(&enabled).cast(),
+                    std::mem::size_of::<nix::libc::c_int>() as nix::libc::socklen_t,
+                )
+            };
+            if res < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        }
+    }
 }
 
 pub use socket::{startup, SocketImpl};
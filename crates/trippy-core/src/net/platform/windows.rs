@@ -12,16 +12,16 @@ use std::mem::{size_of, zeroed};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::os::windows::prelude::AsRawSocket;
 use std::ptr::{addr_of, addr_of_mut, null_mut};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use tracing::instrument;
 use windows_sys::Win32::Foundation::{WAIT_FAILED, WAIT_TIMEOUT};
 use windows_sys::Win32::Networking::WinSock::{
     AF_INET, AF_INET6, FD_CONNECT, FD_WRITE, ICMP_ERROR_INFO, IN6_ADDR, IN6_ADDR_0, IN_ADDR,
-    IN_ADDR_0, IPPROTO_RAW, IPPROTO_TCP, SIO_ROUTING_INTERFACE_QUERY, SOCKADDR_IN, SOCKADDR_IN6,
-    SOCKADDR_IN6_0, SOCKADDR_STORAGE, SOCKET_ERROR, SOL_SOCKET, SO_ERROR, SO_PORT_SCALABILITY,
-    SO_REUSE_UNICASTPORT, TCP_FAIL_CONNECT_ON_ICMP_ERROR, TCP_ICMP_ERROR_INFO, WSABUF, WSADATA,
-    WSAEADDRNOTAVAIL, WSAECONNREFUSED, WSAEHOSTUNREACH, WSAEINPROGRESS, WSAENETUNREACH, WSAENOBUFS,
-    WSA_IO_INCOMPLETE, WSA_IO_PENDING,
+    IN_ADDR_0, IPPROTO_IPV6, IPPROTO_RAW, IPPROTO_TCP, IPV6_TCLASS, SIO_ROUTING_INTERFACE_QUERY,
+    SOCKADDR_IN, SOCKADDR_IN6, SOCKADDR_IN6_0, SOCKADDR_STORAGE, SOCKET_ERROR, SOL_SOCKET,
+    SO_ERROR, SO_PORT_SCALABILITY, SO_REUSE_UNICASTPORT, TCP_FAIL_CONNECT_ON_ICMP_ERROR,
+    TCP_ICMP_ERROR_INFO, WSABUF, WSADATA, WSAEADDRNOTAVAIL, WSAECONNREFUSED, WSAEHOSTUNREACH,
+    WSAEINPROGRESS, WSAENETUNREACH, WSAENOBUFS, WSA_IO_INCOMPLETE, WSA_IO_PENDING,
 };
 use windows_sys::Win32::System::IO::OVERLAPPED;
 
@@ -77,6 +77,10 @@ impl Platform for PlatformImpl {
         }
     }
 
+    fn lookup_interface_index(name: &str) -> Result<u32> {
+        lookup_interface_index(&Adapters::ipv6()?, name)
+    }
+
     fn discover_local_addr(target_addr: IpAddr, _port: u16) -> Result<IpAddr> {
         routing_interface_query(target_addr)
     }
@@ -444,6 +448,30 @@ impl Socket for SocketImpl {
             .map_err(|err| IoError::Other(err, IoOperation::SetUnicastHopsV6))
     }
 
+    #[instrument(skip(self))]
+    fn set_tclass_v6(&mut self, tclass: u32) -> IoResult<()> {
+        self.setsockopt_u32(IPPROTO_IPV6, IPV6_TCLASS as _, tclass)
+            .map_err(|err| IoError::Other(err, IoOperation::SetTclassV6))
+    }
+
+    /// Setting the flow label is not supported on Windows.
+    #[instrument(skip(self))]
+    fn set_flow_label_v6(&mut self, _dest_addr: Ipv6Addr, _flow_label: u32) -> IoResult<()> {
+        Err(IoError::Other(
+            StdIoError::from(StdErrorKind::Unsupported),
+            IoOperation::SetFlowLabelV6,
+        ))
+    }
+
+    /// Kernel receive timestamps are not supported on Windows.
+    #[instrument(skip(self))]
+    fn set_recv_timestamps(&mut self, _enabled: bool) -> IoResult<()> {
+        Err(IoError::Other(
+            StdIoError::from(StdErrorKind::Unsupported),
+            IoOperation::SetRecvTimestamps,
+        ))
+    }
+
     #[instrument(skip(self))]
     fn connect(&mut self, addr: SocketAddr) -> IoResult<()> {
         self.set_fail_connect_on_icmp_error(true)?;
@@ -525,6 +553,15 @@ impl Socket for SocketImpl {
         Ok(bytes_read)
     }
 
+    #[instrument(skip(self, buf), ret)]
+    fn recv_from_timestamped(
+        &mut self,
+        buf: &mut [u8],
+    ) -> IoResult<(usize, Option<SocketAddr>, Option<SystemTime>)> {
+        let (len, addr) = self.recv_from(buf)?;
+        Ok((len, addr, None))
+    }
+
     #[instrument(skip(self))]
     fn shutdown(&mut self) -> IoResult<()> {
         self.inner
@@ -768,6 +805,20 @@ fn lookup_interface_addr(adapters: &Adapters, name: &str) -> Result<IpAddr> {
         .ok_or_else(|| Error::UnknownInterface(name.to_string()))
 }
 
+#[instrument(skip(adapters), ret)]
+fn lookup_interface_index(adapters: &Adapters, name: &str) -> Result<u32> {
+    adapters
+        .iter()
+        .find_map(|addr| {
+            if addr.name.eq_ignore_ascii_case(name) {
+                Some(addr.index)
+            } else {
+                None
+            }
+        })
+        .ok_or_else(|| Error::UnknownInterface(name.to_string()))
+}
+
 mod adapter {
     use crate::error::{Error, Result};
     use crate::net::platform::windows::sockaddrptr_to_ipaddr;
@@ -852,6 +903,8 @@ mod adapter {
         pub name: String,
         /// The adapter `IpAddress`.
         pub addr: IpAddr,
+        /// The adapter IPv6 interface index.
+        pub index: u32,
     }
 
     /// An iterator for `Adapters` which yields `AdapterAddress`
@@ -891,10 +944,12 @@ mod adapter {
                         let sockaddr = socket_address.lpSockaddr;
                         sockaddrptr_to_ipaddr(sockaddr.cast()).ok()?
                     };
+                    let index = (*self.next).Ipv6IfIndex;
                     self.next = (*self.next).Next;
                     Some(AdapterAddress {
                         name: friendly_name,
                         addr,
+                        index,
                     })
                 }
             }
@@ -1,6 +1,6 @@
 use crate::error::IoResult as Result;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 #[cfg_attr(test, mockall::automock)]
 pub trait Socket
@@ -33,14 +33,55 @@ where
     fn set_reuse_port(&mut self, reuse: bool) -> Result<()>;
     fn set_header_included(&mut self, included: bool) -> Result<()>;
     fn set_unicast_hops_v6(&mut self, hops: u8) -> Result<()>;
+    /// Set the `IPv6` traffic class (`IPV6_TCLASS`).
+    fn set_tclass_v6(&mut self, tclass: u32) -> Result<()>;
+    /// Set the `IPv6` flow label of datagrams sent to `dest_addr`.
+    ///
+    /// The flow label must also be set in the `flowinfo` of the destination address of each
+    /// datagram sent.
+    fn set_flow_label_v6(&mut self, dest_addr: Ipv6Addr, flow_label: u32) -> Result<()>;
+    /// Enable or disable kernel receive timestamps.
+    fn set_recv_timestamps(&mut self, enabled: bool) -> Result<()>;
     fn connect(&mut self, address: SocketAddr) -> Result<()>;
     fn send_to(&mut self, buf: &[u8], addr: SocketAddr) -> Result<()>;
+    /// Send several datagrams, using a single system call where supported.
+    ///
+    /// Returns the number of datagrams sent, which may be fewer than requested if a send failed
+    /// after the first datagram was sent.  The default implementation sends each datagram in
+    /// turn.
+    #[allow(clippy::needless_lifetimes)] // required by mockall
+    fn send_to_many<'a>(&mut self, bufs: &[(&'a [u8], SocketAddr)]) -> Result<usize> {
+        for (i, (buf, addr)) in bufs.iter().enumerate() {
+            if let Err(err) = self.send_to(buf, *addr) {
+                return if i == 0 { Err(err) } else { Ok(i) };
+            }
+        }
+        Ok(bufs.len())
+    }
     /// Returns true if the socket becomes readable before the timeout, false otherwise.
     fn is_readable(&mut self, timeout: Duration) -> Result<bool>;
     /// Returns true if the socket is currently writeable, false otherwise.
     fn is_writable(&mut self) -> Result<bool>;
     fn recv_from(&mut self, buf: &mut [u8]) -> Result<(usize, Option<SocketAddr>)>;
     fn read(&mut self, buf: &mut [u8]) -> Result<usize>;
+    /// Receive from the socket, including the kernel receive timestamp if available.
+    fn recv_from_timestamped(
+        &mut self,
+        buf: &mut [u8],
+    ) -> Result<(usize, Option<SocketAddr>, Option<SystemTime>)>;
+    /// Receive several datagrams, using a single system call where supported.
+    ///
+    /// At most one datagram is received into each buffer and the number of bytes read, the source
+    /// address and the kernel receive timestamp, if available, are returned for each datagram
+    /// received.  Fewer datagrams than buffers may be received.  The default implementation
+    /// receives a single datagram.
+    #[allow(clippy::needless_lifetimes)] // required by mockall
+    fn recv_from_many<'a>(&mut self, bufs: &mut [&'a mut [u8]]) -> Result<Vec<RecvDatagram>> {
+        match bufs.first_mut() {
+            Some(buf) => Ok(vec![self.recv_from_timestamped(buf)?]),
+            None => Ok(vec![]),
+        }
+    }
     fn shutdown(&mut self) -> Result<()>;
     fn peer_addr(&mut self) -> Result<Option<SocketAddr>>;
     fn take_error(&mut self) -> Result<Option<SocketError>>;
@@ -48,6 +89,10 @@ where
     fn close(&mut self) -> Result<()>;
 }
 
+/// The number of bytes read, the source address and the kernel receive timestamp of a datagram
+/// received by `Socket::recv_from_many`.
+pub type RecvDatagram = (usize, Option<SocketAddr>, Option<SystemTime>);
+
 /// A socket error returned by `Socket::take_error`.
 #[derive(Debug)]
 pub enum SocketError {
@@ -78,4 +123,14 @@ pub mod tests {
             }
         };
     }
+
+    #[macro_export]
+    macro_rules! mocket_recv_from_timestamped {
+        ($packet: expr, $addr: expr, $timestamp: expr) => {
+            move |buf: &mut [u8]| -> IoResult<(usize, Option<SocketAddr>, Option<SystemTime>)> {
+                buf[..$packet.len()].copy_from_slice(&$packet);
+                Ok((buf.len(), $addr, $timestamp))
+            }
+        };
+    }
 }
@@ -26,6 +26,20 @@ impl SourceAddr {
         }
     }
 
+    /// Determine the scope id of the target `IpAddr`.
+    ///
+    /// Link-local IPv6 targets are scoped to the given interface.  All other
+    /// targets have a scope id of zero.
+    ///
+    /// The interface of a link-local target is validated by the
+    /// [`crate::Builder`] and so is assumed to be present here.
+    pub fn scope_id<P: Platform>(target_addr: IpAddr, interface: Option<&str>) -> Result<u32> {
+        match interface {
+            Some(interface) if is_link_local(target_addr) => P::lookup_interface_index(interface),
+            _ => Ok(0),
+        }
+    }
+
     /// Validate that we can bind to the source `IpAddr`.
     pub fn validate<S: Socket>(source_addr: IpAddr) -> Result<IpAddr> {
         let mut socket = match source_addr {
@@ -43,6 +57,15 @@ impl SourceAddr {
     }
 }
 
+/// Is the `IpAddr` an IPv6 unicast link-local address (`fe80::/10`)?
+#[must_use]
+pub const fn is_link_local(addr: IpAddr) -> bool {
+    match addr {
+        IpAddr::V6(addr) => (addr.segments()[0] & 0xffc0) == 0xfe80,
+        IpAddr::V4(_) => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,6 +169,45 @@ mod tests {
         assert_eq!(expected_src, src_addr);
     }
 
+    #[test]
+    fn test_scope_id_link_local() {
+        let _m = MTX.lock();
+
+        let target = IpAddr::from_str("fe80::1").unwrap();
+
+        let ctx = MockPlatform::lookup_interface_index_context();
+        ctx.expect()
+            .with(predicate::eq("eth0"))
+            .times(1)
+            .returning(|_| Ok(2));
+
+        let scope_id = SourceAddr::scope_id::<MockPlatform>(target, Some("eth0")).unwrap();
+        assert_eq!(2, scope_id);
+    }
+
+    #[test]
+    fn test_scope_id_link_local_no_interface() {
+        let target = IpAddr::from_str("fe80::1").unwrap();
+        let scope_id = SourceAddr::scope_id::<MockPlatform>(target, None).unwrap();
+        assert_eq!(0, scope_id);
+    }
+
+    #[test]
+    fn test_is_link_local() {
+        assert!(is_link_local(IpAddr::from_str("fe80::1").unwrap()));
+        assert!(is_link_local(IpAddr::from_str("febf::1").unwrap()));
+        assert!(!is_link_local(IpAddr::from_str("fec0::1").unwrap()));
+        assert!(!is_link_local(IpAddr::from_str("::1").unwrap()));
+        assert!(!is_link_local(IpAddr::from_str("169.254.0.1").unwrap()));
+    }
+
+    #[test]
+    fn test_scope_id_global() {
+        let target = IpAddr::from_str("2a00:1450:4009:815::200e").unwrap();
+        let scope_id = SourceAddr::scope_id::<MockPlatform>(target, Some("eth0")).unwrap();
+        assert_eq!(0, scope_id);
+    }
+
     #[test]
     fn test_validate_ipv4() {
         let _m = MTX.lock();
@@ -0,0 +1,83 @@
+use crate::probe::PayloadStamp;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// The marker which identifies a stamped payload.
+const MARKER: [u8; 4] = *b"TRPY";
+
+/// The size of a payload stamp.
+///
+/// A stamp is the marker (4 bytes), followed by the identifier (2 bytes), the
+/// sequence number (2 bytes) and the send time, in microseconds since the
+/// Unix epoch (8 bytes), all in network byte order.
+pub const STAMP_SIZE: usize = 16;
+
+/// Write a stamp to the start of a payload.
+///
+/// The payload must be at least `STAMP_SIZE` bytes.
+pub fn write_stamp(payload: &mut [u8], stamp: PayloadStamp) {
+    let sent_us = stamp
+        .sent
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |dur| u64::try_from(dur.as_micros()).unwrap_or(u64::MAX));
+    payload[0..4].copy_from_slice(&MARKER);
+    payload[4..6].copy_from_slice(&stamp.identifier.to_be_bytes());
+    payload[6..8].copy_from_slice(&stamp.sequence.to_be_bytes());
+    payload[8..16].copy_from_slice(&sent_us.to_be_bytes());
+}
+
+/// Read a stamp from the start of a payload.
+///
+/// Returns `None` if the payload is too short to hold a stamp, which may be
+/// the case if the original packet quoted in an ICMP error was truncated, or
+/// if the payload does not begin with the marker, or if the send time cannot
+/// be represented on this platform.
+pub fn read_stamp(payload: &[u8]) -> Option<PayloadStamp> {
+    if payload.len() < STAMP_SIZE || payload[0..4] != MARKER {
+        return None;
+    }
+    let identifier = u16::from_be_bytes([payload[4], payload[5]]);
+    let sequence = u16::from_be_bytes([payload[6], payload[7]]);
+    let sent_us = u64::from_be_bytes(core::array::from_fn(|i| payload[8 + i]));
+    let sent = UNIX_EPOCH.checked_add(Duration::from_micros(sent_us))?;
+    Some(PayloadStamp::new(identifier, sequence, sent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::SystemTime;
+
+    #[test]
+    fn test_write_read_stamp() {
+        let sent = UNIX_EPOCH + Duration::from_micros(1_700_000_000_123_456);
+        let stamp = PayloadStamp::new(1234, 33434, sent);
+        let mut payload = [0xaa; 20];
+        write_stamp(&mut payload, stamp);
+        assert_eq!(b"TRPY", &payload[0..4]);
+        assert_eq!([0xaa; 4], payload[16..20]);
+        assert_eq!(Some(stamp), read_stamp(&payload));
+    }
+
+    #[test]
+    fn test_read_stamp_truncated() {
+        let stamp = PayloadStamp::new(1234, 33434, SystemTime::UNIX_EPOCH);
+        let mut payload = [0; STAMP_SIZE];
+        write_stamp(&mut payload, stamp);
+        assert_eq!(None, read_stamp(&payload[..STAMP_SIZE - 1]));
+    }
+
+    #[test]
+    fn test_read_stamp_no_marker() {
+        assert_eq!(None, read_stamp(&[0; STAMP_SIZE]));
+    }
+
+    // The largest send time does not overflow on platforms which cannot
+    // represent it, such as Windows.
+    #[test]
+    fn test_read_stamp_max_sent() {
+        let mut payload = [0xff; STAMP_SIZE];
+        payload[0..4].copy_from_slice(&MARKER);
+        let expected = UNIX_EPOCH.checked_add(Duration::from_micros(u64::MAX));
+        assert_eq!(expected, read_stamp(&payload).map(|stamp| stamp.sent));
+    }
+}
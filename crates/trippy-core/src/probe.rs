@@ -1,6 +1,7 @@
+use crate::constants::MAX_TTL_ADDRS;
 use crate::types::{Checksum, Flags, Port, RoundId, Sequence, TimeToLive, TraceId};
-use std::net::IpAddr;
-use std::time::SystemTime;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::{Duration, SystemTime};
 
 /// A network tracing probe.
 ///
@@ -13,6 +14,8 @@ use std::time::SystemTime;
 /// - `Skipped` - The probe was skipped.
 /// - `Awaited` - The probe has been sent and is awaiting a response.
 /// - `Complete` - The probe has been sent and a response has been received.
+// The `Complete` variant holds its `TtlAddrs` inline, boxing it would allocate for every probe.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum ProbeStatus {
     /// The probe has not been sent.
@@ -90,6 +93,7 @@ impl Probe {
 
     /// A response has been received and the probe is now complete.
     #[must_use]
+    #[allow(clippy::too_many_arguments)]
     pub(crate) const fn complete(
         self,
         host: IpAddr,
@@ -98,6 +102,8 @@ impl Probe {
         expected_udp_checksum: Option<Checksum>,
         actual_udp_checksum: Option<Checksum>,
         extensions: Option<Extensions>,
+        reply_ttl: Option<u8>,
+        quoted_len: Option<u16>,
     ) -> ProbeComplete {
         ProbeComplete {
             sequence: self.sequence,
@@ -113,6 +119,10 @@ impl Probe {
             expected_udp_checksum,
             actual_udp_checksum,
             extensions,
+            reply_ttl,
+            quoted_len,
+            jitter: None,
+            ttl_addrs: TtlAddrs::new(),
         }
     }
 
@@ -168,6 +178,93 @@ pub struct ProbeComplete {
     pub actual_udp_checksum: Option<Checksum>,
     /// The ICMP response extensions.
     pub extensions: Option<Extensions>,
+    /// The time-to-live of the response to the probe, if known.
+    pub reply_ttl: Option<u8>,
+    /// The length of the original datagram quoted in the ICMP error response to the probe, if any.
+    pub quoted_len: Option<u16>,
+    /// The absolute difference between the round-trip time of the probe and that of the
+    /// previously completed probe with the same TTL, if any.
+    pub jitter: Option<Duration>,
+    /// The distinct addresses, including `host`, which have responded to probes with the same TTL
+    /// in this or any prior round.
+    ///
+    /// More than one address indicates that probes for the TTL are load balanced.
+    pub ttl_addrs: TtlAddrs,
+}
+
+impl ProbeComplete {
+    /// The round-trip time of the probe.
+    ///
+    /// The round-trip time is zero if the response was received before the probe was sent, which
+    /// can only happen if the system clock moved backwards.
+    #[must_use]
+    pub fn rtt(&self) -> Duration {
+        self.received.duration_since(self.sent).unwrap_or_default()
+    }
+}
+
+/// The distinct addresses which have responded to probes with the same TTL.
+///
+/// At most `MAX_TTL_ADDRS` addresses are held, in the order they first responded, and the
+/// addresses are stored inline such that they can be copied to every completed probe without
+/// allocating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TtlAddrs {
+    addrs: [IpAddr; MAX_TTL_ADDRS],
+    len: usize,
+}
+
+impl TtlAddrs {
+    /// No addresses.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            addrs: [IpAddr::V4(Ipv4Addr::UNSPECIFIED); MAX_TTL_ADDRS],
+            len: 0,
+        }
+    }
+
+    /// The addresses.
+    #[must_use]
+    pub fn as_slice(&self) -> &[IpAddr] {
+        &self.addrs[..self.len]
+    }
+
+    /// The number of addresses.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether there are no addresses.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Add an address, if it is not already present and there is space for it.
+    pub(crate) fn insert(&mut self, addr: IpAddr) {
+        if self.len < MAX_TTL_ADDRS && !self.as_slice().contains(&addr) {
+            self.addrs[self.len] = addr;
+            self.len += 1;
+        }
+    }
+}
+
+impl Default for TtlAddrs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<IpAddr> for TtlAddrs {
+    fn from_iter<T: IntoIterator<Item = IpAddr>>(iter: T) -> Self {
+        let mut ttl_addrs = Self::new();
+        for addr in iter {
+            ttl_addrs.insert(addr);
+        }
+        ttl_addrs
+    }
 }
 
 /// A failed network tracing probe.
@@ -209,6 +306,44 @@ pub enum IcmpPacketType {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct IcmpPacketCode(pub u8);
 
+/// The reason given by a `DestinationUnreachable` ICMP packet.
+///
+/// The ICMP codes differ between `IPv4` and `IPv6`, codes which have no
+/// common meaning are retained as `Other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnreachableCode {
+    /// The network, or for `IPv6` the route, is unreachable.
+    Network,
+    /// The host, or for `IPv6` the address, is unreachable.
+    Host,
+    /// The protocol is unreachable (`IPv4` only).
+    Protocol,
+    /// The port is unreachable.
+    Port,
+    /// Communication is administratively prohibited.
+    AdminProhibited,
+    /// Any other code.
+    Other(u8),
+}
+
+impl UnreachableCode {
+    /// The reason given by the code of a `DestinationUnreachable` packet received from `host`.
+    ///
+    /// The address family of `host` determines whether the code is interpreted as an `ICMPv4` or
+    /// an `ICMPv6` code.
+    #[must_use]
+    pub const fn new(code: IcmpPacketCode, host: IpAddr) -> Self {
+        match (host, code.0) {
+            (_, 0) => Self::Network,
+            (IpAddr::V4(_), 1) | (IpAddr::V6(_), 3) => Self::Host,
+            (IpAddr::V4(_), 2) => Self::Protocol,
+            (IpAddr::V4(_), 3) | (IpAddr::V6(_), 4) => Self::Port,
+            (IpAddr::V4(_), 9 | 10 | 13) | (IpAddr::V6(_), 1) => Self::AdminProhibited,
+            (_, code) => Self::Other(code),
+        }
+    }
+}
+
 /// The response to a probe.
 #[derive(Debug, Clone)]
 pub enum Response {
@@ -283,14 +418,26 @@ pub struct ResponseData {
     pub addr: IpAddr,
     /// Information about the sequence number of the probe response.
     pub resp_seq: ResponseSeq,
+    /// The time-to-live of the probe response, if known.
+    pub ttl: Option<u8>,
+    /// The length of the original datagram quoted in an ICMP error probe response, if any.
+    pub quoted_len: Option<u16>,
 }
 
 impl ResponseData {
-    pub const fn new(recv: SystemTime, addr: IpAddr, resp_seq: ResponseSeq) -> Self {
+    pub const fn new(
+        recv: SystemTime,
+        addr: IpAddr,
+        resp_seq: ResponseSeq,
+        ttl: Option<u8>,
+        quoted_len: Option<u16>,
+    ) -> Self {
         Self {
             recv,
             addr,
             resp_seq,
+            ttl,
+            quoted_len,
         }
     }
 }
@@ -309,13 +456,41 @@ pub struct ResponseSeqIcmp {
     pub identifier: u16,
     /// The ICMP sequence number.
     pub sequence: u16,
+    /// The stamp read from the probe payload, if any.
+    pub stamp: Option<PayloadStamp>,
 }
 
 impl ResponseSeqIcmp {
-    pub const fn new(identifier: u16, sequence: u16) -> Self {
+    pub const fn new(identifier: u16, sequence: u16, stamp: Option<PayloadStamp>) -> Self {
         Self {
             identifier,
             sequence,
+            stamp,
+        }
+    }
+}
+
+/// The stamp embedded in the payload of a probe.
+///
+/// A stamp records the identifier, sequence number and send time of a probe
+/// such that a response can be correlated even if the identifier and sequence
+/// number in the headers of the original packet are rewritten in transit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PayloadStamp {
+    /// The ICMP identifier.
+    pub identifier: u16,
+    /// The ICMP sequence number.
+    pub sequence: u16,
+    /// The time the probe was sent.
+    pub sent: SystemTime,
+}
+
+impl PayloadStamp {
+    pub const fn new(identifier: u16, sequence: u16, sent: SystemTime) -> Self {
+        Self {
+            identifier,
+            sequence,
+            sent,
         }
     }
 }
@@ -433,3 +608,68 @@ impl ProbeStatus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use test_case::test_case;
+
+    #[test_case(1000, 1012, 12; "received after sent")]
+    #[test_case(1000, 1000, 0; "received when sent")]
+    #[test_case(1012, 1000, 0; "received before sent")]
+    fn test_complete_rtt(sent_ms: u64, received_ms: u64, expected_ms: u64) {
+        let complete = ProbeComplete {
+            sequence: Sequence(33434),
+            identifier: TraceId(0),
+            src_port: Port(0),
+            dest_port: Port(0),
+            ttl: TimeToLive(1),
+            round: RoundId(0),
+            sent: SystemTime::UNIX_EPOCH + Duration::from_millis(sent_ms),
+            host: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            received: SystemTime::UNIX_EPOCH + Duration::from_millis(received_ms),
+            icmp_packet_type: IcmpPacketType::NotApplicable,
+            expected_udp_checksum: None,
+            actual_udp_checksum: None,
+            extensions: None,
+            reply_ttl: None,
+            quoted_len: None,
+            jitter: None,
+            ttl_addrs: TtlAddrs::default(),
+        };
+        assert_eq!(Duration::from_millis(expected_ms), complete.rtt());
+    }
+
+    // Each address is held once, in the order it first responded, and at most
+    // `MAX_TTL_ADDRS` addresses are held.
+    #[test]
+    fn test_ttl_addrs() {
+        let addrs = (0..=u8::try_from(MAX_TTL_ADDRS).unwrap())
+            .map(|i| IpAddr::V4(Ipv4Addr::new(10, 0, 0, i)))
+            .collect::<Vec<_>>();
+        let ttl_addrs = [addrs[1], addrs[0], addrs[1]]
+            .into_iter()
+            .collect::<TtlAddrs>();
+        assert_eq!(&[addrs[1], addrs[0]], ttl_addrs.as_slice());
+        let ttl_addrs = addrs.iter().copied().collect::<TtlAddrs>();
+        assert_eq!(MAX_TTL_ADDRS, ttl_addrs.len());
+        assert_eq!(&addrs[..MAX_TTL_ADDRS], ttl_addrs.as_slice());
+        assert!(TtlAddrs::default().is_empty());
+    }
+
+    #[test_case(IpAddr::V4(Ipv4Addr::LOCALHOST), 0, UnreachableCode::Network)]
+    #[test_case(IpAddr::V4(Ipv4Addr::LOCALHOST), 1, UnreachableCode::Host)]
+    #[test_case(IpAddr::V4(Ipv4Addr::LOCALHOST), 2, UnreachableCode::Protocol)]
+    #[test_case(IpAddr::V4(Ipv4Addr::LOCALHOST), 3, UnreachableCode::Port)]
+    #[test_case(IpAddr::V4(Ipv4Addr::LOCALHOST), 13, UnreachableCode::AdminProhibited)]
+    #[test_case(IpAddr::V4(Ipv4Addr::LOCALHOST), 4, UnreachableCode::Other(4))]
+    #[test_case(IpAddr::V6(Ipv6Addr::LOCALHOST), 0, UnreachableCode::Network)]
+    #[test_case(IpAddr::V6(Ipv6Addr::LOCALHOST), 1, UnreachableCode::AdminProhibited)]
+    #[test_case(IpAddr::V6(Ipv6Addr::LOCALHOST), 3, UnreachableCode::Host)]
+    #[test_case(IpAddr::V6(Ipv6Addr::LOCALHOST), 4, UnreachableCode::Port)]
+    #[test_case(IpAddr::V6(Ipv6Addr::LOCALHOST), 2, UnreachableCode::Other(2))]
+    fn test_unreachable_code(host: IpAddr, code: u8, expected: UnreachableCode) {
+        assert_eq!(expected, UnreachableCode::new(IcmpPacketCode(code), host));
+    }
+}
@@ -2,12 +2,15 @@ use crate::config::StateConfig;
 use crate::constants::MAX_TTL;
 use crate::flows::{Flow, FlowId, FlowRegistry};
 use crate::types::Checksum;
-use crate::{Extensions, IcmpPacketType, ProbeStatus, Round, RoundId, TimeToLive};
+use crate::{
+    Extensions, IcmpPacketType, ProbeComplete, ProbeStatus, Round, RoundId, TimeToLive,
+    UnreachableCode,
+};
 use indexmap::IndexMap;
 use std::collections::HashMap;
 use std::iter::once;
 use std::net::IpAddr;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 /// The state of a trace.
 #[derive(Debug, Clone, Default)]
@@ -16,12 +19,24 @@ pub struct State {
     state_config: StateConfig,
     /// The flow id for the current round.
     round_flow_id: FlowId,
+    /// The flow id updated by the last round, if any, to which late responses are attributed.
+    late_flow_id: Option<FlowId>,
     /// Tracing state per registered flow id.
     state: HashMap<FlowId, FlowState>,
     /// Flow registry.
     registry: FlowRegistry,
     /// Tracing error message.
     error: Option<String>,
+    /// The current attempt to retry the setup of the trace, if any.
+    setup_retry_attempt: Option<usize>,
+    /// The total number of probes whose send was deferred by the rate limiter.
+    total_suppressed: usize,
+    /// The number of probes whose send was deferred by the rate limiter in the last round.
+    last_round_suppressed: usize,
+    /// The adaptive read timeout at the end of the last round, if enabled.
+    read_timeout: Option<Duration>,
+    /// Whether probing was curtailed by the maximum number of unresponsive hops in the last round.
+    last_round_curtailed: bool,
 }
 
 impl State {
@@ -35,9 +50,15 @@ impl State {
             ))
             .collect::<HashMap<FlowId, FlowState>>(),
             round_flow_id: Self::default_flow_id(),
+            late_flow_id: None,
             state_config,
             registry: FlowRegistry::new(),
             error: None,
+            setup_retry_attempt: None,
+            total_suppressed: 0,
+            last_round_suppressed: 0,
+            read_timeout: None,
+            last_round_curtailed: false,
         }
     }
 
@@ -94,6 +115,62 @@ impl State {
         self.state[&flow_id].round_count()
     }
 
+    /// The time-to-live at which the target was most recently found for a given flow.
+    ///
+    /// Returns `None` if the target has not been found in any round.
+    #[must_use]
+    pub fn target_ttl(&self, flow_id: FlowId) -> Option<u8> {
+        self.state[&flow_id].target_ttl()
+    }
+
+    /// The number of times the target time-to-live has changed between rounds for a given flow.
+    #[must_use]
+    pub fn target_ttl_changes(&self, flow_id: FlowId) -> usize {
+        self.state[&flow_id].target_ttl_changes()
+    }
+
+    /// Did the target time-to-live change in the latest round for a given flow?
+    #[must_use]
+    pub fn target_ttl_changed(&self, flow_id: FlowId) -> bool {
+        self.state[&flow_id].target_ttl_changed()
+    }
+
+    /// The round in which the target time-to-live most recently changed for a given flow.
+    ///
+    /// Returns `None` if the target time-to-live has never changed.
+    #[must_use]
+    pub fn target_ttl_changed_round(&self, flow_id: FlowId) -> Option<usize> {
+        self.state[&flow_id].target_ttl_changed_round()
+    }
+
+    /// The number of rounds in which the path changed for a given flow.
+    ///
+    /// The path changes in a round if a hop replied from an address which had not previously
+    /// been seen at that time-to-live, or if the time-to-live of the target changed.
+    #[must_use]
+    pub fn path_changes(&self, flow_id: FlowId) -> usize {
+        self.state[&flow_id].path_changes()
+    }
+
+    /// The time at which the path most recently changed for a given flow.
+    ///
+    /// Returns `None` if the path has never changed.
+    #[must_use]
+    pub fn path_changed(&self, flow_id: FlowId) -> Option<SystemTime> {
+        self.state[&flow_id].path_changed()
+    }
+
+    /// The time-to-live values, greater than the target time-to-live, at which the target also
+    /// replied in the latest round for a given flow.
+    ///
+    /// The tracer sends probes with larger time-to-live values before the reply from the target
+    /// is received, and so the target may reply at several time-to-live values in a round.  The
+    /// lowest of these is the target time-to-live, and the remainder are reported here.
+    #[must_use]
+    pub fn over_probed_ttls(&self, flow_id: FlowId) -> &[u8] {
+        self.state[&flow_id].over_probed_ttls()
+    }
+
     /// The `FlowId` for the current round.
     #[must_use]
     pub const fn round_flow_id(&self) -> FlowId {
@@ -116,6 +193,42 @@ impl State {
         self.error = error;
     }
 
+    /// The current attempt to retry the setup of the trace, if any.
+    #[must_use]
+    pub const fn setup_retry_attempt(&self) -> Option<usize> {
+        self.setup_retry_attempt
+    }
+
+    pub fn set_setup_retry_attempt(&mut self, setup_retry_attempt: Option<usize>) {
+        self.setup_retry_attempt = setup_retry_attempt;
+    }
+
+    /// The total number of probes whose send was deferred by the rate limiter.
+    #[must_use]
+    pub const fn total_suppressed(&self) -> usize {
+        self.total_suppressed
+    }
+
+    /// The number of probes whose send was deferred by the rate limiter in the last round.
+    #[must_use]
+    pub const fn last_round_suppressed(&self) -> usize {
+        self.last_round_suppressed
+    }
+
+    /// Whether probing was curtailed by the maximum number of unresponsive hops in the last round.
+    #[must_use]
+    pub const fn last_round_curtailed(&self) -> bool {
+        self.last_round_curtailed
+    }
+
+    /// The adaptive read timeout at the end of the last round.
+    ///
+    /// Returns `None` if the read timeout is not adaptive or no response has been received.
+    #[must_use]
+    pub const fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
     /// The maximum number of samples to record per hop.
     #[must_use]
     pub const fn max_samples(&self) -> usize {
@@ -130,6 +243,7 @@ impl State {
 
     /// Update the tracing state from a `TracerRound`.
     pub fn update_from_round(&mut self, round: &Round<'_>) {
+        self.update_late(round.late);
         let flow = Flow::from_hops(
             round
                 .probes
@@ -141,11 +255,39 @@ impl State {
                 })
                 .take(usize::from(round.largest_ttl.0)),
         );
+        self.total_suppressed += round.suppressed;
+        self.last_round_suppressed = round.suppressed;
+        self.last_round_curtailed = round.curtailed;
+        let warmup = if self.state_config.discard_first_hop_warmup {
+            without_first_hop_warmup(round.probes)
+        } else {
+            None
+        };
+        let round = &warmup.as_deref().map_or_else(
+            || round.clone(),
+            |probes| Round {
+                probes,
+                ..round.clone()
+            },
+        );
+        self.read_timeout = round.read_timeout;
         self.update_trace_flow(Self::default_flow_id(), round);
         if self.registry.flows().len() < self.state_config.max_flows {
             let flow_id = self.registry.register(flow);
             self.round_flow_id = flow_id;
+            self.late_flow_id = Some(flow_id);
             self.update_trace_flow(flow_id, round);
+        } else {
+            self.late_flow_id = None;
+        }
+    }
+
+    /// Attribute late responses to the flows which were updated by the previous round.
+    fn update_late(&mut self, late: &[ProbeStatus]) {
+        for flow_id in once(Self::default_flow_id()).chain(self.late_flow_id) {
+            if let Some(flow_trace) = self.state.get_mut(&flow_id) {
+                flow_trace.update_from_late(late);
+            }
         }
     }
 
@@ -197,10 +339,21 @@ pub struct Hop {
     last_icmp_packet_type: Option<IcmpPacketType>,
     /// The NAT detection status for the last probe for this hop.
     last_nat_status: NatStatus,
+    /// The time-to-live of the last response received for this hop.
+    last_reply_ttl: Option<u8>,
+    /// The length of the original datagram quoted in the last ICMP error received for this hop.
+    last_quoted_len: Option<u16>,
+    /// The reason given by the last `DestinationUnreachable` response for this hop, if the last
+    /// response was one.
+    last_unreachable_code: Option<UnreachableCode>,
+    /// The round in which this hop appeared, if it appeared after the path was first traced.
+    appeared_round: Option<usize>,
     /// The history of round trip times across the last N rounds.
     samples: Vec<Duration>,
     /// The ICMP extensions for this hop.
     extensions: Option<Extensions>,
+    /// The statistics of the probes answered by each address of this hop.
+    addr_hops: IndexMap<IpAddr, Self>,
     mean: f64,
     m2: f64,
 }
@@ -227,6 +380,15 @@ impl Hop {
         self.addrs.len()
     }
 
+    /// The statistics of each address that has responded for this time-to-live.
+    ///
+    /// Each address `Hop` only includes the probes answered by that address, as a probe which
+    /// receives no reply cannot be attributed to any one address.  The sent and received totals of
+    /// an address `Hop` are therefore always equal.
+    pub fn addr_hops(&self) -> impl Iterator<Item = (&IpAddr, &Self)> {
+        self.addr_hops.iter()
+    }
+
     /// The total number of probes sent.
     #[must_use]
     pub const fn total_sent(&self) -> usize {
@@ -249,13 +411,28 @@ impl Hop {
     #[must_use]
     pub fn loss_pct(&self) -> f64 {
         if self.total_sent > 0 {
-            let lost = self.total_sent - self.total_recv;
+            let lost = self.total_sent.saturating_sub(self.total_recv);
             lost as f64 / self.total_sent as f64 * 100f64
         } else {
             0_f64
         }
     }
 
+    /// The exponentially weighted moving average % of recent packets that are lost.
+    ///
+    /// The `weight`, between 0 and 1, is that of each probe relative to the probes before it, such
+    /// that a higher weight responds to a change in loss more quickly.  The average is computed
+    /// over the retained samples only.
+    #[must_use]
+    pub fn recent_loss_pct(&self, weight: f64) -> f64 {
+        self.samples
+            .iter()
+            .rev()
+            .map(|dur| if dur.is_zero() { 100_f64 } else { 0_f64 })
+            .reduce(|avg, loss| weight.mul_add(loss - avg, avg))
+            .unwrap_or_default()
+    }
+
     /// The duration of the last probe.
     #[must_use]
     pub fn last_ms(&self) -> Option<f64> {
@@ -284,7 +461,23 @@ impl Hop {
         }
     }
 
-    /// The standard deviation of all probes.
+    /// The exponentially weighted moving average duration of recent probes.
+    ///
+    /// The `weight`, between 0 and 1, is that of each probe relative to the probes before it, such
+    /// that a higher weight tracks recent probes more closely.  The average is computed over the
+    /// retained samples only, excluding those of lost probes.
+    #[must_use]
+    pub fn ewma_ms(&self, weight: f64) -> f64 {
+        self.samples
+            .iter()
+            .rev()
+            .filter(|dur| !dur.is_zero())
+            .map(|dur| dur.as_secs_f64() * 1000_f64)
+            .reduce(|avg, rtt_ms| weight.mul_add(rtt_ms - avg, avg))
+            .unwrap_or_default()
+    }
+
+    /// The sample standard deviation of all probes.
     #[must_use]
     pub fn stddev_ms(&self) -> f64 {
         if self.total_recv > 1 {
@@ -294,6 +487,16 @@ impl Hop {
         }
     }
 
+    /// The population standard deviation of all probes.
+    #[must_use]
+    pub fn population_stddev_ms(&self) -> f64 {
+        if self.total_recv > 0 {
+            (self.m2 / self.total_recv as f64).sqrt()
+        } else {
+            0_f64
+        }
+    }
+
     /// The duration of the jitter probe observed.
     #[must_use]
     pub fn jitter_ms(&self) -> Option<f64> {
@@ -348,6 +551,41 @@ impl Hop {
         self.last_nat_status
     }
 
+    /// The time-to-live of the last response received for this hop.
+    ///
+    /// This is only available for IPv4 `icmp` responses.
+    #[must_use]
+    pub const fn last_reply_ttl(&self) -> Option<u8> {
+        self.last_reply_ttl
+    }
+
+    /// The length of the original datagram quoted in the last ICMP error received for this hop.
+    ///
+    /// This is the number of bytes of the probe, starting from the IP header, which the hop
+    /// included in its `TimeExceeded` or `DestinationUnreachable` response, excluding any ICMP
+    /// extensions.
+    #[must_use]
+    pub const fn last_quoted_len(&self) -> Option<u16> {
+        self.last_quoted_len
+    }
+
+    /// The reason given by the last response received for this hop, if it was a
+    /// `DestinationUnreachable` response.
+    #[must_use]
+    pub const fn last_unreachable_code(&self) -> Option<UnreachableCode> {
+        self.last_unreachable_code
+    }
+
+    /// The round in which this hop appeared.
+    ///
+    /// This is `None` for hops which were part of the path when it was first
+    /// traced and is only set for hops which appeared later, when the path to
+    /// the target grew longer.
+    #[must_use]
+    pub const fn appeared_round(&self) -> Option<usize> {
+        self.appeared_round
+    }
+
     /// The last N samples.
     #[must_use]
     pub fn samples(&self) -> &[Duration] {
@@ -358,6 +596,91 @@ impl Hop {
     pub const fn extensions(&self) -> Option<&Extensions> {
         self.extensions.as_ref()
     }
+
+    /// The hop with statistics for the most recent `rounds` rounds only.
+    ///
+    /// The probes sent and received, loss, round-trip time and jitter
+    /// statistics are recomputed from the retained samples, and so the window
+    /// is limited by the maximum number of samples.  All other data, such as
+    /// the addresses and the number of failed probes, is unchanged.
+    #[must_use]
+    pub fn windowed(&self, rounds: usize) -> Self {
+        let window = &self.samples[..rounds.min(self.samples.len())];
+        let mut hop = Self {
+            total_sent: window.len(),
+            total_recv: 0,
+            total_time: Duration::default(),
+            last: None,
+            best: None,
+            worst: None,
+            jitter: None,
+            javg: 0f64,
+            jmax: None,
+            jinta: 0f64,
+            mean: 0f64,
+            m2: 0f64,
+            ..self.clone()
+        };
+        for dur in window.iter().rev().filter(|dur| !dur.is_zero()) {
+            hop.total_recv += 1;
+            hop.update_rtt(*dur);
+        }
+        hop
+    }
+
+    /// Update the statistics with a probe which was answered.
+    fn update_from_complete(
+        &mut self,
+        complete: &ProbeComplete,
+        dur: Duration,
+        max_samples: usize,
+    ) {
+        self.total_sent += 1;
+        self.total_recv += 1;
+        self.update_rtt(dur);
+        self.samples.insert(0, dur);
+        if self.samples.len() > max_samples {
+            self.samples.pop();
+        }
+        *self.addrs.entry(complete.host).or_default() += 1;
+        self.extensions.clone_from(&complete.extensions);
+        self.last_src_port = complete.src_port.0;
+        self.last_dest_port = complete.dest_port.0;
+        self.last_sequence = complete.sequence.0;
+        self.last_icmp_packet_type = Some(complete.icmp_packet_type);
+        self.last_reply_ttl = complete.reply_ttl;
+        self.last_quoted_len = complete.quoted_len;
+        self.last_unreachable_code = match complete.icmp_packet_type {
+            IcmpPacketType::Unreachable(code) => Some(UnreachableCode::new(code, complete.host)),
+            _ => None,
+        };
+    }
+
+    /// Update the round-trip time statistics with a received probe.
+    ///
+    /// Must be called after `total_recv` has been incremented.
+    fn update_rtt(&mut self, dur: Duration) {
+        let dur_ms = dur.as_secs_f64() * 1000_f64;
+        self.total_time += dur;
+        // Before last is set use it to calc jitter
+        let last_ms = self.last_ms().unwrap_or_default();
+        let jitter_ms = (dur_ms - last_ms).abs();
+        let jitter_dur = Duration::from_secs_f64(jitter_ms / 1000_f64);
+        self.jitter = self.last.and(Some(jitter_dur));
+        self.javg += (jitter_ms - self.javg) / self.total_recv as f64;
+        // algorithm is from rfc1889, A.8 or rfc3550
+        self.jinta += jitter_ms.max(0.5) - ((self.jinta + 8.0) / 16.0);
+        self.jmax = self
+            .jmax
+            .map_or(Some(jitter_dur), |d| Some(d.max(jitter_dur)));
+        self.last = Some(dur);
+        self.best = self.best.map_or(Some(dur), |d| Some(d.min(dur)));
+        self.worst = self.worst.map_or(Some(dur), |d| Some(d.max(dur)));
+        // algorithm is Welford's online algorithm
+        let delta = dur_ms - self.mean;
+        self.mean += delta / self.total_recv as f64;
+        self.m2 += delta * (dur_ms - self.mean);
+    }
 }
 
 impl Default for Hop {
@@ -384,7 +707,12 @@ impl Default for Hop {
             m2: 0f64,
             samples: Vec::default(),
             extensions: None,
+            addr_hops: IndexMap::default(),
             last_nat_status: NatStatus::NotApplicable,
+            last_reply_ttl: None,
+            last_quoted_len: None,
+            last_unreachable_code: None,
+            appeared_round: None,
         }
     }
 }
@@ -415,6 +743,20 @@ struct FlowState {
     round: Option<usize>,
     /// The total number of rounds received.
     round_count: usize,
+    /// The ttl at which the target was most recently found.
+    target_ttl: Option<u8>,
+    /// The number of times the target ttl has changed between rounds.
+    target_ttl_changes: usize,
+    /// Whether the target ttl changed in the latest round.
+    target_ttl_changed: bool,
+    /// The round in which the target ttl most recently changed.
+    target_ttl_changed_round: Option<usize>,
+    /// The number of rounds in which the path changed.
+    path_changes: usize,
+    /// The time at which the path most recently changed.
+    path_changed: Option<SystemTime>,
+    /// The ttls, greater than the target ttl, at which the target also replied in the latest round.
+    over_probed_ttls: Vec<u8>,
     /// The hops in this trace.
     hops: Vec<Hop>,
 }
@@ -428,6 +770,13 @@ impl FlowState {
             highest_ttl_for_round: 0,
             round: None,
             round_count: 0,
+            target_ttl: None,
+            target_ttl_changes: 0,
+            target_ttl_changed: false,
+            target_ttl_changed_round: None,
+            path_changes: 0,
+            path_changed: None,
+            over_probed_ttls: Vec::new(),
             hops: (0..MAX_TTL).map(|_| Hop::default()).collect(),
         }
     }
@@ -466,59 +815,205 @@ impl FlowState {
         self.round_count
     }
 
+    const fn target_ttl(&self) -> Option<u8> {
+        self.target_ttl
+    }
+
+    const fn target_ttl_changes(&self) -> usize {
+        self.target_ttl_changes
+    }
+
+    const fn target_ttl_changed(&self) -> bool {
+        self.target_ttl_changed
+    }
+
+    const fn target_ttl_changed_round(&self) -> Option<usize> {
+        self.target_ttl_changed_round
+    }
+
+    const fn path_changes(&self) -> usize {
+        self.path_changes
+    }
+
+    const fn path_changed(&self) -> Option<SystemTime> {
+        self.path_changed
+    }
+
+    fn over_probed_ttls(&self) -> &[u8] {
+        &self.over_probed_ttls
+    }
+
     fn update_from_round(&mut self, round: &Round<'_>) {
         self.round_count += 1;
+        self.update_target_ttl(round);
+        self.update_over_probed_ttls(round);
+        self.update_path_changed(round);
+        let prev_highest_ttl = self.highest_ttl;
         self.highest_ttl = std::cmp::max(self.highest_ttl, round.largest_ttl.0);
         self.highest_ttl_for_round = round.largest_ttl.0;
         let mut prev_hop_checksum = None;
         for probe in round.probes {
             self.update_from_probe(probe, &mut prev_hop_checksum);
         }
+        self.update_appeared(prev_highest_ttl);
+        if self.target_ttl_changed {
+            self.target_ttl_changed_round = self.round;
+        }
+    }
+
+    /// Update the hops with probes from the previous round which received a late response.
+    ///
+    /// The probes were counted as sent when the previous round was published and so only the
+    /// received totals and round-trip time statistics are updated, the history of samples is not.
+    ///
+    /// Probes which were not counted as sent, see `admit`, are also not counted as received.
+    fn update_from_late(&mut self, late: &[ProbeStatus]) {
+        for probe in late {
+            if !self.admit(probe) {
+                continue;
+            }
+            if let ProbeStatus::Complete(complete) = probe {
+                let hop = &mut self.hops[usize::from(complete.ttl.0) - 1];
+                let dur = complete.rtt();
+                hop.total_recv += 1;
+                hop.update_rtt(dur);
+                *hop.addrs.entry(complete.host).or_default() += 1;
+                hop.addr_hops
+                    .entry(complete.host)
+                    .or_insert_with(|| Hop {
+                        ttl: complete.ttl.0,
+                        ..Hop::default()
+                    })
+                    .update_from_complete(complete, dur, self.max_samples);
+            }
+        }
+    }
+
+    /// Record the round in which hops appeared, if the path grew longer in the round.
+    ///
+    /// Hops which are no longer part of the path when it grows shorter retain
+    /// their statistics, and so a hop can only appear once.
+    fn update_appeared(&mut self, prev_highest_ttl: u8) {
+        if prev_highest_ttl > 0 {
+            let start = usize::from(prev_highest_ttl);
+            let end = usize::from(self.highest_ttl);
+            for hop in &mut self.hops[start..end] {
+                hop.appeared_round = self.round;
+            }
+        }
+    }
+
+    /// Update the target ttl, if the target was found in the round.
+    ///
+    /// If the target was not found in the round then the previous target ttl is retained.
+    fn update_target_ttl(&mut self, round: &Round<'_>) {
+        if round.reason.target_found() {
+            let target_ttl = round.largest_ttl.0;
+            self.target_ttl_changed = self.target_ttl.is_some_and(|ttl| ttl != target_ttl);
+            if self.target_ttl_changed {
+                self.target_ttl_changes += 1;
+            }
+            self.target_ttl = Some(target_ttl);
+        } else {
+            self.target_ttl_changed = false;
+        }
+    }
+
+    /// Record the time at which the path changed, if it changed in the round.
+    ///
+    /// The path changed if a hop replied from an address not previously seen at that ttl, or if
+    /// the target ttl changed.  Hops which have not replied before, such as those which appear
+    /// when the path is first traced, do not change the path.  The time of the change is that of
+    /// the latest reply in the round.
+    ///
+    /// This must be called before the hops are updated from the probes of the round.
+    fn update_path_changed(&mut self, round: &Round<'_>) {
+        let completed = round.probes.iter().filter_map(|probe| match probe {
+            ProbeStatus::Complete(complete) => Some(complete),
+            _ => None,
+        });
+        let mut changed = self.target_ttl_changed;
+        let mut latest = None;
+        for complete in completed {
+            let addrs = &self.hops[usize::from(complete.ttl.0) - 1].addrs;
+            changed |= !addrs.is_empty() && !addrs.contains_key(&complete.host);
+            latest = latest.max(Some(complete.received));
+        }
+        if changed {
+            if let Some(latest) = latest {
+                self.path_changes += 1;
+                self.path_changed = Some(latest);
+            }
+        }
+    }
+
+    /// Update the ttls, greater than the target ttl, at which the target also replied.
+    ///
+    /// A probe is a reply from the target if it was answered by the same host as the probe at the
+    /// target ttl.
+    fn update_over_probed_ttls(&mut self, round: &Round<'_>) {
+        self.over_probed_ttls.clear();
+        if !round.reason.target_found() {
+            return;
+        }
+        let completed = || {
+            round.probes.iter().filter_map(|probe| match probe {
+                ProbeStatus::Complete(complete) => Some(complete),
+                _ => None,
+            })
+        };
+        let Some(target) = completed().find(|complete| complete.ttl == round.largest_ttl) else {
+            return;
+        };
+        self.over_probed_ttls.extend(
+            completed()
+                .filter(|complete| complete.ttl > round.largest_ttl)
+                .filter(|complete| complete.host == target.host)
+                .map(|complete| complete.ttl.0),
+        );
+    }
+
+    /// Admit a probe to the hops of the flow, updating the lowest ttl and latest round.
+    ///
+    /// Probes may be sent beyond the longest path observed, such as those sent beyond the target
+    /// before it was found.  Such probes are not part of the path and so are not admitted,
+    /// otherwise the hop would have accrued a loss for every such round if the path later grows.
+    ///
+    /// Both the probes of a round and those which received a late response must be admitted such
+    /// that a hop is never counted as receiving more responses than probes sent.
+    fn admit(&mut self, probe: &ProbeStatus) -> bool {
+        let (ttl, round) = match probe {
+            ProbeStatus::Complete(complete) => (complete.ttl, complete.round),
+            ProbeStatus::Awaited(awaited) => (awaited.ttl, awaited.round),
+            ProbeStatus::Failed(failed) => (failed.ttl, failed.round),
+            ProbeStatus::NotSent | ProbeStatus::Skipped => return false,
+        };
+        if ttl.0 > self.highest_ttl {
+            return false;
+        }
+        self.update_lowest_ttl(ttl);
+        self.update_round(round);
+        true
     }
 
     fn update_from_probe(&mut self, probe: &ProbeStatus, prev_hop_checksum: &mut Option<u16>) {
+        if !self.admit(probe) {
+            return;
+        }
         match probe {
             ProbeStatus::Complete(complete) => {
-                self.update_lowest_ttl(complete.ttl);
-                self.update_round(complete.round);
                 let index = usize::from(complete.ttl.0) - 1;
                 let hop = &mut self.hops[index];
                 hop.ttl = complete.ttl.0;
-                hop.total_sent += 1;
-                hop.total_recv += 1;
-                let dur = complete
-                    .received
-                    .duration_since(complete.sent)
-                    .unwrap_or_default();
-                let dur_ms = dur.as_secs_f64() * 1000_f64;
-                hop.total_time += dur;
-                // Before last is set use it to calc jitter
-                let last_ms = hop.last_ms().unwrap_or_default();
-                let jitter_ms = (dur_ms - last_ms).abs();
-                let jitter_dur = Duration::from_secs_f64(jitter_ms / 1000_f64);
-                hop.jitter = hop.last.and(Some(jitter_dur));
-                hop.javg += (jitter_ms - hop.javg) / hop.total_recv as f64;
-                // algorithm is from rfc1889, A.8 or rfc3550
-                hop.jinta += jitter_ms.max(0.5) - ((hop.jinta + 8.0) / 16.0);
-                hop.jmax = hop
-                    .jmax
-                    .map_or(Some(jitter_dur), |d| Some(d.max(jitter_dur)));
-                hop.last = Some(dur);
-                hop.samples.insert(0, dur);
-                hop.best = hop.best.map_or(Some(dur), |d| Some(d.min(dur)));
-                hop.worst = hop.worst.map_or(Some(dur), |d| Some(d.max(dur)));
-                hop.mean += (dur_ms - hop.mean) / hop.total_recv as f64;
-                hop.m2 += (dur_ms - hop.mean) * (dur_ms - hop.mean);
-                if hop.samples.len() > self.max_samples {
-                    hop.samples.pop();
-                }
-                let host = complete.host;
-                *hop.addrs.entry(host).or_default() += 1;
-                hop.extensions.clone_from(&complete.extensions);
-                hop.last_src_port = complete.src_port.0;
-                hop.last_dest_port = complete.dest_port.0;
-                hop.last_sequence = complete.sequence.0;
-                hop.last_icmp_packet_type = Some(complete.icmp_packet_type);
+                let dur = complete.rtt();
+                hop.update_from_complete(complete, dur, self.max_samples);
+                hop.addr_hops
+                    .entry(complete.host)
+                    .or_insert_with(|| Hop {
+                        ttl: complete.ttl.0,
+                        ..Hop::default()
+                    })
+                    .update_from_complete(complete, dur, self.max_samples);
 
                 if let (Some(expected), Some(actual)) =
                     (complete.expected_udp_checksum, complete.actual_udp_checksum)
@@ -529,8 +1024,6 @@ impl FlowState {
                 }
             }
             ProbeStatus::Awaited(awaited) => {
-                self.update_lowest_ttl(awaited.ttl);
-                self.update_round(awaited.round);
                 let index = usize::from(awaited.ttl.0) - 1;
                 self.hops[index].total_sent += 1;
                 self.hops[index].ttl = awaited.ttl.0;
@@ -543,8 +1036,6 @@ impl FlowState {
                 self.hops[index].last_sequence = awaited.sequence.0;
             }
             ProbeStatus::Failed(failed) => {
-                self.update_lowest_ttl(failed.ttl);
-                self.update_round(failed.round);
                 let index = usize::from(failed.ttl.0) - 1;
                 self.hops[index].total_sent += 1;
                 self.hops[index].total_failed += 1;
@@ -577,6 +1068,26 @@ impl FlowState {
     }
 }
 
+/// The probes of a round without the first response from the first hop, if it is the first round.
+///
+/// The first probe of a trace may be delayed by ARP or neighbour discovery for the first hop, and
+/// so the response to it is discarded, as if the probe had not been sent.
+///
+/// Returns `None` if the round is not the first round or the first probe was not answered.
+fn without_first_hop_warmup(probes: &[ProbeStatus]) -> Option<Vec<ProbeStatus>> {
+    let index = probes
+        .iter()
+        .position(|probe| !matches!(probe, ProbeStatus::NotSent | ProbeStatus::Skipped))?;
+    match &probes[index] {
+        ProbeStatus::Complete(complete) if complete.round == RoundId(0) => {
+            let mut probes = probes.to_vec();
+            probes[index] = ProbeStatus::Skipped;
+            Some(probes)
+        }
+        _ => None,
+    }
+}
+
 /// Determine the NAT detection status.
 ///
 /// Returns a tuple of the NAT detection status and the checksum to use for the next hop.
@@ -712,6 +1223,8 @@ mod tests {
                                 expected_udp_checksum,
                                 actual_udp_checksum,
                                 None,
+                                None,
+                                None,
                             ),
                         ))
                     }
@@ -798,6 +1311,367 @@ mod tests {
         }};
     }
 
+    const FOUND: CompletionReason = CompletionReason::TargetFound;
+    const NOT_FOUND: CompletionReason = CompletionReason::RoundTimeLimitExceeded;
+
+    #[test_case(&[(5, NOT_FOUND)] => (None, 0, false); "not found")]
+    #[test_case(&[(5, FOUND)] => (Some(5), 0, false); "found")]
+    #[test_case(&[(5, FOUND), (5, FOUND)] => (Some(5), 0, false); "found unchanged")]
+    #[test_case(&[(5, FOUND), (6, FOUND)] => (Some(6), 1, true); "found changed")]
+    #[test_case(&[(5, FOUND), (6, FOUND), (6, FOUND)] => (Some(6), 1, false); "found changed previous round")]
+    #[test_case(&[(5, FOUND), (6, FOUND), (5, FOUND)] => (Some(5), 2, true); "found changed twice")]
+    #[test_case(&[(5, FOUND), (9, NOT_FOUND)] => (Some(5), 0, false); "found then not found")]
+    #[test_case(&[(5, FOUND), (9, NOT_FOUND), (4, FOUND)] => (Some(4), 1, true); "found changed after not found")]
+    fn test_target_ttl(rounds: &[(u8, CompletionReason)]) -> (Option<u8>, usize, bool) {
+        let mut trace = State::new(StateConfig::default());
+        for (largest_ttl, reason) in rounds {
+            trace.update_from_round(&Round::new(&[], TimeToLive(*largest_ttl), *reason));
+        }
+        let flow_id = State::default_flow_id();
+        (
+            trace.target_ttl(flow_id),
+            trace.target_ttl_changes(flow_id),
+            trace.target_ttl_changed(flow_id),
+        )
+    }
+
+    #[test]
+    fn test_target_ttl_increasing() {
+        let mut trace = State::new(StateConfig::default());
+        let flow_id = State::default_flow_id();
+        update_with_path(&mut trace, 0, 3);
+        assert_eq!(3, trace.hops().len());
+        assert_eq!(None, trace.target_ttl_changed_round(flow_id));
+        update_with_path(&mut trace, 1, 5);
+        assert_eq!(5, trace.hops().len());
+        assert_eq!(Some(1), trace.target_ttl_changed_round(flow_id));
+        let appeared = trace
+            .hops()
+            .iter()
+            .map(Hop::appeared_round)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![None, None, None, Some(1), Some(1)], appeared);
+        let sent = trace.hops().iter().map(Hop::total_sent).collect::<Vec<_>>();
+        assert_eq!(vec![2, 2, 2, 1, 1], sent);
+        assert!(trace
+            .hops()
+            .iter()
+            .all(|hop| trace.is_in_round(hop, flow_id)));
+    }
+
+    #[test]
+    fn test_target_ttl_decreasing() {
+        let mut trace = State::new(StateConfig::default());
+        let flow_id = State::default_flow_id();
+        update_with_path(&mut trace, 0, 5);
+        update_with_path(&mut trace, 1, 3);
+        assert_eq!(5, trace.hops().len());
+        assert_eq!(Some(1), trace.target_ttl_changed_round(flow_id));
+        assert!(trace
+            .hops()
+            .iter()
+            .all(|hop| hop.appeared_round().is_none()));
+        let sent = trace.hops().iter().map(Hop::total_sent).collect::<Vec<_>>();
+        assert_eq!(vec![2, 2, 2, 1, 1], sent);
+        let in_round = trace
+            .hops()
+            .iter()
+            .map(|hop| trace.is_in_round(hop, flow_id))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![true, true, true, false, false], in_round);
+        assert_eq!(3, trace.target_hop(flow_id).ttl());
+        update_with_path(&mut trace, 2, 5);
+        assert_eq!(Some(2), trace.target_ttl_changed_round(flow_id));
+        assert!(trace
+            .hops()
+            .iter()
+            .all(|hop| hop.appeared_round().is_none()));
+        assert_eq!(2, trace.hops()[4].total_sent());
+    }
+
+    #[test]
+    fn test_path_changed() {
+        let mut trace = State::new(StateConfig::default());
+        let flow_id = State::default_flow_id();
+        update_with_path(&mut trace, 0, 3);
+        update_with_path(&mut trace, 1, 3);
+        assert_eq!(0, trace.path_changes(flow_id));
+        assert_eq!(None, trace.path_changed(flow_id));
+        let probes = [
+            completed_probe(2, 1, 1),
+            completed_probe(2, 2, 9),
+            completed_probe(2, 3, 3),
+        ];
+        trace.update_from_round(&Round::new(
+            &probes,
+            TimeToLive(3),
+            CompletionReason::TargetFound,
+        ));
+        let ProbeStatus::Complete(latest) = &probes[2] else {
+            unreachable!()
+        };
+        assert_eq!(1, trace.path_changes(flow_id));
+        assert_eq!(Some(latest.received), trace.path_changed(flow_id));
+        update_with_path(&mut trace, 3, 3);
+        assert_eq!(1, trace.path_changes(flow_id));
+        update_with_path(&mut trace, 4, 4);
+        assert_eq!(2, trace.path_changes(flow_id));
+    }
+
+    #[test_case(&[1, 2, 3], FOUND => Vec::<u8>::new(); "none")]
+    #[test_case(&[1, 2, 3, 3, 3], FOUND => vec![4, 5]; "over probed")]
+    #[test_case(&[1, 2, 3, 9, 3], FOUND => vec![5]; "over probed other host")]
+    #[test_case(&[1, 2, 3, 3, 3], NOT_FOUND => Vec::<u8>::new(); "not found")]
+    fn test_over_probed_ttls(hosts: &[u8], reason: CompletionReason) -> Vec<u8> {
+        let mut trace = State::new(StateConfig::default());
+        let probes = hosts
+            .iter()
+            .zip(1..)
+            .map(|(host, ttl)| completed_probe(0, ttl, *host))
+            .collect::<Vec<_>>();
+        trace.update_from_round(&Round::new(&probes, TimeToLive(3), reason));
+        trace.over_probed_ttls(State::default_flow_id()).to_vec()
+    }
+
+    #[test_case(&[false] => false; "not curtailed")]
+    #[test_case(&[true] => true; "curtailed")]
+    #[test_case(&[true, false] => false; "curtailed previous round")]
+    fn test_last_round_curtailed(rounds: &[bool]) -> bool {
+        let mut trace = State::new(StateConfig::default());
+        for curtailed in rounds {
+            trace.update_from_round(
+                &Round::new(&[], TimeToLive(3), NOT_FOUND).with_curtailed(*curtailed),
+            );
+        }
+        trace.last_round_curtailed()
+    }
+
+    #[test]
+    fn test_addr_hops() {
+        let mut trace = State::new(StateConfig::default());
+        for (round, host) in [1, 2, 1].into_iter().enumerate() {
+            let probes = [
+                completed_probe(round, 1, host),
+                completed_probe(round, 2, 9),
+            ];
+            trace.update_from_round(&Round::new(
+                &probes,
+                TimeToLive(2),
+                CompletionReason::TargetFound,
+            ));
+        }
+        let hop = &trace.hops()[0];
+        assert_eq!(3, hop.total_recv());
+        let addr_hops = hop
+            .addr_hops()
+            .map(|(addr, hop)| (*addr, hop.ttl(), hop.total_sent(), hop.total_recv()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (IpAddr::from([10, 0, 0, 1]), 1, 2, 2),
+                (IpAddr::from([10, 0, 0, 2]), 1, 1, 1)
+            ],
+            addr_hops
+        );
+        assert_eq!(1, trace.hops()[1].addr_hops().count());
+    }
+
+    #[test]
+    fn test_discard_first_hop_warmup() {
+        let mut trace = State::new(StateConfig {
+            discard_first_hop_warmup: true,
+            ..StateConfig::default()
+        });
+        update_with_path(&mut trace, 0, 3);
+        update_with_path(&mut trace, 1, 3);
+        let sent = trace.hops().iter().map(Hop::total_sent).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 2], sent);
+        let recv = trace.hops().iter().map(Hop::total_recv).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 2], recv);
+        assert_eq!(1, trace.hops()[0].samples().len());
+        assert_eq!(3, trace.hops_for_flow(FlowId(1)).len());
+        assert_eq!(1, trace.hops_for_flow(FlowId(1))[0].total_sent());
+    }
+
+    #[test]
+    fn test_late_responses() {
+        let mut trace = State::new(StateConfig::default());
+        let late = completed_probe(0, 1, 1);
+        let ProbeStatus::Complete(complete) = &late else {
+            unreachable!()
+        };
+        let awaited = ProbeStatus::Awaited(Probe::new(
+            complete.sequence,
+            complete.identifier,
+            complete.src_port,
+            complete.dest_port,
+            complete.ttl,
+            complete.round,
+            complete.sent,
+            Flags::empty(),
+        ));
+        trace.update_from_round(&Round::new(
+            &[awaited],
+            TimeToLive(1),
+            CompletionReason::RoundTimeLimitExceeded,
+        ));
+        assert_eq!(0, trace.hops()[0].total_recv());
+        trace.update_from_round(
+            &Round::new(
+                &[completed_probe(1, 1, 1)],
+                TimeToLive(1),
+                CompletionReason::TargetFound,
+            )
+            .with_late(&[late]),
+        );
+        let hop = &trace.hops()[0];
+        assert_eq!(2, hop.total_sent());
+        assert_eq!(2, hop.total_recv());
+        assert_eq!(
+            Some(2),
+            hop.addrs_with_counts().next().map(|(_, &count)| count)
+        );
+        assert_eq!(2, hop.samples().len());
+    }
+
+    #[test]
+    fn test_loss_pct() {
+        let mut trace = State::new(StateConfig::default());
+        for (round, responded) in [true, false, true, false, true].into_iter().enumerate() {
+            let probe = if responded {
+                completed_probe(round, 1, 1)
+            } else {
+                awaited_probe(round, 1)
+            };
+            trace.update_from_round(&Round::new(
+                &[probe, completed_probe(round, 2, 2)],
+                TimeToLive(2),
+                CompletionReason::TargetFound,
+            ));
+        }
+        let hop = &trace.hops()[0];
+        assert_eq!(5, hop.total_sent());
+        assert_eq!(3, hop.total_recv());
+        assert_eq_optional(Some(hop.loss_pct()), Some(40_f64));
+        assert_eq_optional(Some(trace.hops()[1].loss_pct()), Some(0_f64));
+    }
+
+    // Probes sent beyond the longest path observed are not counted, such that
+    // the hop does not accrue a loss if the path later grows.
+    //
+    // This test completes a round with the target at ttl 2 in which a probe
+    // for ttl 3 was also sent, followed by a round in which the path grew to
+    // ttl 3.
+    #[test]
+    fn test_probes_beyond_path_not_counted() {
+        let mut trace = State::new(StateConfig::default());
+        trace.update_from_round(&Round::new(
+            &[
+                completed_probe(0, 1, 1),
+                completed_probe(0, 2, 2),
+                awaited_probe(0, 3),
+            ],
+            TimeToLive(2),
+            CompletionReason::TargetFound,
+        ));
+        update_with_path(&mut trace, 1, 3);
+        let hop = &trace.hops()[2];
+        assert_eq!(1, hop.total_sent());
+        assert_eq!(1, hop.total_recv());
+        assert_eq_optional(Some(hop.loss_pct()), Some(0_f64));
+    }
+
+    // A late response for a probe sent beyond the longest path observed is not
+    // counted, such that the hop never receives more responses than probes
+    // sent.
+    //
+    // This test completes a round with the target at ttl 2 in which a probe
+    // for ttl 3 was also sent, followed by a round in which a late response
+    // for that probe was received, followed by a round in which the path grew
+    // to ttl 3.
+    #[test]
+    fn test_late_responses_beyond_path_not_counted() {
+        let mut trace = State::new(StateConfig::default());
+        let ProbeStatus::Complete(late) = completed_probe(0, 3, 3) else {
+            unreachable!()
+        };
+        trace.update_from_round(&Round::new(
+            &[
+                completed_probe(0, 1, 1),
+                completed_probe(0, 2, 2),
+                awaited_probe(0, 3),
+            ],
+            TimeToLive(2),
+            CompletionReason::TargetFound,
+        ));
+        trace.update_from_round(
+            &Round::new(
+                &[completed_probe(1, 1, 1), completed_probe(1, 2, 2)],
+                TimeToLive(2),
+                CompletionReason::TargetFound,
+            )
+            .with_late(&[ProbeStatus::Complete(late)]),
+        );
+        update_with_path(&mut trace, 2, 3);
+        let hop = &trace.hops()[2];
+        assert_eq!(1, hop.total_sent());
+        assert_eq!(1, hop.total_recv());
+        assert_eq_optional(Some(hop.loss_pct()), Some(0_f64));
+    }
+
+    /// Update the state with a round in which every hop up to the target responded.
+    fn update_with_path(trace: &mut State, round: usize, target_ttl: u8) {
+        let probes = (1..=target_ttl)
+            .map(|ttl| completed_probe(round, ttl, ttl))
+            .collect::<Vec<_>>();
+        trace.update_from_round(&Round::new(
+            &probes,
+            TimeToLive(target_ttl),
+            CompletionReason::TargetFound,
+        ));
+    }
+
+    /// A probe at `ttl` which is awaiting a response.
+    fn awaited_probe(round: usize, ttl: u8) -> ProbeStatus {
+        ProbeStatus::Awaited(Probe::new(
+            Sequence(u16::from(ttl)),
+            TraceId(0),
+            Port(0),
+            Port(0),
+            TimeToLive(ttl),
+            RoundId(round),
+            SystemTime::now(),
+            Flags::empty(),
+        ))
+    }
+
+    /// A probe at `ttl` which was answered by host `10.0.0.{host}`.
+    fn completed_probe(round: usize, ttl: u8, host: u8) -> ProbeStatus {
+        let sent = SystemTime::now();
+        ProbeStatus::Complete(
+            Probe::new(
+                Sequence(u16::from(ttl)),
+                TraceId(0),
+                Port(0),
+                Port(0),
+                TimeToLive(ttl),
+                RoundId(round),
+                sent,
+                Flags::empty(),
+            )
+            .complete(
+                IpAddr::from([10, 0, 0, host]),
+                sent.add(Duration::from_millis(u64::from(ttl))),
+                IcmpPacketType::NotApplicable,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
+        )
+    }
+
     #[test_case(file!("ipv4_3probes_3hops_mixed_multi.yaml"))]
     #[test_case(file!("ipv4_3probes_3hops_completed.yaml"))]
     #[test_case(file!("ipv4_4probes_all_status.yaml"))]
@@ -856,6 +1730,86 @@ mod tests {
         }
     }
 
+    #[test_case(3 => (3, 2, Some(10.0), Some(30.0), 20.0); "within samples")]
+    #[test_case(100 => (4, 3, Some(10.0), Some(30.0), 20.0); "beyond samples")]
+    #[test_case(1 => (1, 1, Some(10.0), Some(10.0), 10.0); "single round")]
+    #[test_case(0 => (0, 0, None, None, 0.0); "empty")]
+    fn test_windowed(rounds: usize) -> (usize, usize, Option<f64>, Option<f64>, f64) {
+        let ms = Duration::from_millis;
+        let hop = Hop {
+            total_sent: 10,
+            total_recv: 3,
+            samples: vec![ms(10), Duration::ZERO, ms(30), ms(20)],
+            ..Hop::default()
+        };
+        let windowed = hop.windowed(rounds);
+        (
+            windowed.total_sent(),
+            windowed.total_recv(),
+            windowed.best_ms(),
+            windowed.worst_ms(),
+            windowed.avg_ms(),
+        )
+    }
+
+    #[test_case(&[10, 20], 7.071, 5.0; "two samples")]
+    #[test_case(&[2, 4, 4, 4, 5, 5, 7, 9], 2.138, 2.0; "many samples")]
+    #[test_case(&[10, 0, 10], 0.0, 0.0; "constant samples")]
+    #[test_case(&[10], 0.0, 0.0; "single sample")]
+    #[test_case(&[], 0.0, 0.0; "empty")]
+    fn test_stddev(samples: &[u64], expected_sample: f64, expected_population: f64) {
+        let hop = Hop {
+            samples: samples
+                .iter()
+                .map(|&ms| Duration::from_millis(ms))
+                .collect(),
+            ..Hop::default()
+        }
+        .windowed(samples.len());
+        let round = |value: f64| (value * 1000_f64).round() / 1000_f64;
+        assert_eq_optional(Some(round(hop.stddev_ms())), Some(expected_sample));
+        assert_eq_optional(
+            Some(round(hop.population_stddev_ms())),
+            Some(expected_population),
+        );
+    }
+
+    #[test_case(&[10, 0, 30, 20], 0.5, 17.5; "half weight")]
+    #[test_case(&[10, 0, 30, 20], 1.0, 10.0; "full weight")]
+    #[test_case(&[10, 0, 30, 20], 0.1, 19.9; "low weight")]
+    #[test_case(&[0, 0], 0.5, 0.0; "no responses")]
+    #[test_case(&[], 0.5, 0.0; "empty")]
+    fn test_ewma(samples: &[u64], weight: f64, expected: f64) {
+        let hop = Hop {
+            samples: samples
+                .iter()
+                .map(|&ms| Duration::from_millis(ms))
+                .collect(),
+            ..Hop::default()
+        };
+        let actual = (hop.ewma_ms(weight) * 1000_f64).round() / 1000_f64;
+        assert_eq_optional(Some(actual), Some(expected));
+    }
+
+    #[test_case(&[10, 0, 30, 20], 0.5, 25.0; "half weight")]
+    #[test_case(&[10, 0, 30, 20], 1.0, 0.0; "full weight")]
+    #[test_case(&[0, 10, 30, 20], 1.0, 100.0; "full weight lost")]
+    #[test_case(&[0, 0, 30, 20], 0.1, 19.0; "low weight")]
+    #[test_case(&[0, 0], 0.5, 100.0; "no responses")]
+    #[test_case(&[10, 20], 0.5, 0.0; "no loss")]
+    #[test_case(&[], 0.5, 0.0; "empty")]
+    fn test_recent_loss_pct(samples: &[u64], weight: f64, expected: f64) {
+        let hop = Hop {
+            samples: samples
+                .iter()
+                .map(|&ms| Duration::from_millis(ms))
+                .collect(),
+            ..Hop::default()
+        };
+        let actual = (hop.recent_loss_pct(weight) * 1000_f64).round() / 1000_f64;
+        assert_eq_optional(Some(actual), Some(expected));
+    }
+
     fn assert_eq_optional(actual: Option<f64>, expected: Option<f64>) {
         match (actual, expected) {
             (Some(actual), Some(expected)) if (expected - actual).abs() < f64::EPSILON => {}
@@ -1,5 +1,6 @@
 use self::state::TracerState;
 use crate::config::StrategyConfig;
+use crate::constants::MIN_ADAPTIVE_READ_TIMEOUT;
 use crate::error::{Error, Result};
 use crate::net::Network;
 use crate::probe::{
@@ -21,6 +22,17 @@ pub struct Round<'a> {
     pub largest_ttl: TimeToLive,
     /// Indicates what triggered the completion of the tracing round.
     pub reason: CompletionReason,
+    /// The number of probes whose send was deferred by the rate limiter in the round.
+    ///
+    /// Each probe is counted once, however many times its send was deferred.
+    pub suppressed: usize,
+    /// The `ProbeStatus` of probes from the previous round which received a late response.
+    pub late: &'a [ProbeStatus],
+    /// The adaptive read timeout at the end of the round, if enabled.
+    pub read_timeout: Option<Duration>,
+    /// Whether a probe was not sent in the round because the maximum number of consecutive
+    /// unresponsive hops was reached.
+    pub curtailed: bool,
 }
 
 impl<'a> Round<'a> {
@@ -34,8 +46,39 @@ impl<'a> Round<'a> {
             probes,
             largest_ttl,
             reason,
+            suppressed: 0,
+            late: &[],
+            read_timeout: None,
+            curtailed: false,
+        }
+    }
+
+    /// Include the number of probes whose send was deferred by the rate limiter in the round.
+    #[must_use]
+    pub const fn with_suppressed(self, suppressed: usize) -> Self {
+        Self { suppressed, ..self }
+    }
+
+    /// Include the probes from the previous round which received a late response.
+    #[must_use]
+    pub const fn with_late(self, late: &'a [ProbeStatus]) -> Self {
+        Self { late, ..self }
+    }
+
+    /// Include the adaptive read timeout at the end of the round.
+    #[must_use]
+    pub const fn with_read_timeout(self, read_timeout: Option<Duration>) -> Self {
+        Self {
+            read_timeout,
+            ..self
         }
     }
+
+    /// Include whether probing was curtailed by the maximum number of unresponsive hops.
+    #[must_use]
+    pub const fn with_curtailed(self, curtailed: bool) -> Self {
+        Self { curtailed, ..self }
+    }
 }
 
 /// Indicates what triggered the completion of the tracing round.
@@ -45,13 +88,39 @@ pub enum CompletionReason {
     TargetFound,
     /// The round ended because the time exceeded the configured maximum round time.
     RoundTimeLimitExceeded,
+    /// The round ended because the time exceeded the configured maximum round time after the
+    /// target was found but before the grace period elapsed.
+    ///
+    /// Responses for probes of the round which were still awaited may have been missed.
+    TargetFoundRoundTimeLimitExceeded,
+}
+
+impl CompletionReason {
+    /// Whether the target was found in the round.
+    #[must_use]
+    pub const fn target_found(self) -> bool {
+        matches!(
+            self,
+            Self::TargetFound | Self::TargetFoundRoundTimeLimitExceeded
+        )
+    }
 }
 
 /// Trace a path to a target.
 #[derive(Debug, Clone)]
-pub struct Strategy<F> {
+pub struct Strategy<
+    F,
+    P = fn(TimeToLive) -> bool,
+    I = fn(&[ProbeStatus]),
+    S = fn() -> bool,
+    H = fn() -> bool,
+> {
     config: StrategyConfig,
     publish: F,
+    is_paused: P,
+    in_progress: I,
+    is_stopped: S,
+    is_suspended: H,
 }
 
 impl<F: Fn(&Round<'_>)> Strategy<F> {
@@ -61,17 +130,110 @@ impl<F: Fn(&Round<'_>)> Strategy<F> {
         Self {
             config: *config,
             publish,
+            is_paused: |_| false,
+            in_progress: |_| (),
+            is_stopped: || false,
+            is_suspended: || false,
+        }
+    }
+}
+
+impl<
+        F: Fn(&Round<'_>),
+        P: Fn(TimeToLive) -> bool,
+        I: Fn(&[ProbeStatus]),
+        S: Fn() -> bool,
+        H: Fn() -> bool,
+    > Strategy<F, P, I, S, H>
+{
+    /// Skip sending probes for any time-to-live (ttl) for which `is_paused` returns `true`.
+    ///
+    /// The predicate is checked each time a probe would be sent and so may change during a trace.
+    pub fn with_paused<Q: Fn(TimeToLive) -> bool>(self, is_paused: Q) -> Strategy<F, Q, I, S, H> {
+        Strategy {
+            config: self.config,
+            publish: self.publish,
+            is_paused,
+            in_progress: self.in_progress,
+            is_stopped: self.is_stopped,
+            is_suspended: self.is_suspended,
+        }
+    }
+
+    /// Publish the probes of the round in progress whenever a probe is sent or a response is
+    /// received, such that probes which are awaiting a response can be observed before the round
+    /// completes.
+    pub fn with_in_progress<J: Fn(&[ProbeStatus])>(
+        self,
+        in_progress: J,
+    ) -> Strategy<F, P, J, S, H> {
+        Strategy {
+            config: self.config,
+            publish: self.publish,
+            is_paused: self.is_paused,
+            in_progress,
+            is_stopped: self.is_stopped,
+            is_suspended: self.is_suspended,
+        }
+    }
+
+    /// Stop the trace once `is_stopped` returns `true`.
+    ///
+    /// The predicate is checked before each iteration of the trace loop, such that the trace stops
+    /// within one iteration of it returning `true`, and the network is dropped normally.
+    pub fn with_stopped<T: Fn() -> bool>(self, is_stopped: T) -> Strategy<F, P, I, T, H> {
+        Strategy {
+            config: self.config,
+            publish: self.publish,
+            is_paused: self.is_paused,
+            in_progress: self.in_progress,
+            is_stopped,
+            is_suspended: self.is_suspended,
+        }
+    }
+
+    /// Suspend the trace while `is_suspended` returns `true`.
+    ///
+    /// No probes are sent and the round in progress is not completed while suspended, however
+    /// responses continue to be received such that responses to probes already sent are recorded.
+    /// Once resumed, the round in progress completes as normal and so includes any responses
+    /// received while suspended.
+    pub fn with_suspended<T: Fn() -> bool>(self, is_suspended: T) -> Strategy<F, P, I, S, T> {
+        Strategy {
+            config: self.config,
+            publish: self.publish,
+            is_paused: self.is_paused,
+            in_progress: self.in_progress,
+            is_stopped: self.is_stopped,
+            is_suspended,
         }
     }
 
     /// Run a continuous trace and publish results.
     #[instrument(skip(self, network))]
     pub fn run<N: Network>(self, mut network: N) -> Result<()> {
+        if self.config.ignore_identifier {
+            tracing::warn!(
+                "ignoring response identifiers, responses may be misattributed between tracers"
+            );
+        }
         let mut state = TracerState::new(self.config);
-        while !state.finished(self.config.max_rounds) {
-            self.send_request(&mut network, &mut state)?;
-            self.recv_response(&mut network, &mut state)?;
-            self.update_round(&mut state);
+        while !state.finished(self.config.max_rounds) && !(self.is_stopped)() {
+            let sent = state.probes().len();
+            let suspended = (self.is_suspended)();
+            let start = SystemTime::now();
+            if !suspended {
+                self.send_request(&mut network, &mut state)?;
+            }
+            let received = self.recv_response(&mut network, &mut state)?;
+            if received || state.probes().len() != sent {
+                (self.in_progress)(state.probes());
+            }
+            if suspended {
+                state.record_suspended(start.elapsed().unwrap_or_default());
+            } else {
+                self.update_round(&mut state);
+            }
         }
         Ok(())
     }
@@ -87,17 +249,45 @@ impl<F: Fn(&Round<'_>)> Strategy<F> {
     ///         round
     ///     otherwise:
     ///       - the number of unknown-in-flight probes is lower than the maximum allowed
+    ///
+    /// If a minimum probe interval is configured and it has not yet elapsed since the previous
+    /// probe was sent then the send is suppressed and recorded as such.
+    ///
+    /// If a maximum number of unresponsive hops is configured then the next ttl must also not be
+    /// more than that number beyond the highest ttl from which a response has been received in any
+    /// round.  If this alone prevents a probe from being sent then probing is recorded as curtailed
+    /// for the round.
+    ///
+    /// If the next ttl is paused then no probe is sent for it and the ttl is skipped.
+    ///
+    /// If a send batch size greater than one is configured, and no minimum probe interval is
+    /// configured, then `icmp` and `udp` probes are sent in batches.
+    ///
+    /// If a send jitter is configured then we sleep for a random duration of up to the send jitter
+    /// before each probe, or batch of probes, is sent.
     #[instrument(skip(self, network, st))]
     fn send_request<N: Network>(&self, network: &mut N, st: &mut TracerState) -> Result<()> {
-        let can_send_ttl = if let Some(target_ttl) = st.target_ttl() {
-            st.ttl() <= target_ttl
-        } else {
-            st.ttl() - st.max_received_ttl().unwrap_or_default()
-                < TimeToLive(self.config.max_inflight.0)
-        };
-        if !st.target_found() && st.ttl() <= self.config.max_ttl && can_send_ttl {
+        if self.can_send(st) {
+            if (self.is_paused)(st.ttl()) {
+                st.skip_ttl();
+                return Ok(());
+            }
             let sent = SystemTime::now();
+            if let (Some(interval), Some(last_sent)) =
+                (self.config.min_probe_interval, st.last_sent_time())
+            {
+                if sent.duration_since(last_sent).unwrap_or_default() < interval {
+                    st.suppress_probe();
+                    return Ok(());
+                }
+            }
+            let sent = self.send_jitter(st, sent);
+            let batched =
+                self.config.send_batch_size > 1 && self.config.min_probe_interval.is_none();
             match self.config.protocol {
+                Protocol::Icmp | Protocol::Udp if batched => {
+                    self.send_batch(network, st, sent)?;
+                }
                 Protocol::Icmp => {
                     let probe = st.next_probe(sent);
                     Self::do_send(network, st, probe)?;
@@ -126,19 +316,118 @@ impl<F: Fn(&Round<'_>)> Strategy<F> {
                     }
                 }
             };
+        } else if self.is_curtailed(st) {
+            st.curtail_probing();
+        }
+        Ok(())
+    }
+
+    /// Can a probe be sent for the next time-to-live (ttl)?
+    ///
+    /// See `send_request` for details.
+    fn can_send(&self, st: &TracerState) -> bool {
+        let can_send_ttl = if let Some(target_ttl) = st.target_ttl() {
+            st.ttl() <= target_ttl
+        } else {
+            st.ttl() - st.max_received_ttl().unwrap_or_default()
+                < TimeToLive(self.config.max_inflight.0)
+        };
+        !st.target_found()
+            && st.ttl() <= self.config.max_ttl
+            && can_send_ttl
+            && self.is_responsive_ttl(st)
+    }
+
+    /// Is the next ttl within the maximum number of consecutive unresponsive hops, if configured?
+    ///
+    /// The number of unresponsive hops is measured from the highest ttl from which a response
+    /// has been received in any round, or from the ttl before the first ttl if no response has
+    /// yet been received.
+    fn is_responsive_ttl(&self, st: &TracerState) -> bool {
+        self.config.max_unresponsive_hops.map_or(true, |max| {
+            let responsive_ttl = st
+                .highest_received_ttl()
+                .unwrap_or_default()
+                .0
+                .max(self.config.first_ttl.0.saturating_sub(1));
+            st.ttl().0 <= responsive_ttl.saturating_add(max)
+        })
+    }
+
+    /// Is sending a probe for the next ttl prevented only by the maximum number of consecutive
+    /// unresponsive hops?
+    fn is_curtailed(&self, st: &TracerState) -> bool {
+        !st.target_found() && st.ttl() <= self.config.max_ttl && !self.is_responsive_ttl(st)
+    }
+
+    /// Sleep for a random duration of up to the configured send jitter.
+    ///
+    /// Returns the time after sleeping, or `sent` if no send jitter is configured.
+    fn send_jitter(&self, st: &mut TracerState, sent: SystemTime) -> SystemTime {
+        match self.config.send_jitter {
+            Some(max) if !max.is_zero() => {
+                std::thread::sleep(st.next_jitter(max));
+                SystemTime::now()
+            }
+            _ => sent,
+        }
+    }
+
+    /// Send a batch of probes for the next and subsequent time-to-live (ttl) values.
+    ///
+    /// Probes are added to the batch, up to the configured batch size, for as long as a probe could
+    /// be sent for the next ttl and paused ttls are skipped.  The batch is sent as soon as it has
+    /// been assembled and so no probe is held back beyond the end of a round.
+    ///
+    /// All probes in the batch are recorded as sent at the time the batch was sent, and so the
+    /// recorded send time of the earlier probes in a batch may be slightly later than the time at
+    /// which they were actually sent.
+    ///
+    /// Transient errors are handled for each probe as for `do_send`.
+    fn send_batch<N: Network>(
+        &self,
+        network: &mut N,
+        st: &mut TracerState,
+        sent: SystemTime,
+    ) -> Result<()> {
+        let batch_size = usize::from(self.config.send_batch_size);
+        let mut probes = Vec::with_capacity(batch_size);
+        probes.push(st.next_probe(sent));
+        while probes.len() < batch_size && self.can_send(st) {
+            if (self.is_paused)(st.ttl()) {
+                st.skip_ttl();
+            } else {
+                probes.push(st.next_probe(sent));
+            }
+        }
+        let results = network.send_probes(&probes);
+        let sent = SystemTime::now();
+        for (probe, result) in probes.iter().zip(results) {
+            match result {
+                Ok(()) => st.update_sent(probe.sequence, sent),
+                Err(Error::ProbeFailed(_)) => st.fail_probe(probe.sequence),
+                Err(err) => return Err(err),
+            }
         }
         Ok(())
     }
 
     /// Send the probe and handle errors.
     ///
+    /// The send time of the probe is updated once it has been sent, such that the time taken to
+    /// build and send the probe is not included in the round-trip time.
+    ///
     /// Some errors are transient and should not be considered fatal.  In these cases we mark the
     /// probe as failed and continue.
     fn do_send<N: Network>(network: &mut N, st: &mut TracerState, probe: Probe) -> Result<()> {
+        let sequence = probe.sequence;
         match network.send_probe(probe) {
-            Ok(()) => Ok(()),
+            Ok(()) => {
+                st.update_sent(sequence, SystemTime::now());
+                Ok(())
+            }
             Err(Error::ProbeFailed(_)) => {
-                st.fail_probe();
+                st.fail_probe(sequence);
                 Ok(())
             }
             Err(err) => Err(err),
@@ -164,18 +453,47 @@ impl<F: Fn(&Round<'_>)> Strategy<F> {
     /// corresponding original `EchoRequest`.  Note that this may not be the greatest
     /// time-to-live that was sent in the round as the algorithm will send `EchoRequest` with
     /// larger time-to-live values before the `EchoReply` is received.
+    ///
+    /// Returns true if a response was received for a probe in the current round.
     #[instrument(skip(self, network, st))]
-    fn recv_response<N: Network>(&self, network: &mut N, st: &mut TracerState) -> Result<()> {
+    fn recv_response<N: Network>(&self, network: &mut N, st: &mut TracerState) -> Result<bool> {
+        if let Some(read_timeout) = self.read_timeout(st) {
+            network.set_read_timeout(read_timeout);
+        }
         let next = network.recv_probe()?;
         if let Some(resp) = next {
             if self.validate(resp.data()) {
                 let resp = StrategyResponse::from((resp, &self.config));
-                if self.check_trace_id(resp.trace_id) && st.in_round(resp.sequence) {
-                    st.complete_probe(resp);
+                if self.check_trace_id(resp.trace_id, resp.sent.is_some()) {
+                    if st.in_round(resp.sequence) {
+                        st.complete_probe(resp);
+                        return Ok(true);
+                    } else if self.config.accept_late_responses && st.in_prev_round(resp.sequence) {
+                        st.complete_late_probe(resp);
+                    }
                 }
             }
         }
-        Ok(())
+        Ok(false)
+    }
+
+    /// The adaptive read timeout, if enabled and any response has been received.
+    ///
+    /// The timeout is no shorter than `MIN_ADAPTIVE_READ_TIMEOUT` and no longer than the maximum
+    /// round duration.
+    fn read_timeout(&self, st: &TracerState) -> Option<Duration> {
+        if self.config.adaptive_read_timeout {
+            st.rtt_estimate().timeout().map(|timeout| {
+                timeout.clamp(
+                    MIN_ADAPTIVE_READ_TIMEOUT,
+                    self.config
+                        .max_round_duration
+                        .max(MIN_ADAPTIVE_READ_TIMEOUT),
+                )
+            })
+        } else {
+            None
+        }
     }
 
     /// Check if the round is complete and publish the results.
@@ -187,16 +505,32 @@ impl<F: Fn(&Round<'_>)> Strategy<F> {
     /// 3 - either:
     ///     A - the target has been found OR
     ///     B - the target has not been found and the round has exceeded the maximum round duration
+    ///
+    /// The time spent sleeping for the send jitter, and the time spent suspended, is not included
+    /// in the duration of the round.
     #[instrument(skip(self, st))]
     fn update_round(&self, st: &mut TracerState) {
         let now = SystemTime::now();
-        let round_duration = now.duration_since(st.round_start()).unwrap_or_default();
+        let round_duration = now
+            .duration_since(st.round_start())
+            .unwrap_or_default()
+            .saturating_sub(st.round_jitter())
+            .saturating_sub(st.round_suspended());
         let round_min = round_duration > self.config.min_round_duration;
         let grace_exceeded = exceeds(st.received_time(), now, self.config.grace_duration);
         let round_max = round_duration > self.config.max_round_duration;
         let target_found = st.target_found();
-        if round_min && grace_exceeded && target_found || round_max {
-            self.publish_trace(st);
+        let reason = if round_min && grace_exceeded && target_found {
+            Some(CompletionReason::TargetFound)
+        } else if round_max && target_found {
+            Some(CompletionReason::TargetFoundRoundTimeLimitExceeded)
+        } else if round_max {
+            Some(CompletionReason::RoundTimeLimitExceeded)
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            self.publish_trace(st, reason);
             st.advance_round(self.config.first_ttl);
         }
     }
@@ -206,33 +540,37 @@ impl<F: Fn(&Round<'_>)> Strategy<F> {
     /// If the round completed without receiving an `EchoReply` from the target host then we also
     /// publish the next `ProbeStatus` which is assumed to represent the TTL of the target host.
     #[instrument(skip(self, state))]
-    fn publish_trace(&self, state: &TracerState) {
-        let max_received_ttl = if let Some(target_ttl) = state.target_ttl() {
-            target_ttl
-        } else {
-            state
-                .max_received_ttl()
-                .map_or(TimeToLive(0), |max_received_ttl| {
-                    let max_sent_ttl = state.ttl() - TimeToLive(1);
-                    max_sent_ttl.min(max_received_ttl + TimeToLive(1))
-                })
-        };
+    fn publish_trace(&self, state: &TracerState, reason: CompletionReason) {
+        let largest_ttl = round_largest_ttl(
+            self.config.first_ttl,
+            state.ttl(),
+            state.max_received_ttl(),
+            state.target_ttl(),
+        );
         let probes = state.probes();
-        let largest_ttl = max_received_ttl;
-        let reason = if state.target_found() {
-            CompletionReason::TargetFound
-        } else {
-            CompletionReason::RoundTimeLimitExceeded
-        };
-        (self.publish)(&Round::new(probes, largest_ttl, reason));
+        (self.publish)(
+            &Round::new(probes, largest_ttl, reason)
+                .with_suppressed(state.suppressed())
+                .with_late(state.late())
+                .with_read_timeout(self.read_timeout(state))
+                .with_curtailed(state.curtailed()),
+        );
     }
 
     /// Check if the `TraceId` matches the expected value for this tracer.
     ///
     /// A special value of `0` is accepted for `udp` and `tcp` which do not have an identifier.
+    ///
+    /// If `ignore_identifier` is set then the `TraceId` echoed in the headers of the response is
+    /// not trusted.  If the response is `stamped`, that is the quoted payload of the original
+    /// probe carries a `PayloadStamp`, then the `TraceId` is read from the stamp, which is not
+    /// rewritten in transit, and is still checked.  Otherwise any `TraceId` is accepted and
+    /// responses are correlated using the sequence number only.
     #[instrument(skip(self))]
-    fn check_trace_id(&self, trace_id: TraceId) -> bool {
-        self.config.trace_identifier == trace_id || trace_id == TraceId(0)
+    fn check_trace_id(&self, trace_id: TraceId, stamped: bool) -> bool {
+        (self.config.ignore_identifier && !stamped)
+            || self.config.trace_identifier == trace_id
+            || trace_id == TraceId(0)
     }
 
     /// Validate the probe response data.
@@ -300,10 +638,13 @@ struct StrategyResponse {
     sequence: Sequence,
     expected_udp_checksum: Option<Checksum>,
     actual_udp_checksum: Option<Checksum>,
+    sent: Option<SystemTime>,
     received: SystemTime,
     addr: IpAddr,
     is_target: bool,
     exts: Option<Extensions>,
+    reply_ttl: Option<u8>,
+    quoted_len: Option<u16>,
 }
 
 impl From<(Response, &StrategyConfig)> for StrategyResponse {
@@ -318,10 +659,13 @@ impl From<(Response, &StrategyConfig)> for StrategyResponse {
                     sequence: resp_seq.sequence,
                     expected_udp_checksum: resp_seq.expected_udp_checksum,
                     actual_udp_checksum: resp_seq.actual_udp_checksum,
+                    sent: resp_seq.sent,
                     received: data.recv,
                     addr: data.addr,
                     is_target,
                     exts,
+                    reply_ttl: data.ttl,
+                    quoted_len: data.quoted_len,
                 }
             }
             Response::DestinationUnreachable(data, code, exts) => {
@@ -333,10 +677,13 @@ impl From<(Response, &StrategyConfig)> for StrategyResponse {
                     sequence: resp_seq.sequence,
                     expected_udp_checksum: resp_seq.expected_udp_checksum,
                     actual_udp_checksum: resp_seq.actual_udp_checksum,
+                    sent: resp_seq.sent,
                     received: data.recv,
                     addr: data.addr,
                     is_target,
                     exts,
+                    reply_ttl: data.ttl,
+                    quoted_len: data.quoted_len,
                 }
             }
             Response::EchoReply(data, code) => {
@@ -347,10 +694,13 @@ impl From<(Response, &StrategyConfig)> for StrategyResponse {
                     sequence: resp_seq.sequence,
                     expected_udp_checksum: resp_seq.expected_udp_checksum,
                     actual_udp_checksum: resp_seq.actual_udp_checksum,
+                    sent: resp_seq.sent,
                     received: data.recv,
                     addr: data.addr,
                     is_target: true,
                     exts: None,
+                    reply_ttl: data.ttl,
+                    quoted_len: data.quoted_len,
                 }
             }
             Response::TcpReply(data) | Response::TcpRefused(data) => {
@@ -361,10 +711,13 @@ impl From<(Response, &StrategyConfig)> for StrategyResponse {
                     sequence: resp_seq.sequence,
                     expected_udp_checksum: resp_seq.expected_udp_checksum,
                     actual_udp_checksum: resp_seq.actual_udp_checksum,
+                    sent: resp_seq.sent,
                     received: data.recv,
                     addr: data.addr,
                     is_target: true,
                     exts: None,
+                    reply_ttl: data.ttl,
+                    quoted_len: data.quoted_len,
                 }
             }
         }
@@ -378,6 +731,7 @@ struct StrategyResponseSeq {
     sequence: Sequence,
     expected_udp_checksum: Option<Checksum>,
     actual_udp_checksum: Option<Checksum>,
+    sent: Option<SystemTime>,
 }
 
 impl From<(ResponseSeq, &StrategyConfig)> for StrategyResponseSeq {
@@ -386,11 +740,22 @@ impl From<(ResponseSeq, &StrategyConfig)> for StrategyResponseSeq {
             ResponseSeq::Icmp(ResponseSeqIcmp {
                 identifier,
                 sequence,
-            }) => Self {
-                trace_id: TraceId(identifier),
-                sequence: Sequence(sequence),
-                expected_udp_checksum: None,
-                actual_udp_checksum: None,
+                stamp,
+            }) => match stamp {
+                Some(stamp) => Self {
+                    trace_id: TraceId(stamp.identifier),
+                    sequence: Sequence(stamp.sequence),
+                    expected_udp_checksum: None,
+                    actual_udp_checksum: None,
+                    sent: Some(stamp.sent),
+                },
+                None => Self {
+                    trace_id: TraceId(identifier),
+                    sequence: Sequence(sequence),
+                    expected_udp_checksum: None,
+                    actual_udp_checksum: None,
+                    sent: None,
+                },
             },
             ResponseSeq::Udp(ResponseSeqUdp {
                 identifier,
@@ -429,6 +794,7 @@ impl From<(ResponseSeq, &StrategyConfig)> for StrategyResponseSeq {
                     sequence: Sequence(sequence),
                     expected_udp_checksum,
                     actual_udp_checksum,
+                    sent: None,
                 }
             }
             ResponseSeq::Tcp(ResponseSeqTcp {
@@ -445,6 +811,7 @@ impl From<(ResponseSeq, &StrategyConfig)> for StrategyResponseSeq {
                     sequence: Sequence(sequence),
                     expected_udp_checksum: None,
                     actual_udp_checksum: None,
+                    sent: None,
                 }
             }
         }
@@ -454,11 +821,15 @@ impl From<(ResponseSeq, &StrategyConfig)> for StrategyResponseSeq {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::IoError;
     use crate::net::MockNetwork;
-    use crate::probe::IcmpPacketCode;
+    use crate::probe::{IcmpPacketCode, PayloadStamp};
     use crate::{MaxRounds, Port};
-    use std::net::Ipv4Addr;
+    use std::net::{Ipv4Addr, SocketAddr};
     use std::num::NonZeroUsize;
+    use test_case::test_case;
+
+    const ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0);
 
     #[test]
     fn test_time_exceeded_target_response() {
@@ -597,10 +968,27 @@ mod tests {
         let resp_seq = ResponseSeq::Icmp(ResponseSeqIcmp {
             identifier: 1234,
             sequence: 33434,
+            stamp: None,
+        });
+        let strategy_resp = StrategyResponseSeq::from((resp_seq, &config));
+        assert_eq!(strategy_resp.trace_id, TraceId(1234));
+        assert_eq!(strategy_resp.sequence, Sequence(33434));
+        assert_eq!(strategy_resp.sent, None);
+    }
+
+    #[test]
+    fn test_icmp_response_stamped() {
+        let config = StrategyConfig::default();
+        let sent = SystemTime::UNIX_EPOCH + Duration::from_secs(1);
+        let resp_seq = ResponseSeq::Icmp(ResponseSeqIcmp {
+            identifier: 0,
+            sequence: 0,
+            stamp: Some(PayloadStamp::new(1234, 33434, sent)),
         });
         let strategy_resp = StrategyResponseSeq::from((resp_seq, &config));
         assert_eq!(strategy_resp.trace_id, TraceId(1234));
         assert_eq!(strategy_resp.sequence, Sequence(33434));
+        assert_eq!(strategy_resp.sent, Some(sent));
     }
 
     #[test]
@@ -625,6 +1013,41 @@ mod tests {
         assert_eq!(strategy_resp.sequence, Sequence(33434));
     }
 
+    // The sequence number of a classic udp probe is encoded in a port of the
+    // probe and decoded from the same port of the original datagram quoted
+    // in the response.
+    //
+    // This test creates probes for several rounds and asserts that the
+    // sequence decoded from the ports of each probe is that of the probe.
+    #[test_case(PortDirection::FixedSrc(Port(5000)); "fixed src")]
+    #[test_case(PortDirection::FixedDest(Port(5000)); "fixed dest")]
+    fn test_udp_classic_port_sequence_round_trip(port_direction: PortDirection) {
+        let config = StrategyConfig {
+            protocol: Protocol::Udp,
+            port_direction,
+            ..Default::default()
+        };
+        let mut state = TracerState::new(config);
+        for _ in 0..3 {
+            for _ in 0..10 {
+                let probe = state.next_probe(SystemTime::now());
+                let resp_seq = ResponseSeq::Udp(ResponseSeqUdp {
+                    identifier: 0,
+                    dest_addr: config.target_addr,
+                    src_port: probe.src_port.0,
+                    dest_port: probe.dest_port.0,
+                    expected_udp_checksum: 0,
+                    actual_udp_checksum: 0,
+                    payload_len: 0,
+                    has_magic: false,
+                });
+                let strategy_resp = StrategyResponseSeq::from((resp_seq, &config));
+                assert_eq!(probe.sequence, strategy_resp.sequence);
+            }
+            state.advance_round(config.first_ttl);
+        }
+    }
+
     #[test]
     fn test_udp_classic_fixed_dest_response() {
         let config = StrategyConfig {
@@ -787,6 +1210,8 @@ mod tests {
                         SystemTime::now(),
                         target_addr,
                         ResponseSeq::Tcp(ResponseSeqTcp::new(target_addr, sequence, 80)),
+                        None,
+                        None,
                     ),
                     IcmpPacketCode(1),
                     None,
@@ -801,6 +1226,8 @@ mod tests {
                     SystemTime::now(),
                     target_addr,
                     ResponseSeq::Tcp(ResponseSeqTcp::new(target_addr, sequence, 80)),
+                    None,
+                    None,
                 ))))
             });
 
@@ -820,93 +1247,766 @@ mod tests {
         Ok(())
     }
 
-    const fn response_data(now: SystemTime) -> ResponseData {
-        ResponseData::new(
-            now,
-            IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)),
-            ResponseSeq::Icmp(ResponseSeqIcmp {
-                identifier: 0,
-                sequence: 33434,
-            }),
-        )
+    // Sending is suppressed by the rate limiter if the minimum probe interval
+    // has not elapsed since the previous probe was sent.
+    //
+    // This test attempts to send 3 probes in quick succession with a large
+    // minimum probe interval and asserts that only the first is sent and that
+    // the deferred probe for ttl 2 is counted once.
+    #[test]
+    fn test_min_probe_interval_suppresses_send() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network.expect_send_probe().times(1).returning(|_| Ok(()));
+        let config = StrategyConfig {
+            min_probe_interval: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let tracer = Strategy::new(&config, |_| {});
+        let mut state = TracerState::new(config);
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        assert_eq!(1, state.probes().len());
+        assert_eq!(1, state.suppressed());
+        state.advance_round(TimeToLive(1));
+        assert_eq!(0, state.suppressed());
+        Ok(())
     }
-}
 
-/// Mutable state needed for the tracing algorithm.
-///
-/// This is contained within a submodule to ensure that mutations are only performed via methods on
-/// the `TracerState` struct.
-mod state {
-    use crate::constants::MAX_SEQUENCE_PER_ROUND;
-    use crate::probe::{Probe, ProbeStatus};
-    use crate::strategy::{StrategyConfig, StrategyResponse};
-    use crate::types::{MaxRounds, Port, RoundId, Sequence, TimeToLive, TraceId};
-    use crate::{Flags, MultipathStrategy, PortDirection, Protocol};
-    use std::array::from_fn;
-    use std::net::IpAddr;
-    use std::time::SystemTime;
-    use tracing::instrument;
+    // Each deferred ttl is counted once, no matter how many times its send is
+    // deferred.
+    //
+    // This test defers the send for ttl 1 three times and for ttl 2 twice and
+    // asserts that exactly 2 suppressed probes are recorded for the round.
+    #[test]
+    fn test_suppressed_counted_once_per_ttl() {
+        let config = StrategyConfig {
+            min_probe_interval: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let mut state = TracerState::new(config);
+        state.suppress_probe();
+        state.suppress_probe();
+        state.suppress_probe();
+        assert_eq!(1, state.suppressed());
+        state.skip_ttl();
+        state.suppress_probe();
+        state.suppress_probe();
+        assert_eq!(2, state.suppressed());
+        state.advance_round(TimeToLive(1));
+        state.suppress_probe();
+        assert_eq!(1, state.suppressed());
+    }
 
-    /// The maximum number of `ProbeStatus` entries in the buffer.
-    ///
-    /// This is larger than maximum number of time-to-live (TTL) we can support to allow for skipped
-    /// sequences.
-    const BUFFER_SIZE: u16 = MAX_SEQUENCE_PER_ROUND;
+    // No probe is sent for a paused ttl and the next ttl is probed instead.
+    //
+    // This test pauses ttl 2 and attempts to send 3 probes, asserting that
+    // probes are sent for ttl 1 and 3 only.
+    #[test]
+    fn test_paused_ttl_is_skipped() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network
+            .expect_send_probe()
+            .times(2)
+            .withf(|probe| probe.ttl != TimeToLive(2))
+            .returning(|_| Ok(()));
+        let config = StrategyConfig::default();
+        let tracer = Strategy::new(&config, |_| {}).with_paused(|ttl| ttl == TimeToLive(2));
+        let mut state = TracerState::new(config);
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        assert_eq!(2, state.probes().len());
+        assert_eq!(TimeToLive(4), state.ttl());
+        Ok(())
+    }
 
-    /// The maximum sequence number.
-    ///
-    /// The sequence number is only ever wrapped between rounds, and so we need to ensure that there
-    /// are enough sequence numbers for a complete round.
-    ///
-    /// A sequence number can be skipped if, for example, the port for that sequence number cannot
-    /// be bound as it is already in use.
-    ///
-    /// To ensure each `ProbeStatus` is in the correct place in the buffer (i.e. the index into the
-    /// buffer is always `Probe.sequence - round_sequence`), when we skip a sequence we leave
-    /// the skipped `ProbeStatus` in-place and use the next slot for the next sequence.
-    ///
-    /// We cap the number of sequences that can potentially be skipped in a round to ensure that
-    /// sequence number does not even need to wrap around during a round.
-    ///
-    /// We only ever send `ttl` in the range 1..255, and so we may use all buffer capacity, except
-    /// the minimum needed to send up to a max `ttl` of 255 (a `ttl` of 0 is never sent).
-    const MAX_SEQUENCE: Sequence = Sequence(u16::MAX - BUFFER_SIZE);
+    #[test]
+    fn test_unresponsive_ttl_is_not_sent() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network
+            .expect_send_probe()
+            .times(2)
+            .withf(|probe| probe.ttl <= TimeToLive(2))
+            .returning(|_| Ok(()));
+        let config = StrategyConfig {
+            max_unresponsive_hops: Some(2),
+            ..Default::default()
+        };
+        let tracer = Strategy::new(&config, |_| {});
+        let mut state = TracerState::new(config);
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        assert_eq!(2, state.probes().len());
+        assert_eq!(TimeToLive(3), state.ttl());
+        assert!(state.curtailed());
+        state.advance_round(TimeToLive(1));
+        assert!(!state.curtailed());
+        Ok(())
+    }
 
-    /// Mutable state needed for the tracing algorithm.
-    #[derive(Debug)]
-    pub struct TracerState {
-        /// Tracer configuration.
-        config: StrategyConfig,
-        /// The state of all `ProbeStatus` requests and responses.
-        buffer: [ProbeStatus; BUFFER_SIZE as usize],
-        /// An increasing sequence number for every `EchoRequest`.
-        sequence: Sequence,
-        /// The starting sequence number of the current round.
-        round_sequence: Sequence,
-        /// The time-to-live for the _next_ `EchoRequest` packet to be sent.
-        ttl: TimeToLive,
-        /// The current round.
-        round: RoundId,
-        /// The timestamp of when the current round started.
-        round_start: SystemTime,
-        /// Did we receive an `EchoReply` from the target host in this round?
-        target_found: bool,
-        /// The maximum time-to-live echo response packet we have received.
-        max_received_ttl: Option<TimeToLive>,
-        /// The observed time-to-live of the `EchoReply` from the target host.
-        ///
-        /// Note that this is _not_ reset each round and that it can also _change_ over time,
-        /// including going _down_ as responses can be received out-of-order.
-        target_ttl: Option<TimeToLive>,
-        /// The timestamp of the echo response packet.
-        received_time: Option<SystemTime>,
+    // The number of unresponsive hops is measured from the ttl before the
+    // first ttl if no response has yet been received.
+    //
+    // This test starts from ttl 5 with a maximum of 2 unresponsive hops and
+    // asserts that probes are sent for ttl 5 and 6 only.
+    #[test]
+    fn test_unresponsive_ttl_from_first_ttl() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network
+            .expect_send_probe()
+            .times(2)
+            .withf(|probe| probe.ttl >= TimeToLive(5) && probe.ttl <= TimeToLive(6))
+            .returning(|_| Ok(()));
+        let config = StrategyConfig {
+            first_ttl: TimeToLive(5),
+            max_unresponsive_hops: Some(2),
+            ..Default::default()
+        };
+        let tracer = Strategy::new(&config, |_| {});
+        let mut state = TracerState::new(config);
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        assert_eq!(2, state.probes().len());
+        assert_eq!(TimeToLive(7), state.ttl());
+        assert!(state.curtailed());
+        Ok(())
     }
 
-    impl TracerState {
+    // The send time of a probe is the time at which it was sent rather
+    // than the time at which it was created.
+    //
+    // This test sends a probe which takes 20ms to send and asserts that the
+    // send time of the probe is no earlier than the time it finished sending.
+    #[test]
+    fn test_sent_is_send_time() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network.expect_send_probe().times(1).returning(|_| {
+            std::thread::sleep(Duration::from_millis(20));
+            Ok(())
+        });
+        let config = StrategyConfig::default();
+        let tracer = Strategy::new(&config, |_| {});
+        let mut state = TracerState::new(config);
+        let created = SystemTime::now();
+        tracer.send_request(&mut network, &mut state)?;
+        let probe = state.probes()[0].clone().try_into_awaited().unwrap();
+        assert!(probe.sent.duration_since(created)? >= Duration::from_millis(20));
+        assert_eq!(Some(probe.sent), state.last_sent_time());
+        Ok(())
+    }
+
+    // The tracer sleeps for the send jitter before sending a probe.
+    //
+    // This test configures a seeded send jitter and asserts that the probe is
+    // sent no earlier than the jitter chosen for it.
+    #[test]
+    fn test_send_jitter() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network.expect_send_probe().times(1).returning(|_| Ok(()));
+        let config = StrategyConfig {
+            send_jitter: Some(Duration::from_millis(20)),
+            send_jitter_seed: Some(1),
+            ..Default::default()
+        };
+        let tracer = Strategy::new(&config, |_| {});
+        let mut state = TracerState::new(config);
+        let created = SystemTime::now();
+        tracer.send_request(&mut network, &mut state)?;
+        let probe = state.probes()[0].clone().try_into_awaited().unwrap();
+        assert!(state.round_jitter() <= Duration::from_millis(20));
+        assert!(probe.sent.duration_since(created)? >= state.round_jitter());
+        Ok(())
+    }
+
+    // Probes are sent in batches when a send batch size is configured.
+    //
+    // This test configures a batch size of 4, pauses ttl 2 and asserts that a
+    // single batch of probes is sent for ttl 1, 3, 4 and 5.  The probe for
+    // ttl 3 fails to send and is marked as failed whilst the others are
+    // recorded as sent.
+    #[test]
+    fn test_send_batch() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network
+            .expect_send_probes()
+            .times(1)
+            .withf(|probes| {
+                probes.iter().map(|p| p.ttl).collect::<Vec<_>>()
+                    == [TimeToLive(1), TimeToLive(3), TimeToLive(4), TimeToLive(5)]
+            })
+            .returning(|probes| {
+                probes
+                    .iter()
+                    .map(|p| {
+                        if p.ttl == TimeToLive(3) {
+                            let err = std::io::Error::from(std::io::ErrorKind::HostUnreachable);
+                            Err(Error::ProbeFailed(IoError::SendTo(err, ADDR)))
+                        } else {
+                            Ok(())
+                        }
+                    })
+                    .collect()
+            });
+        let config = StrategyConfig {
+            send_batch_size: 4,
+            ..Default::default()
+        };
+        let tracer = Strategy::new(&config, |_| {}).with_paused(|ttl| ttl == TimeToLive(2));
+        let mut state = TracerState::new(config);
+        tracer.send_request(&mut network, &mut state)?;
+        assert_eq!(4, state.probes().len());
+        assert!(matches!(state.probes()[0], ProbeStatus::Awaited(_)));
+        assert!(matches!(state.probes()[1], ProbeStatus::Failed(_)));
+        assert!(matches!(state.probes()[2], ProbeStatus::Awaited(_)));
+        assert!(matches!(state.probes()[3], ProbeStatus::Awaited(_)));
+        assert_eq!(TimeToLive(6), state.ttl());
+        Ok(())
+    }
+
+    // A probe which fails to send is marked as failed and the trace continues.
+    //
+    // This test fails to send the probe for ttl 1 and asserts that it is
+    // marked as failed and that the probe for ttl 2 is then sent.
+    #[test]
+    fn test_send_failed() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        let mut seq = mockall::Sequence::new();
+        network
+            .expect_send_probe()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| {
+                let err = std::io::Error::from(std::io::ErrorKind::WouldBlock);
+                Err(Error::ProbeFailed(IoError::SendTo(err, ADDR)))
+            });
+        network
+            .expect_send_probe()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|_| Ok(()));
+        let config = StrategyConfig::default();
+        let tracer = Strategy::new(&config, |_| {});
+        let mut state = TracerState::new(config);
+        tracer.send_request(&mut network, &mut state)?;
+        tracer.send_request(&mut network, &mut state)?;
+        assert_eq!(2, state.probes().len());
+        assert!(matches!(state.probes()[0], ProbeStatus::Failed(_)));
+        assert!(matches!(state.probes()[1], ProbeStatus::Awaited(_)));
+        Ok(())
+    }
+
+    // The probes of the round in progress are published whenever a probe is
+    // sent or a response is received.
+    //
+    // This test sends a probe for ttl 1, receives nothing, sends a probe for
+    // ttl 2 and then receives an `EchoReply` for ttl 1, and asserts that the
+    // awaited probes are published as ttl 1 and then ttl 2 only.
+    #[test]
+    fn test_in_progress_published() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        let mut seq = mockall::Sequence::new();
+        network.expect_send_probe().times(2).returning(|_| Ok(()));
+        network
+            .expect_recv_probe()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Ok(None));
+        network
+            .expect_recv_probe()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| {
+                Ok(Some(Response::EchoReply(
+                    response_data(SystemTime::now()),
+                    IcmpPacketCode(0),
+                )))
+            });
+        network.expect_recv_probe().returning(|| Ok(None));
+        let config = StrategyConfig {
+            max_rounds: Some(MaxRounds(NonZeroUsize::MIN)),
+            initial_sequence: Sequence(33434),
+            min_round_duration: Duration::ZERO,
+            grace_duration: Duration::ZERO,
+            ..Default::default()
+        };
+        let published = std::cell::RefCell::new(vec![]);
+        Strategy::new(&config, |_| {})
+            .with_in_progress(|probes| {
+                let awaited = probes
+                    .iter()
+                    .filter_map(|probe| probe.clone().try_into_awaited())
+                    .map(|probe| probe.ttl)
+                    .collect::<Vec<_>>();
+                published.borrow_mut().push(awaited);
+            })
+            .run(network)?;
+        assert_eq!(
+            vec![vec![TimeToLive(1)], vec![TimeToLive(2)]],
+            published.into_inner()
+        );
+        Ok(())
+    }
+
+    // A reply from the target which is received after the loss timeout finds the
+    // target, but the probe is accounted as lost.
+    //
+    // This test sends a probe for ttl 1 and receives an `EchoReply` from the
+    // target 100ms later, with a loss timeout of 50ms, and asserts that the
+    // round completes because the target was found and the probe remains
+    // awaited.
+    #[test]
+    fn test_target_reply_after_loss_timeout() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network.expect_send_probe().times(1).returning(|_| Ok(()));
+        network.expect_recv_probe().times(1).returning(|| {
+            Ok(Some(Response::EchoReply(
+                response_data(SystemTime::now() + Duration::from_millis(100)),
+                IcmpPacketCode(0),
+            )))
+        });
+        network.expect_recv_probe().returning(|| Ok(None));
+        let config = StrategyConfig {
+            max_rounds: Some(MaxRounds(NonZeroUsize::MIN)),
+            initial_sequence: Sequence(33434),
+            max_ttl: TimeToLive(1),
+            min_round_duration: Duration::ZERO,
+            max_round_duration: Duration::from_secs(60),
+            grace_duration: Duration::ZERO,
+            loss_timeout: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+        let published = std::cell::RefCell::new(vec![]);
+        Strategy::new(&config, |round| {
+            let awaited = round
+                .probes
+                .iter()
+                .filter_map(|probe| probe.clone().try_into_awaited())
+                .map(|probe| probe.ttl)
+                .collect::<Vec<_>>();
+            published.borrow_mut().push((round.reason, awaited));
+        })
+        .run(network)?;
+        assert_eq!(
+            vec![(CompletionReason::TargetFound, vec![TimeToLive(1)])],
+            published.into_inner()
+        );
+        Ok(())
+    }
+
+    // An unbounded trace stops within one iteration of the stop predicate
+    // returning `true`.
+    //
+    // This test stops the trace once the first probe has been sent and asserts
+    // that no further probe is sent.
+    #[test]
+    fn test_stopped() -> anyhow::Result<()> {
+        let mut network = MockNetwork::new();
+        network.expect_send_probe().times(1).returning(|_| Ok(()));
+        network.expect_recv_probe().times(1).returning(|| Ok(None));
+        let config = StrategyConfig {
+            max_rounds: None,
+            initial_sequence: Sequence(33434),
+            min_round_duration: Duration::ZERO,
+            ..Default::default()
+        };
+        let stopped = std::cell::Cell::new(false);
+        Strategy::new(&config, |_| {})
+            .with_in_progress(|_| stopped.set(true))
+            .with_stopped(|| stopped.get())
+            .run(network)?;
+        Ok(())
+    }
+
+    // No probes are sent, and no rounds are published, while the trace is
+    // suspended, however responses are still received.
+    //
+    // This test sends a probe for ttl 1 in the first iteration and suspends the
+    // trace for the second to fourth iterations, during which an `EchoReply` is
+    // received for the probe.  The round is only published, including the
+    // reply, once the trace is resumed in the fifth iteration.
+    //
+    // The time spent suspended is not included in the duration of the round
+    // and so a round in which no reply is received, and which is suspended for
+    // longer than the maximum round duration, is not completed on resume.
+    #[test_case(true, Duration::ZERO => vec![(5, vec![33434], CompletionReason::TargetFound)]; "reply")]
+    #[test_case(false, Duration::from_millis(50) => Vec::<(usize, Vec<u16>, CompletionReason)>::new(); "no reply")]
+    fn test_suspended(
+        reply: bool,
+        suspended_delay: Duration,
+    ) -> Vec<(usize, Vec<u16>, CompletionReason)> {
+        let mut network = MockNetwork::new();
+        let mut recv_count = 0;
+        network.expect_send_probe().times(1).returning(|_| Ok(()));
+        network.expect_recv_probe().times(5).returning(move || {
+            recv_count += 1;
+            if (2..=4).contains(&recv_count) {
+                std::thread::sleep(suspended_delay);
+            }
+            if reply && recv_count == 2 {
+                Ok(Some(Response::EchoReply(
+                    response_data(SystemTime::now()),
+                    IcmpPacketCode(0),
+                )))
+            } else {
+                Ok(None)
+            }
+        });
+        let config = StrategyConfig {
+            max_rounds: None,
+            initial_sequence: Sequence(33434),
+            max_ttl: TimeToLive(1),
+            min_round_duration: Duration::ZERO,
+            max_round_duration: Duration::from_millis(100),
+            grace_duration: Duration::ZERO,
+            ..Default::default()
+        };
+        let iteration = std::cell::Cell::new(0);
+        let published = std::cell::RefCell::new(vec![]);
+        Strategy::new(&config, |round| {
+            let sequences = round
+                .probes
+                .iter()
+                .filter_map(|probe| probe.clone().try_into_complete())
+                .map(|probe| probe.sequence.0)
+                .collect::<Vec<_>>();
+            published
+                .borrow_mut()
+                .push((iteration.get(), sequences, round.reason));
+        })
+        .with_stopped(|| {
+            iteration.set(iteration.get() + 1);
+            iteration.get() > 5
+        })
+        .with_suspended(|| (2..=4).contains(&iteration.get()))
+        .run(network)
+        .unwrap();
+        published.into_inner()
+    }
+
+    // A reply for a probe from the previous round is only published with the
+    // round in which it was received if late responses are accepted.
+    //
+    // This test sends a probe for ttl 1 which receives no reply in the first
+    // round, sends a probe for ttl 1 in the second round and then receives an
+    // `EchoReply` for the probe from the first round.
+    #[test_case(false => Vec::<Vec<u16>>::from([vec![], vec![]]); "drop late responses")]
+    #[test_case(true => Vec::<Vec<u16>>::from([vec![], vec![33434]]); "accept late responses")]
+    fn test_late_response(accept_late_responses: bool) -> Vec<Vec<u16>> {
+        let mut network = MockNetwork::new();
+        let mut seq = mockall::Sequence::new();
+        network.expect_send_probe().times(2).returning(|_| Ok(()));
+        network
+            .expect_recv_probe()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| Ok(None));
+        network
+            .expect_recv_probe()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(|| {
+                Ok(Some(Response::EchoReply(
+                    response_data(SystemTime::now()),
+                    IcmpPacketCode(0),
+                )))
+            });
+        let config = StrategyConfig {
+            max_rounds: Some(MaxRounds(NonZeroUsize::new(2).unwrap())),
+            initial_sequence: Sequence(33434),
+            max_ttl: TimeToLive(1),
+            max_round_duration: Duration::ZERO,
+            accept_late_responses,
+            ..Default::default()
+        };
+        let published = std::cell::RefCell::new(vec![]);
+        Strategy::new(&config, |round| {
+            let late = round
+                .late
+                .iter()
+                .filter_map(|probe| probe.clone().try_into_complete())
+                .map(|probe| probe.sequence.0)
+                .collect::<Vec<_>>();
+            published.borrow_mut().push(late);
+        })
+        .run(network)
+        .unwrap();
+        published.into_inner()
+    }
+
+    // The reason a round completed distinguishes a round which completed after
+    // the grace period elapsed from one forced by the maximum round duration.
+    //
+    // This test sends a single probe (ttl 1) which, if `reply` is set, receives
+    // an `EchoReply` from the target.
+    #[test_case(Duration::from_secs(60), Duration::ZERO, true => CompletionReason::TargetFound; "target found")]
+    #[test_case(Duration::ZERO, Duration::ZERO, false => CompletionReason::RoundTimeLimitExceeded; "target not found")]
+    #[test_case(Duration::ZERO, Duration::from_secs(60), true => CompletionReason::TargetFoundRoundTimeLimitExceeded; "target found grace not elapsed")]
+    fn test_completion_reason(
+        max_round_duration: Duration,
+        grace_duration: Duration,
+        reply: bool,
+    ) -> CompletionReason {
+        let mut network = MockNetwork::new();
+        let mut replied = !reply;
+        network.expect_send_probe().times(1).returning(|_| Ok(()));
+        network.expect_recv_probe().returning(move || {
+            if replied {
+                Ok(None)
+            } else {
+                replied = true;
+                Ok(Some(Response::EchoReply(
+                    response_data(SystemTime::now()),
+                    IcmpPacketCode(0),
+                )))
+            }
+        });
+        let config = StrategyConfig {
+            max_rounds: Some(MaxRounds(NonZeroUsize::new(1).unwrap())),
+            initial_sequence: Sequence(33434),
+            max_ttl: TimeToLive(1),
+            min_round_duration: Duration::ZERO,
+            max_round_duration,
+            grace_duration,
+            ..Default::default()
+        };
+        let published = std::cell::RefCell::new(vec![]);
+        Strategy::new(&config, |round| published.borrow_mut().push(round.reason))
+            .run(network)
+            .unwrap();
+        assert_eq!(1, published.borrow().len());
+        published.into_inner()[0]
+    }
+
+    // A reply from the target for a lower sequence which arrives after a reply
+    // from the target for a higher sequence, but within the grace period,
+    // resizes the round to the lower ttl.
+    //
+    // This test sends 4 probes (ttl 1..=4) and receives an `EchoReply` for
+    // ttl 4 followed by an `EchoReply` for ttl 3.  The round is not completed
+    // after the first reply as the grace period has not elapsed and the
+    // published round is sized to ttl 3.
+    #[test]
+    fn test_out_of_order_target_reply_resizes_round() -> anyhow::Result<()> {
+        let echo_reply = |sequence: u16| {
+            Response::EchoReply(
+                ResponseData::new(
+                    SystemTime::now(),
+                    IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)),
+                    ResponseSeq::Icmp(ResponseSeqIcmp {
+                        identifier: 0,
+                        sequence,
+                        stamp: None,
+                    }),
+                    None,
+                    None,
+                ),
+                IcmpPacketCode(0),
+            )
+        };
+        let mut network = MockNetwork::new();
+        let mut seq = mockall::Sequence::new();
+        network.expect_send_probe().times(4).returning(|_| Ok(()));
+        network
+            .expect_recv_probe()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(move || Ok(Some(echo_reply(33437))));
+        network
+            .expect_recv_probe()
+            .times(1)
+            .in_sequence(&mut seq)
+            .returning(move || Ok(Some(echo_reply(33436))));
+        let config = StrategyConfig {
+            initial_sequence: Sequence(33434),
+            grace_duration: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let published = std::cell::Cell::new(None);
+        let tracer = Strategy::new(&config, |round| published.set(Some(round.largest_ttl)));
+        let mut state = TracerState::new(config);
+        for _ in 0..4 {
+            tracer.send_request(&mut network, &mut state)?;
+        }
+        tracer.recv_response(&mut network, &mut state)?;
+        assert_eq!(Some(TimeToLive(4)), state.target_ttl());
+        tracer.update_round(&mut state);
+        assert_eq!(None, published.get());
+        tracer.recv_response(&mut network, &mut state)?;
+        assert_eq!(Some(TimeToLive(3)), state.target_ttl());
+        assert_eq!(Some(TimeToLive(4)), state.max_received_ttl());
+        assert!(state.target_found());
+        tracer.publish_trace(&state, CompletionReason::TargetFound);
+        assert_eq!(Some(TimeToLive(3)), published.get());
+        Ok(())
+    }
+
+    #[test_case(1, 1, None, None, 0; "no ttl sent")]
+    #[test_case(1, 11, None, None, 0; "no response")]
+    #[test_case(1, 11, Some(4), None, 5; "response below max sent ttl")]
+    #[test_case(1, 11, Some(9), None, 10; "response one below max sent ttl")]
+    #[test_case(1, 11, Some(10), None, 10; "response at max sent ttl")]
+    #[test_case(1, 2, Some(1), None, 1; "response at single sent ttl")]
+    #[test_case(1, 255, Some(254), None, 254; "response at max ttl")]
+    #[test_case(1, 255, Some(255), None, 254; "response beyond max ttl")]
+    #[test_case(5, 5, None, None, 0; "windowed no ttl sent")]
+    #[test_case(5, 5, Some(7), None, 0; "windowed no ttl sent with response")]
+    #[test_case(5, 13, None, None, 0; "windowed no response")]
+    #[test_case(5, 13, Some(7), None, 8; "windowed response below max sent ttl")]
+    #[test_case(5, 13, Some(12), None, 12; "windowed response at max sent ttl")]
+    #[test_case(5, 6, Some(5), None, 5; "windowed response at first ttl")]
+    #[test_case(1, 11, Some(4), Some(6), 6; "target below max sent ttl")]
+    #[test_case(1, 11, None, Some(10), 10; "target at max sent ttl")]
+    #[test_case(1, 5, Some(4), Some(8), 8; "target beyond max sent ttl")]
+    #[test_case(1, 255, Some(254), Some(254), 254; "target at max ttl")]
+    #[test_case(5, 13, Some(9), Some(9), 9; "windowed target")]
+    #[test_case(5, 5, None, Some(9), 9; "windowed target no ttl sent")]
+    fn test_round_largest_ttl(
+        first_ttl: u8,
+        next_ttl: u8,
+        max_received_ttl: Option<u8>,
+        target_ttl: Option<u8>,
+        expected: u8,
+    ) {
+        let largest_ttl = round_largest_ttl(
+            TimeToLive(first_ttl),
+            TimeToLive(next_ttl),
+            max_received_ttl.map(TimeToLive),
+            target_ttl.map(TimeToLive),
+        );
+        assert_eq!(TimeToLive(expected), largest_ttl);
+    }
+
+    #[test_case(false, TraceId(1234), false, true; "matching identifier")]
+    #[test_case(false, TraceId(0), false, true; "zero identifier")]
+    #[test_case(false, TraceId(4321), false, false; "rewritten identifier")]
+    #[test_case(true, TraceId(4321), false, true; "rewritten identifier ignored")]
+    #[test_case(true, TraceId(1234), true, true; "matching stamped identifier")]
+    #[test_case(true, TraceId(4321), true, false; "other stamped identifier")]
+    fn test_check_trace_id(
+        ignore_identifier: bool,
+        trace_id: TraceId,
+        stamped: bool,
+        expected: bool,
+    ) {
+        let config = StrategyConfig {
+            trace_identifier: TraceId(1234),
+            ignore_identifier,
+            ..Default::default()
+        };
+        let tracer = Strategy::new(&config, |_| {});
+        assert_eq!(expected, tracer.check_trace_id(trace_id, stamped));
+    }
+
+    const fn response_data(now: SystemTime) -> ResponseData {
+        ResponseData::new(
+            now,
+            IpAddr::V4(Ipv4Addr::new(1, 2, 3, 4)),
+            ResponseSeq::Icmp(ResponseSeqIcmp {
+                identifier: 0,
+                sequence: 33434,
+                stamp: None,
+            }),
+            None,
+            None,
+        )
+    }
+}
+
+/// Mutable state needed for the tracing algorithm.
+///
+/// This is contained within a submodule to ensure that mutations are only performed via methods on
+/// the `TracerState` struct.
+mod state {
+    use crate::probe::{Probe, ProbeComplete, ProbeStatus, TtlAddrs};
+    use crate::strategy::{StrategyConfig, StrategyResponse};
+    use crate::types::{MaxRounds, Port, RoundId, Sequence, TimeToLive, TraceId};
+    use crate::{Flags, MultipathStrategy, PortDirection, Protocol};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::net::IpAddr;
+    use std::time::{Duration, SystemTime};
+    use tracing::instrument;
+
+    /// Mutable state needed for the tracing algorithm.
+    #[derive(Debug)]
+    pub struct TracerState {
+        /// Tracer configuration.
+        config: StrategyConfig,
+        /// The state of all `ProbeStatus` requests and responses.
+        ///
+        /// The buffer holds `buffer_size` entries, which is larger than maximum number of
+        /// time-to-live (TTL) we can support to allow for skipped sequences.
+        buffer: Vec<ProbeStatus>,
+        /// An increasing sequence number for every `EchoRequest`.
+        sequence: Sequence,
+        /// The starting sequence number of the current round.
+        round_sequence: Sequence,
+        /// The time-to-live for the _next_ `EchoRequest` packet to be sent.
+        ttl: TimeToLive,
+        /// The current round.
+        round: RoundId,
+        /// The timestamp of when the current round started.
+        round_start: SystemTime,
+        /// Did we receive an `EchoReply` from the target host in this round?
+        target_found: bool,
+        /// The maximum time-to-live echo response packet we have received.
+        max_received_ttl: Option<TimeToLive>,
+        /// The maximum time-to-live echo response packet we have received in any round.
+        ///
+        /// Note that this is _not_ reset each round.
+        highest_received_ttl: Option<TimeToLive>,
+        /// The observed time-to-live of the `EchoReply` from the target host.
+        ///
+        /// Note that this is _not_ reset each round and that it can also _change_ over time,
+        /// including going _down_ as responses can be received out-of-order.
+        target_ttl: Option<TimeToLive>,
+        /// The timestamp of the echo response packet.
+        received_time: Option<SystemTime>,
+        /// The timestamp of the most recently sent probe.
+        ///
+        /// Note that this is _not_ reset each round.
+        last_sent_time: Option<SystemTime>,
+        /// The number of probes whose send was deferred by the rate limiter in this round.
+        suppressed: usize,
+        /// The time-to-live whose send is currently deferred by the rate limiter, if any.
+        suppressed_ttl: Option<TimeToLive>,
+        /// Whether a probe was not sent in this round as the maximum number of consecutive
+        /// unresponsive hops was reached.
+        curtailed: bool,
+        /// The state of all `ProbeStatus` of the previous round, if late responses are accepted.
+        prev_probes: Vec<ProbeStatus>,
+        /// The starting sequence number of the previous round.
+        prev_round_sequence: Sequence,
+        /// The `ProbeStatus` of probes from the previous round completed in this round.
+        late: Vec<ProbeStatus>,
+        /// The random number generator for the send jitter.
+        jitter_rng: StdRng,
+        /// The total send jitter slept in this round.
+        round_jitter: Duration,
+        /// The total time spent suspended in this round.
+        round_suspended: Duration,
+        /// The round-trip time of the most recently completed probe for each time-to-live.
+        ///
+        /// Note that this is _not_ reset each round.
+        last_rtt: Vec<Option<Duration>>,
+        /// The distinct addresses which have responded for each time-to-live.
+        ///
+        /// Note that this is _not_ reset each round.
+        ttl_addrs: Vec<TtlAddrs>,
+        /// The estimate of the round-trip time of all responses.
+        ///
+        /// Note that this is _not_ reset each round.
+        rtt_estimate: RttEstimate,
+    }
+
+    impl TracerState {
         pub fn new(config: StrategyConfig) -> Self {
             Self {
                 config,
-                buffer: from_fn(|_| ProbeStatus::default()),
+                buffer: vec![ProbeStatus::default(); usize::from(config.buffer_size)],
                 sequence: config.initial_sequence,
                 round_sequence: config.initial_sequence,
                 ttl: config.first_ttl,
@@ -914,8 +2014,24 @@ mod state {
                 round_start: SystemTime::now(),
                 target_found: false,
                 max_received_ttl: None,
+                highest_received_ttl: None,
                 target_ttl: None,
                 received_time: None,
+                last_sent_time: None,
+                suppressed: 0,
+                suppressed_ttl: None,
+                curtailed: false,
+                prev_probes: Vec::new(),
+                prev_round_sequence: config.initial_sequence,
+                late: Vec::new(),
+                jitter_rng: config
+                    .send_jitter_seed
+                    .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64),
+                round_jitter: Duration::ZERO,
+                round_suspended: Duration::ZERO,
+                last_rtt: vec![None; usize::from(u8::MAX) + 1],
+                ttl_addrs: vec![TtlAddrs::default(); usize::from(u8::MAX) + 1],
+                rtt_estimate: RttEstimate::default(),
             }
         }
 
@@ -946,6 +2062,10 @@ mod state {
             self.max_received_ttl
         }
 
+        pub const fn highest_received_ttl(&self) -> Option<TimeToLive> {
+            self.highest_received_ttl
+        }
+
         pub const fn target_ttl(&self) -> Option<TimeToLive> {
             self.target_ttl
         }
@@ -954,15 +2074,71 @@ mod state {
             self.received_time
         }
 
+        pub const fn last_sent_time(&self) -> Option<SystemTime> {
+            self.last_sent_time
+        }
+
+        pub const fn suppressed(&self) -> usize {
+            self.suppressed
+        }
+
+        pub const fn curtailed(&self) -> bool {
+            self.curtailed
+        }
+
+        pub fn late(&self) -> &[ProbeStatus] {
+            &self.late
+        }
+
+        /// The distinct addresses which have responded for `ttl` in this or any prior round.
+        ///
+        /// At most `MAX_TTL_ADDRS` addresses are recorded.
+        pub fn ttl_addrs(&self, ttl: TimeToLive) -> TtlAddrs {
+            self.ttl_addrs[usize::from(ttl.0)]
+        }
+
+        pub const fn round_jitter(&self) -> Duration {
+            self.round_jitter
+        }
+
+        pub const fn round_suspended(&self) -> Duration {
+            self.round_suspended
+        }
+
+        /// Record time spent suspended in this round.
+        pub fn record_suspended(&mut self, duration: Duration) {
+            self.round_suspended += duration;
+        }
+
+        pub const fn rtt_estimate(&self) -> RttEstimate {
+            self.rtt_estimate
+        }
+
+        /// Choose a random send jitter in the range `0..=max` and record it for this round.
+        pub fn next_jitter(&mut self, max: Duration) -> Duration {
+            let jitter = self.jitter_rng.gen_range(Duration::ZERO..=max);
+            self.round_jitter += jitter;
+            jitter
+        }
+
         /// Is `sequence` in the current round?
         pub fn in_round(&self, sequence: Sequence) -> bool {
-            sequence >= self.round_sequence && sequence.0 - self.round_sequence.0 < BUFFER_SIZE
+            sequence >= self.round_sequence
+                && sequence.0 - self.round_sequence.0 < self.config.buffer_size
+        }
+
+        /// Is `sequence` in the previous round?
+        ///
+        /// This is always false unless late responses are accepted.
+        pub fn in_prev_round(&self, sequence: Sequence) -> bool {
+            sequence >= self.prev_round_sequence
+                && usize::from(sequence - self.prev_round_sequence) < self.prev_probes.len()
         }
 
         /// Do we have capacity in the current round for another sequence?
         pub fn round_has_capacity(&self) -> bool {
             let round_size = self.sequence - self.round_sequence;
-            round_size.0 < BUFFER_SIZE
+            round_size.0 < self.config.buffer_size
         }
 
         /// Are all rounds complete?
@@ -992,6 +2168,7 @@ mod state {
             );
             let probe_index = usize::from(self.sequence - self.round_sequence);
             self.buffer[probe_index] = ProbeStatus::Awaited(probe.clone());
+            self.last_sent_time = Some(sent);
             debug_assert!(self.ttl < TimeToLive(u8::MAX));
             self.ttl += TimeToLive(1);
             debug_assert!(self.sequence < Sequence(u16::MAX));
@@ -1024,19 +2201,55 @@ mod state {
                 flags,
             );
             self.buffer[probe_index] = ProbeStatus::Awaited(probe.clone());
+            self.last_sent_time = Some(sent);
             debug_assert!(self.sequence < Sequence(u16::MAX));
             self.sequence += Sequence(1);
             probe
         }
 
-        /// Mark the `ProbeStatus` at the current `sequence` as failed.
+        /// Update the send time of the `Probe` at `sequence` once it has been sent.
         #[instrument(skip(self))]
-        pub fn fail_probe(&mut self) {
-            let probe_index = usize::from(self.sequence - self.round_sequence);
-            let probe = self.buffer[probe_index - 1].clone();
+        pub fn update_sent(&mut self, sequence: Sequence, sent: SystemTime) {
+            let probe_index = usize::from(sequence - self.round_sequence);
+            if let ProbeStatus::Awaited(awaited) = &mut self.buffer[probe_index] {
+                awaited.sent = sent;
+                self.last_sent_time = Some(sent);
+            }
+        }
+
+        /// Skip the current `ttl` without sending a probe for it.
+        #[instrument(skip(self))]
+        pub fn skip_ttl(&mut self) {
+            debug_assert!(self.ttl < TimeToLive(u8::MAX));
+            self.ttl += TimeToLive(1);
+        }
+
+        /// Record that the send of the probe for the current `ttl` was deferred by the rate limiter.
+        ///
+        /// A send may be deferred many times before it is permitted, the probe is only counted
+        /// the first time its `ttl` is deferred.
+        #[instrument(skip(self))]
+        pub fn suppress_probe(&mut self) {
+            if self.suppressed_ttl != Some(self.ttl) {
+                self.suppressed_ttl = Some(self.ttl);
+                self.suppressed += 1;
+            }
+        }
+
+        /// Record that a probe was not sent as the maximum number of unresponsive hops was reached.
+        #[instrument(skip(self))]
+        pub fn curtail_probing(&mut self) {
+            self.curtailed = true;
+        }
+
+        /// Mark the `ProbeStatus` at `sequence` as failed.
+        #[instrument(skip(self))]
+        pub fn fail_probe(&mut self, sequence: Sequence) {
+            let probe_index = usize::from(sequence - self.round_sequence);
+            let probe = self.buffer[probe_index].clone();
             match probe {
                 ProbeStatus::Awaited(awaited) => {
-                    self.buffer[probe_index - 1] = ProbeStatus::Failed(awaited.failed());
+                    self.buffer[probe_index] = ProbeStatus::Failed(awaited.failed());
                 }
                 _ => unreachable!("expected ProbeStatus::Awaited"),
             }
@@ -1153,13 +2366,19 @@ mod state {
         ///
         /// - the `target_ttl` to be the time-to-live of the `ProbeStatus` request from the target
         /// - the `max_received_ttl` we have observed this round
-        /// - the latest packet `received_time` in this round
+        /// - the latest packet `received_time` in this round, which never moves backwards such
+        ///   that a reply processed out-of-order cannot shorten the grace period of the round
         /// - whether the target has been found in this round
         ///
         /// The ICMP replies may arrive out-of-order, and so we must be careful here to avoid
         /// overwriting the state with stale values.  We may also receive multiple replies
         /// from the target host with differing time-to-live values and so must ensure we
         /// use the time-to-live with the lowest sequence number.
+        ///
+        /// If a `loss_timeout` is configured and the reply was received after the timeout
+        /// elapsed then the `ProbeStatus` is left in the `Awaited` state, such that it is
+        /// accounted as lost exactly once when the round completes.  The state of the round is
+        /// still updated from the reply, such that a target which replies late is found.
         #[instrument(skip(self))]
         pub fn complete_probe(&mut self, resp: StrategyResponse) {
             // Retrieve and update the `ProbeStatus` at `sequence`.
@@ -1179,17 +2398,15 @@ mod state {
                     return;
                 }
             };
-            let completed = awaited.complete(
-                resp.addr,
-                resp.received,
-                resp.icmp_packet_type,
-                resp.expected_udp_checksum,
-                resp.actual_udp_checksum,
-                resp.exts,
-            );
-            let ttl = completed.ttl;
-            self.buffer[usize::from(resp.sequence - self.round_sequence)] =
-                ProbeStatus::Complete(completed);
+            let ttl = awaited.ttl;
+            if self.is_lost(&awaited, resp.received) {
+                tracing::debug!(?awaited, "reply received after loss timeout");
+            } else {
+                let completed = self.completed(awaited, &resp);
+                self.rtt_estimate.update(completed.rtt());
+                self.buffer[usize::from(resp.sequence - self.round_sequence)] =
+                    ProbeStatus::Complete(completed);
+            }
 
             // If this `ProbeStatus` found the target then we set the `target_tll` if not already
             // set, being careful to account for `Probes` being received out-of-order.
@@ -1216,18 +2433,93 @@ mod state {
                 None => Some(ttl),
                 Some(max_received_ttl) => Some(max_received_ttl.max(ttl)),
             };
+            self.highest_received_ttl = match self.highest_received_ttl {
+                None => Some(ttl),
+                Some(highest_received_ttl) => Some(highest_received_ttl.max(ttl)),
+            };
 
-            self.received_time = Some(resp.received);
+            self.received_time = self.received_time.max(Some(resp.received));
             self.target_found |= resp.is_target;
         }
 
+        /// Update the `ProbeStatus` of the previous round for which a late response was received.
+        ///
+        /// The completed `ProbeStatus` is published with the current round such that it can be
+        /// attributed to the round in which it was sent.  The state of the current round is not
+        /// updated.
+        #[instrument(skip(self))]
+        pub fn complete_late_probe(&mut self, resp: StrategyResponse) {
+            let index = usize::from(resp.sequence - self.prev_round_sequence);
+            let ProbeStatus::Awaited(awaited) = self.prev_probes[index].clone() else {
+                return;
+            };
+            if self.is_lost(&awaited, resp.received) {
+                tracing::debug!(?awaited, "late reply received after loss timeout");
+                return;
+            }
+            let completed = ProbeStatus::Complete(self.completed(awaited, &resp));
+            self.prev_probes[index] = completed.clone();
+            self.late.push(completed);
+        }
+
+        /// Complete an awaited `Probe` with a response.
+        ///
+        /// The jitter of the probe is the absolute difference between its round-trip time and that
+        /// of the previously completed probe with the same time-to-live, from this or any prior
+        /// round.
+        ///
+        /// The responding address is added to the distinct addresses of the time-to-live, which are
+        /// included in the completed probe.
+        fn completed(&mut self, awaited: Probe, resp: &StrategyResponse) -> ProbeComplete {
+            let completed = awaited.complete(
+                resp.addr,
+                resp.received,
+                resp.icmp_packet_type,
+                resp.expected_udp_checksum,
+                resp.actual_udp_checksum,
+                resp.exts.clone(),
+                resp.reply_ttl,
+                resp.quoted_len,
+            );
+            let sent = resp.sent.unwrap_or(completed.sent);
+            let rtt = completed.received.duration_since(sent).unwrap_or_default();
+            let last_rtt = &mut self.last_rtt[usize::from(completed.ttl.0)];
+            let jitter = last_rtt.map(|last| rtt.saturating_sub(last) + last.saturating_sub(rtt));
+            *last_rtt = Some(rtt);
+            self.ttl_addrs[usize::from(completed.ttl.0)].insert(completed.host);
+            ProbeComplete {
+                sent,
+                jitter,
+                ttl_addrs: self.ttl_addrs(completed.ttl),
+                ..completed
+            }
+        }
+
+        /// Has the loss timeout for an awaited `Probe` elapsed at the time `received`?
+        fn is_lost(&self, awaited: &Probe, received: SystemTime) -> bool {
+            self.config.loss_timeout.is_some_and(|loss_timeout| {
+                received.duration_since(awaited.sent).unwrap_or_default() > loss_timeout
+            })
+        }
+
         /// Advance to the next round.
         ///
         /// If, during the rond which just completed, we went above the max sequence number then we
         /// reset it here. We do this here to avoid having to deal with the sequence number
         /// wrapping during a round, which is more problematic.
+        ///
+        /// If late responses are accepted then the `ProbeStatus` of the round which just completed
+        /// are retained, such that a late response can be matched to the probe.
         #[instrument(skip(self))]
         pub fn advance_round(&mut self, first_ttl: TimeToLive) {
+            if self.config.accept_late_responses {
+                self.prev_probes.clear();
+                let round_size = self.sequence - self.round_sequence;
+                self.prev_probes
+                    .extend_from_slice(&self.buffer[..usize::from(round_size)]);
+                self.prev_round_sequence = self.round_sequence;
+            }
+            self.late.clear();
             if self.sequence >= self.max_sequence() {
                 self.sequence = self.config.initial_sequence;
             }
@@ -1236,6 +2528,11 @@ mod state {
             self.received_time = None;
             self.round_start = SystemTime::now();
             self.max_received_ttl = None;
+            self.suppressed = 0;
+            self.suppressed_ttl = None;
+            self.curtailed = false;
+            self.round_jitter = Duration::ZERO;
+            self.round_suspended = Duration::ZERO;
             self.round += RoundId(1);
             self.ttl = first_ttl;
         }
@@ -1247,23 +2544,77 @@ mod state {
         /// number must be no larger than the maximum IPv6/udp payload size.
         ///
         /// It is also required that the range of possible sequence numbers is
-        /// _at least_ `BUFFER_SIZE` to ensure delayed responses from a prior
+        /// _at least_ `buffer_size` to ensure delayed responses from a prior
         /// round are not incorrectly associated with later rounds (see
         /// `in_round` function).
+        ///
+        /// Otherwise the sequence number is only ever wrapped between rounds, and so we need to
+        /// ensure that there are enough sequence numbers for a complete round.
+        ///
+        /// A sequence number can be skipped if, for example, the port for that sequence number
+        /// cannot be bound as it is already in use.
+        ///
+        /// To ensure each `ProbeStatus` is in the correct place in the buffer (i.e. the index into
+        /// the buffer is always `Probe.sequence - round_sequence`), when we skip a sequence we
+        /// leave the skipped `ProbeStatus` in-place and use the next slot for the next sequence.
+        ///
+        /// We cap the number of sequences that can potentially be skipped in a round to ensure
+        /// that sequence number does not even need to wrap around during a round.
+        ///
+        /// We only ever send `ttl` in the range 1..255, and so we may use all buffer capacity,
+        /// except the minimum needed to send up to a max `ttl` of 255 (a `ttl` of 0 is never
+        /// sent).
         fn max_sequence(&self) -> Sequence {
             match (self.config.multipath_strategy, self.config.target_addr) {
                 (MultipathStrategy::Dublin, IpAddr::V6(_)) => {
-                    self.config.initial_sequence + Sequence(BUFFER_SIZE)
+                    self.config.initial_sequence + Sequence(self.config.buffer_size)
+                }
+                _ => Sequence(u16::MAX - self.config.buffer_size),
+            }
+        }
+    }
+
+    /// A smoothed estimate of the round-trip time.
+    ///
+    /// The estimate is maintained in the same way as for the TCP retransmission timeout (RFC 6298).
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    pub struct RttEstimate {
+        /// The smoothed round-trip time, if any response has been received.
+        srtt: Option<Duration>,
+        /// The round-trip time variation.
+        rttvar: Duration,
+    }
+
+    impl RttEstimate {
+        /// Update the estimate with the round-trip time of a response.
+        pub fn update(&mut self, rtt: Duration) {
+            match self.srtt {
+                None => {
+                    self.srtt = Some(rtt);
+                    self.rttvar = rtt / 2;
+                }
+                Some(srtt) => {
+                    let delta = srtt.saturating_sub(rtt) + rtt.saturating_sub(srtt);
+                    self.rttvar = (self.rttvar * 3 + delta) / 4;
+                    self.srtt = Some((srtt * 7 + rtt) / 8);
                 }
-                _ => MAX_SEQUENCE,
             }
         }
+
+        /// The smoothed round-trip time plus a margin of four times the variation.
+        ///
+        /// Returns `None` if no response has been received.
+        pub fn timeout(&self) -> Option<Duration> {
+            self.srtt.map(|srtt| srtt + self.rttvar * 4)
+        }
     }
 
     #[cfg(test)]
     mod tests {
         use super::*;
+        use crate::config::defaults::DEFAULT_STRATEGY_BUFFER_SIZE;
         use crate::probe::{IcmpPacketCode, IcmpPacketType};
+        use crate::strategy::Strategy;
         use crate::types::MaxInflight;
         use rand::Rng;
         use std::net::{IpAddr, Ipv4Addr};
@@ -1309,10 +2660,13 @@ mod state {
                 sequence: Sequence(33434),
                 expected_udp_checksum: None,
                 actual_udp_checksum: None,
+                sent: None,
                 received: received_1,
                 addr: host,
                 is_target: false,
                 exts: None,
+                reply_ttl: None,
+                quoted_len: None,
             });
 
             // Validate the state of the probe 1 after the update
@@ -1384,10 +2738,13 @@ mod state {
                 sequence: Sequence(33435),
                 expected_udp_checksum: None,
                 actual_udp_checksum: None,
+                sent: None,
                 received: received_2,
                 addr: host,
                 is_target: false,
                 exts: None,
+                reply_ttl: None,
+                quoted_len: None,
             });
             let probe_2_recv = state.probe_at(Sequence(33435));
 
@@ -1419,10 +2776,13 @@ mod state {
                 sequence: Sequence(33436),
                 expected_udp_checksum: None,
                 actual_udp_checksum: None,
+                sent: None,
                 received: received_3,
                 addr: host,
                 is_target: true,
                 exts: None,
+                reply_ttl: None,
+                quoted_len: None,
             });
             let probe_3_recv = state.probe_at(Sequence(33436));
 
@@ -1474,7 +2834,7 @@ mod state {
                         .sequence,
                     Sequence(65278)
                 );
-                iter.take(BUFFER_SIZE as usize - 1)
+                iter.take(usize::from(DEFAULT_STRATEGY_BUFFER_SIZE) - 1)
                     .for_each(|p| assert!(matches!(p, ProbeStatus::NotSent)));
             }
 
@@ -1503,7 +2863,7 @@ mod state {
                         .sequence,
                     Sequence(65278)
                 );
-                iter.take(BUFFER_SIZE as usize - 1)
+                iter.take(usize::from(DEFAULT_STRATEGY_BUFFER_SIZE) - 1)
                     .for_each(|p| assert!(matches!(p, ProbeStatus::NotSent)));
             }
         }
@@ -1538,6 +2898,57 @@ mod state {
             }
         }
 
+        // The sequence wraps to the configured initial sequence, which may be
+        // below the default, once the maximum sequence is exceeded.
+        #[test]
+        fn test_sequence_wrap_to_initial_sequence() {
+            let initial_sequence = Sequence(1000);
+            let mut state = TracerState::new(cfg(initial_sequence));
+            let mut wraps = 0;
+            for _ in 0..300 {
+                for _ in 0..254 {
+                    let probe = state.next_probe(SystemTime::now());
+                    assert!(probe.sequence >= initial_sequence);
+                }
+                let sequence = state.sequence;
+                state.advance_round(TimeToLive(1));
+                if state.sequence < sequence {
+                    assert_eq!(initial_sequence, state.sequence);
+                    assert_eq!(initial_sequence, state.round_sequence);
+                    wraps += 1;
+                }
+            }
+            assert_eq!(1, wraps);
+        }
+
+        #[test]
+        fn test_buffer_size_no_alias() {
+            let mut state = TracerState::new(StrategyConfig {
+                buffer_size: 512,
+                ..cfg(Sequence(33434))
+            });
+            let mut rounds = vec![];
+            for _ in 0..2 {
+                let mut sequences = vec![];
+                for _ in 0..254 {
+                    _ = state.next_probe(SystemTime::now());
+                    sequences.push(state.reissue_probe(SystemTime::now()).sequence);
+                }
+                rounds.push(sequences);
+                if rounds.len() == 1 {
+                    state.advance_round(TimeToLive(1));
+                }
+            }
+            assert_eq!(508, state.probes().len());
+            assert!(rounds[0].iter().all(|&sequence| !state.in_round(sequence)));
+            for (sequence, ttl) in rounds[1].iter().zip(1..) {
+                let probe = state.probe_at(*sequence).try_into_awaited().unwrap();
+                assert_eq!(*sequence, probe.sequence);
+                assert_eq!(TimeToLive(ttl), probe.ttl);
+                assert_eq!(RoundId(1), probe.round);
+            }
+        }
+
         #[test]
         fn test_sequence_wrap_with_skip() {
             let total_rounds = 2000;
@@ -1555,6 +2966,24 @@ mod state {
             assert_eq!(state.sequence, Sequence(57310));
         }
 
+        #[test]
+        fn test_next_jitter() {
+            let config = StrategyConfig {
+                send_jitter_seed: Some(42),
+                ..cfg(Sequence(33434))
+            };
+            let max = Duration::from_millis(10);
+            let mut state1 = TracerState::new(config);
+            let mut state2 = TracerState::new(config);
+            let jitters1 = (0..10).map(|_| state1.next_jitter(max)).collect::<Vec<_>>();
+            let jitters2 = (0..10).map(|_| state2.next_jitter(max)).collect::<Vec<_>>();
+            assert_eq!(jitters1, jitters2);
+            assert!(jitters1.iter().all(|jitter| *jitter <= max));
+            assert_eq!(jitters1.iter().sum::<Duration>(), state1.round_jitter());
+            state1.advance_round(TimeToLive(1));
+            assert_eq!(Duration::ZERO, state1.round_jitter());
+        }
+
         #[test]
         fn test_in_round() {
             let state = TracerState::new(cfg(Sequence(33434)));
@@ -1574,6 +3003,275 @@ mod state {
             assert!(!state.in_round(Sequence(64491)));
         }
 
+        #[test]
+        fn test_reply_after_loss_timeout() {
+            let mut state = TracerState::new(StrategyConfig {
+                loss_timeout: Some(Duration::from_millis(500)),
+                ..cfg(Sequence(33434))
+            });
+            let sent = SystemTime::now();
+            let host = IpAddr::V4(Ipv4Addr::LOCALHOST);
+            let resp = |sequence: u16, received: SystemTime| StrategyResponse {
+                icmp_packet_type: IcmpPacketType::TimeExceeded(IcmpPacketCode(1)),
+                trace_id: TraceId(0),
+                sequence: Sequence(sequence),
+                expected_udp_checksum: None,
+                actual_udp_checksum: None,
+                sent: None,
+                received,
+                addr: host,
+                is_target: false,
+                exts: None,
+                reply_ttl: None,
+                quoted_len: None,
+            };
+            _ = state.next_probe(sent);
+            _ = state.next_probe(sent);
+
+            // a reply within the loss timeout completes the probe
+            state.complete_probe(resp(33434, sent + Duration::from_millis(100)));
+            assert!(state
+                .probe_at(Sequence(33434))
+                .try_into_complete()
+                .is_some());
+
+            // a reply after the loss timeout leaves the probe awaited but updates the round
+            state.complete_probe(resp(33435, sent + Duration::from_millis(600)));
+            assert!(state.probe_at(Sequence(33435)).try_into_awaited().is_some());
+            assert_eq!(state.max_received_ttl, Some(TimeToLive(2)));
+            assert_eq!(state.received_time, Some(sent + Duration::from_millis(600)));
+        }
+
+        // The maximum responsive ttl saturates rather than overflows.
+        //
+        // This test records a response from ttl 10 with a maximum of 250
+        // unresponsive hops and asserts that ttl 255 is responsive.
+        #[test]
+        fn test_is_responsive_ttl_saturates() {
+            let config = StrategyConfig {
+                max_unresponsive_hops: Some(250),
+                ..cfg(Sequence(33434))
+            };
+            let strategy = Strategy::new(&config, |_| {});
+            let mut state = TracerState::new(config);
+            state.highest_received_ttl = Some(TimeToLive(10));
+            state.ttl = TimeToLive(255);
+            assert!(strategy.is_responsive_ttl(&state));
+        }
+
+        #[test]
+        fn test_complete_probe_stamped_sent() {
+            let mut state = TracerState::new(cfg(Sequence(33434)));
+            let sent = SystemTime::now();
+            let stamped = sent + Duration::from_millis(5);
+            let received = sent + Duration::from_millis(20);
+            _ = state.next_probe(sent);
+            state.complete_probe(StrategyResponse {
+                icmp_packet_type: IcmpPacketType::TimeExceeded(IcmpPacketCode(1)),
+                trace_id: TraceId(0),
+                sequence: Sequence(33434),
+                expected_udp_checksum: None,
+                actual_udp_checksum: None,
+                sent: Some(stamped),
+                received,
+                addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                is_target: false,
+                exts: None,
+                reply_ttl: None,
+                quoted_len: None,
+            });
+            let complete = state.probe_at(Sequence(33434)).try_into_complete().unwrap();
+            assert_eq!(complete.sent, stamped);
+            assert_eq!(complete.received, received);
+        }
+
+        // The jitter of a probe is the difference between its round-trip
+        // time and that of the previous probe with the same ttl.
+        //
+        // This test completes the probe for ttl 1 in three rounds, with
+        // round-trip times of 20ms, 15ms and 25ms, and asserts that the first
+        // probe has no jitter and the later probes have a jitter of 5ms and
+        // 10ms.
+        #[test]
+        fn test_complete_probe_jitter() {
+            let mut state = TracerState::new(cfg(Sequence(33434)));
+            let jitters = [20, 15, 25]
+                .into_iter()
+                .map(|rtt| {
+                    let sent = SystemTime::now();
+                    let sequence = state.next_probe(sent).sequence;
+                    state.complete_probe(StrategyResponse {
+                        icmp_packet_type: IcmpPacketType::TimeExceeded(IcmpPacketCode(1)),
+                        trace_id: TraceId(0),
+                        sequence,
+                        expected_udp_checksum: None,
+                        actual_udp_checksum: None,
+                        sent: None,
+                        received: sent + Duration::from_millis(rtt),
+                        addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                        is_target: false,
+                        exts: None,
+                        reply_ttl: None,
+                        quoted_len: None,
+                    });
+                    let complete = state.probe_at(sequence).try_into_complete().unwrap();
+                    state.advance_round(TimeToLive(1));
+                    complete.jitter
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec![
+                    None,
+                    Some(Duration::from_millis(5)),
+                    Some(Duration::from_millis(10))
+                ],
+                jitters
+            );
+        }
+
+        // The distinct addresses which respond for a ttl are recorded across
+        // rounds and included in each completed probe.
+        //
+        // This test completes the probe for ttl 1 in three rounds, with
+        // responses from two distinct addresses, and asserts that each
+        // address is recorded once.
+        #[test]
+        fn test_complete_probe_ttl_addrs() {
+            let mut state = TracerState::new(cfg(Sequence(33434)));
+            let addr1 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+            let addr2 = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+            let ttl_addrs = [addr1, addr2, addr1]
+                .into_iter()
+                .map(|addr| {
+                    let sent = SystemTime::now();
+                    let sequence = state.next_probe(sent).sequence;
+                    state.complete_probe(StrategyResponse {
+                        icmp_packet_type: IcmpPacketType::TimeExceeded(IcmpPacketCode(1)),
+                        trace_id: TraceId(0),
+                        sequence,
+                        expected_udp_checksum: None,
+                        actual_udp_checksum: None,
+                        sent: None,
+                        received: sent,
+                        addr,
+                        is_target: false,
+                        exts: None,
+                        reply_ttl: None,
+                        quoted_len: None,
+                    });
+                    let complete = state.probe_at(sequence).try_into_complete().unwrap();
+                    state.advance_round(TimeToLive(1));
+                    complete.ttl_addrs.as_slice().to_vec()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec![vec![addr1], vec![addr1, addr2], vec![addr1, addr2]],
+                ttl_addrs
+            );
+            assert_eq!(&[addr1, addr2], state.ttl_addrs(TimeToLive(1)).as_slice());
+            assert!(state.ttl_addrs(TimeToLive(2)).is_empty());
+        }
+
+        // The adaptive timeout is not known until a response has been
+        // received, after which it is the round-trip time plus four times
+        // the variation.
+        #[test]
+        fn test_rtt_estimate_initial() {
+            let mut estimate = RttEstimate::default();
+            assert_eq!(None, estimate.timeout());
+            estimate.update(Duration::from_millis(100));
+            assert_eq!(Some(Duration::from_millis(300)), estimate.timeout());
+        }
+
+        // The adaptive timeout grows after consecutive slow responses.
+        //
+        // This test completes the probe for ttl 1 in four rounds, with a
+        // round-trip time of 10ms in the first round and 200ms in the
+        // following rounds, and asserts that the timeout grows after each
+        // slow response.
+        #[test]
+        fn test_rtt_estimate_grows_after_slow_responses() {
+            let mut state = TracerState::new(cfg(Sequence(33434)));
+            assert_eq!(None, state.rtt_estimate().timeout());
+            let timeouts = [10, 200, 200, 200]
+                .into_iter()
+                .map(|rtt| {
+                    let sent = SystemTime::now();
+                    let sequence = state.next_probe(sent).sequence;
+                    state.complete_probe(StrategyResponse {
+                        icmp_packet_type: IcmpPacketType::TimeExceeded(IcmpPacketCode(1)),
+                        trace_id: TraceId(0),
+                        sequence,
+                        expected_udp_checksum: None,
+                        actual_udp_checksum: None,
+                        sent: None,
+                        received: sent + Duration::from_millis(rtt),
+                        addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+                        is_target: false,
+                        exts: None,
+                        reply_ttl: None,
+                        quoted_len: None,
+                    });
+                    state.advance_round(TimeToLive(1));
+                    state.rtt_estimate().timeout().unwrap()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(Duration::from_millis(30), timeouts[0]);
+            assert!(timeouts.windows(2).all(|w| w[0] < w[1]), "{timeouts:?}");
+        }
+
+        // Replies from the target may be processed out-of-order, in which
+        // case the target ttl is lowered to the ttl of the lowest sequence
+        // and the received time of the round is not moved backwards.
+        #[test]
+        fn test_out_of_order_target_replies() {
+            let mut state = TracerState::new(cfg(Sequence(33434)));
+            let sent = SystemTime::now();
+            let host = IpAddr::V4(Ipv4Addr::LOCALHOST);
+            let resp = |sequence: u16, received: SystemTime, is_target: bool| StrategyResponse {
+                icmp_packet_type: if is_target {
+                    IcmpPacketType::EchoReply(IcmpPacketCode(0))
+                } else {
+                    IcmpPacketType::TimeExceeded(IcmpPacketCode(1))
+                },
+                trace_id: TraceId(0),
+                sequence: Sequence(sequence),
+                expected_udp_checksum: None,
+                actual_udp_checksum: None,
+                sent: None,
+                received,
+                addr: host,
+                is_target,
+                exts: None,
+                reply_ttl: None,
+                quoted_len: None,
+            };
+            for _ in 0..4 {
+                _ = state.next_probe(sent);
+            }
+            let received_4 = sent + Duration::from_millis(20);
+            let received_3 = sent + Duration::from_millis(10);
+
+            // the target replies for ttl 4 first
+            state.complete_probe(resp(33437, received_4, true));
+            assert_eq!(state.target_ttl, Some(TimeToLive(4)));
+            assert_eq!(state.max_received_ttl, Some(TimeToLive(4)));
+            assert_eq!(state.received_time, Some(received_4));
+            assert!(state.target_found);
+
+            // the target reply for ttl 3 is processed later but lowers the target ttl
+            state.complete_probe(resp(33436, received_3, true));
+            assert_eq!(state.target_ttl, Some(TimeToLive(3)));
+            assert_eq!(state.max_received_ttl, Some(TimeToLive(4)));
+            assert_eq!(state.received_time, Some(received_4));
+            assert!(state.target_found);
+
+            // a reply for a lower ttl which is not from the target retains the target ttl
+            state.complete_probe(resp(33435, received_3, false));
+            assert_eq!(state.target_ttl, Some(TimeToLive(3)));
+            assert_eq!(state.received_time, Some(received_4));
+        }
+
         fn cfg(initial_sequence: Sequence) -> StrategyConfig {
             StrategyConfig {
                 target_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
@@ -1584,11 +3282,21 @@ mod state {
                 max_ttl: TimeToLive(24),
                 grace_duration: Duration::default(),
                 max_inflight: MaxInflight::default(),
+                send_batch_size: 1,
                 initial_sequence,
                 multipath_strategy: MultipathStrategy::Classic,
                 port_direction: PortDirection::None,
                 min_round_duration: Duration::default(),
                 max_round_duration: Duration::default(),
+                min_probe_interval: None,
+                loss_timeout: None,
+                max_unresponsive_hops: None,
+                ignore_identifier: false,
+                buffer_size: DEFAULT_STRATEGY_BUFFER_SIZE,
+                accept_late_responses: false,
+                adaptive_read_timeout: false,
+                send_jitter: None,
+                send_jitter_seed: None,
             }
         }
     }
@@ -1600,3 +3308,31 @@ fn exceeds(start: Option<SystemTime>, end: SystemTime, dur: Duration) -> bool {
         end.duration_since(start).unwrap_or_default() > dur
     })
 }
+
+/// Determine the largest time-to-live (ttl) of a completed round.
+///
+/// If the ttl of the target is known then it is the largest ttl of the round.
+///
+/// Otherwise, if a response was received for any ttl in the round, the largest ttl is one greater
+/// than the largest ttl for which a response was received, as the next ttl is assumed to represent
+/// the target host, but is never greater than the largest ttl sent in the round.  The largest ttl
+/// sent in the round is the ttl preceding `next_ttl`, the ttl which would have been sent next.
+///
+/// If no response was received, or no ttl was sent, in the round then the largest ttl is zero.
+fn round_largest_ttl(
+    first_ttl: TimeToLive,
+    next_ttl: TimeToLive,
+    max_received_ttl: Option<TimeToLive>,
+    target_ttl: Option<TimeToLive>,
+) -> TimeToLive {
+    if let Some(target_ttl) = target_ttl {
+        return target_ttl;
+    }
+    match max_received_ttl {
+        Some(max_received_ttl) if next_ttl > first_ttl => {
+            let max_sent_ttl = next_ttl - TimeToLive(1);
+            max_sent_ttl.min(TimeToLive(max_received_ttl.0.saturating_add(1)))
+        }
+        _ => TimeToLive(0),
+    }
+}
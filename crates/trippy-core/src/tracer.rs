@@ -1,15 +1,15 @@
 use crate::error::Result;
 use crate::{
-    Error, IcmpExtensionParseMode, MaxInflight, MaxRounds, MultipathStrategy, PacketSize,
-    PayloadPattern, PortDirection, PrivilegeMode, Protocol, Round, Sequence, State, TimeToLive,
-    TraceId, TypeOfService,
+    Error, FlowLabel, IcmpExtensionParseMode, MaxInflight, MaxRounds, MultipathStrategy,
+    PacketSize, PayloadPattern, PayloadStrategy, PortDirection, PrivilegeMode, Probe, Protocol,
+    Round, Sequence, State, TimeToLive, TraceId, TypeOfService,
 };
 use std::fmt::Debug;
 use std::net::IpAddr;
 use std::sync::Arc;
 use std::thread;
 use std::thread::JoinHandle;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 /// A traceroute implementation.
 ///
@@ -25,7 +25,7 @@ impl Tracer {
     /// Create a `Tracer`.
     ///
     /// Use the [`crate::Builder`] type to create a [`Tracer`].
-    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
     #[must_use]
     pub(crate) fn new(
         interface: Option<String>,
@@ -36,22 +36,40 @@ impl Tracer {
         packet_size: PacketSize,
         payload_pattern: PayloadPattern,
         tos: TypeOfService,
+        flow_label: FlowLabel,
         icmp_extension_parse_mode: IcmpExtensionParseMode,
         read_timeout: Duration,
         tcp_connect_timeout: Duration,
+        kernel_recv_timestamps: bool,
+        stamped_payload: bool,
+        payload_strategy: PayloadStrategy,
         trace_identifier: TraceId,
         max_rounds: Option<MaxRounds>,
         first_ttl: TimeToLive,
         max_ttl: TimeToLive,
         grace_duration: Duration,
         max_inflight: MaxInflight,
+        send_batch_size: u8,
+        recv_batch_size: u8,
         initial_sequence: Sequence,
         multipath_strategy: MultipathStrategy,
         port_direction: PortDirection,
         min_round_duration: Duration,
         max_round_duration: Duration,
+        min_probe_interval: Option<Duration>,
+        loss_timeout: Option<Duration>,
+        max_unresponsive_hops: Option<u8>,
+        ignore_identifier: bool,
+        buffer_size: u16,
+        accept_late_responses: bool,
+        adaptive_read_timeout: bool,
+        send_jitter: Option<Duration>,
+        send_jitter_seed: Option<u64>,
+        setup_retry_attempts: usize,
+        setup_retry_backoff: Duration,
         max_samples: usize,
         max_flows: usize,
+        discard_first_hop_warmup: bool,
         drop_privileges: bool,
     ) -> Self {
         Self {
@@ -64,22 +82,40 @@ impl Tracer {
                 packet_size,
                 payload_pattern,
                 tos,
+                flow_label,
                 icmp_extension_parse_mode,
                 read_timeout,
                 tcp_connect_timeout,
+                kernel_recv_timestamps,
+                stamped_payload,
+                payload_strategy,
                 trace_identifier,
                 max_rounds,
                 first_ttl,
                 max_ttl,
                 grace_duration,
                 max_inflight,
+                send_batch_size,
+                recv_batch_size,
                 initial_sequence,
                 multipath_strategy,
                 port_direction,
                 min_round_duration,
                 max_round_duration,
+                min_probe_interval,
+                loss_timeout,
+                max_unresponsive_hops,
+                ignore_identifier,
+                buffer_size,
+                accept_late_responses,
+                adaptive_read_timeout,
+                send_jitter,
+                send_jitter_seed,
+                setup_retry_attempts,
+                setup_retry_backoff,
                 max_samples,
                 max_flows,
+                discard_first_hop_warmup,
                 drop_privileges,
             )),
         }
@@ -98,7 +134,7 @@ impl Tracer {
     /// [`crate::Builder::max_rounds`]), you can either clone and run the
     /// tracer on a separate thread by using the [`Tracer::spawn`] method or
     /// by use the [`Tracer::run_with`] method in the current thread to gather
-    /// pee round state manually.
+    /// per round state manually.
     ///
     /// # Example
     ///
@@ -159,6 +195,22 @@ impl Tracer {
     /// # }
     /// ```
     ///
+    /// The following will run a single round of tracing, print the data from
+    /// the round and then return, without the need for a separate thread:
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// # use std::net::IpAddr;
+    /// # use std::str::FromStr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from_str("1.1.1.1")?;
+    /// let tracer = Builder::new(addr).max_rounds(Some(1)).build()?;
+    /// tracer.run_with(|round| println!("{:?}", round))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
     /// # See Also
     ///
     /// - [`Tracer::run`] - Run the tracer without a custom round handler.
@@ -264,6 +316,36 @@ impl Tracer {
         Ok((self, handle))
     }
 
+    /// Run the [`Tracer`] without sending or receiving any probes.
+    ///
+    /// This method will block until the trace completes all rounds, and so
+    /// [`crate::Builder::max_rounds`] should be set to a non-zero value.
+    ///
+    /// The provided function is called for every probe which would be sent.
+    /// No responses are received, and so every round is paced as if all
+    /// probes were lost.  No sockets are opened and so no privileges are
+    /// required.
+    ///
+    /// # Example
+    ///
+    /// The following will print the probes which would be sent for 3 rounds:
+    ///
+    /// ```no_run
+    /// # fn main() -> anyhow::Result<()> {
+    /// # use std::net::IpAddr;
+    /// # use std::str::FromStr;
+    /// use trippy_core::Builder;
+    ///
+    /// let addr = IpAddr::from_str("1.1.1.1")?;
+    /// let tracer = Builder::new(addr).max_rounds(Some(3)).build()?;
+    /// tracer.dry_run_with(|probe| println!("{:?}", probe))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dry_run_with<F: Fn(&Probe)>(&self, func: F) -> Result<()> {
+        self.inner.dry_run_with(func)
+    }
+
     /// Take a snapshot of the tracer state.
     #[must_use]
     pub fn snapshot(&self) -> State {
@@ -275,6 +357,79 @@ impl Tracer {
         self.inner.clear();
     }
 
+    /// Pause probing of the given time-to-live (ttl).
+    ///
+    /// No probes are sent for a paused ttl until it is resumed, and so the
+    /// state of the hop for the ttl is retained as it was when paused.
+    pub fn pause_ttl(&self, ttl: TimeToLive) {
+        self.inner.pause_ttl(ttl);
+    }
+
+    /// Resume probing of the given time-to-live (ttl).
+    pub fn resume_ttl(&self, ttl: TimeToLive) {
+        self.inner.resume_ttl(ttl);
+    }
+
+    /// Whether probing of the given time-to-live (ttl) is paused.
+    #[must_use]
+    pub fn is_paused_ttl(&self, ttl: TimeToLive) -> bool {
+        self.inner.is_paused_ttl(ttl)
+    }
+
+    /// Pause the trace.
+    ///
+    /// No probes are sent, and no rounds are completed, until the trace is
+    /// resumed.  Responses to probes which were sent before the trace was
+    /// paused are still received and recorded in the round in progress.
+    pub fn pause(&self) {
+        self.inner.pause();
+    }
+
+    /// Resume a paused trace.
+    pub fn resume(&self) {
+        self.inner.resume();
+    }
+
+    /// Whether the trace is paused.
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.inner.is_paused()
+    }
+
+    /// Stop the trace.
+    ///
+    /// The trace stops, and the network channel is closed, within one
+    /// iteration of the trace loop, after which `run` returns `Ok(())`.
+    /// A stopped tracer cannot be restarted.
+    pub fn stop(&self) {
+        self.inner.stop();
+    }
+
+    /// Whether the trace has been stopped.
+    #[must_use]
+    pub fn is_stopped(&self) -> bool {
+        self.inner.is_stopped()
+    }
+
+    /// The time at which the probe for the given time-to-live (ttl) was sent,
+    /// if the probe is awaiting a response in the round in progress.
+    ///
+    /// Probes which are still awaiting a response when the round completes
+    /// are considered lost.
+    #[must_use]
+    pub fn awaited_since(&self, ttl: TimeToLive) -> Option<SystemTime> {
+        self.inner.awaited_since(ttl)
+    }
+
+    /// Update the tracer state from a round.
+    ///
+    /// This allows the state of a tracer which has not been run or spawned to
+    /// be populated from a source other than the network, such as when
+    /// testing.
+    pub fn update_from_round(&self, round: &Round<'_>) {
+        self.inner.handler(round);
+    }
+
     /// The maximum number of flows to record.
     #[must_use]
     pub fn max_flows(&self) -> usize {
@@ -287,6 +442,12 @@ impl Tracer {
         self.inner.max_samples()
     }
 
+    /// Whether the first response from the first hop is discarded.
+    #[must_use]
+    pub fn discard_first_hop_warmup(&self) -> bool {
+        self.inner.discard_first_hop_warmup()
+    }
+
     /// The privilege mode of the tracer.
     #[must_use]
     pub fn privilege_mode(&self) -> PrivilegeMode {
@@ -341,6 +502,12 @@ impl Tracer {
         self.inner.tos()
     }
 
+    /// The `IPv6` flow label of the tracer.
+    #[must_use]
+    pub fn flow_label(&self) -> FlowLabel {
+        self.inner.flow_label()
+    }
+
     /// The ICMP extension parse mode of the tracer.
     #[must_use]
     pub fn icmp_extension_parse_mode(&self) -> IcmpExtensionParseMode {
@@ -359,6 +526,24 @@ impl Tracer {
         self.inner.tcp_connect_timeout()
     }
 
+    /// Whether kernel receive timestamps are used.
+    #[must_use]
+    pub fn kernel_recv_timestamps(&self) -> bool {
+        self.inner.kernel_recv_timestamps()
+    }
+
+    /// Whether a stamp is embedded in the payload of ICMP probes.
+    #[must_use]
+    pub fn stamped_payload(&self) -> bool {
+        self.inner.stamped_payload()
+    }
+
+    /// The strategy for the payload of ICMP probes.
+    #[must_use]
+    pub fn payload_strategy(&self) -> PayloadStrategy {
+        self.inner.payload_strategy()
+    }
+
     /// The trace identifier of the tracer.
     #[must_use]
     pub fn trace_identifier(&self) -> TraceId {
@@ -395,6 +580,18 @@ impl Tracer {
         self.inner.max_inflight()
     }
 
+    /// The maximum number of probes the tracer sends in a single batch.
+    #[must_use]
+    pub fn send_batch_size(&self) -> u8 {
+        self.inner.send_batch_size()
+    }
+
+    /// The maximum number of packets the tracer receives in a single batch.
+    #[must_use]
+    pub fn recv_batch_size(&self) -> u8 {
+        self.inner.recv_batch_size()
+    }
+
     /// The multipath strategy of the tracer.
     #[must_use]
     pub fn multipath_strategy(&self) -> MultipathStrategy {
@@ -418,26 +615,102 @@ impl Tracer {
     pub fn max_round_duration(&self) -> Duration {
         self.inner.max_round_duration()
     }
+
+    /// The minimum interval between probes of the tracer.
+    #[must_use]
+    pub fn min_probe_interval(&self) -> Option<Duration> {
+        self.inner.min_probe_interval()
+    }
+
+    /// The per-probe loss timeout of the tracer.
+    #[must_use]
+    pub fn loss_timeout(&self) -> Option<Duration> {
+        self.inner.loss_timeout()
+    }
+
+    /// The maximum number of unresponsive hops to probe beyond the furthest
+    /// hop which has responded.
+    #[must_use]
+    pub fn max_unresponsive_hops(&self) -> Option<u8> {
+        self.inner.max_unresponsive_hops()
+    }
+
+    /// Whether the identifier of ICMP responses is ignored.
+    #[must_use]
+    pub fn ignore_identifier(&self) -> bool {
+        self.inner.ignore_identifier()
+    }
+
+    /// The maximum number of sequence numbers which may be used in a round.
+    #[must_use]
+    pub fn buffer_size(&self) -> u16 {
+        self.inner.buffer_size()
+    }
+
+    /// Whether late responses for probes sent in the previous round are accepted.
+    #[must_use]
+    pub fn accept_late_responses(&self) -> bool {
+        self.inner.accept_late_responses()
+    }
+
+    /// Whether the read timeout is adapted to the observed round-trip time.
+    #[must_use]
+    pub fn adaptive_read_timeout(&self) -> bool {
+        self.inner.adaptive_read_timeout()
+    }
+
+    /// The maximum send jitter of the tracer.
+    #[must_use]
+    pub fn send_jitter(&self) -> Option<Duration> {
+        self.inner.send_jitter()
+    }
+
+    /// The seed of the send jitter random number generator of the tracer.
+    #[must_use]
+    pub fn send_jitter_seed(&self) -> Option<u64> {
+        self.inner.send_jitter_seed()
+    }
+
+    /// The maximum number of attempts to retry the setup of the tracer.
+    #[must_use]
+    pub fn setup_retry_attempts(&self) -> usize {
+        self.inner.setup_retry_attempts()
+    }
+
+    /// The initial backoff between attempts to retry the setup of the tracer.
+    #[must_use]
+    pub fn setup_retry_backoff(&self) -> Duration {
+        self.inner.setup_retry_backoff()
+    }
 }
 
 mod inner {
     use crate::config::{ChannelConfig, StateConfig, StrategyConfig};
     use crate::error::Result;
+    use crate::net::dry_run::DryRunNetwork;
     use crate::net::{PlatformImpl, SocketImpl};
     use crate::{
-        Channel, Error, IcmpExtensionParseMode, MaxInflight, MaxRounds, MultipathStrategy,
-        PacketSize, PayloadPattern, PortDirection, PrivilegeMode, Protocol, Round, Sequence,
-        SourceAddr, State, Strategy, TimeToLive, TraceId, TypeOfService,
+        Channel, Error, FlowLabel, IcmpExtensionParseMode, MaxInflight, MaxRounds,
+        MultipathStrategy, PacketSize, PayloadPattern, PayloadStrategy, PortDirection,
+        PrivilegeMode, Probe, ProbeStatus, Protocol, Round, Sequence, SourceAddr, State, Strategy,
+        TimeToLive, TraceId, TypeOfService,
     };
     use parking_lot::RwLock;
+    use std::collections::{BTreeMap, BTreeSet};
     use std::fmt::Debug;
     use std::net::IpAddr;
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::OnceLock;
-    use std::time::Duration;
+    use std::thread;
+    use std::time::{Duration, SystemTime};
     use tracing::instrument;
     use trippy_privilege::Privilege;
 
+    /// The maximum backoff between attempts to retry the setup of a tracer.
+    const MAX_SETUP_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
     #[derive(Debug)]
+    #[allow(clippy::struct_excessive_bools)]
     pub(super) struct TracerInner {
         source_addr: Option<IpAddr>,
         interface: Option<String>,
@@ -447,29 +720,51 @@ mod inner {
         packet_size: PacketSize,
         payload_pattern: PayloadPattern,
         tos: TypeOfService,
+        flow_label: FlowLabel,
         icmp_extension_parse_mode: IcmpExtensionParseMode,
         read_timeout: Duration,
         tcp_connect_timeout: Duration,
+        kernel_recv_timestamps: bool,
+        stamped_payload: bool,
+        payload_strategy: PayloadStrategy,
         trace_identifier: TraceId,
         max_rounds: Option<MaxRounds>,
         first_ttl: TimeToLive,
         max_ttl: TimeToLive,
         grace_duration: Duration,
         max_inflight: MaxInflight,
+        send_batch_size: u8,
+        recv_batch_size: u8,
         initial_sequence: Sequence,
         multipath_strategy: MultipathStrategy,
         port_direction: PortDirection,
         min_round_duration: Duration,
         max_round_duration: Duration,
+        min_probe_interval: Option<Duration>,
+        loss_timeout: Option<Duration>,
+        max_unresponsive_hops: Option<u8>,
+        ignore_identifier: bool,
+        buffer_size: u16,
+        accept_late_responses: bool,
+        adaptive_read_timeout: bool,
+        send_jitter: Option<Duration>,
+        send_jitter_seed: Option<u64>,
+        setup_retry_attempts: usize,
+        setup_retry_backoff: Duration,
         max_samples: usize,
         max_flows: usize,
+        discard_first_hop_warmup: bool,
         drop_privileges: bool,
         state: RwLock<State>,
+        paused_ttls: RwLock<BTreeSet<TimeToLive>>,
+        paused: AtomicBool,
+        stopped: AtomicBool,
+        awaited: RwLock<BTreeMap<TimeToLive, SystemTime>>,
         src: OnceLock<IpAddr>,
     }
 
     impl TracerInner {
-        #[allow(clippy::too_many_arguments)]
+        #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
         pub(super) fn new(
             interface: Option<String>,
             source_addr: Option<IpAddr>,
@@ -479,22 +774,40 @@ mod inner {
             packet_size: PacketSize,
             payload_pattern: PayloadPattern,
             tos: TypeOfService,
+            flow_label: FlowLabel,
             icmp_extension_parse_mode: IcmpExtensionParseMode,
             read_timeout: Duration,
             tcp_connect_timeout: Duration,
+            kernel_recv_timestamps: bool,
+            stamped_payload: bool,
+            payload_strategy: PayloadStrategy,
             trace_identifier: TraceId,
             max_rounds: Option<MaxRounds>,
             first_ttl: TimeToLive,
             max_ttl: TimeToLive,
             grace_duration: Duration,
             max_inflight: MaxInflight,
+            send_batch_size: u8,
+            recv_batch_size: u8,
             initial_sequence: Sequence,
             multipath_strategy: MultipathStrategy,
             port_direction: PortDirection,
             min_round_duration: Duration,
             max_round_duration: Duration,
+            min_probe_interval: Option<Duration>,
+            loss_timeout: Option<Duration>,
+            max_unresponsive_hops: Option<u8>,
+            ignore_identifier: bool,
+            buffer_size: u16,
+            accept_late_responses: bool,
+            adaptive_read_timeout: bool,
+            send_jitter: Option<Duration>,
+            send_jitter_seed: Option<u64>,
+            setup_retry_attempts: usize,
+            setup_retry_backoff: Duration,
             max_samples: usize,
             max_flows: usize,
+            discard_first_hop_warmup: bool,
             drop_privileges: bool,
         ) -> Self {
             Self {
@@ -506,24 +819,50 @@ mod inner {
                 packet_size,
                 payload_pattern,
                 tos,
+                flow_label,
                 icmp_extension_parse_mode,
                 read_timeout,
                 tcp_connect_timeout,
+                kernel_recv_timestamps,
+                stamped_payload,
+                payload_strategy,
                 trace_identifier,
                 max_rounds,
                 first_ttl,
                 max_ttl,
                 grace_duration,
                 max_inflight,
+                send_batch_size,
+                recv_batch_size,
                 initial_sequence,
                 multipath_strategy,
                 port_direction,
                 min_round_duration,
                 max_round_duration,
+                min_probe_interval,
+                loss_timeout,
+                max_unresponsive_hops,
+                ignore_identifier,
+                buffer_size,
+                accept_late_responses,
+                adaptive_read_timeout,
+                send_jitter,
+                send_jitter_seed,
+                setup_retry_attempts,
+                setup_retry_backoff,
                 max_samples,
                 max_flows,
+                discard_first_hop_warmup,
                 drop_privileges,
-                state: RwLock::new(State::new(Self::make_state_config(max_flows, max_samples))),
+                state: RwLock::new(State::new(Self::make_state_config(
+                    max_flows,
+                    max_samples,
+                    discard_first_hop_warmup,
+                ))),
+                paused_ttls: RwLock::new(BTreeSet::new()),
+                paused: AtomicBool::new(false),
+                stopped: AtomicBool::new(false),
+                awaited: RwLock::new(BTreeMap::new()),
                 src: OnceLock::new(),
             }
         }
@@ -540,13 +879,75 @@ mod inner {
                 .map_err(|err| self.handle_error(err))
         }
 
+        #[instrument(skip_all)]
+        pub(super) fn dry_run_with<F: Fn(&Probe)>(&self, func: F) -> Result<()> {
+            let strategy_config = self.make_strategy_config();
+            let strategy = Strategy::new(&strategy_config, |_| ())
+                .with_paused(|ttl| self.is_paused_ttl(ttl))
+                .with_stopped(|| self.is_stopped())
+                .with_suspended(|| self.is_paused());
+            strategy.run(DryRunNetwork::new(self.read_timeout, func))?;
+            Ok(())
+        }
+
         pub(super) fn snapshot(&self) -> State {
             self.state.read().clone()
         }
 
         pub(super) fn clear(&self) {
-            *self.state.write() =
-                State::new(Self::make_state_config(self.max_flows, self.max_samples));
+            *self.state.write() = State::new(Self::make_state_config(
+                self.max_flows,
+                self.max_samples,
+                self.discard_first_hop_warmup,
+            ));
+            self.awaited.write().clear();
+        }
+
+        pub(super) fn pause_ttl(&self, ttl: TimeToLive) {
+            self.paused_ttls.write().insert(ttl);
+        }
+
+        pub(super) fn resume_ttl(&self, ttl: TimeToLive) {
+            self.paused_ttls.write().remove(&ttl);
+        }
+
+        pub(super) fn is_paused_ttl(&self, ttl: TimeToLive) -> bool {
+            self.paused_ttls.read().contains(&ttl)
+        }
+
+        pub(super) fn pause(&self) {
+            self.paused.store(true, Ordering::Relaxed);
+        }
+
+        pub(super) fn resume(&self) {
+            self.paused.store(false, Ordering::Relaxed);
+        }
+
+        pub(super) fn is_paused(&self) -> bool {
+            self.paused.load(Ordering::Relaxed)
+        }
+
+        pub(super) fn stop(&self) {
+            self.stopped.store(true, Ordering::Relaxed);
+        }
+
+        pub(super) fn is_stopped(&self) -> bool {
+            self.stopped.load(Ordering::Relaxed)
+        }
+
+        pub(super) fn awaited_since(&self, ttl: TimeToLive) -> Option<SystemTime> {
+            self.awaited.read().get(&ttl).copied()
+        }
+
+        /// Record the send time of the probes of the round in progress which are awaiting a
+        /// response.
+        fn update_awaited(&self, probes: &[ProbeStatus]) {
+            let mut awaited = self.awaited.write();
+            awaited.clear();
+            awaited.extend(probes.iter().filter_map(|probe| match probe {
+                ProbeStatus::Awaited(probe) => Some((probe.ttl, probe.sent)),
+                _ => None,
+            }));
         }
 
         pub(super) const fn max_flows(&self) -> usize {
@@ -557,6 +958,10 @@ mod inner {
             self.max_samples
         }
 
+        pub(super) const fn discard_first_hop_warmup(&self) -> bool {
+            self.discard_first_hop_warmup
+        }
+
         pub(super) const fn privilege_mode(&self) -> PrivilegeMode {
             self.privilege_mode
         }
@@ -593,6 +998,10 @@ mod inner {
             self.tos
         }
 
+        pub(super) const fn flow_label(&self) -> FlowLabel {
+            self.flow_label
+        }
+
         pub(super) const fn icmp_extension_parse_mode(&self) -> IcmpExtensionParseMode {
             self.icmp_extension_parse_mode
         }
@@ -605,6 +1014,18 @@ mod inner {
             self.tcp_connect_timeout
         }
 
+        pub(super) const fn kernel_recv_timestamps(&self) -> bool {
+            self.kernel_recv_timestamps
+        }
+
+        pub(super) const fn stamped_payload(&self) -> bool {
+            self.stamped_payload
+        }
+
+        pub(super) const fn payload_strategy(&self) -> PayloadStrategy {
+            self.payload_strategy
+        }
+
         pub(super) const fn trace_identifier(&self) -> TraceId {
             self.trace_identifier
         }
@@ -629,6 +1050,14 @@ mod inner {
             self.max_inflight
         }
 
+        pub(super) const fn send_batch_size(&self) -> u8 {
+            self.send_batch_size
+        }
+
+        pub(super) const fn recv_batch_size(&self) -> u8 {
+            self.recv_batch_size
+        }
+
         pub(super) const fn multipath_strategy(&self) -> MultipathStrategy {
             self.multipath_strategy
         }
@@ -645,23 +1074,56 @@ mod inner {
             self.max_round_duration
         }
 
+        pub(super) const fn min_probe_interval(&self) -> Option<Duration> {
+            self.min_probe_interval
+        }
+
+        pub(super) const fn loss_timeout(&self) -> Option<Duration> {
+            self.loss_timeout
+        }
+
+        pub(super) const fn max_unresponsive_hops(&self) -> Option<u8> {
+            self.max_unresponsive_hops
+        }
+
+        pub(super) const fn ignore_identifier(&self) -> bool {
+            self.ignore_identifier
+        }
+
+        pub(super) const fn buffer_size(&self) -> u16 {
+            self.buffer_size
+        }
+
+        pub(super) const fn accept_late_responses(&self) -> bool {
+            self.accept_late_responses
+        }
+
+        pub(super) const fn adaptive_read_timeout(&self) -> bool {
+            self.adaptive_read_timeout
+        }
+
+        pub(super) const fn send_jitter(&self) -> Option<Duration> {
+            self.send_jitter
+        }
+
+        pub(super) const fn send_jitter_seed(&self) -> Option<u64> {
+            self.send_jitter_seed
+        }
+
+        pub(super) const fn setup_retry_attempts(&self) -> usize {
+            self.setup_retry_attempts
+        }
+
+        pub(super) const fn setup_retry_backoff(&self) -> Duration {
+            self.setup_retry_backoff
+        }
+
         #[instrument(skip_all)]
         fn run_internal<F: Fn(&Round<'_>)>(&self, func: F) -> Result<()> {
-            // if we are given a source address, validate it otherwise
-            // discover it based on the target address and interface.
-            let source_addr = match self.source_addr {
-                None => SourceAddr::discover::<SocketImpl, PlatformImpl>(
-                    self.target_addr,
-                    self.port_direction,
-                    self.interface.as_deref(),
-                )?,
-                Some(addr) => SourceAddr::validate::<SocketImpl>(addr)?,
-            };
+            let (source_addr, channel) = self.setup_with_retry()?;
             self.src
                 .set(source_addr)
                 .map_err(|_| Error::Other(String::from("failed to set source_addr")))?;
-            let channel_config = self.make_channel_config(source_addr);
-            let channel = Channel::<SocketImpl>::connect(&channel_config)?;
             if self.drop_privileges {
                 Privilege::drop_privileges()?;
             }
@@ -669,13 +1131,63 @@ mod inner {
             let strategy = Strategy::new(&strategy_config, |round| {
                 self.handler(round);
                 func(round);
-            });
+            })
+            .with_paused(|ttl| self.is_paused_ttl(ttl))
+            .with_in_progress(|probes| self.update_awaited(probes))
+            .with_stopped(|| self.is_stopped())
+            .with_suspended(|| self.is_paused());
             strategy.run(channel)?;
             Ok(())
         }
 
-        fn handler(&self, round: &Round<'_>) {
+        /// Setup the tracer, retrying if the setup fails due to a transient error.
+        ///
+        /// The backoff between attempts is doubled after each attempt, up to a
+        /// maximum of `MAX_SETUP_RETRY_BACKOFF` or the initial backoff.
+        fn setup_with_retry(&self) -> Result<(IpAddr, Channel<SocketImpl>)> {
+            let max_backoff = MAX_SETUP_RETRY_BACKOFF.max(self.setup_retry_backoff);
+            let mut backoff = self.setup_retry_backoff;
+            let mut attempt = 0;
+            loop {
+                match self.setup() {
+                    Ok(setup) => {
+                        self.state.write().set_setup_retry_attempt(None);
+                        return Ok(setup);
+                    }
+                    Err(err) if err.is_transient() && attempt < self.setup_retry_attempts => {
+                        attempt += 1;
+                        tracing::warn!(%err, attempt, ?backoff, "retrying tracer setup");
+                        self.state.write().set_setup_retry_attempt(Some(attempt));
+                        thread::sleep(backoff);
+                        backoff = backoff.saturating_mul(2).min(max_backoff);
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+        }
+
+        /// Setup the source address and the channel of the tracer.
+        fn setup(&self) -> Result<(IpAddr, Channel<SocketImpl>)> {
+            // if we are given a source address, validate it otherwise
+            // discover it based on the target address and interface.
+            let source_addr = match self.source_addr {
+                None => SourceAddr::discover::<SocketImpl, PlatformImpl>(
+                    self.target_addr,
+                    self.port_direction,
+                    self.interface.as_deref(),
+                )?,
+                Some(addr) => SourceAddr::validate::<SocketImpl>(addr)?,
+            };
+            let target_scope_id =
+                SourceAddr::scope_id::<PlatformImpl>(self.target_addr, self.interface.as_deref())?;
+            let channel_config = self.make_channel_config(source_addr, target_scope_id);
+            let channel = Channel::<SocketImpl>::connect(&channel_config)?;
+            Ok((source_addr, channel))
+        }
+
+        pub(super) fn handler(&self, round: &Round<'_>) {
             self.state.write().update_from_round(round);
+            self.awaited.write().clear();
         }
 
         fn handle_error(&self, err: Error) -> Error {
@@ -683,26 +1195,41 @@ mod inner {
             err
         }
 
-        const fn make_state_config(max_flows: usize, max_samples: usize) -> StateConfig {
+        const fn make_state_config(
+            max_flows: usize,
+            max_samples: usize,
+            discard_first_hop_warmup: bool,
+        ) -> StateConfig {
             StateConfig {
                 max_samples,
                 max_flows,
+                discard_first_hop_warmup,
             }
         }
 
-        const fn make_channel_config(&self, source_addr: IpAddr) -> ChannelConfig {
+        const fn make_channel_config(
+            &self,
+            source_addr: IpAddr,
+            target_scope_id: u32,
+        ) -> ChannelConfig {
             ChannelConfig {
                 privilege_mode: self.privilege_mode,
                 protocol: self.protocol,
                 source_addr,
                 target_addr: self.target_addr,
+                target_scope_id,
                 packet_size: self.packet_size,
                 payload_pattern: self.payload_pattern,
                 initial_sequence: self.initial_sequence,
                 tos: self.tos,
+                flow_label: self.flow_label,
                 icmp_extension_parse_mode: self.icmp_extension_parse_mode,
                 read_timeout: self.read_timeout,
                 tcp_connect_timeout: self.tcp_connect_timeout,
+                kernel_recv_timestamps: self.kernel_recv_timestamps,
+                recv_batch_size: self.recv_batch_size,
+                stamped_payload: self.stamped_payload,
+                payload_strategy: self.payload_strategy,
             }
         }
 
@@ -716,11 +1243,21 @@ mod inner {
                 max_ttl: self.max_ttl,
                 grace_duration: self.grace_duration,
                 max_inflight: self.max_inflight,
+                send_batch_size: self.send_batch_size,
                 initial_sequence: self.initial_sequence,
                 multipath_strategy: self.multipath_strategy,
                 port_direction: self.port_direction,
                 min_round_duration: self.min_round_duration,
                 max_round_duration: self.max_round_duration,
+                min_probe_interval: self.min_probe_interval,
+                loss_timeout: self.loss_timeout,
+                max_unresponsive_hops: self.max_unresponsive_hops,
+                ignore_identifier: self.ignore_identifier,
+                buffer_size: self.buffer_size,
+                accept_late_responses: self.accept_late_responses,
+                adaptive_read_timeout: self.adaptive_read_timeout,
+                send_jitter: self.send_jitter,
+                send_jitter_seed: self.send_jitter_seed,
             }
         }
     }
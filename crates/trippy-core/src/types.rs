@@ -35,9 +35,38 @@ pub struct PacketSize(pub u16);
 pub struct PayloadPattern(pub u8);
 
 /// `TypeOfService` (aka `DSCP` & `ECN`) newtype.
+///
+/// The upper 6 bits are the Differentiated Services Code Point (`DSCP`) and the lower 2 bits are
+/// the Explicit Congestion Notification (`ECN`) field.  The value is used as the `TOS` byte for
+/// `IPv4` and as the traffic class for `IPv6`.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Ord, PartialOrd)]
 pub struct TypeOfService(pub u8);
 
+impl TypeOfService {
+    /// The Differentiated Services Code Point (`DSCP`), the upper 6 bits.
+    #[must_use]
+    pub const fn dscp(self) -> u8 {
+        self.0 >> 2
+    }
+
+    /// The Explicit Congestion Notification (`ECN`) field, the lower 2 bits.
+    #[must_use]
+    pub const fn ecn(self) -> u8 {
+        self.0 & 0x3
+    }
+}
+
+/// `IPv6` flow label newtype.
+///
+/// Only the lower 20 bits are valid.  A flow label of zero leaves the flow label to the kernel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Ord, PartialOrd)]
+pub struct FlowLabel(pub u32);
+
+impl FlowLabel {
+    /// The maximum flow label.
+    pub const MAX: Self = Self(0xf_ffff);
+}
+
 /// Port newtype.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Ord, PartialOrd)]
 pub struct Port(pub u16);
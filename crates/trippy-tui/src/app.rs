@@ -1,15 +1,29 @@
-use crate::config::{LogFormat, LogSpanEvents, Mode, TrippyConfig};
+use crate::config::{
+    BellTrigger, LocalTargetMode, LogFormat, LogSpanEvents, Mode, TrippyConfig, WebhookTrigger,
+};
+use crate::fixture::Fixture;
 use crate::frontend::TuiConfig;
 use crate::geoip::GeoIpLookup;
 use crate::locale::set_locale;
+use crate::notes::Notes;
+use crate::prometheus::{PrometheusExport, PrometheusExportHandle};
+use crate::report::probes::{ProbeFormat, ProbeRow};
+use crate::report::rounds::RoundRow;
+#[cfg(feature = "sqlite")]
+use crate::sqlite::{SqliteExport, SqliteExportHandle};
+use crate::webhook::Webhook;
 use crate::{frontend, report};
 use anyhow::{anyhow, Error};
-use std::net::IpAddr;
+use std::io::{self, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket};
+use std::str::FromStr;
+use std::sync::mpsc;
+use std::sync::mpsc::{Receiver, Sender};
 use tracing_chrome::{ChromeLayerBuilder, FlushGuard};
 use tracing_subscriber::fmt::format::FmtSpan;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
-use trippy_core::{Builder, Tracer};
+use trippy_core::{is_link_local, Builder, CompletionReason, Tracer};
 use trippy_dns::{DnsResolver, Resolver};
 use trippy_privilege::Privilege;
 
@@ -19,6 +33,7 @@ pub fn run_trippy(cfg: &TrippyConfig, pid: u16) -> anyhow::Result<()> {
     let _guard = configure_logging(cfg);
     let resolver = start_dns_resolver(cfg)?;
     let geoip_lookup = create_geoip_lookup(cfg)?;
+    let notes = create_notes(cfg)?;
     let addrs = resolve_targets(cfg, &resolver)?;
     if addrs.is_empty() {
         return Err(anyhow!(
@@ -27,41 +42,119 @@ pub fn run_trippy(cfg: &TrippyConfig, pid: u16) -> anyhow::Result<()> {
             cfg.addr_family,
         ));
     }
-    let traces = start_tracers(cfg, &addrs, pid)?;
+    check_local_targets(cfg.local_target_mode, &addrs)?;
+    let (probe_tx, probe_rx) = mpsc::channel();
+    let (round_tx, round_rx) = mpsc::channel();
+    let publishers = Publishers {
+        probes: matches!(cfg.mode, Mode::ProbesJson | Mode::ProbesCsv).then_some(probe_tx),
+        rounds: (cfg.mode == Mode::RoundsJson).then_some(round_tx),
+    };
+    let traces = start_tracers(cfg, &addrs, pid, publishers)?;
     Privilege::drop_privileges()?;
-    run_frontend(cfg, resolver, geoip_lookup, traces)
+    let webhook = create_webhook(cfg);
+    if let Some(webhook) = &webhook {
+        if webhook.trigger() == WebhookTrigger::ReachabilityChange {
+            for trace in &traces {
+                webhook.clone().watch(trace.clone(), *resolver.config())?;
+            }
+        }
+    }
+    #[cfg(feature = "sqlite")]
+    let sqlite_export = start_sqlite_export(cfg, &traces, *resolver.config())?;
+    let prometheus_export = start_prometheus_export(cfg, &traces)?;
+    run_frontend(
+        cfg,
+        resolver.clone(),
+        geoip_lookup,
+        notes,
+        traces.clone(),
+        &probe_rx,
+        &round_rx,
+    )?;
+    #[cfg(feature = "sqlite")]
+    if let Some(sqlite_export) = sqlite_export {
+        sqlite_export.finish();
+    }
+    if let Some(prometheus_export) = prometheus_export {
+        prometheus_export.finish();
+    }
+    if let Some(webhook) = &webhook {
+        if webhook.trigger() == WebhookTrigger::Completion {
+            for trace in &traces {
+                webhook.post_completion(trace, &resolver);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The channels to which every round of a tracer is published.
+#[derive(Debug, Default)]
+struct Publishers {
+    /// Receives the probes of every round.
+    probes: Option<Sender<Vec<ProbeRow>>>,
+    /// Receives every round.
+    rounds: Option<Sender<RoundRow>>,
+}
+
+impl Publishers {
+    const fn is_empty(&self) -> bool {
+        self.probes.is_none() && self.rounds.is_none()
+    }
 }
 
 /// Start all tracers.
+///
+/// Every round of the first tracer is sent to the given `publishers`.
 fn start_tracers(
     cfg: &TrippyConfig,
     addrs: &[TargetInfo],
     pid: u16,
+    mut publishers: Publishers,
 ) -> anyhow::Result<Vec<TraceInfo>> {
     addrs
         .iter()
         .enumerate()
-        .map(|(i, TargetInfo { hostname, addr })| {
-            start_tracer(cfg, hostname, *addr, pid + i as u16)
+        .map(|(i, target)| {
+            start_tracer(
+                cfg,
+                target,
+                trace_identifier(pid, i),
+                std::mem::take(&mut publishers),
+            )
         })
         .collect::<anyhow::Result<Vec<_>>>()
 }
 
+/// The trace identifier of the tracer for the target at `index`.
+///
+/// Every target is traced with a distinct identifier such that the responses of each tracer can be
+/// told apart.  The identifier wraps and is never zero, as responses with a zero identifier are
+/// accepted by every tracer.
+#[allow(clippy::cast_possible_truncation)]
+fn trace_identifier(pid: u16, index: usize) -> u16 {
+    let base = u32::from(pid.max(1)) - 1;
+    let offset = (index % usize::from(u16::MAX)) as u32;
+    ((base + offset) % u32::from(u16::MAX) + 1) as u16
+}
+
 /// Start a tracer to a given target.
 fn start_tracer(
     cfg: &TrippyConfig,
-    target_host: &str,
-    target_addr: IpAddr,
+    target: &TargetInfo,
     trace_identifier: u16,
+    publishers: Publishers,
 ) -> Result<TraceInfo, Error> {
-    let (tracer, _) = Builder::new(target_addr)
-        .interface(cfg.interface.clone())
+    let interface = target_interface(cfg, target)?;
+    let tracer = Builder::new(target.addr)
+        .interface(interface)
         .source_addr(cfg.source_addr)
         .privilege_mode(cfg.privilege_mode)
         .protocol(cfg.protocol)
-        .packet_size(cfg.packet_size)
+        .packet_size(cfg.packet_size_for(target.addr))
         .payload_pattern(cfg.payload_pattern)
         .tos(cfg.tos)
+        .flow_label(cfg.flow_label)
         .icmp_extension_parse_mode(cfg.icmp_extension_parse_mode)
         .read_timeout(cfg.read_timeout)
         .tcp_connect_timeout(cfg.min_round_duration)
@@ -71,17 +164,101 @@ fn start_tracer(
         .max_ttl(cfg.max_ttl)
         .grace_duration(cfg.grace_duration)
         .max_inflight(cfg.max_inflight)
+        .send_batch_size(cfg.send_batch_size)
+        .recv_batch_size(cfg.recv_batch_size)
         .initial_sequence(cfg.initial_sequence)
         .multipath_strategy(cfg.multipath_strategy)
         .port_direction(cfg.port_direction)
         .min_round_duration(cfg.min_round_duration)
         .max_round_duration(cfg.max_round_duration)
+        .min_probe_interval(cfg.min_probe_interval)
+        .send_jitter(cfg.send_jitter)
+        .loss_timeout(cfg.loss_timeout)
+        .ignore_identifier(cfg.ignore_identifier)
+        .discard_first_hop_warmup(cfg.discard_first_hop_warmup)
+        .accept_late_responses(cfg.accept_late_responses)
+        .adaptive_read_timeout(cfg.adaptive_read_timeout)
+        .max_unresponsive_hops(
+            cfg.max_unresponsive_hops
+                .filter(|_| cfg.curtail_unresponsive),
+        )
+        .setup_retry_attempts(cfg.setup_retry_attempts)
+        .setup_retry_backoff(cfg.setup_retry_backoff)
+        .kernel_recv_timestamps(cfg.kernel_recv_timestamps)
+        .stamped_payload(cfg.stamped_payload)
+        .payload_strategy(cfg.payload_strategy)
         .max_flows(cfg.max_flows())
         .max_samples(cfg.max_samples)
         .drop_privileges(true)
-        .build()?
-        .spawn()?;
-    Ok(make_trace_info(tracer, target_host.to_string()))
+        .build()?;
+    if let Some(fixture) = &cfg.tui_debug_fixture {
+        Fixture::read(fixture)?.replay(&tracer);
+        return Ok(make_trace_info(tracer, target.hostname.clone()));
+    }
+    if cfg.mode == Mode::DryRun {
+        return Ok(make_trace_info(tracer, target.hostname.clone()));
+    }
+    let bell_trigger = cfg.bell_trigger;
+    let (tracer, _) = match (publishers, bell_trigger) {
+        (publishers, BellTrigger::Never) if publishers.is_empty() => tracer.spawn()?,
+        (publishers, bell_trigger) => tracer.spawn_with(move |round| {
+            // the receivers may have already been dropped
+            if let Some(probe_tx) = &publishers.probes {
+                let _ = probe_tx.send(ProbeRow::from_round(round));
+            }
+            if let Some(round_tx) = &publishers.rounds {
+                let _ = round_tx.send(RoundRow::from_round(round));
+            }
+            if is_bell_round(bell_trigger, round.reason) {
+                ring_bell();
+            }
+        })?,
+    };
+    Ok(make_trace_info(tracer, target.hostname.clone()))
+}
+
+/// Whether a terminal bell should be emitted for a round which completed for the given reason.
+///
+/// A round which completes for any reason other than the target being found had no reply from
+/// the target.
+const fn is_bell_round(trigger: BellTrigger, reason: CompletionReason) -> bool {
+    match trigger {
+        BellTrigger::Never => false,
+        BellTrigger::EveryRound => true,
+        BellTrigger::LossRounds => !reason.target_found(),
+    }
+}
+
+/// Emit a terminal bell.
+///
+/// The bell is written to stderr such that it does not interfere with any report written to
+/// stdout.
+fn ring_bell() {
+    let mut stderr = io::stderr();
+    // a failure to ring the bell is not fatal
+    let _ = stderr.write_all(b"\x07").and_then(|()| stderr.flush());
+}
+
+/// Determine the interface to trace from for a given target.
+///
+/// The zone id of a scoped target is used as the interface, link-local
+/// targets must have either a zone id or an interface.
+fn target_interface(cfg: &TrippyConfig, target: &TargetInfo) -> anyhow::Result<Option<String>> {
+    match (&target.zone, &cfg.interface) {
+        (Some(zone), Some(interface)) if zone != interface => Err(anyhow!(
+            "zone id {} of target {} conflicts with interface {}",
+            zone,
+            target.hostname,
+            interface
+        )),
+        (Some(zone), _) => Ok(Some(zone.clone())),
+        (None, None) if is_link_local(target.addr) => Err(anyhow!(
+            "link-local target {} requires a zone id (i.e. {}%eth0) or an interface",
+            target.hostname,
+            target.addr
+        )),
+        (None, interface) => Ok(interface.clone()),
+    }
 }
 
 /// Run the TUI, stream or report.
@@ -89,10 +266,15 @@ fn run_frontend(
     args: &TrippyConfig,
     resolver: DnsResolver,
     geoip_lookup: GeoIpLookup,
+    notes: Notes,
     traces: Vec<TraceInfo>,
+    probe_rx: &Receiver<Vec<ProbeRow>>,
+    round_rx: &Receiver<RoundRow>,
 ) -> anyhow::Result<()> {
     match args.mode {
-        Mode::Tui => frontend::run_frontend(traces, make_tui_config(args), resolver, geoip_lookup)?,
+        Mode::Tui => {
+            frontend::run_frontend(traces, make_tui_config(args), resolver, geoip_lookup, notes)?;
+        }
         Mode::Stream => report::stream::report(&traces[0], &resolver)?,
         Mode::Csv => report::csv::report(&traces[0], args.report_cycles, &resolver)?,
         Mode::Json => report::json::report(&traces[0], args.report_cycles, &resolver)?,
@@ -100,7 +282,19 @@ fn run_frontend(
         Mode::Markdown => report::table::report_md(&traces[0], args.report_cycles, &resolver)?,
         Mode::Dot => report::dot::report(&traces[0], args.report_cycles)?,
         Mode::Flows => report::flows::report(&traces[0], args.report_cycles)?,
+        Mode::Tree => report::tree::report(&traces[0], args.report_cycles, &resolver)?,
+        Mode::ProbesJson => report::probes::report(
+            &traces[0],
+            args.report_cycles,
+            probe_rx,
+            ProbeFormat::JsonLines,
+        )?,
+        Mode::ProbesCsv => {
+            report::probes::report(&traces[0], args.report_cycles, probe_rx, ProbeFormat::Csv)?;
+        }
+        Mode::RoundsJson => report::rounds::report(&traces[0], args.report_cycles, round_rx)?,
         Mode::Silent => report::silent::report(&traces[0], args.report_cycles)?,
+        Mode::DryRun => report::dry_run::report(&traces[0])?,
     }
     Ok(())
 }
@@ -109,26 +303,111 @@ fn run_frontend(
 fn resolve_targets(cfg: &TrippyConfig, resolver: &DnsResolver) -> anyhow::Result<Vec<TargetInfo>> {
     cfg.targets
         .iter()
-        .flat_map(|target| match resolver.lookup(target) {
-            Ok(addrs) => addrs
-                .into_iter()
-                .enumerate()
-                .take_while(|(i, _)| if cfg.dns_resolve_all { true } else { *i == 0 })
-                .map(|(i, addr)| {
-                    let hostname = if cfg.dns_resolve_all {
-                        format!("{} [{}]", target, i + 1)
-                    } else {
-                        target.to_string()
-                    };
-                    Ok(TargetInfo { hostname, addr })
+        .flat_map(|target| match parse_scoped_target(target) {
+            Some(scoped) => vec![scoped].into_iter(),
+            None => resolve_target(cfg, resolver, target),
+        })
+        .collect::<anyhow::Result<Vec<_>>>()
+}
+
+/// Resolve a single (unscoped) target.
+fn resolve_target(
+    cfg: &TrippyConfig,
+    resolver: &DnsResolver,
+    target: &str,
+) -> std::vec::IntoIter<anyhow::Result<TargetInfo>> {
+    match resolver.lookup(target) {
+        Ok(addrs) => addrs
+            .into_iter()
+            .enumerate()
+            .take_while(|(i, _)| if cfg.dns_resolve_all { true } else { *i == 0 })
+            .map(|(i, addr)| {
+                let hostname = if cfg.dns_resolve_all {
+                    format!("{} [{}]", target, i + 1)
+                } else {
+                    target.to_string()
+                };
+                Ok(TargetInfo {
+                    hostname,
+                    addr,
+                    zone: None,
                 })
-                .collect::<Vec<_>>()
-                .into_iter(),
-            Err(e) => {
-                vec![Err(anyhow!("failed to resolve target: {} ({})", target, e))].into_iter()
+            })
+            .collect::<Vec<_>>()
+            .into_iter(),
+        Err(e) => vec![Err(anyhow!("failed to resolve target: {} ({})", target, e))].into_iter(),
+    }
+}
+
+/// Check for targets which are addresses of the local host.
+///
+/// Depending on the `LocalTargetMode`, local targets are either traced without comment, traced
+/// with a note written to stderr or rejected.
+fn check_local_targets(mode: LocalTargetMode, targets: &[TargetInfo]) -> anyhow::Result<()> {
+    for target in targets.iter().filter(|target| is_local_target(target)) {
+        match mode {
+            LocalTargetMode::Trace => {}
+            LocalTargetMode::Warn => eprintln!(
+                "note: target {} ({}) is an address of the local host, expect a single hop",
+                target.hostname, target.addr
+            ),
+            LocalTargetMode::Reject => {
+                return Err(anyhow!(
+                    "target {} ({}) is an address of the local host (see --local-target-mode)",
+                    target.hostname,
+                    target.addr
+                ))
             }
+        }
+    }
+    Ok(())
+}
+
+/// Is the target an address of the local host?
+///
+/// Loopback and unspecified addresses are always local.  Any other address is local if the
+/// operating system would choose it as the source address for traffic sent to it, i.e. it is the
+/// address of a local interface.  Scoped targets are never considered local.
+fn is_local_target(target: &TargetInfo) -> bool {
+    if target.addr.is_loopback() || target.addr.is_unspecified() {
+        return true;
+    }
+    if target.zone.is_some() {
+        return false;
+    }
+    let bind_addr = match target.addr {
+        IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    // no packets are sent by connecting a udp socket, it only selects a route
+    UdpSocket::bind(SocketAddr::new(bind_addr, 0))
+        .and_then(|socket| {
+            socket.connect(SocketAddr::new(target.addr, 80))?;
+            socket.local_addr()
         })
-        .collect::<anyhow::Result<Vec<_>>>()
+        .is_ok_and(|local| local.ip() == target.addr)
+}
+
+/// Parse a scoped IPv6 target address, such as `fe80::1%eth0`.
+///
+/// Returns `None` if the target is not a scoped IPv6 address.
+fn parse_scoped_target(target: &str) -> Option<anyhow::Result<TargetInfo>> {
+    let (addr, zone) = target.split_once('%')?;
+    let addr = IpAddr::V6(Ipv6Addr::from_str(addr).ok()?);
+    Some(if zone.is_empty() {
+        Err(anyhow!("missing zone id for target {}", target))
+    } else if !is_link_local(addr) {
+        Err(anyhow!(
+            "zone id is only supported for link-local targets: {}",
+            target
+        ))
+    } else {
+        Ok(TargetInfo {
+            hostname: target.to_string(),
+            addr,
+            zone: Some(zone.to_string()),
+        })
+    })
 }
 
 /// Start the DNS resolver.
@@ -149,6 +428,50 @@ fn create_geoip_lookup(cfg: &TrippyConfig) -> anyhow::Result<GeoIpLookup> {
     }
 }
 
+fn create_notes(cfg: &TrippyConfig) -> anyhow::Result<Notes> {
+    if let Some(path) = cfg.tui_notes_file.as_ref() {
+        Notes::from_file(path)
+    } else {
+        Ok(Notes::empty())
+    }
+}
+
+fn create_webhook(cfg: &TrippyConfig) -> Option<Webhook> {
+    cfg.webhook_url.as_ref().map(|url| {
+        Webhook::new(
+            url.clone(),
+            cfg.webhook_trigger,
+            cfg.webhook_fields.clone(),
+            cfg.webhook_retries,
+        )
+    })
+}
+
+#[cfg(feature = "sqlite")]
+fn start_sqlite_export(
+    cfg: &TrippyConfig,
+    traces: &[TraceInfo],
+    resolver_config: trippy_dns::Config,
+) -> anyhow::Result<Option<SqliteExportHandle>> {
+    cfg.sqlite_file
+        .as_ref()
+        .map(|path| {
+            SqliteExport::new(path.clone(), cfg.sqlite_retention)
+                .start(traces.to_vec(), resolver_config)
+        })
+        .transpose()
+}
+
+fn start_prometheus_export(
+    cfg: &TrippyConfig,
+    traces: &[TraceInfo],
+) -> anyhow::Result<Option<PrometheusExportHandle>> {
+    cfg.prometheus_file
+        .as_ref()
+        .map(|path| PrometheusExport::new(path).start(traces.to_vec()))
+        .transpose()
+}
+
 fn configure_logging(cfg: &TrippyConfig) -> Option<FlushGuard> {
     if cfg.verbose {
         let fmt_span = match cfg.log_span_events {
@@ -196,16 +519,50 @@ fn make_tui_config(args: &TrippyConfig) -> TuiConfig {
     TuiConfig::new(
         args.tui_refresh_rate,
         args.tui_privacy_max_ttl,
+        args.tui_first_hop_warning_rounds,
         args.tui_preserve_screen,
         args.tui_address_mode,
         args.dns_lookup_as_info,
         args.tui_as_mode,
+        args.tui_as_colors,
+        args.tui_summary_footer,
+        args.tui_rtt_deltas,
+        args.tui_rtt_compact_threshold,
+        args.tui_rtt_heat_mode,
+        args.tui_rtt_heat_max,
         args.tui_icmp_extension_mode,
         args.tui_geoip_mode,
+        args.tui_unresponsive_hop_mode,
+        args.tui_trailing_hop_mode,
+        args.tui_time_exceeded_rtt_mode,
+        args.tui_average_mode,
+        args.tui_ewma_weight,
+        args.tui_loss_ewma_weight,
+        args.tui_target_ttl_mode,
+        args.tui_host_truncation,
+        args.tui_glyph_set,
         args.tui_max_addrs,
+        args.tui_max_display_hops,
+        args.max_unresponsive_hops,
+        args.tui_stats_window,
+        args.tui_stddev_mode,
+        args.tui_stddev_scope,
+        args.tui_path_change_rounds,
+        args.tui_path_stability_window,
+        args.tui_flow_sort_mode,
+        args.tui_probe_countdown,
+        args.tui_rtt_baseline,
+        args.tui_tunnel_ttl_jump,
+        args.tui_status_mode,
+        args.tui_addr_aggregation,
+        args.tui_auto_quit_stable_rounds,
+        args.tui_auto_quit_stable_threshold,
         args.tui_theme,
         &args.tui_bindings,
         &args.tui_custom_columns,
+        &args.tui_column_groups,
+        &args.tui_rtt_precision,
+        &args.tui_provider_classes,
         args.geoip_mmdb_file.clone(),
         args.dns_resolve_all,
     )
@@ -238,4 +595,85 @@ impl TraceInfo {
 struct TargetInfo {
     pub hostname: String,
     pub addr: IpAddr,
+    /// The zone id of a scoped IPv6 target, if any.
+    pub zone: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("fe80::1%eth0", "fe80::1", "eth0"; "link-local")]
+    #[test_case("fe80::abcd:1%en0", "fe80::abcd:1", "en0"; "link-local long")]
+    fn test_parse_scoped_target(target: &str, expected_addr: &str, expected_zone: &str) {
+        let info = parse_scoped_target(target).unwrap().unwrap();
+        assert_eq!(IpAddr::from_str(expected_addr).unwrap(), info.addr);
+        assert_eq!(Some(expected_zone), info.zone.as_deref());
+        assert_eq!(target, info.hostname);
+    }
+
+    #[test_case("example.com"; "hostname")]
+    #[test_case("fe80::1"; "unscoped")]
+    #[test_case("1.2.3.4"; "ipv4")]
+    #[test_case("foo%bar"; "not an address")]
+    fn test_parse_unscoped_target(target: &str) {
+        assert!(parse_scoped_target(target).is_none());
+    }
+
+    #[test_case(BellTrigger::Never, CompletionReason::TargetFound, false; "never found")]
+    #[test_case(BellTrigger::Never, CompletionReason::RoundTimeLimitExceeded, false; "never lost")]
+    #[test_case(BellTrigger::EveryRound, CompletionReason::TargetFound, true; "every found")]
+    #[test_case(BellTrigger::EveryRound, CompletionReason::RoundTimeLimitExceeded, true; "every lost")]
+    #[test_case(BellTrigger::LossRounds, CompletionReason::TargetFound, false; "loss found")]
+    #[test_case(BellTrigger::LossRounds, CompletionReason::RoundTimeLimitExceeded, true; "loss lost")]
+    #[test_case(BellTrigger::LossRounds, CompletionReason::TargetFoundRoundTimeLimitExceeded, false; "loss found time limit exceeded")]
+    fn test_is_bell_round(trigger: BellTrigger, reason: CompletionReason, expected: bool) {
+        assert_eq!(expected, is_bell_round(trigger, reason));
+    }
+
+    #[test_case("fe80::1%"; "missing zone")]
+    #[test_case("2001:db8::1%eth0"; "not link-local")]
+    fn test_parse_scoped_target_invalid(target: &str) {
+        assert!(parse_scoped_target(target).unwrap().is_err());
+    }
+
+    #[test_case("127.0.0.1"; "ipv4 loopback")]
+    #[test_case("127.1.2.3"; "ipv4 loopback range")]
+    #[test_case("0.0.0.0"; "ipv4 unspecified")]
+    #[test_case("::1"; "ipv6 loopback")]
+    #[test_case("::"; "ipv6 unspecified")]
+    fn test_is_local_target(addr: &str) {
+        assert!(is_local_target(&target_info(addr, None)));
+    }
+
+    #[test]
+    fn test_is_local_target_scoped() {
+        assert!(!is_local_target(&target_info("fe80::1", Some("eth0"))));
+    }
+
+    #[test_case(LocalTargetMode::Trace, true; "trace")]
+    #[test_case(LocalTargetMode::Warn, true; "warn")]
+    #[test_case(LocalTargetMode::Reject, false; "reject")]
+    fn test_check_local_targets(mode: LocalTargetMode, expected: bool) {
+        let targets = [target_info("127.0.0.1", None)];
+        assert_eq!(expected, check_local_targets(mode, &targets).is_ok());
+    }
+
+    #[test_case(100, 0 => 100; "first target")]
+    #[test_case(100, 2 => 102; "third target")]
+    #[test_case(65534, 1 => 65535; "max identifier")]
+    #[test_case(65535, 1 => 1; "wraps skipping zero")]
+    #[test_case(0, 0 => 1; "zero pid")]
+    fn test_trace_identifier(pid: u16, index: usize) -> u16 {
+        trace_identifier(pid, index)
+    }
+
+    fn target_info(addr: &str, zone: Option<&str>) -> TargetInfo {
+        TargetInfo {
+            hostname: addr.to_string(),
+            addr: IpAddr::from_str(addr).unwrap(),
+            zone: zone.map(ToString::to_string),
+        }
+    }
 }
@@ -8,8 +8,8 @@ use std::collections::HashMap;
 use std::net::IpAddr;
 use std::time::Duration;
 use trippy_core::{
-    defaults, IcmpExtensionParseMode, MultipathStrategy, PortDirection, PrivilegeMode, Protocol,
-    MAX_TTL,
+    defaults, FlowLabel, IcmpExtensionParseMode, MultipathStrategy, PayloadStrategy, PortDirection,
+    PrivilegeMode, Protocol, MAX_TTL,
 };
 use trippy_dns::{IpAddrFamily, ResolveMethod};
 
@@ -23,7 +23,7 @@ mod theme;
 use crate::config::file::ConfigTui;
 pub use binding::{TuiBindings, TuiCommandItem, TuiKeyBinding};
 pub use cmd::Args;
-pub use columns::{TuiColumn, TuiColumns};
+pub use columns::{TuiColumn, TuiColumnGroup, TuiColumnGroups, TuiColumns, TuiRttPrecision};
 pub use theme::{TuiColor, TuiTheme, TuiThemeItem};
 use trippy_privilege::Privilege;
 
@@ -47,8 +47,18 @@ pub enum Mode {
     Dot,
     /// Display all flows for N cycles.
     Flows,
+    /// Generate a JSON tree of all flows for N cycles.
+    Tree,
+    /// Generate a JSON lines report of all probes for N cycles.
+    ProbesJson,
+    /// Generate a CSV report of all probes for N cycles.
+    ProbesCsv,
+    /// Generate a JSON lines report of all rounds for N cycles.
+    RoundsJson,
     /// Do not generate any tracing output for N cycles.
     Silent,
+    /// Print the probes which would be sent for N cycles without sending them.
+    DryRun,
 }
 
 /// The tracing protocol.
@@ -122,6 +132,25 @@ impl From<MultipathStrategy> for MultipathStrategyConfig {
     }
 }
 
+/// The strategy for the payload of icmp probes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PayloadStrategyConfig {
+    /// Fill the payload with the payload pattern.
+    Pattern,
+    /// Adjust the payload to hold the icmp checksum constant.
+    ConstantChecksum,
+}
+
+impl From<PayloadStrategy> for PayloadStrategyConfig {
+    fn from(value: PayloadStrategy) -> Self {
+        match value {
+            PayloadStrategy::Pattern => Self::Pattern,
+            PayloadStrategy::ConstantChecksum => Self::ConstantChecksum,
+        }
+    }
+}
+
 /// How to render the addresses.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -152,6 +181,203 @@ pub enum AsMode {
     Name,
 }
 
+/// How to render unresponsive hops in the hops table.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnresponsiveHopMode {
+    /// Show `No response`.
+    NoResponse,
+    /// Show nothing.
+    Blank,
+    /// Show `* * *`.
+    Stars,
+    /// Show the last known addresses dimmed.
+    LastKnown,
+}
+
+/// How to render the trailing hop in the hops table.
+///
+/// The trailing hop is the hop beyond the highest responding hop which is
+/// probed whilst the target has not been found, and which has never responded.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TrailingHopMode {
+    /// Show `(waiting for response)`.
+    Waiting,
+    /// Show as any other unresponsive hop.
+    Unresponsive,
+    /// Do not show the trailing hop.
+    Hide,
+}
+
+/// How to render round-trip times derived from `TimeExceeded` responses in the hops table.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeExceededRttMode {
+    /// Show round-trip times for all hops.
+    Include,
+    /// Show round-trip times derived from `TimeExceeded` responses in italics.
+    Distinguish,
+    /// Do not show round-trip times derived from `TimeExceeded` responses.
+    Exclude,
+}
+
+/// How to compute the average round-trip time of a hop.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AverageMode {
+    /// The mean of all round-trip times.
+    Mean,
+    /// The exponentially weighted moving average of recent round-trip times.
+    Ewma,
+}
+
+/// What to show in the status column.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusMode {
+    /// A glyph showing whether the hop has no, some or total loss.
+    Health,
+    /// The number of probes received and sent.
+    Summary,
+    /// The type of the last ICMP response and the reason for any unreachable response.
+    Icmp,
+}
+
+/// How to show a hop which is answered by more than one address.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AddrAggregationMode {
+    /// Show the statistics of all addresses of the hop combined.
+    Combined,
+    /// Also show a row per address with the statistics of that address.
+    Split,
+}
+
+/// How to sort the flows.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FlowSortMode {
+    /// Sort by the number of rounds in which the flow was seen.
+    Rounds,
+    /// Sort by the average round-trip time of the last hop of the flow.
+    Rtt,
+    /// Sort by the loss % of the last hop of the flow.
+    Loss,
+}
+
+/// Which time-to-live values at which the target replied to show.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TargetTtlMode {
+    /// Show only the lowest time-to-live at which the target replied.
+    Lowest,
+    /// Show all time-to-live values at which the target replied.
+    All,
+}
+
+/// How to color the `Last` round-trip time of hops.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RttHeatMode {
+    /// Do not color round-trip times.
+    Off,
+    /// Color round-trip times on a gradient scaled to the range of round-trip times of the path.
+    Path,
+    /// Color round-trip times on a gradient scaled from zero to a fixed maximum.
+    Fixed,
+}
+
+/// How to compute the standard deviation of round-trip times.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StdDevMode {
+    /// The sample standard deviation, which divides by one less than the number of replies.
+    Sample,
+    /// The population standard deviation, which divides by the number of replies.
+    Population,
+}
+
+/// The rounds over which to compute the standard deviation of round-trip times.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StdDevScope {
+    /// Compute over the statistics window, which is all rounds if no window is set.
+    Window,
+    /// Compute over all rounds, even if a statistics window is set.
+    All,
+}
+
+/// When to post to the webhook.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookTrigger {
+    /// Post a summary when the trace completes.
+    Completion,
+    /// Post a summary when the reachability of the target changes.
+    ReachabilityChange,
+}
+
+/// A field of the webhook payload.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookField {
+    /// The target address and hostname.
+    Target,
+    /// The number of completed rounds.
+    Round,
+    /// Whether the target replied in the latest round.
+    Reachable,
+    /// The statistics of every hop.
+    Hops,
+}
+
+/// How to handle a target which is an address of the local host.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LocalTargetMode {
+    /// Trace the target without comment.
+    Trace,
+    /// Trace the target and print a note that it is local.
+    Warn,
+    /// Do not trace the target and report an error.
+    Reject,
+}
+
+/// When to emit a terminal bell.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BellTrigger {
+    /// Never emit a bell.
+    Never,
+    /// Emit a bell when each round completes.
+    EveryRound,
+    /// Emit a bell when a round completes without a reply from the target.
+    LossRounds,
+}
+
+/// Which side to truncate hostnames which do not fit in the hops table.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum HostTruncation {
+    /// Truncate the end of hostnames, with a trailing ellipsis.
+    Right,
+    /// Truncate the start of hostnames, with a leading ellipsis.
+    Left,
+}
+
+/// Which set of glyphs to use for the symbols shown in the Tui.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GlyphSet {
+    /// Use Unicode glyphs if the locale uses UTF-8, otherwise ASCII glyphs.
+    Auto,
+    /// Use Unicode glyphs.
+    Unicode,
+    /// Use ASCII glyphs only.
+    Ascii,
+}
+
 /// How to render `icmp` extensions in the hops table.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -288,14 +514,33 @@ pub struct TrippyConfig {
     pub max_round_duration: Duration,
     pub grace_duration: Duration,
     pub max_inflight: u8,
+    pub send_batch_size: u8,
+    pub recv_batch_size: u8,
     pub initial_sequence: u16,
     pub tos: u8,
+    pub flow_label: u32,
     pub icmp_extension_parse_mode: IcmpExtensionParseMode,
     pub read_timeout: Duration,
+    pub min_probe_interval: Option<Duration>,
+    pub send_jitter: Option<Duration>,
+    pub loss_timeout: Option<Duration>,
+    pub ignore_identifier: bool,
+    pub discard_first_hop_warmup: bool,
+    pub accept_late_responses: bool,
+    pub adaptive_read_timeout: bool,
+    pub max_unresponsive_hops: Option<u8>,
+    pub curtail_unresponsive: bool,
+    pub setup_retry_attempts: usize,
+    pub setup_retry_backoff: Duration,
+    pub kernel_recv_timestamps: bool,
+    pub stamped_payload: bool,
+    pub payload_strategy: PayloadStrategy,
     pub packet_size: u16,
+    pub icmp_data_length: Option<u16>,
     pub payload_pattern: u8,
     pub source_addr: Option<IpAddr>,
     pub interface: Option<String>,
+    pub local_target_mode: LocalTargetMode,
     pub multipath_strategy: MultipathStrategy,
     pub port_direction: PortDirection,
     pub dns_timeout: Duration,
@@ -307,19 +552,62 @@ pub struct TrippyConfig {
     pub tui_preserve_screen: bool,
     pub tui_refresh_rate: Duration,
     pub tui_privacy_max_ttl: u8,
+    pub tui_first_hop_warning_rounds: usize,
     pub tui_address_mode: AddressMode,
     pub tui_as_mode: AsMode,
+    pub tui_as_colors: bool,
+    pub tui_summary_footer: bool,
+    pub tui_rtt_deltas: bool,
+    pub tui_rtt_baseline: bool,
+    pub tui_rtt_compact_threshold: Option<Duration>,
+    pub tui_rtt_heat_mode: RttHeatMode,
+    pub tui_rtt_heat_max: Duration,
     pub tui_custom_columns: TuiColumns,
+    pub tui_column_groups: TuiColumnGroups,
+    pub tui_rtt_precision: TuiRttPrecision,
     pub tui_icmp_extension_mode: IcmpExtensionMode,
     pub tui_geoip_mode: GeoIpMode,
+    pub tui_unresponsive_hop_mode: UnresponsiveHopMode,
+    pub tui_trailing_hop_mode: TrailingHopMode,
+    pub tui_time_exceeded_rtt_mode: TimeExceededRttMode,
+    pub tui_average_mode: AverageMode,
+    pub tui_ewma_weight: u8,
+    pub tui_loss_ewma_weight: u8,
+    pub tui_tunnel_ttl_jump: u8,
+    pub tui_status_mode: StatusMode,
+    pub tui_addr_aggregation: AddrAggregationMode,
+    pub tui_target_ttl_mode: TargetTtlMode,
+    pub tui_host_truncation: HostTruncation,
+    pub tui_glyph_set: GlyphSet,
     pub tui_max_addrs: Option<u8>,
+    pub tui_max_display_hops: Option<u8>,
+    pub tui_stats_window: Option<usize>,
+    pub tui_stddev_mode: StdDevMode,
+    pub tui_stddev_scope: StdDevScope,
+    pub tui_path_change_rounds: usize,
+    pub tui_path_stability_window: Duration,
+    pub tui_flow_sort_mode: FlowSortMode,
+    pub tui_probe_countdown: bool,
+    pub tui_auto_quit_stable_rounds: Option<usize>,
+    pub tui_auto_quit_stable_threshold: Duration,
     pub tui_locale: Option<String>,
+    pub tui_notes_file: Option<String>,
+    pub tui_debug_fixture: Option<String>,
+    pub tui_provider_classes: HashMap<u32, String>,
     pub tui_theme: TuiTheme,
     pub tui_bindings: TuiBindings,
     pub mode: Mode,
     pub privilege_mode: PrivilegeMode,
     pub dns_resolve_all: bool,
     pub report_cycles: usize,
+    pub webhook_url: Option<String>,
+    pub webhook_trigger: WebhookTrigger,
+    pub webhook_fields: Vec<WebhookField>,
+    pub webhook_retries: u8,
+    pub sqlite_file: Option<String>,
+    pub sqlite_retention: Option<Duration>,
+    pub prometheus_file: Option<String>,
+    pub bell_trigger: BellTrigger,
     pub geoip_mmdb_file: Option<String>,
     pub max_rounds: Option<usize>,
     pub verbose: bool,
@@ -348,6 +636,18 @@ impl TrippyConfig {
         }
     }
 
+    /// The packet size to use for a given target address.
+    ///
+    /// If an icmp data length is configured then the packet size is derived
+    /// from the address family of the target.
+    pub const fn packet_size_for(&self, addr: IpAddr) -> u16 {
+        match (self.icmp_data_length, addr) {
+            (Some(data_length), IpAddr::V4(_)) => constants::MIN_PACKET_SIZE_IPV4 + data_length,
+            (Some(data_length), IpAddr::V6(_)) => constants::MIN_PACKET_SIZE_IPV6 + data_length,
+            (None, _) => self.packet_size,
+        }
+    }
+
     #[allow(clippy::too_many_lines)]
     fn build_config(
         args: Args,
@@ -411,6 +711,11 @@ impl TrippyConfig {
         let source_port = cfg_layer_opt(args.source_port, cfg_file_strategy.source_port);
         let source_addr = cfg_layer_opt(args.source_address, cfg_file_strategy.source_address);
         let interface = cfg_layer_opt(args.interface, cfg_file_strategy.interface);
+        let local_target_mode = cfg_layer(
+            args.local_target_mode,
+            cfg_file_strategy.local_target_mode,
+            constants::DEFAULT_LOCAL_TARGET_MODE,
+        );
         let min_round_duration = cfg_layer(
             args.min_round_duration,
             cfg_file_strategy.min_round_duration,
@@ -441,6 +746,16 @@ impl TrippyConfig {
             cfg_file_strategy.max_inflight,
             defaults::DEFAULT_STRATEGY_MAX_INFLIGHT,
         );
+        let send_batch_size = cfg_layer(
+            args.send_batch_size,
+            cfg_file_strategy.send_batch_size,
+            defaults::DEFAULT_STRATEGY_SEND_BATCH_SIZE,
+        );
+        let recv_batch_size = cfg_layer(
+            args.recv_batch_size,
+            cfg_file_strategy.recv_batch_size,
+            defaults::DEFAULT_STRATEGY_RECV_BATCH_SIZE,
+        );
         let first_ttl = cfg_layer(
             args.first_ttl,
             cfg_file_strategy.first_ttl,
@@ -456,6 +771,11 @@ impl TrippyConfig {
             cfg_file_strategy.packet_size,
             defaults::DEFAULT_STRATEGY_PACKET_SIZE,
         );
+        let icmp_data_length = match (args.icmp_data_length, args.packet_size) {
+            (Some(icmp_data_length), _) => Some(icmp_data_length),
+            (None, Some(_)) => None,
+            (None, None) => cfg_file_strategy.icmp_data_length,
+        };
         let payload_pattern = cfg_layer(
             args.payload_pattern,
             cfg_file_strategy.payload_pattern,
@@ -466,6 +786,11 @@ impl TrippyConfig {
             cfg_file_strategy.tos,
             defaults::DEFAULT_STRATEGY_TOS,
         );
+        let flow_label = cfg_layer(
+            args.flow_label,
+            cfg_file_strategy.flow_label,
+            defaults::DEFAULT_STRATEGY_FLOW_LABEL,
+        );
         let icmp_extensions = cfg_layer_bool_flag(
             args.icmp_extensions,
             cfg_file_strategy.icmp_extensions,
@@ -481,6 +806,69 @@ impl TrippyConfig {
             cfg_file_strategy.read_timeout,
             defaults::DEFAULT_STRATEGY_READ_TIMEOUT,
         );
+        let min_probe_interval = cfg_layer_opt(
+            args.min_probe_interval,
+            cfg_file_strategy.min_probe_interval,
+        );
+        let send_jitter = cfg_layer_opt(args.send_jitter, cfg_file_strategy.send_jitter);
+        let loss_timeout = cfg_layer_opt(args.loss_timeout, cfg_file_strategy.loss_timeout);
+        let ignore_identifier = cfg_layer_bool_flag(
+            args.ignore_identifier,
+            cfg_file_strategy.ignore_identifier,
+            false,
+        );
+        let discard_first_hop_warmup = cfg_layer_bool_flag(
+            args.discard_first_hop_warmup,
+            cfg_file_strategy.discard_first_hop_warmup,
+            false,
+        );
+        let accept_late_responses = cfg_layer_bool_flag(
+            args.accept_late_responses,
+            cfg_file_strategy.accept_late_responses,
+            false,
+        );
+        let adaptive_read_timeout = cfg_layer_bool_flag(
+            args.adaptive_read_timeout,
+            cfg_file_strategy.adaptive_read_timeout,
+            false,
+        );
+        let max_unresponsive_hops = cfg_layer_opt(
+            args.max_unresponsive_hops,
+            cfg_file_strategy.max_unresponsive_hops,
+        );
+        let curtail_unresponsive = cfg_layer_bool_flag(
+            args.curtail_unresponsive,
+            cfg_file_strategy.curtail_unresponsive,
+            false,
+        );
+        let setup_retry_attempts = cfg_layer(
+            args.setup_retry_attempts,
+            cfg_file_strategy.setup_retry_attempts,
+            defaults::DEFAULT_SETUP_RETRY_ATTEMPTS,
+        );
+        let setup_retry_backoff = cfg_layer(
+            args.setup_retry_backoff,
+            cfg_file_strategy.setup_retry_backoff,
+            defaults::DEFAULT_SETUP_RETRY_BACKOFF,
+        );
+        let kernel_recv_timestamps = cfg_layer_bool_flag(
+            args.kernel_recv_timestamps,
+            cfg_file_strategy.kernel_recv_timestamps,
+            false,
+        );
+        let stamped_payload = cfg_layer_bool_flag(
+            args.stamped_payload,
+            cfg_file_strategy.stamped_payload,
+            false,
+        );
+        let payload_strategy = match cfg_layer(
+            args.payload_strategy,
+            cfg_file_strategy.payload_strategy,
+            PayloadStrategyConfig::from(defaults::DEFAULT_STRATEGY_PAYLOAD_STRATEGY),
+        ) {
+            PayloadStrategyConfig::Pattern => PayloadStrategy::Pattern,
+            PayloadStrategyConfig::ConstantChecksum => PayloadStrategy::ConstantChecksum,
+        };
         let max_samples = cfg_layer(
             args.max_samples,
             cfg_file_strategy.max_samples,
@@ -506,6 +894,11 @@ impl TrippyConfig {
             cfg_file_tui.tui_privacy_max_ttl,
             constants::DEFAULT_TUI_PRIVACY_MAX_TTL,
         );
+        let tui_first_hop_warning_rounds = cfg_layer(
+            args.tui_first_hop_warning_rounds,
+            cfg_file_tui.tui_first_hop_warning_rounds,
+            constants::DEFAULT_TUI_FIRST_HOP_WARNING_ROUNDS,
+        );
         let tui_address_mode = cfg_layer(
             args.tui_address_mode,
             cfg_file_tui.tui_address_mode,
@@ -516,12 +909,60 @@ impl TrippyConfig {
             cfg_file_tui.tui_as_mode,
             constants::DEFAULT_TUI_AS_MODE,
         );
+        let tui_as_colors = cfg_layer_bool_flag(
+            args.tui_as_colors,
+            cfg_file_tui.tui_as_colors,
+            constants::DEFAULT_TUI_AS_COLORS,
+        );
+        let tui_summary_footer = cfg_layer_bool_flag(
+            args.tui_summary_footer,
+            cfg_file_tui.tui_summary_footer,
+            constants::DEFAULT_TUI_SUMMARY_FOOTER,
+        );
+        let tui_rtt_deltas = cfg_layer_bool_flag(
+            args.tui_rtt_deltas,
+            cfg_file_tui.tui_rtt_deltas,
+            constants::DEFAULT_TUI_RTT_DELTAS,
+        );
+        let tui_rtt_baseline = cfg_layer_bool_flag(
+            args.tui_rtt_baseline,
+            cfg_file_tui.tui_rtt_baseline,
+            constants::DEFAULT_TUI_RTT_BASELINE,
+        );
+        let tui_rtt_compact_threshold = cfg_layer_opt(
+            args.tui_rtt_compact_threshold,
+            cfg_file_tui.tui_rtt_compact_threshold,
+        );
+        let tui_rtt_heat_mode = cfg_layer(
+            args.tui_rtt_heat_mode,
+            cfg_file_tui.tui_rtt_heat_mode,
+            constants::DEFAULT_TUI_RTT_HEAT_MODE,
+        );
+        let tui_rtt_heat_max = cfg_layer(
+            args.tui_rtt_heat_max,
+            cfg_file_tui.tui_rtt_heat_max,
+            constants::DEFAULT_TUI_RTT_HEAT_MAX,
+        );
         let columns = cfg_layer(
             args.tui_custom_columns,
             cfg_file_tui.tui_custom_columns,
             String::from(constants::DEFAULT_CUSTOM_COLUMNS),
         );
         let tui_custom_columns = TuiColumns::try_from(columns.as_str())?;
+        let column_groups = cfg_layer(
+            args.tui_column_groups,
+            cfg_file_tui.tui_column_groups,
+            String::from(constants::DEFAULT_TUI_COLUMN_GROUPS),
+        );
+        let tui_column_groups = TuiColumnGroups::try_from(column_groups.as_str())?;
+        let tui_rtt_precision = TuiRttPrecision::try_from(
+            cfg_layer(
+                args.tui_rtt_precision,
+                cfg_file_tui.tui_rtt_precision,
+                String::from(constants::DEFAULT_TUI_RTT_PRECISION),
+            )
+            .as_str(),
+        )?;
         let tui_icmp_extension_mode = cfg_layer(
             args.tui_icmp_extension_mode,
             cfg_file_tui.tui_icmp_extension_mode,
@@ -532,13 +973,128 @@ impl TrippyConfig {
             cfg_file_tui.tui_geoip_mode,
             constants::DEFAULT_TUI_GEOIP_MODE,
         );
+        let tui_unresponsive_hop_mode = cfg_layer(
+            args.tui_unresponsive_hop_mode,
+            cfg_file_tui.tui_unresponsive_hop_mode,
+            constants::DEFAULT_TUI_UNRESPONSIVE_HOP_MODE,
+        );
+        let tui_trailing_hop_mode = cfg_layer(
+            args.tui_trailing_hop_mode,
+            cfg_file_tui.tui_trailing_hop_mode,
+            constants::DEFAULT_TUI_TRAILING_HOP_MODE,
+        );
+        let tui_time_exceeded_rtt_mode = cfg_layer(
+            args.tui_time_exceeded_rtt_mode,
+            cfg_file_tui.tui_time_exceeded_rtt_mode,
+            constants::DEFAULT_TUI_TIME_EXCEEDED_RTT_MODE,
+        );
+        let tui_average_mode = cfg_layer(
+            args.tui_average_mode,
+            cfg_file_tui.tui_average_mode,
+            constants::DEFAULT_TUI_AVERAGE_MODE,
+        );
+        let tui_ewma_weight = cfg_layer(
+            args.tui_ewma_weight,
+            cfg_file_tui.tui_ewma_weight,
+            constants::DEFAULT_TUI_EWMA_WEIGHT,
+        );
+        let tui_loss_ewma_weight = cfg_layer(
+            args.tui_loss_ewma_weight,
+            cfg_file_tui.tui_loss_ewma_weight,
+            constants::DEFAULT_TUI_LOSS_EWMA_WEIGHT,
+        );
+        let tui_tunnel_ttl_jump = cfg_layer(
+            args.tui_tunnel_ttl_jump,
+            cfg_file_tui.tui_tunnel_ttl_jump,
+            constants::DEFAULT_TUI_TUNNEL_TTL_JUMP,
+        );
+        let tui_status_mode = cfg_layer(
+            args.tui_status_mode,
+            cfg_file_tui.tui_status_mode,
+            constants::DEFAULT_TUI_STATUS_MODE,
+        );
+        let tui_addr_aggregation = cfg_layer(
+            args.tui_addr_aggregation,
+            cfg_file_tui.tui_addr_aggregation,
+            constants::DEFAULT_TUI_ADDR_AGGREGATION,
+        );
+        let tui_target_ttl_mode = cfg_layer(
+            args.tui_target_ttl_mode,
+            cfg_file_tui.tui_target_ttl_mode,
+            constants::DEFAULT_TUI_TARGET_TTL_MODE,
+        );
+        let tui_host_truncation = cfg_layer(
+            args.tui_host_truncation,
+            cfg_file_tui.tui_host_truncation,
+            constants::DEFAULT_TUI_HOST_TRUNCATION,
+        );
+        let tui_glyph_set = cfg_layer(
+            args.tui_glyph_set,
+            cfg_file_tui.tui_glyph_set,
+            constants::DEFAULT_TUI_GLYPH_SET,
+        );
         let tui_max_addrs = cfg_layer_opt(args.tui_max_addrs, cfg_file_tui.tui_max_addrs);
+        let tui_max_display_hops =
+            cfg_layer_opt(args.tui_max_display_hops, cfg_file_tui.tui_max_display_hops);
+        let tui_stats_window = cfg_layer_opt(args.tui_stats_window, cfg_file_tui.tui_stats_window)
+            .filter(|&rounds| rounds > 0);
+        let tui_stddev_mode = cfg_layer(
+            args.tui_stddev_mode,
+            cfg_file_tui.tui_stddev_mode,
+            constants::DEFAULT_TUI_STDDEV_MODE,
+        );
+        let tui_stddev_scope = cfg_layer(
+            args.tui_stddev_scope,
+            cfg_file_tui.tui_stddev_scope,
+            constants::DEFAULT_TUI_STDDEV_SCOPE,
+        );
+        let tui_max_rounds = cfg_layer_opt(args.tui_max_rounds, cfg_file_tui.tui_max_rounds)
+            .filter(|&rounds| rounds > 0);
+        let tui_path_change_rounds = cfg_layer(
+            args.tui_path_change_rounds,
+            cfg_file_tui.tui_path_change_rounds,
+            constants::DEFAULT_TUI_PATH_CHANGE_ROUNDS,
+        );
+        let tui_path_stability_window = cfg_layer(
+            args.tui_path_stability_window,
+            cfg_file_tui.tui_path_stability_window,
+            constants::DEFAULT_TUI_PATH_STABILITY_WINDOW,
+        );
+        let tui_flow_sort_mode = cfg_layer(
+            args.tui_flow_sort_mode,
+            cfg_file_tui.tui_flow_sort_mode,
+            constants::DEFAULT_TUI_FLOW_SORT_MODE,
+        );
+        let tui_probe_countdown = cfg_layer_bool_flag(
+            args.tui_probe_countdown,
+            cfg_file_tui.tui_probe_countdown,
+            constants::DEFAULT_TUI_PROBE_COUNTDOWN,
+        );
+        let tui_auto_quit_stable_rounds = cfg_layer_opt(
+            args.tui_auto_quit_stable_rounds,
+            cfg_file_tui.tui_auto_quit_stable_rounds,
+        )
+        .filter(|&rounds| rounds > 0);
+        let tui_auto_quit_stable_threshold = cfg_layer(
+            args.tui_auto_quit_stable_threshold,
+            cfg_file_tui.tui_auto_quit_stable_threshold,
+            constants::DEFAULT_TUI_AUTO_QUIT_STABLE_THRESHOLD,
+        );
         let dns_resolve_method_config = cfg_layer(
             args.dns_resolve_method,
             cfg_file_dns.dns_resolve_method,
             constants::DEFAULT_DNS_RESOLVE_METHOD,
         );
         let tui_locale = cfg_layer_opt(args.tui_locale, cfg_file_tui.tui_locale);
+        let tui_notes_file = cfg_layer_opt(args.tui_notes_file, cfg_file_tui.tui_notes_file);
+        let tui_debug_fixture = args.tui_debug_fixture;
+        let tui_provider_classes = cfg_file_tui
+            .tui_provider_classes
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(asn, class)| Ok((parse_asn(&asn)?, class)))
+            .chain(args.tui_provider_classes.into_iter().map(Ok))
+            .collect::<anyhow::Result<HashMap<_, _>>>()?;
         let dns_lookup_as_info = cfg_layer_bool_flag(
             args.dns_lookup_as_info,
             cfg_file_dns.dns_lookup_as_info,
@@ -559,6 +1115,33 @@ impl TrippyConfig {
             cfg_file_report.report_cycles,
             constants::DEFAULT_REPORT_CYCLES,
         );
+        let webhook_url = cfg_layer_opt(args.webhook_url, cfg_file_report.webhook_url);
+        let webhook_trigger = cfg_layer(
+            args.webhook_trigger,
+            cfg_file_report.webhook_trigger,
+            constants::DEFAULT_WEBHOOK_TRIGGER,
+        );
+        let webhook_fields = if args.webhook_fields.is_empty() {
+            cfg_file_report
+                .webhook_fields
+                .unwrap_or_else(|| constants::DEFAULT_WEBHOOK_FIELDS.to_vec())
+        } else {
+            args.webhook_fields
+        };
+        let webhook_retries = cfg_layer(
+            args.webhook_retries,
+            cfg_file_report.webhook_retries,
+            constants::DEFAULT_WEBHOOK_RETRIES,
+        );
+        let sqlite_file = cfg_layer_opt(args.sqlite_file, cfg_file_report.sqlite_file);
+        let sqlite_retention =
+            cfg_layer_opt(args.sqlite_retention, cfg_file_report.sqlite_retention);
+        let prometheus_file = cfg_layer_opt(args.prometheus_file, cfg_file_report.prometheus_file);
+        let bell_trigger = cfg_layer(
+            args.bell_trigger,
+            cfg_file_report.bell_trigger,
+            constants::DEFAULT_BELL_TRIGGER,
+        );
         let geoip_mmdb_file = cfg_layer_opt(args.geoip_mmdb_file, cfg_file_tui.geoip_mmdb_file);
         let protocol = match (args.udp, args.tcp, args.icmp, protocol) {
             (false, false, false, ProtocolConfig::Udp) | (true, _, _, _) => Protocol::Udp,
@@ -623,33 +1206,73 @@ impl TrippyConfig {
             DnsResolveMethodConfig::Cloudflare => ResolveMethod::Cloudflare,
         };
         let max_rounds = match mode {
-            Mode::Stream | Mode::Tui => None,
+            Mode::Stream => None,
+            Mode::Tui => tui_max_rounds,
             Mode::Pretty
             | Mode::Markdown
             | Mode::Csv
             | Mode::Json
             | Mode::Dot
             | Mode::Flows
-            | Mode::Silent => Some(report_cycles),
+            | Mode::Tree
+            | Mode::ProbesJson
+            | Mode::ProbesCsv
+            | Mode::RoundsJson
+            | Mode::Silent
+            | Mode::DryRun => Some(report_cycles),
         };
         let tui_max_addrs = match tui_max_addrs {
             Some(n) if n > 0 => Some(n),
             _ => None,
         };
-        validate_privilege(privilege_mode, has_privileges, needs_privileges)?;
+        let tui_max_display_hops = match tui_max_display_hops {
+            Some(n) if n > 0 => Some(n),
+            _ => None,
+        };
+        let max_unresponsive_hops = match max_unresponsive_hops {
+            Some(n) if n > 0 => Some(n),
+            _ => None,
+        };
+        validate_privilege(mode, privilege_mode, has_privileges, needs_privileges)?;
         validate_logging(mode, verbose)?;
+        validate_tui_debug_fixture(mode, tui_debug_fixture.as_deref())?;
         validate_strategy(multipath_strategy, unprivileged)?;
         validate_protocol_strategy(protocol, multipath_strategy)?;
         validate_multi(mode, protocol, &args.targets, dns_resolve_all)?;
         validate_flows(mode, multipath_strategy)?;
         validate_ttl(first_ttl, max_ttl)?;
         validate_max_inflight(max_inflight)?;
+        validate_send_batch_size(send_batch_size)?;
+        validate_recv_batch_size(recv_batch_size)?;
+        validate_flow_label(flow_label)?;
+        validate_tui_ewma_weight(tui_ewma_weight)?;
+        validate_tui_loss_ewma_weight(tui_loss_ewma_weight)?;
+        validate_tui_tunnel_ttl_jump(tui_tunnel_ttl_jump)?;
         validate_read_timeout(read_timeout)?;
         validate_round_duration(min_round_duration, max_round_duration)?;
         validate_grace_duration(grace_duration)?;
+        validate_icmp_data_length(icmp_data_length, protocol, addr_family)?;
+        let packet_size = match icmp_data_length {
+            Some(icmp_data_length) => min_packet_size(addr_family) + icmp_data_length,
+            None => packet_size,
+        };
         validate_packet_size(addr_family, packet_size)?;
+        validate_stamped_payload(stamped_payload, protocol, addr_family, packet_size)?;
+        validate_payload_strategy(
+            payload_strategy,
+            stamped_payload,
+            protocol,
+            addr_family,
+            packet_size,
+        )?;
         validate_tui_refresh_rate(tui_refresh_rate)?;
+        validate_tui_rtt_heat_max(tui_rtt_heat_max)?;
         validate_report_cycles(report_cycles)?;
+        validate_webhook_url(webhook_url.as_deref())?;
+        validate_webhook_retries(webhook_retries)?;
+        validate_sqlite_file(sqlite_file.as_deref())?;
+        validate_tui_auto_quit_stable_rounds(tui_auto_quit_stable_rounds, max_samples)?;
+        validate_tui_first_hop_warning_rounds(tui_first_hop_warning_rounds, max_samples)?;
         validate_dns(dns_resolve_method, dns_lookup_as_info)?;
         validate_geoip(tui_geoip_mode, &geoip_mmdb_file)?;
         validate_tui_custom_columns(&tui_custom_columns)?;
@@ -674,15 +1297,34 @@ impl TrippyConfig {
             max_round_duration,
             grace_duration,
             max_inflight,
+            send_batch_size,
+            recv_batch_size,
             initial_sequence,
             multipath_strategy,
             read_timeout,
+            min_probe_interval,
+            send_jitter,
+            loss_timeout,
+            ignore_identifier,
+            discard_first_hop_warmup,
+            accept_late_responses,
+            adaptive_read_timeout,
+            max_unresponsive_hops,
+            curtail_unresponsive,
+            setup_retry_attempts,
+            setup_retry_backoff,
+            kernel_recv_timestamps,
+            stamped_payload,
+            payload_strategy,
             packet_size,
+            icmp_data_length,
             payload_pattern,
             tos,
+            flow_label,
             icmp_extension_parse_mode,
             source_addr,
             interface,
+            local_target_mode,
             port_direction,
             dns_timeout,
             dns_ttl,
@@ -693,19 +1335,62 @@ impl TrippyConfig {
             tui_preserve_screen,
             tui_refresh_rate,
             tui_privacy_max_ttl,
+            tui_first_hop_warning_rounds,
             tui_address_mode,
             tui_as_mode,
+            tui_as_colors,
+            tui_summary_footer,
+            tui_rtt_deltas,
+            tui_rtt_baseline,
+            tui_rtt_compact_threshold,
+            tui_rtt_heat_mode,
+            tui_rtt_heat_max,
             tui_custom_columns,
+            tui_column_groups,
+            tui_rtt_precision,
             tui_icmp_extension_mode,
             tui_geoip_mode,
+            tui_unresponsive_hop_mode,
+            tui_trailing_hop_mode,
+            tui_time_exceeded_rtt_mode,
+            tui_average_mode,
+            tui_ewma_weight,
+            tui_loss_ewma_weight,
+            tui_tunnel_ttl_jump,
+            tui_status_mode,
+            tui_addr_aggregation,
+            tui_target_ttl_mode,
+            tui_host_truncation,
+            tui_glyph_set,
             tui_max_addrs,
+            tui_max_display_hops,
+            tui_stats_window,
+            tui_stddev_mode,
+            tui_stddev_scope,
+            tui_path_change_rounds,
+            tui_path_stability_window,
+            tui_flow_sort_mode,
+            tui_probe_countdown,
+            tui_auto_quit_stable_rounds,
+            tui_auto_quit_stable_threshold,
             tui_locale,
+            tui_notes_file,
+            tui_debug_fixture,
+            tui_provider_classes,
             tui_theme,
             tui_bindings,
             mode,
             privilege_mode,
             dns_resolve_all,
             report_cycles,
+            webhook_url,
+            webhook_trigger,
+            webhook_fields,
+            webhook_retries,
+            sqlite_file,
+            sqlite_retention,
+            prometheus_file,
+            bell_trigger,
             geoip_mmdb_file,
             max_rounds,
             verbose,
@@ -717,6 +1402,7 @@ impl TrippyConfig {
 }
 
 impl Default for TrippyConfig {
+    #[allow(clippy::too_many_lines)]
     fn default() -> Self {
         Self {
             targets: vec![],
@@ -728,14 +1414,33 @@ impl Default for TrippyConfig {
             max_round_duration: defaults::DEFAULT_STRATEGY_MAX_ROUND_DURATION,
             grace_duration: defaults::DEFAULT_STRATEGY_GRACE_DURATION,
             max_inflight: defaults::DEFAULT_STRATEGY_MAX_INFLIGHT,
+            send_batch_size: defaults::DEFAULT_STRATEGY_SEND_BATCH_SIZE,
+            recv_batch_size: defaults::DEFAULT_STRATEGY_RECV_BATCH_SIZE,
             initial_sequence: defaults::DEFAULT_STRATEGY_INITIAL_SEQUENCE,
             tos: defaults::DEFAULT_STRATEGY_TOS,
+            flow_label: defaults::DEFAULT_STRATEGY_FLOW_LABEL,
             icmp_extension_parse_mode: defaults::DEFAULT_ICMP_EXTENSION_PARSE_MODE,
             read_timeout: defaults::DEFAULT_STRATEGY_READ_TIMEOUT,
+            min_probe_interval: None,
+            send_jitter: None,
+            loss_timeout: None,
+            ignore_identifier: false,
+            discard_first_hop_warmup: false,
+            accept_late_responses: false,
+            adaptive_read_timeout: false,
+            max_unresponsive_hops: None,
+            curtail_unresponsive: false,
+            setup_retry_attempts: defaults::DEFAULT_SETUP_RETRY_ATTEMPTS,
+            setup_retry_backoff: defaults::DEFAULT_SETUP_RETRY_BACKOFF,
+            kernel_recv_timestamps: false,
+            stamped_payload: false,
+            payload_strategy: defaults::DEFAULT_STRATEGY_PAYLOAD_STRATEGY,
+            icmp_data_length: None,
             packet_size: defaults::DEFAULT_STRATEGY_PACKET_SIZE,
             payload_pattern: defaults::DEFAULT_STRATEGY_PAYLOAD_PATTERN,
             source_addr: None,
             interface: None,
+            local_target_mode: constants::DEFAULT_LOCAL_TARGET_MODE,
             multipath_strategy: defaults::DEFAULT_STRATEGY_MULTIPATH,
             port_direction: PortDirection::None,
             dns_timeout: constants::DEFAULT_DNS_TIMEOUT,
@@ -747,18 +1452,59 @@ impl Default for TrippyConfig {
             tui_preserve_screen: constants::DEFAULT_TUI_PRESERVE_SCREEN,
             tui_refresh_rate: constants::DEFAULT_TUI_REFRESH_RATE,
             tui_privacy_max_ttl: constants::DEFAULT_TUI_PRIVACY_MAX_TTL,
+            tui_first_hop_warning_rounds: constants::DEFAULT_TUI_FIRST_HOP_WARNING_ROUNDS,
             tui_address_mode: constants::DEFAULT_TUI_ADDRESS_MODE,
             tui_as_mode: constants::DEFAULT_TUI_AS_MODE,
+            tui_as_colors: constants::DEFAULT_TUI_AS_COLORS,
+            tui_summary_footer: constants::DEFAULT_TUI_SUMMARY_FOOTER,
+            tui_rtt_deltas: constants::DEFAULT_TUI_RTT_DELTAS,
+            tui_rtt_baseline: constants::DEFAULT_TUI_RTT_BASELINE,
+            tui_rtt_compact_threshold: None,
+            tui_rtt_heat_mode: constants::DEFAULT_TUI_RTT_HEAT_MODE,
+            tui_rtt_heat_max: constants::DEFAULT_TUI_RTT_HEAT_MAX,
             tui_icmp_extension_mode: constants::DEFAULT_TUI_ICMP_EXTENSION_MODE,
             tui_geoip_mode: constants::DEFAULT_TUI_GEOIP_MODE,
+            tui_unresponsive_hop_mode: constants::DEFAULT_TUI_UNRESPONSIVE_HOP_MODE,
+            tui_trailing_hop_mode: constants::DEFAULT_TUI_TRAILING_HOP_MODE,
+            tui_time_exceeded_rtt_mode: constants::DEFAULT_TUI_TIME_EXCEEDED_RTT_MODE,
+            tui_average_mode: constants::DEFAULT_TUI_AVERAGE_MODE,
+            tui_ewma_weight: constants::DEFAULT_TUI_EWMA_WEIGHT,
+            tui_loss_ewma_weight: constants::DEFAULT_TUI_LOSS_EWMA_WEIGHT,
+            tui_tunnel_ttl_jump: constants::DEFAULT_TUI_TUNNEL_TTL_JUMP,
+            tui_status_mode: constants::DEFAULT_TUI_STATUS_MODE,
+            tui_addr_aggregation: constants::DEFAULT_TUI_ADDR_AGGREGATION,
+            tui_target_ttl_mode: constants::DEFAULT_TUI_TARGET_TTL_MODE,
+            tui_host_truncation: constants::DEFAULT_TUI_HOST_TRUNCATION,
+            tui_glyph_set: constants::DEFAULT_TUI_GLYPH_SET,
             tui_max_addrs: None,
+            tui_max_display_hops: None,
+            tui_stats_window: None,
+            tui_stddev_mode: constants::DEFAULT_TUI_STDDEV_MODE,
+            tui_stddev_scope: constants::DEFAULT_TUI_STDDEV_SCOPE,
+            tui_path_change_rounds: constants::DEFAULT_TUI_PATH_CHANGE_ROUNDS,
+            tui_path_stability_window: constants::DEFAULT_TUI_PATH_STABILITY_WINDOW,
+            tui_flow_sort_mode: constants::DEFAULT_TUI_FLOW_SORT_MODE,
+            tui_probe_countdown: constants::DEFAULT_TUI_PROBE_COUNTDOWN,
+            tui_auto_quit_stable_rounds: None,
+            tui_auto_quit_stable_threshold: constants::DEFAULT_TUI_AUTO_QUIT_STABLE_THRESHOLD,
             tui_locale: None,
+            tui_notes_file: None,
+            tui_debug_fixture: None,
+            tui_provider_classes: HashMap::new(),
             tui_theme: TuiTheme::default(),
             tui_bindings: TuiBindings::default(),
             mode: constants::DEFAULT_MODE,
             privilege_mode: defaults::DEFAULT_PRIVILEGE_MODE,
             dns_resolve_all: constants::DEFAULT_DNS_RESOLVE_ALL,
             report_cycles: constants::DEFAULT_REPORT_CYCLES,
+            webhook_url: None,
+            webhook_trigger: constants::DEFAULT_WEBHOOK_TRIGGER,
+            webhook_fields: constants::DEFAULT_WEBHOOK_FIELDS.to_vec(),
+            webhook_retries: constants::DEFAULT_WEBHOOK_RETRIES,
+            sqlite_file: None,
+            sqlite_retention: None,
+            prometheus_file: None,
+            bell_trigger: constants::DEFAULT_BELL_TRIGGER,
             geoip_mmdb_file: None,
             max_rounds: None,
             verbose: false,
@@ -766,6 +1512,8 @@ impl Default for TrippyConfig {
             log_filter: String::from(constants::DEFAULT_LOG_FILTER),
             log_span_events: constants::DEFAULT_LOG_SPAN_EVENTS,
             tui_custom_columns: TuiColumns::default(),
+            tui_column_groups: TuiColumnGroups::default(),
+            tui_rtt_precision: TuiRttPrecision::default(),
         }
     }
 }
@@ -822,12 +1570,18 @@ fn validate_deprecated(cfg_file_tui: &ConfigTui) -> anyhow::Result<()> {
 }
 
 /// Validate privileges.
+///
+/// No privileges are required in dry-run mode, as no probes are sent.
 fn validate_privilege(
+    mode: Mode,
     privilege_mode: PrivilegeMode,
     has_privileges: bool,
     needs_privileges: bool,
 ) -> anyhow::Result<()> {
     const PRIVILEGE_URL: &str = "https://github.com/fujiapple852/trippy#privileges";
+    if mode == Mode::DryRun {
+        return Ok(());
+    }
     match (privilege_mode, has_privileges, needs_privileges) {
         (PrivilegeMode::Privileged, true, _) | (PrivilegeMode::Unprivileged, _, false) => Ok(()),
         (PrivilegeMode::Privileged, false, true) => Err(anyhow!(format!(
@@ -849,6 +1603,15 @@ fn validate_privilege(
     }
 }
 
+/// Parse an autonomous system (AS) number, with or without an `AS` prefix.
+fn parse_asn(value: &str) -> anyhow::Result<u32> {
+    value
+        .trim()
+        .trim_start_matches("AS")
+        .parse()
+        .map_err(|_| anyhow!("invalid AS number: {}", value))
+}
+
 /// Validate the TUI custom columns.
 fn validate_tui_custom_columns(tui_custom_columns: &TuiColumns) -> anyhow::Result<()> {
     let duplicates = tui_custom_columns.find_duplicates();
@@ -873,6 +1636,15 @@ fn validate_logging(mode: Mode, verbose: bool) -> anyhow::Result<()> {
     }
 }
 
+/// Validate the debug fixture against the mode.
+fn validate_tui_debug_fixture(mode: Mode, tui_debug_fixture: Option<&str>) -> anyhow::Result<()> {
+    if !matches!(mode, Mode::Tui) && tui_debug_fixture.is_some() {
+        Err(anyhow!("tui-debug-fixture may only be used in tui mode"))
+    } else {
+        Ok(())
+    }
+}
+
 /// Validate the multipath strategy against the privilege mode.
 fn validate_strategy(strategy: MultipathStrategy, unprivileged: bool) -> anyhow::Result<()> {
     match (strategy, unprivileged) {
@@ -930,11 +1702,11 @@ fn validate_multi(
     }
 }
 
-/// Validate that flows and dot mode are only used with paris or dublin
+/// Validate that flows, dot and tree mode are only used with paris or dublin
 /// multipath strategy.
 fn validate_flows(mode: Mode, strategy: MultipathStrategy) -> anyhow::Result<()> {
     match (mode, strategy) {
-        (Mode::Flows | Mode::Dot, MultipathStrategy::Classic) => Err(anyhow!(
+        (Mode::Flows | Mode::Dot | Mode::Tree, MultipathStrategy::Classic) => Err(anyhow!(
             "this mode requires the paris or dublin multipath strategy"
         )),
         _ => Ok(()),
@@ -972,6 +1744,79 @@ fn validate_max_inflight(max_inflight: u8) -> anyhow::Result<()> {
     }
 }
 
+/// Validate `flow_label`.
+fn validate_flow_label(flow_label: u32) -> anyhow::Result<()> {
+    if flow_label > FlowLabel::MAX.0 {
+        Err(anyhow!(
+            "flow-label ({}) must be between 0 and {} inclusive",
+            flow_label,
+            FlowLabel::MAX.0
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `send_batch_size`.
+fn validate_send_batch_size(send_batch_size: u8) -> anyhow::Result<()> {
+    if send_batch_size == 0 {
+        Err(anyhow!(
+            "send-batch-size ({}) must be greater than zero",
+            send_batch_size
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `recv_batch_size`.
+fn validate_recv_batch_size(recv_batch_size: u8) -> anyhow::Result<()> {
+    if recv_batch_size == 0 {
+        Err(anyhow!(
+            "recv-batch-size ({}) must be greater than zero",
+            recv_batch_size
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `tui_loss_ewma_weight`.
+fn validate_tui_loss_ewma_weight(tui_loss_ewma_weight: u8) -> anyhow::Result<()> {
+    if (1..=100).contains(&tui_loss_ewma_weight) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "tui-loss-ewma-weight ({}) must be between 1 and 100 inclusive",
+            tui_loss_ewma_weight
+        ))
+    }
+}
+
+/// Validate `tui_tunnel_ttl_jump`.
+fn validate_tui_tunnel_ttl_jump(tui_tunnel_ttl_jump: u8) -> anyhow::Result<()> {
+    if tui_tunnel_ttl_jump > 0 {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "tui-tunnel-ttl-jump ({}) must be greater than zero",
+            tui_tunnel_ttl_jump
+        ))
+    }
+}
+
+/// Validate `tui_ewma_weight`.
+fn validate_tui_ewma_weight(tui_ewma_weight: u8) -> anyhow::Result<()> {
+    if (1..=100).contains(&tui_ewma_weight) {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "tui-ewma-weight ({}) must be between 1 and 100 inclusive",
+            tui_ewma_weight
+        ))
+    }
+}
+
 /// Validate `read_timeout`.
 fn validate_read_timeout(read_timeout: Duration) -> anyhow::Result<()> {
     if read_timeout < constants::MIN_READ_TIMEOUT_MS
@@ -1020,14 +1865,48 @@ fn validate_grace_duration(grace_duration: Duration) -> anyhow::Result<()> {
     }
 }
 
-/// Validate `packet_size`.
-fn validate_packet_size(address_family: IpAddrFamily, packet_size: u16) -> anyhow::Result<()> {
-    let min_size = match address_family {
+/// The minimum packet size for an address family.
+///
+/// The address family of the target is not known until it has been resolved
+/// and so the larger IPv6 minimum is used unless the family is IPv4 only.
+const fn min_packet_size(address_family: IpAddrFamily) -> u16 {
+    match address_family {
         IpAddrFamily::Ipv4Only => constants::MIN_PACKET_SIZE_IPV4,
         IpAddrFamily::Ipv6Only | IpAddrFamily::Ipv6thenIpv4 | IpAddrFamily::Ipv4thenIpv6 => {
             constants::MIN_PACKET_SIZE_IPV6
         }
+    }
+}
+
+/// Validate `icmp_data_length`.
+fn validate_icmp_data_length(
+    icmp_data_length: Option<u16>,
+    protocol: Protocol,
+    address_family: IpAddrFamily,
+) -> anyhow::Result<()> {
+    let Some(icmp_data_length) = icmp_data_length else {
+        return Ok(());
     };
+    if protocol != Protocol::Icmp {
+        return Err(anyhow!("icmp-data-length is only supported for icmp"));
+    }
+    let max_length = constants::MAX_PACKET_SIZE - min_packet_size(address_family);
+    if icmp_data_length > max_length {
+        Err(anyhow!(
+            "icmp-data-length ({}) must be at most {} for {} (the maximum packet size of {} less the IP and ICMP headers)",
+            icmp_data_length,
+            max_length,
+            address_family,
+            constants::MAX_PACKET_SIZE,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `packet_size`.
+fn validate_packet_size(address_family: IpAddrFamily, packet_size: u16) -> anyhow::Result<()> {
+    let min_size = min_packet_size(address_family);
     if (min_size..=constants::MAX_PACKET_SIZE).contains(&packet_size) {
         Ok(())
     } else {
@@ -1041,6 +1920,67 @@ fn validate_packet_size(address_family: IpAddrFamily, packet_size: u16) -> anyho
     }
 }
 
+/// Validate `stamped_payload`.
+fn validate_stamped_payload(
+    stamped_payload: bool,
+    protocol: Protocol,
+    address_family: IpAddrFamily,
+    packet_size: u16,
+) -> anyhow::Result<()> {
+    if !stamped_payload {
+        return Ok(());
+    }
+    if protocol != Protocol::Icmp {
+        return Err(anyhow!("stamped-payload is only supported for icmp"));
+    }
+    let min_size = min_packet_size(address_family) + constants::STAMPED_PAYLOAD_SIZE;
+    if packet_size < min_size {
+        Err(anyhow!(
+            "packet-size ({}) must be at least {} for stamped-payload for {}",
+            packet_size,
+            min_size,
+            address_family,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `payload_strategy`.
+fn validate_payload_strategy(
+    payload_strategy: PayloadStrategy,
+    stamped_payload: bool,
+    protocol: Protocol,
+    address_family: IpAddrFamily,
+    packet_size: u16,
+) -> anyhow::Result<()> {
+    if payload_strategy == PayloadStrategy::Pattern {
+        return Ok(());
+    }
+    if protocol != Protocol::Icmp {
+        return Err(anyhow!(
+            "constant-checksum payload-strategy is only supported for icmp"
+        ));
+    }
+    let stamp_size = if stamped_payload {
+        constants::STAMPED_PAYLOAD_SIZE
+    } else {
+        0
+    };
+    let min_size =
+        min_packet_size(address_family) + stamp_size + constants::CHECKSUM_WORD_PAYLOAD_SIZE;
+    if packet_size < min_size {
+        Err(anyhow!(
+            "packet-size ({}) must be at least {} for constant-checksum payload-strategy for {}",
+            packet_size,
+            min_size,
+            address_family,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 /// Validate `source_port`.
 fn validate_source_port(source_port: u16) -> anyhow::Result<()> {
     if source_port < 1024 {
@@ -1066,12 +2006,103 @@ fn validate_tui_refresh_rate(tui_refresh_rate: Duration) -> anyhow::Result<()> {
     }
 }
 
-/// Validate `report_cycles`.
-fn validate_report_cycles(report_cycles: usize) -> anyhow::Result<()> {
-    if report_cycles == 0 {
+/// Validate `tui_rtt_heat_max`.
+fn validate_tui_rtt_heat_max(tui_rtt_heat_max: Duration) -> anyhow::Result<()> {
+    if tui_rtt_heat_max.is_zero() {
         Err(anyhow!(
-            "report-cycles ({}) must be greater than zero",
-            report_cycles
+            "tui-rtt-heat-max ({:?}) must be greater than zero",
+            tui_rtt_heat_max
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `report_cycles`.
+fn validate_report_cycles(report_cycles: usize) -> anyhow::Result<()> {
+    if report_cycles == 0 {
+        Err(anyhow!(
+            "report-cycles ({}) must be greater than zero",
+            report_cycles
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `webhook_url`.
+///
+/// Posting to a webhook requires the `webhook` feature.
+fn validate_webhook_url(webhook_url: Option<&str>) -> anyhow::Result<()> {
+    match webhook_url {
+        Some(_) if cfg!(not(feature = "webhook")) => Err(anyhow!(
+            "webhook-url requires Trippy to be built with the `webhook` feature"
+        )),
+        Some(url) if !url.starts_with("http://") && !url.starts_with("https://") => Err(anyhow!(
+            "webhook-url ({}) must be an http or https url",
+            url
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Validate `webhook_retries`.
+fn validate_webhook_retries(webhook_retries: u8) -> anyhow::Result<()> {
+    if webhook_retries > constants::MAX_WEBHOOK_RETRIES {
+        Err(anyhow!(
+            "webhook-retries ({}) must be at most {}",
+            webhook_retries,
+            constants::MAX_WEBHOOK_RETRIES
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `sqlite_file`.
+///
+/// Recording to a `SQLite` database requires the `sqlite` feature.
+fn validate_sqlite_file(sqlite_file: Option<&str>) -> anyhow::Result<()> {
+    if sqlite_file.is_some() && cfg!(not(feature = "sqlite")) {
+        Err(anyhow!(
+            "sqlite-file requires Trippy to be built with the `sqlite` feature"
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate `tui_auto_quit_stable_rounds`.
+///
+/// The stability of a hop is computed from the retained samples and so the
+/// number of rounds cannot exceed `max_samples`.
+fn validate_tui_auto_quit_stable_rounds(
+    tui_auto_quit_stable_rounds: Option<usize>,
+    max_samples: usize,
+) -> anyhow::Result<()> {
+    match tui_auto_quit_stable_rounds {
+        Some(rounds) if rounds > max_samples => Err(anyhow!(
+            "tui-auto-quit-stable-rounds ({}) must not exceed max-samples ({})",
+            rounds,
+            max_samples
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Validate `tui_first_hop_warning_rounds`.
+///
+/// The warning is determined from the retained samples of the first hop and so could never be
+/// shown if the number of rounds exceeded `max_samples`.
+fn validate_tui_first_hop_warning_rounds(
+    tui_first_hop_warning_rounds: usize,
+    max_samples: usize,
+) -> anyhow::Result<()> {
+    if tui_first_hop_warning_rounds > max_samples {
+        Err(anyhow!(
+            "tui-first-hop-warning-rounds ({}) must not exceed max-samples ({})",
+            tui_first_hop_warning_rounds,
+            max_samples
         ))
     } else {
         Ok(())
@@ -1181,11 +2212,17 @@ mod tests {
     #[test_case("trip example.com --mode json", Ok(cfg().mode(Mode::Json).max_rounds(Some(10)).build()); "json mode")]
     #[test_case("trip example.com --mode dot --udp -R paris", Ok(cfg().mode(Mode::Dot).max_rounds(Some(10)).multipath_strategy(MultipathStrategy::Paris).protocol(Protocol::Udp).port_direction(PortDirection::FixedSrc(Port(1024))).build()); "dot mode")]
     #[test_case("trip example.com --mode flows --udp -R paris", Ok(cfg().mode(Mode::Flows).max_rounds(Some(10)).multipath_strategy(MultipathStrategy::Paris).protocol(Protocol::Udp).port_direction(PortDirection::FixedSrc(Port(1024))).build()); "flows mode")]
+    #[test_case("trip example.com --mode tree --udp -R paris", Ok(cfg().mode(Mode::Tree).max_rounds(Some(10)).multipath_strategy(MultipathStrategy::Paris).protocol(Protocol::Udp).port_direction(PortDirection::FixedSrc(Port(1024))).build()); "tree mode")]
+    #[test_case("trip example.com --mode probes-json", Ok(cfg().mode(Mode::ProbesJson).max_rounds(Some(10)).build()); "probes json mode")]
+    #[test_case("trip example.com --mode probes-csv", Ok(cfg().mode(Mode::ProbesCsv).max_rounds(Some(10)).build()); "probes csv mode")]
+    #[test_case("trip example.com --mode rounds-json", Ok(cfg().mode(Mode::RoundsJson).max_rounds(Some(10)).build()); "rounds json mode")]
     #[test_case("trip example.com --mode silent", Ok(cfg().mode(Mode::Silent).max_rounds(Some(10)).build()); "silent mode")]
+    #[test_case("trip example.com --mode dry-run", Ok(cfg().mode(Mode::DryRun).max_rounds(Some(10)).build()); "dry run mode")]
     #[test_case("trip example.com -m tui", Ok(cfg().mode(Mode::Tui).build()); "tui mode short")]
-    #[test_case("trip example.com --mode foo", Err(anyhow!(format!("error: invalid value 'foo' for '--mode <MODE>' [possible values: tui, stream, pretty, markdown, csv, json, dot, flows, silent] For more information, try '--help'."))); "invalid mode")]
+    #[test_case("trip example.com --mode foo", Err(anyhow!(format!("error: invalid value 'foo' for '--mode <MODE>' [possible values: tui, stream, pretty, markdown, csv, json, dot, flows, tree, probes-json, probes-csv, rounds-json, silent, dry-run] For more information, try '--help'."))); "invalid mode")]
     #[test_case("trip example.com --mode dot", Err(anyhow!(format!("this mode requires the paris or dublin multipath strategy"))); "invalid dot mode")]
     #[test_case("trip example.com --mode flows", Err(anyhow!(format!("this mode requires the paris or dublin multipath strategy"))); "invalid flows mode")]
+    #[test_case("trip example.com --mode tree", Err(anyhow!(format!("this mode requires the paris or dublin multipath strategy"))); "invalid tree mode")]
     fn test_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
         compare(parse_config(cmd), expected);
     }
@@ -1197,6 +2234,8 @@ mod tests {
     #[test_case("trip example.com foo.com bar.com -U 20", Ok(cfg_multi().max_inflight(20).build()); "multiple targets before args")]
     #[test_case("trip -U 20 example.com foo.com bar.com", Ok(cfg_multi().max_inflight(20).build()); "multiple targets after args")]
     #[test_case("trip example.com -U 20 foo.com -Q 255 bar.com", Ok(cfg_multi().max_inflight(20).tos(255).build()); "multiple targets between args")]
+    #[test_case("trip --prompt", Ok(TrippyConfigBuilder::new(vec![]).build()); "no target with prompt")]
+    #[test_case("trip --prompt example.com", Ok(cfg().build()); "single target with prompt")]
     fn test_target(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
         compare(parse_config(cmd), expected);
     }
@@ -1320,6 +2359,22 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().send_batch_size(1).build()); "default send batch size")]
+    #[test_case("trip example.com --send-batch-size 8", Ok(cfg().send_batch_size(8).build()); "custom send batch size")]
+    #[test_case("trip example.com --send-batch-size foo", Err(anyhow!("error: invalid value 'foo' for '--send-batch-size <SEND_BATCH_SIZE>': invalid digit found in string For more information, try '--help'.")); "invalid format send batch size")]
+    #[test_case("trip example.com --send-batch-size 0", Err(anyhow!("send-batch-size (0) must be greater than zero")); "invalid low send batch size")]
+    fn test_send_batch_size(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().recv_batch_size(1).build()); "default recv batch size")]
+    #[test_case("trip example.com --recv-batch-size 16", Ok(cfg().recv_batch_size(16).build()); "custom recv batch size")]
+    #[test_case("trip example.com --recv-batch-size foo", Err(anyhow!("error: invalid value 'foo' for '--recv-batch-size <RECV_BATCH_SIZE>': invalid digit found in string For more information, try '--help'.")); "invalid format recv batch size")]
+    #[test_case("trip example.com --recv-batch-size 0", Err(anyhow!("recv-batch-size (0) must be greater than zero")); "invalid low recv batch size")]
+    fn test_recv_batch_size(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().initial_sequence(33434).build()); "default initial sequence")]
     #[test_case("trip example.com --initial-sequence 5000", Ok(cfg().initial_sequence(5000).build()); "custom initial sequence")]
     #[test_case("trip example.com --initial-sequence foo", Err(anyhow!("error: invalid value 'foo' for '--initial-sequence <INITIAL_SEQUENCE>': invalid digit found in string For more information, try '--help'. ")); "invalid format initial sequence")]
@@ -1337,6 +2392,15 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().flow_label(0).build()); "default flow label")]
+    #[test_case("trip example.com --flow-label 74565", Ok(cfg().flow_label(0x12345).build()); "custom flow label")]
+    #[test_case("trip example.com --flow-label 1048575", Ok(cfg().flow_label(0xf_ffff).build()); "max flow label")]
+    #[test_case("trip example.com --flow-label 1048576", Err(anyhow!("flow-label (1048576) must be between 0 and 1048575 inclusive")); "invalid high flow label")]
+    #[test_case("trip example.com --flow-label foo", Err(anyhow!("error: invalid value 'foo' for '--flow-label <FLOW_LABEL>': invalid digit found in string For more information, try '--help'.")); "invalid format flow label")]
+    fn test_flow_label(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().icmp_extension_parse_mode(IcmpExtensionParseMode::Disabled).build()); "default icmp extensions")]
     #[test_case("trip example.com --icmp-extensions", Ok(cfg().icmp_extension_parse_mode(IcmpExtensionParseMode::Enabled).build()); "enabled icmp extensions")]
     #[test_case("trip example.com -e", Ok(cfg().icmp_extension_parse_mode(IcmpExtensionParseMode::Enabled).build()); "enabled icmp extensions short")]
@@ -1353,6 +2417,105 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().min_probe_interval(None).build()); "default min probe interval")]
+    #[test_case("trip example.com --min-probe-interval 20ms", Ok(cfg().min_probe_interval(Some(Duration::from_millis(20))).build()); "custom min probe interval")]
+    #[test_case("trip example.com --min-probe-interval 20", Err(anyhow!("error: invalid value '20' for '--min-probe-interval <MIN_PROBE_INTERVAL>': time unit needed, for example 20sec or 20ms For more information, try '--help'.")); "invalid custom min probe interval")]
+    fn test_min_probe_interval(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().send_jitter(None).build()); "default send jitter")]
+    #[test_case("trip example.com --send-jitter 5ms", Ok(cfg().send_jitter(Some(Duration::from_millis(5))).build()); "custom send jitter")]
+    #[test_case("trip example.com --send-jitter foo", Err(anyhow!("error: invalid value 'foo' for '--send-jitter <SEND_JITTER>': expected number at 0 For more information, try '--help'.")); "invalid format send jitter")]
+    fn test_send_jitter(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().loss_timeout(None).build()); "default loss timeout")]
+    #[test_case("trip example.com --loss-timeout 500ms", Ok(cfg().loss_timeout(Some(Duration::from_millis(500))).build()); "custom loss timeout")]
+    fn test_loss_timeout(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().ignore_identifier(false).build()); "default ignore identifier")]
+    #[test_case("trip example.com --ignore-identifier", Ok(cfg().ignore_identifier(true).build()); "enabled ignore identifier")]
+    fn test_ignore_identifier(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().discard_first_hop_warmup(false).build()); "default discard first hop warmup")]
+    #[test_case("trip example.com --discard-first-hop-warmup", Ok(cfg().discard_first_hop_warmup(true).build()); "enabled discard first hop warmup")]
+    fn test_discard_first_hop_warmup(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().accept_late_responses(false).build()); "default accept late responses")]
+    #[test_case("trip example.com --accept-late-responses", Ok(cfg().accept_late_responses(true).build()); "enabled accept late responses")]
+    fn test_accept_late_responses(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().adaptive_read_timeout(false).build()); "default adaptive read timeout")]
+    #[test_case("trip example.com --adaptive-read-timeout", Ok(cfg().adaptive_read_timeout(true).build()); "enabled adaptive read timeout")]
+    fn test_adaptive_read_timeout(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().max_unresponsive_hops(None).build()); "default max unresponsive hops")]
+    #[test_case("trip example.com --max-unresponsive-hops 3", Ok(cfg().max_unresponsive_hops(Some(3)).build()); "custom max unresponsive hops")]
+    #[test_case("trip example.com --max-unresponsive-hops 0", Ok(cfg().max_unresponsive_hops(None).build()); "zero max unresponsive hops")]
+    fn test_max_unresponsive_hops(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().curtail_unresponsive(false).build()); "default curtail unresponsive")]
+    #[test_case("trip example.com --curtail-unresponsive", Ok(cfg().curtail_unresponsive(true).build()); "enabled curtail unresponsive")]
+    fn test_curtail_unresponsive(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().setup_retry_attempts(0).build()); "default setup retry attempts")]
+    #[test_case("trip example.com --setup-retry-attempts 10", Ok(cfg().setup_retry_attempts(10).build()); "custom setup retry attempts")]
+    #[test_case("trip example.com --setup-retry-attempts foo", Err(anyhow!("error: invalid value 'foo' for '--setup-retry-attempts <SETUP_RETRY_ATTEMPTS>': invalid digit found in string For more information, try '--help'.")); "invalid setup retry attempts")]
+    fn test_setup_retry_attempts(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().setup_retry_backoff(Duration::from_secs(1)).build()); "default setup retry backoff")]
+    #[test_case("trip example.com --setup-retry-backoff 5s", Ok(cfg().setup_retry_backoff(Duration::from_secs(5)).build()); "custom setup retry backoff")]
+    fn test_setup_retry_backoff(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().kernel_recv_timestamps(false).build()); "default kernel timestamps")]
+    #[test_case("trip example.com --kernel-recv-timestamps", Ok(cfg().kernel_recv_timestamps(true).build()); "enabled kernel timestamps")]
+    fn test_kernel_recv_timestamps(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().stamped_payload(false).build()); "default stamped payload")]
+    #[test_case("trip example.com --stamped-payload", Ok(cfg().stamped_payload(true).build()); "enabled stamped payload")]
+    #[test_case("trip example.com --stamped-payload --packet-size 64", Ok(cfg().stamped_payload(true).packet_size(64).build()); "stamped payload with minimum packet size")]
+    #[test_case("trip example.com --stamped-payload --packet-size 44 -F ipv4", Ok(cfg().stamped_payload(true).packet_size(44).addr_family(IpAddrFamily::Ipv4Only).build()); "stamped payload with minimum packet size for ipv4")]
+    #[test_case("trip example.com --stamped-payload --packet-size 63", Err(anyhow!("packet-size (63) must be at least 64 for stamped-payload for Ipv4thenIpv6")); "stamped payload with packet size too small")]
+    #[test_case("trip example.com --stamped-payload --packet-size 43 -F ipv4", Err(anyhow!("packet-size (43) must be at least 44 for stamped-payload for Ipv4Only")); "stamped payload with packet size too small for ipv4")]
+    #[test_case("trip example.com --stamped-payload --udp", Err(anyhow!("stamped-payload is only supported for icmp")); "stamped payload with udp")]
+    fn test_stamped_payload(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().payload_strategy(PayloadStrategy::Pattern).build()); "default payload strategy")]
+    #[test_case("trip example.com --payload-strategy pattern", Ok(cfg().payload_strategy(PayloadStrategy::Pattern).build()); "pattern payload strategy")]
+    #[test_case("trip example.com --payload-strategy constant-checksum", Ok(cfg().payload_strategy(PayloadStrategy::ConstantChecksum).build()); "constant checksum payload strategy")]
+    #[test_case("trip example.com --payload-strategy constant-checksum --packet-size 50", Ok(cfg().payload_strategy(PayloadStrategy::ConstantChecksum).packet_size(50).build()); "constant checksum payload strategy with minimum packet size")]
+    #[test_case("trip example.com --payload-strategy constant-checksum --packet-size 49", Err(anyhow!("packet-size (49) must be at least 50 for constant-checksum payload-strategy for Ipv4thenIpv6")); "constant checksum payload strategy with packet size too small")]
+    #[test_case("trip example.com --payload-strategy constant-checksum --stamped-payload --packet-size 65", Err(anyhow!("packet-size (65) must be at least 66 for constant-checksum payload-strategy for Ipv4thenIpv6")); "constant checksum payload strategy with stamped payload and packet size too small")]
+    #[test_case("trip example.com --payload-strategy constant-checksum --udp", Err(anyhow!("constant-checksum payload-strategy is only supported for icmp")); "constant checksum payload strategy with udp")]
+    #[test_case("trip example.com --payload-strategy foo", Err(anyhow!("error: invalid value 'foo' for '--payload-strategy <PAYLOAD_STRATEGY>' [possible values: pattern, constant-checksum] For more information, try '--help'.")); "invalid payload strategy")]
+    fn test_payload_strategy(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().packet_size(84).build()); "default packet size")]
     #[test_case("trip example.com --packet-size 120", Ok(cfg().packet_size(120).build()); "custom packet size")]
     #[test_case("trip example.com --packet-size foo", Err(anyhow!("error: invalid value 'foo' for '--packet-size <PACKET_SIZE>': invalid digit found in string For more information, try '--help'.")); "invalid format packet size")]
@@ -1367,6 +2530,32 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().icmp_data_length(None).packet_size(84).build()); "default icmp data length")]
+    #[test_case("trip example.com --icmp-data-length 56", Ok(cfg().icmp_data_length(Some(56)).packet_size(104).build()); "custom icmp data length")]
+    #[test_case("trip example.com --icmp-data-length 56 -F ipv4", Ok(cfg().icmp_data_length(Some(56)).packet_size(84).addr_family(IpAddrFamily::Ipv4Only).build()); "custom icmp data length for ipv4")]
+    #[test_case("trip example.com --icmp-data-length 0", Ok(cfg().icmp_data_length(Some(0)).packet_size(48).build()); "zero icmp data length")]
+    #[test_case("trip example.com --icmp-data-length 976", Ok(cfg().icmp_data_length(Some(976)).packet_size(1024).build()); "maximum icmp data length")]
+    #[test_case("trip example.com --icmp-data-length 977", Err(anyhow!("icmp-data-length (977) must be at most 976 for Ipv4thenIpv6 (the maximum packet size of 1024 less the IP and ICMP headers)")); "invalid high icmp data length")]
+    #[test_case("trip example.com --icmp-data-length 997 -F ipv4", Err(anyhow!("icmp-data-length (997) must be at most 996 for Ipv4Only (the maximum packet size of 1024 less the IP and ICMP headers)")); "invalid high icmp data length for ipv4")]
+    #[test_case("trip example.com --icmp-data-length 56 --udp", Err(anyhow!("icmp-data-length is only supported for icmp")); "invalid icmp data length for udp")]
+    #[test_case("trip example.com --icmp-data-length 8 --stamped-payload", Err(anyhow!("packet-size (56) must be at least 64 for stamped-payload for Ipv4thenIpv6")); "invalid icmp data length for stamped payload")]
+    #[test_case("trip example.com --icmp-data-length 56 --packet-size 84", Err(anyhow!("error: the argument '--icmp-data-length <ICMP_DATA_LENGTH>' cannot be used with '--packet-size <PACKET_SIZE>' Usage: trip --icmp-data-length <ICMP_DATA_LENGTH> <TARGETS>... For more information, try '--help'.")); "icmp data length conflicts with packet size")]
+    fn test_icmp_data_length(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test]
+    fn test_packet_size_for() {
+        let ipv4 = IpAddr::from([10, 0, 0, 1]);
+        let ipv6 = IpAddr::from([0xfe80, 0, 0, 0, 0, 0, 0, 1]);
+        let config = cfg().packet_size(100).build();
+        assert_eq!(100, config.packet_size_for(ipv4));
+        assert_eq!(100, config.packet_size_for(ipv6));
+        let config = cfg().icmp_data_length(Some(56)).packet_size(104).build();
+        assert_eq!(84, config.packet_size_for(ipv4));
+        assert_eq!(104, config.packet_size_for(ipv6));
+    }
+
     #[test_case("trip example.com", Ok(cfg().payload_pattern(0).build()); "default payload pattern size")]
     #[test_case("trip example.com --payload-pattern 255", Ok(cfg().payload_pattern(0xFF).build()); "custom payload pattern")]
     #[test_case("trip example.com --payload-pattern foo", Err(anyhow!("error: invalid value 'foo' for '--payload-pattern <PAYLOAD_PATTERN>': invalid digit found in string For more information, try '--help'.")); "invalid format payload pattern")]
@@ -1469,12 +2658,43 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().tui_first_hop_warning_rounds(5).build()); "default tui first hop warning rounds")]
+    #[test_case("trip example.com --tui-first-hop-warning-rounds 10", Ok(cfg().tui_first_hop_warning_rounds(10).build()); "custom tui first hop warning rounds")]
+    #[test_case("trip example.com --tui-first-hop-warning-rounds 0", Ok(cfg().tui_first_hop_warning_rounds(0).build()); "disabled tui first hop warning rounds")]
+    #[test_case("trip example.com --tui-first-hop-warning-rounds 100 --max-samples 100", Ok(cfg().tui_first_hop_warning_rounds(100).max_samples(100).build()); "maximum tui first hop warning rounds")]
+    #[test_case("trip example.com --tui-first-hop-warning-rounds 101 --max-samples 100", Err(anyhow!("tui-first-hop-warning-rounds (101) must not exceed max-samples (100)")); "tui first hop warning rounds exceed max samples")]
+    #[test_case("trip example.com --tui-first-hop-warning-rounds foo", Err(anyhow!("error: invalid value 'foo' for '--tui-first-hop-warning-rounds <TUI_FIRST_HOP_WARNING_ROUNDS>': invalid digit found in string For more information, try '--help'.")); "invalid tui first hop warning rounds")]
+    fn test_tui_first_hop_warning_rounds(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().tui_locale(None).build()); "default tui locale")]
     #[test_case("trip example.com --tui-locale fr", Ok(cfg().tui_locale(Some(String::from("fr"))).build()); "custom tui locale")]
     fn test_tui_locale(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().tui_notes_file(None).build()); "default tui notes file")]
+    #[test_case("trip example.com --tui-notes-file notes.json", Ok(cfg().tui_notes_file(Some(String::from("notes.json"))).build()); "custom tui notes file")]
+    fn test_tui_notes_file(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_debug_fixture(None).build()); "default tui debug fixture")]
+    #[test_case("trip example.com --tui-debug-fixture hops.toml", Ok(cfg().tui_debug_fixture(Some(String::from("hops.toml"))).build()); "custom tui debug fixture")]
+    #[test_case("trip example.com --tui-debug-fixture hops.toml -m pretty", Err(anyhow!("tui-debug-fixture may only be used in tui mode")); "invalid tui debug fixture mode")]
+    fn test_tui_debug_fixture(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_provider_classes(HashMap::new()).build()); "default tui provider classes")]
+    #[test_case("trip example.com --tui-provider-classes 13335=dns,AS64512=lab", Ok(cfg().tui_provider_classes(HashMap::from([(13335, String::from("dns")), (64512, String::from("lab"))])).build()); "custom tui provider classes")]
+    #[test_case("trip example.com --tui-provider-classes 13335", Err(anyhow!("error: invalid value '13335' for '--tui-provider-classes <TUI_PROVIDER_CLASSES>': invalid provider class value: expected format `asn=class` For more information, try '--help'.")); "invalid tui provider classes format")]
+    #[test_case("trip example.com --tui-provider-classes foo=cdn", Err(anyhow!("error: invalid value 'foo=cdn' for '--tui-provider-classes <TUI_PROVIDER_CLASSES>': invalid AS number: foo For more information, try '--help'.")); "invalid tui provider classes asn")]
+    fn test_tui_provider_classes(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().tui_address_mode(AddressMode::Host).build()); "default tui address mode")]
     #[test_case("trip example.com --tui-address-mode ip", Ok(cfg().tui_address_mode(AddressMode::Ip).build()); "ip tui address mode")]
     #[test_case("trip example.com --tui-address-mode host", Ok(cfg().tui_address_mode(AddressMode::Host).build()); "host tui address mode")]
@@ -1497,6 +2717,146 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().tui_as_colors(false).build()); "default tui as colors")]
+    #[test_case("trip example.com --tui-as-colors", Ok(cfg().tui_as_colors(true).build()); "enable tui as colors")]
+    fn test_tui_as_colors(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_summary_footer(false).build()); "default tui summary footer")]
+    #[test_case("trip example.com --tui-summary-footer", Ok(cfg().tui_summary_footer(true).build()); "enable tui summary footer")]
+    fn test_tui_summary_footer(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_rtt_deltas(false).build()); "default tui rtt deltas")]
+    #[test_case("trip example.com --tui-rtt-deltas", Ok(cfg().tui_rtt_deltas(true).build()); "enable tui rtt deltas")]
+    fn test_tui_rtt_deltas(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_rtt_baseline(false).build()); "default tui rtt baseline")]
+    #[test_case("trip example.com --tui-rtt-baseline", Ok(cfg().tui_rtt_baseline(true).build()); "enable tui rtt baseline")]
+    fn test_tui_rtt_baseline(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_tunnel_ttl_jump(3).build()); "default tui tunnel ttl jump")]
+    #[test_case("trip example.com --tui-tunnel-ttl-jump 5", Ok(cfg().tui_tunnel_ttl_jump(5).build()); "custom tui tunnel ttl jump")]
+    #[test_case("trip example.com --tui-tunnel-ttl-jump 0", Err(anyhow!("tui-tunnel-ttl-jump (0) must be greater than zero")); "zero tui tunnel ttl jump")]
+    fn test_tui_tunnel_ttl_jump(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_status_mode(StatusMode::Health).build()); "default tui status mode")]
+    #[test_case("trip example.com --tui-status-mode summary", Ok(cfg().tui_status_mode(StatusMode::Summary).build()); "summary tui status mode")]
+    #[test_case("trip example.com --tui-status-mode icmp", Ok(cfg().tui_status_mode(StatusMode::Icmp).build()); "icmp tui status mode")]
+    #[test_case("trip example.com --tui-status-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-status-mode <TUI_STATUS_MODE>' [possible values: health, summary, icmp] For more information, try '--help'.")); "invalid tui status mode")]
+    fn test_tui_status_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_addr_aggregation(AddrAggregationMode::Combined).build()); "default tui addr aggregation")]
+    #[test_case("trip example.com --tui-addr-aggregation split", Ok(cfg().tui_addr_aggregation(AddrAggregationMode::Split).build()); "split tui addr aggregation")]
+    #[test_case("trip example.com --tui-addr-aggregation foo", Err(anyhow!("error: invalid value 'foo' for '--tui-addr-aggregation <TUI_ADDR_AGGREGATION>' [possible values: combined, split] For more information, try '--help'.")); "invalid tui addr aggregation")]
+    fn test_tui_addr_aggregation(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_rtt_compact_threshold(None).build()); "default tui rtt compact threshold")]
+    #[test_case("trip example.com --tui-rtt-compact-threshold 1s", Ok(cfg().tui_rtt_compact_threshold(Some(Duration::from_secs(1))).build()); "custom tui rtt compact threshold")]
+    #[test_case("trip example.com --tui-rtt-compact-threshold 1500ms", Ok(cfg().tui_rtt_compact_threshold(Some(Duration::from_millis(1500))).build()); "custom tui rtt compact threshold millis")]
+    #[test_case("trip example.com --tui-rtt-compact-threshold foo", Err(anyhow!("error: invalid value 'foo' for '--tui-rtt-compact-threshold <TUI_RTT_COMPACT_THRESHOLD>': expected number at 0 For more information, try '--help'.")); "invalid tui rtt compact threshold")]
+    fn test_tui_rtt_compact_threshold(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_rtt_heat_mode(RttHeatMode::Off).build()); "default tui rtt heat mode")]
+    #[test_case("trip example.com --tui-rtt-heat-mode off", Ok(cfg().tui_rtt_heat_mode(RttHeatMode::Off).build()); "off tui rtt heat mode")]
+    #[test_case("trip example.com --tui-rtt-heat-mode path", Ok(cfg().tui_rtt_heat_mode(RttHeatMode::Path).build()); "path tui rtt heat mode")]
+    #[test_case("trip example.com --tui-rtt-heat-mode fixed", Ok(cfg().tui_rtt_heat_mode(RttHeatMode::Fixed).build()); "fixed tui rtt heat mode")]
+    #[test_case("trip example.com --tui-rtt-heat-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-rtt-heat-mode <TUI_RTT_HEAT_MODE>' [possible values: off, path, fixed] For more information, try '--help'.")); "invalid tui rtt heat mode")]
+    fn test_tui_rtt_heat_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_rtt_heat_max(Duration::from_millis(250)).build()); "default tui rtt heat max")]
+    #[test_case("trip example.com --tui-rtt-heat-max 1s", Ok(cfg().tui_rtt_heat_max(Duration::from_secs(1)).build()); "custom tui rtt heat max")]
+    #[test_case("trip example.com --tui-rtt-heat-max 0s", Err(anyhow!("tui-rtt-heat-max (0ns) must be greater than zero")); "invalid tui rtt heat max")]
+    fn test_tui_rtt_heat_max(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_unresponsive_hop_mode(UnresponsiveHopMode::NoResponse).build()); "default tui unresponsive hop mode")]
+    #[test_case("trip example.com --tui-unresponsive-hop-mode no-response", Ok(cfg().tui_unresponsive_hop_mode(UnresponsiveHopMode::NoResponse).build()); "no response tui unresponsive hop mode")]
+    #[test_case("trip example.com --tui-unresponsive-hop-mode blank", Ok(cfg().tui_unresponsive_hop_mode(UnresponsiveHopMode::Blank).build()); "blank tui unresponsive hop mode")]
+    #[test_case("trip example.com --tui-unresponsive-hop-mode stars", Ok(cfg().tui_unresponsive_hop_mode(UnresponsiveHopMode::Stars).build()); "stars tui unresponsive hop mode")]
+    #[test_case("trip example.com --tui-unresponsive-hop-mode last-known", Ok(cfg().tui_unresponsive_hop_mode(UnresponsiveHopMode::LastKnown).build()); "last known tui unresponsive hop mode")]
+    #[test_case("trip example.com --tui-unresponsive-hop-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-unresponsive-hop-mode <TUI_UNRESPONSIVE_HOP_MODE>' [possible values: no-response, blank, stars, last-known] For more information, try '--help'.")); "invalid tui unresponsive hop mode")]
+    fn test_tui_unresponsive_hop_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_trailing_hop_mode(TrailingHopMode::Waiting).build()); "default tui trailing hop mode")]
+    #[test_case("trip example.com --tui-trailing-hop-mode waiting", Ok(cfg().tui_trailing_hop_mode(TrailingHopMode::Waiting).build()); "waiting tui trailing hop mode")]
+    #[test_case("trip example.com --tui-trailing-hop-mode unresponsive", Ok(cfg().tui_trailing_hop_mode(TrailingHopMode::Unresponsive).build()); "unresponsive tui trailing hop mode")]
+    #[test_case("trip example.com --tui-trailing-hop-mode hide", Ok(cfg().tui_trailing_hop_mode(TrailingHopMode::Hide).build()); "hide tui trailing hop mode")]
+    #[test_case("trip example.com --tui-trailing-hop-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-trailing-hop-mode <TUI_TRAILING_HOP_MODE>' [possible values: waiting, unresponsive, hide] For more information, try '--help'.")); "invalid tui trailing hop mode")]
+    fn test_tui_trailing_hop_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_time_exceeded_rtt_mode(TimeExceededRttMode::Include).build()); "default tui time exceeded rtt mode")]
+    #[test_case("trip example.com --tui-time-exceeded-rtt-mode include", Ok(cfg().tui_time_exceeded_rtt_mode(TimeExceededRttMode::Include).build()); "include tui time exceeded rtt mode")]
+    #[test_case("trip example.com --tui-time-exceeded-rtt-mode distinguish", Ok(cfg().tui_time_exceeded_rtt_mode(TimeExceededRttMode::Distinguish).build()); "distinguish tui time exceeded rtt mode")]
+    #[test_case("trip example.com --tui-time-exceeded-rtt-mode exclude", Ok(cfg().tui_time_exceeded_rtt_mode(TimeExceededRttMode::Exclude).build()); "exclude tui time exceeded rtt mode")]
+    #[test_case("trip example.com --tui-time-exceeded-rtt-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-time-exceeded-rtt-mode <TUI_TIME_EXCEEDED_RTT_MODE>' [possible values: include, distinguish, exclude] For more information, try '--help'.")); "invalid tui time exceeded rtt mode")]
+    fn test_tui_time_exceeded_rtt_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_average_mode(AverageMode::Mean).build()); "default tui average mode")]
+    #[test_case("trip example.com --tui-average-mode mean", Ok(cfg().tui_average_mode(AverageMode::Mean).build()); "mean tui average mode")]
+    #[test_case("trip example.com --tui-average-mode ewma", Ok(cfg().tui_average_mode(AverageMode::Ewma).build()); "ewma tui average mode")]
+    #[test_case("trip example.com --tui-average-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-average-mode <TUI_AVERAGE_MODE>' [possible values: mean, ewma] For more information, try '--help'.")); "invalid tui average mode")]
+    fn test_tui_average_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_ewma_weight(10).build()); "default tui ewma weight")]
+    #[test_case("trip example.com --tui-ewma-weight 1", Ok(cfg().tui_ewma_weight(1).build()); "minimum tui ewma weight")]
+    #[test_case("trip example.com --tui-ewma-weight 100", Ok(cfg().tui_ewma_weight(100).build()); "maximum tui ewma weight")]
+    #[test_case("trip example.com --tui-ewma-weight 0", Err(anyhow!("tui-ewma-weight (0) must be between 1 and 100 inclusive")); "zero tui ewma weight")]
+    #[test_case("trip example.com --tui-ewma-weight 101", Err(anyhow!("tui-ewma-weight (101) must be between 1 and 100 inclusive")); "too large tui ewma weight")]
+    fn test_tui_ewma_weight(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_loss_ewma_weight(10).build()); "default tui loss ewma weight")]
+    #[test_case("trip example.com --tui-loss-ewma-weight 1", Ok(cfg().tui_loss_ewma_weight(1).build()); "minimum tui loss ewma weight")]
+    #[test_case("trip example.com --tui-loss-ewma-weight 100", Ok(cfg().tui_loss_ewma_weight(100).build()); "maximum tui loss ewma weight")]
+    #[test_case("trip example.com --tui-loss-ewma-weight 0", Err(anyhow!("tui-loss-ewma-weight (0) must be between 1 and 100 inclusive")); "zero tui loss ewma weight")]
+    #[test_case("trip example.com --tui-loss-ewma-weight 101", Err(anyhow!("tui-loss-ewma-weight (101) must be between 1 and 100 inclusive")); "too large tui loss ewma weight")]
+    fn test_tui_loss_ewma_weight(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_target_ttl_mode(TargetTtlMode::Lowest).build()); "default tui target ttl mode")]
+    #[test_case("trip example.com --tui-target-ttl-mode lowest", Ok(cfg().tui_target_ttl_mode(TargetTtlMode::Lowest).build()); "lowest tui target ttl mode")]
+    #[test_case("trip example.com --tui-target-ttl-mode all", Ok(cfg().tui_target_ttl_mode(TargetTtlMode::All).build()); "all tui target ttl mode")]
+    #[test_case("trip example.com --tui-target-ttl-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-target-ttl-mode <TUI_TARGET_TTL_MODE>' [possible values: lowest, all] For more information, try '--help'.")); "invalid tui target ttl mode")]
+    fn test_tui_target_ttl_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_host_truncation(HostTruncation::Right).build()); "default tui host truncation")]
+    #[test_case("trip example.com --tui-host-truncation right", Ok(cfg().tui_host_truncation(HostTruncation::Right).build()); "right tui host truncation")]
+    #[test_case("trip example.com --tui-host-truncation left", Ok(cfg().tui_host_truncation(HostTruncation::Left).build()); "left tui host truncation")]
+    #[test_case("trip example.com --tui-host-truncation foo", Err(anyhow!("error: invalid value 'foo' for '--tui-host-truncation <TUI_HOST_TRUNCATION>' [possible values: right, left] For more information, try '--help'.")); "invalid tui host truncation")]
+    fn test_tui_host_truncation(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().tui_custom_columns(TuiColumns::default()).build()); "default tui custom columns")]
     #[test_case("trip example.com --tui-custom-columns hol", Ok(cfg().tui_custom_columns(TuiColumns(vec![TuiColumn::Ttl, TuiColumn::Host, TuiColumn::LossPct])).build()); "custom tui custom columns")]
     #[test_case("trip example.com --tui-custom-columns hh", Err(anyhow!("Duplicate custom columns: h")); "invalid duplicate tui custom columns")]
@@ -1505,6 +2865,24 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().tui_column_groups(TuiColumnGroups::default()).build()); "default tui column groups")]
+    #[test_case("trip example.com --tui-column-groups rtt=ab", Ok(cfg().tui_column_groups(TuiColumnGroups(vec![TuiColumnGroup { name: String::from("rtt"), columns: TuiColumns(vec![TuiColumn::Last, TuiColumn::Best]) }])).build()); "custom tui column groups")]
+    #[test_case("trip example.com --tui-column-groups=", Ok(cfg().tui_column_groups(TuiColumnGroups(vec![])).build()); "no tui column groups")]
+    #[test_case("trip example.com --tui-column-groups rtt", Err(anyhow!("invalid column group: rtt (expected name=columns)")); "invalid missing columns tui column groups")]
+    #[test_case("trip example.com --tui-column-groups rtt=u", Err(anyhow!("unknown column code: u")); "invalid unknown tui column groups")]
+    fn test_tui_column_groups(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_rtt_precision(TuiRttPrecision::default()).build()); "default tui rtt precision")]
+    #[test_case("trip example.com --tui-rtt-precision a=0,v=2", Ok(cfg().tui_rtt_precision(TuiRttPrecision(vec![(TuiColumn::Last, 0), (TuiColumn::Average, 2)])).build()); "custom tui rtt precision")]
+    #[test_case("trip example.com --tui-rtt-precision=", Ok(cfg().tui_rtt_precision(TuiRttPrecision(vec![])).build()); "no tui rtt precision")]
+    #[test_case("trip example.com --tui-rtt-precision l=2", Err(anyhow!("invalid rtt precision column: l (not a round-trip time column)")); "invalid column tui rtt precision")]
+    #[test_case("trip example.com --tui-rtt-precision a=5", Err(anyhow!("invalid rtt precision places: 5 (must be at most 4)")); "invalid places tui rtt precision")]
+    fn test_tui_rtt_precision(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().tui_icmp_extension_mode(IcmpExtensionMode::Off).build()); "default tui icmp extension mode")]
     #[test_case("trip example.com --tui-icmp-extension-mode off", Ok(cfg().tui_icmp_extension_mode(IcmpExtensionMode::Off).build()); "off tui icmp extension mode")]
     #[test_case("trip example.com --tui-icmp-extension-mode mpls", Ok(cfg().tui_icmp_extension_mode(IcmpExtensionMode::Mpls).build()); "mpls tui icmp extension mode")]
@@ -1526,6 +2904,14 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().tui_glyph_set(GlyphSet::Auto).build()); "default tui glyph set")]
+    #[test_case("trip example.com --tui-glyph-set unicode", Ok(cfg().tui_glyph_set(GlyphSet::Unicode).build()); "unicode tui glyph set")]
+    #[test_case("trip example.com --tui-glyph-set ascii", Ok(cfg().tui_glyph_set(GlyphSet::Ascii).build()); "ascii tui glyph set")]
+    #[test_case("trip example.com --tui-glyph-set foo", Err(anyhow!("error: invalid value 'foo' for '--tui-glyph-set <TUI_GLYPH_SET>' [possible values: auto, unicode, ascii] For more information, try '--help'.")); "invalid tui glyph set")]
+    fn test_tui_glyph_set(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().tui_max_addrs(None).build()); "default tui max addrs")]
     #[test_case("trip example.com --tui-max-addrs 5", Ok(cfg().tui_max_addrs(Some(5)).build()); "custom tui max addrs")]
     #[test_case("trip example.com -M 7", Ok(cfg().tui_max_addrs(Some(7)).build()); "custom tui max addrs short")]
@@ -1534,6 +2920,92 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().tui_max_display_hops(None).build()); "default tui max display hops")]
+    #[test_case("trip example.com --tui-max-display-hops 15", Ok(cfg().tui_max_display_hops(Some(15)).build()); "custom tui max display hops")]
+    #[test_case("trip example.com --tui-max-display-hops 0", Ok(cfg().tui_max_display_hops(None).build()); "zero tui max display hops")]
+    #[test_case("trip example.com --tui-max-display-hops foo", Err(anyhow!("error: invalid value 'foo' for '--tui-max-display-hops <TUI_MAX_DISPLAY_HOPS>': invalid digit found in string For more information, try '--help'.")); "invalid tui max display hops")]
+    fn test_tui_max_display_hops(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_stats_window(None).build()); "default tui stats window")]
+    #[test_case("trip example.com --tui-stats-window 0", Ok(cfg().tui_stats_window(None).build()); "all tui stats window")]
+    #[test_case("trip example.com --tui-stats-window 20", Ok(cfg().tui_stats_window(Some(20)).build()); "custom tui stats window")]
+    #[test_case("trip example.com --tui-stats-window foo", Err(anyhow!("error: invalid value 'foo' for '--tui-stats-window <TUI_STATS_WINDOW>': invalid digit found in string For more information, try '--help'.")); "invalid tui stats window")]
+    fn test_tui_stats_window(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_stddev_mode(StdDevMode::Sample).build()); "default tui stddev mode")]
+    #[test_case("trip example.com --tui-stddev-mode sample", Ok(cfg().tui_stddev_mode(StdDevMode::Sample).build()); "sample tui stddev mode")]
+    #[test_case("trip example.com --tui-stddev-mode population", Ok(cfg().tui_stddev_mode(StdDevMode::Population).build()); "population tui stddev mode")]
+    #[test_case("trip example.com --tui-stddev-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-stddev-mode <TUI_STDDEV_MODE>' [possible values: sample, population] For more information, try '--help'.")); "invalid tui stddev mode")]
+    fn test_tui_stddev_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_stddev_scope(StdDevScope::Window).build()); "default tui stddev scope")]
+    #[test_case("trip example.com --tui-stddev-scope window", Ok(cfg().tui_stddev_scope(StdDevScope::Window).build()); "window tui stddev scope")]
+    #[test_case("trip example.com --tui-stddev-scope all", Ok(cfg().tui_stddev_scope(StdDevScope::All).build()); "all tui stddev scope")]
+    #[test_case("trip example.com --tui-stddev-scope foo", Err(anyhow!("error: invalid value 'foo' for '--tui-stddev-scope <TUI_STDDEV_SCOPE>' [possible values: window, all] For more information, try '--help'.")); "invalid tui stddev scope")]
+    fn test_tui_stddev_scope(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().max_rounds(None).build()); "default tui max rounds")]
+    #[test_case("trip example.com --tui-max-rounds 0", Ok(cfg().max_rounds(None).build()); "unlimited tui max rounds")]
+    #[test_case("trip example.com --tui-max-rounds 20", Ok(cfg().max_rounds(Some(20)).build()); "custom tui max rounds")]
+    #[test_case("trip example.com --mode stream --tui-max-rounds 20", Ok(cfg().mode(Mode::Stream).max_rounds(None).build()); "tui max rounds ignored in stream mode")]
+    #[test_case("trip example.com --mode silent --tui-max-rounds 20", Ok(cfg().mode(Mode::Silent).max_rounds(Some(10)).build()); "tui max rounds ignored in report mode")]
+    fn test_tui_max_rounds(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_auto_quit_stable_rounds(None).build()); "default tui auto quit stable rounds")]
+    #[test_case("trip example.com --tui-auto-quit-stable-rounds 0", Ok(cfg().tui_auto_quit_stable_rounds(None).build()); "disabled tui auto quit stable rounds")]
+    #[test_case("trip example.com --tui-auto-quit-stable-rounds 20", Ok(cfg().tui_auto_quit_stable_rounds(Some(20)).build()); "custom tui auto quit stable rounds")]
+    #[test_case("trip example.com --tui-auto-quit-stable-rounds 100 --max-samples 100", Ok(cfg().tui_auto_quit_stable_rounds(Some(100)).max_samples(100).build()); "maximum tui auto quit stable rounds")]
+    #[test_case("trip example.com --tui-auto-quit-stable-rounds 101 --max-samples 100", Err(anyhow!("tui-auto-quit-stable-rounds (101) must not exceed max-samples (100)")); "invalid tui auto quit stable rounds")]
+    fn test_tui_auto_quit_stable_rounds(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_auto_quit_stable_threshold(Duration::from_millis(5)).build()); "default tui auto quit stable threshold")]
+    #[test_case("trip example.com --tui-auto-quit-stable-threshold 20ms", Ok(cfg().tui_auto_quit_stable_threshold(Duration::from_millis(20)).build()); "custom tui auto quit stable threshold")]
+    #[test_case("trip example.com --tui-auto-quit-stable-threshold foo", Err(anyhow!("error: invalid value 'foo' for '--tui-auto-quit-stable-threshold <TUI_AUTO_QUIT_STABLE_THRESHOLD>': expected number at 0 For more information, try '--help'.")); "invalid tui auto quit stable threshold")]
+    fn test_tui_auto_quit_stable_threshold(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_path_change_rounds(5).build()); "default tui path change rounds")]
+    #[test_case("trip example.com --tui-path-change-rounds 0", Ok(cfg().tui_path_change_rounds(0).build()); "disabled tui path change rounds")]
+    #[test_case("trip example.com --tui-path-change-rounds 20", Ok(cfg().tui_path_change_rounds(20).build()); "custom tui path change rounds")]
+    fn test_tui_path_change_rounds(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_path_stability_window(Duration::from_secs(60)).build()); "default tui path stability window")]
+    #[test_case("trip example.com --tui-path-stability-window 0s", Ok(cfg().tui_path_stability_window(Duration::ZERO).build()); "disabled tui path stability window")]
+    #[test_case("trip example.com --tui-path-stability-window 5m", Ok(cfg().tui_path_stability_window(Duration::from_secs(300)).build()); "custom tui path stability window")]
+    #[test_case("trip example.com --tui-path-stability-window foo", Err(anyhow!("error: invalid value 'foo' for '--tui-path-stability-window <TUI_PATH_STABILITY_WINDOW>': expected number at 0 For more information, try '--help'.")); "invalid tui path stability window")]
+    fn test_tui_path_stability_window(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_flow_sort_mode(FlowSortMode::Rounds).build()); "default tui flow sort mode")]
+    #[test_case("trip example.com --tui-flow-sort-mode rtt", Ok(cfg().tui_flow_sort_mode(FlowSortMode::Rtt).build()); "rtt tui flow sort mode")]
+    #[test_case("trip example.com --tui-flow-sort-mode loss", Ok(cfg().tui_flow_sort_mode(FlowSortMode::Loss).build()); "loss tui flow sort mode")]
+    #[test_case("trip example.com --tui-flow-sort-mode foo", Err(anyhow!("error: invalid value 'foo' for '--tui-flow-sort-mode <TUI_FLOW_SORT_MODE>' [possible values: rounds, rtt, loss] For more information, try '--help'.")); "invalid tui flow sort mode")]
+    fn test_tui_flow_sort_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().tui_probe_countdown(false).build()); "default tui probe countdown")]
+    #[test_case("trip example.com --tui-probe-countdown", Ok(cfg().tui_probe_countdown(true).build()); "enable tui probe countdown")]
+    fn test_tui_probe_countdown(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().tui_theme(TuiTheme::default()).build()); "default tui theme")]
     #[test_case("trip example.com --tui-theme-colors bg-color=red", Ok(cfg().tui_theme(TuiTheme { bg: TuiColor::Red, ..Default::default() }).build()); "custom tui theme named color")]
     #[test_case("trip example.com --tui-theme-colors bg-color=010203", Ok(cfg().tui_theme(TuiTheme { bg: TuiColor::Rgb(1, 2, 3), ..Default::default() }).build()); "custom tui theme hex color")]
@@ -1553,6 +3025,7 @@ mod tests {
     #[test_case("trip example.com --tui-key-bindings foo=h", Err(anyhow!("error: invalid value 'foo=h' for '--tui-key-bindings <TUI_KEY_BINDINGS>': Matching variant not found For more information, try '--help'.")); "invalid tui binding command")]
     #[test_case("trip example.com --tui-key-bindings toggle-help=123", Err(anyhow!("error: invalid value 'toggle-help=123' for '--tui-key-bindings <TUI_KEY_BINDINGS>': unknown key binding '123' For more information, try '--help'.")); "invalid tui binding key")]
     #[test_case("trip example.com --tui-key-bindings toggle-help=h,toggle-map=h", Err(anyhow!("Duplicate key bindings: h: [toggle-map and toggle-help]")); "invalid tui binding duplicate binding")]
+    #[test_case("trip example.com --tui-key-bindings toggle-help=h,toggle-settings-dns=h", Err(anyhow!("Duplicate key bindings: h: [toggle-settings-dns and toggle-help]")); "invalid tui binding duplicate settings binding")]
     #[test_case("trip example.com --tui-key-bindings toggle-help=h, toggle-map=m", Err(anyhow!("error: invalid value '' for '--tui-key-bindings <TUI_KEY_BINDINGS>': invalid binding value: expected format `item=value` For more information, try '--help'.")); "invalid tui binding multiple with space")]
     fn test_tui_bindings(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
         compare(parse_config(cmd), expected);
@@ -1567,6 +3040,92 @@ mod tests {
         compare(parse_config(cmd), expected);
     }
 
+    #[test_case("trip example.com", Ok(cfg().webhook_url(None).build()); "default webhook url")]
+    #[test_case("trip example.com --webhook-url https://example.com/hook", Ok(cfg().webhook_url(Some(String::from("https://example.com/hook"))).build()); "https webhook url")]
+    #[test_case("trip example.com --webhook-url http://example.com/hook", Ok(cfg().webhook_url(Some(String::from("http://example.com/hook"))).build()); "http webhook url")]
+    #[test_case("trip example.com --webhook-url example.com/hook", Err(anyhow!("webhook-url (example.com/hook) must be an http or https url")); "invalid webhook url")]
+    #[cfg(feature = "webhook")]
+    fn test_webhook_url(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().webhook_url(None).build()); "default webhook url")]
+    #[test_case("trip example.com --webhook-url https://example.com/hook", Err(anyhow!("webhook-url requires Trippy to be built with the `webhook` feature")); "webhook feature disabled")]
+    #[cfg(not(feature = "webhook"))]
+    fn test_webhook_url(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().webhook_trigger(WebhookTrigger::Completion).build()); "default webhook trigger")]
+    #[test_case("trip example.com --webhook-trigger completion", Ok(cfg().webhook_trigger(WebhookTrigger::Completion).build()); "completion webhook trigger")]
+    #[test_case("trip example.com --webhook-trigger reachability-change", Ok(cfg().webhook_trigger(WebhookTrigger::ReachabilityChange).build()); "reachability change webhook trigger")]
+    #[test_case("trip example.com --webhook-trigger foo", Err(anyhow!("error: invalid value 'foo' for '--webhook-trigger <WEBHOOK_TRIGGER>' [possible values: completion, reachability-change] For more information, try '--help'.")); "invalid webhook trigger")]
+    fn test_webhook_trigger(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().webhook_fields(vec![WebhookField::Target, WebhookField::Round, WebhookField::Reachable, WebhookField::Hops]).build()); "default webhook fields")]
+    #[test_case("trip example.com --webhook-fields reachable", Ok(cfg().webhook_fields(vec![WebhookField::Reachable]).build()); "single webhook field")]
+    #[test_case("trip example.com --webhook-fields target,reachable", Ok(cfg().webhook_fields(vec![WebhookField::Target, WebhookField::Reachable]).build()); "multiple webhook fields")]
+    #[test_case("trip example.com --webhook-fields foo", Err(anyhow!("error: invalid value 'foo' for '--webhook-fields <WEBHOOK_FIELDS>' [possible values: target, round, reachable, hops] For more information, try '--help'.")); "invalid webhook field")]
+    fn test_webhook_fields(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().webhook_retries(3).build()); "default webhook retries")]
+    #[test_case("trip example.com --webhook-retries 0", Ok(cfg().webhook_retries(0).build()); "no webhook retries")]
+    #[test_case("trip example.com --webhook-retries 10", Ok(cfg().webhook_retries(10).build()); "custom webhook retries")]
+    #[test_case("trip example.com --webhook-retries 11", Err(anyhow!("webhook-retries (11) must be at most 10")); "invalid high webhook retries")]
+    #[test_case("trip example.com --webhook-retries 255", Err(anyhow!("webhook-retries (255) must be at most 10")); "invalid max webhook retries")]
+    fn test_webhook_retries(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().sqlite_file(None).build()); "default sqlite file")]
+    #[test_case("trip example.com --sqlite-file trippy.db", Ok(cfg().sqlite_file(Some(String::from("trippy.db"))).build()); "custom sqlite file")]
+    #[cfg(feature = "sqlite")]
+    fn test_sqlite_file(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().sqlite_file(None).build()); "default sqlite file")]
+    #[test_case("trip example.com --sqlite-file trippy.db", Err(anyhow!("sqlite-file requires Trippy to be built with the `sqlite` feature")); "sqlite feature disabled")]
+    #[cfg(not(feature = "sqlite"))]
+    fn test_sqlite_file(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().sqlite_retention(None).build()); "default sqlite retention")]
+    #[test_case("trip example.com --sqlite-retention 30d", Ok(cfg().sqlite_retention(Some(Duration::from_secs(30 * 24 * 60 * 60))).build()); "custom sqlite retention")]
+    #[test_case("trip example.com --sqlite-retention foo", Err(anyhow!("error: invalid value 'foo' for '--sqlite-retention <SQLITE_RETENTION>': expected number at 0 For more information, try '--help'.")); "invalid sqlite retention")]
+    fn test_sqlite_retention(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().prometheus_file(None).build()); "default prometheus file")]
+    #[test_case("trip example.com --prometheus-file trippy.prom", Ok(cfg().prometheus_file(Some(String::from("trippy.prom"))).build()); "custom prometheus file")]
+    fn test_prometheus_file(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().local_target_mode(LocalTargetMode::Warn).build()); "default local target mode")]
+    #[test_case("trip example.com --local-target-mode trace", Ok(cfg().local_target_mode(LocalTargetMode::Trace).build()); "trace local target mode")]
+    #[test_case("trip example.com --local-target-mode warn", Ok(cfg().local_target_mode(LocalTargetMode::Warn).build()); "warn local target mode")]
+    #[test_case("trip example.com --local-target-mode reject", Ok(cfg().local_target_mode(LocalTargetMode::Reject).build()); "reject local target mode")]
+    #[test_case("trip example.com --local-target-mode foo", Err(anyhow!("error: invalid value 'foo' for '--local-target-mode <LOCAL_TARGET_MODE>' [possible values: trace, warn, reject] For more information, try '--help'.")); "invalid local target mode")]
+    fn test_local_target_mode(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
+    #[test_case("trip example.com", Ok(cfg().bell_trigger(BellTrigger::Never).build()); "default bell trigger")]
+    #[test_case("trip example.com --bell-trigger never", Ok(cfg().bell_trigger(BellTrigger::Never).build()); "never bell trigger")]
+    #[test_case("trip example.com --bell-trigger every-round", Ok(cfg().bell_trigger(BellTrigger::EveryRound).build()); "every round bell trigger")]
+    #[test_case("trip example.com --bell-trigger loss-rounds", Ok(cfg().bell_trigger(BellTrigger::LossRounds).build()); "loss rounds bell trigger")]
+    #[test_case("trip example.com --bell-trigger foo", Err(anyhow!("error: invalid value 'foo' for '--bell-trigger <BELL_TRIGGER>' [possible values: never, every-round, loss-rounds] For more information, try '--help'.")); "invalid bell trigger")]
+    fn test_bell_trigger(cmd: &str, expected: anyhow::Result<TrippyConfig>) {
+        compare(parse_config(cmd), expected);
+    }
+
     #[test_case("trip example.com", Ok(cfg().geoip_mmdb_file(None).build()); "default geoip mmdb file")]
     #[test_case("trip example.com --geoip-mmdb-file foo.mmdb", Ok(cfg().geoip_mmdb_file(Some(String::from("foo.mmdb"))).build()); "custom geoip mmdb file")]
     #[test_case("trip example.com -G foo.mmdb", Ok(cfg().geoip_mmdb_file(Some(String::from("foo.mmdb"))).build()); "custom geoip mmdb file short")]
@@ -1618,6 +3177,7 @@ mod tests {
     #[test_case("trip example.com --unprivileged", false, false, Ok(cfg().privilege_mode(PrivilegeMode::Unprivileged).build()); "no privilege and not needs in unprivileged mode")]
     #[test_case("trip example.com --unprivileged", false, true, Err(anyhow!("unprivileged mode not supported on this platform\n\nsee https://github.com/fujiapple852/trippy#privileges for details")); "no privilege and needs in unprivileged mode")]
     #[test_case("trip example.com --unprivileged", true, true, Err(anyhow!("unprivileged mode not supported on this platform (hint: process is privileged so disable unprivileged mode)\n\nsee https://github.com/fujiapple852/trippy#privileges for details")); "has privilege and needs in unprivileged mode")]
+    #[test_case("trip example.com --mode dry-run", false, false, Ok(cfg().mode(Mode::DryRun).max_rounds(Some(10)).privilege_mode(PrivilegeMode::Privileged).build()); "no privilege in dry run mode")]
     fn test_privilege(
         cmd: &str,
         has_privileges: bool,
@@ -1870,22 +3430,49 @@ mod tests {
             }
         }
 
-        pub fn initial_sequence(self, initial_sequence: u16) -> Self {
+        pub fn send_batch_size(self, send_batch_size: u8) -> Self {
             Self {
                 config: TrippyConfig {
-                    initial_sequence,
+                    send_batch_size,
                     ..self.config
                 },
             }
         }
 
-        pub fn tos(self, tos: u8) -> Self {
+        pub fn recv_batch_size(self, recv_batch_size: u8) -> Self {
             Self {
-                config: TrippyConfig { tos, ..self.config },
+                config: TrippyConfig {
+                    recv_batch_size,
+                    ..self.config
+                },
             }
         }
 
-        pub fn icmp_extension_parse_mode(
+        pub fn initial_sequence(self, initial_sequence: u16) -> Self {
+            Self {
+                config: TrippyConfig {
+                    initial_sequence,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tos(self, tos: u8) -> Self {
+            Self {
+                config: TrippyConfig { tos, ..self.config },
+            }
+        }
+
+        pub fn flow_label(self, flow_label: u32) -> Self {
+            Self {
+                config: TrippyConfig {
+                    flow_label,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn icmp_extension_parse_mode(
             self,
             icmp_extension_parse_mode: IcmpExtensionParseMode,
         ) -> Self {
@@ -1906,6 +3493,141 @@ mod tests {
             }
         }
 
+        pub fn min_probe_interval(self, min_probe_interval: Option<Duration>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    min_probe_interval,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn send_jitter(self, send_jitter: Option<Duration>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    send_jitter,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn loss_timeout(self, loss_timeout: Option<Duration>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    loss_timeout,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn ignore_identifier(self, ignore_identifier: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    ignore_identifier,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn discard_first_hop_warmup(self, discard_first_hop_warmup: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    discard_first_hop_warmup,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn accept_late_responses(self, accept_late_responses: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    accept_late_responses,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn adaptive_read_timeout(self, adaptive_read_timeout: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    adaptive_read_timeout,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn max_unresponsive_hops(self, max_unresponsive_hops: Option<u8>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    max_unresponsive_hops,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn curtail_unresponsive(self, curtail_unresponsive: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    curtail_unresponsive,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn setup_retry_attempts(self, setup_retry_attempts: usize) -> Self {
+            Self {
+                config: TrippyConfig {
+                    setup_retry_attempts,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn setup_retry_backoff(self, setup_retry_backoff: Duration) -> Self {
+            Self {
+                config: TrippyConfig {
+                    setup_retry_backoff,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn kernel_recv_timestamps(self, kernel_recv_timestamps: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    kernel_recv_timestamps,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn stamped_payload(self, stamped_payload: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    stamped_payload,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn payload_strategy(self, payload_strategy: PayloadStrategy) -> Self {
+            Self {
+                config: TrippyConfig {
+                    payload_strategy,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn icmp_data_length(self, icmp_data_length: Option<u16>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    icmp_data_length,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn packet_size(self, packet_size: u16) -> Self {
             Self {
                 config: TrippyConfig {
@@ -1942,6 +3664,15 @@ mod tests {
             }
         }
 
+        pub fn local_target_mode(self, local_target_mode: LocalTargetMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    local_target_mode,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn port_direction(self, port_direction: PortDirection) -> Self {
             Self {
                 config: TrippyConfig {
@@ -2050,6 +3781,15 @@ mod tests {
             }
         }
 
+        pub fn tui_first_hop_warning_rounds(self, tui_first_hop_warning_rounds: usize) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_first_hop_warning_rounds,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn tui_locale(self, tui_locale: Option<String>) -> Self {
             Self {
                 config: TrippyConfig {
@@ -2059,6 +3799,33 @@ mod tests {
             }
         }
 
+        pub fn tui_notes_file(self, tui_notes_file: Option<String>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_notes_file,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_debug_fixture(self, tui_debug_fixture: Option<String>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_debug_fixture,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_provider_classes(self, tui_provider_classes: HashMap<u32, String>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_provider_classes,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn tui_address_mode(self, tui_address_mode: AddressMode) -> Self {
             Self {
                 config: TrippyConfig {
@@ -2077,6 +3844,72 @@ mod tests {
             }
         }
 
+        pub fn tui_as_colors(self, tui_as_colors: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_as_colors,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_summary_footer(self, tui_summary_footer: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_summary_footer,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_rtt_deltas(self, tui_rtt_deltas: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_rtt_deltas,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_rtt_baseline(self, tui_rtt_baseline: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_rtt_baseline,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_rtt_compact_threshold(
+            self,
+            tui_rtt_compact_threshold: Option<Duration>,
+        ) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_rtt_compact_threshold,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_rtt_heat_mode(self, tui_rtt_heat_mode: RttHeatMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_rtt_heat_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_rtt_heat_max(self, tui_rtt_heat_max: Duration) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_rtt_heat_max,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn tui_custom_columns(self, tui_custom_columns: TuiColumns) -> Self {
             Self {
                 config: TrippyConfig {
@@ -2086,6 +3919,24 @@ mod tests {
             }
         }
 
+        pub fn tui_column_groups(self, tui_column_groups: TuiColumnGroups) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_column_groups,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_rtt_precision(self, tui_rtt_precision: TuiRttPrecision) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_rtt_precision,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn tui_icmp_extension_mode(self, tui_icmp_extension_mode: IcmpExtensionMode) -> Self {
             Self {
                 config: TrippyConfig {
@@ -2104,6 +3955,120 @@ mod tests {
             }
         }
 
+        pub fn tui_unresponsive_hop_mode(
+            self,
+            tui_unresponsive_hop_mode: UnresponsiveHopMode,
+        ) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_unresponsive_hop_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_trailing_hop_mode(self, tui_trailing_hop_mode: TrailingHopMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_trailing_hop_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_time_exceeded_rtt_mode(
+            self,
+            tui_time_exceeded_rtt_mode: TimeExceededRttMode,
+        ) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_time_exceeded_rtt_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_average_mode(self, tui_average_mode: AverageMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_average_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_ewma_weight(self, tui_ewma_weight: u8) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_ewma_weight,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_loss_ewma_weight(self, tui_loss_ewma_weight: u8) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_loss_ewma_weight,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_tunnel_ttl_jump(self, tui_tunnel_ttl_jump: u8) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_tunnel_ttl_jump,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_status_mode(self, tui_status_mode: StatusMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_status_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_addr_aggregation(self, tui_addr_aggregation: AddrAggregationMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_addr_aggregation,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_target_ttl_mode(self, tui_target_ttl_mode: TargetTtlMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_target_ttl_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_host_truncation(self, tui_host_truncation: HostTruncation) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_host_truncation,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_glyph_set(self, tui_glyph_set: GlyphSet) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_glyph_set,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn tui_max_addrs(self, tui_max_addrs: Option<u8>) -> Self {
             Self {
                 config: TrippyConfig {
@@ -2113,6 +4078,102 @@ mod tests {
             }
         }
 
+        pub fn tui_max_display_hops(self, tui_max_display_hops: Option<u8>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_max_display_hops,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_stats_window(self, tui_stats_window: Option<usize>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_stats_window,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_stddev_mode(self, tui_stddev_mode: StdDevMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_stddev_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_stddev_scope(self, tui_stddev_scope: StdDevScope) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_stddev_scope,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_path_change_rounds(self, tui_path_change_rounds: usize) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_path_change_rounds,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_path_stability_window(self, tui_path_stability_window: Duration) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_path_stability_window,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_flow_sort_mode(self, tui_flow_sort_mode: FlowSortMode) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_flow_sort_mode,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_probe_countdown(self, tui_probe_countdown: bool) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_probe_countdown,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_auto_quit_stable_rounds(
+            self,
+            tui_auto_quit_stable_rounds: Option<usize>,
+        ) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_auto_quit_stable_rounds,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn tui_auto_quit_stable_threshold(
+            self,
+            tui_auto_quit_stable_threshold: Duration,
+        ) -> Self {
+            Self {
+                config: TrippyConfig {
+                    tui_auto_quit_stable_threshold,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn tui_theme(self, tui_theme: TuiTheme) -> Self {
             Self {
                 config: TrippyConfig {
@@ -2141,6 +4202,78 @@ mod tests {
             }
         }
 
+        pub fn webhook_url(self, webhook_url: Option<String>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    webhook_url,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn webhook_trigger(self, webhook_trigger: WebhookTrigger) -> Self {
+            Self {
+                config: TrippyConfig {
+                    webhook_trigger,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn webhook_fields(self, webhook_fields: Vec<WebhookField>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    webhook_fields,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn webhook_retries(self, webhook_retries: u8) -> Self {
+            Self {
+                config: TrippyConfig {
+                    webhook_retries,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn sqlite_file(self, sqlite_file: Option<String>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    sqlite_file,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn sqlite_retention(self, sqlite_retention: Option<Duration>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    sqlite_retention,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn prometheus_file(self, prometheus_file: Option<String>) -> Self {
+            Self {
+                config: TrippyConfig {
+                    prometheus_file,
+                    ..self.config
+                },
+            }
+        }
+
+        pub fn bell_trigger(self, bell_trigger: BellTrigger) -> Self {
+            Self {
+                config: TrippyConfig {
+                    bell_trigger,
+                    ..self.config
+                },
+            }
+        }
+
         pub fn geoip_mmdb_file(self, geoip_mmdb_file: Option<String>) -> Self {
             Self {
                 config: TrippyConfig {
@@ -45,6 +45,28 @@ pub struct TuiBindings {
     pub clear_selection: TuiKeyBinding,
     pub toggle_as_info: TuiKeyBinding,
     pub toggle_hop_details: TuiKeyBinding,
+    pub previous_column: TuiKeyBinding,
+    pub next_column: TuiKeyBinding,
+    pub narrow_column: TuiKeyBinding,
+    pub widen_column: TuiKeyBinding,
+    pub reset_column_width: TuiKeyBinding,
+    pub dismiss_warning: TuiKeyBinding,
+    pub contract_stats_window: TuiKeyBinding,
+    pub expand_stats_window: TuiKeyBinding,
+    pub select_worst_loss_hop: TuiKeyBinding,
+    pub select_worst_latency_hop: TuiKeyBinding,
+    pub toggle_as_colors: TuiKeyBinding,
+    pub toggle_summary_footer: TuiKeyBinding,
+    pub toggle_rtt_deltas: TuiKeyBinding,
+    pub edit_note: TuiKeyBinding,
+    pub toggle_trailing_hop: TuiKeyBinding,
+    pub save_image: TuiKeyBinding,
+    pub toggle_hop_paused: TuiKeyBinding,
+    pub toggle_trace_paused: TuiKeyBinding,
+    pub toggle_column_group: TuiKeyBinding,
+    pub toggle_flow_sort: TuiKeyBinding,
+    pub save_ansi: TuiKeyBinding,
+    pub toggle_hop_sort: TuiKeyBinding,
     pub quit: TuiKeyBinding,
 }
 
@@ -95,30 +117,77 @@ impl Default for TuiBindings {
             clear_selection: TuiKeyBinding::new(KeyCode::Esc),
             toggle_as_info: TuiKeyBinding::new(KeyCode::Char('z')),
             toggle_hop_details: TuiKeyBinding::new(KeyCode::Char('d')),
+            previous_column: TuiKeyBinding::new(KeyCode::Char('<')),
+            next_column: TuiKeyBinding::new(KeyCode::Char('>')),
+            narrow_column: TuiKeyBinding::new_with_modifier(KeyCode::Left, KeyModifiers::SHIFT),
+            widen_column: TuiKeyBinding::new_with_modifier(KeyCode::Right, KeyModifiers::SHIFT),
+            reset_column_width: TuiKeyBinding::new(KeyCode::Backspace),
+            dismiss_warning: TuiKeyBinding::new(KeyCode::Char('w')),
+            contract_stats_window: TuiKeyBinding::new(KeyCode::Char('(')),
+            expand_stats_window: TuiKeyBinding::new(KeyCode::Char(')')),
+            select_worst_loss_hop: TuiKeyBinding::new(KeyCode::Char('l')),
+            select_worst_latency_hop: TuiKeyBinding::new(KeyCode::Char('t')),
+            toggle_as_colors: TuiKeyBinding::new(KeyCode::Char('a')),
+            toggle_summary_footer: TuiKeyBinding::new(KeyCode::Char('o')),
+            toggle_rtt_deltas: TuiKeyBinding::new(KeyCode::Char('v')),
+            edit_note: TuiKeyBinding::new(KeyCode::Char('e')),
+            toggle_trailing_hop: TuiKeyBinding::new(KeyCode::Char('u')),
+            save_image: TuiKeyBinding::new(KeyCode::Char('g')),
+            toggle_hop_paused: TuiKeyBinding::new_with_modifier(
+                KeyCode::Char('p'),
+                KeyModifiers::CONTROL,
+            ),
+            toggle_trace_paused: TuiKeyBinding::new_with_modifier(
+                KeyCode::Char('p'),
+                KeyModifiers::SHIFT,
+            ),
+            toggle_column_group: TuiKeyBinding::new_with_modifier(
+                KeyCode::Char('c'),
+                KeyModifiers::SHIFT,
+            ),
+            toggle_flow_sort: TuiKeyBinding::new_with_modifier(
+                KeyCode::Char('f'),
+                KeyModifiers::SHIFT,
+            ),
+            save_ansi: TuiKeyBinding::new_with_modifier(KeyCode::Char('g'), KeyModifiers::SHIFT),
+            toggle_hop_sort: TuiKeyBinding::new_with_modifier(
+                KeyCode::Char('s'),
+                KeyModifiers::SHIFT,
+            ),
             quit: TuiKeyBinding::new(KeyCode::Char('q')),
         }
     }
 }
 
 impl TuiBindings {
-    /// Validate the bindings.
-    ///
-    /// Returns any duplicate bindings.
-    pub fn find_duplicates(&self) -> Vec<String> {
-        let (_, duplicates) = [
+    /// All bindings with the command they are bound to.
+    pub fn commands(&self) -> Vec<(TuiKeyBinding, TuiCommandItem)> {
+        vec![
             (self.toggle_help, TuiCommandItem::ToggleHelp),
             (self.toggle_help_alt, TuiCommandItem::ToggleHelpAlt),
             (self.toggle_settings, TuiCommandItem::ToggleSettings),
-            (self.toggle_settings_tui, TuiCommandItem::ToggleSettings),
-            (self.toggle_settings_trace, TuiCommandItem::ToggleSettings),
-            (self.toggle_settings_dns, TuiCommandItem::ToggleSettings),
-            (self.toggle_settings_geoip, TuiCommandItem::ToggleSettings),
+            (self.toggle_settings_tui, TuiCommandItem::ToggleSettingsTui),
+            (
+                self.toggle_settings_trace,
+                TuiCommandItem::ToggleSettingsTrace,
+            ),
+            (self.toggle_settings_dns, TuiCommandItem::ToggleSettingsDns),
+            (
+                self.toggle_settings_geoip,
+                TuiCommandItem::ToggleSettingsGeoip,
+            ),
             (
                 self.toggle_settings_bindings,
-                TuiCommandItem::ToggleSettings,
+                TuiCommandItem::ToggleSettingsBindings,
+            ),
+            (
+                self.toggle_settings_theme,
+                TuiCommandItem::ToggleSettingsTheme,
+            ),
+            (
+                self.toggle_settings_columns,
+                TuiCommandItem::ToggleSettingsColumns,
             ),
-            (self.toggle_settings_theme, TuiCommandItem::ToggleSettings),
-            (self.toggle_settings_columns, TuiCommandItem::ToggleSettings),
             (self.previous_hop, TuiCommandItem::PreviousHop),
             (self.next_hop, TuiCommandItem::NextHop),
             (self.previous_trace, TuiCommandItem::PreviousTrace),
@@ -147,10 +216,49 @@ impl TuiBindings {
             (self.clear_selection, TuiCommandItem::ClearSelection),
             (self.toggle_as_info, TuiCommandItem::ToggleASInfo),
             (self.toggle_hop_details, TuiCommandItem::ToggleHopDetails),
+            (self.previous_column, TuiCommandItem::PreviousColumn),
+            (self.next_column, TuiCommandItem::NextColumn),
+            (self.narrow_column, TuiCommandItem::NarrowColumn),
+            (self.widen_column, TuiCommandItem::WidenColumn),
+            (self.reset_column_width, TuiCommandItem::ResetColumnWidth),
+            (self.dismiss_warning, TuiCommandItem::DismissWarning),
+            (
+                self.contract_stats_window,
+                TuiCommandItem::ContractStatsWindow,
+            ),
+            (self.expand_stats_window, TuiCommandItem::ExpandStatsWindow),
+            (
+                self.select_worst_loss_hop,
+                TuiCommandItem::SelectWorstLossHop,
+            ),
+            (
+                self.select_worst_latency_hop,
+                TuiCommandItem::SelectWorstLatencyHop,
+            ),
+            (self.toggle_as_colors, TuiCommandItem::ToggleAsColors),
+            (
+                self.toggle_summary_footer,
+                TuiCommandItem::ToggleSummaryFooter,
+            ),
+            (self.toggle_rtt_deltas, TuiCommandItem::ToggleRttDeltas),
+            (self.edit_note, TuiCommandItem::EditNote),
+            (self.toggle_trailing_hop, TuiCommandItem::ToggleTrailingHop),
+            (self.save_image, TuiCommandItem::SaveImage),
+            (self.toggle_hop_paused, TuiCommandItem::ToggleHopPaused),
+            (self.toggle_trace_paused, TuiCommandItem::ToggleTracePaused),
+            (self.toggle_column_group, TuiCommandItem::ToggleColumnGroup),
+            (self.toggle_flow_sort, TuiCommandItem::ToggleFlowSort),
+            (self.save_ansi, TuiCommandItem::SaveAnsi),
+            (self.toggle_hop_sort, TuiCommandItem::ToggleHopSort),
             (self.quit, TuiCommandItem::Quit),
         ]
-        .iter()
-        .fold(
+    }
+
+    /// Validate the bindings.
+    ///
+    /// Returns any duplicate bindings.
+    pub fn find_duplicates(&self) -> Vec<String> {
+        let (_, duplicates) = self.commands().iter().fold(
             (HashMap::<TuiKeyBinding, TuiCommandItem>::new(), Vec::new()),
             |(mut all, mut dups), (binding, item)| {
                 if let Some(existing) = all.get(binding) {
@@ -315,6 +423,94 @@ impl From<(HashMap<TuiCommandItem, TuiKeyBinding>, ConfigBindings)> for TuiBindi
                 .get(&TuiCommandItem::ToggleHopDetails)
                 .or(cfg.toggle_hop_details.as_ref())
                 .unwrap_or(&Self::default().toggle_hop_details),
+            previous_column: *cmd_items
+                .get(&TuiCommandItem::PreviousColumn)
+                .or(cfg.previous_column.as_ref())
+                .unwrap_or(&Self::default().previous_column),
+            next_column: *cmd_items
+                .get(&TuiCommandItem::NextColumn)
+                .or(cfg.next_column.as_ref())
+                .unwrap_or(&Self::default().next_column),
+            narrow_column: *cmd_items
+                .get(&TuiCommandItem::NarrowColumn)
+                .or(cfg.narrow_column.as_ref())
+                .unwrap_or(&Self::default().narrow_column),
+            widen_column: *cmd_items
+                .get(&TuiCommandItem::WidenColumn)
+                .or(cfg.widen_column.as_ref())
+                .unwrap_or(&Self::default().widen_column),
+            reset_column_width: *cmd_items
+                .get(&TuiCommandItem::ResetColumnWidth)
+                .or(cfg.reset_column_width.as_ref())
+                .unwrap_or(&Self::default().reset_column_width),
+            dismiss_warning: *cmd_items
+                .get(&TuiCommandItem::DismissWarning)
+                .or(cfg.dismiss_warning.as_ref())
+                .unwrap_or(&Self::default().dismiss_warning),
+            contract_stats_window: *cmd_items
+                .get(&TuiCommandItem::ContractStatsWindow)
+                .or(cfg.contract_stats_window.as_ref())
+                .unwrap_or(&Self::default().contract_stats_window),
+            expand_stats_window: *cmd_items
+                .get(&TuiCommandItem::ExpandStatsWindow)
+                .or(cfg.expand_stats_window.as_ref())
+                .unwrap_or(&Self::default().expand_stats_window),
+            select_worst_loss_hop: *cmd_items
+                .get(&TuiCommandItem::SelectWorstLossHop)
+                .or(cfg.select_worst_loss_hop.as_ref())
+                .unwrap_or(&Self::default().select_worst_loss_hop),
+            select_worst_latency_hop: *cmd_items
+                .get(&TuiCommandItem::SelectWorstLatencyHop)
+                .or(cfg.select_worst_latency_hop.as_ref())
+                .unwrap_or(&Self::default().select_worst_latency_hop),
+            toggle_as_colors: *cmd_items
+                .get(&TuiCommandItem::ToggleAsColors)
+                .or(cfg.toggle_as_colors.as_ref())
+                .unwrap_or(&Self::default().toggle_as_colors),
+            toggle_summary_footer: *cmd_items
+                .get(&TuiCommandItem::ToggleSummaryFooter)
+                .or(cfg.toggle_summary_footer.as_ref())
+                .unwrap_or(&Self::default().toggle_summary_footer),
+            toggle_rtt_deltas: *cmd_items
+                .get(&TuiCommandItem::ToggleRttDeltas)
+                .or(cfg.toggle_rtt_deltas.as_ref())
+                .unwrap_or(&Self::default().toggle_rtt_deltas),
+            edit_note: *cmd_items
+                .get(&TuiCommandItem::EditNote)
+                .or(cfg.edit_note.as_ref())
+                .unwrap_or(&Self::default().edit_note),
+            toggle_trailing_hop: *cmd_items
+                .get(&TuiCommandItem::ToggleTrailingHop)
+                .or(cfg.toggle_trailing_hop.as_ref())
+                .unwrap_or(&Self::default().toggle_trailing_hop),
+            save_image: *cmd_items
+                .get(&TuiCommandItem::SaveImage)
+                .or(cfg.save_image.as_ref())
+                .unwrap_or(&Self::default().save_image),
+            toggle_hop_paused: *cmd_items
+                .get(&TuiCommandItem::ToggleHopPaused)
+                .or(cfg.toggle_hop_paused.as_ref())
+                .unwrap_or(&Self::default().toggle_hop_paused),
+            toggle_trace_paused: *cmd_items
+                .get(&TuiCommandItem::ToggleTracePaused)
+                .or(cfg.toggle_trace_paused.as_ref())
+                .unwrap_or(&Self::default().toggle_trace_paused),
+            toggle_column_group: *cmd_items
+                .get(&TuiCommandItem::ToggleColumnGroup)
+                .or(cfg.toggle_column_group.as_ref())
+                .unwrap_or(&Self::default().toggle_column_group),
+            toggle_flow_sort: *cmd_items
+                .get(&TuiCommandItem::ToggleFlowSort)
+                .or(cfg.toggle_flow_sort.as_ref())
+                .unwrap_or(&Self::default().toggle_flow_sort),
+            save_ansi: *cmd_items
+                .get(&TuiCommandItem::SaveAnsi)
+                .or(cfg.save_ansi.as_ref())
+                .unwrap_or(&Self::default().save_ansi),
+            toggle_hop_sort: *cmd_items
+                .get(&TuiCommandItem::ToggleHopSort)
+                .or(cfg.toggle_hop_sort.as_ref())
+                .unwrap_or(&Self::default().toggle_hop_sort),
             quit: *cmd_items
                 .get(&TuiCommandItem::Quit)
                 .or(cfg.quit.as_ref())
@@ -612,6 +808,50 @@ pub enum TuiCommandItem {
     ToggleASInfo,
     /// Toggle hop details.
     ToggleHopDetails,
+    /// Select the previous column.
+    PreviousColumn,
+    /// Select the next column.
+    NextColumn,
+    /// Narrow the selected column.
+    NarrowColumn,
+    /// Widen the selected column.
+    WidenColumn,
+    /// Reset the width of the selected column.
+    ResetColumnWidth,
+    /// Dismiss the active warning.
+    DismissWarning,
+    /// Contract the window of rounds the hop statistics are computed over.
+    ContractStatsWindow,
+    /// Expand the window of rounds the hop statistics are computed over.
+    ExpandStatsWindow,
+    /// Select the hop with the highest packet loss.
+    SelectWorstLossHop,
+    /// Select the hop with the highest average round-trip time.
+    SelectWorstLatencyHop,
+    /// Toggle tinting hops by autonomous system (AS).
+    ToggleAsColors,
+    /// Toggle the summary footer of the hops table.
+    ToggleSummaryFooter,
+    /// Toggle showing round-trip times with the increment over the previous hop.
+    ToggleRttDeltas,
+    /// Edit the note of the selected hop address.
+    EditNote,
+    /// Toggle showing the unresponsive trailing hop.
+    ToggleTrailingHop,
+    /// Save the current screen as a PNG image.
+    SaveImage,
+    /// Toggle pausing probing of the selected hop.
+    ToggleHopPaused,
+    /// Toggle pausing probing of all hops.
+    ToggleTracePaused,
+    /// Toggle the group of the selected column on or off.
+    ToggleColumnGroup,
+    /// Cycle the order in which flows are sorted.
+    ToggleFlowSort,
+    /// Save the current screen as text with ANSI escape codes.
+    SaveAnsi,
+    /// Cycle the sort of the hops table by the selected column.
+    ToggleHopSort,
     /// Quit the application.
     Quit,
 }
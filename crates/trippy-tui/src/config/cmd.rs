@@ -1,9 +1,12 @@
 use crate::config::binding::TuiCommandItem;
 use crate::config::theme::TuiThemeItem;
 use crate::config::{
-    AddressFamilyConfig, AddressMode, AsMode, DnsResolveMethodConfig, GeoIpMode, IcmpExtensionMode,
-    LogFormat, LogSpanEvents, Mode, MultipathStrategyConfig, ProtocolConfig, TuiColor,
-    TuiKeyBinding,
+    AddrAggregationMode, AddressFamilyConfig, AddressMode, AsMode, AverageMode, BellTrigger,
+    DnsResolveMethodConfig, FlowSortMode, GeoIpMode, GlyphSet, HostTruncation, IcmpExtensionMode,
+    LocalTargetMode, LogFormat, LogSpanEvents, Mode, MultipathStrategyConfig,
+    PayloadStrategyConfig, ProtocolConfig, RttHeatMode, StatusMode, StdDevMode, StdDevScope,
+    TargetTtlMode, TimeExceededRttMode, TrailingHopMode, TuiColor, TuiKeyBinding,
+    UnresponsiveHopMode, WebhookField, WebhookTrigger,
 };
 use anyhow::anyhow;
 use clap::builder::Styles;
@@ -19,13 +22,17 @@ use std::time::Duration;
 #[command(name = "trip", author, version, about, long_about = None, arg_required_else_help(true), styles=Styles::styled())]
 pub struct Args {
     /// A space delimited list of hostnames and IPs to trace
-    #[arg(required_unless_present_any(["print_tui_theme_items", "print_tui_binding_commands", "print_config_template", "generate", "generate_man"]))]
+    #[arg(required_unless_present_any(["print_tui_theme_items", "print_tui_binding_commands", "print_config_template", "generate", "generate_man", "prompt"]))]
     pub targets: Vec<String>,
 
     /// Config file
     #[arg(value_enum, short = 'c', long, value_hint = clap::ValueHint::FilePath)]
     pub config_file: Option<String>,
 
+    /// Prompt for the target and tracing options if no target is given
+    #[arg(long)]
+    pub prompt: bool,
+
     /// Output mode [default: tui]
     #[arg(value_enum, short = 'm', long)]
     pub mode: Option<Mode>,
@@ -103,6 +110,10 @@ pub struct Args {
     #[arg(short = 'I', long)]
     pub interface: Option<String>,
 
+    /// How to handle a target which is an address of the local host [default: warn]
+    #[arg(value_enum, long)]
+    pub local_target_mode: Option<LocalTargetMode>,
+
     /// The minimum duration of every round [default: 1s]
     #[arg(short = 'i', long, value_parser = parse_duration)]
     pub min_round_duration: Option<Duration>,
@@ -128,6 +139,14 @@ pub struct Args {
     #[arg(short = 'U', long)]
     pub max_inflight: Option<u8>,
 
+    /// The maximum number of ICMP or UDP probes to send in a single batch [default: 1]
+    #[arg(long)]
+    pub send_batch_size: Option<u8>,
+
+    /// The maximum number of packets to receive in a single batch [default: 1]
+    #[arg(long)]
+    pub recv_batch_size: Option<u8>,
+
     /// The TTL to start from [default: 1]
     #[arg(short = 'f', long)]
     pub first_ttl: Option<u8>,
@@ -140,14 +159,28 @@ pub struct Args {
     #[arg(long)]
     pub packet_size: Option<u16>,
 
+    /// The size of the ICMP echo data, from which the packet size is derived
+    ///
+    /// The packet size is the sum of the IP header, the ICMP header and the
+    /// echo data and so depends on the address family of the target.
+    #[arg(long, conflicts_with = "packet_size")]
+    pub icmp_data_length: Option<u16>,
+
     /// The repeating pattern in the payload of the ICMP packet [default: 0]
     #[arg(long)]
     pub payload_pattern: Option<u8>,
 
-    /// The TOS (i.e. DSCP+ECN) IP header value (TCP and UDP only) [default: 0]
+    /// The TOS (i.e. DSCP+ECN) IP header value, or IPv6 traffic class [default: 0]
     #[arg(short = 'Q', long)]
     pub tos: Option<u8>,
 
+    /// The IPv6 flow label [default: 0]
+    ///
+    /// Only the lower 20 bits are valid.  A flow label of zero leaves the flow label
+    /// to the kernel.  Ignored for IPv4 targets.  Only supported on Linux.
+    #[arg(long)]
+    pub flow_label: Option<u32>,
+
     /// Parse ICMP extensions
     #[arg(short = 'e', long)]
     pub icmp_extensions: bool,
@@ -156,6 +189,97 @@ pub struct Args {
     #[arg(long, value_parser = parse_duration)]
     pub read_timeout: Option<Duration>,
 
+    /// The minimum interval between sending probes [default: none]
+    #[arg(long, value_parser = parse_duration)]
+    pub min_probe_interval: Option<Duration>,
+
+    /// The maximum random delay before sending each probe [default: none]
+    #[arg(long, value_parser = parse_duration)]
+    pub send_jitter: Option<Duration>,
+
+    /// The duration after which a probe without a reply is considered lost [default: none]
+    #[arg(long, value_parser = parse_duration)]
+    pub loss_timeout: Option<Duration>,
+
+    /// Ignore the identifier of ICMP responses and correlate by sequence only
+    ///
+    /// If --stamped-payload is also set then the identifier read from the
+    /// stamp in the quoted original packet is still checked.
+    ///
+    /// Warning: responses for probes sent by other tracers may be misattributed.
+    #[arg(long)]
+    pub ignore_identifier: bool,
+
+    /// Discard the first response from the first hop
+    ///
+    /// The first probe may be delayed by ARP or neighbour discovery for the first
+    /// hop, which skews the statistics of the hop.
+    #[arg(long)]
+    pub discard_first_hop_warmup: bool,
+
+    /// Accept responses received after the round of the probe has completed
+    ///
+    /// Warning: late responses update the totals and round-trip times of a hop but
+    /// not the history of the round in which the probe was sent.
+    #[arg(long)]
+    pub accept_late_responses: bool,
+
+    /// Adapt the read timeout to the observed round-trip time
+    ///
+    /// The read timeout is set to the smoothed round-trip time plus four times its
+    /// variation, as for the TCP retransmission timeout, once a response has been
+    /// received.
+    #[arg(long)]
+    pub adaptive_read_timeout: bool,
+
+    /// The maximum number of consecutive unresponsive hops to show beyond the last responsive hop
+    /// [default: all]
+    ///
+    /// If set, any further unresponsive hops are not shown.  Use `--curtail-unresponsive` to also
+    /// stop probing beyond them.
+    #[arg(long)]
+    pub max_unresponsive_hops: Option<u8>,
+
+    /// Stop probing beyond the maximum number of consecutive unresponsive hops
+    #[arg(long)]
+    pub curtail_unresponsive: bool,
+
+    /// The number of attempts to retry the setup of the trace on a transient failure [default: 0]
+    ///
+    /// Transient failures, such as the network being unreachable or an interface
+    /// being down, are retried with backoff.  Fatal failures are never retried.
+    #[arg(long)]
+    pub setup_retry_attempts: Option<usize>,
+
+    /// The initial backoff between attempts to retry the setup of the trace [default: 1s]
+    ///
+    /// The backoff is doubled after each attempt, up to a maximum of one minute.
+    #[arg(long, value_parser = parse_duration)]
+    pub setup_retry_backoff: Option<Duration>,
+
+    /// Use kernel timestamps for received probe responses [default: false]
+    ///
+    /// Only the receive time is taken from the kernel, the send time is always
+    /// measured in user space.  Falls back to user space timestamps if not
+    /// supported on the platform (i.e. on Windows).
+    #[arg(long)]
+    pub kernel_recv_timestamps: bool,
+
+    /// Embed a stamp in the payload of icmp probes [default: false]
+    ///
+    /// The stamp is used to correlate responses on paths which rewrite the
+    /// icmp identifier or sequence number and requires a packet size at least
+    /// 16 bytes larger than the minimum.
+    #[arg(long)]
+    pub stamped_payload: bool,
+
+    /// The strategy for the payload of icmp probes [default: pattern]
+    ///
+    /// The constant-checksum strategy adjusts the last 16-bit aligned word of the
+    /// payload such that all probes have the same icmp checksum.
+    #[arg(value_enum, long)]
+    pub payload_strategy: Option<PayloadStrategyConfig>,
+
     /// How to perform DNS queries [default: system]
     #[arg(value_enum, short = 'r', long)]
     pub dns_resolve_method: Option<DnsResolveMethodConfig>,
@@ -192,10 +316,55 @@ pub struct Args {
     #[arg(value_enum, long)]
     pub tui_as_mode: Option<AsMode>,
 
+    /// Tint hops by autonomous system (AS) [default: false]
+    #[arg(long)]
+    pub tui_as_colors: bool,
+
+    /// Show a summary footer of path-wide aggregates in the hops table [default: false]
+    #[arg(long)]
+    pub tui_summary_footer: bool,
+
+    /// Show round-trip times with the increment over the previous hop [default: false]
+    #[arg(long)]
+    pub tui_rtt_deltas: bool,
+
+    /// Show round-trip times relative to the first responding hop [default: false]
+    #[arg(long)]
+    pub tui_rtt_baseline: bool,
+
+    /// The round-trip time at or above which round-trip times are shown in seconds [default: none]
+    ///
+    /// All round-trip times of a hop are shown in the same unit.
+    #[arg(long, value_parser = parse_duration)]
+    pub tui_rtt_compact_threshold: Option<Duration>,
+
+    /// How to color the Last round-trip time of hops [default: off]
+    #[arg(value_enum, long)]
+    pub tui_rtt_heat_mode: Option<RttHeatMode>,
+
+    /// The round-trip time at the top of the fixed rtt heat gradient [default: 250ms]
+    #[arg(long, value_parser = parse_duration)]
+    pub tui_rtt_heat_max: Option<Duration>,
+
     /// Custom columns to be displayed in the TUI hops table [default: holsravbwdt]
     #[arg(long)]
     pub tui_custom_columns: Option<String>,
 
+    /// Named groups of columns which are toggled on or off together [default:
+    /// rtt=avbwd,counters=lsrf,identity=hop]
+    ///
+    /// Each group is given as a name and the columns of the group, using the same characters as
+    /// `--tui-custom-columns`, such as `rtt=avbwd`.
+    #[arg(long)]
+    pub tui_column_groups: Option<String>,
+
+    /// The number of decimal places to show for individual round-trip time columns [default: 1]
+    ///
+    /// Each column is given as the column character, as for `--tui-custom-columns`, and the
+    /// number of decimal places, such as `a=0,v=2`.  Only round-trip time columns may be given.
+    #[arg(long)]
+    pub tui_rtt_precision: Option<String>,
+
     /// How to render ICMP extensions [default: off]
     #[arg(value_enum, long)]
     pub tui_icmp_extension_mode: Option<IcmpExtensionMode>,
@@ -204,10 +373,105 @@ pub struct Args {
     #[arg(value_enum, long)]
     pub tui_geoip_mode: Option<GeoIpMode>,
 
+    /// How to render hops which have not responded [default: no-response]
+    #[arg(value_enum, long)]
+    pub tui_unresponsive_hop_mode: Option<UnresponsiveHopMode>,
+
+    /// How to render the unresponsive trailing hop whilst the target has not been found [default: waiting]
+    #[arg(value_enum, long)]
+    pub tui_trailing_hop_mode: Option<TrailingHopMode>,
+
+    /// How to render round-trip times derived from `TimeExceeded` responses [default: include]
+    #[arg(value_enum, long)]
+    pub tui_time_exceeded_rtt_mode: Option<TimeExceededRttMode>,
+
+    /// How to compute the average round-trip time of a hop [default: mean]
+    #[arg(value_enum, long)]
+    pub tui_average_mode: Option<AverageMode>,
+
+    /// The weight, as a percentage, of each round-trip time in the ewma average [default: 10]
+    #[arg(long)]
+    pub tui_ewma_weight: Option<u8>,
+
+    /// The weight, as a percentage, of each probe in the recent loss ewma [default: 10]
+    #[arg(long)]
+    pub tui_loss_ewma_weight: Option<u8>,
+
+    /// The increase in the reverse path length at which a tunnel is suspected [default: 3]
+    #[arg(long)]
+    pub tui_tunnel_ttl_jump: Option<u8>,
+
+    /// What to show in the status column [default: health]
+    #[arg(value_enum, long)]
+    pub tui_status_mode: Option<StatusMode>,
+
+    /// How to show a hop which is answered by more than one address [default: combined]
+    #[arg(value_enum, long)]
+    pub tui_addr_aggregation: Option<AddrAggregationMode>,
+
+    /// Which time-to-live values at which the target replied to show [default: lowest]
+    #[arg(value_enum, long)]
+    pub tui_target_ttl_mode: Option<TargetTtlMode>,
+
+    /// Which side to truncate hostnames which do not fit in the Host column [default: right]
+    #[arg(value_enum, long)]
+    pub tui_host_truncation: Option<HostTruncation>,
+
+    /// The set of glyphs to use for symbols in the Tui [default: auto]
+    #[arg(value_enum, long)]
+    pub tui_glyph_set: Option<GlyphSet>,
+
     /// The maximum number of addresses to show per hop [default: auto]
     #[arg(short = 'M', long)]
     pub tui_max_addrs: Option<u8>,
 
+    /// The maximum number of hops to display in the hops table at once [default: all]
+    ///
+    /// Hops beyond the maximum are reached by scrolling.  This does not change the maximum
+    /// time-to-live probed.
+    #[arg(long)]
+    pub tui_max_display_hops: Option<u8>,
+
+    /// The number of recent rounds to compute the hop statistics over [default: all]
+    #[arg(long)]
+    pub tui_stats_window: Option<usize>,
+
+    /// How to compute the standard deviation of round-trip times [default: sample]
+    #[arg(value_enum, long)]
+    pub tui_stddev_mode: Option<StdDevMode>,
+
+    /// The rounds over which to compute the standard deviation of round-trip times [default: window]
+    #[arg(value_enum, long)]
+    pub tui_stddev_scope: Option<StdDevScope>,
+
+    /// The number of rounds to trace before stopping, keeping the Tui open [default: unlimited]
+    #[arg(long)]
+    pub tui_max_rounds: Option<usize>,
+
+    /// The number of rounds to flag path length changes for [default: 5]
+    #[arg(long)]
+    pub tui_path_change_rounds: Option<usize>,
+
+    /// The duration for which a change to the path is considered recent [default: 1m]
+    #[arg(long, value_parser = parse_duration)]
+    pub tui_path_stability_window: Option<Duration>,
+
+    /// How to sort the flows [default: rounds]
+    #[arg(value_enum, long)]
+    pub tui_flow_sort_mode: Option<FlowSortMode>,
+
+    /// Show how long the selected hop has been awaiting a reply in the hop details [default: false]
+    #[arg(long)]
+    pub tui_probe_countdown: bool,
+
+    /// The number of rounds the trace must be stable for before the Tui quits [default: never]
+    #[arg(long)]
+    pub tui_auto_quit_stable_rounds: Option<usize>,
+
+    /// The maximum round-trip time standard deviation of a stable hop [default: 5ms]
+    #[arg(long, value_parser = parse_duration)]
+    pub tui_auto_quit_stable_threshold: Option<Duration>,
+
     /// Preserve the screen on exit [default: false]
     #[arg(long)]
     pub tui_preserve_screen: bool,
@@ -220,10 +484,29 @@ pub struct Args {
     #[arg(long)]
     pub tui_privacy_max_ttl: Option<u8>,
 
+    /// The number of rounds without a reply from the first hop before warning [default: 5]
+    ///
+    /// Use a zero value to disable the warning.
+    #[arg(long)]
+    pub tui_first_hop_warning_rounds: Option<usize>,
+
     /// The locale to use for the TUI [default: auto]
     #[arg(long)]
     pub tui_locale: Option<String>,
 
+    /// The file to persist hop address notes to [default: none]
+    ///
+    /// Notes may be attached to hop addresses whilst tracing and are shown in the
+    /// hop details.  If not set, notes are not persisted.
+    #[arg(long, value_hint = clap::ValueHint::FilePath)]
+    pub tui_notes_file: Option<String>,
+
+    /// The TUI provider classes for AS numbers [asn=class,asn=class,..]
+    ///
+    /// Overrides the bundled classes, for example `13335=cdn,64512=lab`.
+    #[arg(long, value_delimiter(','), value_parser = parse_tui_provider_class_value)]
+    pub tui_provider_classes: Vec<(u32, String)>,
+
     /// The TUI theme colors [item=color,item=color,..]
     #[arg(long, value_delimiter(','), value_parser = parse_tui_theme_color_value)]
     pub tui_theme_colors: Vec<(TuiThemeItem, TuiColor)>,
@@ -240,10 +523,46 @@ pub struct Args {
     #[arg(long)]
     pub print_tui_binding_commands: bool,
 
+    /// Render the hops of a fixture file in the TUI rather than tracing
+    #[arg(long, hide = true, value_hint = clap::ValueHint::FilePath)]
+    pub tui_debug_fixture: Option<String>,
+
     /// The number of report cycles to run [default: 10]
     #[arg(short = 'C', long)]
     pub report_cycles: Option<usize>,
 
+    /// The URL to post a JSON summary of the trace to [default: none]
+    #[arg(long, value_hint = clap::ValueHint::Url)]
+    pub webhook_url: Option<String>,
+
+    /// When to post to the webhook [default: completion]
+    #[arg(value_enum, long)]
+    pub webhook_trigger: Option<WebhookTrigger>,
+
+    /// The fields to include in the webhook payload [default: target,round,reachable,hops]
+    #[arg(value_enum, long, value_delimiter(','))]
+    pub webhook_fields: Vec<WebhookField>,
+
+    /// The maximum number of times, at most 10, to retry a failed webhook post [default: 3]
+    #[arg(long)]
+    pub webhook_retries: Option<u8>,
+
+    /// The SQLite database file to record the result of every round to [default: none]
+    #[arg(long, value_hint = clap::ValueHint::FilePath)]
+    pub sqlite_file: Option<String>,
+
+    /// The duration for which samples are retained in the SQLite database [default: forever]
+    #[arg(long, value_parser = parse_duration)]
+    pub sqlite_retention: Option<Duration>,
+
+    /// The Prometheus textfile to write the metrics of every hop to [default: none]
+    #[arg(long, value_hint = clap::ValueHint::FilePath)]
+    pub prometheus_file: Option<String>,
+
+    /// When to emit a terminal bell [default: never]
+    #[arg(value_enum, long)]
+    pub bell_trigger: Option<BellTrigger>,
+
     /// The supported MaxMind or IPinfo GeoIp mmdb file
     #[arg(short = 'G', long, value_hint = clap::ValueHint::FilePath)]
     pub geoip_mmdb_file: Option<String>,
@@ -286,6 +605,14 @@ fn parse_tui_theme_color_value(value: &str) -> anyhow::Result<(TuiThemeItem, Tui
     Ok((item, color))
 }
 
+fn parse_tui_provider_class_value(value: &str) -> anyhow::Result<(u32, String)> {
+    let pos = value
+        .find('=')
+        .ok_or_else(|| anyhow!("invalid provider class value: expected format `asn=class`"))?;
+    let asn = super::parse_asn(&value[..pos])?;
+    Ok((asn, value[pos + 1..].to_string()))
+}
+
 fn parse_tui_binding_value(value: &str) -> anyhow::Result<(TuiCommandItem, TuiKeyBinding)> {
     let pos = value
         .find('=')
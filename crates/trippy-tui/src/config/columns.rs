@@ -46,6 +46,100 @@ impl TuiColumns {
     }
 }
 
+/// Named groups of columns which are toggled on or off together.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TuiColumnGroups(pub Vec<TuiColumnGroup>);
+
+/// A named group of columns.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TuiColumnGroup {
+    pub name: String,
+    pub columns: TuiColumns,
+}
+
+impl TryFrom<&str> for TuiColumnGroups {
+    type Error = anyhow::Error;
+
+    /// Parse a comma separated list of groups, such as `rtt=avbwd,counters=lsrf`.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(Self(
+            value
+                .split(',')
+                .filter(|group| !group.trim().is_empty())
+                .map(TuiColumnGroup::try_from)
+                .collect::<Result<Vec<_>, Self::Error>>()?,
+        ))
+    }
+}
+
+impl TryFrom<&str> for TuiColumnGroup {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let (name, columns) = value
+            .split_once('=')
+            .ok_or_else(|| anyhow!("invalid column group: {value} (expected name=columns)"))?;
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(anyhow!("invalid column group: {value} (missing name)"));
+        }
+        Ok(Self {
+            name: name.to_string(),
+            columns: TuiColumns::try_from(columns.trim())?,
+        })
+    }
+}
+
+impl Default for TuiColumnGroups {
+    fn default() -> Self {
+        Self::try_from(super::constants::DEFAULT_TUI_COLUMN_GROUPS).expect("column groups")
+    }
+}
+
+/// The number of decimal places to show for round-trip time columns.
+///
+/// Any round-trip time column which is not listed uses the default number of decimal places.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct TuiRttPrecision(pub Vec<(TuiColumn, u8)>);
+
+impl TryFrom<&str> for TuiRttPrecision {
+    type Error = anyhow::Error;
+
+    /// Parse a comma separated list of columns and decimal places, such as `a=0,v=2`.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value
+            .split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .map(|entry| {
+                let (column, places) = entry.split_once('=').ok_or_else(|| {
+                    anyhow!("invalid rtt precision: {entry} (expected column=places)")
+                })?;
+                let column = match column.trim().chars().exactly_one() {
+                    Ok(c) => TuiColumn::try_from(c)?,
+                    Err(_) => return Err(anyhow!("invalid rtt precision column: {column}")),
+                };
+                if !column.is_rtt() {
+                    return Err(anyhow!(
+                        "invalid rtt precision column: {column} (not a round-trip time column)"
+                    ));
+                }
+                let places = places
+                    .trim()
+                    .parse::<u8>()
+                    .map_err(|_| anyhow!("invalid rtt precision places: {places}"))?;
+                if places > super::constants::MAX_TUI_RTT_PRECISION {
+                    return Err(anyhow!(
+                        "invalid rtt precision places: {places} (must be at most {})",
+                        super::constants::MAX_TUI_RTT_PRECISION
+                    ));
+                }
+                Ok((column, places))
+            })
+            .collect::<Result<Vec<_>, Self::Error>>()
+            .map(Self)
+    }
+}
+
 /// A TUI hops table column.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum TuiColumn {
@@ -93,6 +187,42 @@ pub enum TuiColumn {
     LastNatStatus,
     /// The number of probes that failed for a hop.
     Failed,
+    /// The provider class of the AS for a hop.
+    Provider,
+    /// The estimated path asymmetry for a hop.
+    Asymmetry,
+    /// The protocol of the probes for a hop.
+    Protocol,
+    /// Whether a hop is likely to be rate limiting ICMP.
+    RateLimit,
+    /// The exponentially weighted % of recent packets lost for a hop.
+    RecentLoss,
+    /// Whether a hop is likely to be the ingress or egress of a tunnel.
+    Tunnel,
+    /// The MPLS labels of the last response for a hop.
+    Mpls,
+    /// The AS number of a hop.
+    Asn,
+    /// The `GeoIp` location of a hop.
+    GeoLoc,
+}
+
+impl TuiColumn {
+    /// Whether the column shows a round-trip time, or a statistic derived from round-trip times.
+    pub const fn is_rtt(self) -> bool {
+        matches!(
+            self,
+            Self::Last
+                | Self::Average
+                | Self::Best
+                | Self::Worst
+                | Self::StdDev
+                | Self::Jitter
+                | Self::Javg
+                | Self::Jmax
+                | Self::Jinta
+        )
+    }
 }
 
 impl TryFrom<char> for TuiColumn {
@@ -122,6 +252,15 @@ impl TryFrom<char> for TuiColumn {
             'C' => Ok(Self::LastIcmpPacketCode),
             'N' => Ok(Self::LastNatStatus),
             'f' => Ok(Self::Failed),
+            'p' => Ok(Self::Provider),
+            'y' => Ok(Self::Asymmetry),
+            'e' => Ok(Self::Protocol),
+            'R' => Ok(Self::RateLimit),
+            'L' => Ok(Self::RecentLoss),
+            'U' => Ok(Self::Tunnel),
+            'M' => Ok(Self::Mpls),
+            'n' => Ok(Self::Asn),
+            'G' => Ok(Self::GeoLoc),
             c => Err(anyhow!(format!("unknown column code: {c}"))),
         }
     }
@@ -152,6 +291,15 @@ impl Display for TuiColumn {
             Self::LastIcmpPacketCode => write!(f, "C"),
             Self::LastNatStatus => write!(f, "N"),
             Self::Failed => write!(f, "f"),
+            Self::Provider => write!(f, "p"),
+            Self::Asymmetry => write!(f, "y"),
+            Self::Protocol => write!(f, "e"),
+            Self::RateLimit => write!(f, "R"),
+            Self::RecentLoss => write!(f, "L"),
+            Self::Tunnel => write!(f, "U"),
+            Self::Mpls => write!(f, "M"),
+            Self::Asn => write!(f, "n"),
+            Self::GeoLoc => write!(f, "G"),
         }
     }
 }
@@ -236,6 +384,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_try_from_str_for_tui_column_groups() {
+        let groups = TuiColumnGroups::try_from("rtt=avb, counters = ls").unwrap();
+        assert_eq!(
+            groups,
+            TuiColumnGroups(vec![
+                TuiColumnGroup {
+                    name: String::from("rtt"),
+                    columns: TuiColumns(
+                        vec![TuiColumn::Last, TuiColumn::Average, TuiColumn::Best,]
+                    ),
+                },
+                TuiColumnGroup {
+                    name: String::from("counters"),
+                    columns: TuiColumns(vec![TuiColumn::LossPct, TuiColumn::Sent]),
+                },
+            ])
+        );
+        assert_eq!(
+            TuiColumnGroups(vec![]),
+            TuiColumnGroups::try_from("").unwrap()
+        );
+    }
+
+    #[test_case("rtt" ; "missing columns")]
+    #[test_case("=avb" ; "missing name")]
+    #[test_case("rtt=avk" ; "invalid column")]
+    fn test_try_invalid_str_for_tui_column_groups(value: &str) {
+        assert!(TuiColumnGroups::try_from(value).is_err());
+    }
+
+    #[test]
+    fn test_try_from_str_for_tui_rtt_precision() {
+        assert_eq!(
+            TuiRttPrecision(vec![(TuiColumn::Last, 0), (TuiColumn::Average, 2)]),
+            TuiRttPrecision::try_from("a=0, v=2").unwrap()
+        );
+        assert_eq!(
+            TuiRttPrecision(vec![]),
+            TuiRttPrecision::try_from("").unwrap()
+        );
+    }
+
+    #[test_case("a" ; "missing places")]
+    #[test_case("av=1" ; "several columns")]
+    #[test_case("k=1" ; "invalid column")]
+    #[test_case("s=1" ; "not rtt column")]
+    #[test_case("a=x" ; "invalid places")]
+    #[test_case("a=5" ; "too many places")]
+    fn test_try_invalid_str_for_tui_rtt_precision(value: &str) {
+        assert!(TuiRttPrecision::try_from(value).is_err());
+    }
+
     #[test]
     fn test_find_duplicates_for_tui_columns() {
         let columns_with_duplicates = TuiColumns(vec![
@@ -1,6 +1,9 @@
 use crate::config::{
-    AddressFamilyConfig, AddressMode, AsMode, DnsResolveMethodConfig, GeoIpMode, IcmpExtensionMode,
-    LogFormat, LogSpanEvents, Mode,
+    AddrAggregationMode, AddressFamilyConfig, AddressMode, AsMode, AverageMode, BellTrigger,
+    DnsResolveMethodConfig, FlowSortMode, GeoIpMode, GlyphSet, HostTruncation, IcmpExtensionMode,
+    LocalTargetMode, LogFormat, LogSpanEvents, Mode, RttHeatMode, StatusMode, StdDevMode,
+    StdDevScope, TargetTtlMode, TimeExceededRttMode, TrailingHopMode, UnresponsiveHopMode,
+    WebhookField, WebhookTrigger,
 };
 use std::time::Duration;
 
@@ -25,18 +28,114 @@ pub const DEFAULT_TUI_PRESERVE_SCREEN: bool = false;
 /// The default value for `tui-as-mode`.
 pub const DEFAULT_TUI_AS_MODE: AsMode = AsMode::Asn;
 
+/// The default value for `tui-as-colors`.
+pub const DEFAULT_TUI_AS_COLORS: bool = false;
+
+/// The default value for `tui-summary-footer`.
+pub const DEFAULT_TUI_SUMMARY_FOOTER: bool = false;
+
+/// The default value for `tui-rtt-deltas`.
+pub const DEFAULT_TUI_RTT_DELTAS: bool = false;
+
+/// The default value for `tui-rtt-baseline`.
+pub const DEFAULT_TUI_RTT_BASELINE: bool = false;
+
 /// The default value for `tui-custom-columns`.
 pub const DEFAULT_CUSTOM_COLUMNS: &str = "holsravbwdt";
 
+/// The default value for `tui-column-groups`.
+pub const DEFAULT_TUI_COLUMN_GROUPS: &str = "rtt=avbwd,counters=lsrf,identity=hop";
+
+/// The default value for `tui-rtt-precision`.
+pub const DEFAULT_TUI_RTT_PRECISION: &str = "";
+
 /// The default value for `tui-icmp-extension-mode`.
 pub const DEFAULT_TUI_ICMP_EXTENSION_MODE: IcmpExtensionMode = IcmpExtensionMode::Off;
 
 /// The default value for `tui-geoip-mode`.
 pub const DEFAULT_TUI_GEOIP_MODE: GeoIpMode = GeoIpMode::Off;
 
+/// The default value for `tui-unresponsive-hop-mode`.
+pub const DEFAULT_TUI_UNRESPONSIVE_HOP_MODE: UnresponsiveHopMode = UnresponsiveHopMode::NoResponse;
+
+/// The default value for `tui-trailing-hop-mode`.
+pub const DEFAULT_TUI_TRAILING_HOP_MODE: TrailingHopMode = TrailingHopMode::Waiting;
+
+/// The default value for `tui-time-exceeded-rtt-mode`.
+pub const DEFAULT_TUI_TIME_EXCEEDED_RTT_MODE: TimeExceededRttMode = TimeExceededRttMode::Include;
+
+/// The default value for `tui-average-mode`.
+pub const DEFAULT_TUI_AVERAGE_MODE: AverageMode = AverageMode::Mean;
+
+/// The default value for `tui-ewma-weight`.
+pub const DEFAULT_TUI_EWMA_WEIGHT: u8 = 10;
+
+/// The default value for `tui-loss-ewma-weight`.
+pub const DEFAULT_TUI_LOSS_EWMA_WEIGHT: u8 = 10;
+
+/// The default value for `tui-tunnel-ttl-jump`.
+pub const DEFAULT_TUI_TUNNEL_TTL_JUMP: u8 = 3;
+
+/// The default value for `tui-status-mode`.
+pub const DEFAULT_TUI_STATUS_MODE: StatusMode = StatusMode::Health;
+
+/// The default value for `tui-addr-aggregation`.
+pub const DEFAULT_TUI_ADDR_AGGREGATION: AddrAggregationMode = AddrAggregationMode::Combined;
+
+/// The default value for `tui-rtt-heat-mode`.
+pub const DEFAULT_TUI_RTT_HEAT_MODE: RttHeatMode = RttHeatMode::Off;
+
+/// The default value for `tui-rtt-heat-max`.
+pub const DEFAULT_TUI_RTT_HEAT_MAX: Duration = Duration::from_millis(250);
+
+/// The default value for `tui-target-ttl-mode`.
+pub const DEFAULT_TUI_TARGET_TTL_MODE: TargetTtlMode = TargetTtlMode::Lowest;
+
+/// The default value for `tui-host-truncation`.
+pub const DEFAULT_TUI_HOST_TRUNCATION: HostTruncation = HostTruncation::Right;
+
 /// The default value for `tui-max-addrs`.
 pub const DEFAULT_TUI_MAX_ADDRS: u8 = 0;
 
+/// The default value for `tui-glyph-set`.
+pub const DEFAULT_TUI_GLYPH_SET: GlyphSet = GlyphSet::Auto;
+
+/// The default value for `tui-max-display-hops`.
+pub const DEFAULT_TUI_MAX_DISPLAY_HOPS: u8 = 0;
+
+/// The default value for `max-unresponsive-hops`.
+pub const DEFAULT_MAX_UNRESPONSIVE_HOPS: u8 = 0;
+
+/// The default value for `tui-stats-window`.
+pub const DEFAULT_TUI_STATS_WINDOW: usize = 0;
+
+/// The default value for `tui-stddev-mode`.
+pub const DEFAULT_TUI_STDDEV_MODE: StdDevMode = StdDevMode::Sample;
+
+/// The default value for `tui-stddev-scope`.
+pub const DEFAULT_TUI_STDDEV_SCOPE: StdDevScope = StdDevScope::Window;
+
+/// The default value for `tui-max-rounds`.
+pub const DEFAULT_TUI_MAX_ROUNDS: usize = 0;
+
+/// The default value for `tui-path-change-rounds`.
+pub const DEFAULT_TUI_PATH_CHANGE_ROUNDS: usize = 5;
+
+/// The default value for `tui-path-stability-window`.
+pub const DEFAULT_TUI_PATH_STABILITY_WINDOW: Duration = Duration::from_secs(60);
+
+/// The default value for `tui-flow-sort-mode`.
+pub const DEFAULT_TUI_FLOW_SORT_MODE: FlowSortMode = FlowSortMode::Rounds;
+
+/// The default value for `tui-probe-countdown`.
+pub const DEFAULT_TUI_PROBE_COUNTDOWN: bool = false;
+
+/// The default value for `tui-auto-quit-stable-rounds`.
+pub const DEFAULT_TUI_AUTO_QUIT_STABLE_ROUNDS: usize = 0;
+
+/// The default value for `tui-auto-quit-stable-threshold`.
+pub const DEFAULT_TUI_AUTO_QUIT_STABLE_THRESHOLD: Duration = Duration::from_millis(5);
+
 /// The default value for `tui-address-mode`.
 pub const DEFAULT_TUI_ADDRESS_MODE: AddressMode = AddressMode::Host;
 
@@ -46,6 +145,9 @@ pub const DEFAULT_TUI_REFRESH_RATE: Duration = Duration::from_millis(100);
 /// The default value for `tui-privacy-max-ttl`.
 pub const DEFAULT_TUI_PRIVACY_MAX_TTL: u8 = 0;
 
+/// The default value for `tui-first-hop-warning-rounds`.
+pub const DEFAULT_TUI_FIRST_HOP_WARNING_ROUNDS: usize = 5;
+
 /// The default value for `dns-resolve-method`.
 pub const DEFAULT_DNS_RESOLVE_METHOD: DnsResolveMethodConfig = DnsResolveMethodConfig::System;
 
@@ -64,6 +166,26 @@ pub const DEFAULT_DNS_TTL: Duration = Duration::from_secs(300);
 /// The default value for `report-cycles`.
 pub const DEFAULT_REPORT_CYCLES: usize = 10;
 
+/// The default value for `webhook-trigger`.
+pub const DEFAULT_WEBHOOK_TRIGGER: WebhookTrigger = WebhookTrigger::Completion;
+
+/// The default value for `webhook-fields`.
+pub const DEFAULT_WEBHOOK_FIELDS: [WebhookField; 4] = [
+    WebhookField::Target,
+    WebhookField::Round,
+    WebhookField::Reachable,
+    WebhookField::Hops,
+];
+
+/// The default value for `webhook-retries`.
+pub const DEFAULT_WEBHOOK_RETRIES: u8 = 3;
+
+/// The maximum value for `webhook-retries`.
+pub const MAX_WEBHOOK_RETRIES: u8 = 10;
+
+/// The default value for `bell-trigger`.
+pub const DEFAULT_BELL_TRIGGER: BellTrigger = BellTrigger::Never;
+
 /// The minimum TUI refresh rate.
 pub const TUI_MIN_REFRESH_RATE_MS: Duration = Duration::from_millis(50);
 
@@ -88,5 +210,17 @@ pub const MIN_PACKET_SIZE_IPV4: u16 = 28;
 /// The minimum IPv6 packet size we allow.
 pub const MIN_PACKET_SIZE_IPV6: u16 = 48;
 
+/// The size of the stamp embedded in the payload of ICMP probes.
+pub const STAMPED_PAYLOAD_SIZE: u16 = 16;
+
+/// The size of the payload word which is adjusted by the constant-checksum payload strategy.
+pub const CHECKSUM_WORD_PAYLOAD_SIZE: u16 = 2;
+
 /// The maximum packet size we allow.
 pub const MAX_PACKET_SIZE: u16 = 1024;
+
+/// The maximum number of decimal places of round-trip time columns.
+pub const MAX_TUI_RTT_PRECISION: u8 = 4;
+
+/// The default value for `local-target-mode`.
+pub const DEFAULT_LOCAL_TARGET_MODE: LocalTargetMode = LocalTargetMode::Warn;
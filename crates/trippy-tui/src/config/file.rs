@@ -1,13 +1,18 @@
 use crate::config::binding::TuiKeyBinding;
 use crate::config::theme::TuiColor;
 use crate::config::{
-    AddressFamilyConfig, AddressMode, AsMode, DnsResolveMethodConfig, GeoIpMode, IcmpExtensionMode,
-    LogFormat, LogSpanEvents, Mode, MultipathStrategyConfig, ProtocolConfig,
+    AddrAggregationMode, AddressFamilyConfig, AddressMode, AsMode, AverageMode, BellTrigger,
+    DnsResolveMethodConfig, FlowSortMode, GeoIpMode, GlyphSet, HostTruncation, IcmpExtensionMode,
+    LocalTargetMode, LogFormat, LogSpanEvents, Mode, MultipathStrategyConfig,
+    PayloadStrategyConfig, ProtocolConfig, RttHeatMode, StatusMode, StdDevMode, StdDevScope,
+    TargetTtlMode, TimeExceededRttMode, TrailingHopMode, UnresponsiveHopMode, WebhookField,
+    WebhookTrigger,
 };
 use anyhow::Context;
 use encoding_rs_io::DecodeReaderBytes;
 use etcetera::BaseStrategy;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufReader, Read};
 use std::net::IpAddr;
@@ -136,6 +141,7 @@ pub struct ConfigStrategy {
     #[serde(deserialize_with = "addr_deser")]
     pub source_address: Option<IpAddr>,
     pub interface: Option<String>,
+    pub local_target_mode: Option<LocalTargetMode>,
     #[serde(default)]
     #[serde(deserialize_with = "humantime_deser")]
     pub min_round_duration: Option<Duration>,
@@ -148,15 +154,41 @@ pub struct ConfigStrategy {
     #[serde(deserialize_with = "humantime_deser")]
     pub grace_duration: Option<Duration>,
     pub max_inflight: Option<u8>,
+    pub send_batch_size: Option<u8>,
+    pub recv_batch_size: Option<u8>,
     pub first_ttl: Option<u8>,
     pub max_ttl: Option<u8>,
     pub packet_size: Option<u16>,
+    pub icmp_data_length: Option<u16>,
     pub payload_pattern: Option<u8>,
     pub tos: Option<u8>,
+    pub flow_label: Option<u32>,
     pub icmp_extensions: Option<bool>,
     #[serde(default)]
     #[serde(deserialize_with = "humantime_deser")]
     pub read_timeout: Option<Duration>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub min_probe_interval: Option<Duration>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub send_jitter: Option<Duration>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub loss_timeout: Option<Duration>,
+    pub ignore_identifier: Option<bool>,
+    pub discard_first_hop_warmup: Option<bool>,
+    pub accept_late_responses: Option<bool>,
+    pub adaptive_read_timeout: Option<bool>,
+    pub max_unresponsive_hops: Option<u8>,
+    pub curtail_unresponsive: Option<bool>,
+    pub setup_retry_attempts: Option<usize>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub setup_retry_backoff: Option<Duration>,
+    pub kernel_recv_timestamps: Option<bool>,
+    pub stamped_payload: Option<bool>,
+    pub payload_strategy: Option<PayloadStrategyConfig>,
     pub max_samples: Option<usize>,
     pub max_flows: Option<usize>,
 }
@@ -170,6 +202,7 @@ impl Default for ConfigStrategy {
             source_port: None,
             source_address: None,
             interface: None,
+            local_target_mode: Some(super::constants::DEFAULT_LOCAL_TARGET_MODE),
             min_round_duration: Some(defaults::DEFAULT_STRATEGY_MIN_ROUND_DURATION),
             max_round_duration: Some(defaults::DEFAULT_STRATEGY_MAX_ROUND_DURATION),
             initial_sequence: Some(defaults::DEFAULT_STRATEGY_INITIAL_SEQUENCE),
@@ -178,13 +211,33 @@ impl Default for ConfigStrategy {
             )),
             grace_duration: Some(defaults::DEFAULT_STRATEGY_GRACE_DURATION),
             max_inflight: Some(defaults::DEFAULT_STRATEGY_MAX_INFLIGHT),
+            send_batch_size: Some(defaults::DEFAULT_STRATEGY_SEND_BATCH_SIZE),
+            recv_batch_size: Some(defaults::DEFAULT_STRATEGY_RECV_BATCH_SIZE),
             first_ttl: Some(defaults::DEFAULT_STRATEGY_FIRST_TTL),
             max_ttl: Some(defaults::DEFAULT_STRATEGY_MAX_TTL),
             packet_size: Some(defaults::DEFAULT_STRATEGY_PACKET_SIZE),
+            icmp_data_length: None,
             payload_pattern: Some(defaults::DEFAULT_STRATEGY_PAYLOAD_PATTERN),
             tos: Some(defaults::DEFAULT_STRATEGY_TOS),
+            flow_label: Some(defaults::DEFAULT_STRATEGY_FLOW_LABEL),
             icmp_extensions: Some(defaults::DEFAULT_ICMP_EXTENSION_PARSE_MODE.is_enabled()),
             read_timeout: Some(defaults::DEFAULT_STRATEGY_READ_TIMEOUT),
+            min_probe_interval: None,
+            send_jitter: None,
+            loss_timeout: None,
+            ignore_identifier: Some(false),
+            discard_first_hop_warmup: Some(false),
+            accept_late_responses: Some(false),
+            adaptive_read_timeout: Some(false),
+            max_unresponsive_hops: Some(super::constants::DEFAULT_MAX_UNRESPONSIVE_HOPS),
+            curtail_unresponsive: Some(false),
+            setup_retry_attempts: Some(defaults::DEFAULT_SETUP_RETRY_ATTEMPTS),
+            setup_retry_backoff: Some(defaults::DEFAULT_SETUP_RETRY_BACKOFF),
+            kernel_recv_timestamps: Some(false),
+            stamped_payload: Some(false),
+            payload_strategy: Some(PayloadStrategyConfig::from(
+                defaults::DEFAULT_STRATEGY_PAYLOAD_STRATEGY,
+            )),
             max_samples: Some(defaults::DEFAULT_MAX_SAMPLES),
             max_flows: Some(defaults::DEFAULT_MAX_FLOWS),
         }
@@ -222,12 +275,30 @@ impl Default for ConfigDns {
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 pub struct ConfigReport {
     pub report_cycles: Option<usize>,
+    pub webhook_url: Option<String>,
+    pub webhook_trigger: Option<WebhookTrigger>,
+    pub webhook_fields: Option<Vec<WebhookField>>,
+    pub webhook_retries: Option<u8>,
+    pub sqlite_file: Option<String>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub sqlite_retention: Option<Duration>,
+    pub prometheus_file: Option<String>,
+    pub bell_trigger: Option<BellTrigger>,
 }
 
 impl Default for ConfigReport {
     fn default() -> Self {
         Self {
             report_cycles: Some(super::constants::DEFAULT_REPORT_CYCLES),
+            webhook_url: None,
+            webhook_trigger: Some(super::constants::DEFAULT_WEBHOOK_TRIGGER),
+            webhook_fields: Some(super::constants::DEFAULT_WEBHOOK_FIELDS.to_vec()),
+            webhook_retries: Some(super::constants::DEFAULT_WEBHOOK_RETRIES),
+            sqlite_file: None,
+            sqlite_retention: None,
+            prometheus_file: None,
+            bell_trigger: Some(super::constants::DEFAULT_BELL_TRIGGER),
         }
     }
 }
@@ -240,14 +311,57 @@ pub struct ConfigTui {
     #[serde(deserialize_with = "humantime_deser")]
     pub tui_refresh_rate: Option<Duration>,
     pub tui_privacy_max_ttl: Option<u8>,
+    pub tui_first_hop_warning_rounds: Option<usize>,
     pub tui_address_mode: Option<AddressMode>,
     pub tui_as_mode: Option<AsMode>,
+    pub tui_as_colors: Option<bool>,
+    pub tui_summary_footer: Option<bool>,
+    pub tui_rtt_deltas: Option<bool>,
+    pub tui_rtt_baseline: Option<bool>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub tui_rtt_compact_threshold: Option<Duration>,
+    pub tui_rtt_heat_mode: Option<RttHeatMode>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub tui_rtt_heat_max: Option<Duration>,
     pub tui_icmp_extension_mode: Option<IcmpExtensionMode>,
     pub tui_geoip_mode: Option<GeoIpMode>,
+    pub tui_unresponsive_hop_mode: Option<UnresponsiveHopMode>,
+    pub tui_trailing_hop_mode: Option<TrailingHopMode>,
+    pub tui_time_exceeded_rtt_mode: Option<TimeExceededRttMode>,
+    pub tui_average_mode: Option<AverageMode>,
+    pub tui_ewma_weight: Option<u8>,
+    pub tui_loss_ewma_weight: Option<u8>,
+    pub tui_tunnel_ttl_jump: Option<u8>,
+    pub tui_status_mode: Option<StatusMode>,
+    pub tui_addr_aggregation: Option<AddrAggregationMode>,
+    pub tui_target_ttl_mode: Option<TargetTtlMode>,
+    pub tui_host_truncation: Option<HostTruncation>,
+    pub tui_glyph_set: Option<GlyphSet>,
     pub tui_max_addrs: Option<u8>,
+    pub tui_max_display_hops: Option<u8>,
+    pub tui_stats_window: Option<usize>,
+    pub tui_stddev_mode: Option<StdDevMode>,
+    pub tui_stddev_scope: Option<StdDevScope>,
+    pub tui_max_rounds: Option<usize>,
+    pub tui_path_change_rounds: Option<usize>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub tui_path_stability_window: Option<Duration>,
+    pub tui_flow_sort_mode: Option<FlowSortMode>,
+    pub tui_probe_countdown: Option<bool>,
+    pub tui_auto_quit_stable_rounds: Option<usize>,
+    #[serde(default)]
+    #[serde(deserialize_with = "humantime_deser")]
+    pub tui_auto_quit_stable_threshold: Option<Duration>,
     pub geoip_mmdb_file: Option<String>,
     pub tui_custom_columns: Option<String>,
+    pub tui_column_groups: Option<String>,
+    pub tui_rtt_precision: Option<String>,
     pub tui_locale: Option<String>,
+    pub tui_notes_file: Option<String>,
+    pub tui_provider_classes: Option<HashMap<String, String>>,
     #[serde(rename = "tui-max-samples")]
     pub deprecated_tui_max_samples: Option<usize>,
     #[serde(rename = "tui-max-flows")]
@@ -260,13 +374,54 @@ impl Default for ConfigTui {
             tui_preserve_screen: Some(super::constants::DEFAULT_TUI_PRESERVE_SCREEN),
             tui_refresh_rate: Some(super::constants::DEFAULT_TUI_REFRESH_RATE),
             tui_privacy_max_ttl: Some(super::constants::DEFAULT_TUI_PRIVACY_MAX_TTL),
+            tui_first_hop_warning_rounds: Some(
+                super::constants::DEFAULT_TUI_FIRST_HOP_WARNING_ROUNDS,
+            ),
             tui_address_mode: Some(super::constants::DEFAULT_TUI_ADDRESS_MODE),
             tui_as_mode: Some(super::constants::DEFAULT_TUI_AS_MODE),
+            tui_as_colors: Some(super::constants::DEFAULT_TUI_AS_COLORS),
+            tui_summary_footer: Some(super::constants::DEFAULT_TUI_SUMMARY_FOOTER),
+            tui_rtt_deltas: Some(super::constants::DEFAULT_TUI_RTT_DELTAS),
+            tui_rtt_baseline: Some(super::constants::DEFAULT_TUI_RTT_BASELINE),
+            tui_rtt_compact_threshold: None,
+            tui_rtt_heat_mode: Some(super::constants::DEFAULT_TUI_RTT_HEAT_MODE),
+            tui_rtt_heat_max: Some(super::constants::DEFAULT_TUI_RTT_HEAT_MAX),
             tui_custom_columns: Some(String::from(super::constants::DEFAULT_CUSTOM_COLUMNS)),
+            tui_column_groups: Some(String::from(super::constants::DEFAULT_TUI_COLUMN_GROUPS)),
+            tui_rtt_precision: Some(String::from(super::constants::DEFAULT_TUI_RTT_PRECISION)),
             tui_icmp_extension_mode: Some(super::constants::DEFAULT_TUI_ICMP_EXTENSION_MODE),
             tui_geoip_mode: Some(super::constants::DEFAULT_TUI_GEOIP_MODE),
+            tui_unresponsive_hop_mode: Some(super::constants::DEFAULT_TUI_UNRESPONSIVE_HOP_MODE),
+            tui_trailing_hop_mode: Some(super::constants::DEFAULT_TUI_TRAILING_HOP_MODE),
+            tui_time_exceeded_rtt_mode: Some(super::constants::DEFAULT_TUI_TIME_EXCEEDED_RTT_MODE),
+            tui_average_mode: Some(super::constants::DEFAULT_TUI_AVERAGE_MODE),
+            tui_ewma_weight: Some(super::constants::DEFAULT_TUI_EWMA_WEIGHT),
+            tui_loss_ewma_weight: Some(super::constants::DEFAULT_TUI_LOSS_EWMA_WEIGHT),
+            tui_tunnel_ttl_jump: Some(super::constants::DEFAULT_TUI_TUNNEL_TTL_JUMP),
+            tui_status_mode: Some(super::constants::DEFAULT_TUI_STATUS_MODE),
+            tui_addr_aggregation: Some(super::constants::DEFAULT_TUI_ADDR_AGGREGATION),
+            tui_target_ttl_mode: Some(super::constants::DEFAULT_TUI_TARGET_TTL_MODE),
+            tui_host_truncation: Some(super::constants::DEFAULT_TUI_HOST_TRUNCATION),
+            tui_glyph_set: Some(super::constants::DEFAULT_TUI_GLYPH_SET),
             tui_max_addrs: Some(super::constants::DEFAULT_TUI_MAX_ADDRS),
+            tui_max_display_hops: Some(super::constants::DEFAULT_TUI_MAX_DISPLAY_HOPS),
+            tui_stats_window: Some(super::constants::DEFAULT_TUI_STATS_WINDOW),
+            tui_stddev_mode: Some(super::constants::DEFAULT_TUI_STDDEV_MODE),
+            tui_stddev_scope: Some(super::constants::DEFAULT_TUI_STDDEV_SCOPE),
+            tui_max_rounds: Some(super::constants::DEFAULT_TUI_MAX_ROUNDS),
+            tui_path_change_rounds: Some(super::constants::DEFAULT_TUI_PATH_CHANGE_ROUNDS),
+            tui_path_stability_window: Some(super::constants::DEFAULT_TUI_PATH_STABILITY_WINDOW),
+            tui_flow_sort_mode: Some(super::constants::DEFAULT_TUI_FLOW_SORT_MODE),
+            tui_probe_countdown: Some(super::constants::DEFAULT_TUI_PROBE_COUNTDOWN),
+            tui_auto_quit_stable_rounds: Some(
+                super::constants::DEFAULT_TUI_AUTO_QUIT_STABLE_ROUNDS,
+            ),
+            tui_auto_quit_stable_threshold: Some(
+                super::constants::DEFAULT_TUI_AUTO_QUIT_STABLE_THRESHOLD,
+            ),
             tui_locale: None,
+            tui_notes_file: None,
+            tui_provider_classes: None,
             geoip_mmdb_file: None,
             deprecated_tui_max_samples: None,
             deprecated_tui_max_flows: None,
@@ -284,6 +439,8 @@ pub struct ConfigThemeColors {
     pub tab_text_color: Option<TuiColor>,
     pub hops_table_header_bg_color: Option<TuiColor>,
     pub hops_table_header_text_color: Option<TuiColor>,
+    pub hops_table_header_selected_bg_color: Option<TuiColor>,
+    pub hops_table_header_selected_text_color: Option<TuiColor>,
     pub hops_table_row_active_text_color: Option<TuiColor>,
     pub hops_table_row_inactive_text_color: Option<TuiColor>,
     pub hops_chart_selected_color: Option<TuiColor>,
@@ -310,6 +467,8 @@ pub struct ConfigThemeColors {
     pub map_info_panel_border_color: Option<TuiColor>,
     pub map_info_panel_bg_color: Option<TuiColor>,
     pub map_info_panel_text_color: Option<TuiColor>,
+    pub rtt_heat_low_color: Option<TuiColor>,
+    pub rtt_heat_high_color: Option<TuiColor>,
 }
 
 impl Default for ConfigThemeColors {
@@ -322,6 +481,8 @@ impl Default for ConfigThemeColors {
             tab_text_color: Some(theme.tab_text),
             hops_table_header_bg_color: Some(theme.hops_table_header_bg),
             hops_table_header_text_color: Some(theme.hops_table_header_text),
+            hops_table_header_selected_bg_color: Some(theme.hops_table_header_selected_bg),
+            hops_table_header_selected_text_color: Some(theme.hops_table_header_selected_text),
             hops_table_row_active_text_color: Some(theme.hops_table_row_active_text),
             hops_table_row_inactive_text_color: Some(theme.hops_table_row_inactive_text),
             hops_chart_selected_color: Some(theme.hops_chart_selected),
@@ -348,6 +509,8 @@ impl Default for ConfigThemeColors {
             map_info_panel_border_color: Some(theme.map_info_panel_border),
             map_info_panel_bg_color: Some(theme.map_info_panel_bg),
             map_info_panel_text_color: Some(theme.map_info_panel_text),
+            rtt_heat_low_color: Some(theme.rtt_heat_low),
+            rtt_heat_high_color: Some(theme.rtt_heat_high),
         }
     }
 }
@@ -390,6 +553,28 @@ pub struct ConfigBindings {
     pub clear_selection: Option<TuiKeyBinding>,
     pub toggle_as_info: Option<TuiKeyBinding>,
     pub toggle_hop_details: Option<TuiKeyBinding>,
+    pub previous_column: Option<TuiKeyBinding>,
+    pub next_column: Option<TuiKeyBinding>,
+    pub narrow_column: Option<TuiKeyBinding>,
+    pub widen_column: Option<TuiKeyBinding>,
+    pub reset_column_width: Option<TuiKeyBinding>,
+    pub dismiss_warning: Option<TuiKeyBinding>,
+    pub contract_stats_window: Option<TuiKeyBinding>,
+    pub expand_stats_window: Option<TuiKeyBinding>,
+    pub select_worst_loss_hop: Option<TuiKeyBinding>,
+    pub select_worst_latency_hop: Option<TuiKeyBinding>,
+    pub toggle_as_colors: Option<TuiKeyBinding>,
+    pub toggle_summary_footer: Option<TuiKeyBinding>,
+    pub toggle_rtt_deltas: Option<TuiKeyBinding>,
+    pub edit_note: Option<TuiKeyBinding>,
+    pub toggle_trailing_hop: Option<TuiKeyBinding>,
+    pub save_image: Option<TuiKeyBinding>,
+    pub toggle_hop_paused: Option<TuiKeyBinding>,
+    pub toggle_trace_paused: Option<TuiKeyBinding>,
+    pub toggle_column_group: Option<TuiKeyBinding>,
+    pub toggle_flow_sort: Option<TuiKeyBinding>,
+    pub save_ansi: Option<TuiKeyBinding>,
+    pub toggle_hop_sort: Option<TuiKeyBinding>,
     pub quit: Option<TuiKeyBinding>,
 }
 
@@ -432,6 +617,28 @@ impl Default for ConfigBindings {
             clear_selection: Some(bindings.clear_selection),
             toggle_as_info: Some(bindings.toggle_as_info),
             toggle_hop_details: Some(bindings.toggle_hop_details),
+            previous_column: Some(bindings.previous_column),
+            next_column: Some(bindings.next_column),
+            narrow_column: Some(bindings.narrow_column),
+            widen_column: Some(bindings.widen_column),
+            reset_column_width: Some(bindings.reset_column_width),
+            dismiss_warning: Some(bindings.dismiss_warning),
+            contract_stats_window: Some(bindings.contract_stats_window),
+            expand_stats_window: Some(bindings.expand_stats_window),
+            select_worst_loss_hop: Some(bindings.select_worst_loss_hop),
+            select_worst_latency_hop: Some(bindings.select_worst_latency_hop),
+            toggle_as_colors: Some(bindings.toggle_as_colors),
+            toggle_summary_footer: Some(bindings.toggle_summary_footer),
+            toggle_rtt_deltas: Some(bindings.toggle_rtt_deltas),
+            edit_note: Some(bindings.edit_note),
+            toggle_trailing_hop: Some(bindings.toggle_trailing_hop),
+            save_image: Some(bindings.save_image),
+            toggle_hop_paused: Some(bindings.toggle_hop_paused),
+            toggle_trace_paused: Some(bindings.toggle_trace_paused),
+            toggle_column_group: Some(bindings.toggle_column_group),
+            toggle_flow_sort: Some(bindings.toggle_flow_sort),
+            save_ansi: Some(bindings.save_ansi),
+            toggle_hop_sort: Some(bindings.toggle_hop_sort),
             quit: Some(bindings.quit),
         }
     }
@@ -25,6 +25,10 @@ pub struct TuiTheme {
     pub hops_table_header_bg: TuiColor,
     /// The color of text in the hops table header.
     pub hops_table_header_text: TuiColor,
+    /// The background color of the selected column in the hops table header.
+    pub hops_table_header_selected_bg: TuiColor,
+    /// The color of text of the selected column in the hops table header.
+    pub hops_table_header_selected_text: TuiColor,
     /// The color of text of active rows in the hops table.
     pub hops_table_row_active_text: TuiColor,
     /// The color of text of inactive rows in the hops table.
@@ -77,6 +81,10 @@ pub struct TuiTheme {
     pub map_info_panel_bg: TuiColor,
     /// The color of text in the map info panel.
     pub map_info_panel_text: TuiColor,
+    /// The color of the lowest round-trip time in the rtt heat gradient.
+    pub rtt_heat_low: TuiColor,
+    /// The color of the highest round-trip time in the rtt heat gradient.
+    pub rtt_heat_high: TuiColor,
 }
 
 impl Default for TuiTheme {
@@ -88,6 +96,8 @@ impl Default for TuiTheme {
             tab_text: TuiColor::Green,
             hops_table_header_bg: TuiColor::White,
             hops_table_header_text: TuiColor::Black,
+            hops_table_header_selected_bg: TuiColor::Black,
+            hops_table_header_selected_text: TuiColor::White,
             hops_table_row_active_text: TuiColor::Gray,
             hops_table_row_inactive_text: TuiColor::DarkGray,
             hops_chart_selected: TuiColor::Green,
@@ -114,6 +124,8 @@ impl Default for TuiTheme {
             map_info_panel_border: TuiColor::Gray,
             map_info_panel_bg: TuiColor::Black,
             map_info_panel_text: TuiColor::Gray,
+            rtt_heat_low: TuiColor::Green,
+            rtt_heat_high: TuiColor::Red,
         }
     }
 }
@@ -147,6 +159,14 @@ impl From<(HashMap<TuiThemeItem, TuiColor>, ConfigThemeColors)> for TuiTheme {
                 .get(&TuiThemeItem::HopsTableHeaderTextColor)
                 .or(cfg.hops_table_header_text_color.as_ref())
                 .unwrap_or(&Self::default().hops_table_header_text),
+            hops_table_header_selected_bg: *color_map
+                .get(&TuiThemeItem::HopsTableHeaderSelectedBgColor)
+                .or(cfg.hops_table_header_selected_bg_color.as_ref())
+                .unwrap_or(&Self::default().hops_table_header_selected_bg),
+            hops_table_header_selected_text: *color_map
+                .get(&TuiThemeItem::HopsTableHeaderSelectedTextColor)
+                .or(cfg.hops_table_header_selected_text_color.as_ref())
+                .unwrap_or(&Self::default().hops_table_header_selected_text),
             hops_table_row_active_text: *color_map
                 .get(&TuiThemeItem::HopsTableRowActiveTextColor)
                 .or(cfg.hops_table_row_active_text_color.as_ref())
@@ -251,6 +271,14 @@ impl From<(HashMap<TuiThemeItem, TuiColor>, ConfigThemeColors)> for TuiTheme {
                 .get(&TuiThemeItem::MapInfoPanelTextColor)
                 .or(cfg.map_info_panel_text_color.as_ref())
                 .unwrap_or(&Self::default().map_info_panel_text),
+            rtt_heat_low: *color_map
+                .get(&TuiThemeItem::RttHeatLowColor)
+                .or(cfg.rtt_heat_low_color.as_ref())
+                .unwrap_or(&Self::default().rtt_heat_low),
+            rtt_heat_high: *color_map
+                .get(&TuiThemeItem::RttHeatHighColor)
+                .or(cfg.rtt_heat_high_color.as_ref())
+                .unwrap_or(&Self::default().rtt_heat_high),
         }
     }
 }
@@ -272,6 +300,10 @@ pub enum TuiThemeItem {
     HopsTableHeaderBgColor,
     /// The color of text in the hops table header.
     HopsTableHeaderTextColor,
+    /// The background color of the selected column in the hops table header.
+    HopsTableHeaderSelectedBgColor,
+    /// The color of text of the selected column in the hops table header.
+    HopsTableHeaderSelectedTextColor,
     /// The color of text of active rows in the hops table.
     HopsTableRowActiveTextColor,
     /// The color of text of inactive rows in the hops table.
@@ -324,6 +356,10 @@ pub enum TuiThemeItem {
     MapInfoPanelBgColor,
     /// The color of text in the map info panel.
     MapInfoPanelTextColor,
+    /// The color of the lowest round-trip time in the rtt heat gradient.
+    RttHeatLowColor,
+    /// The color of the highest round-trip time in the rtt heat gradient.
+    RttHeatHighColor,
 }
 
 /// A TUI color.
@@ -0,0 +1,185 @@
+use anyhow::{anyhow, Context};
+use encoding_rs_io::DecodeReaderBytes;
+use serde::Deserialize;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::net::IpAddr;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use trippy_core::{
+    CompletionReason, Flags, IcmpPacketCode, IcmpPacketType, Port, Probe, ProbeComplete,
+    ProbeStatus, Round, RoundId, Sequence, TimeToLive, TraceId, Tracer, TtlAddrs,
+};
+
+/// A hand authored set of hops, used in place of tracing the network.
+///
+/// This allows the Tui to render arbitrary hop states, such as a hop with
+/// 100% loss or a very long path, without a network which produces them.
+///
+/// A fixture is a list of hops, ordered by time-to-live, for example:
+///
+/// ```toml
+/// [[hops]]
+/// addrs = ["10.0.0.1"]
+/// samples = [1.2, 0.0, 1.5]
+///
+/// [[hops]]
+/// addrs = []
+/// samples = []
+///
+/// [[hops]]
+/// addrs = ["10.0.0.3", "10.0.0.4"]
+/// samples = [12.1, 14.9, 13.0]
+/// ```
+///
+/// Each hop has the round-trip time, in milliseconds, of the probe in each
+/// round, where a zero value is a lost probe, and is assumed to be lost in
+/// any round which has no sample.  The address of the hop in each round is
+/// taken from `addrs` in turn.  The last hop is the target.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Fixture {
+    hops: Vec<FixtureHop>,
+}
+
+/// A single hop of a fixture.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FixtureHop {
+    addrs: Vec<IpAddr>,
+    samples: Vec<f64>,
+}
+
+impl Fixture {
+    /// Read a fixture from a file.
+    pub fn read<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let file = File::open(path.as_ref())
+            .with_context(|| format!("fixture file not found: {}", path.as_ref().display()))?;
+        let mut decoder = DecodeReaderBytes::new(BufReader::new(file));
+        let mut dest = String::new();
+        decoder.read_to_string(&mut dest)?;
+        let fixture: Self = toml::from_str(&dest)?;
+        if fixture.hops.is_empty() || fixture.hops.len() > usize::from(trippy_core::MAX_TTL) {
+            return Err(anyhow!(
+                "fixture must have between 1 and {} hops",
+                trippy_core::MAX_TTL
+            ));
+        }
+        Ok(fixture)
+    }
+
+    /// Populate the state of a tracer with every round of the fixture.
+    pub fn replay(&self, tracer: &Tracer) {
+        for round in 0..self.round_count() {
+            let probes = self.probes(round);
+            let target_found = matches!(probes.last(), Some(ProbeStatus::Complete(_)));
+            let reason = if target_found {
+                CompletionReason::TargetFound
+            } else {
+                CompletionReason::RoundTimeLimitExceeded
+            };
+            let largest_ttl = TimeToLive(u8::try_from(probes.len()).unwrap_or(u8::MAX));
+            tracer.update_from_round(&Round::new(&probes, largest_ttl, reason));
+        }
+    }
+
+    /// The number of rounds, which is the largest number of samples of any hop.
+    fn round_count(&self) -> usize {
+        self.hops
+            .iter()
+            .map(|hop| hop.samples.len())
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// The probes of all hops for a given round.
+    fn probes(&self, round: usize) -> Vec<ProbeStatus> {
+        let sent = SystemTime::now();
+        let target_ttl = self.hops.len();
+        self.hops
+            .iter()
+            .enumerate()
+            .map(|(i, hop)| {
+                let ttl = u8::try_from(i + 1).unwrap_or(u8::MAX);
+                let probe = Probe {
+                    sequence: Sequence(u16::try_from(round * target_ttl + i).unwrap_or(u16::MAX)),
+                    identifier: TraceId(0),
+                    src_port: Port(0),
+                    dest_port: Port(0),
+                    ttl: TimeToLive(ttl),
+                    round: RoundId(round),
+                    sent,
+                    flags: Flags::empty(),
+                };
+                let sample = hop.samples.get(round).copied().unwrap_or_default();
+                if hop.addrs.is_empty() || sample <= 0_f64 {
+                    return ProbeStatus::Awaited(probe);
+                }
+                let icmp_packet_type = if i + 1 == target_ttl {
+                    IcmpPacketType::EchoReply(IcmpPacketCode(0))
+                } else {
+                    IcmpPacketType::TimeExceeded(IcmpPacketCode(0))
+                };
+                ProbeStatus::Complete(ProbeComplete {
+                    sequence: probe.sequence,
+                    identifier: probe.identifier,
+                    src_port: probe.src_port,
+                    dest_port: probe.dest_port,
+                    ttl: probe.ttl,
+                    round: probe.round,
+                    sent,
+                    host: hop.addrs[round % hop.addrs.len()],
+                    received: sent + Duration::from_secs_f64(sample / 1000_f64),
+                    icmp_packet_type,
+                    expected_udp_checksum: None,
+                    actual_udp_checksum: None,
+                    extensions: None,
+                    reply_ttl: None,
+                    quoted_len: None,
+                    jitter: None,
+                    ttl_addrs: TtlAddrs::default(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use trippy_core::{Builder, State};
+
+    #[test]
+    fn test_replay() {
+        let fixture: Fixture = toml::from_str(
+            r#"
+            [[hops]]
+            addrs = ["10.0.0.1"]
+            samples = [1.0, 0.0, 3.0]
+
+            [[hops]]
+            addrs = []
+            samples = []
+
+            [[hops]]
+            addrs = ["10.0.0.3", "10.0.0.4"]
+            samples = [10.0, 20.0]
+            "#,
+        )
+        .unwrap();
+        let tracer = Builder::new(IpAddr::from([10, 0, 0, 3])).build().unwrap();
+        fixture.replay(&tracer);
+        let state = tracer.snapshot();
+        let flow_id = State::default_flow_id();
+        let hops = state.hops_for_flow(flow_id);
+        assert_eq!(3, state.round_count(flow_id));
+        assert_eq!(3, hops.len());
+        assert_eq!(3, hops[0].total_sent());
+        assert_eq!(2, hops[0].total_recv());
+        assert_eq!(Some(3.0), hops[0].last_ms());
+        assert_eq!(0, hops[1].total_recv());
+        assert_eq!(2, hops[2].total_recv());
+        assert_eq!(2, hops[2].addr_count());
+        assert_eq!(Some(3), state.target_ttl(flow_id));
+    }
+}
@@ -1,27 +1,36 @@
 use crate::app::TraceInfo;
-use crate::config::AddressMode;
+use crate::config::{AddressMode, TuiCommandItem};
 use crate::frontend::binding::CTRL_C;
 use crate::geoip::GeoIpLookup;
+use crate::notes::Notes;
 pub use config::TuiConfig;
-use crossterm::event::KeyEventKind;
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use crossterm::{
     event::{self, Event},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+pub use prompt::run_prompt;
 use ratatui::{
     backend::{Backend, CrosstermBackend},
     Terminal,
 };
 use std::io;
+use std::time::{Duration, Instant};
 use trippy_dns::DnsResolver;
-use tui_app::TuiApp;
+use tui_app::{ImageFormat, TuiApp};
 
+mod ansi;
 mod binding;
 mod columns;
 mod config;
+mod glyphs;
+#[cfg(feature = "png")]
+mod image;
+mod prompt;
 mod render;
 mod theme;
+mod timeline;
 mod tui_app;
 
 /// Run the frontend TUI.
@@ -30,6 +39,7 @@ pub fn run_frontend(
     tui_config: TuiConfig,
     resolver: DnsResolver,
     geoip_lookup: GeoIpLookup,
+    notes: Notes,
 ) -> anyhow::Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -43,7 +53,14 @@ pub fn run_frontend(
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     let preserve_screen = tui_config.preserve_screen;
-    let res = run_app(&mut terminal, traces, tui_config, resolver, geoip_lookup);
+    let res = run_app(
+        &mut terminal,
+        traces,
+        tui_config,
+        resolver,
+        geoip_lookup,
+        notes,
+    );
     disable_raw_mode()?;
     if !preserve_screen {
         execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
@@ -55,176 +72,208 @@ pub fn run_frontend(
     Ok(())
 }
 
-#[allow(clippy::too_many_lines, clippy::cognitive_complexity)]
+/// Run the main loop of the TUI.
+///
+/// The trace data is refreshed and the screen redrawn at the configured refresh rate, independently
+/// of the tracer.  Input is handled as soon as it is received and all pending input is handled
+/// before the screen is redrawn, such that a burst of input results in a single redraw.  Handling
+/// input does not delay the next scheduled refresh.
 fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     trace_info: Vec<TraceInfo>,
     tui_config: TuiConfig,
     resolver: DnsResolver,
     geoip_lookup: GeoIpLookup,
+    notes: Notes,
 ) -> io::Result<()> {
-    let mut app = TuiApp::new(tui_config, resolver, geoip_lookup, trace_info);
+    let mut app = TuiApp::new(tui_config, resolver, geoip_lookup, notes, trace_info);
+    let mut next_refresh = Instant::now();
     loop {
         if app.frozen_start.is_none() {
             app.snapshot_trace_data();
             app.clamp_selected_hop();
             app.update_order_flow_counts();
+            app.update_timeline();
         };
-        terminal.draw(|f| render::app::render(f, &mut app))?;
-        if event::poll(app.tui_config.refresh_rate)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    let bindings = &app.tui_config.bindings;
-                    if app.show_help {
-                        if bindings.toggle_help.check(key)
-                            || bindings.toggle_help_alt.check(key)
-                            || bindings.clear_selection.check(key)
-                            || bindings.quit.check(key)
-                        {
-                            app.toggle_help();
-                        } else if bindings.toggle_settings.check(key) {
-                            app.toggle_help();
-                            app.toggle_settings();
-                        } else if bindings.toggle_settings_tui.check(key) {
-                            app.toggle_help();
-                            app.show_settings_columns(0);
-                        } else if bindings.toggle_settings_trace.check(key) {
-                            app.toggle_help();
-                            app.show_settings_columns(1);
-                        } else if bindings.toggle_settings_dns.check(key) {
-                            app.toggle_help();
-                            app.show_settings_columns(2);
-                        } else if bindings.toggle_settings_geoip.check(key) {
-                            app.toggle_help();
-                            app.show_settings_columns(3);
-                        } else if bindings.toggle_settings_bindings.check(key) {
-                            app.toggle_help();
-                            app.show_settings_columns(4);
-                        } else if bindings.toggle_settings_theme.check(key) {
-                            app.toggle_help();
-                            app.show_settings_columns(5);
-                        } else if bindings.toggle_settings_columns.check(key) {
-                            app.toggle_help();
-                            app.show_settings_columns(6);
-                        }
-                    } else if app.show_settings {
-                        if bindings.toggle_settings.check(key)
-                            || bindings.clear_selection.check(key)
-                            || bindings.quit.check(key)
-                        {
-                            app.toggle_settings();
-                        } else if bindings.toggle_settings_tui.check(key) {
-                            app.show_settings_columns(0);
-                        } else if bindings.toggle_settings_trace.check(key) {
-                            app.show_settings_columns(1);
-                        } else if bindings.toggle_settings_dns.check(key) {
-                            app.show_settings_columns(2);
-                        } else if bindings.toggle_settings_geoip.check(key) {
-                            app.show_settings_columns(3);
-                        } else if bindings.toggle_settings_bindings.check(key) {
-                            app.show_settings_columns(4);
-                        } else if bindings.toggle_settings_theme.check(key) {
-                            app.show_settings_columns(5);
-                        } else if bindings.toggle_settings_columns.check(key) {
-                            app.show_settings_columns(6);
-                        } else if bindings.previous_trace.check(key) {
-                            app.previous_settings_tab();
-                        } else if bindings.next_trace.check(key) {
-                            app.next_settings_tab();
-                        } else if bindings.next_hop.check(key) {
-                            app.next_settings_item();
-                        } else if bindings.previous_hop.check(key) {
-                            app.previous_settings_item();
-                        } else if bindings.toggle_chart.check(key) {
-                            app.toggle_column_visibility();
-                        } else if bindings.next_hop_address.check(key) {
-                            app.move_column_down();
-                        } else if bindings.previous_hop_address.check(key) {
-                            app.move_column_up();
-                        }
-                    } else if bindings.toggle_help.check(key) || bindings.toggle_help_alt.check(key)
-                    {
-                        app.toggle_help();
-                    } else if bindings.toggle_settings.check(key) {
-                        app.toggle_settings();
-                    } else if bindings.toggle_settings_tui.check(key) {
-                        app.show_settings_columns(0);
-                    } else if bindings.toggle_settings_trace.check(key) {
-                        app.show_settings_columns(1);
-                    } else if bindings.toggle_settings_dns.check(key) {
-                        app.show_settings_columns(2);
-                    } else if bindings.toggle_settings_geoip.check(key) {
-                        app.show_settings_columns(3);
-                    } else if bindings.toggle_settings_bindings.check(key) {
-                        app.show_settings_columns(4);
-                    } else if bindings.toggle_settings_theme.check(key) {
-                        app.show_settings_columns(5);
-                    } else if bindings.toggle_settings_columns.check(key) {
-                        app.show_settings_columns(6);
-                    } else if bindings.next_hop.check(key) {
-                        app.next_hop();
-                    } else if bindings.previous_hop.check(key) {
-                        app.previous_hop();
-                    } else if bindings.previous_trace.check(key) {
-                        if app.show_flows {
-                            app.previous_flow();
-                        } else {
-                            app.previous_trace();
-                        }
-                    } else if bindings.next_trace.check(key) {
-                        if app.show_flows {
-                            app.next_flow();
-                        } else {
-                            app.next_trace();
-                        }
-                    } else if bindings.next_hop_address.check(key) {
-                        app.next_hop_address();
-                    } else if bindings.previous_hop_address.check(key) {
-                        app.previous_hop_address();
-                    } else if bindings.address_mode_ip.check(key) {
-                        app.tui_config.address_mode = AddressMode::Ip;
-                    } else if bindings.address_mode_host.check(key) {
-                        app.tui_config.address_mode = AddressMode::Host;
-                    } else if bindings.address_mode_both.check(key) {
-                        app.tui_config.address_mode = AddressMode::Both;
-                    } else if bindings.toggle_freeze.check(key) {
-                        app.toggle_freeze();
-                    } else if bindings.toggle_chart.check(key) {
-                        app.toggle_chart();
-                    } else if bindings.toggle_map.check(key) {
-                        app.toggle_map();
-                    } else if bindings.toggle_flows.check(key) {
-                        app.toggle_flows();
-                    } else if bindings.toggle_privacy.check(key) {
-                        app.toggle_privacy();
-                    } else if bindings.contract_hosts_min.check(key) {
-                        app.contract_hosts_min();
-                    } else if bindings.expand_hosts_max.check(key) {
-                        app.expand_hosts_max();
-                    } else if bindings.contract_hosts.check(key) {
-                        app.contract_hosts();
-                    } else if bindings.expand_hosts.check(key) {
-                        app.expand_hosts();
-                    } else if bindings.chart_zoom_in.check(key) {
-                        app.zoom_in();
-                    } else if bindings.chart_zoom_out.check(key) {
-                        app.zoom_out();
-                    } else if bindings.clear_trace_data.check(key) {
-                        app.clear();
-                        app.clear_trace_data();
-                    } else if bindings.clear_dns_cache.check(key) {
-                        app.resolver.flush();
-                    } else if bindings.clear_selection.check(key) {
-                        app.clear();
-                    } else if bindings.toggle_as_info.check(key) {
-                        app.toggle_asinfo();
-                    } else if bindings.toggle_hop_details.check(key) {
-                        app.toggle_hop_details();
-                    } else if bindings.quit.check(key) || CTRL_C.check(key) {
-                        return Ok(());
-                    }
+        let frame = terminal.draw(|f| render::app::render(f, &mut app))?;
+        if app.save_image_requested.is_some() {
+            app.save_image(frame.buffer);
+        }
+        if app.frozen_start.is_none() && app.is_stable() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        if now >= next_refresh {
+            next_refresh = now + app.tui_config.refresh_rate;
+        }
+        if event::poll(next_refresh.saturating_duration_since(now))? {
+            loop {
+                if handle_event(&mut app, &event::read()?) {
+                    return Ok(());
+                }
+                if !event::poll(Duration::ZERO)? {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Handle an input event.
+///
+/// Returns true if the application should quit.
+fn handle_event(app: &mut TuiApp, event: &Event) -> bool {
+    if let Event::Key(key) = *event {
+        if key.kind == KeyEventKind::Press && app.note_editor.is_some() {
+            handle_note_key(app, key);
+        } else if key.kind == KeyEventKind::Press {
+            match app.tui_config.bindings.command(key) {
+                Some(command) if app.show_help => handle_help_command(app, command),
+                Some(command) if app.show_settings => {
+                    handle_settings_command(app, command);
+                }
+                Some(TuiCommandItem::Quit) => return true,
+                Some(command) => handle_command(app, command),
+                None if CTRL_C.check(key) && !app.show_help && !app.show_settings => {
+                    return true;
                 }
+                None => {}
             }
         }
     }
+    false
+}
+
+/// Handle a key while the note editor is shown.
+///
+/// Keys are not mapped to commands whilst editing a note, such that any character may be typed.
+fn handle_note_key(app: &mut TuiApp, key: KeyEvent) {
+    match key.code {
+        _ if CTRL_C.check(key) => app.cancel_note(),
+        KeyCode::Enter => app.save_note(),
+        KeyCode::Esc => app.cancel_note(),
+        KeyCode::Backspace => app.delete_note_char(),
+        KeyCode::Char(c) => app.insert_note_char(c),
+        _ => {}
+    }
+}
+
+/// Handle a command while the help dialog is shown.
+fn handle_help_command(app: &mut TuiApp, command: TuiCommandItem) {
+    match command {
+        TuiCommandItem::ToggleHelp
+        | TuiCommandItem::ToggleHelpAlt
+        | TuiCommandItem::ClearSelection
+        | TuiCommandItem::Quit => app.toggle_help(),
+        TuiCommandItem::ToggleSettings => {
+            app.toggle_help();
+            app.toggle_settings();
+        }
+        command => {
+            if let Some(tab) = settings_tab(command) {
+                app.toggle_help();
+                app.show_settings_columns(tab);
+            }
+        }
+    }
+}
+
+/// Handle a command while the settings dialog is shown.
+fn handle_settings_command(app: &mut TuiApp, command: TuiCommandItem) {
+    match command {
+        TuiCommandItem::ToggleSettings | TuiCommandItem::ClearSelection | TuiCommandItem::Quit => {
+            app.toggle_settings();
+        }
+        TuiCommandItem::PreviousTrace => app.previous_settings_tab(),
+        TuiCommandItem::NextTrace => app.next_settings_tab(),
+        TuiCommandItem::NextHop => app.next_settings_item(),
+        TuiCommandItem::PreviousHop => app.previous_settings_item(),
+        TuiCommandItem::ToggleChart => app.toggle_column_visibility(),
+        TuiCommandItem::ToggleColumnGroup => app.toggle_column_group(),
+        TuiCommandItem::NextHopAddress => app.move_column_down(),
+        TuiCommandItem::PreviousHopAddress => app.move_column_up(),
+        command => {
+            if let Some(tab) = settings_tab(command) {
+                app.show_settings_columns(tab);
+            }
+        }
+    }
+}
+
+/// Handle a command in the main view.
+fn handle_command(app: &mut TuiApp, command: TuiCommandItem) {
+    match command {
+        TuiCommandItem::ToggleHelp | TuiCommandItem::ToggleHelpAlt => app.toggle_help(),
+        TuiCommandItem::ToggleSettings => app.toggle_settings(),
+        TuiCommandItem::NextHop => app.next_hop(),
+        TuiCommandItem::PreviousHop => app.previous_hop(),
+        TuiCommandItem::SelectWorstLossHop => app.select_worst_loss_hop(),
+        TuiCommandItem::SelectWorstLatencyHop => app.select_worst_latency_hop(),
+        TuiCommandItem::PreviousTrace if app.show_flows => app.previous_flow(),
+        TuiCommandItem::PreviousTrace => app.previous_trace(),
+        TuiCommandItem::NextTrace if app.show_flows => app.next_flow(),
+        TuiCommandItem::NextTrace => app.next_trace(),
+        TuiCommandItem::NextHopAddress => app.next_hop_address(),
+        TuiCommandItem::PreviousHopAddress => app.previous_hop_address(),
+        TuiCommandItem::AddressModeIp => app.tui_config.address_mode = AddressMode::Ip,
+        TuiCommandItem::AddressModeHost => app.tui_config.address_mode = AddressMode::Host,
+        TuiCommandItem::AddressModeBoth => app.tui_config.address_mode = AddressMode::Both,
+        TuiCommandItem::ToggleFreeze => app.toggle_freeze(),
+        TuiCommandItem::ToggleChart => app.toggle_chart(),
+        TuiCommandItem::ToggleMap => app.toggle_map(),
+        TuiCommandItem::ToggleFlows => app.toggle_flows(),
+        TuiCommandItem::ToggleFlowSort => app.toggle_flow_sort(),
+        TuiCommandItem::ToggleHopSort => app.toggle_hop_sort(),
+        TuiCommandItem::TogglePrivacy => app.toggle_privacy(),
+        TuiCommandItem::ContractHostsMin => app.contract_hosts_min(),
+        TuiCommandItem::ExpandHostsMax => app.expand_hosts_max(),
+        TuiCommandItem::ContractHosts => app.contract_hosts(),
+        TuiCommandItem::ExpandHosts => app.expand_hosts(),
+        TuiCommandItem::ChartZoomIn => app.zoom_in(),
+        TuiCommandItem::ChartZoomOut => app.zoom_out(),
+        TuiCommandItem::ClearTraceData => {
+            app.clear();
+            app.clear_trace_data();
+        }
+        TuiCommandItem::ClearDnsCache => app.resolver.flush(),
+        TuiCommandItem::ClearSelection => app.clear(),
+        TuiCommandItem::ToggleASInfo => app.toggle_asinfo(),
+        TuiCommandItem::ToggleAsColors => app.toggle_as_colors(),
+        TuiCommandItem::ToggleSummaryFooter => app.toggle_summary_footer(),
+        TuiCommandItem::ToggleRttDeltas => app.toggle_rtt_deltas(),
+        TuiCommandItem::ToggleTrailingHop => app.toggle_trailing_hop(),
+        TuiCommandItem::SaveImage => app.request_save_image(ImageFormat::Png),
+        TuiCommandItem::SaveAnsi => app.request_save_image(ImageFormat::Ansi),
+        TuiCommandItem::ToggleHopPaused => app.toggle_hop_paused(),
+        TuiCommandItem::ToggleTracePaused => app.toggle_trace_paused(),
+        TuiCommandItem::ToggleHopDetails => app.toggle_hop_details(),
+        TuiCommandItem::NextColumn => app.next_column(),
+        TuiCommandItem::PreviousColumn => app.previous_column(),
+        TuiCommandItem::WidenColumn => app.widen_column(),
+        TuiCommandItem::NarrowColumn => app.narrow_column(),
+        TuiCommandItem::ResetColumnWidth => app.reset_column_width(),
+        TuiCommandItem::DismissWarning => app.dismiss_warning(),
+        TuiCommandItem::ContractStatsWindow => app.contract_stats_window(),
+        TuiCommandItem::ExpandStatsWindow => app.expand_stats_window(),
+        TuiCommandItem::EditNote => app.edit_note(),
+        command => {
+            if let Some(tab) = settings_tab(command) {
+                app.show_settings_columns(tab);
+            }
+        }
+    }
+}
+
+/// The settings tab shown by a command, if any.
+const fn settings_tab(command: TuiCommandItem) -> Option<usize> {
+    match command {
+        TuiCommandItem::ToggleSettingsTui => Some(0),
+        TuiCommandItem::ToggleSettingsTrace => Some(1),
+        TuiCommandItem::ToggleSettingsDns => Some(2),
+        TuiCommandItem::ToggleSettingsGeoip => Some(3),
+        TuiCommandItem::ToggleSettingsBindings => Some(4),
+        TuiCommandItem::ToggleSettingsTheme => Some(5),
+        TuiCommandItem::ToggleSettingsColumns => Some(6),
+        _ => None,
+    }
 }
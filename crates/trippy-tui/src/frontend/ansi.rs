@@ -0,0 +1,134 @@
+use anyhow::Context;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use std::fmt::Write;
+use std::path::Path;
+use unicode_width::UnicodeWidthStr;
+
+/// The SGR parameters of each modifier.
+const MODIFIERS: [(Modifier, u8); 9] = [
+    (Modifier::BOLD, 1),
+    (Modifier::DIM, 2),
+    (Modifier::ITALIC, 3),
+    (Modifier::UNDERLINED, 4),
+    (Modifier::SLOW_BLINK, 5),
+    (Modifier::RAPID_BLINK, 6),
+    (Modifier::REVERSED, 7),
+    (Modifier::HIDDEN, 8),
+    (Modifier::CROSSED_OUT, 9),
+];
+
+/// Save a rendered frame as text with ANSI escape codes.
+pub fn save_ansi(buffer: &Buffer, path: &Path) -> anyhow::Result<()> {
+    std::fs::write(path, to_ansi(buffer))
+        .with_context(|| format!("failed to create ansi file: {}", path.display()))
+}
+
+/// Render a frame as text with ANSI escape codes.
+///
+/// An SGR escape sequence is written whenever the style changes between cells and the style is
+/// reset at the end of every line.  The cells covered by the preceding cell of a wide glyph, such
+/// as an emoji, are not written.
+fn to_ansi(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut style = None;
+        let mut skip = 0;
+        for x in area.left()..area.right() {
+            let cell = &buffer[(x, y)];
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                out.push_str(&sgr(cell));
+                style = Some(cell_style);
+            }
+            out.push_str(cell.symbol());
+            skip = cell.symbol().width().saturating_sub(1);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// The SGR escape sequence which resets the style and then applies the style of a cell.
+fn sgr(cell: &Cell) -> String {
+    let mut params = vec![String::from("0")];
+    params.extend(
+        MODIFIERS
+            .iter()
+            .filter(|(modifier, _)| cell.modifier.contains(*modifier))
+            .map(|(_, param)| param.to_string()),
+    );
+    params.extend(color_param(cell.fg, 30));
+    params.extend(color_param(cell.bg, 40));
+    let mut out = String::from("\x1b[");
+    _ = write!(out, "{}m", params.join(";"));
+    out
+}
+
+/// The SGR parameter of a color, where `base` is 30 for the foreground and 40 for the background.
+///
+/// No parameter is needed for the default (reset) color.
+fn color_param(color: Color, base: u8) -> Option<String> {
+    let named = |index: u8| {
+        if index < 8 {
+            base + index
+        } else {
+            base + 60 + index - 8
+        }
+    };
+    match color {
+        Color::Reset => None,
+        Color::Black => Some(named(0).to_string()),
+        Color::Red => Some(named(1).to_string()),
+        Color::Green => Some(named(2).to_string()),
+        Color::Yellow => Some(named(3).to_string()),
+        Color::Blue => Some(named(4).to_string()),
+        Color::Magenta => Some(named(5).to_string()),
+        Color::Cyan => Some(named(6).to_string()),
+        Color::Gray => Some(named(7).to_string()),
+        Color::DarkGray => Some(named(8).to_string()),
+        Color::LightRed => Some(named(9).to_string()),
+        Color::LightGreen => Some(named(10).to_string()),
+        Color::LightYellow => Some(named(11).to_string()),
+        Color::LightBlue => Some(named(12).to_string()),
+        Color::LightMagenta => Some(named(13).to_string()),
+        Color::LightCyan => Some(named(14).to_string()),
+        Color::White => Some(named(15).to_string()),
+        Color::Indexed(index) => Some(format!("{};5;{index}", base + 8)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{r};{g};{b}", base + 8)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_to_ansi() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+        buffer.set_string(0, 0, "ab", Style::default().fg(Color::Red).bg(Color::Blue));
+        buffer.set_string(2, 0, "c", Style::default().add_modifier(Modifier::BOLD));
+        buffer.set_string(0, 1, "🟢", Style::default().fg(Color::Rgb(1, 2, 3)));
+        buffer.set_string(2, 1, "d", Style::default().bg(Color::Indexed(200)));
+        assert_eq!(
+            "\x1b[0;31;44mab\x1b[0;1mc\x1b[0m \x1b[0m\n\x1b[0;38;2;1;2;3m🟢\x1b[0;48;5;200md\x1b[0m \x1b[0m\n",
+            to_ansi(&buffer)
+        );
+    }
+
+    #[test]
+    fn test_color_param() {
+        assert_eq!(None, color_param(Color::Reset, 30));
+        assert_eq!(Some(String::from("30")), color_param(Color::Black, 30));
+        assert_eq!(Some(String::from("47")), color_param(Color::Gray, 40));
+        assert_eq!(Some(String::from("90")), color_param(Color::DarkGray, 30));
+        assert_eq!(Some(String::from("107")), color_param(Color::White, 40));
+    }
+}
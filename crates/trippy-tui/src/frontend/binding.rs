@@ -1,10 +1,10 @@
-use crate::config::{TuiBindings, TuiKeyBinding};
+use crate::config::{TuiBindings, TuiCommandItem, TuiKeyBinding};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use itertools::Itertools;
 use std::fmt::{Display, Formatter};
 
 /// Tui key bindings.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Bindings {
     pub toggle_help: KeyBinding,
     pub toggle_help_alt: KeyBinding,
@@ -41,7 +41,42 @@ pub struct Bindings {
     pub clear_selection: KeyBinding,
     pub toggle_as_info: KeyBinding,
     pub toggle_hop_details: KeyBinding,
+    pub previous_column: KeyBinding,
+    pub next_column: KeyBinding,
+    pub narrow_column: KeyBinding,
+    pub widen_column: KeyBinding,
+    pub reset_column_width: KeyBinding,
+    pub dismiss_warning: KeyBinding,
+    pub contract_stats_window: KeyBinding,
+    pub expand_stats_window: KeyBinding,
+    pub select_worst_loss_hop: KeyBinding,
+    pub select_worst_latency_hop: KeyBinding,
+    pub toggle_as_colors: KeyBinding,
+    pub toggle_summary_footer: KeyBinding,
+    pub toggle_rtt_deltas: KeyBinding,
+    pub edit_note: KeyBinding,
+    pub toggle_trailing_hop: KeyBinding,
+    pub save_image: KeyBinding,
+    pub toggle_hop_paused: KeyBinding,
+    pub toggle_trace_paused: KeyBinding,
+    pub toggle_column_group: KeyBinding,
+    pub toggle_flow_sort: KeyBinding,
+    pub save_ansi: KeyBinding,
+    pub toggle_hop_sort: KeyBinding,
     pub quit: KeyBinding,
+    commands: Vec<(KeyBinding, TuiCommandItem)>,
+}
+
+impl Bindings {
+    /// The command bound to a key event, if any.
+    ///
+    /// If a key is bound to more than one command the first is returned.
+    pub fn command(&self, event: KeyEvent) -> Option<TuiCommandItem> {
+        self.commands
+            .iter()
+            .find(|(binding, _)| binding.check(event))
+            .map(|(_, item)| *item)
+    }
 }
 
 impl From<TuiBindings> for Bindings {
@@ -82,7 +117,34 @@ impl From<TuiBindings> for Bindings {
             clear_selection: KeyBinding::from(value.clear_selection),
             toggle_as_info: KeyBinding::from(value.toggle_as_info),
             toggle_hop_details: KeyBinding::from(value.toggle_hop_details),
+            previous_column: KeyBinding::from(value.previous_column),
+            next_column: KeyBinding::from(value.next_column),
+            narrow_column: KeyBinding::from(value.narrow_column),
+            widen_column: KeyBinding::from(value.widen_column),
+            reset_column_width: KeyBinding::from(value.reset_column_width),
+            dismiss_warning: KeyBinding::from(value.dismiss_warning),
+            contract_stats_window: KeyBinding::from(value.contract_stats_window),
+            expand_stats_window: KeyBinding::from(value.expand_stats_window),
+            select_worst_loss_hop: KeyBinding::from(value.select_worst_loss_hop),
+            select_worst_latency_hop: KeyBinding::from(value.select_worst_latency_hop),
+            toggle_as_colors: KeyBinding::from(value.toggle_as_colors),
+            toggle_summary_footer: KeyBinding::from(value.toggle_summary_footer),
+            toggle_rtt_deltas: KeyBinding::from(value.toggle_rtt_deltas),
+            edit_note: KeyBinding::from(value.edit_note),
+            toggle_trailing_hop: KeyBinding::from(value.toggle_trailing_hop),
+            save_image: KeyBinding::from(value.save_image),
+            toggle_hop_paused: KeyBinding::from(value.toggle_hop_paused),
+            toggle_trace_paused: KeyBinding::from(value.toggle_trace_paused),
+            toggle_column_group: KeyBinding::from(value.toggle_column_group),
+            toggle_flow_sort: KeyBinding::from(value.toggle_flow_sort),
+            save_ansi: KeyBinding::from(value.save_ansi),
+            toggle_hop_sort: KeyBinding::from(value.toggle_hop_sort),
             quit: KeyBinding::from(value.quit),
+            commands: value
+                .commands()
+                .into_iter()
+                .map(|(binding, item)| (KeyBinding::from(binding), item))
+                .collect(),
         }
     }
 }
@@ -167,3 +229,34 @@ pub const CTRL_C: KeyBinding = KeyBinding {
     code: KeyCode::Char('c'),
     modifiers: KeyModifiers::CONTROL,
 };
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_default() {
+        let bindings = Bindings::from(TuiBindings::default());
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        assert_eq!(
+            Some(TuiCommandItem::Quit),
+            bindings.command(key(KeyCode::Char('q')))
+        );
+        assert_eq!(
+            Some(TuiCommandItem::ToggleSettingsDns),
+            bindings.command(key(KeyCode::Char('3')))
+        );
+        assert_eq!(None, bindings.command(key(KeyCode::F(12))));
+    }
+
+    #[test]
+    fn test_command_remapped() {
+        let bindings = Bindings::from(TuiBindings {
+            quit: TuiKeyBinding::new(KeyCode::Char('x')),
+            ..TuiBindings::default()
+        });
+        let key = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+        assert_eq!(Some(TuiCommandItem::Quit), bindings.command(key('x')));
+        assert_eq!(None, bindings.command(key('q')));
+    }
+}
@@ -1,6 +1,6 @@
-use crate::config::{TuiColumn, TuiColumns};
+use crate::config::{TuiColumn, TuiColumnGroup, TuiColumns};
 use crate::t;
-use ratatui::layout::{Constraint, Rect};
+use ratatui::layout::{Alignment, Constraint, Rect};
 use std::borrow::Cow;
 use std::fmt::{Debug, Display, Formatter};
 use strum::{EnumIter, IntoEnumIterator};
@@ -8,7 +8,13 @@ use unicode_width::UnicodeWidthStr;
 
 /// The columns to display in the hops table of the TUI.
 #[derive(Debug, Clone, Eq, PartialEq)]
-pub struct Columns(Vec<Column>);
+pub struct Columns {
+    columns: Vec<Column>,
+    /// Whether round-trip times are shown with the increment over the previous hop.
+    rtt_deltas: bool,
+    /// Named groups of columns which are toggled on or off together.
+    groups: Vec<ColumnGroup>,
+}
 
 impl Columns {
     /// Column width constraints.
@@ -19,22 +25,27 @@ impl Columns {
     /// For `Variable` columns the width is calculated by subtracting the total
     /// size of all `Fixed` columns from the width of the containing `Rect` and
     /// dividing by the number of `Variable` columns.
+    ///
+    /// Columns with a width override are treated as `Fixed` columns.
+    ///
+    /// Round-trip time columns are widened if they show the increment over the
+    /// previous hop.
     pub fn constraints(&self, rect: Rect) -> Vec<Constraint> {
         let total_fixed_width = self
             .columns()
-            .map(|c| match c.typ.width() {
+            .map(|c| match c.width(self.rtt_deltas) {
                 ColumnWidth::Fixed(width) => width,
                 ColumnWidth::Variable => 0,
             })
             .sum();
         let variable_width_count = self
             .columns()
-            .filter(|c| matches!(c.typ.width(), ColumnWidth::Variable))
+            .filter(|c| matches!(c.width(self.rtt_deltas), ColumnWidth::Variable))
             .count() as u16;
         let variable_width =
             rect.width.saturating_sub(total_fixed_width) / variable_width_count.max(1);
         self.columns()
-            .map(|c| match c.typ.width() {
+            .map(|c| match c.width(self.rtt_deltas) {
                 ColumnWidth::Fixed(width) => Constraint::Min(width),
                 ColumnWidth::Variable => Constraint::Min(variable_width),
             })
@@ -42,37 +53,130 @@ impl Columns {
     }
 
     pub fn columns(&self) -> impl Iterator<Item = &Column> {
-        self.0
+        self.columns
             .iter()
             .filter(|c| matches!(c.status, ColumnStatus::Shown))
     }
 
     pub fn all_columns(&self) -> impl Iterator<Item = &Column> {
-        self.0.iter()
+        self.columns.iter()
     }
 
     pub fn all_columns_count(&self) -> usize {
-        self.0.len()
+        self.columns.len()
     }
 
     pub fn toggle(&mut self, index: usize) {
-        self.0[index].status = match self.0[index].status {
+        self.columns[index].status = match self.columns[index].status {
             ColumnStatus::Shown => ColumnStatus::Hidden,
             ColumnStatus::Hidden => ColumnStatus::Shown,
         };
     }
 
+    /// Toggle the first group which contains the column at `index`.
+    ///
+    /// All columns of the group are shown if any are hidden, otherwise they are all hidden.
+    pub fn toggle_group(&mut self, index: usize) {
+        let Some(group) = self.group(index) else {
+            return;
+        };
+        let members = group.columns.clone();
+        let status = if self
+            .columns
+            .iter()
+            .any(|c| members.contains(&c.typ) && c.status == ColumnStatus::Hidden)
+        {
+            ColumnStatus::Shown
+        } else {
+            ColumnStatus::Hidden
+        };
+        for column in self.columns.iter_mut().filter(|c| members.contains(&c.typ)) {
+            column.status = status.clone();
+        }
+    }
+
+    /// The first group which contains the column at `index`, if any.
+    pub fn group(&self, index: usize) -> Option<&ColumnGroup> {
+        let typ = self.columns.get(index)?.typ;
+        self.groups.iter().find(|g| g.columns.contains(&typ))
+    }
+
+    pub fn groups(&self) -> &[ColumnGroup] {
+        &self.groups
+    }
+
+    pub fn set_groups(&mut self, groups: Vec<ColumnGroup>) {
+        self.groups = groups;
+    }
+
     pub fn move_down(&mut self, index: usize) {
-        if index < self.0.len() {
-            let removed = self.0.remove(index);
-            self.0.insert(index + 1, removed);
+        if index < self.columns.len() {
+            let removed = self.columns.remove(index);
+            self.columns.insert(index + 1, removed);
         }
     }
 
     pub fn move_up(&mut self, index: usize) {
         if index > 0 {
-            let removed = self.0.remove(index);
-            self.0.insert(index - 1, removed);
+            let removed = self.columns.remove(index);
+            self.columns.insert(index - 1, removed);
+        }
+    }
+
+    /// Whether round-trip times are shown with the increment over the previous hop.
+    pub const fn rtt_deltas(&self) -> bool {
+        self.rtt_deltas
+    }
+
+    pub fn set_rtt_deltas(&mut self, rtt_deltas: bool) {
+        self.rtt_deltas = rtt_deltas;
+    }
+
+    /// The number of shown columns.
+    pub fn columns_count(&self) -> usize {
+        self.columns().count()
+    }
+
+    /// Widen the shown column at `index` by one.
+    ///
+    /// The current width is taken from the constraints for `rect` and so a
+    /// `Variable` column is switched to a fixed width override.
+    pub fn widen(&mut self, index: usize, rect: Rect) {
+        if let Some(width) = self.current_width(index, rect) {
+            self.set_width(index, Some(width.saturating_add(1).min(rect.width)));
+        }
+    }
+
+    /// Narrow the shown column at `index` by one.
+    ///
+    /// The current width is taken from the constraints for `rect` and so a
+    /// `Variable` column is switched to a fixed width override.
+    pub fn narrow(&mut self, index: usize, rect: Rect) {
+        if let Some(width) = self.current_width(index, rect) {
+            self.set_width(index, Some(width.saturating_sub(1).max(MIN_COLUMN_WIDTH)));
+        }
+    }
+
+    /// Remove any width override from the shown column at `index`.
+    pub fn reset_width(&mut self, index: usize) {
+        self.set_width(index, None);
+    }
+
+    fn current_width(&self, index: usize, rect: Rect) -> Option<u16> {
+        match self.constraints(rect).get(index) {
+            Some(Constraint::Min(width)) => Some(*width),
+            _ => None,
+        }
+    }
+
+    fn set_width(&mut self, index: usize, width: Option<u16>) {
+        if let Some(column) = self
+            .columns
+            .iter_mut()
+            .filter(|c| matches!(c.status, ColumnStatus::Shown))
+            .nth(index)
+        {
+            column.width_override = width;
         }
     }
 }
@@ -85,14 +189,18 @@ impl From<TuiColumns> for Columns {
             .map(Column::new_hidden)
             .collect();
         let all = enabled.into_iter().chain(disabled).collect();
-        Self(all)
+        Self {
+            columns: all,
+            rtt_deltas: false,
+            groups: vec![],
+        }
     }
 }
 
 impl Display for Columns {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let output: Vec<char> = self
-            .0
+            .columns
             .iter()
             .filter_map(|c| {
                 if c.status == ColumnStatus::Shown {
@@ -106,10 +214,47 @@ impl Display for Columns {
     }
 }
 
+/// The minimum width of a column with a width override.
+const MIN_COLUMN_WIDTH: u16 = 1;
+
+/// The additional width of a round-trip time column which shows the increment over the previous
+/// hop, i.e. ` (+12.3)`.
+const RTT_DELTA_WIDTH: u16 = 8;
+
+/// A named group of columns.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ColumnGroup {
+    pub name: String,
+    pub columns: Vec<ColumnType>,
+}
+
+impl From<TuiColumnGroup> for ColumnGroup {
+    fn from(value: TuiColumnGroup) -> Self {
+        Self {
+            name: value.name,
+            columns: value
+                .columns
+                .0
+                .into_iter()
+                .map(|c| Column::from(c).typ)
+                .collect(),
+        }
+    }
+}
+
+impl Display for ColumnGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let columns: String = self.columns.iter().map(|&c| char::from(c)).collect();
+        write!(f, "{}={columns}", self.name)
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Column {
     pub typ: ColumnType,
     pub status: ColumnStatus,
+    /// A fixed width which overrides the default width of the column type.
+    pub width_override: Option<u16>,
 }
 
 impl Column {
@@ -117,14 +262,29 @@ impl Column {
         Self {
             typ,
             status: ColumnStatus::Shown,
+            width_override: None,
         }
     }
     pub const fn new_hidden(typ: ColumnType) -> Self {
         Self {
             typ,
             status: ColumnStatus::Hidden,
+            width_override: None,
         }
     }
+
+    /// The width of the column, taking into account any override.
+    fn width(&self, rtt_deltas: bool) -> ColumnWidth {
+        self.width_override.map_or_else(
+            || match self.typ.width() {
+                ColumnWidth::Fixed(width) if rtt_deltas && self.typ.is_rtt() => {
+                    ColumnWidth::Fixed(width + RTT_DELTA_WIDTH)
+                }
+                width => width,
+            },
+            ColumnWidth::Fixed,
+        )
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -143,7 +303,7 @@ impl Display for ColumnStatus {
 }
 
 /// A TUI hops table column.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, EnumIter)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, EnumIter)]
 pub enum ColumnType {
     /// The ttl for a hop.
     Ttl,
@@ -189,6 +349,24 @@ pub enum ColumnType {
     LastNatStatus,
     /// The number of probes that failed for a hop.
     Failed,
+    /// The provider class of the AS for a hop.
+    Provider,
+    /// The estimated path asymmetry for a hop.
+    Asymmetry,
+    /// The protocol of the probes for a hop.
+    Protocol,
+    /// Whether a hop is likely to be rate limiting ICMP.
+    RateLimit,
+    /// The exponentially weighted % of recent packets lost for a hop.
+    RecentLoss,
+    /// Whether a hop is likely to be the ingress or egress of a tunnel.
+    Tunnel,
+    /// The MPLS labels of the last response for a hop.
+    Mpls,
+    /// The AS number of a hop.
+    Asn,
+    /// The `GeoIp` location of a hop.
+    GeoLoc,
 }
 
 impl From<ColumnType> for char {
@@ -216,6 +394,15 @@ impl From<ColumnType> for char {
             ColumnType::LastIcmpPacketCode => 'C',
             ColumnType::LastNatStatus => 'N',
             ColumnType::Failed => 'f',
+            ColumnType::Provider => 'p',
+            ColumnType::Asymmetry => 'y',
+            ColumnType::Protocol => 'e',
+            ColumnType::RateLimit => 'R',
+            ColumnType::RecentLoss => 'L',
+            ColumnType::Tunnel => 'U',
+            ColumnType::Mpls => 'M',
+            ColumnType::Asn => 'n',
+            ColumnType::GeoLoc => 'G',
         }
     }
 }
@@ -245,6 +432,15 @@ impl From<TuiColumn> for Column {
             TuiColumn::LastIcmpPacketCode => Self::new_shown(ColumnType::LastIcmpPacketCode),
             TuiColumn::LastNatStatus => Self::new_shown(ColumnType::LastNatStatus),
             TuiColumn::Failed => Self::new_shown(ColumnType::Failed),
+            TuiColumn::Provider => Self::new_shown(ColumnType::Provider),
+            TuiColumn::Asymmetry => Self::new_shown(ColumnType::Asymmetry),
+            TuiColumn::Protocol => Self::new_shown(ColumnType::Protocol),
+            TuiColumn::RateLimit => Self::new_shown(ColumnType::RateLimit),
+            TuiColumn::RecentLoss => Self::new_shown(ColumnType::RecentLoss),
+            TuiColumn::Tunnel => Self::new_shown(ColumnType::Tunnel),
+            TuiColumn::Mpls => Self::new_shown(ColumnType::Mpls),
+            TuiColumn::Asn => Self::new_shown(ColumnType::Asn),
+            TuiColumn::GeoLoc => Self::new_shown(ColumnType::GeoLoc),
         }
     }
 }
@@ -281,9 +477,24 @@ impl ColumnType {
             Self::LastIcmpPacketCode => t!("column_code"),
             Self::LastNatStatus => t!("column_nat"),
             Self::Failed => t!("column_fail"),
+            Self::Provider => t!("column_provider"),
+            Self::Asymmetry => t!("column_asymmetry"),
+            Self::Protocol => t!("column_proto"),
+            Self::RateLimit => t!("column_rate_limit"),
+            Self::RecentLoss => t!("column_recent_loss"),
+            Self::Tunnel => t!("column_tunnel"),
+            Self::Mpls => t!("column_mpls"),
+            Self::Asn => t!("column_asn"),
+            Self::GeoLoc => t!("column_geoloc"),
         }
     }
 
+    /// Whether the column shows a round-trip time which may include the increment over the
+    /// previous hop.
+    pub const fn is_rtt(self) -> bool {
+        matches!(self, Self::Last | Self::Average | Self::Best | Self::Worst)
+    }
+
     /// The width of the column.
     ///
     /// For most columns the width is calculated based on the column name in
@@ -319,6 +530,55 @@ impl ColumnType {
             Self::LastIcmpPacketCode => ColumnWidth::Fixed(width.max(7)),
             Self::LastNatStatus => ColumnWidth::Fixed(width.max(7)),
             Self::Failed => ColumnWidth::Fixed(width.max(7)),
+            Self::Provider => ColumnWidth::Fixed(width.max(9)),
+            Self::Asymmetry => ColumnWidth::Fixed(width.max(7)),
+            Self::Protocol => ColumnWidth::Fixed(width.max(7)),
+            Self::RateLimit => ColumnWidth::Fixed(width.max(7)),
+            Self::RecentLoss => ColumnWidth::Fixed(width.max(8)),
+            Self::Tunnel => ColumnWidth::Fixed(width.max(8)),
+            Self::Mpls => ColumnWidth::Fixed(width.max(14)),
+            Self::Asn => ColumnWidth::Fixed(width.max(9)),
+            Self::GeoLoc => ColumnWidth::Fixed(width.max(20)),
+        }
+    }
+
+    /// The alignment of the column.
+    ///
+    /// Numeric columns are right aligned, such that the digits and decimal points of each row line
+    /// up, and all other columns are left aligned.
+    pub const fn alignment(self) -> Alignment {
+        match self {
+            Self::Ttl
+            | Self::LossPct
+            | Self::Sent
+            | Self::Received
+            | Self::Last
+            | Self::Average
+            | Self::Best
+            | Self::Worst
+            | Self::StdDev
+            | Self::Jitter
+            | Self::Javg
+            | Self::Jmax
+            | Self::Jinta
+            | Self::LastSrcPort
+            | Self::LastDestPort
+            | Self::LastSeq
+            | Self::LastIcmpPacketCode
+            | Self::Failed
+            | Self::RecentLoss => Alignment::Right,
+            Self::Host
+            | Self::Status
+            | Self::LastIcmpPacketType
+            | Self::LastNatStatus
+            | Self::Provider
+            | Self::Asymmetry
+            | Self::Protocol
+            | Self::RateLimit
+            | Self::Tunnel
+            | Self::Mpls
+            | Self::Asn
+            | Self::GeoLoc => Alignment::Left,
         }
     }
 }
@@ -356,30 +616,43 @@ mod tests {
         let columns = Columns::from(tui_columns);
         assert_eq!(
             columns,
-            Columns(vec![
-                Column::new_shown(ColumnType::Ttl),
-                Column::new_shown(ColumnType::Host),
-                Column::new_shown(ColumnType::LossPct),
-                Column::new_shown(ColumnType::Sent),
-                Column::new_shown(ColumnType::Received),
-                Column::new_shown(ColumnType::Last),
-                Column::new_shown(ColumnType::Average),
-                Column::new_shown(ColumnType::Best),
-                Column::new_shown(ColumnType::Worst),
-                Column::new_shown(ColumnType::StdDev),
-                Column::new_shown(ColumnType::Status),
-                Column::new_hidden(ColumnType::Jitter),
-                Column::new_hidden(ColumnType::Javg),
-                Column::new_hidden(ColumnType::Jmax),
-                Column::new_hidden(ColumnType::Jinta),
-                Column::new_hidden(ColumnType::LastSrcPort),
-                Column::new_hidden(ColumnType::LastDestPort),
-                Column::new_hidden(ColumnType::LastSeq),
-                Column::new_hidden(ColumnType::LastIcmpPacketType),
-                Column::new_hidden(ColumnType::LastIcmpPacketCode),
-                Column::new_hidden(ColumnType::LastNatStatus),
-                Column::new_hidden(ColumnType::Failed),
-            ])
+            Columns {
+                columns: vec![
+                    Column::new_shown(ColumnType::Ttl),
+                    Column::new_shown(ColumnType::Host),
+                    Column::new_shown(ColumnType::LossPct),
+                    Column::new_shown(ColumnType::Sent),
+                    Column::new_shown(ColumnType::Received),
+                    Column::new_shown(ColumnType::Last),
+                    Column::new_shown(ColumnType::Average),
+                    Column::new_shown(ColumnType::Best),
+                    Column::new_shown(ColumnType::Worst),
+                    Column::new_shown(ColumnType::StdDev),
+                    Column::new_shown(ColumnType::Status),
+                    Column::new_hidden(ColumnType::Jitter),
+                    Column::new_hidden(ColumnType::Javg),
+                    Column::new_hidden(ColumnType::Jmax),
+                    Column::new_hidden(ColumnType::Jinta),
+                    Column::new_hidden(ColumnType::LastSrcPort),
+                    Column::new_hidden(ColumnType::LastDestPort),
+                    Column::new_hidden(ColumnType::LastSeq),
+                    Column::new_hidden(ColumnType::LastIcmpPacketType),
+                    Column::new_hidden(ColumnType::LastIcmpPacketCode),
+                    Column::new_hidden(ColumnType::LastNatStatus),
+                    Column::new_hidden(ColumnType::Failed),
+                    Column::new_hidden(ColumnType::Provider),
+                    Column::new_hidden(ColumnType::Asymmetry),
+                    Column::new_hidden(ColumnType::Protocol),
+                    Column::new_hidden(ColumnType::RateLimit),
+                    Column::new_hidden(ColumnType::RecentLoss),
+                    Column::new_hidden(ColumnType::Tunnel),
+                    Column::new_hidden(ColumnType::Mpls),
+                    Column::new_hidden(ColumnType::Asn),
+                    Column::new_hidden(ColumnType::GeoLoc),
+                ],
+                rtt_deltas: false,
+                groups: vec![],
+            }
         );
     }
 
@@ -414,6 +687,41 @@ mod tests {
         assert_eq!(column_type.width(), *width);
     }
 
+    #[test_case(ColumnType::Ttl, Alignment::Right)]
+    #[test_case(ColumnType::Host, Alignment::Left)]
+    #[test_case(ColumnType::LossPct, Alignment::Right)]
+    #[test_case(ColumnType::Sent, Alignment::Right)]
+    #[test_case(ColumnType::Received, Alignment::Right)]
+    #[test_case(ColumnType::Last, Alignment::Right)]
+    #[test_case(ColumnType::Average, Alignment::Right)]
+    #[test_case(ColumnType::Best, Alignment::Right)]
+    #[test_case(ColumnType::Worst, Alignment::Right)]
+    #[test_case(ColumnType::StdDev, Alignment::Right)]
+    #[test_case(ColumnType::Status, Alignment::Left)]
+    #[test_case(ColumnType::Jitter, Alignment::Right)]
+    #[test_case(ColumnType::Javg, Alignment::Right)]
+    #[test_case(ColumnType::Jmax, Alignment::Right)]
+    #[test_case(ColumnType::Jinta, Alignment::Right)]
+    #[test_case(ColumnType::LastSrcPort, Alignment::Right)]
+    #[test_case(ColumnType::LastDestPort, Alignment::Right)]
+    #[test_case(ColumnType::LastSeq, Alignment::Right)]
+    #[test_case(ColumnType::LastIcmpPacketType, Alignment::Left)]
+    #[test_case(ColumnType::LastIcmpPacketCode, Alignment::Right)]
+    #[test_case(ColumnType::LastNatStatus, Alignment::Left)]
+    #[test_case(ColumnType::Failed, Alignment::Right)]
+    #[test_case(ColumnType::Provider, Alignment::Left)]
+    #[test_case(ColumnType::Asymmetry, Alignment::Left)]
+    #[test_case(ColumnType::Protocol, Alignment::Left)]
+    #[test_case(ColumnType::RateLimit, Alignment::Left)]
+    #[test_case(ColumnType::RecentLoss, Alignment::Right)]
+    #[test_case(ColumnType::Tunnel, Alignment::Left)]
+    #[test_case(ColumnType::Mpls, Alignment::Left)]
+    #[test_case(ColumnType::Asn, Alignment::Left)]
+    #[test_case(ColumnType::GeoLoc, Alignment::Left)]
+    fn test_column_alignment(column_type: ColumnType, alignment: Alignment) {
+        assert_eq!(alignment, column_type.alignment());
+    }
+
     #[test]
     fn test_column_constraints() {
         let columns = Columns::from(TuiColumns::default());
@@ -436,49 +744,137 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_column_constraints_rtt_deltas() {
+        let mut columns = Columns::from(TuiColumns::default());
+        columns.set_rtt_deltas(true);
+        let constraints = columns.constraints(Rect::new(0, 0, 120, 0));
+        assert_eq!(
+            vec![
+                Min(4),
+                Min(19),
+                Min(8),
+                Min(7),
+                Min(7),
+                Min(15),
+                Min(15),
+                Min(15),
+                Min(15),
+                Min(8),
+                Min(7)
+            ],
+            constraints
+        );
+    }
+
+    #[test]
+    fn test_columns_toggle_group() {
+        let mut cols = Columns::from(TuiColumns::try_from("holavb").unwrap());
+        cols.set_groups(vec![ColumnGroup {
+            name: String::from("rtt"),
+            columns: vec![ColumnType::Last, ColumnType::Average, ColumnType::Best],
+        }]);
+        assert_eq!(None, cols.group(0));
+        assert_eq!(Some("rtt"), cols.group(4).map(|g| g.name.as_str()));
+        cols.toggle_group(4);
+        assert_eq!("hol", format!("{cols}"));
+        cols.toggle(3);
+        assert_eq!("hola", format!("{cols}"));
+        cols.toggle_group(3);
+        assert_eq!("holavb", format!("{cols}"));
+        cols.toggle_group(0);
+        assert_eq!("holavb", format!("{cols}"));
+    }
+
     /// Expect to test the Column Into <char> flow.
     #[test]
     fn test_columns_into_string_short() {
-        let cols = Columns(vec![
-            Column::new_shown(ColumnType::Ttl),
-            Column::new_shown(ColumnType::Host),
-            Column::new_shown(ColumnType::LossPct),
-            Column::new_shown(ColumnType::Sent),
-        ]);
+        let cols = Columns {
+            columns: vec![
+                Column::new_shown(ColumnType::Ttl),
+                Column::new_shown(ColumnType::Host),
+                Column::new_shown(ColumnType::LossPct),
+                Column::new_shown(ColumnType::Sent),
+            ],
+            rtt_deltas: false,
+            groups: vec![],
+        };
         assert_eq!("hols", format!("{cols}"));
     }
 
     /// Happy path test for full set of columns.
     #[test]
     fn test_columns_into_string_happy_path() {
-        let cols = Columns(vec![
-            Column::new_shown(ColumnType::Ttl),
-            Column::new_shown(ColumnType::Host),
-            Column::new_shown(ColumnType::LossPct),
-            Column::new_shown(ColumnType::Sent),
-            Column::new_shown(ColumnType::Received),
-            Column::new_shown(ColumnType::Last),
-            Column::new_shown(ColumnType::Average),
-            Column::new_shown(ColumnType::Best),
-            Column::new_shown(ColumnType::Worst),
-            Column::new_shown(ColumnType::StdDev),
-            Column::new_shown(ColumnType::Status),
-        ]);
+        let cols = Columns {
+            columns: vec![
+                Column::new_shown(ColumnType::Ttl),
+                Column::new_shown(ColumnType::Host),
+                Column::new_shown(ColumnType::LossPct),
+                Column::new_shown(ColumnType::Sent),
+                Column::new_shown(ColumnType::Received),
+                Column::new_shown(ColumnType::Last),
+                Column::new_shown(ColumnType::Average),
+                Column::new_shown(ColumnType::Best),
+                Column::new_shown(ColumnType::Worst),
+                Column::new_shown(ColumnType::StdDev),
+                Column::new_shown(ColumnType::Status),
+            ],
+            rtt_deltas: false,
+            groups: vec![],
+        };
         assert_eq!("holsravbwdt", format!("{cols}"));
     }
 
     /// Reverse subset test for subset of columns.
     #[test]
     fn test_columns_into_string_reverse_str() {
-        let cols = Columns(vec![
-            Column::new_shown(ColumnType::Status),
-            Column::new_shown(ColumnType::Last),
-            Column::new_shown(ColumnType::StdDev),
-            Column::new_shown(ColumnType::Worst),
-            Column::new_shown(ColumnType::Best),
-            Column::new_shown(ColumnType::Average),
-            Column::new_shown(ColumnType::Received),
-        ]);
+        let cols = Columns {
+            columns: vec![
+                Column::new_shown(ColumnType::Status),
+                Column::new_shown(ColumnType::Last),
+                Column::new_shown(ColumnType::StdDev),
+                Column::new_shown(ColumnType::Worst),
+                Column::new_shown(ColumnType::Best),
+                Column::new_shown(ColumnType::Average),
+                Column::new_shown(ColumnType::Received),
+            ],
+            rtt_deltas: false,
+            groups: vec![],
+        };
         assert_eq!("tadwbvr", format!("{cols}"));
     }
+
+    #[test]
+    fn test_column_widen_and_narrow_fixed() {
+        let mut columns = Columns::from(TuiColumns::default());
+        let rect = Rect::new(0, 0, 80, 0);
+        columns.widen(2, rect);
+        columns.widen(2, rect);
+        assert_eq!(Min(10), columns.constraints(rect)[2]);
+        columns.narrow(2, rect);
+        assert_eq!(Min(9), columns.constraints(rect)[2]);
+        columns.reset_width(2);
+        assert_eq!(Min(8), columns.constraints(rect)[2]);
+    }
+
+    #[test]
+    fn test_column_widen_variable_becomes_fixed() {
+        let mut columns = Columns::from(TuiColumns::default());
+        let rect = Rect::new(0, 0, 80, 0);
+        columns.widen(1, rect);
+        assert_eq!(Some(12), columns.columns().nth(1).unwrap().width_override);
+        assert_eq!(Min(12), columns.constraints(Rect::new(0, 0, 120, 0))[1]);
+        columns.reset_width(1);
+        assert_eq!(Min(51), columns.constraints(Rect::new(0, 0, 120, 0))[1]);
+    }
+
+    #[test]
+    fn test_column_narrow_min_width() {
+        let mut columns = Columns::from(TuiColumns::default());
+        let rect = Rect::new(0, 0, 80, 0);
+        for _ in 0..10 {
+            columns.narrow(0, rect);
+        }
+        assert_eq!(Min(1), columns.constraints(rect)[0]);
+    }
 }
@@ -1,8 +1,16 @@
-use crate::config::{AddressMode, AsMode, GeoIpMode, TuiColumns, TuiTheme};
+use crate::config::{
+    AddrAggregationMode, AddressMode, AsMode, AverageMode, FlowSortMode, GeoIpMode, GlyphSet,
+    HostTruncation, RttHeatMode, StatusMode, StdDevMode, StdDevScope, TargetTtlMode,
+    TimeExceededRttMode, TrailingHopMode, TuiColumnGroups, TuiColumns, TuiRttPrecision, TuiTheme,
+    UnresponsiveHopMode,
+};
 use crate::config::{IcmpExtensionMode, TuiBindings};
 use crate::frontend::binding::Bindings;
-use crate::frontend::columns::Columns;
+use crate::frontend::columns::{Column, ColumnGroup, ColumnType, Columns};
+use crate::frontend::glyphs::Glyphs;
 use crate::frontend::theme::Theme;
+use crate::provider::ProviderLookup;
+use std::collections::HashMap;
 use std::time::Duration;
 
 /// Tui configuration.
@@ -12,6 +20,8 @@ pub struct TuiConfig {
     pub refresh_rate: Duration,
     /// The maximum ttl of hops which will be masked for privacy.
     pub privacy_max_ttl: u8,
+    /// The number of rounds without a reply from the first hop before warning.
+    pub first_hop_warning_rounds: usize,
     /// Preserve screen on exit.
     pub preserve_screen: bool,
     /// How to render addresses.
@@ -20,56 +30,208 @@ pub struct TuiConfig {
     pub lookup_as_info: bool,
     /// How to render autonomous system (AS) data.
     pub as_mode: AsMode,
+    /// Whether to tint hops by autonomous system (AS).
+    pub as_colors: bool,
+    /// Whether to show a summary footer in the hops table.
+    pub summary_footer: bool,
+    /// The round-trip time at or above which the round-trip times of a hop are shown in seconds.
+    pub rtt_compact_threshold: Option<Duration>,
+    /// How to color the `Last` round-trip time of hops.
+    pub rtt_heat_mode: RttHeatMode,
+    /// The round-trip time at the top of the fixed rtt heat gradient.
+    pub rtt_heat_max: Duration,
     /// How to render ICMP extensions.
     pub icmp_extension_mode: IcmpExtensionMode,
     /// How to render `GeoIp` data.
     pub geoip_mode: GeoIpMode,
+    /// How to render hops which have not responded.
+    pub unresponsive_hop_mode: UnresponsiveHopMode,
+    /// How to render the unresponsive trailing hop.
+    pub trailing_hop_mode: TrailingHopMode,
+    /// How to render round-trip times derived from `TimeExceeded` responses.
+    pub time_exceeded_rtt_mode: TimeExceededRttMode,
+    /// How to compute the average round-trip time of a hop.
+    pub average_mode: AverageMode,
+    /// The weight, as a percentage, of each round-trip time in the ewma average.
+    pub ewma_weight: u8,
+    /// The weight, as a percentage, of each probe in the recent loss ewma.
+    pub loss_ewma_weight: u8,
+    /// Which time-to-live values at which the target replied to show.
+    pub target_ttl_mode: TargetTtlMode,
+    /// Which side to truncate hostnames which do not fit in the hops table.
+    pub host_truncation: HostTruncation,
+    /// The glyphs used for symbols.
+    pub glyphs: Glyphs,
     /// The maximum number of addresses to show per hop.
     pub max_addrs: Option<u8>,
+    /// The maximum number of hops to display in the hops table at once.
+    pub max_display_hops: Option<u8>,
+    /// The maximum number of consecutive unresponsive hops to show beyond the last responsive hop.
+    pub max_unresponsive_hops: Option<u8>,
+    /// The number of recent rounds to compute the hop statistics over.
+    pub stats_window: Option<usize>,
+    /// How to compute the standard deviation of round-trip times.
+    pub stddev_mode: StdDevMode,
+    /// The rounds over which to compute the standard deviation of round-trip times.
+    pub stddev_scope: StdDevScope,
+    /// The number of rounds to flag path length changes for.
+    pub path_change_rounds: usize,
+    /// The duration for which a change to the path is considered recent.
+    pub path_stability_window: Duration,
+    /// How to sort the flows.
+    pub flow_sort_mode: FlowSortMode,
+    /// Whether to show how long the selected hop has been awaiting a reply.
+    pub probe_countdown: bool,
+    /// Whether to show round-trip times relative to the first responding hop.
+    pub rtt_baseline: bool,
+    /// The increase in the reverse path length at which a tunnel is suspected.
+    pub tunnel_ttl_jump: u8,
+    /// What to show in the status column.
+    pub status_mode: StatusMode,
+    /// How to show a hop which is answered by more than one address.
+    pub addr_aggregation: AddrAggregationMode,
+    /// The number of rounds the trace must be stable for before quitting.
+    pub auto_quit_stable_rounds: Option<usize>,
+    /// The maximum round-trip time standard deviation of a stable hop.
+    pub auto_quit_stable_threshold: Duration,
     /// The Tui color theme.
     pub theme: Theme,
     /// The Tui keyboard bindings.
     pub bindings: Bindings,
     /// The columns to display in the hops table.
     pub tui_columns: Columns,
+    /// The number of decimal places of individual round-trip time columns.
+    pub rtt_precision: HashMap<ColumnType, usize>,
+    /// The provider classes of autonomous systems.
+    pub provider_lookup: ProviderLookup,
     pub geoip_mmdb_file: Option<String>,
     pub dns_resolve_all: bool,
 }
 
 impl TuiConfig {
-    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
     pub fn new(
         refresh_rate: Duration,
         privacy_max_ttl: u8,
+        first_hop_warning_rounds: usize,
         preserve_screen: bool,
         address_mode: AddressMode,
         lookup_as_info: bool,
         as_mode: AsMode,
+        as_colors: bool,
+        summary_footer: bool,
+        rtt_deltas: bool,
+        rtt_compact_threshold: Option<Duration>,
+        rtt_heat_mode: RttHeatMode,
+        rtt_heat_max: Duration,
         icmp_extension_mode: IcmpExtensionMode,
         geoip_mode: GeoIpMode,
+        unresponsive_hop_mode: UnresponsiveHopMode,
+        trailing_hop_mode: TrailingHopMode,
+        time_exceeded_rtt_mode: TimeExceededRttMode,
+        average_mode: AverageMode,
+        ewma_weight: u8,
+        loss_ewma_weight: u8,
+        target_ttl_mode: TargetTtlMode,
+        host_truncation: HostTruncation,
+        glyph_set: GlyphSet,
         max_addrs: Option<u8>,
+        max_display_hops: Option<u8>,
+        max_unresponsive_hops: Option<u8>,
+        stats_window: Option<usize>,
+        stddev_mode: StdDevMode,
+        stddev_scope: StdDevScope,
+        path_change_rounds: usize,
+        path_stability_window: Duration,
+        flow_sort_mode: FlowSortMode,
+        probe_countdown: bool,
+        rtt_baseline: bool,
+        tunnel_ttl_jump: u8,
+        status_mode: StatusMode,
+        addr_aggregation: AddrAggregationMode,
+        auto_quit_stable_rounds: Option<usize>,
+        auto_quit_stable_threshold: Duration,
         tui_theme: TuiTheme,
         tui_bindings: &TuiBindings,
         tui_columns: &TuiColumns,
-
+        tui_column_groups: &TuiColumnGroups,
+        tui_rtt_precision: &TuiRttPrecision,
+        tui_provider_classes: &HashMap<u32, String>,
         geoip_mmdb_file: Option<String>,
         dns_resolve_all: bool,
     ) -> Self {
+        let mut tui_columns = Columns::from(tui_columns.clone());
+        tui_columns.set_rtt_deltas(rtt_deltas);
+        tui_columns.set_groups(
+            tui_column_groups
+                .0
+                .iter()
+                .cloned()
+                .map(ColumnGroup::from)
+                .collect(),
+        );
         Self {
             refresh_rate,
             privacy_max_ttl,
+            first_hop_warning_rounds,
             preserve_screen,
             address_mode,
             lookup_as_info,
             as_mode,
+            as_colors,
+            summary_footer,
+            rtt_compact_threshold,
+            rtt_heat_mode,
+            rtt_heat_max,
             icmp_extension_mode,
             geoip_mode,
+            unresponsive_hop_mode,
+            trailing_hop_mode,
+            time_exceeded_rtt_mode,
+            average_mode,
+            ewma_weight,
+            loss_ewma_weight,
+            target_ttl_mode,
+            host_truncation,
+            glyphs: Glyphs::from(glyph_set),
             max_addrs,
+            max_display_hops,
+            max_unresponsive_hops,
+            stats_window,
+            stddev_mode,
+            stddev_scope,
+            path_change_rounds,
+            path_stability_window,
+            flow_sort_mode,
+            probe_countdown,
+            rtt_baseline,
+            tunnel_ttl_jump,
+            status_mode,
+            addr_aggregation,
+            auto_quit_stable_rounds,
+            auto_quit_stable_threshold,
             theme: Theme::from(tui_theme),
             bindings: Bindings::from(*tui_bindings),
-            tui_columns: Columns::from(tui_columns.clone()),
+            tui_columns,
+            rtt_precision: tui_rtt_precision
+                .0
+                .iter()
+                .map(|&(column, places)| (Column::from(column).typ, usize::from(places)))
+                .collect(),
+            provider_lookup: ProviderLookup::new(tui_provider_classes),
             geoip_mmdb_file,
             dns_resolve_all,
         }
     }
+
+    /// The number of decimal places of a round-trip time column.
+    pub fn rtt_places(&self, column: ColumnType) -> usize {
+        self.rtt_precision
+            .get(&column)
+            .copied()
+            .unwrap_or(DEFAULT_RTT_PLACES)
+    }
 }
+
+/// The number of decimal places of a round-trip time column without an override.
+const DEFAULT_RTT_PLACES: usize = 1;
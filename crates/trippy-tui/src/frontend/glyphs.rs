@@ -0,0 +1,185 @@
+use crate::config::GlyphSet;
+use crate::frontend::render::widgets::sparkline::EmptyBarSymbol;
+use ratatui::symbols;
+use ratatui::symbols::Marker;
+
+/// The glyphs used for the symbols shown in the Tui.
+#[derive(Debug, Clone)]
+pub struct Glyphs {
+    /// The glyph set, either `Unicode` or `Ascii`.
+    pub set: GlyphSet,
+    /// The status of a hop which has replied to every probe.
+    pub status_ok: &'static str,
+    /// The status of a hop which has not replied to some probes.
+    pub status_hop_loss: &'static str,
+    /// The status of a hop which has not replied to any probe.
+    pub status_hop_lost: &'static str,
+    /// The status of the target which has not replied to some probes.
+    pub status_target_loss: &'static str,
+    /// The status of the target which has not replied to any probe.
+    pub status_target_lost: &'static str,
+    /// The marker for the hop at which the target was found.
+    pub target: &'static str,
+    /// The marker for a warning.
+    pub warning: &'static str,
+    /// The marker for probe failures.
+    pub failure: &'static str,
+    /// The marker for truncated text.
+    pub ellipsis: &'static str,
+    /// The marker for a location on the world map.
+    pub pin: &'static str,
+    /// The marker for the column by which the hops are sorted in ascending order.
+    pub sort_ascending: &'static str,
+    /// The marker for the column by which the hops are sorted in descending order.
+    pub sort_descending: &'static str,
+    /// The timeline marker for a round with a reply.
+    pub reply: char,
+    /// The timeline marker for a round with a round-trip time spike.
+    pub spike: char,
+    /// The timeline marker for a round without a reply.
+    pub loss: char,
+    /// The bars of the samples sparkline.
+    pub bar_set: symbols::bar::Set,
+    /// The bar of a lost sample in the samples sparkline.
+    pub lost_bar: EmptyBarSymbol,
+    /// The marker used to draw charts and the world map.
+    pub marker: Marker,
+}
+
+impl Glyphs {
+    const UNICODE: Self = Self {
+        set: GlyphSet::Unicode,
+        status_ok: "🟢",
+        status_hop_loss: "🔵",
+        status_hop_lost: "🟤",
+        status_target_loss: "🟡",
+        status_target_lost: "🔴",
+        target: "★",
+        warning: "⚠",
+        failure: "❗",
+        ellipsis: "…",
+        pin: "📍",
+        sort_ascending: "▲",
+        sort_descending: "▼",
+        reply: '·',
+        spike: '^',
+        loss: '×',
+        bar_set: symbols::bar::NINE_LEVELS,
+        lost_bar: EmptyBarSymbol::Full,
+        marker: Marker::Braille,
+    };
+
+    const ASCII: Self = Self {
+        set: GlyphSet::Ascii,
+        status_ok: "o",
+        status_hop_loss: "~",
+        status_hop_lost: "-",
+        status_target_loss: "?",
+        status_target_lost: "x",
+        target: "*",
+        warning: "!",
+        failure: "!",
+        ellipsis: "...",
+        pin: "@",
+        sort_ascending: "^",
+        sort_descending: "v",
+        reply: '.',
+        spike: '^',
+        loss: 'x',
+        bar_set: ASCII_BAR_SET,
+        lost_bar: EmptyBarSymbol::Hash,
+        marker: Marker::Dot,
+    };
+}
+
+/// The ASCII bars of the samples sparkline.
+const ASCII_BAR_SET: symbols::bar::Set = symbols::bar::Set {
+    full: "#",
+    seven_eighths: "#",
+    three_quarters: "=",
+    five_eighths: "=",
+    half: "-",
+    three_eighths: "-",
+    one_quarter: ".",
+    one_eighth: ".",
+    empty: " ",
+};
+
+impl From<GlyphSet> for Glyphs {
+    fn from(value: GlyphSet) -> Self {
+        match value {
+            GlyphSet::Auto if is_utf8_locale(locale_env().as_deref()) => Self::UNICODE,
+            GlyphSet::Auto | GlyphSet::Ascii => Self::ASCII,
+            GlyphSet::Unicode => Self::UNICODE,
+        }
+    }
+}
+
+/// The character locale from the environment, if set.
+///
+/// This is the first non-empty value of `LC_ALL`, `LC_CTYPE` and `LANG`.
+fn locale_env() -> Option<String> {
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+}
+
+/// Whether a character locale uses UTF-8.
+///
+/// If no locale is set, such as on Windows, the locale is assumed to use UTF-8.
+fn is_utf8_locale(locale: Option<&str>) -> bool {
+    locale.map_or(true, |locale| {
+        let locale = locale.to_ascii_lowercase();
+        locale.contains("utf-8") || locale.contains("utf8")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(None, true; "no locale")]
+    #[test_case(Some("en_US.UTF-8"), true; "utf-8 locale")]
+    #[test_case(Some("C.utf8"), true; "utf8 locale")]
+    #[test_case(Some("C"), false; "c locale")]
+    #[test_case(Some("POSIX"), false; "posix locale")]
+    #[test_case(Some("en_US.ISO-8859-1"), false; "latin1 locale")]
+    fn test_is_utf8_locale(locale: Option<&str>, expected: bool) {
+        assert_eq!(expected, is_utf8_locale(locale));
+    }
+
+    #[test]
+    fn test_ascii_glyphs() {
+        let glyphs = Glyphs::from(GlyphSet::Ascii);
+        let bars = glyphs.bar_set;
+        assert!([
+            glyphs.status_ok,
+            glyphs.status_hop_loss,
+            glyphs.status_hop_lost,
+            glyphs.status_target_loss,
+            glyphs.status_target_lost,
+            glyphs.target,
+            glyphs.warning,
+            glyphs.failure,
+            glyphs.ellipsis,
+            glyphs.pin,
+            glyphs.lost_bar.as_str(),
+            bars.full,
+            bars.seven_eighths,
+            bars.three_quarters,
+            bars.five_eighths,
+            bars.half,
+            bars.three_eighths,
+            bars.one_quarter,
+            bars.one_eighth,
+            bars.empty,
+        ]
+        .iter()
+        .all(|glyph| glyph.is_ascii()));
+        assert!([glyphs.reply, glyphs.spike, glyphs.loss]
+            .iter()
+            .all(char::is_ascii));
+    }
+}
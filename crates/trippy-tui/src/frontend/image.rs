@@ -0,0 +1,271 @@
+use crate::frontend::theme::to_rgb;
+use anyhow::Context;
+use font8x8::{UnicodeFonts, BASIC_FONTS, BLOCK_FONTS, BOX_FONTS, GREEK_FONTS, LATIN_FONTS};
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::Modifier;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+/// The width of a terminal cell in pixels.
+const CELL_WIDTH: usize = 8;
+
+/// The height of a terminal cell in pixels.
+///
+/// Each row of the 8x8 bundled font is drawn twice to give cells the proportions of a terminal.
+const CELL_HEIGHT: usize = 16;
+
+/// The color of text with the default (reset) foreground color.
+const DEFAULT_FG: [u8; 3] = [229, 229, 229];
+
+/// The color of the background with the default (reset) background color.
+const DEFAULT_BG: [u8; 3] = [0, 0, 0];
+
+/// Save a rendered frame as a PNG image.
+pub fn save_png(buffer: &Buffer, path: &Path) -> anyhow::Result<()> {
+    let image = rasterize(buffer);
+    let file = File::create(path)
+        .with_context(|| format!("failed to create image file: {}", path.display()))?;
+    let mut encoder = png::Encoder::new(
+        BufWriter::new(file),
+        u32::try_from(image.width)?,
+        u32::try_from(image.height)?,
+    );
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&image.pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// An RGB image.
+struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width * height * 3],
+        }
+    }
+
+    fn set(&mut self, x: usize, y: usize, rgb: [u8; 3]) {
+        if x < self.width && y < self.height {
+            let offset = (y * self.width + x) * 3;
+            self.pixels[offset..offset + 3].copy_from_slice(&rgb);
+        }
+    }
+
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, rgb: [u8; 3]) {
+        for py in y..y + height {
+            for px in x..x + width {
+                self.set(px, py, rgb);
+            }
+        }
+    }
+}
+
+/// Draw every cell of a frame with the bundled font.
+///
+/// The backgrounds of all cells are drawn before any glyph, as glyphs which are two cells wide,
+/// such as emoji, overlap the following cell.
+fn rasterize(buffer: &Buffer) -> Image {
+    let area = buffer.area;
+    let mut image = Image::new(
+        usize::from(area.width) * CELL_WIDTH,
+        usize::from(area.height) * CELL_HEIGHT,
+    );
+    let cells = || {
+        (0..area.height).flat_map(move |y| {
+            (0..area.width).map(move |x| {
+                let cell = &buffer[(area.x + x, area.y + y)];
+                let px = usize::from(x) * CELL_WIDTH;
+                let py = usize::from(y) * CELL_HEIGHT;
+                (px, py, cell)
+            })
+        })
+    };
+    for (px, py, cell) in cells() {
+        let (_, bg) = cell_colors(cell);
+        image.fill(px, py, CELL_WIDTH, CELL_HEIGHT, bg);
+    }
+    for (px, py, cell) in cells() {
+        draw_cell(&mut image, px, py, cell);
+    }
+    image
+}
+
+/// Draw the glyph of a cell.
+fn draw_cell(image: &mut Image, px: usize, py: usize, cell: &Cell) {
+    let (fg, _) = cell_colors(cell);
+    let Some(c) = cell.symbol().chars().next() else {
+        return;
+    };
+    if let Some(dots) = braille_dots(c) {
+        draw_braille(image, px, py, dots, fg);
+    } else if let Some(color) = emoji_color(c) {
+        draw_disc(image, px, py, color);
+    } else if let Some(glyph) = glyph(c) {
+        let bold = cell.modifier.contains(Modifier::BOLD);
+        draw_glyph(image, px, py, glyph, fg, bold);
+    } else {
+        draw_unknown(image, px, py, fg);
+    }
+    if cell.modifier.contains(Modifier::UNDERLINED) {
+        image.fill(px, py + CELL_HEIGHT - 2, CELL_WIDTH, 1, fg);
+    }
+    if cell.modifier.contains(Modifier::CROSSED_OUT) {
+        image.fill(px, py + CELL_HEIGHT / 2, CELL_WIDTH, 1, fg);
+    }
+}
+
+/// The glyph of the star used to mark the target hop, which is not in the bundled font.
+const STAR: [u8; 8] = [0x08, 0x1C, 0x7F, 0x3E, 0x1C, 0x36, 0x22, 0x00];
+
+/// The glyph of a character from the bundled font, if any.
+fn glyph(c: char) -> Option<[u8; 8]> {
+    if c == '★' {
+        return Some(STAR);
+    }
+    BASIC_FONTS
+        .get(c)
+        .or_else(|| LATIN_FONTS.get(c))
+        .or_else(|| BOX_FONTS.get(c))
+        .or_else(|| BLOCK_FONTS.get(c))
+        .or_else(|| GREEK_FONTS.get(c))
+}
+
+/// Draw a glyph of the bundled font, where bit 0 of each row is the leftmost pixel.
+///
+/// Bold glyphs are drawn twice, the second offset by one pixel.
+fn draw_glyph(image: &mut Image, px: usize, py: usize, glyph: [u8; 8], fg: [u8; 3], bold: bool) {
+    for (row, bits) in glyph.iter().enumerate() {
+        for col in 0..CELL_WIDTH {
+            if bits & (1 << col) != 0 {
+                image.fill(px + col, py + row * 2, 1, 2, fg);
+                if bold {
+                    image.fill(px + col + 1, py + row * 2, 1, 2, fg);
+                }
+            }
+        }
+    }
+}
+
+/// The dots of a braille pattern character, as used by charts, if any.
+fn braille_dots(c: char) -> Option<u8> {
+    u8::try_from(u32::from(c).checked_sub(0x2800)?).ok()
+}
+
+/// Draw a braille pattern as a grid of two by four dots.
+///
+/// Dots 1-3 and 4-6 are the upper three rows of the left and right columns, and dots 7 and 8
+/// are the bottom row.
+fn draw_braille(image: &mut Image, px: usize, py: usize, dots: u8, fg: [u8; 3]) {
+    const POSITIONS: [(usize, usize); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+    for (bit, (col, row)) in POSITIONS.iter().enumerate() {
+        if dots & (1 << bit) != 0 {
+            image.fill(px + 1 + col * 4, py + 1 + row * 4, 2, 2, fg);
+        }
+    }
+}
+
+/// The color of an emoji shown in the hops table, if any.
+const fn emoji_color(c: char) -> Option<[u8; 3]> {
+    match c {
+        '🟢' => Some([0, 205, 0]),
+        '🟡' => Some([205, 205, 0]),
+        '🟤' => Some([150, 90, 40]),
+        '🔴' => Some([205, 0, 0]),
+        '🔵' => Some([0, 0, 238]),
+        _ => None,
+    }
+}
+
+/// Draw a disc which is two cells wide, as emoji are.
+fn draw_disc(image: &mut Image, px: usize, py: usize, color: [u8; 3]) {
+    let size = CELL_WIDTH * 2;
+    let radius = (size as f64 - 2_f64) / 2_f64;
+    let centre = size as f64 / 2_f64;
+    for y in 0..size {
+        for x in 0..size {
+            let dx = x as f64 + 0.5 - centre;
+            let dy = y as f64 + 0.5 - centre;
+            if dx.hypot(dy) <= radius {
+                image.set(px + x, py + (CELL_HEIGHT - size) / 2 + y, color);
+            }
+        }
+    }
+}
+
+/// Draw a character which is not in the bundled font as an outlined box.
+fn draw_unknown(image: &mut Image, px: usize, py: usize, fg: [u8; 3]) {
+    image.fill(px + 1, py + 3, CELL_WIDTH - 2, 1, fg);
+    image.fill(px + 1, py + CELL_HEIGHT - 4, CELL_WIDTH - 2, 1, fg);
+    image.fill(px + 1, py + 3, 1, CELL_HEIGHT - 6, fg);
+    image.fill(px + CELL_WIDTH - 2, py + 3, 1, CELL_HEIGHT - 6, fg);
+}
+
+/// The foreground and background colors of a cell, taking into account its modifiers.
+fn cell_colors(cell: &Cell) -> ([u8; 3], [u8; 3]) {
+    let fg = to_rgb(cell.fg, DEFAULT_FG);
+    let bg = to_rgb(cell.bg, DEFAULT_BG);
+    let fg = if cell.modifier.contains(Modifier::DIM) {
+        fg.map(|c| c / 2)
+    } else {
+        fg
+    };
+    let (fg, bg) = if cell.modifier.contains(Modifier::REVERSED) {
+        (bg, fg)
+    } else {
+        (fg, bg)
+    };
+    if cell.modifier.contains(Modifier::HIDDEN) {
+        (bg, bg)
+    } else {
+        (fg, bg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::{Color, Style};
+
+    #[test]
+    fn test_rasterize() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.set_string(0, 0, "█", Style::default().fg(Color::Red).bg(Color::Blue));
+        buffer.set_string(1, 0, " ", Style::default().bg(Color::Green));
+        let image = rasterize(&buffer);
+        assert_eq!(16, image.width);
+        assert_eq!(16, image.height);
+        let pixel = |x: usize, y: usize| {
+            let offset = (y * image.width + x) * 3;
+            [
+                image.pixels[offset],
+                image.pixels[offset + 1],
+                image.pixels[offset + 2],
+            ]
+        };
+        assert_eq!([205, 0, 0], pixel(0, 0));
+        assert_eq!([205, 0, 0], pixel(7, 15));
+        assert_eq!([0, 205, 0], pixel(8, 0));
+        assert_eq!([0, 205, 0], pixel(15, 15));
+    }
+}
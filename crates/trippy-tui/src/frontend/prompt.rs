@@ -0,0 +1,241 @@
+use crate::config::{AddressFamilyConfig, Args, Mode, ProtocolConfig};
+use crate::frontend::binding::CTRL_C;
+use crate::frontend::render;
+use crate::t;
+use clap::ValueEnum;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::Terminal;
+use std::io;
+use std::net::{IpAddr, ToSocketAddrs};
+
+/// Prompt for a target and tracing options before tracing starts.
+///
+/// The prompt is initialized from, and on success updates, the given command line arguments.
+///
+/// Returns `false` if the prompt was cancelled.
+pub fn run_prompt(args: &mut Args) -> anyhow::Result<bool> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+    let res = run_prompt_app(&mut terminal, Prompt::new(args));
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(res?.map(|prompt| prompt.apply(args)).is_some())
+}
+
+fn run_prompt_app<B: Backend>(
+    terminal: &mut Terminal<B>,
+    mut prompt: Prompt,
+) -> io::Result<Option<Prompt>> {
+    loop {
+        terminal.draw(|f| render::prompt::render(f, &prompt))?;
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                _ if CTRL_C.check(key) => return Ok(None),
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Enter => match validate_target(&prompt.target, prompt.addr_family) {
+                    Ok(()) => return Ok(Some(prompt)),
+                    Err(err) => prompt.error = Some(err),
+                },
+                KeyCode::Down | KeyCode::Tab => prompt.next_field(),
+                KeyCode::Up | KeyCode::BackTab => prompt.previous_field(),
+                KeyCode::Right => prompt.next_option(),
+                KeyCode::Left => prompt.previous_option(),
+                KeyCode::Backspace if prompt.field == PromptField::Target => {
+                    prompt.target.pop();
+                }
+                KeyCode::Char(c) if prompt.field == PromptField::Target => prompt.target.push(c),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The fields of the prompt.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PromptField {
+    Target,
+    Protocol,
+    AddrFamily,
+    Mode,
+}
+
+impl PromptField {
+    const ALL: [Self; 4] = [Self::Target, Self::Protocol, Self::AddrFamily, Self::Mode];
+}
+
+/// The state of the prompt.
+///
+/// An option which is `None` is not set by the prompt, such that the value from the config file,
+/// or the default, is used.
+#[derive(Debug, Clone)]
+pub struct Prompt {
+    pub target: String,
+    pub protocol: Option<ProtocolConfig>,
+    pub addr_family: Option<AddressFamilyConfig>,
+    pub mode: Option<Mode>,
+    pub field: PromptField,
+    pub error: Option<String>,
+}
+
+impl Prompt {
+    const fn new(args: &Args) -> Self {
+        let protocol = match (args.udp, args.tcp, args.icmp) {
+            (true, _, _) => Some(ProtocolConfig::Udp),
+            (_, true, _) => Some(ProtocolConfig::Tcp),
+            (_, _, true) => Some(ProtocolConfig::Icmp),
+            _ => args.protocol,
+        };
+        let addr_family = match (args.ipv4, args.ipv6) {
+            (true, _) => Some(AddressFamilyConfig::Ipv4),
+            (_, true) => Some(AddressFamilyConfig::Ipv6),
+            _ => args.addr_family,
+        };
+        Self {
+            target: String::new(),
+            protocol,
+            addr_family,
+            mode: args.mode,
+            field: PromptField::Target,
+            error: None,
+        }
+    }
+
+    /// Update the command line arguments with the values entered in the prompt.
+    fn apply(self, args: &mut Args) {
+        args.targets = vec![self.target.trim().to_string()];
+        if self.protocol.is_some() {
+            args.protocol = self.protocol;
+            args.udp = false;
+            args.tcp = false;
+            args.icmp = false;
+        }
+        if self.addr_family.is_some() {
+            args.addr_family = self.addr_family;
+            args.ipv4 = false;
+            args.ipv6 = false;
+        }
+        args.mode = self.mode.or(args.mode);
+    }
+
+    fn next_field(&mut self) {
+        let index = self.field_index();
+        self.field = PromptField::ALL[(index + 1) % PromptField::ALL.len()];
+    }
+
+    fn previous_field(&mut self) {
+        let index = self.field_index();
+        self.field =
+            PromptField::ALL[(index + PromptField::ALL.len() - 1) % PromptField::ALL.len()];
+    }
+
+    fn field_index(&self) -> usize {
+        PromptField::ALL
+            .iter()
+            .position(|field| *field == self.field)
+            .unwrap_or_default()
+    }
+
+    fn next_option(&mut self) {
+        match self.field {
+            PromptField::Target => {}
+            PromptField::Protocol => self.protocol = cycle(self.protocol, true),
+            PromptField::AddrFamily => self.addr_family = cycle(self.addr_family, true),
+            PromptField::Mode => self.mode = cycle(self.mode, true),
+        }
+    }
+
+    fn previous_option(&mut self) {
+        match self.field {
+            PromptField::Target => {}
+            PromptField::Protocol => self.protocol = cycle(self.protocol, false),
+            PromptField::AddrFamily => self.addr_family = cycle(self.addr_family, false),
+            PromptField::Mode => self.mode = cycle(self.mode, false),
+        }
+    }
+}
+
+/// The name of an option value as given on the command line, or `default` if not set.
+pub fn option_name<T: ValueEnum>(value: Option<T>) -> String {
+    value
+        .and_then(|value| value.to_possible_value())
+        .map_or_else(
+            || t!("default").to_string(),
+            |value| value.get_name().to_string(),
+        )
+}
+
+/// Step forwards or backwards through the values of an option, where `None` precedes the first.
+fn cycle<T: ValueEnum + PartialEq>(value: Option<T>, forward: bool) -> Option<T> {
+    let values = T::value_variants();
+    let count = values.len() + 1;
+    let index = value
+        .and_then(|value| values.iter().position(|v| *v == value))
+        .map_or(0, |index| index + 1);
+    let next = if forward {
+        (index + 1) % count
+    } else {
+        (index + count - 1) % count
+    };
+    next.checked_sub(1).map(|i| values[i].clone())
+}
+
+/// Check that a target is an IP address, or a hostname which resolves to an IP address, of the
+/// given address family.
+fn validate_target(target: &str, addr_family: Option<AddressFamilyConfig>) -> Result<(), String> {
+    let target = target.trim();
+    if target.is_empty() {
+        return Err(t!("prompt_no_target").to_string());
+    }
+    let addrs = (target, 0)
+        .to_socket_addrs()
+        .map_err(|err| t!("prompt_unresolved", "target" => target, "err" => err).to_string())?
+        .map(|addr| addr.ip())
+        .collect::<Vec<_>>();
+    let is_family = |addr: &IpAddr| match addr_family {
+        Some(AddressFamilyConfig::Ipv4) => addr.is_ipv4(),
+        Some(AddressFamilyConfig::Ipv6) => addr.is_ipv6(),
+        _ => true,
+    };
+    if addrs.iter().any(is_family) {
+        Ok(())
+    } else {
+        Err(t!("prompt_no_addrs", "target" => target).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(None, true, Some(ProtocolConfig::Icmp); "default to first")]
+    #[test_case(Some(ProtocolConfig::Icmp), true, Some(ProtocolConfig::Udp); "first to second")]
+    #[test_case(Some(ProtocolConfig::Tcp), true, None; "last to default")]
+    #[test_case(None, false, Some(ProtocolConfig::Tcp); "default to last")]
+    #[test_case(Some(ProtocolConfig::Icmp), false, None; "first to default")]
+    fn test_cycle(value: Option<ProtocolConfig>, forward: bool, expected: Option<ProtocolConfig>) {
+        assert_eq!(expected, cycle(value, forward));
+    }
+
+    #[test_case("127.0.0.1", None, true; "ipv4 address")]
+    #[test_case(" ::1 ", None, true; "ipv6 address")]
+    #[test_case("127.0.0.1", Some(AddressFamilyConfig::Ipv4), true; "ipv4 address ipv4 family")]
+    #[test_case("127.0.0.1", Some(AddressFamilyConfig::Ipv6), false; "ipv4 address ipv6 family")]
+    #[test_case("", None, false; "empty target")]
+    #[test_case("  ", None, false; "blank target")]
+    fn test_validate_target(target: &str, addr_family: Option<AddressFamilyConfig>, valid: bool) {
+        assert_eq!(valid, validate_target(target, addr_family).is_ok());
+    }
+}
@@ -8,6 +8,8 @@ pub mod header;
 pub mod help;
 pub mod histogram;
 pub mod history;
+pub mod note;
+pub mod prompt;
 pub mod settings;
 pub mod splash;
 pub mod table;
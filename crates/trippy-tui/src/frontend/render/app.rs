@@ -1,4 +1,4 @@
-use crate::frontend::render::{body, flows, footer, header, help, settings, tabs};
+use crate::frontend::render::{body, flows, footer, header, help, note, settings, tabs};
 use crate::frontend::tui_app::TuiApp;
 use ratatui::layout::{Constraint, Direction, Layout};
 use ratatui::Frame;
@@ -65,6 +65,8 @@ pub fn render(f: &mut Frame<'_>, app: &mut TuiApp) {
         settings::render(f, app);
     } else if app.show_help {
         help::render(f, app);
+    } else if let Some(editor) = &app.note_editor {
+        note::render(f, app, editor);
     }
 }
 
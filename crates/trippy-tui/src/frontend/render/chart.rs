@@ -2,7 +2,6 @@ use crate::frontend::tui_app::TuiApp;
 use crate::t;
 use ratatui::layout::{Alignment, Constraint, Rect};
 use ratatui::style::Style;
-use ratatui::symbols::Marker;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Axis, Block, BorderType, Borders, Chart, Dataset, GraphType};
 use ratatui::Frame;
@@ -38,7 +37,7 @@ pub fn render(f: &mut Frame<'_>, app: &TuiApp, rect: Rect) {
                 .name(format!("{} {}", t!("hop"), i + 1))
                 .data(s)
                 .graph_type(GraphType::Line)
-                .marker(Marker::Braille)
+                .marker(app.tui_config.glyphs.marker)
                 .style(Style::default().fg({
                     match i {
                         i if i + 1 == selected_hop.ttl() as usize => {
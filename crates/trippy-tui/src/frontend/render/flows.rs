@@ -1,3 +1,4 @@
+use crate::frontend::render::settings::format_flow_sort_mode;
 use crate::frontend::tui_app::TuiApp;
 use crate::t;
 use ratatui::layout::{Alignment, Rect};
@@ -36,7 +37,10 @@ pub fn render(f: &mut Frame<'_>, rect: Rect, app: &TuiApp) {
         })
         .collect();
     let block = Block::default()
-        .title(Line::raw(t!("title_flows")))
+        .title(Line::raw(t!(
+            "title_flows_sorted",
+            sort = format_flow_sort_mode(app.tui_config.flow_sort_mode)
+        )))
         .title_alignment(Alignment::Left)
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
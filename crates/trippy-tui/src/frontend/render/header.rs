@@ -1,7 +1,9 @@
+use crate::config::{TargetTtlMode, TrailingHopMode};
 use crate::frontend::tui_app::TuiApp;
 use crate::t;
 use chrono::SecondsFormat;
 use humantime::format_duration;
+use itertools::Itertools;
 use ratatui::layout::{Alignment, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -9,8 +11,8 @@ use ratatui::widgets::{Block, BorderType, Borders, Paragraph};
 use ratatui::Frame;
 use std::borrow::Cow;
 use std::net::IpAddr;
-use std::time::Duration;
-use trippy_core::{Hop, PortDirection, PrivilegeMode, Protocol};
+use std::time::{Duration, SystemTime};
+use trippy_core::{is_link_local, Hop, PortDirection, PrivilegeMode, Protocol};
 use trippy_dns::{ResolveMethod, Resolver};
 
 /// Render the title, config, target, clock and keyboard controls.
@@ -82,11 +84,20 @@ pub fn render(f: &mut Frame<'_>, app: &TuiApp, rect: Rect) {
         .tui_config
         .max_addrs
         .map_or_else(|| String::from(t!("auto")), |m| m.to_string());
+    let stats_window = app
+        .tui_config
+        .stats_window
+        .map_or_else(|| String::from(t!("all")), |w| w.to_string());
     let privacy = if app.hide_private_hops && app.tui_config.privacy_max_ttl > 0 {
         t!("on")
     } else {
         t!("off")
     };
+    let trailing_hop = if app.tui_config.trailing_hop_mode == TrailingHopMode::Hide {
+        t!("off")
+    } else {
+        t!("on")
+    };
     let source = render_source(app);
     let dest = render_destination(app);
     let target = format!("{source} -> {dest}");
@@ -123,12 +134,14 @@ pub fn render(f: &mut Frame<'_>, app: &TuiApp, rect: Rect) {
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::raw(format!(
-                "{}={protocol} {}={as_info} {}={details} {}={max_hosts}, {}={privacy}",
+                "{}={protocol} {}={as_info} {}={details} {}={max_hosts}, {}={stats_window}, {}={privacy}, {}={trailing_hop}",
                 t!("protocol"),
                 t!("as-info"),
                 t!("details"),
                 t!("max-hosts"),
-                t!("privacy")
+                t!("stats-window"),
+                t!("privacy"),
+                t!("trailing-hop")
             )),
         ]),
         Line::from(vec![
@@ -138,6 +151,11 @@ pub fn render(f: &mut Frame<'_>, app: &TuiApp, rect: Rect) {
             ),
             Span::raw(render_status(app)),
             Span::raw(discovered),
+            render_target_ttl(app),
+            render_path_stability(app),
+            render_limiter(app),
+            render_first_hop_warning(app),
+            render_saved_image(app),
         ]),
     ];
 
@@ -186,7 +204,7 @@ fn render_source(app: &TuiApp) -> String {
 /// Render the destination address.
 fn render_destination(app: &TuiApp) -> String {
     let dest_hostname = &app.tracer_config().target_hostname;
-    let dest_addr = app.tracer_config().data.target_addr();
+    let dest_addr = fmt_target_addr(app);
     match app.tracer_config().data.port_direction() {
         PortDirection::None => {
             format!("{dest_hostname} ({dest_addr})")
@@ -200,6 +218,149 @@ fn render_destination(app: &TuiApp) -> String {
     }
 }
 
+/// Format the target address, including the zone id for link-local targets.
+fn fmt_target_addr(app: &TuiApp) -> String {
+    let target_addr = app.tracer_config().data.target_addr();
+    match app.tracer_config().data.interface() {
+        Some(zone) if is_link_local(target_addr) => format!("{target_addr}%{zone}"),
+        _ => target_addr.to_string(),
+    }
+}
+
+/// Render the time-to-live at which the target was found.
+///
+/// This is only shown once the target has been found.  The number of times the target ttl has
+/// changed between rounds is also shown, and highlighted if it changed within the configured
+/// number of recent rounds, as this indicates a change in the path length.
+///
+/// If configured, any larger ttls at which the target also replied in the latest round are shown.
+fn render_target_ttl(app: &TuiApp) -> Span<'static> {
+    let data = &app.selected_tracer_data;
+    let Some(target_ttl) = data.target_ttl(app.selected_flow) else {
+        return Span::raw("");
+    };
+    let over_probed = render_over_probed_ttls(app);
+    let changes = data.target_ttl_changes(app.selected_flow);
+    if changes == 0 {
+        return Span::raw(format!(
+            " [{} {}{over_probed}]",
+            app.tui_config.glyphs.target,
+            t!("status_target_ttl", "ttl" => target_ttl)
+        ));
+    }
+    let text = format!(
+        " [{} {}{over_probed}]",
+        app.tui_config.glyphs.target,
+        t!("status_target_ttl_changed", "ttl" => target_ttl, "changes" => changes)
+    );
+    if app.is_path_change_flagged(data.target_ttl_changed_round(app.selected_flow)) {
+        Span::styled(text, Style::default().add_modifier(Modifier::BOLD))
+    } else {
+        Span::raw(text)
+    }
+}
+
+/// Render the path stability indicator.
+///
+/// The path is shown as stable unless it changed within the configured window, in which case it
+/// is highlighted and the time since it changed is shown.  This is not shown if the window is
+/// zero or before the first round has completed.
+fn render_path_stability(app: &TuiApp) -> Span<'static> {
+    let data = &app.selected_tracer_data;
+    let window = app.tui_config.path_stability_window;
+    if window.is_zero() || data.round(app.selected_flow).is_none() {
+        return Span::raw("");
+    }
+    let elapsed = data
+        .path_changed(app.selected_flow)
+        .map(|changed| {
+            SystemTime::now()
+                .duration_since(changed)
+                .unwrap_or_default()
+        })
+        .filter(|&elapsed| elapsed < window);
+    if let Some(elapsed) = elapsed {
+        let elapsed = format_duration(Duration::from_secs(elapsed.as_secs()));
+        Span::styled(
+            format!(" [{}]", t!("status_path_changed", "elapsed" => elapsed)),
+            Style::default().add_modifier(Modifier::BOLD),
+        )
+    } else {
+        Span::raw(format!(" [{}]", t!("status_path_stable")))
+    }
+}
+
+/// Render the larger ttls at which the target also replied, if configured.
+fn render_over_probed_ttls(app: &TuiApp) -> String {
+    let ttls = app.selected_tracer_data.over_probed_ttls(app.selected_flow);
+    if app.tui_config.target_ttl_mode == TargetTtlMode::Lowest || ttls.is_empty() {
+        return String::new();
+    }
+    format!(
+        ", {}",
+        t!("status_target_over_probed", "ttls" => ttls.iter().join(", "))
+    )
+}
+
+/// Render the rate limiter indicator.
+///
+/// This is only shown if a minimum probe interval has been configured.  The indicator is
+/// highlighted if the rate limiter suppressed any probe sends during the last round, so that
+/// self-imposed throttling is not mistaken for loss.
+fn render_limiter(app: &TuiApp) -> Span<'static> {
+    if app.tracer_config().data.min_probe_interval().is_none() {
+        return Span::raw("");
+    }
+    let data = &app.selected_tracer_data;
+    let text = format!(
+        " [{}]",
+        t!("status_rate_limited", "suppressed" => data.total_suppressed())
+    );
+    if data.last_round_suppressed() > 0 {
+        Span::styled(text, Style::default().add_modifier(Modifier::BOLD))
+    } else {
+        Span::styled(text, Style::default().add_modifier(Modifier::DIM))
+    }
+}
+
+/// Render the first hop warning.
+///
+/// This is shown prominently if the first hop has not replied for several rounds, which usually
+/// indicates a local connectivity problem.
+fn render_first_hop_warning(app: &TuiApp) -> Span<'static> {
+    if !app.show_first_hop_warning() {
+        return Span::raw("");
+    }
+    let text = format!(
+        " [{} {}]",
+        app.tui_config.glyphs.warning,
+        t!("status_first_hop_warning",
+            "rounds" => app.tui_config.first_hop_warning_rounds,
+            "key" => app.tui_config.bindings.dismiss_warning)
+    );
+    Span::styled(
+        text,
+        Style::default().add_modifier(Modifier::BOLD | Modifier::REVERSED),
+    )
+}
+
+/// Render the outcome of saving an image.
+///
+/// This is shown for a short time after an attempt to save the screen as an image.
+fn render_saved_image(app: &TuiApp) -> Span<'static> {
+    match app.recent_saved_image() {
+        Some(Ok(path)) => Span::raw(format!(
+            " [{}]",
+            t!("status_image_saved", "path" => path.display())
+        )),
+        Some(Err(err)) => Span::styled(
+            format!(" [{}]", t!("status_image_failed", "error" => err)),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        None => Span::raw(""),
+    }
+}
+
 /// Render the headline status of the tracing.
 fn render_status(app: &TuiApp) -> String {
     let failure_count: usize = app
@@ -222,22 +383,34 @@ fn render_status(app: &TuiApp) -> String {
         };
         let failure_rate = format!("{failure_rate:.1}");
         format!(
-            " [{}❗]",
+            " [{}{}]",
             t!("status_failures",
                     "failure_count" => failure_count,
                     "total_probes" => total_probes,
-                    "failure_rate" => failure_rate)
+                    "failure_rate" => failure_rate),
+            app.tui_config.glyphs.failure
         )
     } else {
         String::new()
     };
     if app.selected_tracer_data.error().is_some() {
         String::from(t!("status_failed"))
+    } else if let Some(attempt) = app.selected_tracer_data.setup_retry_attempt() {
+        let attempts = app.tracer_config().data.setup_retry_attempts();
+        String::from(t!(
+            "status_retrying",
+            "attempt" => attempt,
+            "attempts" => attempts
+        ))
     } else if let Some(start) = app.frozen_start {
         let frozen = format_duration(Duration::from_secs(
             start.elapsed().unwrap_or_default().as_secs(),
         ));
         format!("{} ({frozen}){failures}", t!("status_frozen"))
+    } else if app.tracer_config().data.is_paused() {
+        format!("{}{failures}", t!("status_paused"))
+    } else if let Some(rounds) = app.stopped_rounds() {
+        format!("{}{failures}", t!("status_stopped", "rounds" => rounds))
     } else {
         format!("{}{failures}", t!("status_running"))
     }
@@ -26,6 +26,7 @@ pub fn render(f: &mut Frame<'_>, app: &TuiApp, rect: Rect) {
                 .border_style(Style::default().fg(app.tui_config.theme.border)),
         )
         .data(freq_data_ref.as_slice())
+        .bar_set(app.tui_config.glyphs.bar_set.clone())
         .bar_width(4)
         .bar_gap(1)
         .bar_style(Style::default().fg(app.tui_config.theme.frequency_chart_bar))
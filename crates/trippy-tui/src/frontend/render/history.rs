@@ -1,4 +1,4 @@
-use crate::frontend::render::widgets::sparkline::{EmptyBarSymbol, Sparkline};
+use crate::frontend::render::widgets::sparkline::Sparkline;
 use crate::frontend::tui_app::TuiApp;
 use crate::t;
 use ratatui::layout::Rect;
@@ -45,6 +45,7 @@ pub fn render(f: &mut Frame<'_>, app: &TuiApp, rect: Rect) {
                 .bg(app.tui_config.theme.bg)
                 .fg(app.tui_config.theme.samples_chart_lost),
         )
-        .empty_bar_symbol(EmptyBarSymbol::Full);
+        .bar_set(app.tui_config.glyphs.bar_set.clone())
+        .empty_bar_symbol(app.tui_config.glyphs.lost_bar);
     f.render_widget(history, rect);
 }
@@ -0,0 +1,43 @@
+use crate::frontend::render::util;
+use crate::frontend::tui_app::NoteEditor;
+use crate::frontend::tui_app::TuiApp;
+use crate::t;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+/// Render the note editor dialog.
+pub fn render(f: &mut Frame<'_>, app: &TuiApp, editor: &NoteEditor) {
+    let mut lines = vec![
+        Line::raw(""),
+        Line::raw(t!("note_for", addr = editor.addr)),
+        Line::raw(""),
+        Line::raw(format!("{}_", editor.text)),
+        Line::raw(""),
+        Line::raw(t!("note_help")),
+    ];
+    if let Some(err) = &editor.error {
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(
+            err.clone(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    let block = Block::default()
+        .title(format!(" {} ", t!("title_note")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(app.tui_config.theme.help_dialog_bg))
+        .border_type(BorderType::Double);
+    let control = Paragraph::new(lines)
+        .style(Style::default().fg(app.tui_config.theme.help_dialog_text))
+        .block(block.clone())
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false });
+    let area = util::centered_rect(60, 40, f.area());
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+    f.render_widget(control, area);
+}
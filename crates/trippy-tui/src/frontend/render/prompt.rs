@@ -0,0 +1,73 @@
+use crate::frontend::prompt::{option_name, Prompt, PromptField};
+use crate::frontend::render::util;
+use crate::t;
+use ratatui::layout::Alignment;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders, Paragraph, Wrap};
+use ratatui::Frame;
+
+/// Render the prompt for a target and tracing options.
+///
+/// The selected field is shown reversed, and options which can be changed are shown between
+/// arrows, i.e. `< icmp >`.
+pub fn render(f: &mut Frame<'_>, prompt: &Prompt) {
+    let field = |field: PromptField, label: String, value: String| {
+        let value = match field {
+            PromptField::Target => format!("{value}_"),
+            _ => format!("< {value} >"),
+        };
+        let style = if prompt.field == field {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        Line::from(vec![
+            Span::raw(format!("{label:>16}: ")),
+            Span::styled(format!("{value:<24}"), style),
+        ])
+    };
+    let mut lines = vec![
+        Line::raw(""),
+        field(
+            PromptField::Target,
+            t!("prompt_target").to_string(),
+            prompt.target.clone(),
+        ),
+        field(
+            PromptField::Protocol,
+            t!("prompt_protocol").to_string(),
+            option_name(prompt.protocol),
+        ),
+        field(
+            PromptField::AddrFamily,
+            t!("prompt_addr_family").to_string(),
+            option_name(prompt.addr_family),
+        ),
+        field(
+            PromptField::Mode,
+            t!("prompt_mode").to_string(),
+            option_name(prompt.mode),
+        ),
+        Line::raw(""),
+        Line::raw(t!("prompt_help")),
+    ];
+    if let Some(err) = &prompt.error {
+        lines.push(Line::raw(""));
+        lines.push(Line::from(Span::styled(
+            err.clone(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    let block = Block::default()
+        .title(format!(" {} ", t!("title_prompt")))
+        .title_alignment(Alignment::Center)
+        .borders(Borders::ALL)
+        .border_type(BorderType::Double);
+    let control = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: false });
+    let area = util::centered_rect(60, 40, f.area());
+    f.render_widget(control, area);
+}
@@ -1,9 +1,15 @@
-use crate::config::{AddressMode, AsMode, GeoIpMode, IcmpExtensionMode};
+use crate::config::{
+    AddrAggregationMode, AddressMode, AsMode, AverageMode, FlowSortMode, GeoIpMode, GlyphSet,
+    HostTruncation, IcmpExtensionMode, RttHeatMode, StatusMode, StdDevMode, StdDevScope,
+    TargetTtlMode, TimeExceededRttMode, TrailingHopMode, UnresponsiveHopMode,
+};
+use crate::frontend::columns::ColumnType;
 use crate::frontend::render::util;
 use crate::frontend::theme;
 use crate::frontend::tui_app::TuiApp;
 use crate::t;
 use humantime::format_duration;
+use itertools::Itertools;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -11,7 +17,9 @@ use ratatui::widgets::{
     Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, Tabs, Wrap,
 };
 use ratatui::Frame;
-use trippy_core::PortDirection;
+use std::collections::HashMap;
+use strum::IntoEnumIterator;
+use trippy_core::{PayloadStrategy, PortDirection};
 use trippy_dns::ResolveMethod;
 
 /// Render settings dialog.
@@ -130,6 +138,7 @@ fn format_all_settings(app: &TuiApp) -> Vec<(String, String, Vec<SettingsItem>)>
     let theme_settings = format_theme_settings(app);
     let columns_settings = format_columns_settings(app);
     let toggle_column = app.tui_config.bindings.toggle_chart.to_string();
+    let toggle_group = app.tui_config.bindings.toggle_column_group.to_string();
     let move_down = app.tui_config.bindings.next_hop_address.to_string();
     let move_up = app.tui_config.bindings.previous_hop_address.to_string();
     vec![
@@ -168,6 +177,7 @@ fn format_all_settings(app: &TuiApp) -> Vec<(String, String, Vec<SettingsItem>)>
             t!(
                 "settings_tab_columns_desc",
                 c = toggle_column,
+                g = toggle_group,
                 d = move_down,
                 u = move_up
             )
@@ -178,6 +188,7 @@ fn format_all_settings(app: &TuiApp) -> Vec<(String, String, Vec<SettingsItem>)>
 }
 
 /// Format Tui settings.
+#[allow(clippy::too_many_lines)]
 fn format_tui_settings(app: &TuiApp) -> Vec<SettingsItem> {
     vec![
         SettingsItem::new(
@@ -192,11 +203,39 @@ fn format_tui_settings(app: &TuiApp) -> Vec<SettingsItem> {
             "tui-privacy-max-ttl",
             format!("{}", app.tui_config.privacy_max_ttl),
         ),
+        SettingsItem::new(
+            "tui-first-hop-warning-rounds",
+            format!("{}", app.tui_config.first_hop_warning_rounds),
+        ),
         SettingsItem::new(
             "tui-address-mode",
             format_address_mode(app.tui_config.address_mode),
         ),
         SettingsItem::new("tui-as-mode", format_as_mode(app.tui_config.as_mode)),
+        SettingsItem::new("tui-as-colors", format!("{}", app.tui_config.as_colors)),
+        SettingsItem::new(
+            "tui-summary-footer",
+            format!("{}", app.tui_config.summary_footer),
+        ),
+        SettingsItem::new(
+            "tui-rtt-deltas",
+            format!("{}", app.tui_config.tui_columns.rtt_deltas()),
+        ),
+        SettingsItem::new(
+            "tui-rtt-compact-threshold",
+            app.tui_config.rtt_compact_threshold.map_or_else(
+                || t!("none").to_string(),
+                |threshold| format!("{}", format_duration(threshold)),
+            ),
+        ),
+        SettingsItem::new(
+            "tui-rtt-heat-mode",
+            format_rtt_heat_mode(app.tui_config.rtt_heat_mode),
+        ),
+        SettingsItem::new(
+            "tui-rtt-heat-max",
+            format!("{}", format_duration(app.tui_config.rtt_heat_max)),
+        ),
         SettingsItem::new(
             "tui-icmp-extension-mode",
             format_extension_mode(app.tui_config.icmp_extension_mode),
@@ -205,20 +244,131 @@ fn format_tui_settings(app: &TuiApp) -> Vec<SettingsItem> {
             "tui-geoip-mode",
             format_geoip_mode(app.tui_config.geoip_mode),
         ),
+        SettingsItem::new(
+            "tui-unresponsive-hop-mode",
+            format_unresponsive_hop_mode(app.tui_config.unresponsive_hop_mode),
+        ),
+        SettingsItem::new(
+            "tui-trailing-hop-mode",
+            format_trailing_hop_mode(app.tui_config.trailing_hop_mode),
+        ),
+        SettingsItem::new(
+            "tui-time-exceeded-rtt-mode",
+            format_time_exceeded_rtt_mode(app.tui_config.time_exceeded_rtt_mode),
+        ),
+        SettingsItem::new(
+            "tui-average-mode",
+            format_average_mode(app.tui_config.average_mode),
+        ),
+        SettingsItem::new("tui-ewma-weight", format!("{}", app.tui_config.ewma_weight)),
+        SettingsItem::new(
+            "tui-loss-ewma-weight",
+            format!("{}", app.tui_config.loss_ewma_weight),
+        ),
+        SettingsItem::new(
+            "tui-target-ttl-mode",
+            format_target_ttl_mode(app.tui_config.target_ttl_mode),
+        ),
+        SettingsItem::new(
+            "tui-host-truncation",
+            format_host_truncation(app.tui_config.host_truncation),
+        ),
+        SettingsItem::new("tui-glyph-set", format_glyph_set(app.tui_config.glyphs.set)),
         SettingsItem::new(
             "tui-max-addrs",
             app.tui_config
                 .max_addrs
                 .map_or_else(|| t!("auto").to_string(), |m| m.to_string()),
         ),
+        SettingsItem::new(
+            "tui-max-display-hops",
+            app.tui_config
+                .max_display_hops
+                .map_or_else(|| t!("all").to_string(), |m| m.to_string()),
+        ),
+        SettingsItem::new(
+            "tui-stats-window",
+            app.tui_config
+                .stats_window
+                .map_or_else(|| t!("all").to_string(), |w| w.to_string()),
+        ),
+        SettingsItem::new(
+            "tui-stddev-mode",
+            format_stddev_mode(app.tui_config.stddev_mode),
+        ),
+        SettingsItem::new(
+            "tui-stddev-scope",
+            format_stddev_scope(app.tui_config.stddev_scope),
+        ),
+        SettingsItem::new(
+            "tui-max-rounds",
+            app.tracer_config()
+                .data
+                .max_rounds()
+                .map_or_else(|| t!("unlimited").to_string(), |r| r.0.to_string()),
+        ),
+        SettingsItem::new(
+            "tui-path-change-rounds",
+            app.tui_config.path_change_rounds.to_string(),
+        ),
+        SettingsItem::new(
+            "tui-path-stability-window",
+            format!("{}", format_duration(app.tui_config.path_stability_window)),
+        ),
+        SettingsItem::new(
+            "tui-flow-sort-mode",
+            format_flow_sort_mode(app.tui_config.flow_sort_mode),
+        ),
+        SettingsItem::new(
+            "tui-probe-countdown",
+            format!("{}", app.tui_config.probe_countdown),
+        ),
+        SettingsItem::new(
+            "tui-rtt-baseline",
+            format!("{}", app.tui_config.rtt_baseline),
+        ),
+        SettingsItem::new(
+            "tui-tunnel-ttl-jump",
+            format!("{}", app.tui_config.tunnel_ttl_jump),
+        ),
+        SettingsItem::new(
+            "tui-status-mode",
+            format_status_mode(app.tui_config.status_mode),
+        ),
+        SettingsItem::new(
+            "tui-addr-aggregation",
+            format_addr_aggregation_mode(app.tui_config.addr_aggregation),
+        ),
+        SettingsItem::new(
+            "tui-auto-quit-stable-rounds",
+            app.tui_config
+                .auto_quit_stable_rounds
+                .map_or_else(|| t!("never").to_string(), |r| r.to_string()),
+        ),
+        SettingsItem::new(
+            "tui-auto-quit-stable-threshold",
+            format!(
+                "{}",
+                format_duration(app.tui_config.auto_quit_stable_threshold)
+            ),
+        ),
         SettingsItem::new(
             "tui-custom-columns",
             format!("{}", app.tui_config.tui_columns),
         ),
+        SettingsItem::new(
+            "tui-column-groups",
+            app.tui_config.tui_columns.groups().iter().join(","),
+        ),
+        SettingsItem::new(
+            "tui-rtt-precision",
+            format_rtt_precision(&app.tui_config.rtt_precision),
+        ),
     ]
 }
 
 /// Format trace settings.
+#[allow(clippy::too_many_lines)]
 fn format_trace_settings(app: &TuiApp) -> Vec<SettingsItem> {
     let cfg = app.tracer_config();
     let interface = if let Some(iface) = cfg.data.interface() {
@@ -248,6 +398,8 @@ fn format_trace_settings(app: &TuiApp) -> Vec<SettingsItem> {
             format!("{}", format_duration(cfg.data.grace_duration())),
         ),
         SettingsItem::new("max-inflight", format!("{}", cfg.data.max_inflight().0)),
+        SettingsItem::new("send-batch-size", format!("{}", cfg.data.send_batch_size())),
+        SettingsItem::new("recv-batch-size", format!("{}", cfg.data.recv_batch_size())),
         SettingsItem::new(
             "initial-sequence",
             format!("{}", cfg.data.initial_sequence().0),
@@ -256,6 +408,77 @@ fn format_trace_settings(app: &TuiApp) -> Vec<SettingsItem> {
             "read-timeout",
             format!("{}", format_duration(cfg.data.read_timeout())),
         ),
+        SettingsItem::new(
+            "min-probe-interval",
+            cfg.data.min_probe_interval().map_or_else(
+                || t!("none").to_string(),
+                |interval| format!("{}", format_duration(interval)),
+            ),
+        ),
+        SettingsItem::new(
+            "send-jitter",
+            cfg.data.send_jitter().map_or_else(
+                || t!("none").to_string(),
+                |jitter| format!("{}", format_duration(jitter)),
+            ),
+        ),
+        SettingsItem::new(
+            "loss-timeout",
+            cfg.data.loss_timeout().map_or_else(
+                || t!("none").to_string(),
+                |timeout| format!("{}", format_duration(timeout)),
+            ),
+        ),
+        SettingsItem::new(
+            "ignore-identifier",
+            format!("{}", cfg.data.ignore_identifier()),
+        ),
+        SettingsItem::new(
+            "discard-first-hop-warmup",
+            format!("{}", cfg.data.discard_first_hop_warmup()),
+        ),
+        SettingsItem::new(
+            "accept-late-responses",
+            format!("{}", cfg.data.accept_late_responses()),
+        ),
+        SettingsItem::new(
+            "adaptive-read-timeout",
+            if cfg.data.adaptive_read_timeout() {
+                app.selected_tracer_data.read_timeout().map_or_else(
+                    || t!("auto").to_string(),
+                    |timeout| format!("{}", format_duration(timeout)),
+                )
+            } else {
+                false.to_string()
+            },
+        ),
+        SettingsItem::new(
+            "max-unresponsive-hops",
+            app.tui_config
+                .max_unresponsive_hops
+                .map_or_else(|| t!("all").to_string(), |m| m.to_string()),
+        ),
+        SettingsItem::new(
+            "curtail-unresponsive",
+            format!("{}", cfg.data.max_unresponsive_hops().is_some()),
+        ),
+        SettingsItem::new(
+            "setup-retry-attempts",
+            format!("{}", cfg.data.setup_retry_attempts()),
+        ),
+        SettingsItem::new(
+            "setup-retry-backoff",
+            format!("{}", format_duration(cfg.data.setup_retry_backoff())),
+        ),
+        SettingsItem::new(
+            "kernel-recv-timestamps",
+            format!("{}", cfg.data.kernel_recv_timestamps()),
+        ),
+        SettingsItem::new("stamped-payload", format!("{}", cfg.data.stamped_payload())),
+        SettingsItem::new(
+            "payload-strategy",
+            format_payload_strategy(cfg.data.payload_strategy()),
+        ),
         SettingsItem::new("packet-size", format!("{}", cfg.data.packet_size().0)),
         SettingsItem::new(
             "payload-pattern",
@@ -321,6 +544,7 @@ fn format_geoip_settings(app: &TuiApp) -> Vec<SettingsItem> {
 }
 
 /// Format binding settings.
+#[allow(clippy::too_many_lines)]
 fn format_binding_settings(app: &TuiApp) -> Vec<SettingsItem> {
     let binds = &app.tui_config.bindings;
     vec![
@@ -389,6 +613,55 @@ fn format_binding_settings(app: &TuiApp) -> Vec<SettingsItem> {
             "toggle-hop-details",
             format!("{}", binds.toggle_hop_details),
         ),
+        SettingsItem::new("previous-column", format!("{}", binds.previous_column)),
+        SettingsItem::new("next-column", format!("{}", binds.next_column)),
+        SettingsItem::new("narrow-column", format!("{}", binds.narrow_column)),
+        SettingsItem::new("widen-column", format!("{}", binds.widen_column)),
+        SettingsItem::new(
+            "reset-column-width",
+            format!("{}", binds.reset_column_width),
+        ),
+        SettingsItem::new("dismiss-warning", format!("{}", binds.dismiss_warning)),
+        SettingsItem::new(
+            "contract-stats-window",
+            format!("{}", binds.contract_stats_window),
+        ),
+        SettingsItem::new(
+            "expand-stats-window",
+            format!("{}", binds.expand_stats_window),
+        ),
+        SettingsItem::new(
+            "select-worst-loss-hop",
+            format!("{}", binds.select_worst_loss_hop),
+        ),
+        SettingsItem::new(
+            "select-worst-latency-hop",
+            format!("{}", binds.select_worst_latency_hop),
+        ),
+        SettingsItem::new("toggle-as-colors", format!("{}", binds.toggle_as_colors)),
+        SettingsItem::new(
+            "toggle-summary-footer",
+            format!("{}", binds.toggle_summary_footer),
+        ),
+        SettingsItem::new("toggle-rtt-deltas", format!("{}", binds.toggle_rtt_deltas)),
+        SettingsItem::new("edit-note", format!("{}", binds.edit_note)),
+        SettingsItem::new(
+            "toggle-trailing-hop",
+            format!("{}", binds.toggle_trailing_hop),
+        ),
+        SettingsItem::new("save-image", format!("{}", binds.save_image)),
+        SettingsItem::new("toggle-hop-paused", format!("{}", binds.toggle_hop_paused)),
+        SettingsItem::new(
+            "toggle-trace-paused",
+            format!("{}", binds.toggle_trace_paused),
+        ),
+        SettingsItem::new(
+            "toggle-column-group",
+            format!("{}", binds.toggle_column_group),
+        ),
+        SettingsItem::new("toggle-flow-sort", format!("{}", binds.toggle_flow_sort)),
+        SettingsItem::new("save-ansi", format!("{}", binds.save_ansi)),
+        SettingsItem::new("toggle-hop-sort", format!("{}", binds.toggle_hop_sort)),
         SettingsItem::new("quit", format!("{}", binds.quit)),
     ]
 }
@@ -410,6 +683,14 @@ fn format_theme_settings(app: &TuiApp) -> Vec<SettingsItem> {
             "hops-table-header-text-color",
             theme::fmt_color(theme.hops_table_header_text),
         ),
+        SettingsItem::new(
+            "hops-table-header-selected-bg-color",
+            theme::fmt_color(theme.hops_table_header_selected_bg),
+        ),
+        SettingsItem::new(
+            "hops-table-header-selected-text-color",
+            theme::fmt_color(theme.hops_table_header_selected_text),
+        ),
         SettingsItem::new(
             "hops-table-row-active-text-color",
             theme::fmt_color(theme.hops_table_row_active_text),
@@ -501,6 +782,8 @@ fn format_theme_settings(app: &TuiApp) -> Vec<SettingsItem> {
             "map-info-panel-text-color",
             theme::fmt_color(theme.map_info_panel_text),
         ),
+        SettingsItem::new("rtt-heat-low-color", theme::fmt_color(theme.rtt_heat_low)),
+        SettingsItem::new("rtt-heat-high-color", theme::fmt_color(theme.rtt_heat_high)),
     ]
 }
 
@@ -509,7 +792,14 @@ fn format_columns_settings(app: &TuiApp) -> Vec<SettingsItem> {
     app.tui_config
         .tui_columns
         .all_columns()
-        .map(|c| SettingsItem::new(c.typ.to_string(), c.status.to_string()))
+        .enumerate()
+        .map(|(i, c)| {
+            let status = match app.tui_config.tui_columns.group(i) {
+                Some(group) => format!("{} ({})", c.status, group.name),
+                None => c.status.to_string(),
+            };
+            SettingsItem::new(c.typ.to_string(), status)
+        })
         .collect()
 }
 
@@ -519,12 +809,12 @@ pub const SETTINGS_TAB_COLUMNS: usize = 6;
 /// The name and number of items for each tabs in the setting dialog.
 pub fn settings_tabs() -> [(String, usize); 7] {
     [
-        (t!("settings_tab_tui_title").to_string(), 9),
-        (t!("settings_tab_trace_title").to_string(), 17),
+        (t!("settings_tab_tui_title").to_string(), 42),
+        (t!("settings_tab_trace_title").to_string(), 33),
         (t!("settings_tab_dns_title").to_string(), 5),
         (t!("settings_tab_geoip_title").to_string(), 1),
-        (t!("settings_tab_bindings_title").to_string(), 36),
-        (t!("settings_tab_theme_title").to_string(), 31),
+        (t!("settings_tab_bindings_title").to_string(), 58),
+        (t!("settings_tab_theme_title").to_string(), 35),
         (t!("settings_tab_columns_title").to_string(), 0),
     ]
 }
@@ -567,6 +857,14 @@ fn format_dns_method(resolve_method: ResolveMethod) -> String {
     }
 }
 
+/// Format the `PayloadStrategy`.
+fn format_payload_strategy(payload_strategy: PayloadStrategy) -> String {
+    match payload_strategy {
+        PayloadStrategy::Pattern => String::from("pattern"),
+        PayloadStrategy::ConstantChecksum => String::from("constant-checksum"),
+    }
+}
+
 fn format_extension_mode(icmp_extension_mode: IcmpExtensionMode) -> String {
     match icmp_extension_mode {
         IcmpExtensionMode::Off => "off".to_string(),
@@ -606,3 +904,129 @@ fn format_geoip_mode(geoip_mode: GeoIpMode) -> String {
         GeoIpMode::Location => "location".to_string(),
     }
 }
+
+/// Format the `UnresponsiveHopMode`.
+fn format_unresponsive_hop_mode(unresponsive_hop_mode: UnresponsiveHopMode) -> String {
+    match unresponsive_hop_mode {
+        UnresponsiveHopMode::NoResponse => "no-response".to_string(),
+        UnresponsiveHopMode::Blank => "blank".to_string(),
+        UnresponsiveHopMode::Stars => "stars".to_string(),
+        UnresponsiveHopMode::LastKnown => "last-known".to_string(),
+    }
+}
+
+/// Format the `TrailingHopMode`.
+fn format_trailing_hop_mode(trailing_hop_mode: TrailingHopMode) -> String {
+    match trailing_hop_mode {
+        TrailingHopMode::Waiting => "waiting".to_string(),
+        TrailingHopMode::Unresponsive => "unresponsive".to_string(),
+        TrailingHopMode::Hide => "hide".to_string(),
+    }
+}
+
+/// Format the `TimeExceededRttMode`.
+fn format_time_exceeded_rtt_mode(time_exceeded_rtt_mode: TimeExceededRttMode) -> String {
+    match time_exceeded_rtt_mode {
+        TimeExceededRttMode::Include => "include".to_string(),
+        TimeExceededRttMode::Distinguish => "distinguish".to_string(),
+        TimeExceededRttMode::Exclude => "exclude".to_string(),
+    }
+}
+
+/// Format the `StatusMode`.
+fn format_status_mode(status_mode: StatusMode) -> String {
+    match status_mode {
+        StatusMode::Health => "health".to_string(),
+        StatusMode::Summary => "summary".to_string(),
+        StatusMode::Icmp => "icmp".to_string(),
+    }
+}
+
+/// Format the `AddrAggregationMode`.
+fn format_addr_aggregation_mode(addr_aggregation: AddrAggregationMode) -> String {
+    match addr_aggregation {
+        AddrAggregationMode::Combined => "combined".to_string(),
+        AddrAggregationMode::Split => "split".to_string(),
+    }
+}
+
+/// Format the `FlowSortMode`.
+pub fn format_flow_sort_mode(flow_sort_mode: FlowSortMode) -> String {
+    match flow_sort_mode {
+        FlowSortMode::Rounds => "rounds".to_string(),
+        FlowSortMode::Rtt => "rtt".to_string(),
+        FlowSortMode::Loss => "loss".to_string(),
+    }
+}
+
+/// Format the `AverageMode`.
+fn format_average_mode(average_mode: AverageMode) -> String {
+    match average_mode {
+        AverageMode::Mean => "mean".to_string(),
+        AverageMode::Ewma => "ewma".to_string(),
+    }
+}
+
+/// Format the decimal places of round-trip time columns, i.e. `a=0,v=2`.
+fn format_rtt_precision(rtt_precision: &HashMap<ColumnType, usize>) -> String {
+    if rtt_precision.is_empty() {
+        return t!("none").to_string();
+    }
+    ColumnType::iter()
+        .filter_map(|column| {
+            rtt_precision
+                .get(&column)
+                .map(|places| format!("{}={places}", char::from(column)))
+        })
+        .join(",")
+}
+
+/// Format the `RttHeatMode`.
+fn format_rtt_heat_mode(rtt_heat_mode: RttHeatMode) -> String {
+    match rtt_heat_mode {
+        RttHeatMode::Off => "off".to_string(),
+        RttHeatMode::Path => "path".to_string(),
+        RttHeatMode::Fixed => "fixed".to_string(),
+    }
+}
+
+/// Format the `TargetTtlMode`.
+fn format_target_ttl_mode(target_ttl_mode: TargetTtlMode) -> String {
+    match target_ttl_mode {
+        TargetTtlMode::Lowest => "lowest".to_string(),
+        TargetTtlMode::All => "all".to_string(),
+    }
+}
+
+/// Format the `StdDevMode`.
+fn format_stddev_mode(stddev_mode: StdDevMode) -> String {
+    match stddev_mode {
+        StdDevMode::Sample => "sample".to_string(),
+        StdDevMode::Population => "population".to_string(),
+    }
+}
+
+/// Format the `StdDevScope`.
+fn format_stddev_scope(stddev_scope: StdDevScope) -> String {
+    match stddev_scope {
+        StdDevScope::Window => "window".to_string(),
+        StdDevScope::All => "all".to_string(),
+    }
+}
+
+/// Format the `HostTruncation`.
+fn format_host_truncation(host_truncation: HostTruncation) -> String {
+    match host_truncation {
+        HostTruncation::Right => "right".to_string(),
+        HostTruncation::Left => "left".to_string(),
+    }
+}
+
+/// Format the `GlyphSet`.
+fn format_glyph_set(glyph_set: GlyphSet) -> String {
+    match glyph_set {
+        GlyphSet::Auto => "auto".to_string(),
+        GlyphSet::Unicode => "unicode".to_string(),
+        GlyphSet::Ascii => "ascii".to_string(),
+    }
+}
@@ -1,21 +1,31 @@
-use crate::config::{AddressMode, AsMode, GeoIpMode, IcmpExtensionMode};
+use crate::config::{
+    AddrAggregationMode, AddressMode, AsMode, AverageMode, GeoIpMode, HostTruncation,
+    IcmpExtensionMode, RttHeatMode, StatusMode, TimeExceededRttMode, TrailingHopMode,
+    UnresponsiveHopMode,
+};
 use crate::frontend::columns::{ColumnType, Columns};
 use crate::frontend::config::TuiConfig;
-use crate::frontend::theme::Theme;
-use crate::frontend::tui_app::TuiApp;
+use crate::frontend::glyphs::Glyphs;
+use crate::frontend::theme::{gradient, Theme};
+use crate::frontend::timeline::{RoundOutcome, Timeline};
+use crate::frontend::tui_app::{SortDir, TuiApp};
 use crate::geoip::{GeoIpCity, GeoIpLookup};
 use crate::t;
+use chrono::{DateTime, Local};
 use itertools::Itertools;
-use ratatui::layout::Rect;
+use ratatui::layout::{Alignment, Constraint, Layout, Margin, Rect};
 use ratatui::prelude::Line;
-use ratatui::style::{Modifier, Style};
-use ratatui::widgets::{Block, BorderType, Borders, Cell, Row, Table};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, BorderType, Borders, Cell, Row, Table, TableState};
 use ratatui::Frame;
+use std::borrow::Cow;
 use std::net::IpAddr;
 use std::rc::Rc;
+use std::time::Duration;
 use trippy_core::{Extension, Extensions, IcmpPacketType, MplsLabelStackMember, UnknownExtension};
-use trippy_core::{Hop, NatStatus};
+use trippy_core::{Hop, NatStatus, Protocol, UnreachableCode};
 use trippy_dns::{AsInfo, DnsEntry, DnsResolver, Resolved, Resolver, Unresolved};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Render the table of data about the hops.
 ///
@@ -39,34 +49,94 @@ use trippy_dns::{AsInfo, DnsEntry, DnsResolver, Resolved, Resolver, Unresolved};
 /// - The average jitter time for all probes at this hop ('Javg')
 /// - The worst round-trip jitter time for all probes at this hop ('Jmax')
 /// - The smoothed jitter value for all probes at this hop ('Jinta')
+///
+/// If enabled, a summary footer of path-wide aggregates is shown below the hops.
+///
+/// If addresses are split, a hop which is answered by more than one address is followed by a row
+/// for each address.
+///
+/// If the number of hops to display is capped, only the displayed range of hops is rendered and
+/// the number of hops not displayed is shown, i.e. `…+5 more`.
 pub fn render(f: &mut Frame<'_>, app: &mut TuiApp, rect: Rect) {
+    app.table_area = rect;
+    let displayed = app.displayed_hops();
     let config = &app.tui_config;
     let widths = config.tui_columns.constraints(rect);
-    let header = render_table_header(app.tui_config.theme, &config.tui_columns);
+    let header = render_table_header(
+        app.tui_config.theme,
+        &config.tui_columns,
+        app.selected_column,
+        app.sort_by,
+        config.average_mode,
+        &config.glyphs,
+    );
     let selected_style = Style::default().add_modifier(Modifier::REVERSED);
-    let rows = app
-        .tracer_data()
-        .hops_for_flow(app.selected_flow)
-        .iter()
-        .map(|hop| {
-            render_table_row(
-                app,
-                hop,
-                &app.resolver,
-                &app.geoip_lookup,
-                &app.tui_config,
-                &config.tui_columns,
+    let hops = app.shown_hops();
+    let as_colors = if config.as_colors && config.lookup_as_info {
+        as_colors(hops, &app.resolver)
+    } else {
+        vec![None; hops.len()]
+    };
+    let hidden = hops.len() - displayed.len();
+    let order = app.hop_order();
+    let mut rows = Vec::new();
+    let mut selected_row = None;
+    for &index in &order[displayed.clone()] {
+        if app.table_state.selected() == Some(index) {
+            selected_row = Some(rows.len());
+        }
+        let as_color = as_colors[index];
+        let hop = app.windowed_hop(&hops[index]);
+        rows.push(render_table_row(
+            app,
+            &hop,
+            &app.resolver,
+            &app.geoip_lookup,
+            &app.tui_config,
+            &config.tui_columns,
+            as_color,
+        ));
+        rows.extend(render_addr_rows(app, &hop));
+    }
+    let is_remapped = app.tui_config.max_display_hops.is_some()
+        || app.sort_by.is_some()
+        || rows.len() > displayed.len();
+    let table = Table::new(rows, widths.as_slice()).header(header);
+    let table = if config.summary_footer {
+        table.footer(render_table_footer(app, hops.len(), &config.tui_columns))
+    } else {
+        table
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(app.tui_config.theme.border))
+        .title(Line::raw(if let Some((ttl, _)) = rtt_baseline(app) {
+            t!("title_hops_baseline", "ttl" => ttl)
+        } else {
+            t!("title_hops")
+        }));
+    let block = if hidden > 0 {
+        block.title_bottom(Line::raw(t!("title_hops_more",
+            "ellipsis" => app.tui_config.glyphs.ellipsis,
+            "count" => hidden)))
+    } else {
+        block
+    };
+    let hidden_unresponsive = app.hidden_unresponsive_hops();
+    let block =
+        if app.is_probing_curtailed() && hops.last().is_some_and(|hop| hop.total_recv() == 0) {
+            block.title_bottom(Line::raw(t!("title_hops_curtailed")).alignment(Alignment::Right))
+        } else if hidden_unresponsive > 0 {
+            block.title_bottom(
+                Line::raw(t!("title_hops_unresponsive", "count" => hidden_unresponsive))
+                    .alignment(Alignment::Right),
             )
-        });
-    let table = Table::new(rows, widths.as_slice())
-        .header(header)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(app.tui_config.theme.border))
-                .title(Line::raw(t!("title_hops"))),
-        )
+        } else {
+            block
+        };
+    let table = table
+        .block(block)
         .style(
             Style::default()
                 .bg(app.tui_config.theme.bg)
@@ -74,13 +144,46 @@ pub fn render(f: &mut Frame<'_>, app: &mut TuiApp, rect: Rect) {
         )
         .highlight_style(selected_style)
         .column_spacing(1);
-    f.render_stateful_widget(table, rect, &mut app.table_state);
+    if is_remapped {
+        let mut state = TableState::default().with_selected(selected_row);
+        f.render_stateful_widget(table, rect, &mut state);
+    } else {
+        f.render_stateful_widget(table, rect, &mut app.table_state);
+    }
 }
 
 /// Render the table header.
-fn render_table_header(theme: Theme, table_columns: &Columns) -> Row<'static> {
-    let header_cells = table_columns.columns().map(|c| {
-        Cell::from(c.typ.to_string()).style(Style::default().fg(theme.hops_table_header_text))
+///
+/// The selected column, if any, is shown underlined in the selected header colors.  The `Avg` column is titled `Ewma` if the
+/// exponentially weighted moving average is shown.  The column by which the hops are sorted, if
+/// any, is marked with the sort direction.
+fn render_table_header(
+    theme: Theme,
+    table_columns: &Columns,
+    selected_column: Option<usize>,
+    sort_by: Option<(ColumnType, SortDir)>,
+    average_mode: AverageMode,
+    glyphs: &Glyphs,
+) -> Row<'static> {
+    let header_cells = table_columns.columns().enumerate().map(|(i, c)| {
+        let style = if selected_column == Some(i) {
+            Style::default()
+                .bg(theme.hops_table_header_selected_bg)
+                .fg(theme.hops_table_header_selected_text)
+                .add_modifier(Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(theme.hops_table_header_text)
+        };
+        let title = match (c.typ, average_mode) {
+            (ColumnType::Average, AverageMode::Ewma) => t!("column_ewma").to_string(),
+            _ => c.typ.to_string(),
+        };
+        let marker = match sort_by {
+            Some((column, SortDir::Ascending)) if column == c.typ => glyphs.sort_ascending,
+            Some((column, SortDir::Descending)) if column == c.typ => glyphs.sort_descending,
+            _ => "",
+        };
+        aligned_cell(c.typ, format!("{title}{marker}")).style(style)
     });
     Row::new(header_cells)
         .style(Style::default().bg(theme.hops_table_header_bg))
@@ -88,7 +191,103 @@ fn render_table_header(theme: Theme, table_columns: &Columns) -> Row<'static> {
         .bottom_margin(0)
 }
 
+/// Render the table summary footer.
+///
+/// The end-to-end loss and round-trip times are those of the target hop, or of the highest hop
+/// in the round if the target has not been found, and the hop count is the number of hops in
+/// the path.
+fn render_table_footer(app: &TuiApp, hop_count: usize, table_columns: &Columns) -> Row<'static> {
+    let theme = app.tui_config.theme;
+    let target = app.windowed_hop(app.tracer_data().target_hop(app.selected_flow));
+    let rtt_mode = app.time_exceeded_rtt_mode(&target);
+    let footer_cells = table_columns.columns().map(|c| match c.typ {
+        ColumnType::Host => Cell::from(t!("summary_footer", "hop_count" => hop_count)),
+        ColumnType::LossPct => aligned_cell(c.typ, format_loss_pct(&target)),
+        ColumnType::Sent => aligned_cell(c.typ, target.total_sent().to_string()),
+        ColumnType::Received => aligned_cell(c.typ, target.total_recv().to_string()),
+        ColumnType::Last | ColumnType::Average | ColumnType::Best | ColumnType::Worst => {
+            let compact = is_compact_rtt(&target, &app.tui_config);
+            let places = app.tui_config.rtt_places(c.typ);
+            let baseline = rtt_baseline(app).map(|(_, baseline)| baseline);
+            let rtt = rtt_stat(c.typ, app, &target)
+                .map(|rtt| format_relative_rtt(rtt, baseline, places, compact));
+            render_rtt_cell(aligned_cell(c.typ, rtt.unwrap_or_default()), rtt_mode)
+        }
+        ColumnType::StdDev => {
+            render_rtt_cell(aligned_cell(c.typ, format_stddev(app, &target)), rtt_mode)
+        }
+        _ => Cell::default(),
+    });
+    Row::new(footer_cells)
+        .style(
+            Style::default()
+                .bg(theme.hops_table_header_bg)
+                .fg(theme.hops_table_header_text),
+        )
+        .height(1)
+        .top_margin(0)
+}
+
+/// The palette of colors used to tint hops by autonomous system (AS).
+const AS_COLORS: [Color; 12] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+];
+
+/// The colors to tint each hop with by autonomous system (AS).
+///
+/// Each AS number is mapped to a color from the palette by a stable hash, such that a given AS
+/// is usually tinted with the same color.  If two adjacent hops in different ASes would be tinted
+/// with the same color then the next color in the palette is used for the latter, so that AS
+/// boundaries are always visible.  Hops without AS information are not tinted.
+fn as_colors(hops: &[Hop], dns: &DnsResolver) -> Vec<Option<Color>> {
+    let mut prev: Option<(u32, usize)> = None;
+    hops.iter()
+        .map(|hop| {
+            let asn = hop_asn(hop, dns)?;
+            let index = match prev {
+                Some((prev_asn, prev_index)) if prev_asn == asn => prev_index,
+                Some((_, prev_index)) if prev_index == as_color_index(asn) => {
+                    (prev_index + 1) % AS_COLORS.len()
+                }
+                _ => as_color_index(asn),
+            };
+            prev = Some((asn, index));
+            Some(AS_COLORS[index])
+        })
+        .collect()
+}
+
+/// The index in the palette of the color for an AS number.
+const fn as_color_index(asn: u32) -> usize {
+    (asn.wrapping_mul(0x9E37_79B9) >> 16) as usize % AS_COLORS.len()
+}
+
+/// The AS number of the first address of a hop, if known.
+fn hop_asn(hop: &Hop, dns: &DnsResolver) -> Option<u32> {
+    let addr = hop.addrs().next()?;
+    match dns.lazy_reverse_lookup_with_asinfo(*addr) {
+        DnsEntry::Resolved(Resolved::WithAsInfo(_, _, asinfo))
+        | DnsEntry::NotFound(Unresolved::WithAsInfo(_, asinfo)) => {
+            asinfo.asn.trim().trim_start_matches("AS").parse().ok()
+        }
+        _ => None,
+    }
+}
+
 /// Render a single row in the table of hops.
+///
+/// If the hop is in the current round and has an `as_color` then the row is tinted with it.
 fn render_table_row(
     app: &TuiApp,
     hop: &Hop,
@@ -96,6 +295,7 @@ fn render_table_row(
     geoip_lookup: &GeoIpLookup,
     config: &TuiConfig,
     custom_columns: &Columns,
+    as_color: Option<Color>,
 ) -> Row<'static> {
     let is_selected_hop = app.selected_hop().is_some_and(|h| h.ttl() == hop.ttl());
     let is_in_round = app.tracer_data().is_in_round(hop, app.selected_flow);
@@ -118,10 +318,10 @@ fn render_table_row(
             )
         })
         .collect();
-    let row_color = if is_in_round {
-        config.theme.hops_table_row_active_text
-    } else {
-        config.theme.hops_table_row_inactive_text
+    let row_color = match (is_in_round, as_color) {
+        (true, Some(as_color)) => as_color,
+        (true, None) => config.theme.hops_table_row_active_text,
+        (false, _) => config.theme.hops_table_row_inactive_text,
     };
     Row::new(cells)
         .height(row_height)
@@ -129,6 +329,65 @@ fn render_table_row(
         .style(Style::default().fg(row_color))
 }
 
+/// Render a row for each address of a hop which is answered by more than one address.
+///
+/// Rows are only rendered if addresses are split.  Each row shows the statistics of the replies
+/// from one address, the loss of an address is not known as a probe which receives no reply
+/// cannot be attributed to any one address.
+fn render_addr_rows(app: &TuiApp, hop: &Hop) -> Vec<Row<'static>> {
+    let config = &app.tui_config;
+    let is_hidden = app.hide_private_hops && config.privacy_max_ttl >= hop.ttl();
+    if config.addr_aggregation == AddrAggregationMode::Combined || hop.addr_count() < 2 || is_hidden
+    {
+        return vec![];
+    }
+    let row_color = if app.tracer_data().is_in_round(hop, app.selected_flow) {
+        config.theme.hops_table_row_active_text
+    } else {
+        config.theme.hops_table_row_inactive_text
+    };
+    hop.addr_hops()
+        .map(|(addr, addr_hop)| {
+            let cells = config.tui_columns.columns().map(|column| match column.typ {
+                ColumnType::Host => {
+                    let address = format_address(
+                        addr,
+                        addr_hop.total_recv(),
+                        hop,
+                        &app.resolver,
+                        &app.geoip_lookup,
+                        config,
+                    );
+                    Cell::from(truncate_host(app, format!("  {address}")))
+                }
+                ColumnType::Received
+                | ColumnType::Last
+                | ColumnType::Average
+                | ColumnType::Best
+                | ColumnType::Worst
+                | ColumnType::StdDev
+                | ColumnType::Jitter
+                | ColumnType::Javg
+                | ColumnType::Jmax
+                | ColumnType::Jinta => new_cell(
+                    column.typ,
+                    false,
+                    app,
+                    addr_hop,
+                    &app.resolver,
+                    &app.geoip_lookup,
+                    config,
+                ),
+                _ => Cell::default(),
+            });
+            Row::new(cells)
+                .height(1)
+                .bottom_margin(0)
+                .style(Style::default().fg(row_color))
+        })
+        .collect()
+}
+
 ///Returns a Cell matched on short char of the Column
 fn new_cell(
     column: ColumnType,
@@ -141,8 +400,10 @@ fn new_cell(
 ) -> Cell<'static> {
     let is_target = app.tracer_data().is_target(hop, app.selected_flow);
     let total_recv = hop.total_recv();
+    let rtt_mode = app.time_exceeded_rtt_mode(hop);
+    let cell = |text: String| aligned_cell(column, text);
     match column {
-        ColumnType::Ttl => render_usize_cell(hop.ttl().into()),
+        ColumnType::Ttl => cell(hop.ttl().to_string()),
         ColumnType::Host => {
             let (host_cell, _) = if is_selected_hop && app.show_hop_details {
                 render_hostname_with_details(app, hop, dns, geoip_lookup, config)
@@ -151,31 +412,280 @@ fn new_cell(
             };
             host_cell
         }
-        ColumnType::LossPct => render_loss_pct_cell(hop),
-        ColumnType::Sent => render_usize_cell(hop.total_sent()),
-        ColumnType::Received => render_usize_cell(hop.total_recv()),
-        ColumnType::Failed => render_usize_cell(hop.total_failed()),
-        ColumnType::Last => render_float_cell(hop.last_ms(), 1, total_recv),
-        ColumnType::Average => render_avg_cell(hop),
-        ColumnType::Best => render_float_cell(hop.best_ms(), 1, total_recv),
-        ColumnType::Worst => render_float_cell(hop.worst_ms(), 1, total_recv),
-        ColumnType::StdDev => render_stddev_cell(hop),
-        ColumnType::Status => render_status_cell(hop, is_target),
-        ColumnType::Jitter => render_float_cell(hop.jitter_ms(), 1, total_recv),
-        ColumnType::Javg => render_float_cell(Some(hop.javg_ms()), 1, total_recv),
-        ColumnType::Jmax => render_float_cell(hop.jmax_ms(), 1, total_recv),
-        ColumnType::Jinta => render_float_cell(Some(hop.jinta()), 1, total_recv),
-        ColumnType::LastSrcPort => render_port_cell(hop.last_src_port()),
-        ColumnType::LastDestPort => render_port_cell(hop.last_dest_port()),
-        ColumnType::LastSeq => render_usize_cell(usize::from(hop.last_sequence())),
+        ColumnType::LossPct => cell(format_loss_pct(hop)),
+        ColumnType::Sent => cell(hop.total_sent().to_string()),
+        ColumnType::Received => cell(hop.total_recv().to_string()),
+        ColumnType::Failed => cell(hop.total_failed().to_string()),
+        ColumnType::Last => {
+            let style = rtt_heat_color(app, hop, config).map_or_else(Style::default, |color| {
+                Style::default().bg(color).fg(Color::Black)
+            });
+            let text = format_rtt_stat(column, app, hop, config);
+            render_styled_rtt_cell(cell(text), rtt_mode, style)
+        }
+        ColumnType::Average | ColumnType::Best | ColumnType::Worst => {
+            render_rtt_cell(cell(format_rtt_stat(column, app, hop, config)), rtt_mode)
+        }
+        ColumnType::StdDev => render_rtt_cell(cell(format_stddev(app, hop)), rtt_mode),
+        ColumnType::Status => {
+            let is_found_target =
+                app.tracer_data().target_ttl(app.selected_flow) == Some(hop.ttl());
+            let is_appeared = app.is_path_change_flagged(hop.appeared_round());
+            render_status_cell(
+                hop,
+                is_target,
+                is_found_target,
+                is_appeared,
+                config.status_mode,
+                &app.tui_config.glyphs,
+            )
+        }
+        ColumnType::Jitter => render_rtt_cell(
+            cell(format_float(
+                hop.jitter_ms(),
+                config.rtt_places(column),
+                total_recv,
+            )),
+            rtt_mode,
+        ),
+        ColumnType::Javg => render_rtt_cell(
+            cell(format_float(
+                Some(hop.javg_ms()),
+                config.rtt_places(column),
+                total_recv,
+            )),
+            rtt_mode,
+        ),
+        ColumnType::Jmax => render_rtt_cell(
+            cell(format_float(
+                hop.jmax_ms(),
+                config.rtt_places(column),
+                total_recv,
+            )),
+            rtt_mode,
+        ),
+        ColumnType::Jinta => render_rtt_cell(
+            cell(format_float(
+                Some(hop.jinta()),
+                config.rtt_places(column),
+                total_recv,
+            )),
+            rtt_mode,
+        ),
+        ColumnType::LastSrcPort => cell(format_port(hop.last_src_port())),
+        ColumnType::LastDestPort => cell(format_port(hop.last_dest_port())),
+        ColumnType::LastSeq => cell(hop.last_sequence().to_string()),
         ColumnType::LastIcmpPacketType => render_icmp_packet_type_cell(hop.last_icmp_packet_type()),
-        ColumnType::LastIcmpPacketCode => render_icmp_packet_code_cell(hop.last_icmp_packet_type()),
+        ColumnType::LastIcmpPacketCode => {
+            cell(format_icmp_packet_code(hop.last_icmp_packet_type()))
+        }
         ColumnType::LastNatStatus => render_nat_cell(hop.last_nat_status()),
+        ColumnType::Provider => render_provider_cell(hop, dns, config),
+        ColumnType::Asymmetry => render_asymmetry_cell(hop),
+        ColumnType::Protocol => render_protocol_cell(app, hop),
+        ColumnType::RateLimit => render_rate_limit_cell(app, hop),
+        ColumnType::RecentLoss => cell(format_recent_loss_pct(hop, config)),
+        ColumnType::Tunnel => render_tunnel_cell(app, hop, config),
+        ColumnType::Mpls => render_mpls_cell(hop),
+        ColumnType::Asn => render_asn_cell(hop, dns, config),
+        ColumnType::GeoLoc => render_geoloc_cell(hop, geoip_lookup),
     }
 }
 
-fn render_usize_cell(value: usize) -> Cell<'static> {
-    Cell::from(format!("{value}"))
+/// Render the provider class of the AS of the first address of a hop.
+///
+/// Shows `-` if AS info lookup is disabled or the provider class is not known.
+fn render_provider_cell(hop: &Hop, dns: &DnsResolver, config: &TuiConfig) -> Cell<'static> {
+    let class = hop
+        .addrs()
+        .next()
+        .filter(|_| config.lookup_as_info)
+        .and_then(|addr| match dns.lazy_reverse_lookup_with_asinfo(*addr) {
+            DnsEntry::Resolved(Resolved::WithAsInfo(_, _, asinfo))
+            | DnsEntry::NotFound(Unresolved::WithAsInfo(_, asinfo)) => config
+                .provider_lookup
+                .lookup(&asinfo.asn)
+                .map(ToString::to_string),
+            _ => None,
+        });
+    Cell::from(class.unwrap_or_else(|| String::from("-")))
+}
+
+/// Render the estimated path asymmetry of a hop.
+///
+/// The number of hops on the reverse path is estimated from the ttl of the
+/// last reply, assuming the responder used the nearest common initial ttl
+/// (`64`, `128` or `255`).  As this is a heuristic it is shown with a `~`.
+///
+/// Shows `-` if the ttl of the reply is not known.
+fn render_asymmetry_cell(hop: &Hop) -> Cell<'static> {
+    Cell::from(hop.last_reply_ttl().map_or_else(
+        || String::from("-"),
+        |reply_ttl| match path_asymmetry(hop.ttl(), reply_ttl) {
+            0 => String::from("~0"),
+            delta => format!("~{delta:+}"),
+        },
+    ))
+}
+
+/// Render the protocol of the probes which a hop responded to.
+///
+/// Shows nothing if the hop has not responded.
+fn render_protocol_cell(app: &TuiApp, hop: &Hop) -> Cell<'static> {
+    if hop.total_recv() == 0 {
+        return Cell::default();
+    }
+    Cell::from(match app.tracer_config().data.protocol() {
+        Protocol::Icmp => t!("icmp"),
+        Protocol::Udp => t!("udp"),
+        Protocol::Tcp => t!("tcp"),
+    })
+}
+
+/// Render whether a hop is likely to be rate limiting ICMP.
+///
+/// Shows nothing if the hop is not likely to be rate limited.
+fn render_rate_limit_cell(app: &TuiApp, hop: &Hop) -> Cell<'static> {
+    let later_loss = app
+        .shown_hops()
+        .iter()
+        .filter(|later| later.ttl() > hop.ttl())
+        .map(|later| app.windowed_hop(later).loss_pct());
+    if is_rate_limited(hop, later_loss) {
+        Cell::from(t!("rate_limit_likely").to_string())
+    } else {
+        Cell::default()
+    }
+}
+
+/// The minimum number of probes sent to a hop before it may be considered rate limited.
+const RATE_LIMIT_MIN_SENT: usize = 10;
+
+/// Is a hop likely to be rate limiting ICMP?
+///
+/// A hop is likely rate limited if it shows partial loss whilst a later hop shows lower loss, as
+/// probes which are forwarded beyond a hop cannot have been lost at it.
+fn is_rate_limited(hop: &Hop, later_loss: impl IntoIterator<Item = f64>) -> bool {
+    let loss = hop.loss_pct();
+    hop.total_sent() >= RATE_LIMIT_MIN_SENT
+        && hop.total_recv() > 0
+        && loss > 0_f64
+        && later_loss.into_iter().any(|later| later < loss)
+}
+
+/// The estimated difference between the reverse and forward hop counts.
+fn path_asymmetry(ttl: u8, reply_ttl: u8) -> i16 {
+    reverse_hops(reply_ttl) - i16::from(ttl)
+}
+
+/// The estimated number of hops on the reverse path, assuming the responder used the nearest
+/// common initial ttl (`64`, `128` or `255`).
+fn reverse_hops(reply_ttl: u8) -> i16 {
+    let initial_ttl = [64, 128, 255]
+        .into_iter()
+        .find(|&initial| initial >= reply_ttl)
+        .unwrap_or(u8::MAX);
+    i16::from(initial_ttl) - i16::from(reply_ttl) + 1
+}
+
+/// Render whether a hop is likely to be the ingress or egress of a tunnel.
+///
+/// Shows `~in` if the reverse path length jumps between the hop and the next responding hop,
+/// `~out` if it jumps between the previous responding hop and the hop and `~in/out` if both, see
+/// [`is_tunnel_jump`].  As this is a heuristic it is shown with a `~`.
+///
+/// Shows nothing if the ttl of the replies from the hop is not known.
+fn render_tunnel_cell(app: &TuiApp, hop: &Hop, config: &TuiConfig) -> Cell<'static> {
+    let Some(reply_ttl) = hop.last_reply_ttl() else {
+        return Cell::default();
+    };
+    let hops = app.tracer_data().hops_for_flow(app.selected_flow);
+    let with_reply_ttl = |other: &Hop| other.last_reply_ttl().map(|ttl| (other.ttl(), ttl));
+    let previous = hops
+        .iter()
+        .take_while(|previous| previous.ttl() < hop.ttl())
+        .filter_map(with_reply_ttl)
+        .last();
+    let next = hops
+        .iter()
+        .filter(|next| next.ttl() > hop.ttl())
+        .find_map(with_reply_ttl);
+    let current = (hop.ttl(), reply_ttl);
+    let jump = config.tunnel_ttl_jump;
+    let is_egress = previous.is_some_and(|previous| is_tunnel_jump(previous, current, jump));
+    let is_ingress = next.is_some_and(|next| is_tunnel_jump(current, next, jump));
+    Cell::from(match (is_ingress, is_egress) {
+        (true, true) => "~in/out",
+        (true, false) => "~in",
+        (false, true) => "~out",
+        (false, false) => "",
+    })
+}
+
+/// Render the MPLS labels of the last response for a hop, outermost first.
+///
+/// Shows nothing if the last response did not include an MPLS label stack extension, which is
+/// only parsed if `icmp-extensions` is enabled.
+/// Render the AS number of the first address of a hop, i.e. `AS15169`.
+///
+/// Shows a dim placeholder if AS info lookup is disabled and nothing if the AS is not known.
+fn render_asn_cell(hop: &Hop, dns: &DnsResolver, config: &TuiConfig) -> Cell<'static> {
+    if !config.lookup_as_info {
+        return render_placeholder_cell();
+    }
+    Cell::from(
+        hop_asn(hop, dns)
+            .map(|asn| format!("AS{asn}"))
+            .unwrap_or_default(),
+    )
+}
+
+/// Render the `GeoIp` location of the first address of a hop, i.e. `Mountain View, CA, US`.
+///
+/// Shows a dim placeholder if no `GeoIp` database is configured and nothing if the location is
+/// not known.
+fn render_geoloc_cell(hop: &Hop, geoip_lookup: &GeoIpLookup) -> Cell<'static> {
+    if !geoip_lookup.is_enabled() {
+        return render_placeholder_cell();
+    }
+    Cell::from(
+        hop.addrs()
+            .next()
+            .and_then(|addr| geoip_lookup.lookup(*addr).unwrap_or_default())
+            .map(|geo| geo.short_name())
+            .unwrap_or_default(),
+    )
+}
+
+/// Render a dim placeholder for a cell for which the data is not available.
+fn render_placeholder_cell() -> Cell<'static> {
+    Cell::from("-").style(Style::default().add_modifier(Modifier::DIM))
+}
+
+fn render_mpls_cell(hop: &Hop) -> Cell<'static> {
+    hop.extensions().map_or_else(Cell::default, |extensions| {
+        Cell::from(mpls_labels(extensions).join(","))
+    })
+}
+
+/// Does the reverse path length jump between two responding hops, given as their ttl and the ttl
+/// of their replies?
+///
+/// The reverse path length is expected to increase by as many hops as the forward path length,
+/// and so it jumps if it increases by at least `jump` hops more than that.
+fn is_tunnel_jump(
+    (from_ttl, from_reply_ttl): (u8, u8),
+    (to_ttl, to_reply_ttl): (u8, u8),
+    jump: u8,
+) -> bool {
+    let forward = i16::from(to_ttl) - i16::from(from_ttl);
+    let reverse = reverse_hops(to_reply_ttl) - reverse_hops(from_reply_ttl);
+    reverse - forward >= i16::from(jump)
+}
+
+/// A cell holding text which is aligned as for its column.
+fn aligned_cell(column: ColumnType, text: String) -> Cell<'static> {
+    Cell::from(Line::from(text).alignment(column.alignment()))
 }
 
 fn render_nat_cell(value: NatStatus) -> Cell<'static> {
@@ -186,71 +696,291 @@ fn render_nat_cell(value: NatStatus) -> Cell<'static> {
     })
 }
 
-fn render_loss_pct_cell(hop: &Hop) -> Cell<'static> {
-    Cell::from(format!("{:.1}%", hop.loss_pct()))
+fn format_loss_pct(hop: &Hop) -> String {
+    format!("{:.1}%", hop.loss_pct())
 }
 
-fn render_avg_cell(hop: &Hop) -> Cell<'static> {
-    Cell::from(if hop.total_recv() > 0 {
-        format!("{:.1}", hop.avg_ms())
-    } else {
-        String::default()
+fn format_recent_loss_pct(hop: &Hop, config: &TuiConfig) -> String {
+    let weight = f64::from(config.loss_ewma_weight) / 100_f64;
+    format!("{:.1}%", hop.recent_loss_pct(weight))
+}
+
+fn format_stddev(app: &TuiApp, hop: &Hop) -> String {
+    let places = app.tui_config.rtt_places(ColumnType::StdDev);
+    app.stddev_ms(hop)
+        .map(|stddev| format!("{stddev:.places$}"))
+        .unwrap_or_default()
+}
+
+/// Format a round-trip time statistic of a hop.
+///
+/// If enabled, the statistic is shown with the increment over the same statistic of the nearest
+/// previous hop with a round-trip time, i.e. `58.0 (+12.0)`.  The increment is not shown if there
+/// is no such previous hop.
+///
+/// If enabled, the statistic is shown relative to the first responding hop, see
+/// [`rtt_baseline`], i.e. `+46.0 (+12.0)`.
+///
+/// The statistic, and any increment, is shown in seconds if the hop is compact, see
+/// [`is_compact_rtt`].
+fn format_rtt_stat(column: ColumnType, app: &TuiApp, hop: &Hop, config: &TuiConfig) -> String {
+    let Some(rtt) = rtt_stat(column, app, hop) else {
+        return String::new();
+    };
+    let previous = config
+        .tui_columns
+        .rtt_deltas()
+        .then(|| previous_rtt_hop(app, hop))
+        .flatten()
+        .and_then(|previous| rtt_stat(column, app, previous));
+    let baseline = rtt_baseline(app).map(|(_, baseline)| baseline);
+    let compact = is_compact_rtt(hop, config);
+    let places = config.rtt_places(column);
+    match previous {
+        Some(previous) => format!(
+            "{} ({})",
+            format_relative_rtt(rtt, baseline, places, compact),
+            format_rtt_delta(rtt - previous, places, compact)
+        ),
+        None => format_relative_rtt(rtt, baseline, places, compact),
+    }
+}
+
+/// The ttl and average round-trip time of the first responding hop, if round-trip times are shown
+/// relative to it.
+///
+/// The first responding hop is the first hop with a round-trip time that is shown, such that the
+/// latency of the local network is excluded from the round-trip times of all hops.
+fn rtt_baseline(app: &TuiApp) -> Option<(u8, f64)> {
+    if !app.tui_config.rtt_baseline {
+        return None;
+    }
+    app.tracer_data()
+        .hops_for_flow(app.selected_flow)
+        .iter()
+        .find(|hop| is_rtt_shown(app, hop))
+        .map(|hop| (hop.ttl(), app.avg_ms(&app.windowed_hop(hop))))
+}
+
+/// Format a round-trip time, relative to a baseline if there is one, i.e. `+46.0`.
+fn format_relative_rtt(
+    rtt_ms: f64,
+    baseline_ms: Option<f64>,
+    places: usize,
+    compact: bool,
+) -> String {
+    match baseline_ms {
+        Some(baseline_ms) => format_rtt_delta(rtt_ms - baseline_ms, places, compact),
+        None => format_rtt(rtt_ms, places, compact),
+    }
+}
+
+/// Whether the round-trip times of a hop should be shown in seconds.
+///
+/// All round-trip times of a hop are shown in the same unit, so a hop is compact if the worst
+/// round-trip time of the hop is at or above the configured threshold.
+fn is_compact_rtt(hop: &Hop, config: &TuiConfig) -> bool {
+    config.rtt_compact_threshold.is_some_and(|threshold| {
+        hop.total_recv() > 0
+            && hop
+                .worst_ms()
+                .is_some_and(|worst| worst >= threshold.as_secs_f64() * 1000_f64)
     })
 }
 
-fn render_stddev_cell(hop: &Hop) -> Cell<'static> {
-    Cell::from(if hop.total_recv() > 1 {
-        format!("{:.1}", hop.stddev_ms())
+/// Format a round-trip time in milliseconds, i.e. `58.0`, or in seconds if compact, i.e. `1.2s`.
+fn format_rtt(rtt_ms: f64, places: usize, compact: bool) -> String {
+    if compact {
+        format!("{:.places$}s", rtt_ms / 1000_f64)
     } else {
-        String::default()
-    })
+        format!("{rtt_ms:.places$}")
+    }
+}
+
+/// Format a signed round-trip time increment, i.e. `+12.0`, or `+0.3s` if compact.
+fn format_rtt_delta(delta_ms: f64, places: usize, compact: bool) -> String {
+    if compact {
+        format!("{:+.places$}s", delta_ms / 1000_f64)
+    } else {
+        format!("{delta_ms:+.places$}")
+    }
+}
+
+/// The round-trip time statistic of a hop for a column, if the hop has responded.
+fn rtt_stat(column: ColumnType, app: &TuiApp, hop: &Hop) -> Option<f64> {
+    if hop.total_recv() == 0 {
+        return None;
+    }
+    match column {
+        ColumnType::Last => hop.last_ms(),
+        ColumnType::Average => Some(app.avg_ms(hop)),
+        ColumnType::Best => hop.best_ms(),
+        ColumnType::Worst => hop.worst_ms(),
+        _ => None,
+    }
+}
+
+/// The nearest hop before `hop` which has a round-trip time that is shown.
+fn previous_rtt_hop<'a>(app: &'a TuiApp, hop: &Hop) -> Option<&'a Hop> {
+    app.tracer_data()
+        .hops_for_flow(app.selected_flow)
+        .iter()
+        .take_while(|previous| previous.ttl() < hop.ttl())
+        .filter(|previous| is_rtt_shown(app, previous))
+        .last()
+}
+
+/// Whether a hop has a round-trip time that is shown.
+const fn is_rtt_shown(app: &TuiApp, hop: &Hop) -> bool {
+    hop.total_recv() > 0
+        && !matches!(
+            app.time_exceeded_rtt_mode(hop),
+            TimeExceededRttMode::Exclude
+        )
 }
 
-fn render_float_cell(value: Option<f64>, places: usize, total_recv: usize) -> Cell<'static> {
-    Cell::from(if total_recv > 0 {
+fn format_float(value: Option<f64>, places: usize, total_recv: usize) -> String {
+    if total_recv > 0 {
         value.map(|v| format!("{v:.places$}")).unwrap_or_default()
     } else {
         String::default()
-    })
+    }
+}
+
+/// Render a cell holding a round-trip time (or jitter) statistic.
+///
+/// Round-trip times derived from `TimeExceeded` responses are shown in italics or not shown at
+/// all, depending on the `TimeExceededRttMode`.
+fn render_rtt_cell(cell: Cell<'static>, rtt_mode: TimeExceededRttMode) -> Cell<'static> {
+    render_styled_rtt_cell(cell, rtt_mode, Style::default())
 }
 
-fn render_status_cell(hop: &Hop, is_target: bool) -> Cell<'static> {
+/// Render a cell holding a round-trip time statistic with a style, such as a heat color.
+fn render_styled_rtt_cell(
+    cell: Cell<'static>,
+    rtt_mode: TimeExceededRttMode,
+    style: Style,
+) -> Cell<'static> {
+    match rtt_mode {
+        TimeExceededRttMode::Include => cell.style(style),
+        TimeExceededRttMode::Distinguish => cell.style(style.add_modifier(Modifier::ITALIC)),
+        TimeExceededRttMode::Exclude => Cell::default(),
+    }
+}
+
+/// The heat color of the last round-trip time of a hop, if any.
+///
+/// The color is on a gradient from the low to the high heat color of the theme, scaled either to
+/// the lowest and highest last round-trip times of all hops on the path, or from zero to the
+/// configured maximum.  No color is used if either heat color of the theme is `reset`, such that
+/// the round-trip time is shown as plain text, as on a monochrome terminal.
+fn rtt_heat_color(app: &TuiApp, hop: &Hop, config: &TuiConfig) -> Option<Color> {
+    let (low, high) = (config.theme.rtt_heat_low, config.theme.rtt_heat_high);
+    if low == Color::Reset || high == Color::Reset {
+        return None;
+    }
+    let rtt = rtt_stat(ColumnType::Last, app, hop)?;
+    let (min, max) = match config.rtt_heat_mode {
+        RttHeatMode::Off => return None,
+        RttHeatMode::Path => app
+            .tracer_data()
+            .hops_for_flow(app.selected_flow)
+            .iter()
+            .filter_map(|hop| rtt_stat(ColumnType::Last, app, hop))
+            .fold((rtt, rtt), |(min, max), rtt| (min.min(rtt), max.max(rtt))),
+        RttHeatMode::Fixed => (0_f64, config.rtt_heat_max.as_secs_f64() * 1000_f64),
+    };
+    let fraction = if max > min {
+        ((rtt - min) / (max - min)).clamp(0_f64, 1_f64)
+    } else {
+        0_f64
+    };
+    Some(gradient(low, high, fraction))
+}
+
+/// Render the status of a hop.
+///
+/// The status is shown as configured by the `StatusMode`:
+///
+/// - `Health`: a glyph showing whether the hop has no, some or total loss
+/// - `Summary`: the number of probes received and sent, i.e. `9/10`
+/// - `Icmp`: the type of the last ICMP response, as for the `Type` column, and the reason given
+///   by a `DestinationUnreachable` response, i.e. `DU:PRT` for port unreachable
+///
+/// The hop at which the target was found is marked with a `★`, or a `*` for ASCII glyphs, and a
+/// hop which recently appeared, as the path grew longer, is marked with a `+`.
+fn render_status_cell(
+    hop: &Hop,
+    is_target: bool,
+    is_found_target: bool,
+    is_appeared: bool,
+    status_mode: StatusMode,
+    glyphs: &Glyphs,
+) -> Cell<'static> {
     let lost = hop.total_sent() - hop.total_recv();
-    Cell::from(match (lost, is_target) {
-        (lost, target) if target && lost == hop.total_sent() => "🔴",
-        (lost, target) if target && lost > 0 => "🟡",
-        (lost, target) if !target && lost == hop.total_sent() => "🟤",
-        (lost, target) if !target && lost > 0 => "🔵",
-        _ => "🟢",
-    })
+    let status = match status_mode {
+        StatusMode::Health => String::from(match (lost, is_target) {
+            (lost, target) if target && lost == hop.total_sent() => glyphs.status_target_lost,
+            (lost, target) if target && lost > 0 => glyphs.status_target_loss,
+            (lost, target) if !target && lost == hop.total_sent() => glyphs.status_hop_lost,
+            (lost, target) if !target && lost > 0 => glyphs.status_hop_loss,
+            _ => glyphs.status_ok,
+        }),
+        StatusMode::Summary => format!("{}/{}", hop.total_recv(), hop.total_sent()),
+        StatusMode::Icmp => match hop.last_unreachable_code() {
+            Some(code) => format!("DU:{}", unreachable_code(code)),
+            None => String::from(icmp_packet_type_code(hop.last_icmp_packet_type())),
+        },
+    };
+    let found_marker = if is_found_target { glyphs.target } else { "" };
+    let appeared_marker = if is_appeared { "+" } else { "" };
+    Cell::from(format!("{status}{found_marker}{appeared_marker}"))
 }
 
 fn render_icmp_packet_type_cell(icmp_packet_type: Option<IcmpPacketType>) -> Cell<'static> {
+    Cell::from(icmp_packet_type_code(icmp_packet_type))
+}
+
+/// The short code of an ICMP packet type, i.e. `TE` for `TimeExceeded`.
+const fn icmp_packet_type_code(icmp_packet_type: Option<IcmpPacketType>) -> &'static str {
     match icmp_packet_type {
-        None => Cell::from("n/a"),
-        Some(IcmpPacketType::TimeExceeded(_)) => Cell::from("TE"),
-        Some(IcmpPacketType::EchoReply(_)) => Cell::from("ER"),
-        Some(IcmpPacketType::Unreachable(_)) => Cell::from("DU"),
-        Some(IcmpPacketType::NotApplicable) => Cell::from("NA"),
+        None => "n/a",
+        Some(IcmpPacketType::TimeExceeded(_)) => "TE",
+        Some(IcmpPacketType::EchoReply(_)) => "ER",
+        Some(IcmpPacketType::Unreachable(_)) => "DU",
+        Some(IcmpPacketType::NotApplicable) => "NA",
     }
 }
 
-fn render_icmp_packet_code_cell(icmp_packet_type: Option<IcmpPacketType>) -> Cell<'static> {
+/// The short text of the reason given by a `DestinationUnreachable` response, i.e. `PRT` for
+/// `Port`, or the numeric code if the reason is not known.
+fn unreachable_code(code: UnreachableCode) -> String {
+    match code {
+        UnreachableCode::Network => String::from("NET"),
+        UnreachableCode::Host => String::from("HST"),
+        UnreachableCode::Protocol => String::from("PRO"),
+        UnreachableCode::Port => String::from("PRT"),
+        UnreachableCode::AdminProhibited => String::from("ADM"),
+        UnreachableCode::Other(code) => code.to_string(),
+    }
+}
+
+fn format_icmp_packet_code(icmp_packet_type: Option<IcmpPacketType>) -> String {
     match icmp_packet_type {
         Some(
             IcmpPacketType::Unreachable(code)
             | IcmpPacketType::TimeExceeded(code)
             | IcmpPacketType::EchoReply(code),
-        ) => Cell::from(format!("{}", code.0)),
-        _ => Cell::from(t!("na")),
+        ) => format!("{}", code.0),
+        _ => t!("na").to_string(),
     }
 }
 
-fn render_port_cell(port: u16) -> Cell<'static> {
+fn format_port(port: u16) -> String {
     if port > 0 {
-        Cell::from(format!("{port}"))
+        format!("{port}")
     } else {
-        Cell::from(t!("na"))
+        t!("na").to_string()
     }
 }
 
@@ -272,6 +1002,7 @@ fn render_hostname(
                         .map(|(addr, &freq)| {
                             format_address(addr, freq, hop, dns, geoip_lookup, &app.tui_config)
                         })
+                        .map(|address| truncate_host(app, address))
                         .join("\n");
                     let count = hop.addr_count().clamp(1, u8::MAX as usize);
                     (hostnames, count as u16)
@@ -285,6 +1016,7 @@ fn render_hostname(
                         .map(|(addr, &freq)| {
                             format_address(addr, freq, hop, dns, geoip_lookup, &app.tui_config)
                         })
+                        .map(|address| truncate_host(app, address))
                         .join("\n");
                     let count = hop.addr_count().clamp(1, max_addr as usize);
                     (hostnames, count as u16)
@@ -292,9 +1024,144 @@ fn render_hostname(
             }
         }
     } else {
-        (format!("{}", t!("no_response")), 1)
+        (format_unresponsive(app, hop), 1)
+    };
+    let hostname = if app.is_hop_paused(hop) {
+        format!("{hostname} [{}]", t!("paused"))
+    } else {
+        hostname
+    };
+    let style = hostname_style(hop, app.tui_config.unresponsive_hop_mode);
+    (Cell::from(hostname).style(style), count)
+}
+
+/// Truncate a formatted address to fit in the `Host` column.
+///
+/// The untruncated address is available in the hop details view.
+fn truncate_host(app: &TuiApp, address: String) -> String {
+    let Some(width) = host_column_width(&app.tui_config.tui_columns, app.table_area) else {
+        return address;
+    };
+    truncate_address(
+        address,
+        usize::from(width),
+        app.tui_config.host_truncation,
+        app.tui_config.glyphs.ellipsis,
+    )
+}
+
+/// Truncate an address to fit in `width` columns, if it does not already fit.
+fn truncate_address(
+    address: String,
+    width: usize,
+    truncation: HostTruncation,
+    ellipsis: &str,
+) -> String {
+    if address.width() <= width {
+        return address;
+    }
+    match truncation {
+        HostTruncation::Right => truncate_right(&address, width, ellipsis).into_owned(),
+        HostTruncation::Left => truncate_left(&address, width, ellipsis).into_owned(),
+    }
+}
+
+/// The width of the `Host` column, as laid out by the hops table.
+///
+/// Returns `None` if the `Host` column is not shown.
+fn host_column_width(columns: &Columns, rect: Rect) -> Option<u16> {
+    let widths: Vec<Constraint> = columns.constraints(rect);
+    Layout::horizontal(widths)
+        .spacing(1)
+        .split(rect.inner(Margin::new(1, 1)))
+        .iter()
+        .zip(columns.columns())
+        .find(|(_, column)| column.typ == ColumnType::Host)
+        .map(|(area, _)| area.width)
+}
+
+/// Truncate a string from the left to fit in `width` columns.
+///
+/// The truncated string begins with an ellipsis.  The width of wide
+/// characters is respected.
+fn truncate_left<'a>(s: &'a str, width: usize, ellipsis: &str) -> Cow<'a, str> {
+    if s.width() <= width {
+        return Cow::Borrowed(s);
+    }
+    let Some(available) = width.checked_sub(ellipsis.width()) else {
+        return Cow::Borrowed("");
+    };
+    let mut used = 0;
+    let mut start = s.len();
+    for (i, c) in s.char_indices().rev() {
+        used += c.width().unwrap_or_default();
+        if used > available {
+            break;
+        }
+        start = i;
+    }
+    Cow::Owned(format!("{ellipsis}{}", &s[start..]))
+}
+
+/// Truncate a string from the right to fit in `width` columns.
+///
+/// The truncated string ends with an ellipsis.  The width of wide
+/// characters is respected.
+fn truncate_right<'a>(s: &'a str, width: usize, ellipsis: &str) -> Cow<'a, str> {
+    if s.width() <= width {
+        return Cow::Borrowed(s);
+    }
+    let Some(available) = width.checked_sub(ellipsis.width()) else {
+        return Cow::Borrowed("");
     };
-    (Cell::from(hostname), count)
+    let mut used = 0;
+    let mut end = 0;
+    for (i, c) in s.char_indices() {
+        used += c.width().unwrap_or_default();
+        if used > available {
+            break;
+        }
+        end = i + c.len_utf8();
+    }
+    Cow::Owned(format!("{}{ellipsis}", &s[..end]))
+}
+
+/// Format a hop which has not responded.
+///
+/// The trailing hop is shown as waiting for a response, as it may not be a real hop, unless it
+/// should be shown as any other unresponsive hop.
+fn format_unresponsive(app: &TuiApp, hop: &Hop) -> String {
+    match app.tui_config.trailing_hop_mode {
+        TrailingHopMode::Waiting if app.is_trailing_hop(hop) => {
+            format!("({})", t!("waiting_for_response"))
+        }
+        _ => format_no_response(app.tui_config.unresponsive_hop_mode),
+    }
+}
+
+/// Format the hostname of a hop which has never responded.
+fn format_no_response(unresponsive_hop_mode: UnresponsiveHopMode) -> String {
+    match unresponsive_hop_mode {
+        UnresponsiveHopMode::NoResponse | UnresponsiveHopMode::LastKnown => {
+            format!("{}", t!("no_response"))
+        }
+        UnresponsiveHopMode::Blank => String::new(),
+        UnresponsiveHopMode::Stars => String::from("* * *"),
+    }
+}
+
+/// The style of the hostname cell of a hop.
+///
+/// For `UnresponsiveHopMode::LastKnown` the last known addresses of a hop
+/// which did not respond in the latest round are dimmed.
+fn hostname_style(hop: &Hop, unresponsive_hop_mode: UnresponsiveHopMode) -> Style {
+    let is_unresponsive =
+        hop.total_recv() > 0 && hop.samples().first().is_some_and(Duration::is_zero);
+    if unresponsive_hop_mode == UnresponsiveHopMode::LastKnown && is_unresponsive {
+        Style::default().add_modifier(Modifier::DIM)
+    } else {
+        Style::default()
+    }
 }
 
 /// Perform a reverse DNS lookup for an address and format the result.
@@ -426,7 +1293,17 @@ fn format_extensions(config: &TuiConfig, hop: &Hop) -> Option<String> {
 ///
 /// If not MPLS extensions are present then None is returned.
 fn format_extensions_mpls(extensions: &Extensions) -> Option<String> {
-    let labels = extensions
+    let labels = mpls_labels(extensions).join(", ");
+    if labels.is_empty() {
+        None
+    } else {
+        Some(format!("{}: {labels}", t!("labels")))
+    }
+}
+
+/// The labels of all MPLS label stack extensions.
+fn mpls_labels(extensions: &Extensions) -> impl Iterator<Item = u32> + '_ {
+    extensions
         .extensions
         .iter()
         .filter_map(|ext| match ext {
@@ -435,13 +1312,6 @@ fn format_extensions_mpls(extensions: &Extensions) -> Option<String> {
         })
         .flat_map(|ext| &ext.members)
         .map(|mem| mem.label)
-        .format(", ")
-        .to_string();
-    if labels.is_empty() {
-        None
-    } else {
-        Some(format!("{}: {labels}", t!("labels")))
-    }
 }
 
 /// Format all known extensions with full details.
@@ -516,12 +1386,60 @@ fn render_hostname_with_details(
             format!("**{}**", t!("hidden"))
         } else {
             let index = app.selected_hop_address;
-            format_details(hop, index, dns, geoip_lookup, config)
+            let details = format_details(hop, index, dns, geoip_lookup, config);
+            let details = match hop
+                .addrs()
+                .nth(index)
+                .and_then(|addr| app.notes.lookup(*addr))
+            {
+                Some(note) => format!("{details}\n{}: {note}", t!("note")),
+                None => details,
+            };
+            format!(
+                "{details}\n{}",
+                format_timeline(&app.timeline, &app.tui_config.glyphs)
+            )
         }
     } else {
-        format!("{}", t!("no_response"))
+        format_unresponsive(app, hop)
+    };
+    let rendered = if app.is_hop_paused(hop) {
+        format!("{rendered}\n[{}]", t!("paused"))
+    } else {
+        rendered
+    };
+    let rendered = match app.awaited_since(hop) {
+        Some(sent) if config.probe_countdown => {
+            let elapsed = sent.elapsed().unwrap_or_default();
+            let loss_timeout = app.tracer_config().data.loss_timeout();
+            format!("{rendered}\n{}", format_awaiting(elapsed, loss_timeout))
+        }
+        _ => rendered,
     };
-    (Cell::from(rendered), 7)
+    let style = hostname_style(hop, config.unresponsive_hop_mode);
+    let height = u16::try_from(rendered.lines().count()).unwrap_or(u16::MAX);
+    (Cell::from(rendered).style(style), height.max(7))
+}
+
+/// Format how long the probe for a hop has been awaiting a reply.
+///
+/// If a loss timeout is configured then the time remaining until the probe is considered lost is
+/// also shown:
+///
+/// ```text
+/// Awaiting reply for 0.8s (1.2s left)
+/// ```
+fn format_awaiting(elapsed: Duration, loss_timeout: Option<Duration>) -> String {
+    let elapsed_fmt = format!("{:.1}s", elapsed.as_secs_f64());
+    match loss_timeout {
+        Some(loss_timeout) => t!(
+            "awaiting_reply_countdown",
+            elapsed = elapsed_fmt,
+            remaining = format!("{:.1}s", loss_timeout.saturating_sub(elapsed).as_secs_f64())
+        )
+        .to_string(),
+        None => t!("awaiting_reply", elapsed = elapsed_fmt).to_string(),
+    }
 }
 
 /// Format hop details.
@@ -545,10 +1463,11 @@ fn format_details(
     };
     let ext = hop.extensions();
     let nat = hop.last_nat_status();
+    let quoted_len = hop.last_quoted_len();
     match dns_entry {
-        DnsEntry::Pending(addr) => {
-            fmt_details_line(addr, index, count, None, None, geoip, ext, nat, config)
-        }
+        DnsEntry::Pending(addr) => fmt_details_line(
+            addr, index, count, None, None, geoip, ext, nat, quoted_len, config,
+        ),
         DnsEntry::Resolved(Resolved::WithAsInfo(addr, hosts, asinfo)) => fmt_details_line(
             addr,
             index,
@@ -558,6 +1477,7 @@ fn format_details(
             geoip,
             ext,
             nat,
+            quoted_len,
             config,
         ),
         DnsEntry::NotFound(Unresolved::WithAsInfo(addr, asinfo)) => fmt_details_line(
@@ -569,6 +1489,7 @@ fn format_details(
             geoip,
             ext,
             nat,
+            quoted_len,
             config,
         ),
         DnsEntry::Resolved(Resolved::Normal(addr, hosts)) => fmt_details_line(
@@ -580,6 +1501,7 @@ fn format_details(
             geoip,
             ext,
             nat,
+            quoted_len,
             config,
         ),
         DnsEntry::NotFound(Unresolved::Normal(addr)) => fmt_details_line(
@@ -591,6 +1513,7 @@ fn format_details(
             geoip,
             ext,
             nat,
+            quoted_len,
             config,
         ),
         DnsEntry::Failed(ip) => {
@@ -602,6 +1525,40 @@ fn format_details(
     }
 }
 
+/// Format the timeline of the selected hop.
+///
+/// Format as follows, with a marker for each round and the time of day of the first and last
+/// rounds and of the last loss and spike:
+///
+/// ```text
+/// Timeline: ····×·····^···
+///           12:00:01 - 12:00:15, loss: 12:00:05, spike: 12:00:11
+/// ```
+fn format_timeline(timeline: &Timeline, glyphs: &Glyphs) -> String {
+    let label = t!("timeline");
+    let (Some((first, _)), Some((last, _))) = (timeline.rounds().next(), timeline.rounds().last())
+    else {
+        return format!("{label}: <{}>", t!("awaited"));
+    };
+    let fmt_time = |time: DateTime<Local>| time.format("%H:%M:%S").to_string();
+    let fmt_last = |outcome| {
+        timeline
+            .last(outcome)
+            .map_or_else(|| String::from("-"), fmt_time)
+    };
+    let strip: String = timeline.rounds().map(|(_, o)| o.marker(glyphs)).collect();
+    let indent = " ".repeat(label.width() + 2);
+    format!(
+        "{label}: {strip}\n{indent}{} - {}, {}: {}, {}: {}",
+        fmt_time(*first),
+        fmt_time(*last),
+        t!("timeline_loss"),
+        fmt_last(RoundOutcome::Loss),
+        t!("timeline_spike"),
+        fmt_last(RoundOutcome::Spike),
+    )
+}
+
 /// Format hostname detail lines.
 ///
 /// Format as follows:
@@ -614,7 +1571,11 @@ fn format_details(
 /// Geo: United States, North America
 /// Pos: 37.751, -97.822 (~1000km)
 /// Ext: [mpls(label=48268, ttl=1, exp=0, bos=1)]
+/// Quoted: 128 bytes
 /// ```
+///
+/// The quoted length is the number of bytes of the probe which the hop quoted in the last ICMP
+/// error it sent, which is not applicable for other responses.
 #[allow(clippy::too_many_arguments, clippy::cognitive_complexity)]
 fn fmt_details_line(
     addr: IpAddr,
@@ -625,6 +1586,7 @@ fn fmt_details_line(
     geoip: Option<Rc<GeoIpCity>>,
     extensions: Option<&Extensions>,
     nat: NatStatus,
+    quoted_len: Option<u16>,
     config: &TuiConfig,
 ) -> String {
     let as_fmt = match (config.lookup_as_info, asinfo) {
@@ -697,11 +1659,67 @@ fn fmt_details_line(
     } else {
         format!("{}: <{}>", t!("ext"), t!("none"))
     };
+    let quoted_fmt = match quoted_len {
+        Some(len) => format!("{}: {len} {}", t!("quoted"), t!("bytes")),
+        None => format!("{}: <{}>", t!("quoted"), t!("na")),
+    };
     let nat_fmt = match nat {
         NatStatus::Detected => " [NAT]",
         _ => "",
     };
     format!(
-        "{addr}{nat_fmt} [{index} of {count}]\n{hosts_rendered}\n{as_fmt}\n{geoip_fmt}\n{ext_fmt}"
+        "{addr}{nat_fmt} [{index} of {count}]\n{hosts_rendered}\n{as_fmt}\n{geoip_fmt}\n{ext_fmt}\n{quoted_fmt}"
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("example.com", 20, "example.com"; "fits")]
+    #[test_case("example.com", 11, "example.com"; "exact fit")]
+    #[test_case("example.com", 8, "example…"; "ascii")]
+    #[test_case("bücher.example", 7, "bücher…"; "multibyte")]
+    #[test_case("日本語.jp", 6, "日本…"; "wide")]
+    #[test_case("日本語.jp", 5, "日本…"; "wide boundary")]
+    #[test_case("example.com", 0, ""; "no space")]
+    fn test_truncate_right(s: &str, width: usize, expected: &str) {
+        let truncated = truncate_right(s, width, "…");
+        assert_eq!(expected, truncated);
+        assert!(truncated.width() <= width);
+    }
+
+    #[test_case("example.com", 20, "example.com"; "fits")]
+    #[test_case("example.com", 8, "…ple.com"; "ascii")]
+    #[test_case("bücher.example", 9, "….example"; "multibyte")]
+    #[test_case("ex.日本語", 6, "…本語"; "wide")]
+    fn test_truncate_left(s: &str, width: usize, expected: &str) {
+        let truncated = truncate_left(s, width, "…");
+        assert_eq!(expected, truncated);
+        assert!(truncated.width() <= width);
+    }
+
+    #[test_case("example.com", 20, HostTruncation::Right, "example.com"; "fits")]
+    #[test_case("example.com", 8, HostTruncation::Right, "example…"; "right")]
+    #[test_case("example.com", 8, HostTruncation::Left, "…ple.com"; "left")]
+    #[test_case("example.com", 1, HostTruncation::Right, "…"; "ellipsis only")]
+    #[test_case("example.com", 0, HostTruncation::Right, ""; "no space right")]
+    #[test_case("example.com", 0, HostTruncation::Left, ""; "no space left")]
+    fn test_truncate_address(s: &str, width: usize, truncation: HostTruncation, expected: &str) {
+        let truncated = truncate_address(s.to_string(), width, truncation, "…");
+        assert_eq!(expected, truncated);
+        assert!(truncated.width() <= width);
+    }
+
+    #[test]
+    fn test_truncate_long_hostname() {
+        let hostname = "ü".repeat(200);
+        let right = truncate_right(&hostname, 20, "…");
+        assert_eq!(format!("{}…", "ü".repeat(19)), right);
+        assert_eq!(20, right.width());
+        let left = truncate_left(&hostname, 20, "…");
+        assert_eq!(format!("…{}", "ü".repeat(19)), left);
+        assert_eq!(20, left.width());
+    }
+}
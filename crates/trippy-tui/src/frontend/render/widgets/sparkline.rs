@@ -265,7 +265,7 @@ impl Sparkline<'_> {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Default)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
 pub enum EmptyBarSymbol {
     #[default]
     Empty,
@@ -273,6 +273,7 @@ pub enum EmptyBarSymbol {
     MediumShade,
     DarkShade,
     Full,
+    Hash,
 }
 
 impl EmptyBarSymbol {
@@ -284,6 +285,7 @@ impl EmptyBarSymbol {
             Self::MediumShade => "▒",
             Self::DarkShade => "▓",
             Self::Full => "█",
+            Self::Hash => "#",
         }
     }
 }
@@ -4,7 +4,6 @@ use itertools::Itertools;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Margin, Rect};
 use ratatui::prelude::Line;
 use ratatui::style::{Color, Style};
-use ratatui::symbols::Marker;
 use ratatui::text::Span;
 use ratatui::widgets::canvas::{Canvas, Circle, Context, Map, MapResolution, Rectangle};
 use ratatui::widgets::{Block, BorderType, Borders, Clear, Paragraph};
@@ -52,7 +51,7 @@ fn render_map_canvas(f: &mut Frame<'_>, app: &TuiApp, rect: Rect, entries: &[Map
                     .iter()
                     .any(|hop| *hop > app.tui_config.privacy_max_ttl);
                 if !app.hide_private_hops || any_show {
-                    render_map_canvas_pin(ctx, entry);
+                    render_map_canvas_pin(ctx, entry, app.tui_config.glyphs.pin);
                     render_map_canvas_radius(ctx, entry, theme.map_radius);
                     render_map_canvas_selected(
                         ctx,
@@ -63,7 +62,7 @@ fn render_map_canvas(f: &mut Frame<'_>, app: &TuiApp, rect: Rect, entries: &[Map
                 }
             }
         })
-        .marker(Marker::Braille)
+        .marker(app.tui_config.glyphs.marker)
         .x_bounds([-180.0, 180.0])
         .y_bounds([-90.0, 90.0]);
     f.render_widget(Clear, rect);
@@ -79,13 +78,13 @@ fn render_map_canvas_world(ctx: &mut Context<'_>, color: Color) {
 }
 
 /// Render the map canvas pin.
-fn render_map_canvas_pin(ctx: &mut Context<'_>, entry: &MapEntry) {
+fn render_map_canvas_pin(ctx: &mut Context<'_>, entry: &MapEntry, pin: &'static str) {
     let MapEntry {
         latitude,
         longitude,
         ..
     } = entry;
-    ctx.print(*longitude, *latitude, Span::styled("📍", Style::default()));
+    ctx.print(*longitude, *latitude, Span::styled(pin, Style::default()));
 }
 
 /// Render the map canvas accuracy radius circle.
@@ -22,6 +22,10 @@ pub struct Theme {
     pub hops_table_header_bg: Color,
     /// The color of text in the hops table header.
     pub hops_table_header_text: Color,
+    /// The background color of the selected column in the hops table header.
+    pub hops_table_header_selected_bg: Color,
+    /// The color of text of the selected column in the hops table header.
+    pub hops_table_header_selected_text: Color,
     /// The color of text of active rows in the hops table.
     pub hops_table_row_active_text: Color,
     /// The color of text of inactive rows in the hops table.
@@ -74,6 +78,10 @@ pub struct Theme {
     pub map_info_panel_bg: Color,
     /// The color of text in the map info panel.
     pub map_info_panel_text: Color,
+    /// The color of the lowest round-trip time in the rtt heat gradient.
+    pub rtt_heat_low: Color,
+    /// The color of the highest round-trip time in the rtt heat gradient.
+    pub rtt_heat_high: Color,
 }
 
 impl From<TuiTheme> for Theme {
@@ -85,6 +93,8 @@ impl From<TuiTheme> for Theme {
             tab_text: Color::from(value.tab_text),
             hops_table_header_bg: Color::from(value.hops_table_header_bg),
             hops_table_header_text: Color::from(value.hops_table_header_text),
+            hops_table_header_selected_bg: Color::from(value.hops_table_header_selected_bg),
+            hops_table_header_selected_text: Color::from(value.hops_table_header_selected_text),
             hops_table_row_active_text: Color::from(value.hops_table_row_active_text),
             hops_table_row_inactive_text: Color::from(value.hops_table_row_inactive_text),
             hops_chart_selected: Color::from(value.hops_chart_selected),
@@ -111,6 +121,8 @@ impl From<TuiTheme> for Theme {
             map_info_panel_border: Color::from(value.map_info_panel_border),
             map_info_panel_bg: Color::from(value.map_info_panel_bg),
             map_info_panel_text: Color::from(value.map_info_panel_text),
+            rtt_heat_low: Color::from(value.rtt_heat_low),
+            rtt_heat_high: Color::from(value.rtt_heat_high),
         }
     }
 }
@@ -289,3 +301,149 @@ pub fn fmt_color(color: Color) -> String {
         _ => "unknown".to_string(),
     }
 }
+
+/// The RGB value of a color, using the xterm palette for named and indexed colors.
+pub const fn to_rgb(color: Color, default: [u8; 3]) -> [u8; 3] {
+    match color {
+        Color::Reset => default,
+        Color::Black => ansi_rgb(0),
+        Color::Red => ansi_rgb(1),
+        Color::Green => ansi_rgb(2),
+        Color::Yellow => ansi_rgb(3),
+        Color::Blue => ansi_rgb(4),
+        Color::Magenta => ansi_rgb(5),
+        Color::Cyan => ansi_rgb(6),
+        Color::Gray => ansi_rgb(7),
+        Color::DarkGray => ansi_rgb(8),
+        Color::LightRed => ansi_rgb(9),
+        Color::LightGreen => ansi_rgb(10),
+        Color::LightYellow => ansi_rgb(11),
+        Color::LightBlue => ansi_rgb(12),
+        Color::LightMagenta => ansi_rgb(13),
+        Color::LightCyan => ansi_rgb(14),
+        Color::White => ansi_rgb(15),
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Indexed(index) => ansi_rgb(index),
+    }
+}
+
+/// The color at `fraction` of the way from one color to another.
+///
+/// The hue, saturation and value of the colors are interpolated, rather than the red, green and
+/// blue components, such that the colors between green and red pass through yellow rather than
+/// brown.
+pub fn gradient(from: Color, to: Color, fraction: f64) -> Color {
+    let (from_hue, from_saturation, from_value) = rgb_to_hsv(to_rgb(from, [0, 0, 0]));
+    let (to_hue, to_saturation, to_value) = rgb_to_hsv(to_rgb(to, [0, 0, 0]));
+    let hue_delta = (to_hue - from_hue + 540_f64).rem_euclid(360_f64) - 180_f64;
+    hsv_to_rgb(
+        hue_delta.mul_add(fraction, from_hue).rem_euclid(360_f64),
+        (to_saturation - from_saturation).mul_add(fraction, from_saturation),
+        (to_value - from_value).mul_add(fraction, from_value),
+    )
+}
+
+/// Convert an RGB value to a hue in degrees, and a saturation and value between 0 and 1.
+fn rgb_to_hsv([r, g, b]: [u8; 3]) -> (f64, f64, f64) {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = f64::from(max - min);
+    let (red, green, blue) = (f64::from(r), f64::from(g), f64::from(b));
+    let hue = if max == min {
+        0_f64
+    } else if max == r {
+        60_f64 * ((green - blue) / delta).rem_euclid(6_f64)
+    } else if max == g {
+        60_f64 * ((blue - red) / delta + 2_f64)
+    } else {
+        60_f64 * ((red - green) / delta + 4_f64)
+    };
+    let saturation = if max == 0 {
+        0_f64
+    } else {
+        delta / f64::from(max)
+    };
+    (hue, saturation, f64::from(max) / 255_f64)
+}
+
+/// Convert a hue in degrees, and a saturation and value between 0 and 1, to an RGB color.
+fn hsv_to_rgb(hue: f64, saturation: f64, value: f64) -> Color {
+    let chroma = value * saturation;
+    let x = chroma * (1_f64 - ((hue / 60_f64) % 2_f64 - 1_f64).abs());
+    let (r, g, b) = match hue as u16 / 60 {
+        0 => (chroma, x, 0_f64),
+        1 => (x, chroma, 0_f64),
+        2 => (0_f64, chroma, x),
+        3 => (0_f64, x, chroma),
+        4 => (x, 0_f64, chroma),
+        _ => (chroma, 0_f64, x),
+    };
+    let channel = |c: f64| ((c + value - chroma) * 255_f64).round() as u8;
+    Color::Rgb(channel(r), channel(g), channel(b))
+}
+
+/// The RGB value of a color of the xterm 256 color palette.
+const fn ansi_rgb(index: u8) -> [u8; 3] {
+    const BASIC: [[u8; 3]; 16] = [
+        [0, 0, 0],
+        [205, 0, 0],
+        [0, 205, 0],
+        [205, 205, 0],
+        [0, 0, 238],
+        [205, 0, 205],
+        [0, 205, 205],
+        [229, 229, 229],
+        [127, 127, 127],
+        [255, 0, 0],
+        [0, 255, 0],
+        [255, 255, 0],
+        [92, 92, 255],
+        [255, 0, 255],
+        [0, 255, 255],
+        [255, 255, 255],
+    ];
+    const CUBE: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    match index {
+        0..=15 => BASIC[index as usize],
+        16..=231 => {
+            let i = index - 16;
+            [
+                CUBE[(i / 36) as usize],
+                CUBE[(i / 6 % 6) as usize],
+                CUBE[(i % 6) as usize],
+            ]
+        }
+        _ => {
+            let level = 8 + (index - 232) * 10;
+            [level, level, level]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(Color::Reset, [9, 9, 9]; "reset")]
+    #[test_case(Color::Red, [205, 0, 0]; "red")]
+    #[test_case(Color::White, [255, 255, 255]; "white")]
+    #[test_case(Color::Rgb(1, 2, 3), [1, 2, 3]; "rgb")]
+    #[test_case(Color::Indexed(16), [0, 0, 0]; "cube start")]
+    #[test_case(Color::Indexed(196), [255, 0, 0]; "cube red")]
+    #[test_case(Color::Indexed(231), [255, 255, 255]; "cube end")]
+    #[test_case(Color::Indexed(232), [8, 8, 8]; "grayscale start")]
+    #[test_case(Color::Indexed(255), [238, 238, 238]; "grayscale end")]
+    fn test_to_rgb(color: Color, expected: [u8; 3]) {
+        assert_eq!(expected, to_rgb(color, [9, 9, 9]));
+    }
+
+    #[test_case(Color::Green, Color::Red, 0.0, Color::Rgb(0, 205, 0); "start")]
+    #[test_case(Color::Green, Color::Red, 0.5, Color::Rgb(205, 205, 0); "middle")]
+    #[test_case(Color::Green, Color::Red, 1.0, Color::Rgb(205, 0, 0); "end")]
+    #[test_case(Color::Rgb(0, 0, 255), Color::Rgb(255, 0, 0), 0.5, Color::Rgb(255, 0, 255); "shortest hue")]
+    #[test_case(Color::Rgb(0, 0, 0), Color::Rgb(255, 255, 255), 0.5, Color::Rgb(128, 128, 128); "gray")]
+    fn test_gradient(from: Color, to: Color, fraction: f64, expected: Color) {
+        assert_eq!(expected, gradient(from, to, fraction));
+    }
+}
@@ -0,0 +1,145 @@
+use crate::frontend::glyphs::Glyphs;
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use trippy_core::{FlowId, Hop};
+
+/// The maximum number of rounds retained in a timeline.
+const MAX_TIMELINE_ROUNDS: usize = 60;
+
+/// The minimum number of replies before a round-trip time may be considered a spike.
+const MIN_SPIKE_REPLIES: usize = 3;
+
+/// The outcome of a round for a hop.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum RoundOutcome {
+    /// A reply was received.
+    Reply,
+    /// A reply was received with a round-trip time well above the average.
+    Spike,
+    /// No reply was received.
+    Loss,
+}
+
+impl RoundOutcome {
+    /// The marker for the outcome in the timeline strip.
+    pub const fn marker(self, glyphs: &Glyphs) -> char {
+        match self {
+            Self::Reply => glyphs.reply,
+            Self::Spike => glyphs.spike,
+            Self::Loss => glyphs.loss,
+        }
+    }
+}
+
+/// The outcome of the recent rounds of the selected hop and the time of day they were observed.
+///
+/// Only a single hop is tracked, and the timeline is restarted whenever a different hop is
+/// selected.
+#[derive(Debug, Clone, Default)]
+pub struct Timeline {
+    /// The trace, flow and ttl of the hop.
+    key: Option<(usize, FlowId, u8)>,
+    total_sent: usize,
+    total_recv: usize,
+    /// The average and standard deviation of the round-trip time before the latest update.
+    avg_ms: f64,
+    stddev_ms: f64,
+    rounds: VecDeque<(DateTime<Local>, RoundOutcome)>,
+}
+
+impl Timeline {
+    /// Update the timeline from the latest state of a hop.
+    ///
+    /// Every round completed since the previous update is recorded at `now`.  If several rounds
+    /// completed then any losses are recorded first, as the order is not known, such that the
+    /// last round has the latest round-trip time.
+    pub fn update(&mut self, key: (usize, FlowId, u8), hop: &Hop, now: DateTime<Local>) {
+        if self.key == Some(key) {
+            let sent = hop.total_sent().saturating_sub(self.total_sent);
+            let recv = hop.total_recv().saturating_sub(self.total_recv);
+            let is_spike = self.total_recv >= MIN_SPIKE_REPLIES
+                && hop
+                    .last_ms()
+                    .is_some_and(|last| is_spike(last, self.avg_ms, self.stddev_ms));
+            for outcome in outcomes(sent, recv, is_spike) {
+                self.rounds.push_back((now, outcome));
+            }
+            while self.rounds.len() > MAX_TIMELINE_ROUNDS {
+                self.rounds.pop_front();
+            }
+        } else {
+            self.key = Some(key);
+            self.rounds.clear();
+        }
+        self.total_sent = hop.total_sent();
+        self.total_recv = hop.total_recv();
+        self.avg_ms = hop.avg_ms();
+        self.stddev_ms = hop.stddev_ms();
+    }
+
+    /// The recorded rounds, oldest first.
+    pub fn rounds(&self) -> impl Iterator<Item = &(DateTime<Local>, RoundOutcome)> {
+        self.rounds.iter()
+    }
+
+    /// The time of day of the most recent round with the given outcome, if any.
+    pub fn last(&self, outcome: RoundOutcome) -> Option<DateTime<Local>> {
+        self.rounds
+            .iter()
+            .rev()
+            .find(|(_, o)| *o == outcome)
+            .map(|(time, _)| *time)
+    }
+}
+
+/// The outcomes of `sent` rounds of which `recv` received a reply.
+///
+/// Losses are ordered first such that the last round is the round of the latest reply, which is
+/// a spike if `is_spike` is set.
+fn outcomes(sent: usize, recv: usize, is_spike: bool) -> impl Iterator<Item = RoundOutcome> {
+    let lost = sent - recv.min(sent);
+    (0..sent).map(move |i| {
+        if i < lost {
+            RoundOutcome::Loss
+        } else if i == sent - 1 && is_spike {
+            RoundOutcome::Spike
+        } else {
+            RoundOutcome::Reply
+        }
+    })
+}
+
+/// Whether a round-trip time is a spike.
+///
+/// A round-trip time is a spike if it exceeds the average by more than three standard deviations
+/// and by more than half the average.
+fn is_spike(last_ms: f64, avg_ms: f64, stddev_ms: f64) -> bool {
+    last_ms > avg_ms + (3_f64 * stddev_ms).max(avg_ms / 2_f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+    use RoundOutcome::{Loss, Reply, Spike};
+
+    #[test_case(0, 0, false, &[]; "no rounds")]
+    #[test_case(1, 1, false, &[Reply]; "reply")]
+    #[test_case(1, 0, false, &[Loss]; "loss")]
+    #[test_case(1, 1, true, &[Spike]; "spike")]
+    #[test_case(1, 0, true, &[Loss]; "loss not spike")]
+    #[test_case(3, 2, true, &[Loss, Reply, Spike]; "several rounds")]
+    #[test_case(1, 2, false, &[Reply]; "late replies")]
+    fn test_outcomes(sent: usize, recv: usize, is_spike: bool, expected: &[RoundOutcome]) {
+        assert_eq!(expected, outcomes(sent, recv, is_spike).collect::<Vec<_>>());
+    }
+
+    #[test_case(10.0, 10.0, 1.0, false; "average")]
+    #[test_case(14.0, 10.0, 1.0, false; "within half average")]
+    #[test_case(16.0, 10.0, 1.0, true; "above half average")]
+    #[test_case(16.0, 10.0, 2.0, false; "within three stddev")]
+    #[test_case(17.0, 10.0, 2.0, true; "above three stddev")]
+    fn test_is_spike(last_ms: f64, avg_ms: f64, stddev_ms: f64, expected: bool) {
+        assert_eq!(expected, is_spike(last_ms, avg_ms, stddev_ms));
+    }
+}
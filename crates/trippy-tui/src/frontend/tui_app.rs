@@ -1,13 +1,31 @@
 use crate::app::TraceInfo;
+use crate::config::{
+    AverageMode, FlowSortMode, StdDevMode, StdDevScope, TimeExceededRttMode, TrailingHopMode,
+};
+use crate::frontend::ansi;
+use crate::frontend::columns::ColumnType;
 use crate::frontend::config::TuiConfig;
+#[cfg(feature = "png")]
+use crate::frontend::image;
 use crate::frontend::render::settings::{settings_tabs, SETTINGS_TAB_COLUMNS};
+use crate::frontend::timeline::Timeline;
 use crate::geoip::GeoIpLookup;
+use crate::notes::Notes;
 use itertools::Itertools;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
 use ratatui::widgets::TableState;
-use std::time::SystemTime;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::net::IpAddr;
+use std::ops::Range;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
 use trippy_core::FlowId;
 use trippy_core::Hop;
+use trippy_core::IcmpPacketType;
 use trippy_core::State;
+use trippy_core::TimeToLive;
 use trippy_dns::{DnsResolver, ResolveMethod};
 
 pub struct TuiApp {
@@ -16,6 +34,10 @@ pub struct TuiApp {
     pub tui_config: TuiConfig,
     /// The state of the hop table.
     pub table_state: TableState,
+    /// The index of the first shown hop displayed in the hops table.
+    ///
+    /// Only used if the number of hops to display is capped.
+    pub displayed_hops_offset: usize,
     /// The state of the settings table.
     pub setting_table_state: TableState,
     /// The selected trace.
@@ -32,8 +54,19 @@ pub struct TuiApp {
     pub selected_flow: FlowId,
     /// Ordered flow ids with counts.
     pub flow_counts: Vec<(FlowId, usize)>,
+    /// The index of the selected (shown) column of the hop table.
+    pub selected_column: Option<usize>,
+    /// The column, which need not be shown, and direction by which the hops table is sorted, if
+    /// any.
+    pub sort_by: Option<(ColumnType, SortDir)>,
+    /// The area of the hop table from the last render.
+    pub table_area: Rect,
     pub resolver: DnsResolver,
     pub geoip_lookup: GeoIpLookup,
+    /// The notes attached to hop addresses.
+    pub notes: Notes,
+    /// The note being edited, if any.
+    pub note_editor: Option<NoteEditor>,
     pub show_help: bool,
     pub show_settings: bool,
     pub show_hop_details: bool,
@@ -44,6 +77,26 @@ pub struct TuiApp {
     pub show_map: bool,
     pub frozen_start: Option<SystemTime>,
     pub zoom_factor: usize,
+    /// Whether the first hop warning has been dismissed.
+    pub first_hop_warning_dismissed: bool,
+    /// How to render the trailing hop when it is toggled to be shown.
+    pub shown_trailing_hop_mode: TrailingHopMode,
+    /// The format in which the next rendered frame should be saved, if any.
+    pub save_image_requested: Option<ImageFormat>,
+    /// The outcome of the last attempt to save an image and when it was made.
+    pub saved_image: Option<(Result<PathBuf, String>, SystemTime)>,
+    /// The timeline of the recent rounds of the selected hop.
+    pub timeline: Timeline,
+}
+
+/// The state of a note being edited.
+pub struct NoteEditor {
+    /// The address the note is attached to.
+    pub addr: IpAddr,
+    /// The text of the note.
+    pub text: String,
+    /// The error from the last attempt to save the note, if any.
+    pub error: Option<String>,
 }
 
 impl TuiApp {
@@ -51,21 +104,32 @@ impl TuiApp {
         tui_config: TuiConfig,
         resolver: DnsResolver,
         geoip_lookup: GeoIpLookup,
+        notes: Notes,
         trace_info: Vec<TraceInfo>,
     ) -> Self {
+        let shown_trailing_hop_mode = match tui_config.trailing_hop_mode {
+            TrailingHopMode::Hide => TrailingHopMode::Waiting,
+            mode => mode,
+        };
         Self {
             selected_tracer_data: State::default(),
             trace_info,
             tui_config,
             table_state: TableState::default(),
+            displayed_hops_offset: 0,
             setting_table_state: TableState::default(),
             trace_selected: 0,
             settings_tab_selected: 0,
             selected_hop_address: 0,
             selected_flow: State::default_flow_id(),
             flow_counts: vec![],
+            selected_column: None,
+            sort_by: None,
+            table_area: Rect::default(),
             resolver,
             geoip_lookup,
+            notes,
+            note_editor: None,
             show_help: false,
             show_settings: false,
             show_hop_details: false,
@@ -75,6 +139,63 @@ impl TuiApp {
             show_map: false,
             frozen_start: None,
             zoom_factor: 1,
+            first_hop_warning_dismissed: false,
+            shown_trailing_hop_mode,
+            save_image_requested: None,
+            saved_image: None,
+            timeline: Timeline::default(),
+        }
+    }
+
+    /// Edit the note of the selected hop address.
+    ///
+    /// This is the address shown in the hop details, or the first address of the hop otherwise.
+    pub fn edit_note(&mut self) {
+        let index = if self.show_hop_details {
+            self.selected_hop_address
+        } else {
+            0
+        };
+        if let Some(addr) = self
+            .selected_hop()
+            .and_then(|hop| hop.addrs().nth(index))
+            .copied()
+        {
+            let text = self.notes.get(addr).unwrap_or_default().to_string();
+            self.note_editor = Some(NoteEditor {
+                addr,
+                text,
+                error: None,
+            });
+        }
+    }
+
+    /// Save the note being edited.
+    ///
+    /// The editor remains open if the note could not be persisted.
+    pub fn save_note(&mut self) {
+        if let Some(editor) = &mut self.note_editor {
+            match self.notes.set(editor.addr, &editor.text) {
+                Ok(()) => self.note_editor = None,
+                Err(err) => editor.error = Some(format!("{err:#}")),
+            }
+        }
+    }
+
+    /// Discard the note being edited.
+    pub fn cancel_note(&mut self) {
+        self.note_editor = None;
+    }
+
+    pub fn insert_note_char(&mut self, c: char) {
+        if let Some(editor) = &mut self.note_editor {
+            editor.text.push(c);
+        }
+    }
+
+    pub fn delete_note_char(&mut self) {
+        if let Some(editor) = &mut self.note_editor {
+            editor.text.pop();
         }
     }
 
@@ -86,6 +207,19 @@ impl TuiApp {
         self.selected_tracer_data = self.trace_info[self.trace_selected].data.snapshot();
     }
 
+    /// Update the timeline of the selected hop.
+    ///
+    /// The timeline is discarded if no hop is selected.
+    pub fn update_timeline(&mut self) {
+        match self.selected_hop().cloned() {
+            Some(hop) => {
+                let key = (self.trace_selected, self.selected_flow, hop.ttl());
+                self.timeline.update(key, &hop, chrono::Local::now());
+            }
+            None => self.timeline = Timeline::default(),
+        }
+    }
+
     pub fn clear_trace_data(&self) {
         self.trace_info[self.trace_selected].data.clear();
     }
@@ -93,38 +227,232 @@ impl TuiApp {
     pub fn selected_hop_or_target(&self) -> &Hop {
         self.table_state.selected().map_or_else(
             || self.tracer_data().target_hop(self.selected_flow),
-            |s| &self.tracer_data().hops_for_flow(self.selected_flow)[s],
+            |s| &self.shown_hops()[s],
         )
     }
 
     pub fn selected_hop(&self) -> Option<&Hop> {
-        self.table_state
-            .selected()
-            .map(|s| &self.tracer_data().hops_for_flow(self.selected_flow)[s])
+        self.table_state.selected().map(|s| &self.shown_hops()[s])
     }
 
     pub fn tracer_config(&self) -> &TraceInfo {
         &self.trace_info[self.trace_selected]
     }
 
+    /// The number of rounds after which tracing stopped, if it has stopped.
+    pub fn stopped_rounds(&self) -> Option<usize> {
+        self.tracer_config()
+            .data
+            .max_rounds()
+            .map(|max_rounds| max_rounds.0.get())
+            .filter(|&rounds| self.tracer_data().round_count(State::default_flow_id()) >= rounds)
+    }
+
+    /// Has the trace been stable for the configured number of rounds?
+    ///
+    /// The trace is stable if, over the most recent rounds, the target has
+    /// responded to every probe and the standard deviation of the round-trip
+    /// time of every responding hop is no more than the configured threshold.
+    pub fn is_stable(&self) -> bool {
+        let Some(rounds) = self.tui_config.auto_quit_stable_rounds else {
+            return false;
+        };
+        let flow_id = State::default_flow_id();
+        let threshold_ms = self.tui_config.auto_quit_stable_threshold.as_secs_f64() * 1000_f64;
+        let target = self.tracer_data().target_hop(flow_id).windowed(rounds);
+        target.total_sent() >= rounds
+            && target.total_recv() == target.total_sent()
+            && self
+                .tracer_data()
+                .hops_for_flow(flow_id)
+                .iter()
+                .map(|hop| hop.windowed(rounds))
+                .filter(|hop| hop.total_recv() > 0)
+                .all(|hop| hop.stddev_ms() <= threshold_ms)
+    }
+
     pub fn clamp_selected_hop(&mut self) {
-        let hop_count = self.tracer_data().hops_for_flow(self.selected_flow).len();
+        let hop_count = self.shown_hops().len();
         if let Some(selected) = self.table_state.selected() {
-            if selected > hop_count - 1 {
-                self.table_state.select(Some(hop_count - 1));
+            if selected > hop_count.saturating_sub(1) {
+                self.table_state.select(Some(hop_count.saturating_sub(1)));
             }
         }
     }
 
+    /// The hops of the selected flow which are shown in the hops table.
+    ///
+    /// The trailing hop is not included if it is hidden, nor are any unresponsive hops beyond the
+    /// maximum number of consecutive unresponsive hops to show.
+    pub fn shown_hops(&self) -> &[Hop] {
+        let hops = self.unhidden_hops();
+        &hops[..hops.len() - self.hidden_unresponsive_hops()]
+    }
+
+    /// The number of trailing unresponsive hops which are not shown in the hops table.
+    pub fn hidden_unresponsive_hops(&self) -> usize {
+        let Some(max) = self.tui_config.max_unresponsive_hops.map(usize::from) else {
+            return 0;
+        };
+        self.unhidden_hops()
+            .iter()
+            .rev()
+            .take_while(|hop| hop.total_recv() == 0)
+            .count()
+            .saturating_sub(max)
+    }
+
+    /// Was probing beyond the maximum number of consecutive unresponsive hops curtailed in the
+    /// last round?
+    pub const fn is_probing_curtailed(&self) -> bool {
+        self.tracer_data().last_round_curtailed()
+    }
+
+    /// The hops of the selected flow, excluding the trailing hop if it is hidden.
+    fn unhidden_hops(&self) -> &[Hop] {
+        let hops = self.tracer_data().hops_for_flow(self.selected_flow);
+        match hops.split_last() {
+            Some((last, rest))
+                if self.tui_config.trailing_hop_mode == TrailingHopMode::Hide
+                    && last.total_recv() == 0 =>
+            {
+                rest
+            }
+            _ => hops,
+        }
+    }
+
+    /// The order in which the shown hops are displayed in the hops table, as indices of the shown
+    /// hops.
+    ///
+    /// The hops are in ttl order unless the table is sorted, in which case hops without a value for
+    /// the sort column are last, in either direction, and ties are in ttl order.
+    pub fn hop_order(&self) -> Vec<usize> {
+        let hops = self.shown_hops();
+        let mut order = (0..hops.len()).collect::<Vec<_>>();
+        if let Some((column, dir)) = self.sort_by {
+            let keys = hops
+                .iter()
+                .map(|hop| self.sort_key(column, &self.windowed_hop(hop)))
+                .collect::<Vec<_>>();
+            order.sort_by(|&i, &j| match (keys[i], keys[j]) {
+                (Some(a), Some(b)) => match dir {
+                    SortDir::Ascending => a.total_cmp(&b),
+                    SortDir::Descending => b.total_cmp(&a),
+                },
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+        }
+        order
+    }
+
+    /// The value of a hop for a column by which the hops table is sorted.
+    ///
+    /// Returns `None` if the hop has no value for the column or if the column is not sortable.
+    fn sort_key(&self, column: ColumnType, hop: &Hop) -> Option<f64> {
+        let responded = hop.total_recv() > 0;
+        match column {
+            ColumnType::Ttl => Some(f64::from(hop.ttl())),
+            ColumnType::LossPct => Some(hop.loss_pct()),
+            ColumnType::Sent => Some(hop.total_sent() as f64),
+            ColumnType::Received => Some(hop.total_recv() as f64),
+            ColumnType::Failed => Some(hop.total_failed() as f64),
+            ColumnType::Last => hop.last_ms(),
+            ColumnType::Average => Some(self.avg_ms(hop)).filter(|_| responded),
+            ColumnType::Best => hop.best_ms(),
+            ColumnType::Worst => hop.worst_ms(),
+            ColumnType::StdDev => self.stddev_ms(hop),
+            ColumnType::Jitter => hop.jitter_ms(),
+            ColumnType::Javg => Some(hop.javg_ms()).filter(|_| responded),
+            ColumnType::Jmax => hop.jmax_ms(),
+            ColumnType::Jinta => Some(hop.jinta()).filter(|_| responded),
+            ColumnType::LastSrcPort => Some(f64::from(hop.last_src_port())).filter(|_| responded),
+            ColumnType::LastDestPort => Some(f64::from(hop.last_dest_port())).filter(|_| responded),
+            ColumnType::LastSeq => Some(f64::from(hop.last_sequence())).filter(|_| responded),
+            ColumnType::LastIcmpPacketCode => match hop.last_icmp_packet_type() {
+                Some(
+                    IcmpPacketType::TimeExceeded(code)
+                    | IcmpPacketType::EchoReply(code)
+                    | IcmpPacketType::Unreachable(code),
+                ) => Some(f64::from(code.0)),
+                _ => None,
+            },
+            ColumnType::RecentLoss => {
+                Some(hop.recent_loss_pct(f64::from(self.tui_config.loss_ewma_weight) / 100_f64))
+            }
+            ColumnType::Host
+            | ColumnType::Status
+            | ColumnType::LastIcmpPacketType
+            | ColumnType::LastNatStatus
+            | ColumnType::Provider
+            | ColumnType::Asymmetry
+            | ColumnType::Protocol
+            | ColumnType::RateLimit
+            | ColumnType::Tunnel
+            | ColumnType::Mpls
+            | ColumnType::Asn
+            | ColumnType::GeoLoc => None,
+        }
+    }
+
+    /// The position of the selected hop in a display order of the shown hops, if a hop is
+    /// selected.
+    fn selected_hop_position(&self, order: &[usize]) -> Option<usize> {
+        let selected = self.table_state.selected()?;
+        order.iter().position(|&index| index == selected)
+    }
+
+    /// The range of positions of the shown hops, in display order, which are displayed in the hops
+    /// table.
+    ///
+    /// If the number of hops to display is capped, the displayed range is scrolled such that the
+    /// selected hop is always displayed.
+    pub fn displayed_hops(&mut self) -> Range<usize> {
+        let hop_count = self.shown_hops().len();
+        let Some(max) = self.tui_config.max_display_hops.map(usize::from) else {
+            return 0..hop_count;
+        };
+        if let Some(selected) = self.selected_hop_position(&self.hop_order()) {
+            if selected < self.displayed_hops_offset {
+                self.displayed_hops_offset = selected;
+            } else if selected >= self.displayed_hops_offset + max {
+                self.displayed_hops_offset = selected + 1 - max;
+            }
+        }
+        self.displayed_hops_offset = self
+            .displayed_hops_offset
+            .min(hop_count.saturating_sub(max));
+        self.displayed_hops_offset..hop_count.min(self.displayed_hops_offset + max)
+    }
+
+    /// Is a given hop the trailing hop?
+    ///
+    /// The trailing hop is the last hop, which is probed beyond the highest
+    /// responding hop whilst the target has not been found, if it has never
+    /// responded.
+    pub fn is_trailing_hop(&self, hop: &Hop) -> bool {
+        self.tracer_data()
+            .hops_for_flow(self.selected_flow)
+            .last()
+            .is_some_and(|last| last.ttl() == hop.ttl() && last.total_recv() == 0)
+    }
+
+    /// Update the flows, sorted as configured.
+    ///
+    /// Flows are sorted in descending order of the configured metric, such that the most
+    /// frequently seen, or the worst, flow is first.  Ties are ordered by the number of rounds in
+    /// which the flow was seen and then by flow id.
     pub fn update_order_flow_counts(&mut self) {
         pub fn order_flows(
-            &(flow_id1, count1): &(FlowId, usize),
-            &(flow_id2, count2): &(FlowId, usize),
+            &(flow_id1, count1, metric1): &(FlowId, usize, f64),
+            &(flow_id2, count2, metric2): &(FlowId, usize, f64),
         ) -> std::cmp::Ordering {
-            match count1.cmp(&count2) {
-                std::cmp::Ordering::Equal => flow_id2.cmp(&flow_id1),
-                ord => ord,
-            }
+            metric1
+                .total_cmp(&metric2)
+                .then(count1.cmp(&count2))
+                .then(flow_id2.cmp(&flow_id1))
         }
         self.flow_counts = self
             .tracer_data()
@@ -132,53 +460,75 @@ impl TuiApp {
             .iter()
             .map(|&(_, flow_id)| {
                 let count = self.tracer_data().round_count(flow_id);
-                (flow_id, count)
+                (flow_id, count, self.flow_metric(flow_id, count))
             })
             .sorted_by(order_flows)
             .rev()
             .take(self.selected_tracer_data.max_flows())
+            .map(|(flow_id, count, _)| (flow_id, count))
             .collect::<Vec<_>>();
     }
 
+    /// The metric by which a flow is sorted.
+    fn flow_metric(&self, flow_id: FlowId, count: usize) -> f64 {
+        let last_hop = || self.tracer_data().hops_for_flow(flow_id).last();
+        match self.tui_config.flow_sort_mode {
+            FlowSortMode::Rounds => count as f64,
+            FlowSortMode::Rtt => last_hop().map_or(0_f64, |hop| self.avg_ms(hop)),
+            FlowSortMode::Loss => last_hop().map_or(0_f64, Hop::loss_pct),
+        }
+    }
+
+    /// Select the hop below the selected hop in the hops table, or the first hop if none is
+    /// selected.
     pub fn next_hop(&mut self) {
-        let hop_count = self.tracer_data().hops_for_flow(self.selected_flow).len();
-        if hop_count == 0 {
+        let order = self.hop_order();
+        if order.is_empty() {
             return;
         }
-        let max_index = 0.max(hop_count.saturating_sub(1));
-        let i = match self.table_state.selected() {
-            Some(i) => {
-                if i < max_index {
-                    i + 1
-                } else {
-                    i
-                }
-            }
+        let max_position = order.len() - 1;
+        let position = match self.selected_hop_position(&order) {
+            Some(position) => (position + 1).min(max_position),
             None => 0,
         };
-        self.table_state.select(Some(i));
+        self.table_state.select(Some(order[position]));
         self.selected_hop_address = 0;
     }
 
+    /// Select the hop above the selected hop in the hops table, or the last hop if none is
+    /// selected.
     pub fn previous_hop(&mut self) {
-        let hop_count = self.tracer_data().hops_for_flow(self.selected_flow).len();
-        if hop_count == 0 {
+        let order = self.hop_order();
+        if order.is_empty() {
             return;
         }
-        let i = match self.table_state.selected() {
-            Some(i) => {
-                if i > 0 {
-                    i - 1
-                } else {
-                    i
-                }
-            }
-            None => 0.max(hop_count.saturating_sub(1)),
+        let position = match self.selected_hop_position(&order) {
+            Some(position) => position.saturating_sub(1),
+            None => order.len() - 1,
         };
-        self.table_state.select(Some(i));
+        self.table_state.select(Some(order[position]));
         self.selected_hop_address = 0;
     }
 
+    /// Cycle the sort of the hops table by the selected column between ascending, descending and
+    /// unsorted.
+    ///
+    /// If no column is selected the hops table is unsorted.
+    pub fn toggle_hop_sort(&mut self) {
+        let column = self
+            .selected_column
+            .and_then(|index| self.tui_config.tui_columns.columns().nth(index))
+            .map(|column| column.typ);
+        self.sort_by = match (column, self.sort_by) {
+            (Some(column), Some((sorted, SortDir::Ascending))) if column == sorted => {
+                Some((column, SortDir::Descending))
+            }
+            (Some(column), Some((sorted, SortDir::Descending))) if column == sorted => None,
+            (Some(column), _) => Some((column, SortDir::Ascending)),
+            (None, _) => None,
+        };
+    }
+
     pub fn next_trace(&mut self) {
         if self.trace_info.len() > 1 && self.trace_selected < self.trace_info.len() - 1 {
             self.trace_selected += 1;
@@ -298,6 +648,15 @@ impl TuiApp {
         }
     }
 
+    /// Toggle the group of the selected column on or off.
+    pub fn toggle_column_group(&mut self) {
+        if self.settings_tab_selected == SETTINGS_TAB_COLUMNS {
+            if let Some(selected) = self.setting_table_state.selected() {
+                self.tui_config.tui_columns.toggle_group(selected);
+            }
+        }
+    }
+
     pub fn move_column_down(&mut self) {
         if self.settings_tab_selected == SETTINGS_TAB_COLUMNS {
             let count = self.tui_config.tui_columns.all_columns_count();
@@ -321,9 +680,47 @@ impl TuiApp {
         }
     }
 
+    pub fn next_column(&mut self) {
+        let count = self.tui_config.tui_columns.columns_count();
+        self.selected_column = match self.selected_column {
+            Some(index) if index + 1 < count => Some(index + 1),
+            Some(_) => Some(count.saturating_sub(1)),
+            None if count > 0 => Some(0),
+            None => None,
+        };
+    }
+
+    pub fn previous_column(&mut self) {
+        let count = self.tui_config.tui_columns.columns_count();
+        self.selected_column = match self.selected_column {
+            Some(index) => Some(index.min(count).saturating_sub(1)),
+            None if count > 0 => Some(count - 1),
+            None => None,
+        };
+    }
+
+    pub fn widen_column(&mut self) {
+        if let Some(index) = self.selected_column {
+            self.tui_config.tui_columns.widen(index, self.table_area);
+        }
+    }
+
+    pub fn narrow_column(&mut self) {
+        if let Some(index) = self.selected_column {
+            self.tui_config.tui_columns.narrow(index, self.table_area);
+        }
+    }
+
+    pub fn reset_column_width(&mut self) {
+        if let Some(index) = self.selected_column {
+            self.tui_config.tui_columns.reset_width(index);
+        }
+    }
+
     pub fn clear(&mut self) {
         self.table_state.select(None);
         self.selected_hop_address = 0;
+        self.selected_column = None;
     }
 
     pub fn toggle_help(&mut self) {
@@ -351,6 +748,28 @@ impl TuiApp {
         self.show_hop_details = !self.show_hop_details;
     }
 
+    pub fn dismiss_warning(&mut self) {
+        self.first_hop_warning_dismissed = true;
+    }
+
+    /// Should the first hop warning be shown?
+    ///
+    /// The warning is shown if the first hop (typically the local gateway) has not replied to any
+    /// of the probes from the last `first_hop_warning_rounds` rounds, unless it is disabled or has
+    /// been dismissed.
+    pub fn show_first_hop_warning(&self) -> bool {
+        self.tracer_data()
+            .hops_for_flow(self.selected_flow)
+            .first()
+            .is_some_and(|hop| {
+                first_hop_warning(
+                    hop.samples(),
+                    self.tui_config.first_hop_warning_rounds,
+                    self.first_hop_warning_dismissed,
+                )
+            })
+    }
+
     pub fn toggle_freeze(&mut self) {
         self.frozen_start = match self.frozen_start {
             None => Some(SystemTime::now()),
@@ -396,6 +815,127 @@ impl TuiApp {
         }
     }
 
+    /// Cycle the order in which flows are sorted.
+    pub fn toggle_flow_sort(&mut self) {
+        self.tui_config.flow_sort_mode = match self.tui_config.flow_sort_mode {
+            FlowSortMode::Rounds => FlowSortMode::Rtt,
+            FlowSortMode::Rtt => FlowSortMode::Loss,
+            FlowSortMode::Loss => FlowSortMode::Rounds,
+        };
+        self.update_order_flow_counts();
+    }
+
+    pub fn toggle_as_colors(&mut self) {
+        self.tui_config.as_colors = !self.tui_config.as_colors;
+    }
+
+    pub fn toggle_summary_footer(&mut self) {
+        self.tui_config.summary_footer = !self.tui_config.summary_footer;
+    }
+
+    pub fn toggle_rtt_deltas(&mut self) {
+        let columns = &mut self.tui_config.tui_columns;
+        columns.set_rtt_deltas(!columns.rtt_deltas());
+    }
+
+    /// Toggle between hiding the unresponsive trailing hop and showing it.
+    ///
+    /// When shown, the trailing hop is rendered as configured, or as `Waiting` if it was
+    /// configured to be hidden.
+    pub fn toggle_trailing_hop(&mut self) {
+        self.tui_config.trailing_hop_mode = match self.tui_config.trailing_hop_mode {
+            TrailingHopMode::Hide => self.shown_trailing_hop_mode,
+            _ => TrailingHopMode::Hide,
+        };
+        self.clamp_selected_hop();
+    }
+
+    /// Toggle pausing probing of the selected hop.
+    ///
+    /// A paused hop is not probed, and so retains its statistics, until it is resumed.
+    pub fn toggle_hop_paused(&self) {
+        if let Some(ttl) = self.selected_hop().map(|hop| TimeToLive(hop.ttl())) {
+            let tracer = &self.tracer_config().data;
+            if tracer.is_paused_ttl(ttl) {
+                tracer.resume_ttl(ttl);
+            } else {
+                tracer.pause_ttl(ttl);
+            }
+        }
+    }
+
+    /// Toggle pausing probing of all hops of the selected trace.
+    ///
+    /// Unlike freezing the display, no probes are sent, and no rounds complete, while paused.
+    pub fn toggle_trace_paused(&self) {
+        let tracer = &self.tracer_config().data;
+        if tracer.is_paused() {
+            tracer.resume();
+        } else {
+            tracer.pause();
+        }
+    }
+
+    /// Whether probing of a hop is paused.
+    pub fn is_hop_paused(&self, hop: &Hop) -> bool {
+        self.tracer_config()
+            .data
+            .is_paused_ttl(TimeToLive(hop.ttl()))
+    }
+
+    /// The time at which the probe for a hop was sent, if it is awaiting a reply in the round in
+    /// progress.
+    pub fn awaited_since(&self, hop: &Hop) -> Option<SystemTime> {
+        self.tracer_config()
+            .data
+            .awaited_since(TimeToLive(hop.ttl()))
+    }
+
+    /// Request that the next rendered frame be saved in the given format.
+    pub fn request_save_image(&mut self, format: ImageFormat) {
+        self.save_image_requested = Some(format);
+    }
+
+    /// Save a rendered frame in the requested format in the current directory.
+    ///
+    /// The file is named after the current local time, such as `trippy-20240101-120000.png` for a
+    /// PNG image or `trippy-20240101-120000.ans` for text with ANSI escape codes.
+    ///
+    /// Saving a PNG image fails unless the `png` feature is enabled.
+    pub fn save_image(&mut self, buffer: &Buffer) {
+        let Some(format) = self.save_image_requested.take() else {
+            return;
+        };
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let result = match format {
+            #[cfg(feature = "png")]
+            ImageFormat::Png => {
+                let path = PathBuf::from(format!("trippy-{timestamp}.png"));
+                image::save_png(buffer, &path).map(|()| path)
+            }
+            #[cfg(not(feature = "png"))]
+            ImageFormat::Png => Err(anyhow::anyhow!(
+                "saving PNG images requires Trippy to be built with the `png` feature"
+            )),
+            ImageFormat::Ansi => {
+                let path = PathBuf::from(format!("trippy-{timestamp}.ans"));
+                ansi::save_ansi(buffer, &path).map(|()| path)
+            }
+        };
+        self.saved_image = Some((result.map_err(|err| err.to_string()), SystemTime::now()));
+    }
+
+    /// The outcome of the last attempt to save an image, if it was made recently.
+    pub fn recent_saved_image(&self) -> Option<&Result<PathBuf, String>> {
+        self.saved_image
+            .as_ref()
+            .filter(|(_, time)| {
+                time.elapsed()
+                    .is_ok_and(|elapsed| elapsed < SAVED_IMAGE_STATUS_DURATION)
+            })
+            .map(|(result, _)| result)
+    }
+
     pub fn expand_hosts(&mut self) {
         self.tui_config.max_addrs = match self.tui_config.max_addrs {
             None => Some(1),
@@ -431,6 +971,118 @@ impl TuiApp {
         self.tui_config.max_addrs = Some(1);
     }
 
+    /// Expand the stats window to the next larger preset, or to all rounds.
+    pub fn expand_stats_window(&mut self) {
+        if let Some(rounds) = self.tui_config.stats_window {
+            self.tui_config.stats_window = STATS_WINDOWS.into_iter().find(|&w| w > rounds);
+        }
+    }
+
+    /// Contract the stats window to the next smaller preset.
+    pub fn contract_stats_window(&mut self) {
+        let rounds = self.tui_config.stats_window.unwrap_or(usize::MAX);
+        self.tui_config.stats_window = STATS_WINDOWS
+            .into_iter()
+            .rev()
+            .find(|&w| w < rounds)
+            .or(self.tui_config.stats_window);
+    }
+
+    /// Is a path length change which occurred in a given round still flagged?
+    pub fn is_path_change_flagged(&self, changed_round: Option<usize>) -> bool {
+        let round = self.tracer_data().round(self.selected_flow);
+        changed_round.zip(round).is_some_and(|(changed, round)| {
+            round.saturating_sub(changed) < self.tui_config.path_change_rounds
+        })
+    }
+
+    /// The hop with statistics computed over the stats window, if any.
+    pub fn windowed_hop<'a>(&self, hop: &'a Hop) -> Cow<'a, Hop> {
+        self.tui_config
+            .stats_window
+            .map_or(Cow::Borrowed(hop), |rounds| {
+                Cow::Owned(hop.windowed(rounds))
+            })
+    }
+
+    /// How to render the round-trip times of a hop.
+    ///
+    /// A hop is classified by the type of the most recent response received from it, and so the
+    /// configured mode applies only to hops whose most recent response was a `TimeExceeded`.
+    pub const fn time_exceeded_rtt_mode(&self, hop: &Hop) -> TimeExceededRttMode {
+        match hop.last_icmp_packet_type() {
+            Some(IcmpPacketType::TimeExceeded(_)) => self.tui_config.time_exceeded_rtt_mode,
+            _ => TimeExceededRttMode::Include,
+        }
+    }
+
+    /// The average round-trip time of a hop, computed as configured.
+    pub fn avg_ms(&self, hop: &Hop) -> f64 {
+        match self.tui_config.average_mode {
+            AverageMode::Mean => hop.avg_ms(),
+            AverageMode::Ewma => hop.ewma_ms(f64::from(self.tui_config.ewma_weight) / 100_f64),
+        }
+    }
+
+    /// The standard deviation of the round-trip times of a hop, computed as configured.
+    ///
+    /// The hop is expected to be windowed, and so if the scope is `All` the hop over all rounds
+    /// is used instead.  Returns `None` if the hop has too few replies.
+    pub fn stddev_ms(&self, hop: &Hop) -> Option<f64> {
+        let hop = match self.tui_config.stddev_scope {
+            StdDevScope::Window => hop,
+            StdDevScope::All => self
+                .tracer_data()
+                .hops_for_flow(self.selected_flow)
+                .iter()
+                .find(|h| h.ttl() == hop.ttl())
+                .unwrap_or(hop),
+        };
+        match self.tui_config.stddev_mode {
+            StdDevMode::Sample if hop.total_recv() > 1 => Some(hop.stddev_ms()),
+            StdDevMode::Population if hop.total_recv() > 0 => Some(hop.population_stddev_ms()),
+            _ => None,
+        }
+    }
+
+    /// Select the hop with the highest packet loss.
+    pub fn select_worst_loss_hop(&mut self) {
+        self.select_worst_hop(|_, hop| Some(hop.loss_pct()));
+    }
+
+    /// Select the hop with the highest average round-trip time.
+    ///
+    /// Hops whose round-trip times are excluded are not considered.
+    pub fn select_worst_latency_hop(&mut self) {
+        self.select_worst_hop(|app, hop| match app.time_exceeded_rtt_mode(hop) {
+            TimeExceededRttMode::Exclude => None,
+            TimeExceededRttMode::Include | TimeExceededRttMode::Distinguish => {
+                Some(app.avg_ms(hop))
+            }
+        });
+    }
+
+    /// Select the hop with the highest value of a given statistic.
+    ///
+    /// Where several hops share the highest value, the first is selected.  Hops without a value
+    /// are not considered.
+    fn select_worst_hop(&mut self, stat: impl Fn(&Self, &Hop) -> Option<f64>) {
+        let worst = self
+            .shown_hops()
+            .iter()
+            .map(|hop| stat(self, &self.windowed_hop(hop)))
+            .enumerate()
+            .filter_map(|(i, value)| Some((i, value?)))
+            .fold(None, |worst, (i, value)| match worst {
+                Some((_, worst_value)) if worst_value >= value => worst,
+                _ => Some((i, value)),
+            });
+        if let Some((i, _)) = worst {
+            self.table_state.select(Some(i));
+            self.selected_hop_address = 0;
+        }
+    }
+
     /// The maximum number of hosts per hop for the currently selected trace.
     pub fn max_hosts(&self) -> Option<u8> {
         self.selected_tracer_data
@@ -443,3 +1095,67 @@ impl TuiApp {
 }
 
 const MAX_ZOOM_FACTOR: usize = 16;
+
+/// The preset windows (in rounds) for the hop statistics.
+const STATS_WINDOWS: [usize; 6] = [5, 10, 20, 50, 100, 200];
+
+/// The direction in which the hops table is sorted.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SortDir {
+    /// The lowest value first.
+    Ascending,
+    /// The highest value first.
+    Descending,
+}
+
+/// The format in which a rendered frame is saved.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ImageFormat {
+    /// A PNG image.
+    Png,
+    /// Text with ANSI escape codes.
+    Ansi,
+}
+
+/// How long the outcome of saving an image is shown for.
+const SAVED_IMAGE_STATUS_DURATION: Duration = Duration::from_secs(5);
+
+/// Should the first hop warning be shown for the given first hop `samples`?
+///
+/// The samples are ordered from the most recent round, a zero duration indicates no reply was
+/// received in that round.
+///
+/// The warning is never shown if fewer than `rounds` samples are retained, and so `rounds` must
+/// not exceed the maximum number of samples, which is validated when the configuration is built.
+fn first_hop_warning(samples: &[Duration], rounds: usize, dismissed: bool) -> bool {
+    rounds > 0
+        && !dismissed
+        && samples.len() >= rounds
+        && samples[..rounds].iter().all(Duration::is_zero)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const LOST: Duration = Duration::ZERO;
+    const RECV: Duration = Duration::from_millis(10);
+
+    #[test_case(&[LOST, LOST], 3, false, false; "threshold not reached")]
+    #[test_case(&[LOST, LOST, LOST], 3, false, true; "most recent samples all lost")]
+    #[test_case(&[LOST, LOST, LOST, RECV], 3, false, true; "older sample received")]
+    #[test_case(&[LOST, RECV, LOST], 3, false, false; "recent sample received")]
+    #[test_case(&[LOST, LOST, LOST], 3, true, false; "dismissed")]
+    #[test_case(&[LOST, LOST, LOST], 0, false, false; "disabled")]
+    #[test_case(&[], 0, false, false; "disabled without samples")]
+    #[test_case(&[LOST, LOST, LOST], 4, false, false; "rounds exceed retained samples")]
+    fn test_first_hop_warning(
+        samples: &[Duration],
+        rounds: usize,
+        dismissed: bool,
+        expected: bool,
+    ) {
+        assert_eq!(expected, first_hop_warning(samples, rounds, dismissed));
+    }
+}
@@ -306,6 +306,11 @@ impl GeoIpLookup {
         }
     }
 
+    /// Whether a `MaxMind` DB is available to lookup `GeoIpCity` data.
+    pub const fn is_enabled(&self) -> bool {
+        self.reader.is_some()
+    }
+
     /// Lookup an `GeoIpCity` for an `IpAddr`.
     ///
     /// If an entry is found it is cached and returned, otherwise None is returned.
@@ -13,19 +13,32 @@ use trippy_privilege::Privilege;
 
 mod app;
 mod config;
+mod fixture;
 mod frontend;
 mod geoip;
 mod locale;
+mod notes;
 mod print;
+mod prometheus;
+mod provider;
 mod report;
+#[cfg(feature = "sqlite")]
+mod sqlite;
 mod util;
+mod webhook;
 
 // initialize the i18n system.
 rust_i18n::i18n!("locales", fallback = "en");
 
 /// Run the Trippy application.
 pub fn trippy() -> anyhow::Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    if args.prompt && args.targets.is_empty() {
+        locale::set_locale(args.tui_locale.as_deref());
+        if !frontend::run_prompt(&mut args)? {
+            return Ok(());
+        }
+    }
     let privilege = Privilege::acquire_privileges()?;
     let pid = u16::try_from(process::id() % u32::from(u16::MAX))?;
     match TrippyAction::from(args, &privilege, pid)? {
@@ -0,0 +1,239 @@
+use anyhow::{anyhow, Context};
+use encoding_rs_io::DecodeReaderBytes;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Notes attached to hop addresses, optionally persisted to a file.
+///
+/// A note is attached to either a single address, such as `10.0.0.1`, or to an address prefix,
+/// such as `10.0.0.0/8`.  The note of an address is used in preference to that of any prefix, and
+/// the note of the longest matching prefix is used in preference to that of any other prefix.
+///
+/// Notes are persisted as a JSON object which maps an address, or prefix, to a note:
+///
+/// ```json
+/// {
+///   "10.0.0.1": "known to rate-limit ICMP, ignore loss here",
+///   "192.168.0.0/16": "home network"
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Notes {
+    path: Option<PathBuf>,
+    notes: BTreeMap<NoteKey, String>,
+}
+
+impl Notes {
+    /// Create a `Notes` which is not persisted.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Create a `Notes` which is persisted to a file.
+    ///
+    /// The notes are read from the file if it exists, otherwise the file is created when a note
+    /// is first set.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self {
+                path: Some(path.to_path_buf()),
+                notes: BTreeMap::new(),
+            });
+        }
+        let file = File::open(path)
+            .with_context(|| format!("failed to open notes file: {}", path.display()))?;
+        let mut decoder = DecodeReaderBytes::new(BufReader::new(file));
+        let mut dest = String::new();
+        decoder.read_to_string(&mut dest)?;
+        let notes = Self::parse(&dest)
+            .with_context(|| format!("invalid notes file: {}", path.display()))?;
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            notes,
+        })
+    }
+
+    /// The note for an address, if any.
+    ///
+    /// This is the note of the address itself, or otherwise that of the longest matching prefix.
+    pub fn lookup(&self, addr: IpAddr) -> Option<&str> {
+        self.notes
+            .get(&NoteKey::Addr(addr))
+            .or_else(|| {
+                self.notes
+                    .iter()
+                    .filter(|(key, _)| key.contains(addr))
+                    .max_by_key(|(key, _)| key.len())
+                    .map(|(_, note)| note)
+            })
+            .map(String::as_str)
+    }
+
+    /// The note of an address itself, if any.
+    ///
+    /// Unlike `lookup`, this does not consider the notes of any prefix.
+    pub fn get(&self, addr: IpAddr) -> Option<&str> {
+        self.notes.get(&NoteKey::Addr(addr)).map(String::as_str)
+    }
+
+    /// Set the note of an address and persist all notes to the file, if any.
+    ///
+    /// An empty note removes the note of the address.
+    pub fn set(&mut self, addr: IpAddr, note: &str) -> anyhow::Result<()> {
+        let note = note.trim();
+        if note.is_empty() {
+            self.notes.remove(&NoteKey::Addr(addr));
+        } else {
+            self.notes.insert(NoteKey::Addr(addr), note.to_string());
+        }
+        self.save()
+    }
+
+    /// Write all notes to the file, if any.
+    fn save(&self) -> anyhow::Result<()> {
+        if let Some(path) = &self.path {
+            let notes = self
+                .notes
+                .iter()
+                .map(|(key, note)| (key.to_string(), note))
+                .collect::<BTreeMap<_, _>>();
+            let json = serde_json::to_string_pretty(&notes)?;
+            std::fs::write(path, json)
+                .with_context(|| format!("failed to write notes file: {}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    fn parse(json: &str) -> anyhow::Result<BTreeMap<NoteKey, String>> {
+        serde_json::from_str::<BTreeMap<String, String>>(json)?
+            .into_iter()
+            .map(|(key, note)| Ok((NoteKey::from_str(&key)?, note)))
+            .collect()
+    }
+}
+
+/// The address, or address prefix, which a note is attached to.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd)]
+enum NoteKey {
+    Addr(IpAddr),
+    Prefix(IpAddr, u8),
+}
+
+impl NoteKey {
+    /// Does this key match an address?
+    fn contains(self, addr: IpAddr) -> bool {
+        match (self, addr) {
+            (Self::Addr(key), addr) => key == addr,
+            (Self::Prefix(IpAddr::V4(network), len), IpAddr::V4(addr)) => {
+                let mask = u32::MAX.checked_shl(32 - u32::from(len)).unwrap_or(0);
+                u32::from(network) & mask == u32::from(addr) & mask
+            }
+            (Self::Prefix(IpAddr::V6(network), len), IpAddr::V6(addr)) => {
+                let mask = u128::MAX.checked_shl(128 - u32::from(len)).unwrap_or(0);
+                u128::from(network) & mask == u128::from(addr) & mask
+            }
+            _ => false,
+        }
+    }
+
+    /// The number of leading bits of the key which must match.
+    const fn len(self) -> u8 {
+        match self {
+            Self::Addr(IpAddr::V4(_)) => 32,
+            Self::Addr(IpAddr::V6(_)) => 128,
+            Self::Prefix(_, len) => len,
+        }
+    }
+}
+
+impl FromStr for NoteKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || anyhow!("invalid note address: {s}");
+        match s.split_once('/') {
+            None => Ok(Self::Addr(IpAddr::from_str(s).map_err(|_| invalid())?)),
+            Some((addr, len)) => {
+                let addr = IpAddr::from_str(addr).map_err(|_| invalid())?;
+                let len = u8::from_str(len).map_err(|_| invalid())?;
+                let max_len = if addr.is_ipv4() { 32 } else { 128 };
+                if len > max_len {
+                    return Err(invalid());
+                }
+                Ok(Self::Prefix(addr, len))
+            }
+        }
+    }
+}
+
+impl Display for NoteKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Addr(addr) => write!(f, "{addr}"),
+            Self::Prefix(addr, len) => write!(f, "{addr}/{len}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const NOTES: &str = r#"
+    {
+        "10.0.0.1": "address",
+        "10.0.0.0/8": "short prefix",
+        "10.1.0.0/16": "long prefix",
+        "2001:db8::/32": "ipv6 prefix"
+    }
+    "#;
+
+    #[test_case("10.0.0.1", Some("address"); "address")]
+    #[test_case("10.0.0.2", Some("short prefix"); "short prefix")]
+    #[test_case("10.1.2.3", Some("long prefix"); "longest prefix")]
+    #[test_case("2001:db8::1", Some("ipv6 prefix"); "ipv6 prefix")]
+    #[test_case("11.0.0.1", None; "no match")]
+    #[test_case("::ffff:10.0.0.1", None; "no match other family")]
+    fn test_lookup(addr: &str, expected: Option<&str>) {
+        let notes = Notes {
+            path: None,
+            notes: Notes::parse(NOTES).unwrap(),
+        };
+        assert_eq!(expected, notes.lookup(IpAddr::from_str(addr).unwrap()));
+    }
+
+    #[test_case("0.0.0.0/0", "1.2.3.4", true; "ipv4 any")]
+    #[test_case("1.2.3.4/32", "1.2.3.4", true; "ipv4 host prefix")]
+    #[test_case("1.2.3.4/32", "1.2.3.5", false; "ipv4 host prefix no match")]
+    #[test_case("::/0", "::1", true; "ipv6 any")]
+    #[test_case("0.0.0.0/0", "::1", false; "mixed family")]
+    fn test_contains(key: &str, addr: &str, expected: bool) {
+        let key = NoteKey::from_str(key).unwrap();
+        assert_eq!(expected, key.contains(IpAddr::from_str(addr).unwrap()));
+    }
+
+    #[test_case("foo"; "invalid address")]
+    #[test_case("10.0.0.0/33"; "invalid ipv4 prefix length")]
+    #[test_case("::/129"; "invalid ipv6 prefix length")]
+    #[test_case("10.0.0.0/x"; "invalid prefix length")]
+    fn test_invalid_key(key: &str) {
+        assert!(NoteKey::from_str(key).is_err());
+    }
+
+    #[test]
+    fn test_set() {
+        let mut notes = Notes::empty();
+        let addr = IpAddr::from([10, 0, 0, 1]);
+        notes.set(addr, " rate limited ").unwrap();
+        assert_eq!(Some("rate limited"), notes.get(addr));
+        notes.set(addr, "").unwrap();
+        assert_eq!(None, notes.get(addr));
+    }
+}
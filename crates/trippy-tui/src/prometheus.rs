@@ -0,0 +1,289 @@
+use crate::app::TraceInfo;
+use std::fmt::Write as _;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use trippy_core::{Hop, State};
+
+/// How often to check whether any trace has completed a round.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The metrics written for every hop, as `(name, type, help)`.
+const METRICS: [(&str, &str, &str); 7] = [
+    (
+        "trippy_hop_sent_total",
+        "counter",
+        "The number of probes sent to the hop.",
+    ),
+    (
+        "trippy_hop_received_total",
+        "counter",
+        "The number of probe responses received from the hop.",
+    ),
+    (
+        "trippy_hop_loss_pct",
+        "gauge",
+        "The percentage of probes to the hop which were lost.",
+    ),
+    (
+        "trippy_hop_last_rtt_ms",
+        "gauge",
+        "The round-trip time of the last probe response from the hop in milliseconds.",
+    ),
+    (
+        "trippy_hop_best_rtt_ms",
+        "gauge",
+        "The best round-trip time of the hop in milliseconds.",
+    ),
+    (
+        "trippy_hop_worst_rtt_ms",
+        "gauge",
+        "The worst round-trip time of the hop in milliseconds.",
+    ),
+    (
+        "trippy_hop_avg_rtt_ms",
+        "gauge",
+        "The average round-trip time of the hop in milliseconds.",
+    ),
+];
+
+/// Write the metrics of every hop of traces to a Prometheus textfile.
+#[derive(Debug, Clone)]
+pub struct PrometheusExport {
+    path: PathBuf,
+}
+
+impl PrometheusExport {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Write the metrics of traces in the background.
+    ///
+    /// Failures to write the textfile are logged rather than returned, as a failing export must
+    /// not stop the trace.
+    pub fn start(self, traces: Vec<TraceInfo>) -> anyhow::Result<PrometheusExportHandle> {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let handle = thread::Builder::new()
+            .name(String::from("prometheus"))
+            .spawn(move || self.export(&traces, &thread_stop))?;
+        Ok(PrometheusExportHandle { stop, handle })
+    }
+
+    /// Write the metrics after every completed round until stopped.
+    ///
+    /// A final check is made after being stopped such that the last round of a trace which
+    /// completes, such as in report modes, is written.
+    fn export(&self, traces: &[TraceInfo], stop: &AtomicBool) {
+        let mut round_counts = vec![0; traces.len()];
+        loop {
+            let stopped = stop.load(Ordering::Relaxed);
+            let snapshots = traces
+                .iter()
+                .map(|info| info.data.snapshot())
+                .collect::<Vec<_>>();
+            let counts = snapshots
+                .iter()
+                .map(|state| state.round_count(State::default_flow_id()))
+                .collect::<Vec<_>>();
+            if counts != round_counts {
+                let metrics = traces
+                    .iter()
+                    .zip(&snapshots)
+                    .flat_map(|(info, state)| HopMetrics::from_trace(info, state))
+                    .collect::<Vec<_>>();
+                if let Err(err) = self.write(&render(&metrics)) {
+                    tracing::error!(path = %self.path.display(), %err, "failed to write prometheus textfile");
+                }
+                round_counts = counts;
+            }
+            if stopped {
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Replace the textfile such that a partially written file is never collected.
+    fn write(&self, contents: &str) -> anyhow::Result<()> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        std::fs::write(&tmp, contents)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+/// A handle to a running Prometheus export.
+#[derive(Debug)]
+pub struct PrometheusExportHandle {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl PrometheusExportHandle {
+    /// Write any remaining rounds and stop.
+    pub fn finish(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if self.handle.join().is_err() {
+            tracing::error!("prometheus export thread panicked");
+        }
+    }
+}
+
+/// The metrics of a single hop of a trace.
+#[derive(Debug, Clone, PartialEq)]
+struct HopMetrics {
+    target: String,
+    ttl: u8,
+    /// The address which responded most often for the hop.
+    host: Option<IpAddr>,
+    sent: usize,
+    received: usize,
+    loss_pct: f64,
+    last_ms: Option<f64>,
+    best_ms: Option<f64>,
+    worst_ms: Option<f64>,
+    avg_ms: f64,
+}
+
+impl HopMetrics {
+    /// The metrics of every hop of a trace.
+    ///
+    /// A single series is created for every ttl, up to the maximum ttl of the trace, such that
+    /// the number of series is bounded regardless of how many addresses respond for a hop.
+    fn from_trace(info: &TraceInfo, state: &State) -> Vec<Self> {
+        let max_ttl = info.data.max_ttl().0;
+        state
+            .hops()
+            .iter()
+            .filter(|hop| hop.ttl() <= max_ttl)
+            .map(|hop| Self::from_hop(&info.target_hostname, hop))
+            .collect()
+    }
+
+    fn from_hop(target: &str, hop: &Hop) -> Self {
+        Self {
+            target: target.to_string(),
+            ttl: hop.ttl(),
+            host: hop
+                .addrs_with_counts()
+                .max_by_key(|(_, &count)| count)
+                .map(|(addr, _)| *addr),
+            sent: hop.total_sent(),
+            received: hop.total_recv(),
+            loss_pct: hop.loss_pct(),
+            last_ms: hop.last_ms(),
+            best_ms: hop.best_ms(),
+            worst_ms: hop.worst_ms(),
+            avg_ms: hop.avg_ms(),
+        }
+    }
+
+    /// The value of a metric, if any, by name.
+    #[allow(clippy::cast_precision_loss)]
+    fn value(&self, name: &str) -> Option<f64> {
+        match name {
+            "trippy_hop_sent_total" => Some(self.sent as f64),
+            "trippy_hop_received_total" => Some(self.received as f64),
+            "trippy_hop_loss_pct" => Some(self.loss_pct),
+            "trippy_hop_last_rtt_ms" => self.last_ms,
+            "trippy_hop_best_rtt_ms" => self.best_ms,
+            "trippy_hop_worst_rtt_ms" => self.worst_ms,
+            "trippy_hop_avg_rtt_ms" => (self.received > 0).then_some(self.avg_ms),
+            _ => None,
+        }
+    }
+}
+
+/// Render metrics in the Prometheus text exposition format.
+///
+/// Round-trip time metrics are omitted for hops which have not responded.
+fn render(metrics: &[HopMetrics]) -> String {
+    let mut out = String::new();
+    for (name, typ, help) in METRICS {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} {typ}");
+        for hop in metrics {
+            if let Some(value) = hop.value(name) {
+                let host = hop.host.map(|addr| addr.to_string()).unwrap_or_default();
+                let _ = writeln!(
+                    out,
+                    "{name}{{target=\"{}\",ttl=\"{}\",host=\"{host}\"}} {value}",
+                    escape_label(&hop.target),
+                    hop.ttl,
+                );
+            }
+        }
+    }
+    out
+}
+
+/// Escape a label value as required by the Prometheus text exposition format.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn hop(ttl: u8, host: Option<&str>, received: usize) -> HopMetrics {
+        HopMetrics {
+            target: String::from("example.com"),
+            ttl,
+            host: host.map(|host| IpAddr::from_str(host).unwrap()),
+            sent: 2,
+            received,
+            loss_pct: 50_f64,
+            last_ms: (received > 0).then_some(12.5),
+            best_ms: (received > 0).then_some(10_f64),
+            worst_ms: (received > 0).then_some(15_f64),
+            avg_ms: 12.5,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(&[hop(1, Some("10.0.0.1"), 1), hop(2, None, 0)]);
+        let expected = r#"# HELP trippy_hop_sent_total The number of probes sent to the hop.
+# TYPE trippy_hop_sent_total counter
+trippy_hop_sent_total{target="example.com",ttl="1",host="10.0.0.1"} 2
+trippy_hop_sent_total{target="example.com",ttl="2",host=""} 2
+# HELP trippy_hop_received_total The number of probe responses received from the hop.
+# TYPE trippy_hop_received_total counter
+trippy_hop_received_total{target="example.com",ttl="1",host="10.0.0.1"} 1
+trippy_hop_received_total{target="example.com",ttl="2",host=""} 0
+# HELP trippy_hop_loss_pct The percentage of probes to the hop which were lost.
+# TYPE trippy_hop_loss_pct gauge
+trippy_hop_loss_pct{target="example.com",ttl="1",host="10.0.0.1"} 50
+trippy_hop_loss_pct{target="example.com",ttl="2",host=""} 50
+# HELP trippy_hop_last_rtt_ms The round-trip time of the last probe response from the hop in milliseconds.
+# TYPE trippy_hop_last_rtt_ms gauge
+trippy_hop_last_rtt_ms{target="example.com",ttl="1",host="10.0.0.1"} 12.5
+# HELP trippy_hop_best_rtt_ms The best round-trip time of the hop in milliseconds.
+# TYPE trippy_hop_best_rtt_ms gauge
+trippy_hop_best_rtt_ms{target="example.com",ttl="1",host="10.0.0.1"} 10
+# HELP trippy_hop_worst_rtt_ms The worst round-trip time of the hop in milliseconds.
+# TYPE trippy_hop_worst_rtt_ms gauge
+trippy_hop_worst_rtt_ms{target="example.com",ttl="1",host="10.0.0.1"} 15
+# HELP trippy_hop_avg_rtt_ms The average round-trip time of the hop in milliseconds.
+# TYPE trippy_hop_avg_rtt_ms gauge
+trippy_hop_avg_rtt_ms{target="example.com",ttl="1",host="10.0.0.1"} 12.5
+"#;
+        assert_eq!(expected, rendered);
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(r#"a\\b\"c\nd"#, escape_label("a\\b\"c\nd"));
+    }
+}
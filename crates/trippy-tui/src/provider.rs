@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+
+/// The bundled autonomous system (AS) provider classes.
+///
+/// This is intentionally a small table of well-known networks, additional
+/// classes (or overrides) may be provided via configuration.
+const BUNDLED_PROVIDER_CLASSES: &[(u32, &str)] = &[
+    // Content delivery networks.
+    (13335, "cdn"),
+    (20940, "cdn"),
+    (16625, "cdn"),
+    (54113, "cdn"),
+    (2906, "cdn"),
+    (22822, "cdn"),
+    // Cloud providers.
+    (15169, "cloud"),
+    (396_982, "cloud"),
+    (16509, "cloud"),
+    (14618, "cloud"),
+    (8075, "cloud"),
+    (14061, "cloud"),
+    (24940, "cloud"),
+    (16276, "cloud"),
+    (31898, "cloud"),
+    (63949, "cloud"),
+    // Transit providers.
+    (174, "transit"),
+    (1299, "transit"),
+    (2914, "transit"),
+    (3257, "transit"),
+    (3356, "transit"),
+    (3491, "transit"),
+    (6453, "transit"),
+    (6461, "transit"),
+    (6762, "transit"),
+    (6939, "transit"),
+    // Residential ISPs.
+    (7922, "isp"),
+    (20115, "isp"),
+    (7018, "isp"),
+    (701, "isp"),
+    (22773, "isp"),
+    (3320, "isp"),
+    (2856, "isp"),
+    (3215, "isp"),
+    (5089, "isp"),
+    (12322, "isp"),
+];
+
+/// Classify autonomous systems (AS) into broad provider classes.
+///
+/// For example: `isp`, `transit`, `cdn` or `cloud`.
+#[derive(Debug, Clone, Default)]
+pub struct ProviderLookup {
+    classes: HashMap<u32, String>,
+}
+
+impl ProviderLookup {
+    /// Create a `ProviderLookup` from the bundled table and the given overrides.
+    ///
+    /// Overrides take precedence over the bundled table.
+    pub fn new(overrides: &HashMap<u32, String>) -> Self {
+        let classes = BUNDLED_PROVIDER_CLASSES
+            .iter()
+            .map(|(asn, class)| (*asn, (*class).to_string()))
+            .chain(overrides.iter().map(|(asn, class)| (*asn, class.clone())))
+            .collect();
+        Self { classes }
+    }
+
+    /// Lookup the provider class for an AS number.
+    ///
+    /// Returns `None` if the AS number is not known or cannot be parsed.
+    pub fn lookup(&self, asn: &str) -> Option<&str> {
+        let asn = asn.trim().trim_start_matches("AS").parse::<u32>().ok()?;
+        self.classes.get(&asn).map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("13335", Some("cdn"); "bundled")]
+    #[test_case("AS13335", Some("cdn"); "bundled with prefix")]
+    #[test_case("64512", None; "unknown")]
+    #[test_case("", None; "empty")]
+    #[test_case("foo", None; "invalid")]
+    fn test_lookup_bundled(asn: &str, expected: Option<&str>) {
+        let lookup = ProviderLookup::new(&HashMap::new());
+        assert_eq!(expected, lookup.lookup(asn));
+    }
+
+    #[test]
+    fn test_lookup_overrides() {
+        let overrides = HashMap::from([(13335, String::from("dns")), (64512, String::from("lab"))]);
+        let lookup = ProviderLookup::new(&overrides);
+        assert_eq!(Some("dns"), lookup.lookup("13335"));
+        assert_eq!(Some("lab"), lookup.lookup("64512"));
+        assert_eq!(Some("transit"), lookup.lookup("3356"));
+    }
+}
@@ -4,12 +4,16 @@ use trippy_core::Tracer;
 
 pub mod csv;
 pub mod dot;
+pub mod dry_run;
 pub mod flows;
 pub mod json;
+pub mod probes;
+pub mod rounds;
 pub mod silent;
 pub mod stream;
 pub mod table;
-mod types;
+pub mod tree;
+pub mod types;
 
 /// Block until trace data for round `round` is available.
 fn wait_for_round(trace_data: &Tracer, report_cycles: usize) -> anyhow::Result<State> {
@@ -0,0 +1,78 @@
+use crate::app::TraceInfo;
+use anyhow::anyhow;
+use std::cell::Cell;
+use std::time::{Duration, SystemTime};
+use trippy_core::{Probe, Protocol, RoundId};
+
+/// Print the probes which would be sent for N cycles without sending them.
+///
+/// No responses are received and so every round lasts for the maximum round duration, as if
+/// every probe was lost.
+pub fn report(info: &TraceInfo) -> anyhow::Result<()> {
+    let protocol = info.data.protocol();
+    println!(
+        "Dry run to {} ({}) with {} probes of {} bytes, no probes will be sent",
+        info.target_hostname,
+        info.data.target_addr(),
+        protocol,
+        info.data.packet_size().0,
+    );
+    let round_start: Cell<Option<(RoundId, SystemTime)>> = Cell::new(None);
+    info.data
+        .dry_run_with(|probe| {
+            let start = match round_start.get() {
+                Some((round, start)) if round == probe.round => start,
+                _ => {
+                    round_start.set(Some((probe.round, probe.sent)));
+                    probe.sent
+                }
+            };
+            let offset = probe.sent.duration_since(start).unwrap_or_default();
+            println!("{}", format_probe(probe, protocol, offset));
+        })
+        .map_err(|err| anyhow!("error: {}", err))
+}
+
+/// Format a probe, i.e. `round=0 offset_ms=0.0 ttl=1 sequence=33434 identifier=1234`.
+///
+/// The identifier is shown for `icmp` probes and the ports are shown for `udp` and `tcp` probes.
+fn format_probe(probe: &Probe, protocol: Protocol, offset: Duration) -> String {
+    let round = probe.round.0;
+    let offset_ms = offset.as_secs_f64() * 1000_f64;
+    let ttl = probe.ttl.0;
+    let sequence = probe.sequence.0;
+    let fields = match protocol {
+        Protocol::Icmp => format!("identifier={}", probe.identifier.0),
+        Protocol::Udp | Protocol::Tcp => format!(
+            "src_port={} dest_port={}",
+            probe.src_port.0, probe.dest_port.0
+        ),
+    };
+    format!("round={round} offset_ms={offset_ms:.1} ttl={ttl} sequence={sequence} {fields}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+    use test_case::test_case;
+    use trippy_core::{Flags, Port, Sequence, TimeToLive, TraceId};
+
+    #[test_case(Protocol::Icmp, "round=2 offset_ms=12.5 ttl=3 sequence=33436 identifier=1234"; "icmp")]
+    #[test_case(Protocol::Udp, "round=2 offset_ms=12.5 ttl=3 sequence=33436 src_port=1024 dest_port=33434"; "udp")]
+    #[test_case(Protocol::Tcp, "round=2 offset_ms=12.5 ttl=3 sequence=33436 src_port=1024 dest_port=33434"; "tcp")]
+    fn test_format_probe(protocol: Protocol, expected: &str) {
+        let probe = Probe {
+            sequence: Sequence(33436),
+            identifier: TraceId(1234),
+            src_port: Port(1024),
+            dest_port: Port(33434),
+            ttl: TimeToLive(3),
+            round: RoundId(2),
+            sent: UNIX_EPOCH,
+            flags: Flags::empty(),
+        };
+        let offset = Duration::from_micros(12500);
+        assert_eq!(expected, format_probe(&probe, protocol, offset));
+    }
+}
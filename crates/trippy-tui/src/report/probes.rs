@@ -0,0 +1,225 @@
+use crate::app::TraceInfo;
+use anyhow::anyhow;
+use serde::Serialize;
+use std::io::Write;
+use std::net::IpAddr;
+use std::sync::mpsc::Receiver;
+use std::time::{SystemTime, UNIX_EPOCH};
+use trippy_core::{ProbeStatus, Round};
+
+/// The output format of the raw probe report.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ProbeFormat {
+    /// One JSON object per line.
+    JsonLines,
+    /// One CSV row per line.
+    Csv,
+}
+
+/// Generate a raw report of every probe for N cycles.
+///
+/// The probes of each round are received from the tracer via `rounds`, one
+/// `Vec<ProbeRow>` per round.
+pub fn report(
+    info: &TraceInfo,
+    report_cycles: usize,
+    rounds: &Receiver<Vec<ProbeRow>>,
+    format: ProbeFormat,
+) -> anyhow::Result<()> {
+    let rows = rounds.iter().take(report_cycles).flatten();
+    match format {
+        ProbeFormat::JsonLines => {
+            let mut writer = std::io::stdout().lock();
+            for row in rows {
+                serde_json::to_writer(&mut writer, &row)?;
+                writeln!(writer)?;
+            }
+        }
+        ProbeFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(std::io::stdout());
+            for row in rows {
+                writer.serialize(row)?;
+            }
+            writer.flush()?;
+        }
+    }
+    match info.data.snapshot().error() {
+        Some(err) => Err(anyhow!("error: {}", err)),
+        None => Ok(()),
+    }
+}
+
+/// A single probe.
+///
+/// Probes which did not receive a response (i.e. lost probes) have no
+/// `host`, `received_us`, `rtt_ms` or `jitter_ms`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProbeRow {
+    pub round: usize,
+    pub ttl: u8,
+    pub sequence: u16,
+    pub src_port: u16,
+    pub dest_port: u16,
+    pub status: ProbeRowStatus,
+    pub host: Option<IpAddr>,
+    /// The time the probe was sent, in microseconds since the Unix epoch.
+    pub sent_us: u64,
+    /// The time the response was received, in microseconds since the Unix epoch.
+    pub received_us: Option<u64>,
+    pub rtt_ms: Option<f64>,
+    /// The absolute difference from the round-trip time of the previous
+    /// response for the same ttl.
+    pub jitter_ms: Option<f64>,
+}
+
+/// The status of a probe.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ProbeRowStatus {
+    /// A response was received for the probe.
+    Complete,
+    /// No response was received for the probe.
+    Lost,
+    /// The probe failed to be sent or received.
+    Failed,
+}
+
+impl ProbeRow {
+    /// Create the rows for all probes in a round.
+    ///
+    /// Only probes up to and including the largest ttl of the round are
+    /// included, which matches the hops which are shown for the round. When
+    /// the target did not respond, this includes the final probe of the round
+    /// which is assumed to represent the target and which will always be lost.
+    ///
+    /// Probes which were not sent or were skipped are not included.
+    pub fn from_round(round: &Round<'_>) -> Vec<Self> {
+        round
+            .probes
+            .iter()
+            .filter_map(Self::from_probe)
+            .filter(|row| row.ttl <= round.largest_ttl.0)
+            .collect()
+    }
+
+    fn from_probe(probe: &ProbeStatus) -> Option<Self> {
+        match probe {
+            ProbeStatus::Complete(complete) => Some(Self {
+                round: complete.round.0,
+                ttl: complete.ttl.0,
+                sequence: complete.sequence.0,
+                src_port: complete.src_port.0,
+                dest_port: complete.dest_port.0,
+                status: ProbeRowStatus::Complete,
+                host: Some(complete.host),
+                sent_us: epoch_micros(complete.sent),
+                received_us: Some(epoch_micros(complete.received)),
+                rtt_ms: Some(complete.rtt().as_secs_f64() * 1000_f64),
+                jitter_ms: complete
+                    .jitter
+                    .map(|jitter| jitter.as_secs_f64() * 1000_f64),
+            }),
+            ProbeStatus::Awaited(awaited) => Some(Self {
+                round: awaited.round.0,
+                ttl: awaited.ttl.0,
+                sequence: awaited.sequence.0,
+                src_port: awaited.src_port.0,
+                dest_port: awaited.dest_port.0,
+                status: ProbeRowStatus::Lost,
+                host: None,
+                sent_us: epoch_micros(awaited.sent),
+                received_us: None,
+                rtt_ms: None,
+                jitter_ms: None,
+            }),
+            ProbeStatus::Failed(failed) => Some(Self {
+                round: failed.round.0,
+                ttl: failed.ttl.0,
+                sequence: failed.sequence.0,
+                src_port: failed.src_port.0,
+                dest_port: failed.dest_port.0,
+                status: ProbeRowStatus::Failed,
+                host: None,
+                sent_us: epoch_micros(failed.sent),
+                received_us: None,
+                rtt_ms: None,
+                jitter_ms: None,
+            }),
+            ProbeStatus::NotSent | ProbeStatus::Skipped => None,
+        }
+    }
+}
+
+fn epoch_micros(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |dur| u64::try_from(dur.as_micros()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use trippy_core::{
+        CompletionReason, Flags, IcmpPacketType, Port, Probe, ProbeComplete, RoundId, Sequence,
+        TimeToLive, TraceId,
+    };
+
+    #[test]
+    fn test_from_round() {
+        let sent = UNIX_EPOCH + Duration::from_secs(1);
+        let host = IpAddr::from_str("10.0.0.1").unwrap();
+        let probe = |ttl: u8| Probe {
+            sequence: Sequence(33000 + u16::from(ttl)),
+            identifier: TraceId(0),
+            src_port: Port(1024),
+            dest_port: Port(80),
+            ttl: TimeToLive(ttl),
+            round: RoundId(0),
+            sent,
+            flags: Flags::empty(),
+        };
+        let probes = [
+            ProbeStatus::Complete(ProbeComplete {
+                sequence: Sequence(33001),
+                identifier: TraceId(0),
+                src_port: Port(1024),
+                dest_port: Port(80),
+                ttl: TimeToLive(1),
+                round: RoundId(0),
+                sent,
+                host,
+                received: sent + Duration::from_micros(12500),
+                icmp_packet_type: IcmpPacketType::NotApplicable,
+                expected_udp_checksum: None,
+                actual_udp_checksum: None,
+                extensions: None,
+                reply_ttl: Some(63),
+                quoted_len: None,
+                jitter: Some(Duration::from_micros(1500)),
+                ttl_addrs: std::iter::once(host).collect(),
+            }),
+            ProbeStatus::Awaited(probe(2)),
+            ProbeStatus::Awaited(probe(3)),
+            ProbeStatus::NotSent,
+        ];
+        let round = Round::new(
+            &probes,
+            TimeToLive(2),
+            CompletionReason::RoundTimeLimitExceeded,
+        );
+        let rows = ProbeRow::from_round(&round);
+        assert_eq!(2, rows.len());
+        assert_eq!(ProbeRowStatus::Complete, rows[0].status);
+        assert_eq!(Some(host), rows[0].host);
+        assert_eq!(1_000_000, rows[0].sent_us);
+        assert_eq!(Some(1_012_500), rows[0].received_us);
+        assert_eq!(Some(12.5), rows[0].rtt_ms);
+        assert_eq!(Some(1.5), rows[0].jitter_ms);
+        assert_eq!(ProbeRowStatus::Lost, rows[1].status);
+        assert_eq!(2, rows[1].ttl);
+        assert_eq!(33002, rows[1].sequence);
+        assert_eq!(None, rows[1].rtt_ms);
+        assert_eq!(None, rows[1].jitter_ms);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::app::TraceInfo;
+use crate::report::probes::ProbeRowStatus;
+use anyhow::anyhow;
+use serde::Serialize;
+use std::io::Write;
+use std::net::IpAddr;
+use std::sync::mpsc::Receiver;
+use std::time::{SystemTime, UNIX_EPOCH};
+use trippy_core::{IcmpPacketType, ProbeStatus, Round, UnreachableCode};
+
+/// The version of the round report schema.
+///
+/// This must be incremented whenever a field is removed or changes meaning.
+pub const ROUND_SCHEMA_VERSION: u8 = 1;
+
+/// Generate a report of every completed round for N cycles.
+///
+/// Each round is written as a single JSON object per line.
+pub fn report(
+    info: &TraceInfo,
+    report_cycles: usize,
+    rounds: &Receiver<RoundRow>,
+) -> anyhow::Result<()> {
+    let mut writer = std::io::stdout().lock();
+    for round in rounds.iter().take(report_cycles) {
+        serde_json::to_writer(&mut writer, &round)?;
+        writeln!(writer)?;
+    }
+    match info.data.snapshot().error() {
+        Some(err) => Err(anyhow!("error: {}", err)),
+        None => Ok(()),
+    }
+}
+
+/// A single completed round.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoundRow {
+    pub schema: u8,
+    pub round: usize,
+    /// The time the first probe of the round was sent, in microseconds since the Unix epoch.
+    pub timestamp_us: u64,
+    pub hops: Vec<RoundHop>,
+}
+
+/// A single hop of a completed round.
+///
+/// Hops which did not respond have no `host`, `rtt_ms`, `icmp_packet_type`
+/// or `unreachable_code`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoundHop {
+    pub ttl: u8,
+    pub host: Option<IpAddr>,
+    pub rtt_ms: Option<f64>,
+    pub status: ProbeRowStatus,
+    pub icmp_packet_type: Option<&'static str>,
+    /// The reason given by a `destination-unreachable` response.
+    pub unreachable_code: Option<String>,
+}
+
+impl RoundRow {
+    /// Create the row for a round.
+    ///
+    /// Only probes up to and including the largest ttl of the round are
+    /// included, and probes which were not sent or were skipped are not
+    /// included.
+    pub fn from_round(round: &Round<'_>) -> Self {
+        let (ids, hops): (Vec<_>, Vec<_>) = round
+            .probes
+            .iter()
+            .filter_map(RoundHop::from_probe)
+            .filter(|(_, hop)| hop.ttl <= round.largest_ttl.0)
+            .unzip();
+        let (round_id, sent) = ids
+            .into_iter()
+            .min_by_key(|&(_, sent)| sent)
+            .unwrap_or((0, UNIX_EPOCH));
+        Self {
+            schema: ROUND_SCHEMA_VERSION,
+            round: round_id,
+            timestamp_us: epoch_micros(sent),
+            hops,
+        }
+    }
+}
+
+impl RoundHop {
+    /// The hop for a probe, along with the round and send time of the probe.
+    fn from_probe(probe: &ProbeStatus) -> Option<((usize, SystemTime), Self)> {
+        match probe {
+            ProbeStatus::Complete(complete) => Some((
+                (complete.round.0, complete.sent),
+                Self {
+                    ttl: complete.ttl.0,
+                    host: Some(complete.host),
+                    rtt_ms: Some(complete.rtt().as_secs_f64() * 1000_f64),
+                    status: ProbeRowStatus::Complete,
+                    icmp_packet_type: Some(format_icmp_packet_type(complete.icmp_packet_type)),
+                    unreachable_code: match complete.icmp_packet_type {
+                        IcmpPacketType::Unreachable(code) => Some(format_unreachable_code(
+                            UnreachableCode::new(code, complete.host),
+                        )),
+                        _ => None,
+                    },
+                },
+            )),
+            ProbeStatus::Awaited(awaited) => Some((
+                (awaited.round.0, awaited.sent),
+                Self::unanswered(awaited.ttl.0, ProbeRowStatus::Lost),
+            )),
+            ProbeStatus::Failed(failed) => Some((
+                (failed.round.0, failed.sent),
+                Self::unanswered(failed.ttl.0, ProbeRowStatus::Failed),
+            )),
+            ProbeStatus::NotSent | ProbeStatus::Skipped => None,
+        }
+    }
+
+    const fn unanswered(ttl: u8, status: ProbeRowStatus) -> Self {
+        Self {
+            ttl,
+            host: None,
+            rtt_ms: None,
+            status,
+            icmp_packet_type: None,
+            unreachable_code: None,
+        }
+    }
+}
+
+const fn format_icmp_packet_type(icmp_packet_type: IcmpPacketType) -> &'static str {
+    match icmp_packet_type {
+        IcmpPacketType::TimeExceeded(_) => "time-exceeded",
+        IcmpPacketType::EchoReply(_) => "echo-reply",
+        IcmpPacketType::Unreachable(_) => "destination-unreachable",
+        IcmpPacketType::NotApplicable => "not-applicable",
+    }
+}
+
+fn format_unreachable_code(code: UnreachableCode) -> String {
+    match code {
+        UnreachableCode::Network => String::from("network"),
+        UnreachableCode::Host => String::from("host"),
+        UnreachableCode::Protocol => String::from("protocol"),
+        UnreachableCode::Port => String::from("port"),
+        UnreachableCode::AdminProhibited => String::from("admin-prohibited"),
+        UnreachableCode::Other(code) => format!("{code}"),
+    }
+}
+
+fn epoch_micros(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |dur| u64::try_from(dur.as_micros()).unwrap_or(u64::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+    use std::time::Duration;
+    use trippy_core::{
+        CompletionReason, Flags, IcmpPacketCode, Port, Probe, ProbeComplete, RoundId, Sequence,
+        TimeToLive, TraceId, TtlAddrs,
+    };
+
+    #[test]
+    fn test_from_round() {
+        let sent = UNIX_EPOCH + Duration::from_secs(1);
+        let complete = |ttl: u8, host: &str, icmp_packet_type: IcmpPacketType| {
+            ProbeStatus::Complete(ProbeComplete {
+                sequence: Sequence(33000 + u16::from(ttl)),
+                identifier: TraceId(0),
+                src_port: Port(1024),
+                dest_port: Port(80),
+                ttl: TimeToLive(ttl),
+                round: RoundId(3),
+                sent: sent + Duration::from_millis(u64::from(ttl)),
+                host: IpAddr::from_str(host).unwrap(),
+                received: sent + Duration::from_micros(12500),
+                icmp_packet_type,
+                expected_udp_checksum: None,
+                actual_udp_checksum: None,
+                extensions: None,
+                reply_ttl: Some(63),
+                quoted_len: None,
+                jitter: None,
+                ttl_addrs: TtlAddrs::default(),
+            })
+        };
+        let probes = [
+            complete(
+                1,
+                "10.0.0.1",
+                IcmpPacketType::TimeExceeded(IcmpPacketCode(0)),
+            ),
+            ProbeStatus::Awaited(Probe {
+                sequence: Sequence(33002),
+                identifier: TraceId(0),
+                src_port: Port(1024),
+                dest_port: Port(80),
+                ttl: TimeToLive(2),
+                round: RoundId(3),
+                sent,
+                flags: Flags::empty(),
+            }),
+            complete(
+                3,
+                "10.0.0.3",
+                IcmpPacketType::Unreachable(IcmpPacketCode(1)),
+            ),
+            ProbeStatus::NotSent,
+        ];
+        let round = Round::new(&probes, TimeToLive(3), CompletionReason::TargetFound);
+        let row = RoundRow::from_round(&round);
+        assert_eq!(ROUND_SCHEMA_VERSION, row.schema);
+        assert_eq!(3, row.round);
+        assert_eq!(1_000_000, row.timestamp_us);
+        assert_eq!(3, row.hops.len());
+        assert_eq!(Some("time-exceeded"), row.hops[0].icmp_packet_type);
+        assert_eq!(None, row.hops[0].unreachable_code);
+        assert_eq!(ProbeRowStatus::Lost, row.hops[1].status);
+        assert_eq!(None, row.hops[1].host);
+        assert_eq!(
+            Some("destination-unreachable"),
+            row.hops[2].icmp_packet_type
+        );
+        assert_eq!(Some(String::from("host")), row.hops[2].unreachable_code);
+        assert_eq!(Some(9.5), row.hops[2].rtt_ms);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let row = RoundRow {
+            schema: ROUND_SCHEMA_VERSION,
+            round: 1,
+            timestamp_us: 1_000_000,
+            hops: vec![RoundHop::unanswered(1, ProbeRowStatus::Lost)],
+        };
+        assert_eq!(
+            r#"{"schema":1,"round":1,"timestamp_us":1000000,"hops":[{"ttl":1,"host":null,"rtt_ms":null,"status":"lost","icmp_packet_type":null,"unreachable_code":null}]}"#,
+            serde_json::to_string(&row).unwrap()
+        );
+    }
+}
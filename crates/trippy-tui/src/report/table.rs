@@ -1,10 +1,14 @@
 use crate::app::TraceInfo;
+use chrono::SecondsFormat;
 use comfy_table::presets::{ASCII_MARKDOWN, UTF8_FULL};
 use comfy_table::{ContentArrangement, Table};
 use itertools::Itertools;
 use trippy_dns::Resolver;
 
 /// Generate a Markdown table report of trace data.
+///
+/// The table is preceded by a header line with the target, date and protocol
+/// of the trace.  Markdown special characters in hostnames are escaped.
 pub fn report_md<R: Resolver>(
     info: &TraceInfo,
     report_cycles: usize,
@@ -29,6 +33,7 @@ fn run_report_table<R: Resolver>(
     preset: &str,
 ) -> anyhow::Result<()> {
     let trace = super::wait_for_round(&info.data, report_cycles)?;
+    let markdown = preset == ASCII_MARKDOWN;
     let columns = vec![
         "Hop", "IPs", "Addrs", "Loss%", "Snt", "Recv", "Last", "Avg", "Best", "Wrst", "StdDev",
     ];
@@ -48,6 +53,13 @@ fn run_report_table<R: Resolver>(
         let hosts = hop
             .addrs()
             .map(|ip| resolver.reverse_lookup(*ip).to_string())
+            .map(|host| {
+                if markdown {
+                    escape_markdown(&host)
+                } else {
+                    host
+                }
+            })
             .join("\n");
         let host = if hosts.is_empty() {
             String::from("???")
@@ -72,6 +84,52 @@ fn run_report_table<R: Resolver>(
             &ttl, &ip, &host, &loss_pct, &sent, &recv, &last, &avg, &best, &worst, &stddev,
         ]);
     }
+    if markdown {
+        println!("{}\n", markdown_header(info));
+    }
     println!("{table}");
     Ok(())
 }
+
+/// The Markdown report header line.
+fn markdown_header(info: &TraceInfo) -> String {
+    let date = chrono::Local::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    format!(
+        "**Target:** {} ({}) **Date:** {} **Protocol:** {}",
+        escape_markdown(&info.target_hostname),
+        info.data.target_addr(),
+        date,
+        info.data.protocol()
+    )
+}
+
+/// Escape Markdown special characters.
+fn escape_markdown(value: &str) -> String {
+    const SPECIAL: &[char] = &[
+        '\\', '`', '*', '_', '{', '}', '[', ']', '<', '>', '(', ')', '#', '+', '!', '|', '~',
+    ];
+    value
+        .chars()
+        .fold(String::with_capacity(value.len()), |mut escaped, c| {
+            if SPECIAL.contains(&c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+            escaped
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("example.com", "example.com"; "no special characters")]
+    #[test_case("my_host.example.com", r"my\_host.example.com"; "underscore")]
+    #[test_case("a*b|c", r"a\*b\|c"; "asterisk and pipe")]
+    #[test_case("[host](link)", r"\[host\]\(link\)"; "link")]
+    #[test_case(r"back\slash", r"back\\slash"; "backslash")]
+    fn test_escape_markdown(value: &str, expected: &str) {
+        assert_eq!(expected, escape_markdown(value));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::app::TraceInfo;
+use crate::report::types::{fixed_width, Host};
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::collections::HashSet;
+use std::net::IpAddr;
+use trippy_core::{FlowEntry, FlowId, Hop};
+use trippy_dns::Resolver;
+
+/// The version of the trace tree schema.
+///
+/// This must be incremented for any incompatible change to the schema.
+const SCHEMA_VERSION: u32 = 1;
+
+/// Run a trace and generate a JSON tree of all flows.
+pub fn report<R: Resolver>(
+    info: &TraceInfo,
+    report_cycles: usize,
+    resolver: &R,
+) -> anyhow::Result<()> {
+    let trace = super::wait_for_round(&info.data, report_cycles)?;
+    let report = TreeReport {
+        schema_version: SCHEMA_VERSION,
+        target: Host {
+            ip: info.data.target_addr(),
+            hostname: info.target_hostname.clone(),
+        },
+        flows: trace.flows().len(),
+        children: Topology::new(trace.flows().iter().map(|(flow, flow_id)| {
+            (
+                flow.entries.as_slice(),
+                *flow_id,
+                trace.hops_for_flow(*flow_id),
+            )
+        }))
+        .tree(&|addr| resolver.reverse_lookup(addr).to_string()),
+    };
+    Ok(serde_json::to_writer_pretty(std::io::stdout(), &report)?)
+}
+
+#[derive(Serialize)]
+struct TreeReport {
+    schema_version: u32,
+    target: Host,
+    flows: usize,
+    children: Vec<TreeEdge>,
+}
+
+/// An edge to an observed next hop.
+#[derive(Serialize)]
+struct TreeEdge {
+    flows: Vec<u64>,
+    sent: usize,
+    recv: usize,
+    #[serde(serialize_with = "fixed_width")]
+    loss_pct: f64,
+    #[serde(serialize_with = "fixed_width")]
+    avg: f64,
+    node: TreeNode,
+}
+
+/// A node in the tree.
+///
+/// A node which has already been emitted elsewhere in the tree (i.e. where
+/// paths converge) is emitted as a reference to the `id` of that node.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum TreeNode {
+    Node {
+        id: String,
+        ttl: u8,
+        host: Option<Host>,
+        children: Vec<TreeEdge>,
+    },
+    Ref {
+        #[serde(rename = "ref")]
+        id: String,
+    },
+}
+
+/// A node of the topology.
+///
+/// Unknown hops are distinguished by their `ttl`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+enum NodeKey {
+    Known(IpAddr),
+    Unknown(u8),
+}
+
+impl NodeKey {
+    fn id(self) -> String {
+        match self {
+            Self::Known(addr) => addr.to_string(),
+            Self::Unknown(ttl) => format!("*{ttl}"),
+        }
+    }
+}
+
+/// The statistics of an edge, accumulated over all flows which traverse it.
+#[derive(Debug, Default)]
+struct EdgeStats {
+    flows: Vec<FlowId>,
+    sent: usize,
+    recv: usize,
+    total_ms: f64,
+}
+
+/// The topology of all flows, as a directed acyclic graph.
+///
+/// Edges are keyed by the parent and child node, where a parent of `None`
+/// is the source of the trace.
+struct Topology {
+    nodes: IndexMap<NodeKey, u8>,
+    edges: IndexMap<(Option<NodeKey>, NodeKey), EdgeStats>,
+}
+
+impl Topology {
+    /// Create the topology from the entries and hops of each flow.
+    ///
+    /// Each flow entry is implicitly associated with a `ttl`, indexed from 1.
+    fn new<'a>(flows: impl IntoIterator<Item = (&'a [FlowEntry], FlowId, &'a [Hop])>) -> Self {
+        let mut nodes = IndexMap::new();
+        let mut edges: IndexMap<_, EdgeStats> = IndexMap::new();
+        for (entries, flow_id, hops) in flows {
+            let mut parent = None;
+            for (i, entry) in entries.iter().enumerate() {
+                let ttl = u8::try_from(i + 1).unwrap_or(u8::MAX);
+                let key = match entry {
+                    FlowEntry::Known(addr) => NodeKey::Known(*addr),
+                    FlowEntry::Unknown => NodeKey::Unknown(ttl),
+                };
+                nodes.entry(key).or_insert(ttl);
+                let stats = edges.entry((parent, key)).or_default();
+                stats.flows.push(flow_id);
+                if let Some(hop) = hops.iter().find(|hop| hop.ttl() == ttl) {
+                    stats.sent += hop.total_sent();
+                    stats.recv += hop.total_recv();
+                    stats.total_ms += hop.avg_ms() * hop.total_recv() as f64;
+                }
+                parent = Some(key);
+            }
+        }
+        Self { nodes, edges }
+    }
+
+    /// The tree of edges from the source of the trace.
+    fn tree(&self, hostname: &impl Fn(IpAddr) -> String) -> Vec<TreeEdge> {
+        self.children(None, hostname, &mut HashSet::new())
+    }
+
+    /// The edges from a parent node to all children, in order of discovery.
+    fn children(
+        &self,
+        parent: Option<NodeKey>,
+        hostname: &impl Fn(IpAddr) -> String,
+        visited: &mut HashSet<NodeKey>,
+    ) -> Vec<TreeEdge> {
+        self.edges
+            .iter()
+            .filter(|((from, _), _)| *from == parent)
+            .map(|((_, to), stats)| {
+                let node = if visited.insert(*to) {
+                    TreeNode::Node {
+                        id: to.id(),
+                        ttl: self.nodes[to],
+                        host: match to {
+                            NodeKey::Known(addr) => Some(Host {
+                                ip: *addr,
+                                hostname: hostname(*addr),
+                            }),
+                            NodeKey::Unknown(_) => None,
+                        },
+                        children: self.children(Some(*to), hostname, visited),
+                    }
+                } else {
+                    TreeNode::Ref { id: to.id() }
+                };
+                TreeEdge {
+                    flows: stats.flows.iter().map(|flow_id| flow_id.0).collect(),
+                    sent: stats.sent,
+                    recv: stats.recv,
+                    loss_pct: if stats.sent > 0 {
+                        (stats.sent - stats.recv) as f64 / stats.sent as f64 * 100f64
+                    } else {
+                        0_f64
+                    },
+                    avg: if stats.recv > 0 {
+                        stats.total_ms / stats.recv as f64
+                    } else {
+                        0_f64
+                    },
+                    node,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_tree_diamond() {
+        let addr = |s: &str| FlowEntry::Known(IpAddr::from_str(s).unwrap());
+        let flow_1 = [addr("10.0.0.1"), addr("10.0.0.2"), addr("10.0.0.4")];
+        let flow_2 = [addr("10.0.0.1"), addr("10.0.0.3"), addr("10.0.0.4")];
+        let flow_3 = [addr("10.0.0.1"), FlowEntry::Unknown, addr("10.0.0.4")];
+        let topology = Topology::new([
+            (flow_1.as_slice(), FlowId(1), [].as_slice()),
+            (flow_2.as_slice(), FlowId(2), [].as_slice()),
+            (flow_3.as_slice(), FlowId(3), [].as_slice()),
+        ]);
+        let tree = serde_json::to_value(topology.tree(&|_| String::from("host"))).unwrap();
+        let expected = json!([edge(
+            &[1, 2, 3],
+            &node(
+                "10.0.0.1",
+                1,
+                &[
+                    edge(
+                        &[1],
+                        &node("10.0.0.2", 2, &[edge(&[1], &node("10.0.0.4", 3, &[]))])
+                    ),
+                    edge(
+                        &[2],
+                        &node("10.0.0.3", 2, &[edge(&[2], &reference("10.0.0.4"))])
+                    ),
+                    edge(&[3], &unknown(2, &[edge(&[3], &reference("10.0.0.4"))])),
+                ]
+            )
+        )]);
+        assert_eq!(expected, tree);
+    }
+
+    fn edge(flows: &[u64], node: &Value) -> Value {
+        json!({
+            "flows": flows,
+            "sent": 0,
+            "recv": 0,
+            "loss_pct": "0.00",
+            "avg": "0.00",
+            "node": node
+        })
+    }
+
+    fn node(id: &str, ttl: u8, children: &[Value]) -> Value {
+        json!({
+            "id": id,
+            "ttl": ttl,
+            "host": { "ip": id, "hostname": "host" },
+            "children": children
+        })
+    }
+
+    fn unknown(ttl: u8, children: &[Value]) -> Value {
+        json!({ "id": format!("*{ttl}"), "ttl": ttl, "host": null, "children": children })
+    }
+
+    fn reference(id: &str) -> Value {
+        json!({ "ref": id })
+    }
+}
@@ -0,0 +1,399 @@
+use crate::app::TraceInfo;
+use crate::report::types::Hosts;
+use chrono::{SecondsFormat, TimeDelta, Utc};
+use itertools::Itertools;
+use rusqlite::{params, Connection, Transaction};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use trippy_core::State;
+use trippy_dns::{DnsResolver, Resolver};
+
+/// How often to check whether any trace has completed a round.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long to wait for the database to be unlocked by other connections.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How often samples older than the retention period are deleted.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The size, in bytes, to which the write-ahead log is truncated after a checkpoint.
+const JOURNAL_SIZE_LIMIT: i64 = 64 * 1024 * 1024;
+
+/// The schema of the database.
+///
+/// A run is a single trace to a target, a hop is a time-to-live of a run and a sample is the
+/// round-trip time of a hop for a single round.  Lost probes are recorded as samples with a `NULL`
+/// round-trip time.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    target TEXT NOT NULL,
+    target_addr TEXT NOT NULL,
+    started_at TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS hops (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES runs (id) ON DELETE CASCADE,
+    ttl INTEGER NOT NULL,
+    addrs TEXT NOT NULL,
+    hostnames TEXT NOT NULL,
+    UNIQUE (run_id, ttl)
+);
+CREATE TABLE IF NOT EXISTS samples (
+    hop_id INTEGER NOT NULL REFERENCES hops (id) ON DELETE CASCADE,
+    round INTEGER NOT NULL,
+    recorded_at TEXT NOT NULL,
+    rtt_ms REAL,
+    PRIMARY KEY (hop_id, round)
+);
+CREATE INDEX IF NOT EXISTS samples_recorded_at ON samples (recorded_at);
+";
+
+/// Record the result of every round of traces to a `SQLite` database.
+#[derive(Debug, Clone)]
+pub struct SqliteExport {
+    path: String,
+    retention: Option<Duration>,
+}
+
+impl SqliteExport {
+    pub const fn new(path: String, retention: Option<Duration>) -> Self {
+        Self { path, retention }
+    }
+
+    /// Open the database and record traces in the background.
+    ///
+    /// The database is opened, and a run is created for every trace, before returning such that
+    /// any failure to do so is reported.  Failures to record a round are logged rather than
+    /// returned, as a failing export must not stop the trace.
+    ///
+    /// The `DnsResolver` cannot be shared between threads and so a new one is started, with the
+    /// same configuration, to resolve the hostnames of hops.
+    pub fn start(
+        self,
+        traces: Vec<TraceInfo>,
+        resolver_config: trippy_dns::Config,
+    ) -> anyhow::Result<SqliteExportHandle> {
+        let mut conn = open(&self.path)?;
+        let started_at = timestamp();
+        let runs = traces
+            .into_iter()
+            .map(|info| {
+                let target_addr = info.data.target_addr().to_string();
+                let run_id = insert_run(&conn, &info.target_hostname, &target_addr, &started_at)?;
+                Ok(Run::new(info, run_id))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let handle = thread::Builder::new()
+            .name(String::from("sqlite"))
+            .spawn(move || match DnsResolver::start(resolver_config) {
+                Ok(resolver) => self.record(&mut conn, runs, &resolver, &thread_stop),
+                Err(err) => tracing::error!(%err, "failed to start sqlite dns resolver"),
+            })?;
+        Ok(SqliteExportHandle { stop, handle })
+    }
+
+    /// Record every completed round until stopped.
+    ///
+    /// A final check is made after being stopped such that the last round of a trace which
+    /// completes, such as in report modes, is recorded.
+    fn record<R: Resolver>(
+        &self,
+        conn: &mut Connection,
+        mut runs: Vec<Run>,
+        resolver: &R,
+        stop: &AtomicBool,
+    ) {
+        let mut last_prune = Instant::now();
+        loop {
+            let stopped = stop.load(Ordering::Relaxed);
+            for run in &mut runs {
+                if let Err(err) = run.record(conn, resolver) {
+                    tracing::error!(path = self.path, %err, "failed to record round to sqlite");
+                }
+            }
+            if let Some(retention) = self.retention {
+                if last_prune.elapsed() >= PRUNE_INTERVAL {
+                    last_prune = Instant::now();
+                    if let Err(err) = prune(conn, retention) {
+                        tracing::error!(path = self.path, %err, "failed to prune sqlite");
+                    }
+                }
+            }
+            if stopped {
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/// A handle to a running `SQLite` export.
+#[derive(Debug)]
+pub struct SqliteExportHandle {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+impl SqliteExportHandle {
+    /// Record any remaining rounds and stop.
+    pub fn finish(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if self.handle.join().is_err() {
+            tracing::error!("sqlite export thread panicked");
+        }
+    }
+}
+
+/// The state of a trace being recorded.
+struct Run {
+    info: TraceInfo,
+    id: i64,
+    /// The number of rounds recorded.
+    round_count: usize,
+    /// The number of probes sent, for each time-to-live, which have been recorded.
+    sent: HashMap<u8, usize>,
+}
+
+impl Run {
+    fn new(info: TraceInfo, id: i64) -> Self {
+        Self {
+            info,
+            id,
+            round_count: 0,
+            sent: HashMap::new(),
+        }
+    }
+
+    /// Record the samples of any rounds which have completed since the previous call.
+    ///
+    /// All samples are recorded in a single transaction.  If several rounds completed then the
+    /// samples of each hop are recorded against the most recent rounds.
+    fn record<R: Resolver>(&mut self, conn: &mut Connection, resolver: &R) -> anyhow::Result<()> {
+        let trace = self.info.data.snapshot();
+        let flow_id = State::default_flow_id();
+        let round_count = trace.round_count(flow_id);
+        if round_count == self.round_count {
+            return Ok(());
+        }
+        let recorded_at = timestamp();
+        let tx = conn.transaction()?;
+        for hop in trace
+            .hops()
+            .iter()
+            .filter(|hop| trace.is_in_round(hop, flow_id))
+        {
+            let recorded = self.sent.get(&hop.ttl()).copied().unwrap_or_default();
+            let count = hop.total_sent().saturating_sub(recorded);
+            if count == 0 {
+                continue;
+            }
+            let hosts = Hosts::from((hop.addrs(), resolver));
+            let addrs = hosts.0.iter().map(|host| host.ip).join(",");
+            let hostnames = hosts.0.iter().map(|host| &host.hostname).join(",");
+            let hop_id = upsert_hop(&tx, self.id, hop.ttl(), &addrs, &hostnames)?;
+            for (offset, rtt) in hop.samples().iter().take(count).enumerate() {
+                let Some(round) = round_count.checked_sub(offset + 1) else {
+                    break;
+                };
+                let rtt_ms = (!rtt.is_zero()).then_some(rtt.as_secs_f64() * 1000_f64);
+                insert_sample(&tx, hop_id, round, &recorded_at, rtt_ms)?;
+            }
+            self.sent.insert(hop.ttl(), hop.total_sent());
+        }
+        tx.commit()?;
+        self.round_count = round_count;
+        Ok(())
+    }
+}
+
+/// Open the database, creating it and the schema if they do not exist.
+///
+/// The database uses write-ahead logging such that it may be read while traces are recorded and
+/// incremental vacuuming such that the space used by pruned samples can be reclaimed.  The vacuum
+/// mode cannot be changed for an existing database without a full vacuum and so only applies to
+/// new databases.
+fn open(path: &str) -> anyhow::Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    conn.pragma_update(None, "auto_vacuum", "INCREMENTAL")?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.pragma_update(None, "journal_size_limit", JOURNAL_SIZE_LIMIT)?;
+    conn.pragma_update(None, "synchronous", "NORMAL")?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+fn insert_run(
+    conn: &Connection,
+    target: &str,
+    target_addr: &str,
+    started_at: &str,
+) -> anyhow::Result<i64> {
+    conn.execute(
+        "INSERT INTO runs (target, target_addr, started_at) VALUES (?1, ?2, ?3)",
+        params![target, target_addr, started_at],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// Insert a hop, or update the addresses and hostnames of an existing hop, and return the id.
+fn upsert_hop(
+    tx: &Transaction<'_>,
+    run_id: i64,
+    ttl: u8,
+    addrs: &str,
+    hostnames: &str,
+) -> anyhow::Result<i64> {
+    Ok(tx
+        .prepare_cached(
+            "INSERT INTO hops (run_id, ttl, addrs, hostnames) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT (run_id, ttl)
+             DO UPDATE SET addrs = excluded.addrs, hostnames = excluded.hostnames
+             RETURNING id",
+        )?
+        .query_row(params![run_id, ttl, addrs, hostnames], |row| row.get(0))?)
+}
+
+fn insert_sample(
+    tx: &Transaction<'_>,
+    hop_id: i64,
+    round: usize,
+    recorded_at: &str,
+    rtt_ms: Option<f64>,
+) -> anyhow::Result<()> {
+    tx.prepare_cached(
+        "INSERT OR REPLACE INTO samples (hop_id, round, recorded_at, rtt_ms)
+         VALUES (?1, ?2, ?3, ?4)",
+    )?
+    .execute(params![hop_id, round, recorded_at, rtt_ms])?;
+    Ok(())
+}
+
+/// Delete samples older than the retention period, and any hops and runs without samples.
+///
+/// The free space is then reclaimed, which only has an effect for databases which were created
+/// with incremental vacuuming.
+fn prune(conn: &mut Connection, retention: Duration) -> anyhow::Result<()> {
+    let Some(cutoff) = TimeDelta::from_std(retention)
+        .ok()
+        .and_then(|retention| Utc::now().checked_sub_signed(retention))
+    else {
+        return Ok(());
+    };
+    prune_before(conn, &cutoff.to_rfc3339_opts(SecondsFormat::Millis, true))
+}
+
+fn prune_before(conn: &mut Connection, cutoff: &str) -> anyhow::Result<()> {
+    let tx = conn.transaction()?;
+    tx.execute(
+        "DELETE FROM samples WHERE recorded_at < ?1",
+        params![cutoff],
+    )?;
+    tx.execute(
+        "DELETE FROM hops WHERE id NOT IN (SELECT DISTINCT hop_id FROM samples)",
+        [],
+    )?;
+    tx.execute(
+        "DELETE FROM runs WHERE id NOT IN (SELECT DISTINCT run_id FROM hops)",
+        [],
+    )?;
+    tx.commit()?;
+    conn.execute_batch("PRAGMA incremental_vacuum")?;
+    Ok(())
+}
+
+/// The current time as an RFC 3339 UTC timestamp, which sorts chronologically as text.
+fn timestamp() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count(conn: &Connection, table: &str) -> usize {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {table}"), [], |row| {
+            row.get(0)
+        })
+        .unwrap()
+    }
+
+    fn record_samples(conn: &mut Connection, run_id: i64, samples: &[(usize, &str, Option<f64>)]) {
+        let tx = conn.transaction().unwrap();
+        let hop_id = upsert_hop(&tx, run_id, 1, "10.0.0.1", "example.com").unwrap();
+        for &(round, recorded_at, rtt_ms) in samples {
+            insert_sample(&tx, hop_id, round, recorded_at, rtt_ms).unwrap();
+        }
+        tx.commit().unwrap();
+    }
+
+    #[test]
+    fn test_schema_is_created_once() {
+        let conn = open(":memory:").unwrap();
+        conn.execute_batch(SCHEMA).unwrap();
+        assert_eq!(0, count(&conn, "runs"));
+    }
+
+    #[test]
+    fn test_record_samples() {
+        let mut conn = open(":memory:").unwrap();
+        let run_id =
+            insert_run(&conn, "example.com", "10.0.0.1", "2024-01-01T00:00:00.000Z").unwrap();
+        record_samples(
+            &mut conn,
+            run_id,
+            &[
+                (0, "2024-01-01T00:00:01.000Z", Some(12.5)),
+                (1, "2024-01-01T00:00:02.000Z", None),
+            ],
+        );
+        record_samples(
+            &mut conn,
+            run_id,
+            &[(2, "2024-01-01T00:00:03.000Z", Some(13.5))],
+        );
+        assert_eq!(1, count(&conn, "hops"));
+        assert_eq!(3, count(&conn, "samples"));
+        let lost: usize = conn
+            .query_row(
+                "SELECT COUNT(*) FROM samples WHERE rtt_ms IS NULL",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(1, lost);
+    }
+
+    #[test]
+    fn test_prune() {
+        let mut conn = open(":memory:").unwrap();
+        let old_run_id =
+            insert_run(&conn, "old.com", "10.0.0.1", "2024-01-01T00:00:00.000Z").unwrap();
+        record_samples(
+            &mut conn,
+            old_run_id,
+            &[(0, "2024-01-01T00:00:01.000Z", Some(1.0))],
+        );
+        let run_id = insert_run(&conn, "new.com", "10.0.0.2", "2024-01-02T00:00:00.000Z").unwrap();
+        record_samples(
+            &mut conn,
+            run_id,
+            &[
+                (0, "2024-01-02T00:00:01.000Z", Some(1.0)),
+                (1, "2024-01-03T00:00:01.000Z", Some(1.0)),
+            ],
+        );
+        prune_before(&mut conn, "2024-01-03T00:00:00.000Z").unwrap();
+        assert_eq!(1, count(&conn, "runs"));
+        assert_eq!(1, count(&conn, "hops"));
+        assert_eq!(1, count(&conn, "samples"));
+    }
+}
@@ -0,0 +1,270 @@
+use crate::app::TraceInfo;
+use crate::config::{WebhookField, WebhookTrigger};
+use crate::report::types::{Hop, Host};
+use serde::Serialize;
+use std::net::IpAddr;
+use std::thread;
+use std::time::Duration;
+use trippy_core::State;
+use trippy_dns::{DnsResolver, Resolver};
+
+/// How often to check whether the reachability of the target has changed.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// The timeout for a single post to the webhook.
+#[cfg(feature = "webhook")]
+const POST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The delay before the first retry of a failed post, which is doubled for every retry.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The maximum delay between retries of a failed post.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Post JSON summaries of traces to a webhook.
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    url: String,
+    trigger: WebhookTrigger,
+    fields: Vec<WebhookField>,
+    retries: u8,
+}
+
+impl Webhook {
+    pub const fn new(
+        url: String,
+        trigger: WebhookTrigger,
+        fields: Vec<WebhookField>,
+        retries: u8,
+    ) -> Self {
+        Self {
+            url,
+            trigger,
+            fields,
+            retries,
+        }
+    }
+
+    pub const fn trigger(&self) -> WebhookTrigger {
+        self.trigger
+    }
+
+    /// Watch a trace in the background and post whenever the reachability of the target changes.
+    ///
+    /// The `DnsResolver` cannot be shared between threads and so a new one is started, with the
+    /// same configuration, to resolve the hostnames of hops.
+    pub fn watch(self, info: TraceInfo, resolver_config: trippy_dns::Config) -> anyhow::Result<()> {
+        thread::Builder::new().name(String::from("webhook")).spawn(
+            move || match DnsResolver::start(resolver_config) {
+                Ok(resolver) => self.watch_reachability(&info, &resolver),
+                Err(err) => tracing::error!(%err, "failed to start webhook dns resolver"),
+            },
+        )?;
+        Ok(())
+    }
+
+    /// Post a summary of a completed trace.
+    pub fn post_completion<R: Resolver>(&self, info: &TraceInfo, resolver: &R) {
+        let trace = info.data.snapshot();
+        self.post(&self.payload(WebhookEvent::Completion, info, &trace, resolver));
+    }
+
+    /// Check the reachability of the target after every round until the trace fails.
+    ///
+    /// Nothing is posted for the first round, as the reachability of the target has not changed.
+    fn watch_reachability<R: Resolver>(&self, info: &TraceInfo, resolver: &R) {
+        let flow_id = State::default_flow_id();
+        let mut last_round = None;
+        let mut last_reachable = None;
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let trace = info.data.snapshot();
+            if trace.error().is_some() {
+                return;
+            }
+            let round = trace.round(flow_id);
+            if round.is_none() || round == last_round {
+                continue;
+            }
+            last_round = round;
+            let reachable = is_reachable(&trace, info.data.target_addr());
+            if last_reachable.is_some_and(|last| last != reachable) {
+                let event = WebhookEvent::ReachabilityChange;
+                self.post(&self.payload(event, info, &trace, resolver));
+            }
+            last_reachable = Some(reachable);
+        }
+    }
+
+    fn payload<R: Resolver>(
+        &self,
+        event: WebhookEvent,
+        info: &TraceInfo,
+        trace: &State,
+        resolver: &R,
+    ) -> Payload {
+        let target_addr = info.data.target_addr();
+        let target = Host {
+            ip: target_addr,
+            hostname: info.target_hostname.clone(),
+        };
+        Payload::new(
+            event,
+            &self.fields,
+            target,
+            trace.round_count(State::default_flow_id()),
+            is_reachable(trace, target_addr),
+            || {
+                trace
+                    .hops()
+                    .iter()
+                    .map(|hop| Hop::from((hop, resolver)))
+                    .collect()
+            },
+        )
+    }
+
+    /// Post a payload, retrying with an exponential backoff, capped at `MAX_BACKOFF`, if the post
+    /// fails.
+    ///
+    /// Failures are logged rather than returned, as a failing webhook must not stop the trace.
+    fn post(&self, payload: &Payload) {
+        let body = match serde_json::to_string(payload) {
+            Ok(body) => body,
+            Err(err) => {
+                tracing::error!(url = self.url, %err, "failed to serialize webhook payload");
+                return;
+            }
+        };
+        let mut backoff = INITIAL_BACKOFF;
+        for attempt in 0..=self.retries {
+            match send(&self.url, &body) {
+                Ok(()) => {
+                    tracing::debug!(url = self.url, attempt, "posted to webhook");
+                    return;
+                }
+                Err(err) => {
+                    tracing::warn!(url = self.url, attempt, %err, "failed to post to webhook");
+                }
+            }
+            if attempt < self.retries {
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+        tracing::error!(
+            url = self.url,
+            retries = self.retries,
+            "giving up posting to webhook"
+        );
+    }
+}
+
+/// Send a serialized payload to the webhook.
+#[cfg(feature = "webhook")]
+fn send(url: &str, body: &str) -> anyhow::Result<()> {
+    ureq::post(url)
+        .timeout(POST_TIMEOUT)
+        .set("Content-Type", "application/json")
+        .send_string(body)?;
+    Ok(())
+}
+
+/// Send a serialized payload to the webhook.
+///
+/// Posting to a webhook requires the `webhook` feature, which is validated when the
+/// configuration is built, and so this always fails.
+#[cfg(not(feature = "webhook"))]
+fn send(_url: &str, _body: &str) -> anyhow::Result<()> {
+    Err(anyhow::anyhow!(
+        "posting to a webhook requires Trippy to be built with the `webhook` feature"
+    ))
+}
+
+/// Did the target reply in the latest round?
+fn is_reachable(trace: &State, target_addr: IpAddr) -> bool {
+    let hop = trace.target_hop(State::default_flow_id());
+    hop.addrs().any(|addr| *addr == target_addr)
+        && hop.samples().first().is_some_and(|rtt| !rtt.is_zero())
+}
+
+/// What triggered a post to the webhook.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum WebhookEvent {
+    Completion,
+    ReachabilityChange,
+}
+
+/// The JSON payload posted to the webhook.
+///
+/// Only the configured fields are included, with the exception of `event` which is always included.
+#[derive(Serialize)]
+struct Payload {
+    event: WebhookEvent,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    target: Option<Host>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    round: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reachable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hops: Option<Vec<Hop>>,
+}
+
+impl Payload {
+    fn new(
+        event: WebhookEvent,
+        fields: &[WebhookField],
+        target: Host,
+        round: usize,
+        reachable: bool,
+        hops: impl FnOnce() -> Vec<Hop>,
+    ) -> Self {
+        let has = |field| fields.contains(&field);
+        Self {
+            event,
+            target: has(WebhookField::Target).then_some(target),
+            round: has(WebhookField::Round).then_some(round),
+            reachable: has(WebhookField::Reachable).then_some(reachable),
+            hops: has(WebhookField::Hops).then(hops),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(&[], r#"{"event":"completion"}"#; "no fields")]
+    #[test_case(&[WebhookField::Round, WebhookField::Reachable], r#"{"event":"completion","round":5,"reachable":true}"#; "some fields")]
+    #[test_case(&[WebhookField::Target, WebhookField::Round, WebhookField::Reachable, WebhookField::Hops], r#"{"event":"completion","target":{"ip":"10.0.0.1","hostname":"example.com"},"round":5,"reachable":true,"hops":[]}"#; "all fields")]
+    fn test_payload(fields: &[WebhookField], expected: &str) {
+        let target = Host {
+            ip: IpAddr::from([10, 0, 0, 1]),
+            hostname: String::from("example.com"),
+        };
+        let payload = Payload::new(WebhookEvent::Completion, fields, target, 5, true, Vec::new);
+        assert_eq!(expected, serde_json::to_string(&payload).unwrap());
+    }
+
+    #[test]
+    fn test_reachability_change_event() {
+        let payload = Payload::new(
+            WebhookEvent::ReachabilityChange,
+            &[],
+            Host {
+                ip: IpAddr::from([10, 0, 0, 1]),
+                hostname: String::from("example.com"),
+            },
+            0,
+            false,
+            Vec::new,
+        );
+        assert_eq!(
+            r#"{"event":"reachability-change"}"#,
+            serde_json::to_string(&payload).unwrap()
+        );
+    }
+}